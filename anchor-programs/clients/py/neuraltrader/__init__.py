@@ -0,0 +1,7 @@
+"""Python client for the agent market program."""
+from .codec import Codec
+from .env import TradingEnv
+from .pda import *  # noqa: F401,F403
+from .program import Program, ProgramError, load_idl
+
+__all__ = ["Codec", "Program", "ProgramError", "load_idl", "TradingEnv"]
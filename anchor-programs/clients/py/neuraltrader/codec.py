@@ -0,0 +1,142 @@
+"""Borsh encoding driven by the program IDL.
+
+Structs decode to dicts keyed by the IDL's snake_case field names, pubkeys to
+`solders.pubkey.Pubkey`, `bytes` values to `bytes` and fixed `u8` arrays to
+`bytes` as well. Enums decode to `{"variant": name, "fields": ...}`.
+"""
+import struct
+
+from solders.pubkey import Pubkey
+
+_INTS = {
+    "u8": (1, False),
+    "i8": (1, True),
+    "u16": (2, False),
+    "i16": (2, True),
+    "u32": (4, False),
+    "i32": (4, True),
+    "u64": (8, False),
+    "i64": (8, True),
+    "u128": (16, False),
+    "i128": (16, True),
+}
+_FLOATS = {"f32": "<f", "f64": "<d"}
+
+
+class Codec:
+    def __init__(self, idl):
+        self.types = {ty["name"]: ty["type"] for ty in idl.get("types", [])}
+
+    def decode(self, ty, data, offset=0):
+        """Decodes one `ty` value at `offset`, returning it and the new offset."""
+        if isinstance(ty, str):
+            if ty in _INTS:
+                size, signed = _INTS[ty]
+                value = int.from_bytes(data[offset : offset + size], "little", signed=signed)
+                return value, offset + size
+            if ty in _FLOATS:
+                size = struct.calcsize(_FLOATS[ty])
+                return struct.unpack_from(_FLOATS[ty], data, offset)[0], offset + size
+            if ty == "bool":
+                return data[offset] != 0, offset + 1
+            if ty == "pubkey":
+                return Pubkey.from_bytes(bytes(data[offset : offset + 32])), offset + 32
+            if ty in ("string", "bytes"):
+                length = int.from_bytes(data[offset : offset + 4], "little")
+                raw = bytes(data[offset + 4 : offset + 4 + length])
+                return (raw.decode() if ty == "string" else raw), offset + 4 + length
+            raise ValueError(f"unsupported type {ty}")
+        if "vec" in ty:
+            length = int.from_bytes(data[offset : offset + 4], "little")
+            return self._decode_seq(ty["vec"], length, data, offset + 4)
+        if "array" in ty:
+            inner, length = ty["array"]
+            if inner == "u8":
+                return bytes(data[offset : offset + length]), offset + length
+            return self._decode_seq(inner, length, data, offset)
+        if "option" in ty:
+            if data[offset] == 0:
+                return None, offset + 1
+            return self.decode(ty["option"], data, offset + 1)
+        if "defined" in ty:
+            return self._decode_defined(self.types[ty["defined"]["name"]], data, offset)
+        raise ValueError(f"unsupported type {ty}")
+
+    def encode(self, ty, value):
+        """Encodes `value` as `ty`."""
+        if isinstance(ty, str):
+            if ty in _INTS:
+                size, signed = _INTS[ty]
+                return int(value).to_bytes(size, "little", signed=signed)
+            if ty in _FLOATS:
+                return struct.pack(_FLOATS[ty], value)
+            if ty == "bool":
+                return bytes([1 if value else 0])
+            if ty == "pubkey":
+                return bytes(value)
+            if ty in ("string", "bytes"):
+                raw = value.encode() if ty == "string" else bytes(value)
+                return len(raw).to_bytes(4, "little") + raw
+            raise ValueError(f"unsupported type {ty}")
+        if "vec" in ty:
+            items = list(value)
+            return len(items).to_bytes(4, "little") + b"".join(
+                self.encode(ty["vec"], item) for item in items
+            )
+        if "array" in ty:
+            inner, length = ty["array"]
+            items = list(value)
+            if len(items) != length:
+                raise ValueError(f"expected {length} items, got {len(items)}")
+            if inner == "u8":
+                return bytes(items)
+            return b"".join(self.encode(inner, item) for item in items)
+        if "option" in ty:
+            if value is None:
+                return b"\x00"
+            return b"\x01" + self.encode(ty["option"], value)
+        if "defined" in ty:
+            return self._encode_defined(self.types[ty["defined"]["name"]], value)
+        raise ValueError(f"unsupported type {ty}")
+
+    def decode_fields(self, fields, data, offset=0):
+        if fields and isinstance(fields[0], dict):
+            value = {}
+            for field in fields:
+                value[field["name"]], offset = self.decode(field["type"], data, offset)
+            return value, offset
+        return self._decode_seq_types(fields, data, offset)
+
+    def encode_fields(self, fields, value):
+        if fields and isinstance(fields[0], dict):
+            return b"".join(self.encode(field["type"], value[field["name"]]) for field in fields)
+        return b"".join(self.encode(ty, item) for ty, item in zip(fields, value))
+
+    def _decode_seq(self, ty, length, data, offset):
+        items = []
+        for _ in range(length):
+            item, offset = self.decode(ty, data, offset)
+            items.append(item)
+        return items, offset
+
+    def _decode_seq_types(self, types, data, offset):
+        items = []
+        for ty in types:
+            item, offset = self.decode(ty, data, offset)
+            items.append(item)
+        return items, offset
+
+    def _decode_defined(self, ty, data, offset):
+        if ty["kind"] == "struct":
+            return self.decode_fields(ty.get("fields", []), data, offset)
+        variant = ty["variants"][data[offset]]
+        fields, offset = self.decode_fields(variant.get("fields", []), data, offset + 1)
+        return {"variant": variant["name"], "fields": fields}, offset
+
+    def _encode_defined(self, ty, value):
+        if ty["kind"] == "struct":
+            return self.encode_fields(ty.get("fields", []), value)
+        names = [variant["name"] for variant in ty["variants"]]
+        index = names.index(value["variant"])
+        fields = ty["variants"][index].get("fields", [])
+        return bytes([index]) + self.encode_fields(fields, value.get("fields", []))
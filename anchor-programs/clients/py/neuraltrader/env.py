@@ -0,0 +1,133 @@
+"""Gymnasium-style wrapper over the harness crate's `TradingEnv`.
+
+The environment runs in the `trading_env` binary, spoken to over JSON lines,
+so episodes execute the real program without a validator:
+
+    env = TradingEnv(config={"background_agents": 4, "max_steps": 500})
+    obs, info = env.reset(seed=7)
+    obs, reward, terminated, truncated, info = env.step(
+        {"type": "bid", "price": 990_000, "quantity": 1_000_000}
+    )
+
+Actions are `{"type": "hold"}`, `{"type": "cancel"}` or a `bid`/`ask` with a
+`price` (token A per token B, 1e6 fixed point) and a `quantity` of token B.
+Network conditions are part of the config, e.g.
+`"chaos": {"latency": {"kind": "exponential", "mean": 2.0}, "drop_percent": 5}`;
+`info["dropped"]` then flags the learner's transactions that never landed.
+Recorded prices or trades replace the random walk with
+`"replay": {"path": "sol_usd.csv", "feed": "pyth_pull"}`, one record per step.
+Observations are the dicts the harness produces; `flatten` turns one into a
+fixed-length list for frameworks that want arrays. If gymnasium is installed
+the class is a `gymnasium.Env`.
+"""
+import json
+import os
+import pathlib
+import subprocess
+
+try:
+    import gymnasium
+
+    _Base = gymnasium.Env
+except ImportError:
+    gymnasium = None
+    _Base = object
+
+_ROOT = pathlib.Path(__file__).resolve().parents[3]
+
+
+def _default_command():
+    binary = os.environ.get("NEURALTRADER_TRADING_ENV")
+    if binary:
+        return [binary]
+    return [
+        "cargo",
+        "run",
+        "-q",
+        "--release",
+        "--manifest-path",
+        str(_ROOT / "Cargo.toml"),
+        "-p",
+        "neuraltrader-harness",
+        "--bin",
+        "trading_env",
+    ]
+
+
+class TradingEnv(_Base):
+    metadata = {"render_modes": []}
+
+    def __init__(self, config=None, command=None):
+        """`config` holds `EnvConfig` fields; `command` starts the binary, by
+        default `$NEURALTRADER_TRADING_ENV` or `cargo run` in this repo."""
+        self.config = dict(config or {})
+        self._process = subprocess.Popen(
+            command or _default_command(),
+            stdin=subprocess.PIPE,
+            stdout=subprocess.PIPE,
+            # Program logs
+            stderr=subprocess.DEVNULL,
+            text=True,
+        )
+        self.observation = None
+
+    def reset(self, seed=None, options=None):
+        config = {**self.config, **((options or {}).get("config") or {})}
+        reply = self._request({"op": "reset", "seed": seed, "config": config})
+        self.observation = reply["observation"]
+        return self.observation, {}
+
+    def step(self, action):
+        reply = self._request({"op": "step", "action": action})
+        self.observation = reply["observation"]
+        info = {key: reply[key] for key in ("fills", "rejected", "dropped")}
+        # The harness only ever ends an episode at its step limit
+        return self.observation, reply["reward"], False, reply["done"], info
+
+    def save(self, path):
+        """Checkpoints the episode, simulation included, to `path`."""
+        self._request({"op": "save", "path": str(path)})
+
+    def load(self, path):
+        """Resumes the episode saved at `path`, returning its observation."""
+        self.observation = self._request({"op": "load", "path": str(path)})["observation"]
+        return self.observation
+
+    def close(self):
+        if self._process.poll() is None:
+            self._process.stdin.close()
+            self._process.wait()
+
+    def _request(self, request):
+        self._process.stdin.write(json.dumps(request) + "\n")
+        self._process.stdin.flush()
+        line = self._process.stdout.readline()
+        if not line:
+            raise RuntimeError("trading_env exited")
+        reply = json.loads(line)
+        if "error" in reply:
+            raise ValueError(reply["error"])
+        return reply
+
+
+def flatten(observation, depth=5, history=16):
+    """`observation` as a flat list of numbers: last price, `history` past
+    prices, `depth` bid then ask levels as price/quantity pairs, balances and
+    the open order as side (1 bid, -1 ask, 0 none), price and remaining size.
+    Missing entries are zero."""
+
+    def pad(values, length):
+        return list(values[-length:]) + [0] * (length - len(values[-length:]))
+
+    features = [observation["last_price"]]
+    features += pad(observation["price_history"], history)
+    for side in ("bids", "asks"):
+        levels = observation[side][:depth]
+        features += pad([x for level in levels for x in (level["price"], level["quantity"])], 2 * depth)
+    features += [observation["balance_a"], observation["balance_b"]]
+    order = observation["order"]
+    if order is None:
+        features += [0, 0, 0]
+    else:
+        features += [1 if order["side"] == "bid" else -1, order["price"], order["remaining"]]
+    return features
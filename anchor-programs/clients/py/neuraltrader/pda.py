@@ -0,0 +1,183 @@
+"""Addresses of the program's seeded accounts, mirroring the TS client."""
+from solders.pubkey import Pubkey
+
+from .program import load_idl
+
+PROGRAM_ID = Pubkey.from_string(load_idl()["address"])
+
+
+def _u64(value):
+    return int(value).to_bytes(8, "little")
+
+
+def _find(seeds, program_id):
+    return Pubkey.find_program_address([bytes(seed) for seed in seeds], program_id)[0]
+
+
+def config_pda(program_id=PROGRAM_ID):
+    return _find([b"config"], program_id)
+
+
+def global_stats_pda(program_id=PROGRAM_ID):
+    return _find([b"global_stats"], program_id)
+
+
+def market_pda(token_a_mint, token_b_mint, program_id=PROGRAM_ID):
+    return _find([b"market", token_a_mint, token_b_mint], program_id)
+
+
+def market_listing_pda(index, program_id=PROGRAM_ID):
+    """Entry `index` of the market list, `0..config.market_count`."""
+    return _find([b"market_listing", _u64(index)], program_id)
+
+
+def vault_pda(token_a_mint, token_b_mint, mint, program_id=PROGRAM_ID):
+    """Vault holding `mint` for the market over `token_a_mint`/`token_b_mint`."""
+    return _find([b"vault", token_a_mint, token_b_mint, mint], program_id)
+
+
+def agent_pda(owner, program_id=PROGRAM_ID):
+    return _find([b"agent", owner], program_id)
+
+
+def agent_listing_pda(index, program_id=PROGRAM_ID):
+    """Entry `index` of the agent list, `0..config.agent_count`."""
+    return _find([b"agent_listing", _u64(index)], program_id)
+
+
+def agent_nft_pda(agent, program_id=PROGRAM_ID):
+    return _find([b"agent_nft", agent], program_id)
+
+
+def subaccount_pda(agent, index, program_id=PROGRAM_ID):
+    return _find([b"subaccount", agent, _u64(index)], program_id)
+
+
+def balance_pda(vault, agent, program_id=PROGRAM_ID):
+    return _find([b"balance", vault, agent], program_id)
+
+
+def deposit_receipt_pda(agent, nonce, program_id=PROGRAM_ID):
+    """Marks an agent's deposit `nonce` as credited."""
+    return _find([b"deposit_receipt", agent, _u64(nonce)], program_id)
+
+
+def trade_pda(agent, market, order_id=0, program_id=PROGRAM_ID):
+    """The agent's order `order_id` on `market`."""
+    return _find([b"trade", agent, market, _u64(order_id)], program_id)
+
+
+def settlement_pda(market, agent, program_id=PROGRAM_ID):
+    return _find([b"settlement", market, agent], program_id)
+
+
+def position_pda(market, agent, program_id=PROGRAM_ID):
+    return _find([b"position", market, agent], program_id)
+
+
+def adl_queue_pda(market, program_id=PROGRAM_ID):
+    return _find([b"adl", market], program_id)
+
+
+def price_cache_pda(market, program_id=PROGRAM_ID):
+    return _find([b"price_cache", market], program_id)
+
+
+def relayer_debt_pda(market, agent, program_id=PROGRAM_ID):
+    return _find([b"relayer_debt", market, agent], program_id)
+
+
+def snapshot_pda(market, epoch, program_id=PROGRAM_ID):
+    return _find([b"snapshot", market, _u64(epoch)], program_id)
+
+
+def leaderboard_pda(market, epoch, program_id=PROGRAM_ID):
+    return _find([b"leaderboard", market, _u64(epoch)], program_id)
+
+
+def emissions_schedule_pda(market, reward_mint, program_id=PROGRAM_ID):
+    return _find([b"emissions", market, reward_mint], program_id)
+
+
+def emissions_account_pda(schedule, agent, program_id=PROGRAM_ID):
+    return _find([b"emissions_account", schedule, agent], program_id)
+
+
+def bond_pda(schedule, agent, program_id=PROGRAM_ID):
+    return _find([b"bond", schedule, agent], program_id)
+
+
+def score_root_pda(market, epoch, program_id=PROGRAM_ID):
+    return _find([b"score_root", market, _u64(epoch)], program_id)
+
+
+def score_claim_pda(score_root, agent, program_id=PROGRAM_ID):
+    return _find([b"score_claim", score_root, agent], program_id)
+
+
+def rfq_pda(market, agent, rfq_id, program_id=PROGRAM_ID):
+    return _find([b"rfq", market, agent, _u64(rfq_id)], program_id)
+
+
+def otc_offer_pda(market, agent, offer_id, program_id=PROGRAM_ID):
+    return _find([b"otc", market, agent, _u64(offer_id)], program_id)
+
+
+def option_pda(market, agent, option_id, program_id=PROGRAM_ID):
+    return _find([b"option", market, agent, _u64(option_id)], program_id)
+
+
+def binary_market_pda(collateral_mint, market_id, program_id=PROGRAM_ID):
+    return _find([b"binary_market", collateral_mint, _u64(market_id)], program_id)
+
+
+def yes_mint_pda(binary_market, program_id=PROGRAM_ID):
+    return _find([b"yes", binary_market], program_id)
+
+
+def no_mint_pda(binary_market, program_id=PROGRAM_ID):
+    return _find([b"no", binary_market], program_id)
+
+
+def index_pda(index_id, program_id=PROGRAM_ID):
+    return _find([b"index", _u64(index_id)], program_id)
+
+
+def index_mint_pda(index, program_id=PROGRAM_ID):
+    return _find([b"index_mint", index], program_id)
+
+
+def guild_pda(market, authority, program_id=PROGRAM_ID):
+    return _find([b"guild", market, authority], program_id)
+
+
+def guild_agent_pda(guild, program_id=PROGRAM_ID):
+    return _find([b"guild_agent", guild], program_id)
+
+
+def guild_member_pda(guild, member, program_id=PROGRAM_ID):
+    return _find([b"guild_member", guild, member], program_id)
+
+
+def copy_feed_pda(market, leader, program_id=PROGRAM_ID):
+    return _find([b"copy_feed", market, leader], program_id)
+
+
+def subscription_pda(market, follower, leader, program_id=PROGRAM_ID):
+    return _find([b"subscription", market, follower, leader], program_id)
+
+
+def strategy_vault_pda(market, manager_agent, program_id=PROGRAM_ID):
+    return _find([b"strategy_vault", market, manager_agent], program_id)
+
+
+def strategy_agent_pda(strategy_vault, program_id=PROGRAM_ID):
+    return _find([b"strategy_agent", strategy_vault], program_id)
+
+
+def strategy_shares_pda(strategy_vault, program_id=PROGRAM_ID):
+    return _find([b"strategy_shares", strategy_vault], program_id)
+
+
+def redemption_pda(strategy_vault, owner, program_id=PROGRAM_ID):
+    return _find([b"redemption", strategy_vault, owner], program_id)
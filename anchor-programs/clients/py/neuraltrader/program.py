@@ -0,0 +1,262 @@
+"""Account decoding, instruction building and RPC helpers for the program.
+
+Everything is driven by the bundled IDL, so names are the program's own
+snake_case ones: `program.instruction("place_trade", {...}, {...})` takes the
+`place_trade` arguments and accounts exactly as the Rust handler names them.
+"""
+import base64
+import json
+import pathlib
+
+from solders.instruction import AccountMeta, Instruction
+from solders.pubkey import Pubkey
+from solders.transaction import Transaction
+
+from .codec import Codec
+
+_IDL_PATH = pathlib.Path(__file__).with_name("agent_market_sim.json")
+_B58 = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def load_idl():
+    return json.loads(_IDL_PATH.read_text())
+
+
+def _b58encode(raw):
+    number = int.from_bytes(raw, "big")
+    encoded = ""
+    while number:
+        number, digit = divmod(number, 58)
+        encoded = _B58[digit] + encoded
+    return "1" * (len(raw) - len(raw.lstrip(b"\x00"))) + encoded
+
+
+class ProgramError(Exception):
+    def __init__(self, code, name, msg):
+        super().__init__(f"{name} ({code}): {msg}")
+        self.code = code
+        self.name = name
+        self.msg = msg
+
+
+class Program:
+    def __init__(self, program_id=None, idl=None):
+        self.idl = idl or load_idl()
+        self.program_id = program_id or Pubkey.from_string(self.idl["address"])
+        self.codec = Codec(self.idl)
+        self.instructions = {ix["name"]: ix for ix in self.idl["instructions"]}
+        self.accounts = {bytes(acc["discriminator"]): acc["name"] for acc in self.idl["accounts"]}
+        self.events = {bytes(ev["discriminator"]): ev["name"] for ev in self.idl.get("events", [])}
+        self.errors = {err["code"]: err for err in self.idl.get("errors", [])}
+
+    # Accounts and events
+
+    def decode_account(self, data, name=None):
+        """Decodes raw account data, returning `(account_name, fields)`."""
+        data = bytes(data)
+        found = self.accounts.get(data[:8])
+        if found is None or (name is not None and found != name):
+            raise ValueError(f"not a {name or 'program'} account")
+        fields, _ = self.codec.decode({"defined": {"name": found}}, data, 8)
+        return found, fields
+
+    def decode_event(self, data):
+        """Decodes one `Program data:` payload (base64-decoded), or None."""
+        data = bytes(data)
+        name = self.events.get(data[:8])
+        if name is None:
+            return None
+        fields, _ = self.codec.decode({"defined": {"name": name}}, data, 8)
+        return name, fields
+
+    def parse_events(self, logs):
+        """Every event the program emitted in a transaction's log messages."""
+        events = []
+        depth = []
+        for line in logs:
+            if line.startswith("Program ") and " invoke [" in line:
+                depth.append(line.split()[1])
+            elif line.startswith("Program ") and (line.endswith(" success") or " failed" in line):
+                if depth:
+                    depth.pop()
+            elif line.startswith("Program data: ") and depth and depth[-1] == str(self.program_id):
+                event = self.decode_event(base64.b64decode(line[len("Program data: ") :]))
+                if event is not None:
+                    events.append(event)
+        return events
+
+    def error(self, code):
+        """The program error for a custom error code, if it is one of ours."""
+        err = self.errors.get(code)
+        return err and ProgramError(code, err["name"], err.get("msg", ""))
+
+    async def fetch(self, client, address, name=None):
+        """Fetches and decodes one account; returns None if it doesn't exist."""
+        info = (await client.get_account_info(address)).value
+        if info is None:
+            return None
+        return self.decode_account(info.data, name)[1]
+
+    async def fetch_all(self, client, name):
+        """Every `name` account the program owns, as `(address, fields)` pairs."""
+        from solana.rpc.types import MemcmpOpts
+
+        discriminator = next(d for d, n in self.accounts.items() if n == name)
+        resp = await client.get_program_accounts(
+            self.program_id,
+            encoding="base64",
+            filters=[MemcmpOpts(offset=0, bytes=_b58encode(discriminator))],
+        )
+        return [
+            (keyed.pubkey, self.decode_account(keyed.account.data, name)[1])
+            for keyed in resp.value
+        ]
+
+    async def fetch_markets(self, client, start=0, limit=None):
+        """Markets from listing `start` on, as `(address, fields)` pairs.
+
+        Reads the listings instead of scanning program accounts, so pages of
+        `limit` cost two `getMultipleAccounts` calls each.
+        """
+        from .pda import market_listing_pda
+
+        return await self._fetch_listed(
+            client, "market", market_listing_pda, start, limit
+        )
+
+    async def fetch_agents(self, client, start=0, limit=None):
+        """Registered agents from listing `start` on, like `fetch_markets`.
+
+        Agents closed since are skipped.
+        """
+        from .pda import agent_listing_pda
+
+        return await self._fetch_listed(
+            client, "agent", agent_listing_pda, start, limit
+        )
+
+    async def _fetch_listed(self, client, kind, listing_pda, start, limit):
+        from .pda import config_pda
+
+        config = await self.fetch(client, config_pda(self.program_id), "Config")
+        end = config[f"{kind}_count"] if config else 0
+        if limit is not None:
+            end = min(end, start + limit)
+        listings = [listing_pda(i, self.program_id) for i in range(start, end)]
+        if not listings:
+            return []
+        name = kind.capitalize()
+        infos = (await client.get_multiple_accounts(listings)).value
+        addresses = [
+            self.decode_account(info.data, f"{name}Listing")[1][kind] for info in infos
+        ]
+        infos = (await client.get_multiple_accounts(addresses)).value
+        return [
+            (address, self.decode_account(info.data, name)[1])
+            for address, info in zip(addresses, infos)
+            if info is not None
+        ]
+
+    async def fetch_market(self, client, address):
+        return await self.fetch(client, address, "Market")
+
+    async def fetch_agent(self, client, address):
+        return await self.fetch(client, address, "Agent")
+
+    async def fetch_trade(self, client, address):
+        return await self.fetch(client, address, "Trade")
+
+    async def fetch_price_cache(self, client, address):
+        return await self.fetch(client, address, "PriceCache")
+
+    async def fetch_snapshot(self, client, address):
+        return await self.fetch(client, address, "Snapshot")
+
+    # Instructions
+
+    def instruction(self, name, args=None, accounts=None, remaining_accounts=()):
+        """Builds `name` from its arguments and accounts.
+
+        Accounts with a fixed address, PDAs whose seeds are constants, other
+        accounts or arguments, and omitted optional accounts are filled in;
+        everything else must be passed. Pass None to leave out an optional
+        account that would otherwise resolve. `remaining_accounts` are appended as
+        given `AccountMeta`s.
+        """
+        ix = self.instructions[name]
+        args = args or {}
+        data = bytes(ix["discriminator"]) + b"".join(
+            self.codec.encode(arg["type"], args[arg["name"]]) for arg in ix["args"]
+        )
+        resolved = self._resolve(ix, args, dict(accounts or {}))
+        metas = []
+        for acc in ix["accounts"]:
+            address = resolved[acc["name"]]
+            # Omitted optional accounts are passed as the program, read-only
+            present = address != self.program_id
+            metas.append(
+                AccountMeta(
+                    address,
+                    is_signer=present and acc.get("signer", False),
+                    is_writable=present and acc.get("writable", False),
+                )
+            )
+        return Instruction(self.program_id, data, metas + list(remaining_accounts))
+
+    def _resolve(self, ix, args, accounts):
+        arg_types = {arg["name"]: arg["type"] for arg in ix["args"]}
+        # An explicit None omits an optional account rather than resolving it
+        for acc_name, address in accounts.items():
+            if address is None:
+                accounts[acc_name] = self.program_id
+        pending = [acc for acc in ix["accounts"] if acc["name"] not in accounts]
+        while pending:
+            progressed = False
+            for acc in list(pending):
+                address = self._address(acc, args, arg_types, accounts)
+                if address is not None:
+                    accounts[acc["name"]] = address
+                    pending.remove(acc)
+                    progressed = True
+            if not progressed:
+                break
+        for acc in pending:
+            if not acc.get("optional"):
+                raise ValueError(f"{ix['name']}: missing account {acc['name']}")
+            accounts[acc["name"]] = self.program_id
+        return accounts
+
+    def _address(self, acc, args, arg_types, accounts):
+        if "address" in acc:
+            return Pubkey.from_string(acc["address"])
+        pda = acc.get("pda")
+        if pda is None:
+            return None
+        seeds = [self._seed(seed, args, arg_types, accounts) for seed in pda["seeds"]]
+        program = bytes(self.program_id)
+        if "program" in pda:
+            program = self._seed(pda["program"], args, arg_types, accounts)
+        if None in seeds or program is None:
+            return None
+        return Pubkey.find_program_address(seeds, Pubkey.from_bytes(program))[0]
+
+    def _seed(self, seed, args, arg_types, accounts):
+        if seed["kind"] == "const":
+            return bytes(seed["value"])
+        path = seed["path"]
+        if seed["kind"] == "arg":
+            return self.codec.encode(arg_types[path], args[path]) if path in args else None
+        # Seeds read from another account's data need that account fetched
+        if "." in path or path not in accounts:
+            return None
+        return bytes(accounts[path])
+
+    # Transactions
+
+    async def send(self, client, instructions, payer, signers=()):
+        """Signs `instructions` with `payer` and `signers` and sends them."""
+        blockhash = (await client.get_latest_blockhash()).value.blockhash
+        tx = Transaction.new_signed_with_payer(
+            list(instructions), payer.pubkey(), [payer, *signers], blockhash
+        )
+        return (await client.send_transaction(tx)).value
@@ -0,0 +1,16 @@
+"""Refreshes the IDL bundled with the package from the Rust client crate."""
+import json
+import pathlib
+import subprocess
+
+root = pathlib.Path(__file__).resolve().parent.parent
+idl = subprocess.run(
+    ["cargo", "run", "-q", "-p", "neuraltrader-client", "--bin", "export_idl"],
+    cwd=root,
+    check=True,
+    capture_output=True,
+    text=True,
+).stdout
+(root / "neuraltrader" / "agent_market_sim.json").write_text(
+    json.dumps(json.loads(idl), indent=2) + "\n"
+)
@@ -0,0 +1,41 @@
+//! Builds the program's IDL into `OUT_DIR`, so the client crate always
+//! carries the interface of the program source it was compiled against.
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use anchor_lang_idl::build::IdlBuilder;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let program_path = manifest_dir.join("../../programs/anchor-programs");
+    println!("cargo:rerun-if-changed={}", program_path.join("src").display());
+    println!("cargo:rerun-if-changed={}", program_path.join("Cargo.toml").display());
+
+    // The IDL comes from a nested `cargo test` of the program, which must not
+    // inherit this build's wrappers or target directory. Its flags are set
+    // here since the builder's own enable a cfg only nightly can compile.
+    for var in [
+        "RUSTC_WRAPPER",
+        "RUSTC_WORKSPACE_WRAPPER",
+        "CARGO_TARGET_DIR",
+        "CARGO_MAKEFLAGS",
+    ] {
+        env::remove_var(var);
+    }
+    env::set_var("CARGO_ENCODED_RUSTFLAGS", "-Awarnings");
+    let idl = IdlBuilder::new()
+        .program_path(program_path)
+        .cargo_args(vec![
+            "--target-dir".into(),
+            out_dir.join("idl-target").display().to_string(),
+        ])
+        .build()
+        .expect("failed to build the program IDL");
+    fs::write(
+        out_dir.join("agent_market_sim.json"),
+        serde_json::to_string_pretty(&idl).unwrap(),
+    )
+    .unwrap();
+}
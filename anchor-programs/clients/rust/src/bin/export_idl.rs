@@ -0,0 +1,4 @@
+//! Prints the program's IDL, for generating clients in other languages.
+fn main() {
+    println!("{}", neuraltrader_client::IDL);
+}
@@ -0,0 +1,316 @@
+//! In-memory copy of one market's order book, loaded from a snapshot of its
+//! `Trade` accounts and kept current by applying the program's
+//! `OrderUpdated` events in `book_sequence` order. Every update carries the
+//! order's whole new state, so applying one is an upsert, or a removal once
+//! the order has nothing left. A skipped sequence number leaves the mirror
+//! stale until the next snapshot is loaded.
+use std::collections::{BTreeMap, HashMap};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_programs::events::OrderUpdated;
+use anchor_programs::{Trade, PRICE_SCALE, TRADE_TAG_LEN};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+/// An order resting on the book.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Order {
+    pub trade: Pubkey,
+    pub agent: Pubkey,
+    pub side: Side,
+    /// Token A per token B, `PRICE_SCALE` fixed point, rounded down
+    pub price: u64,
+    /// Token B left on the order
+    pub size: u64,
+    /// Token B shown, less than `size` for an iceberg
+    pub displayed: u64,
+    /// Queue priority, lower rests ahead
+    pub sequence: u64,
+    pub tag: [u8; TRADE_TAG_LEN],
+}
+
+impl Order {
+    /// The order `trade` holds, `None` once it has nothing left.
+    pub fn from_trade(key: Pubkey, trade: &Trade) -> Option<Self> {
+        Self::new(
+            key,
+            trade.agent,
+            trade.trade_type,
+            trade.amount_in,
+            trade.amount_out,
+            trade.sequence,
+            trade.displayed(),
+            trade.tag,
+        )
+    }
+
+    /// The order as of `update`, `None` if it left the book.
+    pub fn from_update(update: &OrderUpdated) -> Option<Self> {
+        Self::new(
+            update.trade,
+            update.agent,
+            update.trade_type,
+            update.amount_in,
+            update.amount_out,
+            update.sequence,
+            update.displayed,
+            update.tag,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        trade: Pubkey,
+        agent: Pubkey,
+        trade_type: u8,
+        amount_in: u64,
+        amount_out: u64,
+        sequence: u64,
+        displayed: u64,
+        tag: [u8; TRADE_TAG_LEN],
+    ) -> Option<Self> {
+        // Bids pay token A for token B, asks the reverse
+        let (side, size, quote) = match trade_type {
+            0 => (Side::Bid, amount_out, amount_in),
+            1 => (Side::Ask, amount_in, amount_out),
+            _ => return None,
+        };
+        if size == 0 || quote == 0 {
+            return None;
+        }
+        Some(Self {
+            trade,
+            agent,
+            side,
+            price: (quote as u128 * PRICE_SCALE as u128 / size as u128).min(u64::MAX as u128) as u64,
+            size,
+            displayed,
+            sequence,
+            tag,
+        })
+    }
+}
+
+/// Displayed size at one price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Level {
+    /// Token A per token B, `PRICE_SCALE` fixed point
+    pub price: u64,
+    /// Token B shown across the level's orders
+    pub quantity: u64,
+    pub orders: usize,
+}
+
+/// An update arrived out of order: `received` came when `expected` was next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SequenceGap {
+    pub expected: u64,
+    pub received: u64,
+}
+
+/// A market's book as of `book_sequence`, with L3 (order by order) and L2
+/// (aggregated by price) views.
+#[derive(Clone, Debug)]
+pub struct OrderBookMirror {
+    market: Pubkey,
+    book_sequence: u64,
+    orders: HashMap<Pubkey, Order>,
+    gap: Option<SequenceGap>,
+}
+
+impl OrderBookMirror {
+    /// An empty book at sequence 0, as a market starts out.
+    pub fn new(market: Pubkey) -> Self {
+        Self {
+            market,
+            book_sequence: 0,
+            orders: HashMap::new(),
+            gap: None,
+        }
+    }
+
+    /// Replaces the book with `trades`, read along with the market's
+    /// `book_sequence` at the same slot. Trades on other markets or with
+    /// nothing left are skipped.
+    pub fn load_snapshot<'a>(
+        &mut self,
+        book_sequence: u64,
+        trades: impl IntoIterator<Item = (Pubkey, &'a Trade)>,
+    ) {
+        self.book_sequence = book_sequence;
+        self.gap = None;
+        self.orders = trades
+            .into_iter()
+            .filter(|(_, trade)| trade.market == self.market)
+            .filter_map(|(key, trade)| Order::from_trade(key, trade))
+            .map(|order| (order.trade, order))
+            .collect();
+    }
+
+    /// Applies the next update and returns whether it changed the book.
+    /// Updates for other markets, and ones already reflected, such as those
+    /// from before the snapshot, are ignored. Once a gap is found every call
+    /// returns it until a snapshot is loaded.
+    pub fn apply(&mut self, update: &OrderUpdated) -> Result<bool, SequenceGap> {
+        if let Some(gap) = self.gap {
+            return Err(gap);
+        }
+        if update.market != self.market || update.book_sequence <= self.book_sequence {
+            return Ok(false);
+        }
+        let expected = self.book_sequence + 1;
+        if update.book_sequence != expected {
+            let gap = SequenceGap {
+                expected,
+                received: update.book_sequence,
+            };
+            self.gap = Some(gap);
+            return Err(gap);
+        }
+
+        self.book_sequence = update.book_sequence;
+        match Order::from_update(update) {
+            Some(order) => {
+                self.orders.insert(order.trade, order);
+            }
+            None => {
+                self.orders.remove(&update.trade);
+            }
+        }
+        Ok(true)
+    }
+
+    pub fn market(&self) -> Pubkey {
+        self.market
+    }
+
+    /// Sequence of the last update applied or of the snapshot.
+    pub fn book_sequence(&self) -> u64 {
+        self.book_sequence
+    }
+
+    /// Whether an update was missed and the book needs a fresh snapshot.
+    pub fn is_stale(&self) -> bool {
+        self.gap.is_some()
+    }
+
+    pub fn order(&self, trade: &Pubkey) -> Option<&Order> {
+        self.orders.get(trade)
+    }
+
+    /// L3: one side's orders in fill priority, best price first and then
+    /// by queue position.
+    pub fn orders(&self, side: Side) -> Vec<&Order> {
+        let mut orders: Vec<&Order> =
+            self.orders.values().filter(|order| order.side == side).collect();
+        orders.sort_by_key(|order| (priced(side, order.price), order.sequence));
+        orders
+    }
+
+    /// L2: one side's best `depth` price levels, best first.
+    pub fn levels(&self, side: Side, depth: usize) -> Vec<Level> {
+        let mut levels = BTreeMap::new();
+        for order in self.orders.values().filter(|order| order.side == side) {
+            let level = levels.entry(priced(side, order.price)).or_insert(Level {
+                price: order.price,
+                quantity: 0,
+                orders: 0,
+            });
+            level.quantity += order.displayed;
+            level.orders += 1;
+        }
+        levels.into_values().take(depth).collect()
+    }
+
+    pub fn best_bid(&self) -> Option<u64> {
+        self.orders(Side::Bid).first().map(|order| order.price)
+    }
+
+    pub fn best_ask(&self) -> Option<u64> {
+        self.orders(Side::Ask).first().map(|order| order.price)
+    }
+}
+
+/// Sort key that puts a side's best price first: highest bid, lowest ask.
+fn priced(side: Side, price: u64) -> u64 {
+    match side {
+        Side::Bid => u64::MAX - price,
+        Side::Ask => price,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(
+        market: Pubkey,
+        book_sequence: u64,
+        trade: Pubkey,
+        trade_type: u8,
+        price: u64,
+        size: u64,
+    ) -> OrderUpdated {
+        let quote = size * price / PRICE_SCALE;
+        let (amount_in, amount_out) = if trade_type == 0 { (quote, size) } else { (size, quote) };
+        OrderUpdated {
+            market,
+            book_sequence,
+            trade,
+            agent: Pubkey::new_unique(),
+            trade_type,
+            amount_in,
+            amount_out,
+            sequence: book_sequence,
+            displayed: size,
+            tag: [0; TRADE_TAG_LEN],
+        }
+    }
+
+    #[test]
+    fn updates_build_both_views() {
+        let market = Pubkey::new_unique();
+        let (bid, ask, other_bid) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut book = OrderBookMirror::new(market);
+        assert_eq!(book.apply(&update(market, 1, bid, 0, 2 * PRICE_SCALE, 10)), Ok(true));
+        assert_eq!(book.apply(&update(market, 2, ask, 1, 3 * PRICE_SCALE, 5)), Ok(true));
+        assert_eq!(book.apply(&update(market, 3, other_bid, 0, 2 * PRICE_SCALE, 4)), Ok(true));
+
+        assert_eq!(book.best_bid(), Some(2 * PRICE_SCALE));
+        assert_eq!(book.best_ask(), Some(3 * PRICE_SCALE));
+        let bids: Vec<Pubkey> = book.orders(Side::Bid).iter().map(|order| order.trade).collect();
+        assert_eq!(bids, vec![bid, other_bid]);
+        assert_eq!(
+            book.levels(Side::Bid, 5),
+            vec![Level { price: 2 * PRICE_SCALE, quantity: 14, orders: 2 }]
+        );
+
+        // A filled-out order leaves the book
+        assert_eq!(book.apply(&update(market, 4, ask, 1, 3 * PRICE_SCALE, 0)), Ok(true));
+        assert_eq!(book.best_ask(), None);
+        // Replays and other markets' updates are ignored
+        assert_eq!(book.apply(&update(market, 2, ask, 1, 3 * PRICE_SCALE, 5)), Ok(false));
+        assert_eq!(book.apply(&update(Pubkey::new_unique(), 5, ask, 1, PRICE_SCALE, 5)), Ok(false));
+        assert_eq!(book.book_sequence(), 4);
+    }
+
+    #[test]
+    fn gap_marks_the_book_stale_until_a_snapshot() {
+        let market = Pubkey::new_unique();
+        let trade = Pubkey::new_unique();
+        let mut book = OrderBookMirror::new(market);
+        let gap = SequenceGap { expected: 1, received: 2 };
+        assert_eq!(book.apply(&update(market, 2, trade, 0, PRICE_SCALE, 1)), Err(gap));
+        assert_eq!(book.apply(&update(market, 1, trade, 0, PRICE_SCALE, 1)), Err(gap));
+        assert!(book.is_stale());
+
+        book.load_snapshot(2, []);
+        assert!(!book.is_stale());
+        assert_eq!(book.apply(&update(market, 3, trade, 0, PRICE_SCALE, 1)), Ok(true));
+        assert_eq!(book.orders(Side::Bid).len(), 1);
+    }
+}
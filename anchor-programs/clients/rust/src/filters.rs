@@ -0,0 +1,72 @@
+//! `getProgramAccounts` filters for the program's accounts, built from the
+//! fixed offsets their leading keys sit at. Each builder returns the account
+//! type's discriminator filter followed by the key's `memcmp`, ready to be
+//! turned into the RPC client's filter type.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use anchor_programs::{
+    Agent, AgentListing, Balance, CopyFeed, Guild, GuildMember, Market, MarketListing, OptionContract,
+    OtcOffer, Position, RedemptionRequest, RelayerDebt, Rfq, ScoreClaim, Settlement, StrategyVault,
+    Subscription, Trade, Vault,
+};
+
+/// Account data at `offset` equals `bytes`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Memcmp {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl Memcmp {
+    /// Matches accounts of type `T`.
+    pub fn discriminator<T: Discriminator>() -> Self {
+        Self {
+            offset: 0,
+            bytes: T::DISCRIMINATOR.to_vec(),
+        }
+    }
+
+    pub fn pubkey(offset: usize, key: &Pubkey) -> Self {
+        Self {
+            offset,
+            bytes: key.to_bytes().to_vec(),
+        }
+    }
+}
+
+macro_rules! filters {
+    ($($filter:ident($account:ty) { $($by:ident: $field:literal @ $offset:literal),* $(,)? })*) => {$(
+        #[doc = concat!("Filters for `", stringify!($account), "` accounts.")]
+        pub struct $filter;
+
+        impl $filter {$(
+            #[doc = concat!("`", $field, "` equal to `key`, a `memcmp` at offset ", $offset, ".")]
+            pub fn $by(key: &Pubkey) -> Vec<Memcmp> {
+                vec![Memcmp::discriminator::<$account>(), Memcmp::pubkey($offset, key)]
+            }
+        )*}
+    )*};
+}
+
+filters! {
+    MarketFilter(Market) { by_token_a: "token_a" @ 8, by_token_b: "token_b" @ 40, by_authority: "authority" @ 72 }
+    AgentFilter(Agent) { by_owner: "owner" @ 8, by_registrant: "registrant" @ 40 }
+    TradeFilter(Trade) { by_market: "market" @ 8, by_agent: "agent" @ 40 }
+    PositionFilter(Position) { by_market: "market" @ 8, by_agent: "agent" @ 40 }
+    SettlementFilter(Settlement) { by_market: "market" @ 8, by_agent: "agent" @ 40 }
+    RelayerDebtFilter(RelayerDebt) { by_market: "market" @ 8, by_agent: "agent" @ 40, by_relayer: "relayer" @ 72 }
+    VaultFilter(Vault) { by_market: "market" @ 8, by_token: "token" @ 40 }
+    BalanceFilter(Balance) { by_vault: "vault" @ 8, by_agent: "agent" @ 40 }
+    OtcOfferFilter(OtcOffer) { by_market: "market" @ 8, by_maker: "maker" @ 40 }
+    RfqFilter(Rfq) { by_market: "market" @ 8, by_taker: "taker" @ 40 }
+    OptionFilter(OptionContract) { by_market: "market" @ 8, by_writer: "writer" @ 40, by_holder: "holder" @ 72 }
+    ScoreClaimFilter(ScoreClaim) { by_score_root: "score_root" @ 8, by_agent: "agent" @ 40 }
+    GuildFilter(Guild) { by_market: "market" @ 8, by_authority: "authority" @ 40 }
+    GuildMemberFilter(GuildMember) { by_guild: "guild" @ 8, by_member: "member" @ 40 }
+    CopyFeedFilter(CopyFeed) { by_market: "market" @ 8, by_leader: "leader" @ 40 }
+    SubscriptionFilter(Subscription) { by_market: "market" @ 8, by_follower: "follower" @ 40, by_leader: "leader" @ 72 }
+    StrategyVaultFilter(StrategyVault) { by_market: "market" @ 8, by_manager_agent: "manager_agent" @ 40 }
+    RedemptionFilter(RedemptionRequest) { by_strategy_vault: "strategy_vault" @ 8, by_owner: "owner" @ 40 }
+    MarketListingFilter(MarketListing) { by_market: "market" @ 8 }
+    AgentListingFilter(AgentListing) { by_agent: "agent" @ 8 }
+}
@@ -0,0 +1,14 @@
+//! Off-chain client for the agent market program. Re-exports the program's
+//! account and instruction types and carries its IDL, built from the same
+//! source, for codegen in other languages. `filters` builds
+//! `getProgramAccounts` filters for its accounts and `book` mirrors a
+//! market's order book from its events; `subscribe` turns websocket
+//! subscriptions into typed updates that survive reconnects.
+pub mod book;
+pub mod filters;
+pub mod subscribe;
+
+pub use anchor_programs::{accounts, events, instruction, ID};
+
+/// The program's Anchor IDL as JSON.
+pub const IDL: &str = include_str!(concat!(env!("OUT_DIR"), "/agent_market_sim.json"));
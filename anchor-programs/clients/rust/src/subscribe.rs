@@ -0,0 +1,409 @@
+//! Typed, deduplicated updates from the program's websocket subscriptions.
+//! `Subscriber` drives any `PubsubTransport`, such as a wrapper around
+//! `solana_pubsub_client::nonblocking::PubsubClient` on tokio: when the
+//! connection drops it reconnects with exponential backoff, subscribes to
+//! everything again and yields `Update::Reconnected`, since whatever happened
+//! while it was down is lost. Account notifications older than, or the same
+//! as, one already seen for the account are dropped, as resubscribing
+//! replays each account's current state; log notifications are dropped once
+//! their transaction has been seen, and failed transactions are skipped.
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator, Event};
+use anchor_programs::events::{OrderFilled, OrderUpdated};
+use anchor_programs::{Market, Trade};
+use base64::prelude::{Engine, BASE64_STANDARD};
+
+use crate::filters::Memcmp;
+
+/// Slots a seen transaction is remembered for, to drop repeats of its logs.
+pub const LOG_DEDUP_SLOTS: u64 = 150;
+
+/// What to subscribe to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Subscription {
+    /// `accountSubscribe` to one account
+    Account(Pubkey),
+    /// `programSubscribe` to the program's accounts matching all `filters`
+    Program(Vec<Memcmp>),
+    /// `logsSubscribe` to transactions mentioning the program
+    Logs,
+}
+
+/// A notification as the transport received it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Notification {
+    /// Empty `data` for a closed account
+    Account {
+        slot: u64,
+        address: Pubkey,
+        data: Vec<u8>,
+    },
+    Logs {
+        slot: u64,
+        signature: String,
+        failed: bool,
+        logs: Vec<String>,
+    },
+}
+
+/// Websocket connection the subscriber runs over. Errors from `subscribe`
+/// and `next` are treated as a dropped connection.
+pub trait PubsubTransport {
+    type Error;
+
+    fn connect(&mut self) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    fn subscribe(
+        &mut self,
+        subscription: &Subscription,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    /// Waits for the next notification on any subscription.
+    fn next(&mut self) -> impl Future<Output = Result<Notification, Self::Error>> + Send;
+    /// Waits `delay` on the transport's runtime before reconnecting.
+    fn sleep(&mut self, delay: Duration) -> impl Future<Output = ()> + Send;
+}
+
+/// A decoded update.
+pub enum Update {
+    Market {
+        slot: u64,
+        address: Pubkey,
+        market: Box<Market>,
+    },
+    /// `trade` is `None` once the account is closed
+    Trade {
+        slot: u64,
+        address: Pubkey,
+        trade: Option<Trade>,
+    },
+    Fill {
+        slot: u64,
+        signature: String,
+        fill: OrderFilled,
+    },
+    Book {
+        slot: u64,
+        signature: String,
+        update: OrderUpdated,
+    },
+    /// The connection dropped and was restored; notifications in between
+    /// were missed
+    Reconnected,
+}
+
+/// Delays between reconnection attempts, doubling from `initial` up to `max`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(250),
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Market,
+    Trade,
+}
+
+pub struct Subscriber<T: PubsubTransport> {
+    transport: T,
+    subscriptions: Vec<Subscription>,
+    backoff: Backoff,
+    connected: bool,
+    has_connected: bool,
+    pending: VecDeque<Update>,
+    // Each account's kind and the last notification kept for it
+    accounts: HashMap<Pubkey, (Kind, u64, Vec<u8>)>,
+    // Seen transactions, by slot
+    signatures: BTreeMap<u64, HashSet<String>>,
+}
+
+impl<T: PubsubTransport> Subscriber<T> {
+    /// Connects lazily, on the first `next`.
+    pub fn new(transport: T, subscriptions: Vec<Subscription>) -> Self {
+        Self {
+            transport,
+            subscriptions,
+            backoff: Backoff::default(),
+            connected: false,
+            has_connected: false,
+            pending: VecDeque::new(),
+            accounts: HashMap::new(),
+            signatures: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// The next update, reconnecting for as long as it takes.
+    pub async fn next(&mut self) -> Update {
+        loop {
+            if let Some(update) = self.pending.pop_front() {
+                return update;
+            }
+            if !self.connected {
+                self.reconnect().await;
+                if std::mem::replace(&mut self.has_connected, true) {
+                    return Update::Reconnected;
+                }
+            }
+            match self.transport.next().await {
+                Ok(notification) => self.receive(notification),
+                Err(_) => self.connected = false,
+            }
+        }
+    }
+
+    async fn reconnect(&mut self) {
+        let mut delay = self.backoff.initial;
+        loop {
+            if self.resubscribe().await.is_ok() {
+                self.connected = true;
+                return;
+            }
+            self.transport.sleep(delay).await;
+            delay = (delay * 2).min(self.backoff.max);
+        }
+    }
+
+    async fn resubscribe(&mut self) -> Result<(), T::Error> {
+        self.transport.connect().await?;
+        for subscription in &self.subscriptions {
+            self.transport.subscribe(subscription).await?;
+        }
+        Ok(())
+    }
+
+    fn receive(&mut self, notification: Notification) {
+        match notification {
+            Notification::Account { slot, address, data } => self.receive_account(slot, address, data),
+            Notification::Logs {
+                slot,
+                signature,
+                failed,
+                logs,
+            } => {
+                if failed || !self.first_sighting(slot, &signature) {
+                    return;
+                }
+                for data in program_data(&logs) {
+                    if let Some(fill) = decode_event::<OrderFilled>(&data) {
+                        self.pending.push_back(Update::Fill {
+                            slot,
+                            signature: signature.clone(),
+                            fill,
+                        });
+                    } else if let Some(update) = decode_event::<OrderUpdated>(&data) {
+                        self.pending.push_back(Update::Book {
+                            slot,
+                            signature: signature.clone(),
+                            update,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn receive_account(&mut self, slot: u64, address: Pubkey, data: Vec<u8>) {
+        let kind = if data.starts_with(Market::DISCRIMINATOR) {
+            Kind::Market
+        } else if data.starts_with(Trade::DISCRIMINATOR) {
+            Kind::Trade
+        } else {
+            // Closed accounts are only recognised once seen open
+            match self.accounts.get(&address) {
+                Some((kind, ..)) if data.is_empty() => *kind,
+                _ => return,
+            }
+        };
+        if let Some((_, last_slot, last_data)) = self.accounts.get(&address) {
+            if slot < *last_slot || (slot == *last_slot && data == *last_data) {
+                return;
+            }
+        }
+
+        let update = match kind {
+            Kind::Market => match Market::try_deserialize(&mut &data[..]) {
+                Ok(market) => Update::Market {
+                    slot,
+                    address,
+                    market: Box::new(market),
+                },
+                Err(_) => return,
+            },
+            Kind::Trade => Update::Trade {
+                slot,
+                address,
+                trade: Trade::try_deserialize(&mut &data[..]).ok(),
+            },
+        };
+        self.accounts.insert(address, (kind, slot, data));
+        self.pending.push_back(update);
+    }
+
+    /// Records `signature` and returns whether it is new.
+    fn first_sighting(&mut self, slot: u64, signature: &str) -> bool {
+        if self.signatures.values().any(|seen| seen.contains(signature)) {
+            return false;
+        }
+        self.signatures.entry(slot).or_default().insert(signature.to_owned());
+        let latest = *self.signatures.keys().next_back().unwrap_or(&slot);
+        self.signatures = self.signatures.split_off(&latest.saturating_sub(LOG_DEDUP_SLOTS));
+        true
+    }
+}
+
+fn program_data(logs: &[String]) -> impl Iterator<Item = Vec<u8>> + '_ {
+    logs.iter()
+        .filter_map(|line| line.strip_prefix("Program data: "))
+        .filter_map(|data| BASE64_STANDARD.decode(data).ok())
+}
+
+fn decode_event<E: Event + Discriminator + AnchorDeserialize>(data: &[u8]) -> Option<E> {
+    let body = data.strip_prefix(E::DISCRIMINATOR)?;
+    E::try_from_slice(body).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use anchor_lang::{AccountSerialize, AnchorSerialize};
+
+    use super::*;
+
+    /// Plays back `script`, where `None` drops the connection.
+    struct Scripted {
+        script: VecDeque<Option<Notification>>,
+        connects: usize,
+    }
+
+    impl PubsubTransport for Scripted {
+        type Error = ();
+
+        async fn connect(&mut self) -> Result<(), ()> {
+            self.connects += 1;
+            Ok(())
+        }
+
+        async fn subscribe(&mut self, _: &Subscription) -> Result<(), ()> {
+            Ok(())
+        }
+
+        async fn next(&mut self) -> Result<Notification, ()> {
+            self.script.pop_front().flatten().ok_or(())
+        }
+
+        async fn sleep(&mut self, _: Duration) {}
+    }
+
+    // The scripted transport never waits, so one poll always completes
+    fn ready<F: Future>(future: F) -> F::Output {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => unreachable!("scripted transport never waits"),
+        }
+    }
+
+    fn trade_account(slot: u64, address: Pubkey, amount_in: u64) -> Notification {
+        let trade = Trade {
+            market: Pubkey::default(),
+            agent: Pubkey::default(),
+            trade_type: 0,
+            amount_in,
+            amount_out: 1,
+            sequence: 0,
+            display_size: 0,
+            visible_size: 0,
+            flags: 0,
+            tag: [0; anchor_programs::TRADE_TAG_LEN],
+            order_id: 0,
+            booked_level: 0,
+            booked_displayed: 0,
+            pro_rata_round: 0,
+            uncross_end: 0,
+            uncross_size: 0,
+            uncross_reserved: 0,
+            bump: 0,
+        };
+        let mut data = Vec::new();
+        trade.try_serialize(&mut data).unwrap();
+        Notification::Account { slot, address, data }
+    }
+
+    fn fill_logs(slot: u64, signature: &str) -> Notification {
+        let fill = OrderFilled {
+            market: Pubkey::new_unique(),
+            bid_agent: Pubkey::new_unique(),
+            ask_agent: Pubkey::new_unique(),
+            bid_tag: [1; anchor_programs::TRADE_TAG_LEN],
+            ask_tag: [2; anchor_programs::TRADE_TAG_LEN],
+            bid_is_maker: true,
+            quantity: 5,
+            quote_amount: 10,
+            bid_fee: 0,
+            ask_fee: 0,
+            tie_break_seed: [0; 32],
+        };
+        let mut data = OrderFilled::DISCRIMINATOR.to_vec();
+        fill.serialize(&mut data).unwrap();
+        Notification::Logs {
+            slot,
+            signature: signature.into(),
+            failed: false,
+            logs: vec![format!("Program data: {}", BASE64_STANDARD.encode(data))],
+        }
+    }
+
+    #[test]
+    fn reconnects_and_drops_replays() {
+        let trade = Pubkey::new_unique();
+        let script = VecDeque::from([
+            Some(trade_account(10, trade, 100)),
+            Some(fill_logs(11, "a")),
+            None,
+            // Resubscribing replays the account, then the same transaction
+            // arrives again alongside a new one and the trade's close
+            Some(trade_account(10, trade, 100)),
+            Some(fill_logs(11, "a")),
+            Some(fill_logs(12, "b")),
+            Some(Notification::Account {
+                slot: 12,
+                address: trade,
+                data: Vec::new(),
+            }),
+        ]);
+        let transport = Scripted { script, connects: 0 };
+        let subscriptions = vec![Subscription::Account(trade), Subscription::Logs];
+        let mut subscriber = Subscriber::new(transport, subscriptions);
+
+        assert!(matches!(ready(subscriber.next()), Update::Trade { slot: 10, trade: Some(_), .. }));
+        assert!(matches!(ready(subscriber.next()), Update::Fill { slot: 11, .. }));
+        assert!(matches!(ready(subscriber.next()), Update::Reconnected));
+        match ready(subscriber.next()) {
+            Update::Fill { signature, fill, .. } => {
+                assert_eq!(signature, "b");
+                assert_eq!(fill.bid_tag, [1; anchor_programs::TRADE_TAG_LEN]);
+            }
+            _ => panic!("expected a fill"),
+        }
+        assert!(matches!(ready(subscriber.next()), Update::Trade { slot: 12, trade: None, .. }));
+        assert_eq!(subscriber.transport.connects, 2);
+    }
+}
@@ -0,0 +1,67 @@
+// Regenerates src/generated from the IDL the Rust client crate builds, so the
+// TS client always matches the program in this workspace. The type file is
+// the camelCase form `anchor build` writes to target/types.
+import { execFileSync } from "node:child_process";
+import { mkdirSync, writeFileSync } from "node:fs";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const root = dirname(dirname(fileURLToPath(import.meta.url)));
+const out = join(root, "src", "generated");
+
+const idl = JSON.parse(
+  execFileSync(
+    "cargo",
+    ["run", "-q", "-p", "neuraltrader-client", "--bin", "export_idl"],
+    { cwd: root, encoding: "utf8", maxBuffer: 64 * 1024 * 1024 }
+  )
+);
+const name = idl.metadata.name;
+
+// Same word splitting as heck's lowerCamelCase, which the Anchor CLI uses
+function lowerCamel(value) {
+  const words = value
+    .split(/_+/)
+    .flatMap((word) => word.split(/(?<=[a-z0-9])(?=[A-Z])/))
+    .filter(Boolean);
+  return words
+    .map((word, i) =>
+      i === 0
+        ? word.toLowerCase()
+        : word[0].toUpperCase() + word.slice(1).toLowerCase()
+    )
+    .join("");
+}
+
+// Every identifier-like string value is renamed, except addresses
+const isAddress = (value) => /^[1-9A-HJ-NP-Za-km-z]{32,44}$/.test(value);
+function camelize(value) {
+  if (Array.isArray(value)) return value.map(camelize);
+  if (value && typeof value === "object") {
+    return Object.fromEntries(
+      Object.entries(value).map(([key, inner]) => [key, camelize(inner)])
+    );
+  }
+  if (typeof value === "string" && /^\w+$/.test(value) && !isAddress(value)) {
+    return lowerCamel(value);
+  }
+  return value;
+}
+
+const typeName = lowerCamel(name).replace(/^./, (c) => c.toUpperCase());
+
+mkdirSync(out, { recursive: true });
+writeFileSync(join(out, `${name}.json`), JSON.stringify(idl, null, 2) + "\n");
+writeFileSync(
+  join(out, `${name}.ts`),
+  `/**
+ * Program IDL in camelCase format in order to be used in JS/TS.
+ *
+ * Note that this is only a type helper and is not the actual IDL. The original
+ * IDL can be found at \`${name}.json\`.
+ *
+ * Generated by scripts/generate.mjs; do not edit.
+ */
+export type ${typeName} = ${JSON.stringify(camelize(idl), null, 2)};
+`
+);
@@ -0,0 +1,280 @@
+import { AnchorProvider, BN, Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { AgentMarketSim } from "./generated/agent_market_sim";
+import {
+  agentListingPda,
+  agentPda,
+  balancePda,
+  configPda,
+  marketListingPda,
+  marketPda,
+  vaultPda,
+} from "./pda";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  associatedTokenAddress,
+  createAssociatedTokenAccount,
+} from "./token";
+
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+const MINT_SIZE = 82;
+
+type Amount = BN | number | bigint;
+
+export interface BootstrapOptions {
+  /** Agents to register and fund; defaults to 2 */
+  agents?: number;
+  /** Decimals of both test mints; defaults to 6 */
+  decimals?: number;
+  /** Deposited for each agent in each token; defaults to 1,000 tokens */
+  deposit?: Amount;
+  /** SOL in lamports sent to each agent's owner; defaults to 1 SOL */
+  agentLamports?: number;
+  /** Config defaults, only used when the config doesn't exist yet */
+  makerFeeBps?: number;
+  takerFeeBps?: number;
+  features?: Amount;
+}
+
+export interface BootstrapAgent {
+  owner: Keypair;
+  agent: PublicKey;
+  tokenAAccount: PublicKey;
+  tokenBAccount: PublicKey;
+  balanceA: PublicKey;
+  balanceB: PublicKey;
+}
+
+export interface LocalnetBootstrap {
+  config: PublicKey;
+  tokenAMint: PublicKey;
+  tokenBMint: PublicKey;
+  market: PublicKey;
+  vaultA: PublicKey;
+  vaultB: PublicKey;
+  vaultATokenAccount: PublicKey;
+  vaultBTokenAccount: PublicKey;
+  agents: BootstrapAgent[];
+}
+
+function u64(value: Amount): Buffer {
+  return new BN(value.toString()).toArrayLike(Buffer, "le", 8);
+}
+
+// SPL Token InitializeMint2, with no freeze authority
+function initializeMint(
+  mint: PublicKey,
+  decimals: number,
+  authority: PublicKey
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: TOKEN_PROGRAM_ID,
+    keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+    data: Buffer.concat([
+      Buffer.from([20, decimals]),
+      authority.toBuffer(),
+      Buffer.from([0]),
+    ]),
+  });
+}
+
+function mintTo(
+  mint: PublicKey,
+  account: PublicKey,
+  authority: PublicKey,
+  amount: Amount
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: TOKEN_PROGRAM_ID,
+    keys: [
+      { pubkey: mint, isSigner: false, isWritable: true },
+      { pubkey: account, isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: false },
+    ],
+    data: Buffer.concat([Buffer.from([7]), u64(amount)]),
+  });
+}
+
+/**
+ * Sets up a fresh local cluster for trading: the config, if the program
+ * doesn't have one yet, two new test mints, a market over them with its
+ * vaults, and `agents` registered agents with deposits in both tokens. The
+ * provider's wallet pays for everything, mints the tokens and has to be the
+ * program's upgrade authority, as it is after `anchor localnet` or
+ * `anchor test`.
+ */
+export async function bootstrapLocalnet(
+  program: Program<AgentMarketSim>,
+  options: BootstrapOptions = {}
+): Promise<LocalnetBootstrap> {
+  const provider = program.provider as AnchorProvider;
+  const wallet = provider.wallet.publicKey;
+  const decimals = options.decimals ?? 6;
+  const deposit = new BN(
+    (options.deposit ?? 1_000 * 10 ** decimals).toString()
+  );
+  const programId = program.programId;
+
+  const config = configPda(programId);
+  if (!(await provider.connection.getAccountInfo(config))) {
+    const [programData] = PublicKey.findProgramAddressSync(
+      [programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_ID
+    );
+    await program.methods
+      .initializeConfig(
+        wallet,
+        options.makerFeeBps ?? 0,
+        options.takerFeeBps ?? 0,
+        new BN((options.features ?? 0).toString())
+      )
+      .accountsPartial({
+        config,
+        program: programId,
+        programData,
+        upgradeAuthority: wallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  const mints = [Keypair.generate(), Keypair.generate()];
+  const rent = await provider.connection.getMinimumBalanceForRentExemption(
+    MINT_SIZE
+  );
+  const createMints = new Transaction();
+  for (const mint of mints) {
+    createMints.add(
+      SystemProgram.createAccount({
+        fromPubkey: wallet,
+        newAccountPubkey: mint.publicKey,
+        lamports: rent,
+        space: MINT_SIZE,
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      initializeMint(mint.publicKey, decimals, wallet)
+    );
+  }
+  await provider.sendAndConfirm(createMints, mints);
+
+  const [tokenAMint, tokenBMint] = mints.map((mint) => mint.publicKey);
+  const market = marketPda(tokenAMint, tokenBMint, programId);
+  const vaultA = vaultPda(tokenAMint, tokenBMint, tokenAMint, programId);
+  const vaultB = vaultPda(tokenAMint, tokenBMint, tokenBMint, programId);
+  const vaultATokenAccount = associatedTokenAddress(vaultA, tokenAMint);
+  const vaultBTokenAccount = associatedTokenAddress(vaultB, tokenBMint);
+  const { marketCount } = await program.account.config.fetch(config);
+  await program.methods
+    .initializeMarket()
+    .accountsPartial({
+      config,
+      market,
+      marketListing: marketListingPda(marketCount, programId),
+      vaultA,
+      vaultB,
+      vaultATokenAccount,
+      vaultBTokenAccount,
+      tokenAMint,
+      tokenBMint,
+      signer: wallet,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  const agents: BootstrapAgent[] = [];
+  for (let i = 0; i < (options.agents ?? 2); i++) {
+    const owner = Keypair.generate();
+    const agent = agentPda(owner.publicKey, programId);
+    const keys: BootstrapAgent = {
+      owner,
+      agent,
+      tokenAAccount: associatedTokenAddress(owner.publicKey, tokenAMint),
+      tokenBAccount: associatedTokenAddress(owner.publicKey, tokenBMint),
+      balanceA: balancePda(vaultA, agent, programId),
+      balanceB: balancePda(vaultB, agent, programId),
+    };
+
+    // The wallet funds the owner, who then registers and deposits itself
+    const fund = new Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: wallet,
+        toPubkey: owner.publicKey,
+        lamports: options.agentLamports ?? 1_000_000_000,
+      })
+    );
+    for (const [mint, account] of [
+      [tokenAMint, keys.tokenAAccount],
+      [tokenBMint, keys.tokenBAccount],
+    ]) {
+      fund.add(
+        createAssociatedTokenAccount(wallet, owner.publicKey, mint),
+        mintTo(mint, account, wallet, deposit)
+      );
+    }
+    await provider.sendAndConfirm(fund);
+
+    const { agentCount } = await program.account.config.fetch(config);
+    const register = await program.methods
+      .registerAgent()
+      .accountsPartial({
+        config,
+        agent,
+        agentListing: agentListingPda(agentCount, programId),
+        user: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    const sides = [
+      { mint: tokenAMint, vault: vaultA, vaultToken: vaultATokenAccount },
+      { mint: tokenBMint, vault: vaultB, vaultToken: vaultBTokenAccount },
+    ];
+    const deposits = await Promise.all(
+      sides.map(({ mint, vault, vaultToken }, side) =>
+        program.methods
+          .depositTokens(deposit, null)
+          .accountsPartial({
+            agent,
+            market,
+            user: owner.publicKey,
+            tokenMint: mint,
+            userTokenAccount: side ? keys.tokenBAccount : keys.tokenAAccount,
+            vault,
+            vaultTokenAccount: vaultToken,
+            balance: side ? keys.balanceB : keys.balanceA,
+            receipt: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction()
+      )
+    );
+    await provider.sendAndConfirm(
+      new Transaction().add(register, ...deposits),
+      [owner]
+    );
+    agents.push(keys);
+  }
+
+  return {
+    config,
+    tokenAMint,
+    tokenBMint,
+    market,
+    vaultA,
+    vaultB,
+    vaultATokenAccount,
+    vaultBTokenAccount,
+    agents,
+  };
+}
@@ -0,0 +1,51 @@
+import { BorshCoder, EventParser, IdlEvents, Program } from "@coral-xyz/anchor";
+import { Connection, PublicKey } from "@solana/web3.js";
+import idl from "./generated/agent_market_sim.json";
+import { AgentMarketSim } from "./generated/agent_market_sim";
+import { PROGRAM_ID } from "./pda";
+
+export type MarketEvents = IdlEvents<AgentMarketSim>;
+export type MarketEventName = keyof MarketEvents;
+
+/** A decoded event, tagged with its camelCase name. */
+export type MarketEvent = {
+  [N in MarketEventName]: { name: N; data: MarketEvents[N] };
+}[MarketEventName];
+
+const coder = new BorshCoder(idl as AgentMarketSim);
+
+/** Decodes one base64 `Program data:` payload; null if it isn't ours. */
+export function decodeEvent(data: string): MarketEvent | null {
+  return coder.events.decode(data) as MarketEvent | null;
+}
+
+/** Every event the program emitted in a transaction's logs. */
+export function parseEvents(
+  logs: string[],
+  programId: PublicKey = PROGRAM_ID
+): MarketEvent[] {
+  const parser = new EventParser(programId, coder);
+  return Array.from(parser.parseLogs(logs)) as MarketEvent[];
+}
+
+/** Events emitted by a confirmed transaction. */
+export async function fetchEvents(
+  connection: Connection,
+  signature: string,
+  programId: PublicKey = PROGRAM_ID
+): Promise<MarketEvent[]> {
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  return parseEvents(tx?.meta?.logMessages ?? [], programId);
+}
+
+/** Subscribes to one event type; returns the listener id to remove later. */
+export function onEvent<N extends MarketEventName>(
+  program: Program<AgentMarketSim>,
+  name: N,
+  callback: (event: MarketEvents[N], slot: number, signature: string) => void
+): number {
+  return program.addEventListener(name, callback as any);
+}
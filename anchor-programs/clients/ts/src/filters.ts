@@ -0,0 +1,52 @@
+import { GetProgramAccountsFilter, PublicKey } from "@solana/web3.js";
+
+type Filters<K extends string> = Record<
+  K,
+  (key: PublicKey) => GetProgramAccountsFilter
+>;
+
+// memcmp filters on the Pubkeys at the given offsets; program.account.x.all
+// adds the discriminator filter itself
+function at<K extends string>(offsets: Record<K, number>): Filters<K> {
+  const filters = {} as Filters<K>;
+  for (const name of Object.keys(offsets) as K[]) {
+    filters[name] = (key) => ({
+      memcmp: { offset: offsets[name], bytes: key.toBase58() },
+    });
+  }
+  return filters;
+}
+
+/**
+ * `getProgramAccounts` filters on the keys each account keeps at fixed
+ * offsets after its discriminator, e.g.
+ * `program.account.trade.all([tradeFilter.byMarket(market)])`.
+ */
+export const marketFilter = at({ byTokenA: 8, byTokenB: 40, byAuthority: 72 });
+export const agentFilter = at({ byOwner: 8, byRegistrant: 40 });
+export const tradeFilter = at({ byMarket: 8, byAgent: 40 });
+export const positionFilter = at({ byMarket: 8, byAgent: 40 });
+export const settlementFilter = at({ byMarket: 8, byAgent: 40 });
+export const relayerDebtFilter = at({
+  byMarket: 8,
+  byAgent: 40,
+  byRelayer: 72,
+});
+export const vaultFilter = at({ byMarket: 8, byToken: 40 });
+export const balanceFilter = at({ byVault: 8, byAgent: 40 });
+export const otcOfferFilter = at({ byMarket: 8, byMaker: 40 });
+export const rfqFilter = at({ byMarket: 8, byTaker: 40 });
+export const optionFilter = at({ byMarket: 8, byWriter: 40, byHolder: 72 });
+export const scoreClaimFilter = at({ byScoreRoot: 8, byAgent: 40 });
+export const guildFilter = at({ byMarket: 8, byAuthority: 40 });
+export const guildMemberFilter = at({ byGuild: 8, byMember: 40 });
+export const copyFeedFilter = at({ byMarket: 8, byLeader: 40 });
+export const subscriptionFilter = at({
+  byMarket: 8,
+  byFollower: 40,
+  byLeader: 72,
+});
+export const strategyVaultFilter = at({ byMarket: 8, byManagerAgent: 40 });
+export const redemptionFilter = at({ byStrategyVault: 8, byOwner: 40 });
+export const marketListingFilter = at({ byMarket: 8 });
+export const agentListingFilter = at({ byAgent: 8 });
@@ -0,0 +1,22733 @@
+/**
+ * Program IDL in camelCase format in order to be used in JS/TS.
+ *
+ * Note that this is only a type helper and is not the actual IDL. The original
+ * IDL can be found at `agent_market_sim.json`.
+ *
+ * Generated by scripts/generate.mjs; do not edit.
+ */
+export type AgentMarketSim = {
+  "address": "GAmxv5ze5Y1rvaJziz8nrnFtz4pBuF14YDvs8iqm7XkB",
+  "metadata": {
+    "name": "agentMarketSim",
+    "version": "0.1.0",
+    "spec": "0.1.0",
+    "description": "Created with Anchor"
+  },
+  "instructions": [
+    {
+      "name": "acceptAgentOwnership",
+      "discriminator": [
+        106,
+        53,
+        200,
+        10,
+        51,
+        109,
+        210,
+        193
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "newOwner",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "acceptOtcOffer",
+      "discriminator": [
+        33,
+        60,
+        181,
+        217,
+        80,
+        89,
+        99,
+        5
+      ],
+      "accounts": [
+        {
+          "name": "takerAgent"
+        },
+        {
+          "name": "makerAgent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "offer"
+          ]
+        },
+        {
+          "name": "offer",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  116,
+                  99
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "makerAgent"
+              },
+              {
+                "kind": "account",
+                "path": "offer.offer_id",
+                "account": "otcOffer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "giveMint"
+        },
+        {
+          "name": "wantMint"
+        },
+        {
+          "name": "escrowTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "offer"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "giveMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "takerWantAccount",
+          "writable": true
+        },
+        {
+          "name": "takerGiveAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "taker"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "giveMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "makerWantAccount",
+          "writable": true
+        },
+        {
+          "name": "makerOwner",
+          "writable": true
+        },
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "acceptQuote",
+      "discriminator": [
+        129,
+        61,
+        5,
+        81,
+        46,
+        253,
+        210,
+        152
+      ],
+      "accounts": [
+        {
+          "name": "takerAgent"
+        },
+        {
+          "name": "makerAgent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "rfq",
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "rfq",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  102,
+                  113
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "takerAgent"
+              },
+              {
+                "kind": "account",
+                "path": "rfq.rfq_id",
+                "account": "rfq"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultB",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "takerBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "takerAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "takerBalanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "takerAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "makerBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "makerAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "makerBalanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "makerAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "advanceCurriculum",
+      "discriminator": [
+        181,
+        158,
+        235,
+        122,
+        80,
+        254,
+        131,
+        176
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "curriculum"
+          ]
+        },
+        {
+          "name": "curriculum",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  117,
+                  114,
+                  114,
+                  105,
+                  99,
+                  117,
+                  108,
+                  117,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "autoDeleverage",
+      "discriminator": [
+        210,
+        69,
+        163,
+        148,
+        44,
+        245,
+        226,
+        170
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "bankrupt",
+            "counterparty",
+            "vaultA"
+          ]
+        },
+        {
+          "name": "adlQueue",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  100,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bankrupt",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "bankrupt.agent",
+                "account": "position"
+              }
+            ]
+          }
+        },
+        {
+          "name": "counterparty",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "counterparty.agent",
+                "account": "position"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "cranker",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "buyOption",
+      "discriminator": [
+        242,
+        253,
+        221,
+        183,
+        67,
+        244,
+        140,
+        119
+      ],
+      "accounts": [
+        {
+          "name": "buyerAgent"
+        },
+        {
+          "name": "writerAgent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "option",
+            "vaultA"
+          ]
+        },
+        {
+          "name": "option",
+          "writable": true
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "buyerBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "buyerAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writerBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "writerAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "buybackAndBurn",
+      "discriminator": [
+        121,
+        156,
+        154,
+        165,
+        194,
+        86,
+        180,
+        130
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "vaultA"
+          ]
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenBMint",
+          "writable": true
+        },
+        {
+          "name": "buybackTokenAccount",
+          "docs": [
+            "Receives the bought token B for burning, owned by vault A so it can",
+            "sign both sides of the swap"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenBMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        },
+        {
+          "name": "venueProgram"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "minOut",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "cancelOtcOffer",
+      "discriminator": [
+        215,
+        132,
+        175,
+        3,
+        8,
+        133,
+        221,
+        171
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "offer"
+          ]
+        },
+        {
+          "name": "offer",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  116,
+                  99
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "offer.offer_id",
+                "account": "otcOffer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "giveMint"
+        },
+        {
+          "name": "escrowTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "offer"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "giveMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userGiveAccount",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelRfq",
+      "discriminator": [
+        65,
+        47,
+        228,
+        229,
+        50,
+        93,
+        212,
+        236
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "rfq"
+          ]
+        },
+        {
+          "name": "rfq",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  102,
+                  113
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "rfq.rfq_id",
+                "account": "rfq"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelTrade",
+      "discriminator": [
+        124,
+        66,
+        91,
+        59,
+        175,
+        107,
+        208,
+        120
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "trade",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "trade.order_id",
+                "account": "trade"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimEmissions",
+      "discriminator": [
+        251,
+        131,
+        246,
+        29,
+        92,
+        210,
+        241,
+        19
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "schedule",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "schedule.reward_mint",
+                "account": "emissionsSchedule"
+              }
+            ]
+          }
+        },
+        {
+          "name": "emissionsAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  115,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "schedule"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent"
+        },
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "rewardVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "schedule"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "schedule.reward_mint",
+                "account": "emissionsSchedule"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userRewardAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimScoredReward",
+      "discriminator": [
+        178,
+        165,
+        120,
+        203,
+        230,
+        99,
+        101,
+        127
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "scoreRoot",
+            "vaultA"
+          ]
+        },
+        {
+          "name": "scoreRoot",
+          "writable": true
+        },
+        {
+          "name": "scoreClaim",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  99,
+                  111,
+                  114,
+                  101,
+                  95,
+                  99,
+                  108,
+                  97,
+                  105,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "scoreRoot"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "proof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "claimStakingFees",
+      "discriminator": [
+        41,
+        122,
+        96,
+        254,
+        10,
+        172,
+        37,
+        75
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "relations": [
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "schedule"
+        },
+        {
+          "name": "bond",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  110,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "schedule"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent"
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultB",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeAgent",
+      "discriminator": [
+        52,
+        185,
+        104,
+        145,
+        157,
+        30,
+        87,
+        237
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeBalance",
+      "discriminator": [
+        121,
+        14,
+        32,
+        119,
+        90,
+        158,
+        222,
+        250
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "balance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "balance.vault",
+                "account": "balance"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeFilledTrade",
+      "discriminator": [
+        114,
+        248,
+        134,
+        110,
+        54,
+        87,
+        161,
+        210
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "trade",
+          "writable": true
+        },
+        {
+          "name": "owner",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "collectExcessLamports",
+      "discriminator": [
+        28,
+        189,
+        19,
+        40,
+        164,
+        176,
+        118,
+        17
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "account",
+          "docs": [
+            "only lamports above its rent-exempt minimum are moved."
+          ],
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createBinaryMarket",
+      "discriminator": [
+        177,
+        184,
+        109,
+        222,
+        136,
+        20,
+        204,
+        161
+      ],
+      "accounts": [
+        {
+          "name": "binaryMarket",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  105,
+                  110,
+                  97,
+                  114,
+                  121,
+                  95,
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "collateralMint"
+              },
+              {
+                "kind": "arg",
+                "path": "marketId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "collateralMint"
+        },
+        {
+          "name": "yesMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  121,
+                  101,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "binaryMarket"
+              }
+            ]
+          }
+        },
+        {
+          "name": "noMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  111
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "binaryMarket"
+              }
+            ]
+          }
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "binaryMarket"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "collateralMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "marketId",
+          "type": "u64"
+        },
+        {
+          "name": "oracle",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "createCurriculum",
+      "discriminator": [
+        10,
+        183,
+        196,
+        47,
+        186,
+        255,
+        133,
+        105
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "curriculum",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  117,
+                  114,
+                  114,
+                  105,
+                  99,
+                  117,
+                  108,
+                  117,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "stages",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "curriculumStage"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "createEmissionsSchedule",
+      "discriminator": [
+        239,
+        128,
+        251,
+        38,
+        153,
+        123,
+        132,
+        252
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "schedule",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "rewardMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "rewardMint"
+        },
+        {
+          "name": "rewardVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "schedule"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "rewardMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        },
+        {
+          "name": "authorityRewardAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "rate",
+          "type": "u64"
+        },
+        {
+          "name": "start",
+          "type": "u64"
+        },
+        {
+          "name": "end",
+          "type": "u64"
+        },
+        {
+          "name": "makerShareBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "createGuild",
+      "discriminator": [
+        163,
+        27,
+        97,
+        167,
+        132,
+        198,
+        53,
+        168
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "guild",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  117,
+                  105,
+                  108,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "guildAgent",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  117,
+                  105,
+                  108,
+                  100,
+                  95,
+                  97,
+                  103,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "guild"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "manager",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "createIndex",
+      "discriminator": [
+        205,
+        71,
+        124,
+        117,
+        143,
+        136,
+        104,
+        192
+      ],
+      "accounts": [
+        {
+          "name": "index",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  110,
+                  100,
+                  101,
+                  120
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "indexId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "basketMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  110,
+                  100,
+                  101,
+                  120,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "index"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "indexId",
+          "type": "u64"
+        },
+        {
+          "name": "components",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "indexComponent"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "createOtcOffer",
+      "discriminator": [
+        246,
+        109,
+        114,
+        142,
+        182,
+        100,
+        122,
+        200
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market"
+        },
+        {
+          "name": "offer",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  116,
+                  99
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "arg",
+                "path": "offerId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "giveMint"
+        },
+        {
+          "name": "wantMint"
+        },
+        {
+          "name": "userGiveAccount",
+          "writable": true
+        },
+        {
+          "name": "escrowTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "offer"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "giveMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "offerId",
+          "type": "u64"
+        },
+        {
+          "name": "giveAmount",
+          "type": "u64"
+        },
+        {
+          "name": "wantAmount",
+          "type": "u64"
+        },
+        {
+          "name": "counterparty",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "createPriceCache",
+      "discriminator": [
+        1,
+        109,
+        185,
+        23,
+        249,
+        141,
+        169,
+        100
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "priceCache",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  105,
+                  99,
+                  101,
+                  95,
+                  99,
+                  97,
+                  99,
+                  104,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "feedId",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "createRfq",
+      "discriminator": [
+        127,
+        84,
+        185,
+        131,
+        235,
+        154,
+        189,
+        2
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market"
+        },
+        {
+          "name": "rfq",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  102,
+                  113
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "arg",
+                "path": "rfqId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "rfqId",
+          "type": "u64"
+        },
+        {
+          "name": "side",
+          "type": "u8"
+        },
+        {
+          "name": "size",
+          "type": "u64"
+        },
+        {
+          "name": "expirySlot",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "createStrategyVault",
+      "discriminator": [
+        237,
+        58,
+        86,
+        122,
+        184,
+        238,
+        129,
+        159
+      ],
+      "accounts": [
+        {
+          "name": "managerAgent"
+        },
+        {
+          "name": "market"
+        },
+        {
+          "name": "strategyVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  116,
+                  114,
+                  97,
+                  116,
+                  101,
+                  103,
+                  121,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "managerAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "strategyAgent",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  116,
+                  114,
+                  97,
+                  116,
+                  101,
+                  103,
+                  121,
+                  95,
+                  97,
+                  103,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "strategyVault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "shareMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  116,
+                  114,
+                  97,
+                  116,
+                  101,
+                  103,
+                  121,
+                  95,
+                  115,
+                  104,
+                  97,
+                  114,
+                  101,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "strategyVault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "managerShareAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "manager"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shareMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "manager",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "managementFeeBps",
+          "type": "u16"
+        },
+        {
+          "name": "performanceFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "createSubaccount",
+      "discriminator": [
+        232,
+        153,
+        10,
+        118,
+        101,
+        93,
+        197,
+        160
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  103,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent.registrant",
+                "account": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "subaccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  117,
+                  98,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "arg",
+                "path": "index"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "declareBankruptcy",
+      "discriminator": [
+        119,
+        207,
+        255,
+        228,
+        104,
+        178,
+        50,
+        132
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "position",
+            "vaultA"
+          ]
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "position.agent",
+                "account": "position"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "cranker",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "depositCollateral",
+      "discriminator": [
+        156,
+        131,
+        142,
+        116,
+        146,
+        247,
+        162,
+        120
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "vaultA"
+          ]
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "depositToGuild",
+      "discriminator": [
+        43,
+        25,
+        128,
+        141,
+        242,
+        114,
+        47,
+        156
+      ],
+      "accounts": [
+        {
+          "name": "guild",
+          "writable": true
+        },
+        {
+          "name": "guildMember",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  117,
+                  105,
+                  108,
+                  100,
+                  95,
+                  109,
+                  101,
+                  109,
+                  98,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "guild"
+              },
+              {
+                "kind": "account",
+                "path": "member"
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "relations": [
+            "guild",
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "guildAgent"
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultB",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "guildBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "guildAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "guildBalanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "guildAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "member",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "memberTokenAccountA",
+          "writable": true
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "depositToStrategy",
+      "discriminator": [
+        193,
+        61,
+        195,
+        221,
+        81,
+        161,
+        9,
+        170
+      ],
+      "accounts": [
+        {
+          "name": "strategyVault",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "relations": [
+            "strategyVault",
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "shareMint",
+          "writable": true,
+          "relations": [
+            "strategyVault"
+          ]
+        },
+        {
+          "name": "depositorShareAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "depositor"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shareMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "managerShareAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "strategy_vault.manager",
+                "account": "strategyVault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shareMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultB",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "strategyBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "strategy_vault.agent",
+                "account": "strategyVault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "strategyBalanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "strategy_vault.agent",
+                "account": "strategyVault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "depositor",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "depositorTokenAccountA",
+          "writable": true
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "depositTokens",
+      "discriminator": [
+        176,
+        83,
+        229,
+        18,
+        191,
+        143,
+        176,
+        150
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "vault"
+          ]
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenMint"
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "balance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "receipt",
+          "docs": [
+            "Required with a nonce, whose first use creates it"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  95,
+                  114,
+                  101,
+                  99,
+                  101,
+                  105,
+                  112,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "arg",
+                "path": "nonce"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "nonce",
+          "type": {
+            "option": "u64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "distributeFeesToStakers",
+      "discriminator": [
+        5,
+        114,
+        27,
+        131,
+        50,
+        250,
+        212,
+        115
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "schedule",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "schedule.reward_mint",
+                "account": "emissionsSchedule"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "shareBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "electGuildManager",
+      "discriminator": [
+        6,
+        241,
+        180,
+        185,
+        43,
+        218,
+        12,
+        200
+      ],
+      "accounts": [
+        {
+          "name": "guild",
+          "writable": true
+        },
+        {
+          "name": "guildAgent",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "guild"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "manager",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "executeHybridTrade",
+      "discriminator": [
+        44,
+        86,
+        130,
+        214,
+        66,
+        26,
+        239,
+        73
+      ],
+      "accounts": [
+        {
+          "name": "trade",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "trade.order_id",
+                "account": "trade"
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "trade",
+            "vaultIn",
+            "vaultOut"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userTokenAccountIn",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccountOut",
+          "writable": true
+        },
+        {
+          "name": "vaultIn",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "user_token_account_in.mint",
+                "account": "tokenAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountIn",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultIn"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user_token_account_in.mint",
+                "account": "tokenAccount"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultOut",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "user_token_account_out.mint",
+                "account": "tokenAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountOut",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultOut"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user_token_account_out.mint",
+                "account": "tokenAccount"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "venueProgram"
+        },
+        {
+          "name": "globalStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  108,
+                  111,
+                  98,
+                  97,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "maxSlippageBps",
+          "type": "u16"
+        },
+        {
+          "name": "bookOrders",
+          "type": "u8"
+        },
+        {
+          "name": "rewardRecords",
+          "type": "u8"
+        }
+      ],
+      "returns": "u64"
+    },
+    {
+      "name": "executeMirrorTrade",
+      "discriminator": [
+        78,
+        161,
+        0,
+        22,
+        106,
+        112,
+        119,
+        64
+      ],
+      "accounts": [
+        {
+          "name": "trade",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "trade.order_id",
+                "account": "trade"
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userTokenAccountIn",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccountOut",
+          "writable": true
+        },
+        {
+          "name": "venueProgram"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "maxSlippageBps",
+          "type": "u16"
+        }
+      ],
+      "returns": "u64"
+    },
+    {
+      "name": "executeTrade",
+      "discriminator": [
+        77,
+        16,
+        192,
+        135,
+        13,
+        0,
+        106,
+        97
+      ],
+      "accounts": [
+        {
+          "name": "trade",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "trade",
+            "vaultIn",
+            "vaultOut"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenInMint"
+        },
+        {
+          "name": "tokenOutMint"
+        },
+        {
+          "name": "userTokenAccountIn",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccountOut",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenOutMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultIn",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenInMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountIn",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultIn"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenInMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultOut",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenOutMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountOut",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultOut"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenOutMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "settlement",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  116,
+                  116,
+                  108,
+                  101,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "reward",
+          "docs": [
+            "The current epoch's, counting the fill toward the agent's reward"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "reward.epoch",
+                "account": "rewardRecord"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "instructions",
+          "docs": [
+            "market's sandwich guard is on"
+          ],
+          "optional": true,
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "globalStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  108,
+                  111,
+                  98,
+                  97,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [],
+      "returns": "u64"
+    },
+    {
+      "name": "executeTradeViaJupiter",
+      "discriminator": [
+        14,
+        229,
+        119,
+        24,
+        178,
+        29,
+        121,
+        226
+      ],
+      "accounts": [
+        {
+          "name": "trade",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "trade.order_id",
+                "account": "trade"
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userTokenAccountIn",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccountOut",
+          "writable": true
+        },
+        {
+          "name": "jupiterProgram",
+          "address": "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "routeData",
+          "type": "bytes"
+        },
+        {
+          "name": "maxSlippageBps",
+          "type": "u16"
+        }
+      ],
+      "returns": "u64"
+    },
+    {
+      "name": "exerciseOption",
+      "discriminator": [
+        231,
+        98,
+        131,
+        183,
+        245,
+        93,
+        122,
+        48
+      ],
+      "accounts": [
+        {
+          "name": "holderAgent"
+        },
+        {
+          "name": "writerAgent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "option",
+            "collateralVault"
+          ]
+        },
+        {
+          "name": "option",
+          "writable": true
+        },
+        {
+          "name": "collateralVault"
+        },
+        {
+          "name": "holderBalance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "collateralVault"
+              },
+              {
+                "kind": "account",
+                "path": "holderAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writerBalance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "collateralVault"
+              },
+              {
+                "kind": "account",
+                "path": "writerAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "writerOwner",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "expireOption",
+      "discriminator": [
+        38,
+        144,
+        3,
+        237,
+        125,
+        177,
+        141,
+        229
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "option",
+            "collateralVault"
+          ]
+        },
+        {
+          "name": "option",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "option.option_id",
+                "account": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "collateralVault"
+        },
+        {
+          "name": "collateralBalance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "collateralVault"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "flashLoan",
+      "discriminator": [
+        239,
+        246,
+        59,
+        224,
+        139,
+        20,
+        175,
+        14
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "relations": [
+            "vault"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenMint"
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "borrowerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "instructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "flashSwap",
+      "discriminator": [
+        44,
+        12,
+        29,
+        97,
+        110,
+        2,
+        61,
+        146
+      ],
+      "accounts": [
+        {
+          "name": "trade",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "trade",
+            "vaultIn",
+            "vaultOut"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenInMint"
+        },
+        {
+          "name": "tokenOutMint"
+        },
+        {
+          "name": "userTokenAccountOut",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenOutMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultIn",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenInMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountIn",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultIn"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenInMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultOut",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenOutMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountOut",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultOut"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenOutMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "callbackProgram",
+          "docs": [
+            "has been sent; it must not be this program."
+          ]
+        },
+        {
+          "name": "globalStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  108,
+                  111,
+                  98,
+                  97,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "callbackData",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "fundInsurance",
+      "discriminator": [
+        58,
+        216,
+        37,
+        162,
+        201,
+        50,
+        20,
+        66
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "vaultA"
+          ]
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "fundMirrorLiquidity",
+      "discriminator": [
+        84,
+        248,
+        166,
+        117,
+        65,
+        34,
+        100,
+        243
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "vault"
+          ]
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "vault.token",
+                "account": "vault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "indicateAuction",
+      "discriminator": [
+        125,
+        45,
+        183,
+        159,
+        4,
+        238,
+        63,
+        100
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": {
+          "name": "auctionIndication"
+        }
+      }
+    },
+    {
+      "name": "initializeConfig",
+      "discriminator": [
+        208,
+        127,
+        21,
+        1,
+        194,
+        190,
+        196,
+        70
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "globalStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  108,
+                  111,
+                  98,
+                  97,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program",
+          "address": "GAmxv5ze5Y1rvaJziz8nrnFtz4pBuF14YDvs8iqm7XkB"
+        },
+        {
+          "name": "programData"
+        },
+        {
+          "name": "upgradeAuthority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "admin",
+          "type": "pubkey"
+        },
+        {
+          "name": "makerFeeBps",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBps",
+          "type": "u16"
+        },
+        {
+          "name": "features",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "initializeMarket",
+      "discriminator": [
+        35,
+        35,
+        189,
+        193,
+        155,
+        48,
+        170,
+        203
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenAMint"
+              },
+              {
+                "kind": "account",
+                "path": "tokenBMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "marketListing",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116,
+                  105,
+                  110,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "config.market_count",
+                "account": "config"
+              }
+            ]
+          }
+        },
+        {
+          "name": "globalStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  108,
+                  111,
+                  98,
+                  97,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenAMint"
+              },
+              {
+                "kind": "account",
+                "path": "tokenBMint"
+              },
+              {
+                "kind": "account",
+                "path": "tokenAMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenAMint"
+              },
+              {
+                "kind": "account",
+                "path": "tokenBMint"
+              },
+              {
+                "kind": "account",
+                "path": "tokenBMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultATokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenAMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultBTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenBMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenAMint"
+        },
+        {
+          "name": "tokenBMint"
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "internalTransfer",
+      "discriminator": [
+        56,
+        217,
+        60,
+        137,
+        252,
+        221,
+        185,
+        114
+      ],
+      "accounts": [
+        {
+          "name": "fromAgent"
+        },
+        {
+          "name": "toAgent"
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "relations": [
+            "vault"
+          ]
+        },
+        {
+          "name": "vault",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "vault.token",
+                "account": "vault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceFrom",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "account",
+                "path": "fromAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceTo",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "account",
+                "path": "toAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "liquidatePosition",
+      "discriminator": [
+        187,
+        74,
+        229,
+        149,
+        102,
+        81,
+        221,
+        68
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "position",
+            "vaultA"
+          ]
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "position.agent",
+                "account": "position"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "liquidatorAgent"
+        },
+        {
+          "name": "liquidatorBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "liquidatorAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "liquidator",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "lockBond",
+      "discriminator": [
+        30,
+        142,
+        81,
+        179,
+        253,
+        64,
+        109,
+        162
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "schedule",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "schedule.reward_mint",
+                "account": "emissionsSchedule"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bond",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  110,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "schedule"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent"
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "rewardMint"
+        },
+        {
+          "name": "bondVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "bond"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "rewardMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userRewardAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "unlock",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "matchTrades",
+      "discriminator": [
+        61,
+        163,
+        131,
+        236,
+        8,
+        7,
+        90,
+        131
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "bid",
+            "ask",
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "bid",
+          "writable": true
+        },
+        {
+          "name": "ask",
+          "writable": true
+        },
+        {
+          "name": "bidAgent",
+          "writable": true
+        },
+        {
+          "name": "askAgent",
+          "writable": true
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultB",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "bidAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidBalanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "bidAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "askBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "askAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "askBalanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "askAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidCopyFeed",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  112,
+                  121,
+                  95,
+                  102,
+                  101,
+                  101,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "bidAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "askCopyFeed",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  112,
+                  121,
+                  95,
+                  102,
+                  101,
+                  101,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "askAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "leaderboard",
+          "docs": [
+            "The current epoch's, if it has been opened"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  108,
+                  101,
+                  97,
+                  100,
+                  101,
+                  114,
+                  98,
+                  111,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "leaderboard.epoch",
+                "account": "leaderboard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "snapshot",
+          "docs": [
+            "The current epoch's, if it has been taken, for its fill histograms"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  110,
+                  97,
+                  112,
+                  115,
+                  104,
+                  111,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "snapshot.epoch",
+                "account": "snapshot"
+              }
+            ]
+          }
+        },
+        {
+          "name": "randomness",
+          "docs": [
+            "on a pro-rata market that names one"
+          ],
+          "optional": true
+        },
+        {
+          "name": "emissions",
+          "docs": [
+            "Counts the fill toward the schedule's emissions, with both agents'",
+            "accounts under it"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "emissions.reward_mint",
+                "account": "emissionsSchedule"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidEmissions",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  115,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "bid_emissions.schedule",
+                "account": "emissionsAccount"
+              },
+              {
+                "kind": "account",
+                "path": "bidAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "askEmissions",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  115,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "ask_emissions.schedule",
+                "account": "emissionsAccount"
+              },
+              {
+                "kind": "account",
+                "path": "askAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidBond",
+          "docs": [
+            "Boost the agents' counted volume"
+          ],
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  110,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "bid_bond.schedule",
+                "account": "bond"
+              },
+              {
+                "kind": "account",
+                "path": "bidAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "askBond",
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  110,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "ask_bond.schedule",
+                "account": "bond"
+              },
+              {
+                "kind": "account",
+                "path": "askAgent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bidReward",
+          "docs": [
+            "The current epoch's, counting the fill toward the agents' rewards"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "bidAgent"
+              },
+              {
+                "kind": "account",
+                "path": "bid_reward.epoch",
+                "account": "rewardRecord"
+              }
+            ]
+          }
+        },
+        {
+          "name": "askReward",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "askAgent"
+              },
+              {
+                "kind": "account",
+                "path": "ask_reward.epoch",
+                "account": "rewardRecord"
+              }
+            ]
+          }
+        },
+        {
+          "name": "globalStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  108,
+                  111,
+                  98,
+                  97,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "cranker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "crankSequence",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "mintAgentNft",
+      "discriminator": [
+        57,
+        195,
+        246,
+        106,
+        161,
+        195,
+        27,
+        112
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  103,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent.registrant",
+                "account": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "nftMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  103,
+                  101,
+                  110,
+                  116,
+                  95,
+                  110,
+                  102,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userNftAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "nftMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "metadata",
+          "writable": true
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "address": "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "symbol",
+          "type": "string"
+        },
+        {
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "mintIndex",
+      "discriminator": [
+        105,
+        223,
+        80,
+        228,
+        4,
+        250,
+        161,
+        35
+      ],
+      "accounts": [
+        {
+          "name": "index"
+        },
+        {
+          "name": "basketMint",
+          "writable": true,
+          "relations": [
+            "index"
+          ]
+        },
+        {
+          "name": "userBasketAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "basketMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "mintOutcomeTokens",
+      "discriminator": [
+        27,
+        243,
+        237,
+        46,
+        2,
+        226,
+        144,
+        209
+      ],
+      "accounts": [
+        {
+          "name": "binaryMarket"
+        },
+        {
+          "name": "collateralMint",
+          "relations": [
+            "binaryMarket"
+          ]
+        },
+        {
+          "name": "yesMint",
+          "writable": true,
+          "relations": [
+            "binaryMarket"
+          ]
+        },
+        {
+          "name": "noMint",
+          "writable": true,
+          "relations": [
+            "binaryMarket"
+          ]
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "binaryMarket"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "collateralMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userCollateralAccount",
+          "writable": true
+        },
+        {
+          "name": "userYesAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "yesMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userNoAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "noMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "mirrorFill",
+      "discriminator": [
+        143,
+        122,
+        164,
+        172,
+        146,
+        169,
+        74,
+        51
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "subscription",
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "subscription",
+          "writable": true
+        },
+        {
+          "name": "copyFeed",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  112,
+                  121,
+                  95,
+                  102,
+                  101,
+                  101,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "subscription.leader",
+                "account": "subscription"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "followerBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "subscription.follower",
+                "account": "subscription"
+              }
+            ]
+          }
+        },
+        {
+          "name": "followerBalanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "subscription.follower",
+                "account": "subscription"
+              }
+            ]
+          }
+        },
+        {
+          "name": "leaderBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "subscription.leader",
+                "account": "subscription"
+              }
+            ]
+          }
+        },
+        {
+          "name": "followerReward",
+          "docs": [
+            "The current epoch's, counting the fill toward the follower's reward"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "subscription.follower",
+                "account": "subscription"
+              },
+              {
+                "kind": "account",
+                "path": "follower_reward.epoch",
+                "account": "rewardRecord"
+              }
+            ]
+          }
+        },
+        {
+          "name": "globalStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  108,
+                  111,
+                  98,
+                  97,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "cranker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "crankSequence",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "modifyPosition",
+      "discriminator": [
+        48,
+        249,
+        6,
+        139,
+        14,
+        95,
+        106,
+        88
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true,
+          "relations": [
+            "position"
+          ]
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "position",
+            "vaultA"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "reward",
+          "docs": [
+            "The current epoch's, counting the fill toward the agent's reward"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "reward.epoch",
+                "account": "rewardRecord"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "baseDelta",
+          "type": "i64"
+        },
+        {
+          "name": "flags",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "modifyTrade",
+      "discriminator": [
+        70,
+        121,
+        77,
+        103,
+        170,
+        204,
+        41,
+        132
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "trade"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "trade",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "trade.order_id",
+                "account": "trade"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "docs": [
+            "Vault A and the agent's balance in it, which pay the market's",
+            "congestion surcharge when a filled order is put back on the book"
+          ],
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "balance_a.vault",
+                "account": "balance"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amountIn",
+          "type": "u64"
+        },
+        {
+          "name": "amountOut",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "openEmissionsAccount",
+      "discriminator": [
+        152,
+        174,
+        91,
+        171,
+        72,
+        202,
+        38,
+        12
+      ],
+      "accounts": [
+        {
+          "name": "schedule"
+        },
+        {
+          "name": "agent"
+        },
+        {
+          "name": "emissionsAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  109,
+                  105,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110,
+                  115,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "schedule"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "openLeaderboard",
+      "discriminator": [
+        178,
+        74,
+        75,
+        172,
+        117,
+        29,
+        234,
+        240
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "leaderboard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  108,
+                  101,
+                  97,
+                  100,
+                  101,
+                  114,
+                  98,
+                  111,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "arg",
+                "path": "epoch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "epoch",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "openObservationFrame",
+      "discriminator": [
+        172,
+        174,
+        59,
+        165,
+        180,
+        30,
+        209,
+        69
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "observationFrame",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  98,
+                  115,
+                  101,
+                  114,
+                  118,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "interval",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "openRewardRecord",
+      "discriminator": [
+        178,
+        124,
+        41,
+        54,
+        214,
+        132,
+        58,
+        189
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "agent"
+        },
+        {
+          "name": "reward",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "arg",
+                "path": "epoch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "epoch",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "openSettlement",
+      "discriminator": [
+        144,
+        15,
+        89,
+        242,
+        217,
+        186,
+        154,
+        38
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market"
+        },
+        {
+          "name": "settlement",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  116,
+                  116,
+                  108,
+                  101,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "openbookCancelOrder",
+      "discriminator": [
+        176,
+        243,
+        51,
+        175,
+        176,
+        12,
+        76,
+        108
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  103,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent.registrant",
+                "account": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "openbookProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "openbookManageAccount",
+      "discriminator": [
+        254,
+        44,
+        203,
+        29,
+        255,
+        241,
+        99,
+        165
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  103,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent.registrant",
+                "account": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "openbookProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "openbookPlaceOrder",
+      "discriminator": [
+        212,
+        206,
+        65,
+        20,
+        19,
+        46,
+        211,
+        192
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  103,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent.registrant",
+                "account": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "openbookProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "placeAdversaryOrder",
+      "discriminator": [
+        91,
+        141,
+        171,
+        138,
+        109,
+        50,
+        62,
+        231
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "trade",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "arg",
+                "path": "orderId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "orderId",
+          "type": "u64"
+        },
+        {
+          "name": "params",
+          "type": {
+            "defined": {
+              "name": "orderParams"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "placeSignedTrade",
+      "discriminator": [
+        205,
+        142,
+        20,
+        211,
+        56,
+        240,
+        60,
+        228
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "trade",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "arg",
+                "path": "order.order_id"
+              }
+            ]
+          }
+        },
+        {
+          "name": "relayerDebt",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  108,
+                  97,
+                  121,
+                  101,
+                  114,
+                  95,
+                  100,
+                  101,
+                  98,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "docs": [
+            "Vault A and the agent's balance in it, which pay the market's",
+            "congestion surcharge when one applies"
+          ],
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "balance_a.vault",
+                "account": "balance"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "relayer",
+          "docs": [
+            "Pays for the transaction and the trade account on the owner's behalf"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "instructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "order",
+          "type": {
+            "defined": {
+              "name": "signedOrder"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "placeTrade",
+      "discriminator": [
+        102,
+        39,
+        166,
+        38,
+        98,
+        171,
+        190,
+        242
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "trade",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "arg",
+                "path": "orderId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "docs": [
+            "Vault A and the agent's balance in it, which pay the market's",
+            "congestion surcharge when one applies"
+          ],
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "balance_a.vault",
+                "account": "balance"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "orderId",
+          "type": "u64"
+        },
+        {
+          "name": "tradeType",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "displaySize",
+          "type": "u64"
+        },
+        {
+          "name": "flags",
+          "type": "u8"
+        },
+        {
+          "name": "tag",
+          "type": {
+            "array": [
+              "u8",
+              16
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "postPriceUpdate",
+      "discriminator": [
+        152,
+        172,
+        180,
+        83,
+        98,
+        71,
+        47,
+        78
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "relations": [
+            "priceCache"
+          ]
+        },
+        {
+          "name": "priceCache",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  105,
+                  99,
+                  101,
+                  95,
+                  99,
+                  97,
+                  99,
+                  104,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "priceUpdate",
+          "docs": [
+            "are checked by `read_price_update`"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "processRedemptions",
+      "discriminator": [
+        21,
+        222,
+        151,
+        9,
+        210,
+        255,
+        38,
+        136
+      ],
+      "accounts": [
+        {
+          "name": "strategyVault",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "strategyVault",
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "shareMint",
+          "writable": true,
+          "relations": [
+            "strategyVault"
+          ]
+        },
+        {
+          "name": "escrowShareAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "strategyVault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shareMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "managerShareAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "strategy_vault.manager",
+                "account": "strategyVault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shareMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "strategyBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "strategy_vault.agent",
+                "account": "strategyVault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "strategyBalanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "strategy_vault.agent",
+                "account": "strategyVault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        }
+      ],
+      "args": [
+        {
+          "name": "crankSequence",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "publishScoreRoot",
+      "discriminator": [
+        64,
+        199,
+        108,
+        129,
+        180,
+        8,
+        238,
+        122
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "relations": [
+            "vaultA"
+          ]
+        },
+        {
+          "name": "scoreRoot",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  99,
+                  111,
+                  114,
+                  101,
+                  95,
+                  114,
+                  111,
+                  111,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "arg",
+                "path": "epoch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        },
+        {
+          "name": "authorityTokenAccountA",
+          "writable": true
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "epoch",
+          "type": "u64"
+        },
+        {
+          "name": "root",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "totalRewards",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "quote",
+      "discriminator": [
+        149,
+        42,
+        109,
+        247,
+        134,
+        146,
+        213,
+        123
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        }
+      ],
+      "args": [
+        {
+          "name": "size",
+          "type": "u64"
+        },
+        {
+          "name": "side",
+          "type": "u8"
+        }
+      ],
+      "returns": {
+        "defined": {
+          "name": "bookQuote"
+        }
+      }
+    },
+    {
+      "name": "reconcileVault",
+      "discriminator": [
+        155,
+        3,
+        193,
+        142,
+        152,
+        183,
+        87,
+        196
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "relations": [
+            "vault"
+          ]
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "vault.token",
+                "account": "vault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccount",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vault.token",
+                "account": "vault"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "redeemIndex",
+      "discriminator": [
+        97,
+        229,
+        184,
+        138,
+        226,
+        23,
+        20,
+        217
+      ],
+      "accounts": [
+        {
+          "name": "index"
+        },
+        {
+          "name": "basketMint",
+          "writable": true,
+          "relations": [
+            "index"
+          ]
+        },
+        {
+          "name": "userBasketAccount",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "redeemOutcomeTokens",
+      "discriminator": [
+        170,
+        231,
+        168,
+        151,
+        230,
+        80,
+        206,
+        218
+      ],
+      "accounts": [
+        {
+          "name": "binaryMarket"
+        },
+        {
+          "name": "collateralMint",
+          "relations": [
+            "binaryMarket"
+          ]
+        },
+        {
+          "name": "yesMint",
+          "writable": true,
+          "relations": [
+            "binaryMarket"
+          ]
+        },
+        {
+          "name": "noMint",
+          "writable": true,
+          "relations": [
+            "binaryMarket"
+          ]
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "binaryMarket"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "collateralMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userCollateralAccount",
+          "writable": true
+        },
+        {
+          "name": "userYesAccount",
+          "writable": true
+        },
+        {
+          "name": "userNoAccount",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "registerAgent",
+      "discriminator": [
+        135,
+        157,
+        66,
+        195,
+        2,
+        113,
+        175,
+        30
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  103,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "agentListing",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  103,
+                  101,
+                  110,
+                  116,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116,
+                  105,
+                  110,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "config.agent_count",
+                "account": "config"
+              }
+            ]
+          }
+        },
+        {
+          "name": "globalStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  108,
+                  111,
+                  98,
+                  97,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "reimburseRelayer",
+      "discriminator": [
+        64,
+        245,
+        110,
+        83,
+        237,
+        199,
+        182,
+        206
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market"
+        },
+        {
+          "name": "relayerDebt",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  108,
+                  97,
+                  121,
+                  101,
+                  114,
+                  95,
+                  100,
+                  101,
+                  98,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "relayerFeeOracle"
+        },
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "userTokenAccountA",
+          "writable": true
+        },
+        {
+          "name": "relayerTokenAccountA",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "lamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "relockBond",
+      "discriminator": [
+        54,
+        55,
+        218,
+        249,
+        252,
+        150,
+        11,
+        174
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "schedule"
+        },
+        {
+          "name": "bond",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  110,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "schedule"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent"
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "unlock",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "repayFlashLoan",
+      "discriminator": [
+        119,
+        239,
+        18,
+        45,
+        194,
+        107,
+        31,
+        238
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "vault"
+          ]
+        },
+        {
+          "name": "tokenMint"
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "payerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "requestRedeem",
+      "discriminator": [
+        105,
+        49,
+        44,
+        38,
+        207,
+        241,
+        33,
+        173
+      ],
+      "accounts": [
+        {
+          "name": "strategyVault"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "strategyVault"
+          ]
+        },
+        {
+          "name": "shareMint",
+          "relations": [
+            "strategyVault"
+          ]
+        },
+        {
+          "name": "ownerShareAccount",
+          "writable": true
+        },
+        {
+          "name": "escrowShareAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "strategyVault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shareMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "redemptionRequest",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  100,
+                  101,
+                  109,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "strategyVault"
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shares",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "resetAgentForEpisode",
+      "discriminator": [
+        204,
+        205,
+        180,
+        48,
+        65,
+        113,
+        68,
+        15
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "owner",
+          "writable": true
+        },
+        {
+          "name": "trade",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "trade.order_id",
+                "account": "trade"
+              }
+            ]
+          }
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "adlQueue",
+          "docs": [
+            "from once `update_adl_rank` has created it"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  100,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenAMint",
+          "writable": true
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenBMint",
+          "writable": true
+        },
+        {
+          "name": "balanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "docs": [
+            "Mint authority of both tokens whenever capital has to be minted"
+          ],
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resolveMarket",
+      "discriminator": [
+        155,
+        23,
+        80,
+        173,
+        46,
+        74,
+        23,
+        239
+      ],
+      "accounts": [
+        {
+          "name": "binaryMarket",
+          "writable": true
+        },
+        {
+          "name": "oracle",
+          "signer": true,
+          "relations": [
+            "binaryMarket"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "outcome",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setActionEncoding",
+      "discriminator": [
+        110,
+        26,
+        219,
+        31,
+        62,
+        39,
+        248,
+        132
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "actionLot",
+          "type": "u64"
+        },
+        {
+          "name": "actionTick",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setAdversary",
+      "discriminator": [
+        1,
+        199,
+        50,
+        5,
+        2,
+        14,
+        125,
+        138
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "adversary",
+          "type": "pubkey"
+        },
+        {
+          "name": "maxSize",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setAuctionSlots",
+      "discriminator": [
+        55,
+        180,
+        175,
+        59,
+        180,
+        237,
+        246,
+        14
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "auctionSlots",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setCircuitBreaker",
+      "discriminator": [
+        135,
+        207,
+        46,
+        31,
+        152,
+        94,
+        123,
+        247
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "thresholdBps",
+          "type": "u32"
+        },
+        {
+          "name": "haltSlots",
+          "type": "u64"
+        },
+        {
+          "name": "reopenAuctionSlots",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setCongestionTiers",
+      "discriminator": [
+        245,
+        111,
+        77,
+        180,
+        144,
+        238,
+        237,
+        132
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "tiers",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "congestionTier"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setEmergencyMode",
+      "discriminator": [
+        79,
+        138,
+        190,
+        94,
+        0,
+        162,
+        205,
+        253
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setEmissionsBoost",
+      "discriminator": [
+        71,
+        5,
+        125,
+        31,
+        12,
+        250,
+        155,
+        114
+      ],
+      "accounts": [
+        {
+          "name": "schedule",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "schedule"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "minBond",
+          "type": "u64"
+        },
+        {
+          "name": "maxBoostBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setEpisodeCapital",
+      "discriminator": [
+        164,
+        23,
+        210,
+        40,
+        230,
+        234,
+        119,
+        244
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "capitalA",
+          "type": "u64"
+        },
+        {
+          "name": "capitalB",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setFeeTiers",
+      "discriminator": [
+        162,
+        35,
+        72,
+        250,
+        39,
+        183,
+        30,
+        7
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "tiers",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "feeTier"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFundingParams",
+      "discriminator": [
+        173,
+        22,
+        89,
+        194,
+        223,
+        9,
+        159,
+        30
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "maxFundingBps",
+          "type": "u16"
+        },
+        {
+          "name": "borrowRateBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setInventoryPenalty",
+      "discriminator": [
+        213,
+        126,
+        48,
+        154,
+        98,
+        26,
+        77,
+        64
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "inventoryPenaltyBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setJupiterRouting",
+      "discriminator": [
+        161,
+        128,
+        153,
+        171,
+        171,
+        186,
+        230,
+        73
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setMarketFees",
+      "discriminator": [
+        196,
+        84,
+        69,
+        197,
+        217,
+        141,
+        37,
+        236
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "makerFeeBps",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setMatchingPolicy",
+      "discriminator": [
+        57,
+        173,
+        44,
+        164,
+        197,
+        18,
+        194,
+        36
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "matchingPolicy",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setMaxOrderSize",
+      "discriminator": [
+        185,
+        54,
+        190,
+        177,
+        23,
+        24,
+        10,
+        11
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "maxOrderSize",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setMirrorPool",
+      "discriminator": [
+        230,
+        82,
+        59,
+        120,
+        159,
+        244,
+        139,
+        91
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "venue",
+          "type": "u8"
+        },
+        {
+          "name": "pool",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setOpenInterestLimits",
+      "discriminator": [
+        45,
+        201,
+        42,
+        87,
+        220,
+        139,
+        22,
+        237
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "maxLong",
+          "type": "u64"
+        },
+        {
+          "name": "maxShort",
+          "type": "u64"
+        },
+        {
+          "name": "skewScale",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setOracleConfig",
+      "discriminator": [
+        96,
+        171,
+        6,
+        98,
+        153,
+        183,
+        233,
+        31
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "primaryOracle",
+          "type": "pubkey"
+        },
+        {
+          "name": "fallbackOracle",
+          "type": "pubkey"
+        },
+        {
+          "name": "maxStalenessSlots",
+          "type": "u64"
+        },
+        {
+          "name": "maxConfidenceBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setPerformanceFee",
+      "discriminator": [
+        129,
+        89,
+        113,
+        1,
+        18,
+        68,
+        109,
+        22
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "performanceFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setPerpParams",
+      "discriminator": [
+        171,
+        201,
+        76,
+        214,
+        22,
+        136,
+        121,
+        88
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "initialMarginBps",
+          "type": "u16"
+        },
+        {
+          "name": "maintenanceMarginBps",
+          "type": "u16"
+        },
+        {
+          "name": "liquidationFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setRelayer",
+      "discriminator": [
+        23,
+        243,
+        33,
+        88,
+        110,
+        84,
+        196,
+        37
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "relayer",
+          "type": "pubkey"
+        },
+        {
+          "name": "feeOracle",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setSandwichGuard",
+      "discriminator": [
+        220,
+        5,
+        188,
+        229,
+        122,
+        192,
+        188,
+        253
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setSessionLimits",
+      "discriminator": [
+        152,
+        118,
+        86,
+        174,
+        247,
+        227,
+        243,
+        123
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "sessionKey",
+          "type": "pubkey"
+        },
+        {
+          "name": "spendLimits",
+          "type": {
+            "array": [
+              "u64",
+              2
+            ]
+          }
+        },
+        {
+          "name": "epochCaps",
+          "type": {
+            "array": [
+              "u64",
+              2
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "setSettlementMode",
+      "discriminator": [
+        110,
+        67,
+        26,
+        114,
+        146,
+        138,
+        33,
+        53
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "netSettlement",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setStpMode",
+      "discriminator": [
+        99,
+        143,
+        193,
+        157,
+        47,
+        159,
+        98,
+        171
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "stpMode",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setTieBreakRandomness",
+      "discriminator": [
+        231,
+        76,
+        162,
+        74,
+        250,
+        113,
+        216,
+        254
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "randomnessAccount",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setTimeBase",
+      "discriminator": [
+        122,
+        249,
+        139,
+        155,
+        67,
+        152,
+        98,
+        185
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "timeBase",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setTradingHours",
+      "discriminator": [
+        101,
+        219,
+        38,
+        5,
+        243,
+        22,
+        244,
+        84
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "open",
+          "type": "u64"
+        },
+        {
+          "name": "close",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setVolatilityFees",
+      "discriminator": [
+        198,
+        208,
+        6,
+        158,
+        105,
+        86,
+        206,
+        255
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "coefficient",
+          "type": "u16"
+        },
+        {
+          "name": "minTakerFeeBps",
+          "type": "u16"
+        },
+        {
+          "name": "maxTakerFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "settleEpoch",
+      "discriminator": [
+        148,
+        223,
+        178,
+        38,
+        201,
+        158,
+        167,
+        13
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "settlement",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  116,
+                  116,
+                  108,
+                  101,
+                  109,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userTokenAccountA",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccountB",
+          "writable": true
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settleReward",
+      "discriminator": [
+        199,
+        79,
+        145,
+        49,
+        213,
+        101,
+        70,
+        251
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "relations": [
+            "reward"
+          ]
+        },
+        {
+          "name": "reward",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "reward.agent",
+                "account": "rewardRecord"
+              },
+              {
+                "kind": "account",
+                "path": "reward.epoch",
+                "account": "rewardRecord"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "snapshotMarket",
+      "discriminator": [
+        125,
+        83,
+        202,
+        247,
+        144,
+        162,
+        236,
+        73
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "relations": [
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "snapshot",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  110,
+                  97,
+                  112,
+                  115,
+                  104,
+                  111,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "arg",
+                "path": "epoch"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "vaultB",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountB",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "epoch",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "startSession",
+      "discriminator": [
+        23,
+        227,
+        111,
+        142,
+        212,
+        230,
+        3,
+        175
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "sessionKey",
+          "type": "pubkey"
+        },
+        {
+          "name": "expirySlot",
+          "type": "u64"
+        },
+        {
+          "name": "scope",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "submitAction",
+      "discriminator": [
+        222,
+        59,
+        32,
+        151,
+        194,
+        137,
+        175,
+        150
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "trade",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  100,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "arg",
+                "path": "orderId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "docs": [
+            "Vault A and the agent's balance in it, which pay the market's",
+            "congestion surcharge when one applies"
+          ],
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "balance_a.vault",
+                "account": "balance"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "orderId",
+          "type": "u64"
+        },
+        {
+          "name": "action",
+          "type": {
+            "array": [
+              "u8",
+              3
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "submitActionsBatch",
+      "discriminator": [
+        174,
+        157,
+        56,
+        122,
+        86,
+        66,
+        74,
+        227
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "vaultA"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "docs": [
+            "Holds the balances that pay the market's congestion surcharge"
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "coordinator",
+          "docs": [
+            "Owner or session key of every agent acted for, paying for any trade",
+            "accounts the batch creates"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "actions",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "orderAction"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "submitQuote",
+      "discriminator": [
+        230,
+        121,
+        122,
+        202,
+        228,
+        6,
+        91,
+        181
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "rfq"
+          ]
+        },
+        {
+          "name": "rfq",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "quoteAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "subscribeToAgent",
+      "discriminator": [
+        230,
+        83,
+        119,
+        33,
+        147,
+        187,
+        177,
+        138
+      ],
+      "accounts": [
+        {
+          "name": "follower"
+        },
+        {
+          "name": "leader"
+        },
+        {
+          "name": "market"
+        },
+        {
+          "name": "copyFeed",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  112,
+                  121,
+                  95,
+                  102,
+                  101,
+                  101,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "leader"
+              }
+            ]
+          }
+        },
+        {
+          "name": "subscription",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  117,
+                  98,
+                  115,
+                  99,
+                  114,
+                  105,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "follower"
+              },
+              {
+                "kind": "account",
+                "path": "leader"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "fractionBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "sweepDonations",
+      "discriminator": [
+        222,
+        6,
+        23,
+        220,
+        100,
+        239,
+        135,
+        40
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "vault"
+          ]
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "vault.token",
+                "account": "vault"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccount",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vault.token",
+                "account": "vault"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "market"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "insuranceBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "transferAgent",
+      "discriminator": [
+        137,
+        80,
+        56,
+        147,
+        107,
+        99,
+        39,
+        192
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "holderNftAccount"
+        },
+        {
+          "name": "holder",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "transferAgentOwnership",
+      "discriminator": [
+        216,
+        78,
+        134,
+        193,
+        175,
+        186,
+        233,
+        25
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newOwner",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "transferCollateral",
+      "discriminator": [
+        157,
+        163,
+        63,
+        27,
+        242,
+        72,
+        251,
+        97
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "marketFrom"
+        },
+        {
+          "name": "marketTo"
+        },
+        {
+          "name": "tokenMint"
+        },
+        {
+          "name": "vaultFrom",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market_from.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market_from.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountFrom",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultFrom"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "balanceFrom",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultFrom"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTo",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market_to.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market_to.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountTo",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultTo"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "balanceTo",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultTo"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "uncrossAuction",
+      "discriminator": [
+        24,
+        85,
+        59,
+        254,
+        183,
+        177,
+        169,
+        126
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultB",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "globalStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  108,
+                  111,
+                  98,
+                  97,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "rewardRecords",
+          "type": "u8"
+        },
+        {
+          "name": "crankSequence",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "unsubscribeFromAgent",
+      "discriminator": [
+        77,
+        8,
+        226,
+        184,
+        14,
+        6,
+        175,
+        121
+      ],
+      "accounts": [
+        {
+          "name": "follower",
+          "relations": [
+            "subscription"
+          ]
+        },
+        {
+          "name": "subscription",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateAdlRank",
+      "discriminator": [
+        14,
+        47,
+        199,
+        46,
+        142,
+        189,
+        101,
+        231
+      ],
+      "accounts": [
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "position"
+          ]
+        },
+        {
+          "name": "adlQueue",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  100,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "position.agent",
+                "account": "position"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "cranker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateAgentNft",
+      "discriminator": [
+        62,
+        17,
+        90,
+        120,
+        155,
+        192,
+        176,
+        182
+      ],
+      "accounts": [
+        {
+          "name": "agent",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  103,
+                  101,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "agent.registrant",
+                "account": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "metadata",
+          "writable": true
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "address": "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+        }
+      ],
+      "args": [
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "symbol",
+          "type": "string"
+        },
+        {
+          "name": "uri",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "updateConfig",
+      "discriminator": [
+        29,
+        158,
+        252,
+        191,
+        10,
+        83,
+        219,
+        99
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "admin",
+          "type": "pubkey"
+        },
+        {
+          "name": "makerFeeBps",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBps",
+          "type": "u16"
+        },
+        {
+          "name": "features",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateObservationFrame",
+      "discriminator": [
+        100,
+        160,
+        167,
+        97,
+        238,
+        238,
+        221,
+        220
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "observationFrame",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  98,
+                  115,
+                  101,
+                  114,
+                  118,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawAllTokens",
+      "discriminator": [
+        168,
+        208,
+        244,
+        110,
+        148,
+        179,
+        49,
+        95
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "vault"
+          ]
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenMint"
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "balance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawBond",
+      "discriminator": [
+        222,
+        199,
+        141,
+        31,
+        188,
+        93,
+        155,
+        40
+      ],
+      "accounts": [
+        {
+          "name": "market"
+        },
+        {
+          "name": "schedule",
+          "writable": true
+        },
+        {
+          "name": "bond",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  110,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "schedule"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "agent"
+        },
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "bondVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "bond"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "schedule.reward_mint",
+                "account": "emissionsSchedule"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userRewardAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawCollateral",
+      "discriminator": [
+        115,
+        135,
+        168,
+        106,
+        139,
+        214,
+        138,
+        150
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "relations": [
+            "vaultA"
+          ]
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "balanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracle",
+          "optional": true
+        },
+        {
+          "name": "fallbackOracle",
+          "optional": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawShare",
+      "discriminator": [
+        169,
+        159,
+        52,
+        79,
+        59,
+        190,
+        159,
+        9
+      ],
+      "accounts": [
+        {
+          "name": "guild",
+          "writable": true
+        },
+        {
+          "name": "guildMember",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  103,
+                  117,
+                  105,
+                  108,
+                  100,
+                  95,
+                  109,
+                  101,
+                  109,
+                  98,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "guild"
+              },
+              {
+                "kind": "account",
+                "path": "member"
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "relations": [
+            "guild",
+            "vaultA",
+            "vaultB"
+          ]
+        },
+        {
+          "name": "vaultA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "guildBalanceA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultA"
+              },
+              {
+                "kind": "account",
+                "path": "guild.agent",
+                "account": "guild"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "guildBalanceB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vaultB"
+              },
+              {
+                "kind": "account",
+                "path": "guild.agent",
+                "account": "guild"
+              }
+            ]
+          }
+        },
+        {
+          "name": "member",
+          "signer": true
+        },
+        {
+          "name": "memberTokenAccountA",
+          "writable": true
+        },
+        {
+          "name": "memberTokenAccountB",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        }
+      ],
+      "args": [
+        {
+          "name": "shares",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawTokens",
+      "discriminator": [
+        2,
+        4,
+        225,
+        61,
+        19,
+        182,
+        106,
+        170
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "vault"
+          ]
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenMint"
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "vault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.token_a",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "market.token_b",
+                "account": "market"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "balance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "vault"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "writeOption",
+      "discriminator": [
+        96,
+        144,
+        104,
+        51,
+        39,
+        132,
+        235,
+        38
+      ],
+      "accounts": [
+        {
+          "name": "agent"
+        },
+        {
+          "name": "market",
+          "relations": [
+            "collateralVault"
+          ]
+        },
+        {
+          "name": "option",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              },
+              {
+                "kind": "arg",
+                "path": "optionId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "collateralVault"
+        },
+        {
+          "name": "collateralBalance",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  108,
+                  97,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "collateralVault"
+              },
+              {
+                "kind": "account",
+                "path": "agent"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "optionId",
+          "type": "u64"
+        },
+        {
+          "name": "kind",
+          "type": "u8"
+        },
+        {
+          "name": "size",
+          "type": "u64"
+        },
+        {
+          "name": "strikeAmount",
+          "type": "u64"
+        },
+        {
+          "name": "premium",
+          "type": "u64"
+        },
+        {
+          "name": "expirySlot",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "adlQueue",
+      "discriminator": [
+        87,
+        9,
+        253,
+        119,
+        146,
+        1,
+        118,
+        136
+      ]
+    },
+    {
+      "name": "agent",
+      "discriminator": [
+        47,
+        166,
+        112,
+        147,
+        155,
+        197,
+        86,
+        7
+      ]
+    },
+    {
+      "name": "agentListing",
+      "discriminator": [
+        224,
+        156,
+        61,
+        213,
+        111,
+        100,
+        43,
+        56
+      ]
+    },
+    {
+      "name": "balance",
+      "discriminator": [
+        127,
+        71,
+        25,
+        157,
+        105,
+        157,
+        241,
+        182
+      ]
+    },
+    {
+      "name": "binaryMarket",
+      "discriminator": [
+        11,
+        39,
+        215,
+        198,
+        158,
+        79,
+        254,
+        146
+      ]
+    },
+    {
+      "name": "bond",
+      "discriminator": [
+        224,
+        128,
+        48,
+        251,
+        182,
+        246,
+        111,
+        196
+      ]
+    },
+    {
+      "name": "config",
+      "discriminator": [
+        155,
+        12,
+        170,
+        224,
+        30,
+        250,
+        204,
+        130
+      ]
+    },
+    {
+      "name": "copyFeed",
+      "discriminator": [
+        251,
+        154,
+        121,
+        18,
+        210,
+        201,
+        207,
+        58
+      ]
+    },
+    {
+      "name": "curriculum",
+      "discriminator": [
+        120,
+        207,
+        181,
+        250,
+        162,
+        55,
+        42,
+        13
+      ]
+    },
+    {
+      "name": "depositReceipt",
+      "discriminator": [
+        64,
+        175,
+        24,
+        183,
+        138,
+        109,
+        70,
+        78
+      ]
+    },
+    {
+      "name": "emissionsAccount",
+      "discriminator": [
+        89,
+        235,
+        45,
+        61,
+        98,
+        152,
+        175,
+        243
+      ]
+    },
+    {
+      "name": "emissionsSchedule",
+      "discriminator": [
+        79,
+        66,
+        190,
+        16,
+        237,
+        148,
+        93,
+        136
+      ]
+    },
+    {
+      "name": "globalStats",
+      "discriminator": [
+        119,
+        53,
+        78,
+        3,
+        254,
+        129,
+        78,
+        28
+      ]
+    },
+    {
+      "name": "guild",
+      "discriminator": [
+        74,
+        176,
+        57,
+        164,
+        195,
+        188,
+        156,
+        237
+      ]
+    },
+    {
+      "name": "guildMember",
+      "discriminator": [
+        255,
+        176,
+        230,
+        246,
+        44,
+        246,
+        114,
+        56
+      ]
+    },
+    {
+      "name": "indexBasket",
+      "discriminator": [
+        2,
+        252,
+        99,
+        46,
+        163,
+        86,
+        1,
+        206
+      ]
+    },
+    {
+      "name": "leaderboard",
+      "discriminator": [
+        247,
+        186,
+        238,
+        243,
+        194,
+        30,
+        9,
+        36
+      ]
+    },
+    {
+      "name": "market",
+      "discriminator": [
+        219,
+        190,
+        213,
+        55,
+        0,
+        227,
+        198,
+        154
+      ]
+    },
+    {
+      "name": "marketListing",
+      "discriminator": [
+        175,
+        123,
+        31,
+        97,
+        53,
+        211,
+        229,
+        16
+      ]
+    },
+    {
+      "name": "observationFrame",
+      "discriminator": [
+        39,
+        248,
+        52,
+        135,
+        34,
+        81,
+        117,
+        124
+      ]
+    },
+    {
+      "name": "optionContract",
+      "discriminator": [
+        196,
+        220,
+        72,
+        61,
+        245,
+        42,
+        68,
+        234
+      ]
+    },
+    {
+      "name": "otcOffer",
+      "discriminator": [
+        31,
+        34,
+        186,
+        78,
+        188,
+        55,
+        190,
+        46
+      ]
+    },
+    {
+      "name": "position",
+      "discriminator": [
+        170,
+        188,
+        143,
+        228,
+        122,
+        64,
+        247,
+        208
+      ]
+    },
+    {
+      "name": "priceCache",
+      "discriminator": [
+        198,
+        211,
+        186,
+        101,
+        228,
+        22,
+        101,
+        190
+      ]
+    },
+    {
+      "name": "redemptionRequest",
+      "discriminator": [
+        117,
+        157,
+        214,
+        214,
+        64,
+        160,
+        31,
+        58
+      ]
+    },
+    {
+      "name": "relayerDebt",
+      "discriminator": [
+        110,
+        32,
+        117,
+        98,
+        253,
+        36,
+        112,
+        16
+      ]
+    },
+    {
+      "name": "rewardRecord",
+      "discriminator": [
+        44,
+        129,
+        188,
+        244,
+        91,
+        0,
+        49,
+        222
+      ]
+    },
+    {
+      "name": "rfq",
+      "discriminator": [
+        106,
+        19,
+        109,
+        78,
+        169,
+        13,
+        234,
+        58
+      ]
+    },
+    {
+      "name": "scoreClaim",
+      "discriminator": [
+        31,
+        204,
+        91,
+        247,
+        181,
+        183,
+        150,
+        125
+      ]
+    },
+    {
+      "name": "scoreRoot",
+      "discriminator": [
+        150,
+        170,
+        70,
+        221,
+        11,
+        81,
+        98,
+        231
+      ]
+    },
+    {
+      "name": "settlement",
+      "discriminator": [
+        55,
+        11,
+        219,
+        33,
+        36,
+        136,
+        40,
+        182
+      ]
+    },
+    {
+      "name": "snapshot",
+      "discriminator": [
+        137,
+        213,
+        28,
+        133,
+        224,
+        161,
+        48,
+        108
+      ]
+    },
+    {
+      "name": "strategyVault",
+      "discriminator": [
+        159,
+        204,
+        238,
+        219,
+        38,
+        201,
+        136,
+        177
+      ]
+    },
+    {
+      "name": "subscription",
+      "discriminator": [
+        64,
+        7,
+        26,
+        135,
+        102,
+        132,
+        98,
+        33
+      ]
+    },
+    {
+      "name": "trade",
+      "discriminator": [
+        132,
+        139,
+        123,
+        31,
+        157,
+        196,
+        244,
+        190
+      ]
+    },
+    {
+      "name": "vault",
+      "discriminator": [
+        211,
+        8,
+        232,
+        43,
+        2,
+        152,
+        117,
+        119
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "auctionIndicated",
+      "discriminator": [
+        195,
+        26,
+        247,
+        116,
+        204,
+        89,
+        194,
+        15
+      ]
+    },
+    {
+      "name": "auctionUncrossed",
+      "discriminator": [
+        112,
+        234,
+        184,
+        53,
+        82,
+        161,
+        217,
+        51
+      ]
+    },
+    {
+      "name": "autoDeleveraged",
+      "discriminator": [
+        10,
+        14,
+        140,
+        79,
+        30,
+        115,
+        27,
+        9
+      ]
+    },
+    {
+      "name": "bankruptcyDeclared",
+      "discriminator": [
+        234,
+        71,
+        48,
+        138,
+        241,
+        217,
+        127,
+        72
+      ]
+    },
+    {
+      "name": "buybackBurned",
+      "discriminator": [
+        186,
+        84,
+        241,
+        202,
+        152,
+        185,
+        0,
+        115
+      ]
+    },
+    {
+      "name": "circuitBreakerTripped",
+      "discriminator": [
+        188,
+        9,
+        111,
+        118,
+        136,
+        206,
+        199,
+        65
+      ]
+    },
+    {
+      "name": "orderFilled",
+      "discriminator": [
+        120,
+        124,
+        109,
+        66,
+        249,
+        116,
+        174,
+        30
+      ]
+    },
+    {
+      "name": "orderUpdated",
+      "discriminator": [
+        172,
+        140,
+        210,
+        241,
+        108,
+        117,
+        122,
+        145
+      ]
+    },
+    {
+      "name": "positionLiquidated",
+      "discriminator": [
+        40,
+        107,
+        90,
+        214,
+        96,
+        30,
+        61,
+        128
+      ]
+    },
+    {
+      "name": "reconciliationReport",
+      "discriminator": [
+        114,
+        93,
+        160,
+        98,
+        3,
+        249,
+        96,
+        99
+      ]
+    },
+    {
+      "name": "tradeExecuted",
+      "discriminator": [
+        41,
+        110,
+        64,
+        129,
+        60,
+        79,
+        179,
+        80
+      ]
+    },
+    {
+      "name": "tradeRouted",
+      "discriminator": [
+        10,
+        44,
+        206,
+        241,
+        124,
+        249,
+        39,
+        242
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "unauthorized",
+      "msg": "Unauthorized access"
+    },
+    {
+      "code": 6001,
+      "name": "invalidVault",
+      "msg": "Invalid vault"
+    },
+    {
+      "code": 6002,
+      "name": "invalidTokenMint",
+      "msg": "Invalid token mint"
+    },
+    {
+      "code": 6003,
+      "name": "invalidTradeType",
+      "msg": "Invalid trade type"
+    },
+    {
+      "code": 6004,
+      "name": "invalidTokenAccounts",
+      "msg": "Invalid token accounts"
+    },
+    {
+      "code": 6005,
+      "name": "invalidAgentOwner",
+      "msg": "Invalid agent owner"
+    },
+    {
+      "code": 6006,
+      "name": "invalidTradeAmount",
+      "msg": "Invalid trade amount"
+    },
+    {
+      "code": 6007,
+      "name": "mathOverflow",
+      "msg": "Arithmetic overflow"
+    },
+    {
+      "code": 6008,
+      "name": "insufficientBalance",
+      "msg": "Insufficient tracked balance"
+    },
+    {
+      "code": 6009,
+      "name": "tradesDoNotCross",
+      "msg": "Trades do not cross"
+    },
+    {
+      "code": 6010,
+      "name": "invalidStpMode",
+      "msg": "Invalid self-trade prevention mode"
+    },
+    {
+      "code": 6011,
+      "name": "invalidTradeFlags",
+      "msg": "Invalid trade flags"
+    },
+    {
+      "code": 6012,
+      "name": "postOnlyWouldCross",
+      "msg": "Post-only order would cross"
+    },
+    {
+      "code": 6013,
+      "name": "reduceOnlyWouldIncrease",
+      "msg": "Reduce-only order would increase exposure"
+    },
+    {
+      "code": 6014,
+      "name": "invalidFeeSchedule",
+      "msg": "Invalid fee schedule"
+    },
+    {
+      "code": 6015,
+      "name": "settlementRequired",
+      "msg": "Settlement account required for net-settled market"
+    },
+    {
+      "code": 6016,
+      "name": "settlementPending",
+      "msg": "Previous epoch has not been settled"
+    },
+    {
+      "code": 6017,
+      "name": "epochNotEnded",
+      "msg": "Epoch is still in progress"
+    },
+    {
+      "code": 6018,
+      "name": "offerCounterpartyMismatch",
+      "msg": "Offer is reserved for another agent"
+    },
+    {
+      "code": 6019,
+      "name": "rfqExpired",
+      "msg": "Request for quote has expired"
+    },
+    {
+      "code": 6020,
+      "name": "rfqFull",
+      "msg": "Request for quote has no room for more quotes"
+    },
+    {
+      "code": 6021,
+      "name": "noQuotes",
+      "msg": "No quote available to accept"
+    },
+    {
+      "code": 6022,
+      "name": "invalidOptionKind",
+      "msg": "Invalid option kind"
+    },
+    {
+      "code": 6023,
+      "name": "optionExpired",
+      "msg": "Option has expired"
+    },
+    {
+      "code": 6024,
+      "name": "optionNotExpired",
+      "msg": "Option has not expired yet"
+    },
+    {
+      "code": 6025,
+      "name": "optionAlreadySold",
+      "msg": "Option has already been sold"
+    },
+    {
+      "code": 6026,
+      "name": "marketAlreadyResolved",
+      "msg": "Binary market is already resolved"
+    },
+    {
+      "code": 6027,
+      "name": "invalidOutcome",
+      "msg": "Invalid outcome"
+    },
+    {
+      "code": 6028,
+      "name": "invalidIndexComponents",
+      "msg": "Invalid index composition"
+    },
+    {
+      "code": 6029,
+      "name": "flashRepaymentShort",
+      "msg": "Flash swap was not repaid with its fee"
+    },
+    {
+      "code": 6030,
+      "name": "invalidCallbackProgram",
+      "msg": "Invalid callback program"
+    },
+    {
+      "code": 6031,
+      "name": "flashLoanOutstanding",
+      "msg": "Flash loan already outstanding"
+    },
+    {
+      "code": 6032,
+      "name": "flashLoanNotRepaid",
+      "msg": "Flash loan must be repaid later in the same transaction"
+    },
+    {
+      "code": 6033,
+      "name": "flashLoanCpi",
+      "msg": "Flash loans cannot be taken through CPI"
+    },
+    {
+      "code": 6034,
+      "name": "instructionsSysvarRequired",
+      "msg": "Instructions sysvar required by the market's sandwich guard"
+    },
+    {
+      "code": 6035,
+      "name": "sandwichDetected",
+      "msg": "Transaction touches the market in another instruction"
+    },
+    {
+      "code": 6036,
+      "name": "invalidOracleConfig",
+      "msg": "Invalid oracle configuration"
+    },
+    {
+      "code": 6037,
+      "name": "oracleAccountMismatch",
+      "msg": "Oracle account does not match the market's configuration"
+    },
+    {
+      "code": 6038,
+      "name": "invalidOracleAccount",
+      "msg": "Oracle account data is not a recognised price feed"
+    },
+    {
+      "code": 6039,
+      "name": "oraclePriceUnavailable",
+      "msg": "Oracle price is not currently trading"
+    },
+    {
+      "code": 6040,
+      "name": "oracleStale",
+      "msg": "Oracle price is stale"
+    },
+    {
+      "code": 6041,
+      "name": "oracleConfidenceTooWide",
+      "msg": "Oracle confidence interval too wide"
+    },
+    {
+      "code": 6042,
+      "name": "priceUpdateUnverified",
+      "msg": "Price update is not fully verified"
+    },
+    {
+      "code": 6043,
+      "name": "priceUpdateOutdated",
+      "msg": "Price update is older than the cached price"
+    },
+    {
+      "code": 6044,
+      "name": "externalRoutingDisabled",
+      "msg": "External routing is disabled for this market"
+    },
+    {
+      "code": 6045,
+      "name": "slippageExceeded",
+      "msg": "Swap output below the slippage limit"
+    },
+    {
+      "code": 6046,
+      "name": "invalidMirrorVenue",
+      "msg": "Invalid mirror venue"
+    },
+    {
+      "code": 6047,
+      "name": "invalidMirrorAccounts",
+      "msg": "Mirror swap accounts do not match the market's pool"
+    },
+    {
+      "code": 6048,
+      "name": "invalidOpenbookInstruction",
+      "msg": "OpenBook instruction not allowed for this agent"
+    },
+    {
+      "code": 6049,
+      "name": "invalidSession",
+      "msg": "Invalid session key, expiry or scope"
+    },
+    {
+      "code": 6050,
+      "name": "invalidSignedOrder",
+      "msg": "Signed order is expired, replayed or for another agent or market"
+    },
+    {
+      "code": 6051,
+      "name": "signatureVerificationRequired",
+      "msg": "Missing or mismatched Ed25519 signature verification"
+    },
+    {
+      "code": 6052,
+      "name": "relayerMismatch",
+      "msg": "Relayer debt is owed to a different relayer"
+    },
+    {
+      "code": 6053,
+      "name": "perpsDisabled",
+      "msg": "Perpetual positions are disabled for this market"
+    },
+    {
+      "code": 6054,
+      "name": "invalidMarginParams",
+      "msg": "Invalid margin parameters"
+    },
+    {
+      "code": 6055,
+      "name": "insufficientMargin",
+      "msg": "Position would fall below its initial margin"
+    },
+    {
+      "code": 6056,
+      "name": "notLiquidatable",
+      "msg": "Position is above its maintenance margin"
+    },
+    {
+      "code": 6057,
+      "name": "positionBankrupt",
+      "msg": "Position is bankrupt and must go through declare_bankruptcy"
+    },
+    {
+      "code": 6058,
+      "name": "notBankrupt",
+      "msg": "Position equity is not negative"
+    },
+    {
+      "code": 6059,
+      "name": "insuranceSufficient",
+      "msg": "Insurance fund can still cover the deficit"
+    },
+    {
+      "code": 6060,
+      "name": "notTopOfAdlQueue",
+      "msg": "Counterparty is not the top of the auto-deleveraging queue"
+    },
+    {
+      "code": 6061,
+      "name": "openInterestCapExceeded",
+      "msg": "Open interest cap exceeded"
+    },
+    {
+      "code": 6062,
+      "name": "invalidEpoch",
+      "msg": "Epoch is not the current epoch"
+    },
+    {
+      "code": 6063,
+      "name": "invalidMerkleProof",
+      "msg": "Invalid Merkle proof"
+    },
+    {
+      "code": 6064,
+      "name": "rewardsExhausted",
+      "msg": "Claims exceed the published rewards"
+    },
+    {
+      "code": 6065,
+      "name": "agentNftExists",
+      "msg": "Agent already has an identity NFT"
+    },
+    {
+      "code": 6066,
+      "name": "agentNftNotHeld",
+      "msg": "Agent identity NFT not held"
+    },
+    {
+      "code": 6067,
+      "name": "notPendingOwner",
+      "msg": "Signer is not the pending agent owner"
+    },
+    {
+      "code": 6068,
+      "name": "invalidShareAmount",
+      "msg": "Invalid share amount"
+    },
+    {
+      "code": 6069,
+      "name": "invalidSubscription",
+      "msg": "Invalid copy-trading subscription"
+    },
+    {
+      "code": 6070,
+      "name": "noFillToMirror",
+      "msg": "No leader fill left to mirror"
+    },
+    {
+      "code": 6071,
+      "name": "invalidStrategyFee",
+      "msg": "Invalid strategy vault fee"
+    },
+    {
+      "code": 6072,
+      "name": "invalidRedemption",
+      "msg": "Invalid redemption request"
+    },
+    {
+      "code": 6073,
+      "name": "vaultDeficit",
+      "msg": "Vault holds fewer tokens than it has accounted for"
+    },
+    {
+      "code": 6074,
+      "name": "notProgramAccount",
+      "msg": "Account is not owned by this program"
+    },
+    {
+      "code": 6075,
+      "name": "tradeNotFilled",
+      "msg": "Trade still has size left to fill"
+    },
+    {
+      "code": 6076,
+      "name": "balanceNotEmpty",
+      "msg": "Balance is not empty"
+    },
+    {
+      "code": 6077,
+      "name": "featureDisabled",
+      "msg": "Feature is disabled in this deployment"
+    },
+    {
+      "code": 6078,
+      "name": "oracleRequired",
+      "msg": "An external oracle price is required"
+    },
+    {
+      "code": 6079,
+      "name": "emergencyMode",
+      "msg": "Trading is halted while emergency mode is on"
+    },
+    {
+      "code": 6080,
+      "name": "invalidMarket",
+      "msg": "Account belongs to a different market"
+    },
+    {
+      "code": 6081,
+      "name": "invalidAgent",
+      "msg": "Account belongs to a different agent"
+    },
+    {
+      "code": 6082,
+      "name": "orderExpired",
+      "msg": "Signed order has expired"
+    },
+    {
+      "code": 6083,
+      "name": "nonceReused",
+      "msg": "Signed order nonce was already used"
+    },
+    {
+      "code": 6084,
+      "name": "invalidSlippage",
+      "msg": "Slippage tolerance above 100%"
+    },
+    {
+      "code": 6085,
+      "name": "notUpgradeAuthority",
+      "msg": "Signer is not the program's upgrade authority"
+    },
+    {
+      "code": 6086,
+      "name": "invariantViolated",
+      "msg": "Internal invariant violated"
+    },
+    {
+      "code": 6087,
+      "name": "crankOutOfSequence",
+      "msg": "Crank sequence doesn't match the market's"
+    },
+    {
+      "code": 6088,
+      "name": "invalidDepositReceipt",
+      "msg": "Deposit receipt must be passed exactly when a nonce is"
+    },
+    {
+      "code": 6089,
+      "name": "invalidTimeBase",
+      "msg": "Unknown time base"
+    },
+    {
+      "code": 6090,
+      "name": "timeBaseLocked",
+      "msg": "Time base can't change once the market has traded"
+    },
+    {
+      "code": 6091,
+      "name": "invalidFundingParams",
+      "msg": "Funding cap can't exceed 100% per epoch"
+    },
+    {
+      "code": 6092,
+      "name": "marketInFlight",
+      "msg": "Market is mid flash swap"
+    },
+    {
+      "code": 6093,
+      "name": "invalidEmissionsSchedule",
+      "msg": "Emissions need a positive rate over a non-empty window and a maker share of at most 100%"
+    },
+    {
+      "code": 6094,
+      "name": "emissionsAccountRequired",
+      "msg": "Counting a fill for emissions needs both agents' emissions accounts"
+    },
+    {
+      "code": 6095,
+      "name": "nothingToClaim",
+      "msg": "No emissions to claim"
+    },
+    {
+      "code": 6096,
+      "name": "invalidLockDuration",
+      "msg": "Unlock must be in the future, no earlier than the current one and within the longest lock"
+    },
+    {
+      "code": 6097,
+      "name": "bondLocked",
+      "msg": "Bond is still locked"
+    },
+    {
+      "code": 6098,
+      "name": "noBondsToPay",
+      "msg": "No bonds to pay fees to"
+    },
+    {
+      "code": 6099,
+      "name": "insufficientFees",
+      "msg": "Amount exceeds the market's accrued fees"
+    },
+    {
+      "code": 6100,
+      "name": "sessionSpendExceeded",
+      "msg": "Order exceeds the session's spending limit"
+    },
+    {
+      "code": 6101,
+      "name": "duplicateOrder",
+      "msg": "Order passed more than once"
+    },
+    {
+      "code": 6102,
+      "name": "congestionBalanceRequired",
+      "msg": "The market is congested; placing needs the agent's token A balance"
+    },
+    {
+      "code": 6103,
+      "name": "invalidMatchingPolicy",
+      "msg": "Invalid matching policy"
+    },
+    {
+      "code": 6104,
+      "name": "notAtMakerLevel",
+      "msg": "Order is not resting at the maker's price"
+    },
+    {
+      "code": 6105,
+      "name": "randomnessUnavailable",
+      "msg": "Tie-break randomness is missing, wrong or not yet fulfilled"
+    },
+    {
+      "code": 6106,
+      "name": "outOfTieBreakOrder",
+      "msg": "Maker is not next in its level's tie-break order"
+    },
+    {
+      "code": 6107,
+      "name": "invalidTradingHours",
+      "msg": "Trading hours must fall within an epoch"
+    },
+    {
+      "code": 6108,
+      "name": "marketClosed",
+      "msg": "Market is outside its trading hours"
+    },
+    {
+      "code": 6109,
+      "name": "invalidAuctionSlots",
+      "msg": "Auctions need trading hours long enough to hold both"
+    },
+    {
+      "code": 6110,
+      "name": "auctionInProgress",
+      "msg": "Market is in a call auction"
+    },
+    {
+      "code": 6111,
+      "name": "auctionNotDue",
+      "msg": "No call auction is running or due to uncross"
+    },
+    {
+      "code": 6112,
+      "name": "incompleteBook",
+      "msg": "Every resting order must be passed"
+    },
+    {
+      "code": 6113,
+      "name": "marketHalted",
+      "msg": "Market is halted by its circuit breaker"
+    },
+    {
+      "code": 6114,
+      "name": "invalidCircuitBreaker",
+      "msg": "A circuit breaker needs a reopening auction"
+    },
+    {
+      "code": 6115,
+      "name": "invalidObservationInterval",
+      "msg": "Observation interval must be at least one slot"
+    },
+    {
+      "code": 6116,
+      "name": "observationNotDue",
+      "msg": "Observation frame was updated too recently"
+    },
+    {
+      "code": 6117,
+      "name": "actionsDisabled",
+      "msg": "Market doesn't take compact actions"
+    },
+    {
+      "code": 6118,
+      "name": "actionPriceUnavailable",
+      "msg": "Compact actions need a last fill price to offset from"
+    },
+    {
+      "code": 6119,
+      "name": "invalidAction",
+      "msg": "Action doesn't encode a valid order"
+    },
+    {
+      "code": 6120,
+      "name": "invalidActionAccounts",
+      "msg": "Each action needs its agent, trade and vault A balance"
+    },
+    {
+      "code": 6121,
+      "name": "invalidInventoryPenalty",
+      "msg": "Inventory penalty can't exceed 100%"
+    },
+    {
+      "code": 6122,
+      "name": "rewardAlreadySettled",
+      "msg": "Reward record is already settled"
+    },
+    {
+      "code": 6123,
+      "name": "invalidCurriculum",
+      "msg": "Curriculum stages must be known changes in epoch order"
+    },
+    {
+      "code": 6124,
+      "name": "curriculumNotDue",
+      "msg": "No curriculum stage is due"
+    },
+    {
+      "code": 6125,
+      "name": "orderTooLarge",
+      "msg": "Order is larger than the market allows"
+    },
+    {
+      "code": 6126,
+      "name": "notAdversary",
+      "msg": "Agent isn't the market's adversary"
+    },
+    {
+      "code": 6127,
+      "name": "bookLevelsFull",
+      "msg": "Market has no free book level for the order's price"
+    },
+    {
+      "code": 6128,
+      "name": "incompleteLevel",
+      "msg": "Orders passed aren't the rest of the maker's level"
+    },
+    {
+      "code": 6129,
+      "name": "ordersResting",
+      "msg": "Market still has orders resting"
+    },
+    {
+      "code": 6130,
+      "name": "duplicateRewardRecord",
+      "msg": "Agent has more than one reward record passed"
+    },
+    {
+      "code": 6131,
+      "name": "optionOutOfTheMoney",
+      "msg": "Option is out of the money at the oracle price"
+    },
+    {
+      "code": 6132,
+      "name": "auctionUncrossing",
+      "msg": "A call auction is part way through uncrossing"
+    },
+    {
+      "code": 6133,
+      "name": "incompleteTrades",
+      "msg": "Not every trade the agent has open was passed"
+    },
+    {
+      "code": 6134,
+      "name": "delegatesFull",
+      "msg": "Every delegate slot holds an unexpired session"
+    },
+    {
+      "code": 6135,
+      "name": "depositNonceReused",
+      "msg": "Deposit nonce was already used on another vault"
+    }
+  ],
+  "types": [
+    {
+      "name": "adlEntry",
+      "docs": [
+        "A position queued for auto-deleveraging and its rank score."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "position",
+            "docs": [
+              "Default = empty slot"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "score",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "adlQueue",
+      "docs": [
+        "Most profitable positions on each side of a market by `adl_score`, best",
+        "first, kept fresh by `update_adl_rank` and consumed by `auto_deleverage`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "longs",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "adlEntry"
+                  }
+                },
+                8
+              ]
+            }
+          },
+          {
+            "name": "shorts",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "adlEntry"
+                  }
+                },
+                8
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "agent",
+      "docs": [
+        "A trading identity. Primary agents derive from their registrant's wallet;",
+        "sub-accounts from their parent."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "registrant",
+            "docs": [
+              "Wallet a primary agent's PDA derives from, kept when ownership moves"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "stpMode",
+            "docs": [
+              "0 = cancel newest, 1 = cancel oldest, 2 = decrement both"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "volumeEpoch",
+            "docs": [
+              "Most recent epoch written to `volume`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "volume",
+            "docs": [
+              "Token A traded, ring buffer indexed by epoch"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                30
+              ]
+            }
+          },
+          {
+            "name": "parent",
+            "docs": [
+              "Primary agent of a sub-account, default for a primary agent"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "subaccountIndex",
+            "type": "u16"
+          },
+          {
+            "name": "delegates",
+            "docs": [
+              "Hot keys allowed to trade but never withdraw"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "delegate"
+                  }
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "signedOrderNonce",
+            "docs": [
+              "Highest nonce of an off-chain signed order posted"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "nftMint",
+            "docs": [
+              "Identity NFT whose holder owns the agent, default = none"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingOwner",
+            "docs": [
+              "Wallet offered ownership, default = none"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "performanceFeeBps",
+            "docs": [
+              "Share of followers' realized copy profit the agent takes as a leader,",
+              "fixed for each follower when it subscribes"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "openTrades",
+            "docs": [
+              "Trade accounts the agent has open, on any market; an episode reset",
+              "has to be passed every one"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "agentListing",
+      "docs": [
+        "Entry `index` in the list of agents, created at registration and",
+        "enumerated like `MarketListing`s, over `0..config.agent_count`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "index",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "auctionIndicated",
+      "docs": [
+        "Where a running call auction would clear, published by `indicate_auction`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "phase",
+            "docs": [
+              "SESSION_* auction"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "Token A per token B, `PRICE_SCALE` fixed point; 0 when nothing crosses"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "volume",
+            "docs": [
+              "Token B that would trade"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "imbalance",
+            "docs": [
+              "Bids less asks crossing at `price`, in token B"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "auctionIndication",
+      "docs": [
+        "Where a call auction would uncross with the orders resting now, returned",
+        "by `indicate_auction`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "phase",
+            "docs": [
+              "SESSION_* auction the market is calling"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "Token A per token B, `PRICE_SCALE` fixed point; 0 when nothing crosses"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "volume",
+            "docs": [
+              "Token B that would trade"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "imbalance",
+            "docs": [
+              "Bids less asks crossing at `price`, in token B"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "auctionUncrossed",
+      "docs": [
+        "A call auction cleared at a single price."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "phase",
+            "docs": [
+              "SESSION_* auction"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "volume",
+            "docs": [
+              "Token B traded"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "fees",
+            "docs": [
+              "Token A taken in fees and rounding"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "autoDeleveraged",
+      "docs": [
+        "A bankrupt position closed against the top-ranked opposing position."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "bankruptAgent",
+            "type": "pubkey"
+          },
+          {
+            "name": "counterpartyAgent",
+            "type": "pubkey"
+          },
+          {
+            "name": "base",
+            "docs": [
+              "Size taken off the bankrupt position"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "quote",
+            "docs": [
+              "Token A the bankrupt position paid to close, negative if it received"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "balance",
+      "docs": [
+        "Tokens an agent holds in a vault, tracked by the program."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Tokens held in the vault on the agent's behalf"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Tokens set aside for orders in an auction being uncrossed, not part",
+              "of `amount` until their fills are settled"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bankruptcyDeclared",
+      "docs": [
+        "A position's deficit and how it was absorbed; `insurance_drawn`,",
+        "`socialized` and `bad_debt` always sum to `deficit`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "base",
+            "type": "i64"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "deficit",
+            "type": "u64"
+          },
+          {
+            "name": "insuranceDrawn",
+            "type": "u64"
+          },
+          {
+            "name": "socialized",
+            "type": "u64"
+          },
+          {
+            "name": "badDebt",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "binaryMarket",
+      "docs": [
+        "Event market paying one unit of collateral per winning YES or NO token."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "oracle",
+            "docs": [
+              "Signer allowed to resolve the outcome"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "marketId",
+            "type": "u64"
+          },
+          {
+            "name": "collateralMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "yesMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "noMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "outcome",
+            "docs": [
+              "0 = unresolved, 1 = yes, 2 = no"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bond",
+      "docs": [
+        "Reward tokens an agent's owner locked under an emissions schedule until",
+        "`unlock`, vote-escrow style: the agent's volume counts for more the",
+        "longer the lock has left to run, so the boost decays as unlock nears and",
+        "a re-lock restores it. Bonds also share the market fees distributed to",
+        "the schedule, pro rata to their amount."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "schedule",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "unlock",
+            "docs": [
+              "Market time the tokens can be withdrawn from"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeIndexA",
+            "docs": [
+              "The schedule's fee indices when `fees_owed_*` were last settled"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "feeIndexB",
+            "type": "u128"
+          },
+          {
+            "name": "feesOwedA",
+            "docs": [
+              "Market fees earned and not yet claimed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feesOwedB",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bookLevel",
+      "docs": [
+        "The orders resting at one price on one side of a pro-rata market."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeType",
+            "type": "u8"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "priceScale"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "orders",
+            "docs": [
+              "0 = free slot"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "displayed",
+            "docs": [
+              "Token B displayed across the orders"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bookQuote",
+      "docs": [
+        "What taking `size` token B from the book would fill, returned by `quote`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "size",
+            "docs": [
+              "Token B filled, less than asked for when the book runs out"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quoteAmount",
+            "docs": [
+              "Token A paid (taker buying) or received (taker selling), before fees"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "docs": [
+              "Base taker fee on `quote_amount`, in token A"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "averagePrice",
+            "docs": [
+              "`quote_amount` per `size`, `PRICE_SCALE` fixed point, rounded down"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "buybackBurned",
+      "docs": [
+        "Accrued token A fees spent on token B through the mirrored pool, and the",
+        "token B burned."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "spent",
+            "type": "u64"
+          },
+          {
+            "name": "burned",
+            "type": "u64"
+          },
+          {
+            "name": "feesRemaining",
+            "docs": [
+              "Token A fees left accrued afterwards"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "circuitBreakerTripped",
+      "docs": [
+        "A fill moved the price past the market's circuit breaker, halting it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "The fill's price and how far it moved from the one before"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "moveBps",
+            "type": "u64"
+          },
+          {
+            "name": "haltedUntil",
+            "type": "u64"
+          },
+          {
+            "name": "reopensAt",
+            "docs": [
+              "End of the reopening auction that follows the halt"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "config",
+      "docs": [
+        "Deployment-wide settings, one per program."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Creates markets and becomes their authority"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "makerFeeBps",
+            "docs": [
+              "Fees new markets start with"
+            ],
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "features",
+            "docs": [
+              "FEATURE_* bitmask"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "emergencyMode",
+            "docs": [
+              "Trading halted, withdrawals skip epoch waits"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "marketCount",
+            "docs": [
+              "Markets created so far, and the index of the next `MarketListing`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "agentCount",
+            "docs": [
+              "Agents registered so far, and the index of the next `AgentListing`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "congestionTier",
+      "docs": [
+        "Token A charged for each order placed while `min_open_orders` or more",
+        "are resting on the market, paid into its fee pool."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minOpenOrders",
+            "type": "u64"
+          },
+          {
+            "name": "surcharge",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "copyFeed",
+      "docs": [
+        "Most recent order book fills of a leader agent on a market, appended by",
+        "`match_trades` whenever the cranker passes the feed, for followers to copy."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "leader",
+            "type": "pubkey"
+          },
+          {
+            "name": "fillCount",
+            "docs": [
+              "Fills ever recorded; fill n lives at n % COPY_FEED_LEN"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "fills",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "copyFill"
+                  }
+                },
+                16
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "copyFill",
+      "docs": [
+        "A leader fill as recorded for followers to mirror."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeType",
+            "docs": [
+              "0 = bought token B, 1 = sold"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "quantity",
+            "docs": [
+              "Token B"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quoteAmount",
+            "docs": [
+              "Token A, before fees"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "curriculum",
+      "docs": [
+        "A market's schedule of parameter changes for staged training, applied by",
+        "`advance_curriculum` as the market's epochs start. Stages are in epoch",
+        "order; `next_stage` is the first not yet applied."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "stageCount",
+            "type": "u8"
+          },
+          {
+            "name": "stages",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "curriculumStage"
+                  }
+                },
+                16
+              ]
+            }
+          },
+          {
+            "name": "nextStage",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "curriculumStage",
+      "docs": [
+        "A change a curriculum makes to its market once the market reaches `epoch`,",
+        "CURRICULUM_* `kind` with its `value`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "kind",
+            "type": "u8"
+          },
+          {
+            "name": "value",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "delegate",
+      "docs": [
+        "A hot key an agent's owner delegated trading to, with its own allowance.",
+        "Limits and spend are indexed by trade type: token A committed to bids,",
+        "token B to asks."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "key",
+            "docs": [
+              "Default = free slot"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "expirySlot",
+            "docs": [
+              "Valid strictly before this slot"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "scope",
+            "docs": [
+              "SESSION_SCOPE_* bitmask"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "spendLimit",
+            "docs": [
+              "What the key may commit over its session, 0 = unbounded"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                2
+              ]
+            }
+          },
+          {
+            "name": "spent",
+            "type": {
+              "array": [
+                "u64",
+                2
+              ]
+            }
+          },
+          {
+            "name": "epochCap",
+            "docs": [
+              "What the key may commit per epoch, 0 = unbounded"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                2
+              ]
+            }
+          },
+          {
+            "name": "epoch",
+            "docs": [
+              "Epoch `epoch_spent` counts"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "epochSpent",
+            "type": {
+              "array": [
+                "u64",
+                2
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "depositReceipt",
+      "docs": [
+        "Marks deposit `nonce` of an agent as credited, so a retried",
+        "`deposit_tokens` carrying the same nonce credits nothing."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "emissionsAccount",
+      "docs": [
+        "An agent's volume under an emissions schedule in the last epoch it was",
+        "counted in, and what earlier epochs earned it. An epoch's share is lost",
+        "if the schedule's ring buffer moves past it unsettled, so agents claim",
+        "within EMISSIONS_EPOCHS epochs."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "schedule",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "makerVolume",
+            "type": "u64"
+          },
+          {
+            "name": "takerVolume",
+            "type": "u64"
+          },
+          {
+            "name": "unclaimed",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "emissionsEpoch",
+      "docs": [
+        "Token A volume an emissions schedule counted in one epoch."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "makerVolume",
+            "type": "u64"
+          },
+          {
+            "name": "takerVolume",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "emissionsSchedule",
+      "docs": [
+        "A reward token paid out over a window of market time at `rate` per slot",
+        "(or second, per the market's time base), for studying incentive-driven",
+        "trading. Each epoch's emission is split between the book's liquidity",
+        "providers, its makers, and its takers by `maker_share_bps`, then pro rata",
+        "to the volume each side's agents traded in the epoch, counting the",
+        "`match_trades` fills the cranker passed the schedule to."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "rewardMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "rate",
+            "type": "u64"
+          },
+          {
+            "name": "start",
+            "docs": [
+              "Market time emissions run over, start inclusive"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "end",
+            "type": "u64"
+          },
+          {
+            "name": "makerShareBps",
+            "docs": [
+              "Of each epoch's emission, rest to takers"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "epochs",
+            "docs": [
+              "Volume counted per epoch, ring buffer indexed by epoch"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "emissionsEpoch"
+                  }
+                },
+                30
+              ]
+            }
+          },
+          {
+            "name": "minBond",
+            "docs": [
+              "Smallest bond that earns a boost"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxBoostBps",
+            "docs": [
+              "Boost for a bond locked the longest, BPS_DENOMINATOR = 1x; below 1x",
+              "turns boosts off"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "bonded",
+            "docs": [
+              "Reward tokens locked in bonds"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeIndexA",
+            "docs": [
+              "Market fees paid to bonds per bonded token, ACCRUAL_INDEX_SCALE"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "feeIndexB",
+            "type": "u128"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeTier",
+      "docs": [
+        "Discounted fee rates for agents whose rolling volume reaches `min_volume`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minVolume",
+            "docs": [
+              "Token A traded over the last VOLUME_EPOCHS epochs"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeBps",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBps",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "globalStats",
+      "docs": [
+        "Deployment-wide running totals, one per program, created with the config",
+        "and kept current by the instructions that move them, so a dashboard gets",
+        "its headline numbers from one account. Amounts add up base units of each",
+        "market's own mints, so they only mean one thing across markets that share",
+        "them."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "markets",
+            "type": "u64"
+          },
+          {
+            "name": "agents",
+            "type": "u64"
+          },
+          {
+            "name": "trades",
+            "docs": [
+              "Fills the program settled: order book matches, vault fills and",
+              "copied fills"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "volume",
+            "docs": [
+              "Token A that changed hands in those fills"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "feesA",
+            "docs": [
+              "Fees charged on them, net of maker rebates"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "feesB",
+            "type": "u128"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "guild",
+      "docs": [
+        "Capital pooled by several wallets into one guild agent on a market. The",
+        "elected manager trades the agent as its session key; members own it",
+        "through shares and exit pro rata in kind with `withdraw_share`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "authority",
+            "docs": [
+              "Elects the manager"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "docs": [
+              "Guild agent PDA, owned by the guild so no wallet can withdraw"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "manager",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalShares",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "guildMember",
+      "docs": [
+        "A member's shares in a guild."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "guild",
+            "type": "pubkey"
+          },
+          {
+            "name": "member",
+            "type": "pubkey"
+          },
+          {
+            "name": "shares",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "indexBasket",
+      "docs": [
+        "Basket token backed by fixed amounts of several component tokens held in",
+        "ATAs owned by this account."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "indexId",
+            "type": "u64"
+          },
+          {
+            "name": "basketMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "componentCount",
+            "type": "u8"
+          },
+          {
+            "name": "components",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "indexComponent"
+                  }
+                },
+                8
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "indexComponent",
+      "docs": [
+        "A mint in an index basket and the amount backing each whole basket token."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "units",
+            "docs": [
+              "Component base units backing one whole basket token"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "leaderboard",
+      "docs": [
+        "A market epoch's top agents by volume, highest first, kept current by",
+        "`match_trades` so headline rankings need no indexer."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "entries",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "leaderboardEntry"
+                  }
+                },
+                32
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "leaderboardEntry",
+      "docs": [
+        "An agent on a leaderboard and the token A it traded in the epoch."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "agent",
+            "docs": [
+              "Default = empty slot"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "volume",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "market",
+      "docs": [
+        "A token A / token B pair with its vaults, fee schedule, perp parameters",
+        "and price state. Token A is the quote token throughout."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenA",
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenB",
+            "type": "pubkey"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "sequence",
+            "docs": [
+              "Next order sequence number, used for queue priority"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "crankSequence",
+            "docs": [
+              "Number of the next crank: `match_trades`, `uncross_auction`,",
+              "`mirror_fill` or `process_redemptions`, which the cranker must pass"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bookSequence",
+            "docs": [
+              "Order book updates emitted so far, the last `OrderUpdated::book_sequence`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeBps",
+            "docs": [
+              "Negative = rebate paid out of taker fees"
+            ],
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "feesAccrued",
+            "docs": [
+              "Token A fees held in vault A, not owned by any agent"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feesAccruedB",
+            "docs": [
+              "Token B fees held in vault B"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "epochSlots",
+            "docs": [
+              "Epoch length, in slots or seconds per `time_base`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timeBase",
+            "docs": [
+              "TIME_BASE_*, what the market's epochs and expiries are counted in"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "feeTierCount",
+            "type": "u8"
+          },
+          {
+            "name": "feeTiers",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "feeTier"
+                  }
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "netSettlement",
+            "docs": [
+              "Vault trades accrue to Settlement instead of transferring"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "matchingPolicy",
+            "docs": [
+              "MATCHING_*, how a taker is shared among makers at one price"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "randomnessAccount",
+            "docs": [
+              "VRF account ordering pro-rata makers at a level, default = cranker's order"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "levels",
+            "docs": [
+              "A pro-rata market's resting orders by price level, so a crank can be",
+              "held to passing a maker's whole level"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "bookLevel"
+                  }
+                },
+                32
+              ]
+            }
+          },
+          {
+            "name": "proRataRound",
+            "docs": [
+              "Taker being shared out among the makers of one pro-rata level"
+            ],
+            "type": {
+              "defined": {
+                "name": "proRataRound"
+              }
+            }
+          },
+          {
+            "name": "actionLot",
+            "docs": [
+              "Token B size of a compact action's size bucket 0, each bucket doubling",
+              "it; 0 = compact actions disabled"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "actionTick",
+            "docs": [
+              "Price step of a compact action's offset from the last fill, PRICE_SCALE"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "episodeCapitalA",
+            "docs": [
+              "Token A and token B `reset_agent_for_episode` leaves an agent holding"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "episodeCapitalB",
+            "type": "u64"
+          },
+          {
+            "name": "inventoryPenaltyBps",
+            "docs": [
+              "Charge against an agent's epoch reward for the inventory it held",
+              "over the epoch, bps of its time-weighted average value"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "maxOrderSize",
+            "docs": [
+              "Token B cap on an order's size, 0 = uncapped"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "adversary",
+            "docs": [
+              "Agent whose scripted flow `place_adversary_order` places, default =",
+              "none, and the cap on its orders' size there, 0 = uncapped"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "adversaryMaxSize",
+            "type": "u64"
+          },
+          {
+            "name": "tradingOpen",
+            "docs": [
+              "Offsets into each epoch, in market time, that trading opens at and",
+              "closes at; equal = always open, open after close = open across the",
+              "epoch boundary"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tradingClose",
+            "type": "u64"
+          },
+          {
+            "name": "auctionSlots",
+            "docs": [
+              "Length of the call auctions that open and close each session, in",
+              "market time; 0 = continuous trading throughout"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastUncross",
+            "docs": [
+              "End of the last call auction uncrossed, in market time"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "circuitBreakerBps",
+            "docs": [
+              "Fill-to-fill price move that halts the market, 0 = no circuit breaker"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "haltSlots",
+            "docs": [
+              "How long a halt lasts and the reopening auction after it runs, in",
+              "market time"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reopenAuctionSlots",
+            "type": "u64"
+          },
+          {
+            "name": "haltedUntil",
+            "docs": [
+              "When the last halt ends, 0 = never halted"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sandwichGuard",
+            "docs": [
+              "execute_trade must be the only instruction touching the market"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "jupiterRouting",
+            "docs": [
+              "Trades may be filled by external liquidity through Jupiter"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "mirrorVenue",
+            "docs": [
+              "adapters::MIRROR_*, external pool execute_mirror_trade routes to"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "mirrorPool",
+            "type": "pubkey"
+          },
+          {
+            "name": "relayer",
+            "docs": [
+              "Relayer whose signed-order postings agents owe lamports for"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "relayerFeeOracle",
+            "docs": [
+              "Feed quoting SOL in token A, for repaying the relayer"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "initialMarginBps",
+            "docs": [
+              "0 = perps disabled"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "maintenanceMarginBps",
+            "type": "u16"
+          },
+          {
+            "name": "liquidationFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "insuranceFund",
+            "docs": [
+              "Token A held in vault A to absorb bankrupt positions"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "badDebt",
+            "docs": [
+              "Bankrupt deficit neither insurance nor the winning side covered"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "openInterestLong",
+            "docs": [
+              "Token B base units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "openInterestShort",
+            "type": "u64"
+          },
+          {
+            "name": "maxOpenInterestLong",
+            "docs": [
+              "0 = uncapped"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxOpenInterestShort",
+            "type": "u64"
+          },
+          {
+            "name": "skewScale",
+            "docs": [
+              "Skew in token B at which fills pay a 100% premium, 0 = no impact"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "longLossIndex",
+            "docs": [
+              "Socialized loss per long base unit, LOSS_INDEX_SCALE"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "shortLossIndex",
+            "type": "u128"
+          },
+          {
+            "name": "longFundingIndex",
+            "docs": [
+              "Funding owed per long base unit, ACCRUAL_INDEX_SCALE, negative = received"
+            ],
+            "type": "i128"
+          },
+          {
+            "name": "shortFundingIndex",
+            "type": "i128"
+          },
+          {
+            "name": "fundingPerEpoch",
+            "docs": [
+              "Token A per long base unit per epoch, ACCRUAL_INDEX_SCALE, negative =",
+              "shorts pay; set from the premium at the last priced interaction"
+            ],
+            "type": "i128"
+          },
+          {
+            "name": "borrowIndex",
+            "docs": [
+              "Interest owed per token A borrowed, ACCRUAL_INDEX_SCALE"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "accruedAt",
+            "docs": [
+              "Market time the funding and borrow indices were last brought up to"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxFundingBps",
+            "docs": [
+              "Cap on the funding premium per epoch, 0 = no funding"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "borrowRateBps",
+            "docs": [
+              "Yearly interest on negative position collateral"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "decimalsA",
+            "type": "u8"
+          },
+          {
+            "name": "decimalsB",
+            "type": "u8"
+          },
+          {
+            "name": "primaryOracle",
+            "docs": [
+              "Pyth or Switchboard feed quoting token B in token A, default = none"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "fallbackOracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "oracleMaxStalenessSlots",
+            "type": "u64"
+          },
+          {
+            "name": "oracleMaxConfidenceBps",
+            "type": "u16"
+          },
+          {
+            "name": "lastPrice",
+            "docs": [
+              "Last fill, whole token A per whole token B scaled by PRICE_SCALE"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "twapPrice",
+            "docs": [
+              "Last-trade TWAP over TWAP_WINDOW_SLOTS, same scale"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "twapSlot",
+            "docs": [
+              "Slot of the last fill folded into the TWAP"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "volatilityBps",
+            "docs": [
+              "EWMA of fill-to-fill price moves over VOLATILITY_WINDOW_FILLS"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "volatilityFeeCoefficient",
+            "docs": [
+              "Extra taker bps per 100% volatility, BPS_DENOMINATOR scale, 0 = flat fees"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "minTakerFeeBps",
+            "docs": [
+              "Bounds on the volatility-scaled taker rate"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "maxTakerFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "openOrders",
+            "docs": [
+              "Orders resting with something left to fill"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "congestionTierCount",
+            "docs": [
+              "Placement surcharges by open-order count, none = no surcharge"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "congestionTiers",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "congestionTier"
+                  }
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "inFlight",
+            "docs": [
+              "A flash swap's callback is running; every instruction that writes the",
+              "market or its vaults refuses it until the swap settles"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "uncross",
+            "docs": [
+              "Call auction part way through `uncross_auction`; the book is frozen",
+              "until it finishes"
+            ],
+            "type": {
+              "defined": {
+                "name": "uncross"
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "marketListing",
+      "docs": [
+        "Entry `index` in the list of markets, created with the market. Clients",
+        "enumerate markets by deriving listings `0..config.market_count`, in pages",
+        "of any size."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "index",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "observationFrame",
+      "docs": [
+        "A market's fixed-size feature vector for agents to observe, rewritten by",
+        "`update_observation_frame` at most once every `interval` slots. Prices are",
+        "PRICE_SCALE per whole token like `Market::last_price`, sizes token B;",
+        "zero-copy so readers can cast it in place."
+      ],
+      "serialization": "bytemuck",
+      "repr": {
+        "kind": "c"
+      },
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "fundingPerEpoch",
+            "docs": [
+              "`Market::funding_per_epoch` as of the update"
+            ],
+            "type": "i128"
+          },
+          {
+            "name": "interval",
+            "docs": [
+              "Minimum slots between updates"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "slot",
+            "docs": [
+              "Slot of the last update"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sequence",
+            "docs": [
+              "Updates made, 0 = never updated"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "mid",
+            "docs": [
+              "Midpoint of the best bid and ask, the last fill price while a side is empty"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "spread",
+            "docs": [
+              "Best ask less best bid, 0 while a side is empty or the book is crossed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastPrice",
+            "type": "u64"
+          },
+          {
+            "name": "openInterestLong",
+            "type": "u64"
+          },
+          {
+            "name": "openInterestShort",
+            "type": "u64"
+          },
+          {
+            "name": "bidPrices",
+            "docs": [
+              "Best OBSERVATION_LEVELS price levels per side, best first; a level",
+              "with no size is empty"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "bidSizes",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "askPrices",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "askSizes",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "buildBookSequence",
+            "docs": [
+              "`Market::book_sequence` the update being built reads; any change to",
+              "the book starts it over"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "buildOrdersLeft",
+            "docs": [
+              "Resting orders the update being built has still to read"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "buildLastOrder",
+            "docs": [
+              "Last order the update being built read, pages passing orders in",
+              "ascending key order"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "buildBidPrices",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "buildBidSizes",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "buildAskPrices",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "buildAskSizes",
+            "type": {
+              "array": [
+                "u64",
+                5
+              ]
+            }
+          },
+          {
+            "name": "volatilityBps",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "building",
+            "docs": [
+              "An update is part way through being built"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                10
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "optionContract",
+      "docs": [
+        "Covered option on `size` of token B. Calls lock the writer's token B,",
+        "puts lock `strike_amount` of token A; exercise cash-settles, paying the",
+        "holder the intrinsic value at the oracle price out of that collateral."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "writer",
+            "docs": [
+              "Writer agent"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "holder",
+            "docs": [
+              "Buyer agent, default = not sold yet"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "optionId",
+            "type": "u64"
+          },
+          {
+            "name": "kind",
+            "docs": [
+              "0 = call, 1 = put"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "size",
+            "type": "u64"
+          },
+          {
+            "name": "strikeAmount",
+            "docs": [
+              "Token A for the full size"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "premium",
+            "docs": [
+              "Token A"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "expirySlot",
+            "docs": [
+              "In the market's time units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "orderAction",
+      "docs": [
+        "A compact action for the agent's trade `order_id`, see `ACTION_LEN`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "orderId",
+            "type": "u64"
+          },
+          {
+            "name": "action",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "orderFilled",
+      "docs": [
+        "A bid and ask matched on the book, at the resting order's price."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidAgent",
+            "type": "pubkey"
+          },
+          {
+            "name": "askAgent",
+            "type": "pubkey"
+          },
+          {
+            "name": "bidTag",
+            "docs": [
+              "Tags the two orders were placed with"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          },
+          {
+            "name": "askTag",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          },
+          {
+            "name": "bidIsMaker",
+            "type": "bool"
+          },
+          {
+            "name": "quantity",
+            "docs": [
+              "Token B"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quoteAmount",
+            "docs": [
+              "Token A, before fees"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bidFee",
+            "docs": [
+              "Token A, negative for a rebate"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "askFee",
+            "type": "i64"
+          },
+          {
+            "name": "tieBreakSeed",
+            "docs": [
+              "Seed the maker's level was ordered by, zero when none was drawn"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "orderParams",
+      "docs": [
+        "Everything needed to open a trade, shared by on-chain and signed orders."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeType",
+            "type": "u8"
+          },
+          {
+            "name": "amountIn",
+            "type": "u64"
+          },
+          {
+            "name": "amountOut",
+            "type": "u64"
+          },
+          {
+            "name": "displaySize",
+            "type": "u64"
+          },
+          {
+            "name": "flags",
+            "type": "u8"
+          },
+          {
+            "name": "tag",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "orderUpdated",
+      "docs": [
+        "An order's state on the book after it was placed, changed, filled or",
+        "taken off; zero amounts mean it left the book. `book_sequence` numbers a",
+        "market's updates from 1 without gaps, so a client mirroring the book can",
+        "tell when it missed one."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "bookSequence",
+            "type": "u64"
+          },
+          {
+            "name": "trade",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "tradeType",
+            "type": "u8"
+          },
+          {
+            "name": "amountIn",
+            "type": "u64"
+          },
+          {
+            "name": "amountOut",
+            "type": "u64"
+          },
+          {
+            "name": "sequence",
+            "docs": [
+              "Queue priority, lower rests ahead"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "displayed",
+            "docs": [
+              "Token B shown on the book, less than the size for an iceberg"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tag",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "otcOffer",
+      "docs": [
+        "Off-book block trade: the maker's `give_amount` sits in an escrow ATA owned",
+        "by this account until a taker pays `want_amount` or the maker cancels."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "maker",
+            "docs": [
+              "Maker agent"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "offerId",
+            "type": "u64"
+          },
+          {
+            "name": "giveMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "giveAmount",
+            "type": "u64"
+          },
+          {
+            "name": "wantMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "wantAmount",
+            "type": "u64"
+          },
+          {
+            "name": "counterparty",
+            "docs": [
+              "Agent allowed to accept, default = anyone"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "position",
+      "docs": [
+        "Perpetual exposure of an agent to a market's token B, margined in token A",
+        "held in vault A and marked at the oracle price."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "base",
+            "docs": [
+              "Token B base units, positive = long"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "quote",
+            "docs": [
+              "Token A base units: collateral less the cost of `base`"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "costBasis",
+            "docs": [
+              "Token A paid for the open `base`, negative when short"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "lossIndex",
+            "docs": [
+              "Side's loss index when socialized losses were last applied"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "fundingIndex",
+            "docs": [
+              "Side's funding index when funding was last applied"
+            ],
+            "type": "i128"
+          },
+          {
+            "name": "borrowIndex",
+            "docs": [
+              "Market's borrow index when interest was last charged"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionLiquidated",
+      "docs": [
+        "A position closed at the oracle price for falling below maintenance",
+        "margin."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "liquidator",
+            "type": "pubkey"
+          },
+          {
+            "name": "base",
+            "docs": [
+              "Size closed at the oracle price"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "liquidatorFee",
+            "type": "u64"
+          },
+          {
+            "name": "insuranceFee",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "priceCache",
+      "docs": [
+        "Latest verified Pyth pull-oracle price for a market, refreshed by anyone",
+        "through `post_price_update` and readable as a feed by `oracle::read_price`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "feedId",
+            "docs": [
+              "Pyth price feed id accepted for this market"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "price",
+            "docs": [
+              "PRICE_SCALE fixed point"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "conf",
+            "type": "u64"
+          },
+          {
+            "name": "publishTime",
+            "type": "i64"
+          },
+          {
+            "name": "postedSlot",
+            "docs": [
+              "Slot the update was posted to the receiver program"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "proRataRound",
+      "docs": [
+        "A taker part way through a pro-rata level. Each maker's share is taken",
+        "of the taker and level as they stood when the round began, whatever",
+        "turn it fills in; anything else touching the level or taker in between",
+        "starts a new round."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "round",
+            "docs": [
+              "Rounds so far; a maker filled in this one carries its number"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "taker",
+            "type": "pubkey"
+          },
+          {
+            "name": "level",
+            "docs": [
+              "Index into `Market::levels`"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "takerSize",
+            "docs": [
+              "Displayed sizes of the taker and the level when the round began"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "levelSize",
+            "type": "u64"
+          },
+          {
+            "name": "ordersLeft",
+            "docs": [
+              "Level makers yet to fill in the round and their displayed size"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "displayedLeft",
+            "type": "u64"
+          },
+          {
+            "name": "levelOrders",
+            "docs": [
+              "Level and taker as the round's last fill left them"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "levelDisplayed",
+            "type": "u64"
+          },
+          {
+            "name": "takerDisplayed",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "reconciliationReport",
+      "docs": [
+        "Outcome of reconcile_vault: `surplus` is what the token account held",
+        "above the vault's reserves and has now been added to them."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "actual",
+            "docs": [
+              "Token account balance"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserves",
+            "docs": [
+              "After taking in `surplus`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "surplus",
+            "type": "u64"
+          },
+          {
+            "name": "donations",
+            "docs": [
+              "Unswept total, including `surplus`"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "redemptionRequest",
+      "docs": [
+        "Shares queued for redemption, held in the strategy vault's escrow."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "strategyVault",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "shares",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "docs": [
+              "Redeemable once this epoch has ended"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "relayerDebt",
+      "docs": [
+        "Lamports a market's designated relayer has spent posting signed orders",
+        "for an agent, repaid in token A through `reimburse_relayer`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "relayer",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardRecord",
+      "docs": [
+        "An agent's reward for one market epoch, built up from the `match_trades`",
+        "fills cranked with the record passed, then split into its components by",
+        "`settle_reward` once the epoch is over."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "cashA",
+            "docs": [
+              "Token A the fills received less paid, before fees"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "inventoryB",
+            "docs": [
+              "Token B they bought less sold"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "fees",
+            "docs": [
+              "Token A fees paid, net of maker rebates"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "fills",
+            "type": "u64"
+          },
+          {
+            "name": "inventoryAt",
+            "docs": [
+              "Market time `inventory_b` last changed, and its absolute value summed",
+              "over market time up to then"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "inventoryTime",
+            "type": "u128"
+          },
+          {
+            "name": "settled",
+            "type": "bool"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "Last fill price the inventory was marked at, PRICE_SCALE"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "averageInventory",
+            "docs": [
+              "Mean absolute `inventory_b` over the epoch's market time"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "pnl",
+            "docs": [
+              "Cash plus the inventory's value at `price`"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "inventoryPenalty",
+            "docs": [
+              "`inventory_penalty_bps` of `average_inventory`'s value at `price`"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "reward",
+            "docs": [
+              "`pnl` less fees and the inventory penalty"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rfq",
+      "docs": [
+        "Request for quote on `size` of token B; market makers answer with the",
+        "amount of token A they would pay (taker selling) or charge (taker buying)."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "taker",
+            "docs": [
+              "Requesting agent"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "rfqId",
+            "type": "u64"
+          },
+          {
+            "name": "side",
+            "docs": [
+              "0 = taker buys token B, 1 = taker sells token B"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "size",
+            "type": "u64"
+          },
+          {
+            "name": "expirySlot",
+            "docs": [
+              "In the market's time units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quoteCount",
+            "type": "u8"
+          },
+          {
+            "name": "quotes",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "rfqQuote"
+                  }
+                },
+                8
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rfqQuote",
+      "docs": [
+        "One maker's response to a request for quote."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maker",
+            "docs": [
+              "Quoting agent"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "quoteAmount",
+            "docs": [
+              "Token A for the full size"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "scoreClaim",
+      "docs": [
+        "Marks an agent's reward under a `ScoreRoot` as claimed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "scoreRoot",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "scoreRoot",
+      "docs": [
+        "Merkle root over off-chain agent scores for one market epoch, with the",
+        "token A rewards the authority deposited to back it. Leaves are",
+        "`score_leaf(agent, amount)`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "root",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "totalRewards",
+            "docs": [
+              "Token A held in vault A for claims"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "claimed",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "settlement",
+      "docs": [
+        "Token movements owed between an agent and the vaults for one epoch of a",
+        "net-settled market; positive deltas are owed to the agent."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "deltaA",
+            "type": "i64"
+          },
+          {
+            "name": "deltaB",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "signedOrder",
+      "docs": [
+        "Order signed off-chain by an agent owner; the Borsh encoding is the",
+        "signed message."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "orderId",
+            "docs": [
+              "Id of the trade the order opens"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "params",
+            "type": {
+              "defined": {
+                "name": "orderParams"
+              }
+            }
+          },
+          {
+            "name": "nonce",
+            "docs": [
+              "Must exceed the agent's signed_order_nonce"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "expirySlot",
+            "docs": [
+              "In the market's time units"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "snapshot",
+      "docs": [
+        "A market's key aggregates as of the first `snapshot_market` call in an",
+        "epoch. Only the histograms change afterwards, counting the epoch's fills",
+        "cranked with the snapshot passed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          },
+          {
+            "name": "slot",
+            "docs": [
+              "Slot the snapshot was taken at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sequence",
+            "type": "u64"
+          },
+          {
+            "name": "reserveA",
+            "docs": [
+              "Vault token account balances"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserveB",
+            "type": "u64"
+          },
+          {
+            "name": "lastPrice",
+            "type": "u64"
+          },
+          {
+            "name": "twapPrice",
+            "type": "u64"
+          },
+          {
+            "name": "openInterestLong",
+            "type": "u64"
+          },
+          {
+            "name": "openInterestShort",
+            "type": "u64"
+          },
+          {
+            "name": "feesAccrued",
+            "type": "u64"
+          },
+          {
+            "name": "feesAccruedB",
+            "type": "u64"
+          },
+          {
+            "name": "sizeHistogram",
+            "docs": [
+              "Fills by token B size: bucket `i` counts sizes in `[10^i, 10^(i+1))`"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                20
+              ]
+            }
+          },
+          {
+            "name": "impactHistogram",
+            "docs": [
+              "Fills by move from the previous fill's price: bucket 0 counts no move",
+              "and the market's first fill, bucket `i` moves in `[2^(i-1), 2^i)` bps,",
+              "the last everything beyond"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                16
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "strategyVault",
+      "docs": [
+        "Third-party capital managed by one agent. Depositors hold SPL share",
+        "tokens of `share_mint`; the manager trades the strategy agent as its",
+        "session key and earns management and performance fees, crystallized by",
+        "the first deposit or redemption processing of each epoch. Redemptions",
+        "queue for an epoch and are paid in token A at the NAV per share fixed",
+        "when the next epoch's redemptions are first processed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "managerAgent",
+            "type": "pubkey"
+          },
+          {
+            "name": "manager",
+            "docs": [
+              "Wallet trading the strategy agent and holding fee shares"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "docs": [
+              "Strategy agent PDA, owned by the strategy vault"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "shareMint",
+            "docs": [
+              "Mint authority is the strategy vault"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "managementFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "performanceFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "highWaterMark",
+            "docs": [
+              "Highest NAV per share fees were charged at, PRICE_SCALE"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeEpoch",
+            "docs": [
+              "Epoch fees were last crystallized in"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeSlot",
+            "docs": [
+              "Market time fees were last crystallized at"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "settlementEpoch",
+            "docs": [
+              "Epoch the settlement below was fixed in"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "settlementSupply",
+            "docs": [
+              "Share supply and strategy holdings when the epoch began; redemptions",
+              "are paid their share of both tokens in kind"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "settlementA",
+            "type": "u64"
+          },
+          {
+            "name": "settlementB",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "subscription",
+      "docs": [
+        "A follower copying `fraction_bps` of a leader's fills, paying the leader",
+        "`performance_fee_bps` of the profit realized on copied positions."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "follower",
+            "type": "pubkey"
+          },
+          {
+            "name": "leader",
+            "type": "pubkey"
+          },
+          {
+            "name": "fractionBps",
+            "type": "u16"
+          },
+          {
+            "name": "performanceFeeBps",
+            "type": "u16"
+          },
+          {
+            "name": "nextFill",
+            "docs": [
+              "Next CopyFeed fill to mirror"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "baseHeld",
+            "docs": [
+              "Token B bought by copying and not yet sold"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "costBasis",
+            "docs": [
+              "Token A paid for `base_held`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "trade",
+      "docs": [
+        "An agent's resting order on a market, one per order id."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "tradeType",
+            "docs": [
+              "0 = buy, 1 = sell"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "amountIn",
+            "type": "u64"
+          },
+          {
+            "name": "amountOut",
+            "type": "u64"
+          },
+          {
+            "name": "sequence",
+            "docs": [
+              "Queue priority, lower rests ahead"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "displaySize",
+            "docs": [
+              "Iceberg tranche size, 0 = fully displayed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "visibleSize",
+            "docs": [
+              "Unfilled part of the current tranche"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "flags",
+            "docs": [
+              "TRADE_FLAG_* bitmask"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "tag",
+            "docs": [
+              "Placer's label for the order, echoed in its fills"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          },
+          {
+            "name": "orderId",
+            "docs": [
+              "Agent's id for the order, part of its address, so an agent rests one",
+              "order per id on each market and as many ids as it likes"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bookedLevel",
+            "docs": [
+              "Slot in `Market::levels` the order is counted in plus one, 0 = none,",
+              "and the displayed size it's counted with"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "bookedDisplayed",
+            "type": "u64"
+          },
+          {
+            "name": "proRataRound",
+            "docs": [
+              "`ProRataRound::round` the order last filled in as a maker"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "uncrossEnd",
+            "docs": [
+              "`Uncross::end` of the auction the order is tallied in, 0 once it has",
+              "had its fill turn, with the size it takes part with and the token its",
+              "agent set aside for it"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "uncrossSize",
+            "type": "u64"
+          },
+          {
+            "name": "uncrossReserved",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "tradeExecuted",
+      "docs": [
+        "A trade filled in full against the market's vaults."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "tag",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          },
+          {
+            "name": "tradeType",
+            "type": "u8"
+          },
+          {
+            "name": "amountIn",
+            "type": "u64"
+          },
+          {
+            "name": "amountOut",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "tradeRouted",
+      "docs": [
+        "How a hybrid trade split between the book and the mirrored pool."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "agent",
+            "type": "pubkey"
+          },
+          {
+            "name": "tag",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          },
+          {
+            "name": "tradeType",
+            "type": "u8"
+          },
+          {
+            "name": "bookQuantity",
+            "docs": [
+              "Token B filled against resting orders"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bookAmountIn",
+            "docs": [
+              "Paid and received across the book fills, fees included"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bookAmountOut",
+            "type": "u64"
+          },
+          {
+            "name": "poolAmountIn",
+            "type": "u64"
+          },
+          {
+            "name": "poolAmountOut",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "uncross",
+      "docs": [
+        "A call auction being uncrossed over as many `uncross_auction` cranks as",
+        "its book needs: first every resting order is tallied into `levels`, with",
+        "what its agent can pay for set aside, then each is passed again to fill",
+        "at the clearing price."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "end",
+            "docs": [
+              "End of the auction, in market time, 0 = none under way"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "phase",
+            "docs": [
+              "SESSION_* auction"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "stage",
+            "docs": [
+              "UNCROSS_TALLY, then UNCROSS_FILL"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "ordersLeft",
+            "docs": [
+              "Orders still to pass in the stage"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "orders",
+            "docs": [
+              "Orders tallied, each owed a fill turn"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "takerFeeBps",
+            "docs": [
+              "Base taker rate as the uncross began, charged on every fill"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "levelCount",
+            "type": "u8"
+          },
+          {
+            "name": "levels",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "uncrossLevel"
+                  }
+                },
+                32
+              ]
+            }
+          },
+          {
+            "name": "price",
+            "docs": [
+              "Clearing price and volume, once every order is tallied"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "volume",
+            "type": "u64"
+          },
+          {
+            "name": "bidEdge",
+            "type": {
+              "defined": {
+                "name": "uncrossEdge"
+              }
+            }
+          },
+          {
+            "name": "askEdge",
+            "type": {
+              "defined": {
+                "name": "uncrossEdge"
+              }
+            }
+          },
+          {
+            "name": "bought",
+            "docs": [
+              "Token B bought and sold so far, and token A paid, received and",
+              "taken in fees"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sold",
+            "type": "u64"
+          },
+          {
+            "name": "paid",
+            "type": "u64"
+          },
+          {
+            "name": "received",
+            "type": "u64"
+          },
+          {
+            "name": "fees",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "uncrossEdge",
+      "docs": [
+        "The level one side of an uncross stops filling in full at: better",
+        "prices fill in full, this one shares `fill` of its `size` pro rata and",
+        "worse ones sit out. `seen` is the size of its orders filled so far, so",
+        "the shares add up to `fill` whatever order they're passed in."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "size",
+            "type": "u64"
+          },
+          {
+            "name": "fill",
+            "type": "u64"
+          },
+          {
+            "name": "seen",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "uncrossLevel",
+      "docs": [
+        "Token B tallied at one limit price on one side of an uncross."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeType",
+            "type": "u8"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "priceScale"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "size",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "vault",
+      "docs": [
+        "Owner of a market's token account for one of its mints."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "market",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "flashLoanAmount",
+            "docs": [
+              "Outstanding within the current transaction"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserves",
+            "docs": [
+              "Tokens moved in through the program less those moved out"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "donations",
+            "docs": [
+              "Part of `reserves` reconcile_vault found sent in directly, not yet swept"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "mirrorLiquidity",
+            "docs": [
+              "Part of `reserves` owned by the vault itself, the side it takes in",
+              "mirrored fills and against perp positions"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    }
+  ]
+};
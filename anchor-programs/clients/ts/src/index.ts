@@ -0,0 +1,21 @@
+import { AnchorProvider, Program } from "@coral-xyz/anchor";
+import idl from "./generated/agent_market_sim.json";
+import { AgentMarketSim } from "./generated/agent_market_sim";
+
+export { AgentMarketSim };
+export const IDL = idl;
+
+export * from "./bootstrap";
+export * from "./events";
+export * from "./filters";
+export * from "./pda";
+export * from "./registry";
+export * from "./teardown";
+export * from "./token";
+
+/** Typed program client; accounts decode through `program.account`. */
+export function marketProgram(
+  provider: AnchorProvider
+): Program<AgentMarketSim> {
+  return new Program(idl as AgentMarketSim, provider);
+}
@@ -0,0 +1,399 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import idl from "./generated/agent_market_sim.json";
+
+/** The program these helpers derive addresses for. */
+export const PROGRAM_ID = new PublicKey(idl.address);
+
+type U64 = BN | number | bigint;
+
+function u64(value: U64): Buffer {
+  return new BN(value.toString()).toArrayLike(Buffer, "le", 8);
+}
+
+function find(
+  seeds: (Buffer | Uint8Array)[],
+  programId: PublicKey
+): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+const seed = (value: string) => Buffer.from(value);
+
+export function configPda(programId = PROGRAM_ID): PublicKey {
+  return find([seed("config")], programId);
+}
+
+export function globalStatsPda(programId = PROGRAM_ID): PublicKey {
+  return find([seed("global_stats")], programId);
+}
+
+export function marketPda(
+  tokenAMint: PublicKey,
+  tokenBMint: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("market"), tokenAMint.toBuffer(), tokenBMint.toBuffer()],
+    programId
+  );
+}
+
+/** Entry `index` of the market list, `0..config.marketCount`. */
+export function marketListingPda(
+  index: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("market_listing"), u64(index)], programId);
+}
+
+/** Vault holding `mint` for the market over `tokenAMint`/`tokenBMint`. */
+export function vaultPda(
+  tokenAMint: PublicKey,
+  tokenBMint: PublicKey,
+  mint: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [
+      seed("vault"),
+      tokenAMint.toBuffer(),
+      tokenBMint.toBuffer(),
+      mint.toBuffer(),
+    ],
+    programId
+  );
+}
+
+/** Agent registered by `owner`. */
+export function agentPda(owner: PublicKey, programId = PROGRAM_ID): PublicKey {
+  return find([seed("agent"), owner.toBuffer()], programId);
+}
+
+/** Entry `index` of the agent list, `0..config.agentCount`. */
+export function agentListingPda(
+  index: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("agent_listing"), u64(index)], programId);
+}
+
+export function agentNftPda(
+  agent: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("agent_nft"), agent.toBuffer()], programId);
+}
+
+export function subaccountPda(
+  agent: PublicKey,
+  index: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("subaccount"), agent.toBuffer(), u64(index)], programId);
+}
+
+/** An agent's balance in one vault. */
+export function balancePda(
+  vault: PublicKey,
+  agent: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("balance"), vault.toBuffer(), agent.toBuffer()], programId);
+}
+
+/** Marks an agent's deposit `nonce` as credited. */
+export function depositReceiptPda(
+  agent: PublicKey,
+  nonce: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("deposit_receipt"), agent.toBuffer(), u64(nonce)],
+    programId
+  );
+}
+
+/** The agent's order `orderId` on `market`. */
+export function tradePda(
+  agent: PublicKey,
+  market: PublicKey,
+  orderId: U64 = 0,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("trade"), agent.toBuffer(), market.toBuffer(), u64(orderId)],
+    programId
+  );
+}
+
+export function settlementPda(
+  market: PublicKey,
+  agent: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("settlement"), market.toBuffer(), agent.toBuffer()],
+    programId
+  );
+}
+
+export function positionPda(
+  market: PublicKey,
+  agent: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("position"), market.toBuffer(), agent.toBuffer()],
+    programId
+  );
+}
+
+export function adlQueuePda(
+  market: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("adl"), market.toBuffer()], programId);
+}
+
+export function priceCachePda(
+  market: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("price_cache"), market.toBuffer()], programId);
+}
+
+export function relayerDebtPda(
+  market: PublicKey,
+  agent: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("relayer_debt"), market.toBuffer(), agent.toBuffer()],
+    programId
+  );
+}
+
+export function snapshotPda(
+  market: PublicKey,
+  epoch: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("snapshot"), market.toBuffer(), u64(epoch)], programId);
+}
+
+export function leaderboardPda(
+  market: PublicKey,
+  epoch: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("leaderboard"), market.toBuffer(), u64(epoch)], programId);
+}
+
+export function emissionsSchedulePda(
+  market: PublicKey,
+  rewardMint: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("emissions"), market.toBuffer(), rewardMint.toBuffer()], programId);
+}
+
+export function emissionsAccountPda(
+  schedule: PublicKey,
+  agent: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("emissions_account"), schedule.toBuffer(), agent.toBuffer()], programId);
+}
+
+export function bondPda(
+  schedule: PublicKey,
+  agent: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("bond"), schedule.toBuffer(), agent.toBuffer()], programId);
+}
+
+export function scoreRootPda(
+  market: PublicKey,
+  epoch: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("score_root"), market.toBuffer(), u64(epoch)], programId);
+}
+
+export function scoreClaimPda(
+  scoreRoot: PublicKey,
+  agent: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("score_claim"), scoreRoot.toBuffer(), agent.toBuffer()],
+    programId
+  );
+}
+
+export function rfqPda(
+  market: PublicKey,
+  agent: PublicKey,
+  rfqId: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("rfq"), market.toBuffer(), agent.toBuffer(), u64(rfqId)],
+    programId
+  );
+}
+
+export function otcOfferPda(
+  market: PublicKey,
+  agent: PublicKey,
+  offerId: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("otc"), market.toBuffer(), agent.toBuffer(), u64(offerId)],
+    programId
+  );
+}
+
+export function optionPda(
+  market: PublicKey,
+  agent: PublicKey,
+  optionId: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("option"), market.toBuffer(), agent.toBuffer(), u64(optionId)],
+    programId
+  );
+}
+
+export function binaryMarketPda(
+  collateralMint: PublicKey,
+  marketId: U64,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("binary_market"), collateralMint.toBuffer(), u64(marketId)],
+    programId
+  );
+}
+
+export function yesMintPda(
+  binaryMarket: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("yes"), binaryMarket.toBuffer()], programId);
+}
+
+export function noMintPda(
+  binaryMarket: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("no"), binaryMarket.toBuffer()], programId);
+}
+
+export function indexPda(indexId: U64, programId = PROGRAM_ID): PublicKey {
+  return find([seed("index"), u64(indexId)], programId);
+}
+
+export function indexMintPda(
+  index: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("index_mint"), index.toBuffer()], programId);
+}
+
+export function guildPda(
+  market: PublicKey,
+  authority: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("guild"), market.toBuffer(), authority.toBuffer()],
+    programId
+  );
+}
+
+export function guildAgentPda(
+  guild: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("guild_agent"), guild.toBuffer()], programId);
+}
+
+export function guildMemberPda(
+  guild: PublicKey,
+  member: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("guild_member"), guild.toBuffer(), member.toBuffer()],
+    programId
+  );
+}
+
+export function copyFeedPda(
+  market: PublicKey,
+  leader: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("copy_feed"), market.toBuffer(), leader.toBuffer()],
+    programId
+  );
+}
+
+export function subscriptionPda(
+  market: PublicKey,
+  follower: PublicKey,
+  leader: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [
+      seed("subscription"),
+      market.toBuffer(),
+      follower.toBuffer(),
+      leader.toBuffer(),
+    ],
+    programId
+  );
+}
+
+export function strategyVaultPda(
+  market: PublicKey,
+  managerAgent: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("strategy_vault"), market.toBuffer(), managerAgent.toBuffer()],
+    programId
+  );
+}
+
+export function strategyAgentPda(
+  strategyVault: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("strategy_agent"), strategyVault.toBuffer()], programId);
+}
+
+export function strategySharesPda(
+  strategyVault: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find([seed("strategy_shares"), strategyVault.toBuffer()], programId);
+}
+
+export function redemptionPda(
+  strategyVault: PublicKey,
+  owner: PublicKey,
+  programId = PROGRAM_ID
+): PublicKey {
+  return find(
+    [seed("redemption"), strategyVault.toBuffer(), owner.toBuffer()],
+    programId
+  );
+}
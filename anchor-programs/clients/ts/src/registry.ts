@@ -0,0 +1,81 @@
+import { IdlAccounts, Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { AgentMarketSim } from "./generated/agent_market_sim";
+import { agentListingPda, configPda, marketListingPda } from "./pda";
+
+type Accounts = IdlAccounts<AgentMarketSim>;
+
+// Listings `start..start + limit` that exist out of `count`
+function listings(
+  count: number,
+  start: number,
+  limit: number,
+  pda: (index: number) => PublicKey
+): PublicKey[] {
+  const addresses: PublicKey[] = [];
+  for (let i = start; i < Math.min(count, start + limit); i++) {
+    addresses.push(pda(i));
+  }
+  return addresses;
+}
+
+/**
+ * Markets from listing `start` on, at most `limit` of them, read through
+ * their listings rather than a `getProgramAccounts` scan.
+ */
+export async function fetchMarkets(
+  program: Program<AgentMarketSim>,
+  start = 0,
+  limit = Infinity
+): Promise<{ address: PublicKey; market: Accounts["market"] }[]> {
+  const programId = program.programId;
+  const config = await program.account.config.fetchNullable(
+    configPda(programId)
+  );
+  const keys = listings(
+    config ? config.marketCount.toNumber() : 0,
+    start,
+    limit,
+    (i) => marketListingPda(i, programId)
+  );
+  if (keys.length === 0) {
+    return [];
+  }
+  const addresses = (
+    await program.account.marketListing.fetchMultiple(keys)
+  ).map((listing) => listing!.market);
+  const markets = await program.account.market.fetchMultiple(addresses);
+  return addresses.map((address, i) => ({ address, market: markets[i]! }));
+}
+
+/**
+ * Registered agents from listing `start` on, like `fetchMarkets`. Agents
+ * closed since are skipped.
+ */
+export async function fetchAgents(
+  program: Program<AgentMarketSim>,
+  start = 0,
+  limit = Infinity
+): Promise<{ address: PublicKey; agent: Accounts["agent"] }[]> {
+  const programId = program.programId;
+  const config = await program.account.config.fetchNullable(
+    configPda(programId)
+  );
+  const keys = listings(
+    config ? config.agentCount.toNumber() : 0,
+    start,
+    limit,
+    (i) => agentListingPda(i, programId)
+  );
+  if (keys.length === 0) {
+    return [];
+  }
+  const addresses = (
+    await program.account.agentListing.fetchMultiple(keys)
+  ).map((listing) => listing!.agent);
+  const agents = await program.account.agent.fetchMultiple(addresses);
+  return addresses.flatMap((address, i) => {
+    const agent = agents[i];
+    return agent ? [{ address, agent }] : [];
+  });
+}
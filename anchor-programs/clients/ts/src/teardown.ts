@@ -0,0 +1,222 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { AgentMarketSim } from "./generated/agent_market_sim";
+import {
+  balancePda,
+  configPda,
+  positionPda,
+  settlementPda,
+  subaccountPda,
+  vaultPda,
+} from "./pda";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  associatedTokenAddress,
+  createAssociatedTokenAccount,
+} from "./token";
+
+export interface TeardownOptions {
+  /** Markets the agent may hold orders, positions or balances on */
+  markets: PublicKey[];
+  /** Indices of sub-accounts to tear down along with the agent */
+  subaccounts?: number[];
+}
+
+// TRADE_FLAG_REDUCE_ONLY, so flattening can never flip the position
+const REDUCE_ONLY = 2;
+
+// Unset optional keys are stored as the default key
+function optional(key: PublicKey): PublicKey | null {
+  return key.equals(PublicKey.default) ? null : key;
+}
+
+/**
+ * Winds an agent down at the end of a run: on each market it cancels the
+ * agent's orders, flattens its perp position and withdraws the collateral,
+ * settles an ended net-settlement epoch, withdraws both balances to the
+ * owner's token accounts and closes them. Then it closes the sub-accounts
+ * and the agent. That's one transaction per market, two where a position
+ * was open, and one for the closes. The provider's wallet pays the fees.
+ */
+export async function teardownAgent(
+  program: Program<AgentMarketSim>,
+  owner: Keypair,
+  agent: PublicKey,
+  options: TeardownOptions
+): Promise<void> {
+  const programId = program.programId;
+  const provider = program.provider;
+  const send = (instructions: TransactionInstruction[]) =>
+    instructions.length
+      ? provider.sendAndConfirm!(new Transaction().add(...instructions), [
+          owner,
+        ])
+      : Promise.resolve("");
+  const user = owner.publicKey;
+  const config = configPda(programId);
+  const { emergencyMode } = await program.account.config.fetch(config);
+  const slot = await provider.connection.getSlot();
+  const unixTime =
+    (await provider.connection.getBlockTime(slot)) ??
+    Math.floor(Date.now() / 1000);
+
+  const agents = (options.subaccounts ?? []).map((index) =>
+    subaccountPda(agent, index, programId)
+  );
+  agents.push(agent);
+  for (const current of agents) {
+    for (const market of options.markets) {
+      const state = await program.account.market.fetch(market);
+      const { tokenA, tokenB } = state;
+      const oracles = {
+        oracle: optional(state.primaryOracle),
+        fallbackOracle: optional(state.fallbackOracle),
+      };
+      const vaultA = vaultPda(tokenA, tokenB, tokenA, programId);
+      const vaultB = vaultPda(tokenA, tokenB, tokenB, programId);
+      const exists = async (address: PublicKey) =>
+        (await provider.connection.getAccountInfo(address)) !== null;
+      let instructions: TransactionInstruction[] = [
+        createAssociatedTokenAccount(provider.publicKey!, user, tokenA),
+        createAssociatedTokenAccount(provider.publicKey!, user, tokenB),
+      ];
+
+      // Trade accounts start with the market and then the agent
+      const trades = await program.account.trade.all([
+        { memcmp: { offset: 8, bytes: market.toBase58() } },
+        { memcmp: { offset: 40, bytes: current.toBase58() } },
+      ]);
+      for (const { publicKey: trade } of trades) {
+        instructions.push(
+          await program.methods
+            .cancelTrade()
+            .accountsPartial({ agent: current, market, trade, user })
+            .instruction()
+        );
+      }
+
+      const settlement = settlementPda(market, current, programId);
+      const owed = await program.account.settlement.fetchNullable(settlement);
+      // Markets with time base 1 keep time in unix seconds instead of slots
+      const now = new BN(state.timeBase === 1 ? unixTime : slot);
+      const ended =
+        owed &&
+        (emergencyMode ||
+          owed.epoch.lt(now.div(BN.max(state.epochSlots, new BN(1)))));
+      if (owed && ended && !(owed.deltaA.isZero() && owed.deltaB.isZero())) {
+        instructions.push(
+          await program.methods
+            .settleEpoch()
+            .accountsPartial({
+              agent: current,
+              market,
+              config,
+              settlement,
+              user,
+              userTokenAccountA: associatedTokenAddress(user, tokenA),
+              userTokenAccountB: associatedTokenAddress(user, tokenB),
+              vaultA,
+              vaultTokenAccountA: associatedTokenAddress(vaultA, tokenA),
+              vaultB,
+              vaultTokenAccountB: associatedTokenAddress(vaultB, tokenB),
+              tokenProgram: TOKEN_PROGRAM_ID,
+              associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            })
+            .instruction()
+        );
+      }
+
+      const position = positionPda(market, current, programId);
+      let open = await program.account.position.fetchNullable(position);
+      if (open && !open.base.isZero()) {
+        instructions.push(
+          await program.methods
+            .modifyPosition(open.base.neg(), REDUCE_ONLY)
+            .accountsPartial({
+              agent: current,
+              market,
+              config,
+              position,
+              vaultA,
+              ...oracles,
+              user,
+            })
+            .instruction()
+        );
+        // The collateral left depends on the closing fill
+        await send(instructions);
+        instructions = [];
+        open = await program.account.position.fetch(position);
+      }
+      const balanceA = balancePda(vaultA, current, programId);
+      if (open && open.quote.gtn(0)) {
+        instructions.push(
+          await program.methods
+            .withdrawCollateral(open.quote)
+            .accountsPartial({
+              agent: current,
+              market,
+              position,
+              vaultA,
+              balanceA,
+              ...oracles,
+              user,
+              systemProgram: SystemProgram.programId,
+            })
+            .instruction()
+        );
+      }
+
+      for (const [mint, vault] of [
+        [tokenA, vaultA],
+        [tokenB, vaultB],
+      ]) {
+        const balance = balancePda(vault, current, programId);
+        if (!(await exists(balance))) {
+          continue;
+        }
+        instructions.push(
+          await program.methods
+            .withdrawAllTokens()
+            .accountsPartial({
+              agent: current,
+              market,
+              user,
+              tokenMint: mint,
+              userTokenAccount: associatedTokenAddress(user, mint),
+              vault,
+              vaultTokenAccount: associatedTokenAddress(vault, mint),
+              balance,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+            })
+            .instruction(),
+          await program.methods
+            .closeBalance()
+            .accountsPartial({ agent: current, balance, user })
+            .instruction()
+        );
+      }
+      await send(instructions);
+    }
+  }
+
+  await send(
+    await Promise.all(
+      agents.map((current) =>
+        program.methods
+          .closeAgent()
+          .accountsPartial({ agent: current, user })
+          .instruction()
+      )
+    )
+  );
+}
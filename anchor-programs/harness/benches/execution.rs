@@ -0,0 +1,175 @@
+//! Execution cost of the two venue types a simulated market can trade on:
+//! the program's order book, matched by `match_trades`, and a mirrored
+//! constant-product pool, swapped against by `execute_mirror_trade`.
+//!
+//! ```text
+//! cargo bench -p neuraltrader-harness --bench execution [-- <criterion options>]
+//! ```
+//!
+//! Client-side math is the quote an agent computes before trading: one
+//! constant-product formula against walking a book of a given depth. On-chain
+//! costs are per transaction in the harness runtime, timing only the
+//! transaction measured, with whatever it needs placed beforehand. The
+//! runtime runs the program natively and has no compute meter, so compute
+//! units are out of scope here: they need the program built for SBF and run
+//! by a validator. What's given next to the wall time is the program
+//! invocations each transaction makes, CPIs included, which is what bounds a
+//! large simulation.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use anchor_programs::PRICE_SCALE;
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, Criterion};
+use neuraltrader_harness::{amm, Receipt, Side, Simulation};
+
+const PRICE: u64 = PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+const RESERVES: u64 = 1_000_000_000_000;
+
+fn main() {
+    let mut criterion = Criterion::default().configure_from_args();
+    client_math(&mut criterion);
+    let invocations = on_chain(&mut criterion);
+    criterion.final_summary();
+
+    println!("\nprogram invocations per transaction");
+    for (name, invocations) in invocations {
+        println!("  {name:<24}{invocations:>8}");
+    }
+}
+
+fn client_math(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("client-side math");
+    group.bench_function("amm quote", |bencher| {
+        bencher.iter(|| amm::quote(black_box(RESERVES), RESERVES, QUANTITY, 30))
+    });
+    for depth in [1, 10, 100] {
+        // Equal levels, sized so a quote walks all of them
+        let levels: Vec<(u64, u64)> = (0..depth).map(|level| (PRICE + level, QUANTITY / depth)).collect();
+        group.bench_function(format!("book walk, depth {depth}"), |bencher| {
+            bencher.iter(|| walk(black_box(&levels), QUANTITY))
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks each on-chain transaction, returning the invocations it makes.
+fn on_chain(criterion: &mut Criterion) -> Vec<(&'static str, usize)> {
+    let quiet = ProgramLogs::silence();
+    let mut sim = Simulation::new();
+    let maker = sim.add_agent(RESERVES, RESERVES).expect("agent setup failed");
+    let taker = sim.add_agent(RESERVES, RESERVES).expect("agent setup failed");
+    let pool = amm::add_pool(&mut sim, RESERVES, RESERVES, 30).expect("pool setup failed");
+    // Swaps pay from the owner's wallet, not the agent's deposits
+    let (keys, wallet) = (sim.keys, sim.agents[taker]);
+    sim.mint_to(wallet.token_a_account, keys.token_a_mint, wallet.owner, RESERVES);
+    drop(quiet);
+
+    let mut group = criterion.benchmark_group("on-chain");
+    let replace = bench(&mut group, "cancel + place_trade", &mut sim, |sim| {
+        let instructions = sim.replace_order_instructions(maker, Some((Side::Ask, PRICE, QUANTITY)));
+        let owner = sim.agents[maker].owner;
+        timed(|| sim.send(&instructions, &[owner]))
+    });
+    let matched = bench(&mut group, "match_trades", &mut sim, |sim| {
+        place(sim, maker, Side::Ask, PRICE);
+        place(sim, taker, Side::Bid, PRICE);
+        timed(|| sim.match_orders(taker, maker))
+    });
+    let swapped = bench(&mut group, "execute_mirror_trade", &mut sim, |sim| {
+        place(sim, taker, Side::Bid, PRICE * 2);
+        timed(|| amm::mirror_trade(sim, taker, &pool, 10_000))
+    });
+    group.finish();
+    vec![replace, matched, swapped]
+}
+
+/// Benchmarks the transaction `send` times, returning its name and the
+/// invocations it made the last time.
+fn bench(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &'static str,
+    sim: &mut Simulation,
+    mut send: impl FnMut(&mut Simulation) -> Measured,
+) -> (&'static str, usize) {
+    let mut invocations = 0;
+    group.bench_function(name, |bencher| {
+        bencher.iter_custom(|iterations| {
+            let _quiet = ProgramLogs::silence();
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iterations {
+                let (took, receipt) = send(sim);
+                elapsed += took;
+                invocations = receipt.logs.iter().filter(|log| log.contains(" invoke [")).count();
+            }
+            elapsed
+        })
+    });
+    (name, invocations)
+}
+
+/// Wall time and receipt of one transaction.
+type Measured = (Duration, Receipt);
+
+fn timed<E: std::fmt::Debug>(send: impl FnOnce() -> Result<Receipt, E>) -> Measured {
+    let started = Instant::now();
+    let receipt = send().expect("benchmarked transaction failed");
+    (started.elapsed(), receipt)
+}
+
+/// Replaces the agent's order with one for QUANTITY at `price`, untimed.
+fn place(sim: &mut Simulation, agent: usize, side: Side, price: u64) {
+    let instructions = sim.replace_order_instructions(agent, Some((side, price, QUANTITY)));
+    let owner = sim.agents[agent].owner;
+    sim.send(&instructions, &[owner]).expect("order setup failed");
+}
+
+/// Token A paid for `quantity` token B from `levels`, best first.
+fn walk(levels: &[(u64, u64)], mut quantity: u64) -> u64 {
+    let mut paid = 0;
+    for &(price, size) in levels {
+        let take = size.min(quantity);
+        paid += (take as u128 * price as u128 / PRICE_SCALE as u128) as u64;
+        quantity -= take;
+        if quantity == 0 {
+            break;
+        }
+    }
+    paid
+}
+
+/// Stdout pointed at /dev/null while held. Off chain `msg!` prints straight
+/// to stdout, which would bury criterion's report, so each measurement runs
+/// with it silenced and criterion writes in between.
+struct ProgramLogs(i32);
+
+impl ProgramLogs {
+    fn silence() -> Self {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+
+        std::io::stdout().flush().expect("stdout not flushed");
+        let null = std::fs::File::create("/dev/null").expect("/dev/null not opened");
+        // SAFETY: only duplicates the process's standard descriptors
+        unsafe {
+            let saved = libc::dup(libc::STDOUT_FILENO);
+            assert!(saved >= 0, "failed to duplicate stdout");
+            libc::dup2(null.as_raw_fd(), libc::STDOUT_FILENO);
+            Self(saved)
+        }
+    }
+}
+
+impl Drop for ProgramLogs {
+    fn drop(&mut self) {
+        use std::io::Write;
+
+        let _ = std::io::stdout().flush();
+        // SAFETY: restores the descriptor `silence` saved
+        unsafe {
+            libc::dup2(self.0, libc::STDOUT_FILENO);
+            libc::close(self.0);
+        }
+    }
+}
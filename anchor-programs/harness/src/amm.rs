@@ -0,0 +1,326 @@
+//! A constant-product pool standing in for an Orca Whirlpool, so mirror
+//! markets can be simulated: `execute_mirror_trade` and
+//! `execute_hybrid_trade` route to it exactly as they would to a real
+//! Whirlpool. The pool swaps at `x * y = k` less its fee and ignores tick
+//! arrays, the oracle and the sqrt price limit. Its account keeps a real
+//! Whirlpool's bump, fee rate, sqrt price and first mint where a Whirlpool
+//! does, with the sqrt price following the reserves.
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_program;
+use anchor_programs::adapters::{MIRROR_WHIRLPOOL, WHIRLPOOL_PROGRAM_ID};
+use anchor_programs::{accounts, instruction};
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{Account, Failure, Receipt};
+use crate::sim::{config_address, global_stats_address, program_instruction, AgentKeys, Simulation};
+
+/// Swap data: discriminator, amount, other amount threshold, sqrt price
+/// limit, amount is input, a to b.
+const SWAP_DATA_LEN: usize = 8 + 8 + 8 + 16 + 1 + 1;
+
+// Offsets into a Whirlpool account of the fields the stand-in keeps
+const BUMP_OFFSET: usize = 40;
+const FEE_RATE_OFFSET: usize = 45;
+const SQRT_PRICE_OFFSET: usize = 65;
+const MINT_A_OFFSET: usize = 101;
+const POOL_LEN: usize = MINT_A_OFFSET + 32;
+
+/// Addresses and fee of a pool made by `add_pool`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Pool {
+    pub whirlpool: Pubkey,
+    /// Token accounts owned by the pool
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub fee_bps: u16,
+}
+
+/// Output of swapping `amount_in` into a pool holding `reserve_in` and
+/// `reserve_out`, rounded down.
+pub fn quote(reserve_in: u64, reserve_out: u64, amount_in: u64, fee_bps: u16) -> u64 {
+    let amount_in = amount_in as u128 * (10_000 - fee_bps.min(10_000) as u128) / 10_000;
+    (reserve_out as u128 * amount_in / (reserve_in as u128 + amount_in).max(1)) as u64
+}
+
+/// Q64.64 sqrt of the token B per token A price a pool with these reserves
+/// quotes, as a Whirlpool keeps it.
+pub fn sqrt_price(reserve_a: u64, reserve_b: u64) -> u128 {
+    ((reserve_b as f64 / reserve_a.max(1) as f64).sqrt() * 2f64.powi(64)) as u128
+}
+
+/// The pool over the simulation's market mints. The pool account signs for
+/// its vaults, so it's a PDA of the stand-in program.
+pub fn pool_address(sim: &Simulation) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"whirlpool", sim.keys.token_a_mint.as_ref(), sim.keys.token_b_mint.as_ref()],
+        &WHIRLPOOL_PROGRAM_ID,
+    )
+}
+
+/// Deploys the stand-in, opens a pool holding `reserve_a` and `reserve_b`
+/// and points the market's mirror venue at it. Like a Whirlpool's, the fee
+/// can be at most 6%.
+pub fn add_pool(sim: &mut Simulation, reserve_a: u64, reserve_b: u64, fee_bps: u16) -> Result<Pool, Failure> {
+    assert!(fee_bps <= 600, "Whirlpool fees top out at 6%");
+    sim.runtime.add_program(WHIRLPOOL_PROGRAM_ID, process);
+    let (whirlpool, bump) = pool_address(sim);
+    let mut data = vec![0; POOL_LEN];
+    data[BUMP_OFFSET] = bump;
+    // Whirlpool fee rates are in hundredths of a basis point
+    data[FEE_RATE_OFFSET..FEE_RATE_OFFSET + 2].copy_from_slice(&(fee_bps * 100).to_le_bytes());
+    data[SQRT_PRICE_OFFSET..SQRT_PRICE_OFFSET + 16].copy_from_slice(&sqrt_price(reserve_a, reserve_b).to_le_bytes());
+    data[MINT_A_OFFSET..POOL_LEN].copy_from_slice(sim.keys.token_a_mint.as_ref());
+    let lamports = sim.runtime.rent().minimum_balance(data.len());
+    sim.runtime.set_account(
+        whirlpool,
+        Account {
+            lamports,
+            data,
+            owner: WHIRLPOOL_PROGRAM_ID,
+            executable: false,
+        },
+    );
+    let pool = Pool {
+        whirlpool,
+        vault_a: sim.new_key(),
+        vault_b: sim.new_key(),
+        fee_bps,
+    };
+    let keys = sim.keys;
+    sim.mint_to(pool.vault_a, keys.token_a_mint, whirlpool, reserve_a);
+    sim.mint_to(pool.vault_b, keys.token_b_mint, whirlpool, reserve_b);
+
+    let set_mirror_pool = program_instruction(
+        accounts::SetMirrorPool {
+            market: keys.market,
+            authority: sim.admin,
+        },
+        instruction::SetMirrorPool {
+            venue: MIRROR_WHIRLPOOL,
+            pool: whirlpool,
+        },
+    );
+    let admin = sim.admin;
+    sim.send(&[set_mirror_pool], &[admin])?;
+    Ok(pool)
+}
+
+/// Reserves of token A and token B in `pool`.
+pub fn reserves(sim: &Simulation, pool: &Pool) -> (u64, u64) {
+    let amount = |key: &Pubkey| {
+        sim.runtime
+            .account(key)
+            .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+            .map_or(0, |state| state.amount)
+    };
+    (amount(&pool.vault_a), amount(&pool.vault_b))
+}
+
+/// Fills the agent's open order against `pool` with `execute_mirror_trade`,
+/// paying from and to the owner's token accounts.
+pub fn mirror_trade(
+    sim: &mut Simulation,
+    agent: usize,
+    pool: &Pool,
+    max_slippage_bps: u16,
+) -> Result<Receipt, Failure> {
+    let execute = mirror_trade_instruction(sim, agent, pool, max_slippage_bps);
+    let owner = sim.agents[agent].owner;
+    sim.send(&[execute], &[owner])
+}
+
+/// The `execute_mirror_trade` instruction behind `mirror_trade`.
+pub fn mirror_trade_instruction(sim: &Simulation, agent: usize, pool: &Pool, max_slippage_bps: u16) -> Instruction {
+    let keys = sim.agents[agent];
+    let trade = sim.order(agent).expect("agent has no order");
+    let (account_in, account_out) = match trade.trade_type {
+        0 => (keys.token_a_account, keys.token_b_account),
+        _ => (keys.token_b_account, keys.token_a_account),
+    };
+    let mut execute = program_instruction(
+        accounts::ExecuteMirrorTrade {
+            trade: keys.trade,
+            market: sim.keys.market,
+            config: config_address(),
+            agent: keys.agent,
+            user: keys.owner,
+            user_token_account_in: account_in,
+            user_token_account_out: account_out,
+            venue_program: WHIRLPOOL_PROGRAM_ID,
+            token_program: spl_token::ID,
+        },
+        instruction::ExecuteMirrorTrade { max_slippage_bps },
+    );
+    execute.accounts.extend(swap_accounts(&keys, pool));
+    execute
+}
+
+/// Fills the agent's open order across the book and `pool` with
+/// `execute_hybrid_trade`, offering it the resting orders of `makers`.
+pub fn hybrid_trade(
+    sim: &mut Simulation,
+    agent: usize,
+    pool: &Pool,
+    max_slippage_bps: u16,
+    makers: &[usize],
+) -> Result<Receipt, Failure> {
+    let execute = hybrid_trade_instruction(sim, agent, pool, max_slippage_bps, makers);
+    let owner = sim.agents[agent].owner;
+    sim.send(&[execute], &[owner])
+}
+
+/// The `execute_hybrid_trade` instruction behind `hybrid_trade`.
+pub fn hybrid_trade_instruction(
+    sim: &Simulation,
+    agent: usize,
+    pool: &Pool,
+    max_slippage_bps: u16,
+    makers: &[usize],
+) -> Instruction {
+    let keys = sim.agents[agent];
+    let trade = sim.order(agent).expect("agent has no order");
+    let market = sim.keys;
+    let (account_in, account_out, vault_in, vault_token_account_in, vault_out, vault_token_account_out) =
+        match trade.trade_type {
+            0 => (
+                keys.token_a_account,
+                keys.token_b_account,
+                market.vault_a,
+                market.vault_a_token_account,
+                market.vault_b,
+                market.vault_b_token_account,
+            ),
+            _ => (
+                keys.token_b_account,
+                keys.token_a_account,
+                market.vault_b,
+                market.vault_b_token_account,
+                market.vault_a,
+                market.vault_a_token_account,
+            ),
+        };
+    // Every agent's current reward record, once opened, counts its fills
+    let rewards: Vec<Pubkey> = std::iter::once(agent)
+        .chain(makers.iter().copied())
+        .filter_map(|agent| sim.current_reward(agent))
+        .collect();
+    let mut execute = program_instruction(
+        accounts::ExecuteHybridTrade {
+            trade: keys.trade,
+            market: market.market,
+            config: config_address(),
+            agent: keys.agent,
+            user: keys.owner,
+            user_token_account_in: account_in,
+            user_token_account_out: account_out,
+            vault_in,
+            vault_token_account_in,
+            vault_out,
+            vault_token_account_out,
+            venue_program: WHIRLPOOL_PROGRAM_ID,
+            global_stats: global_stats_address(),
+            token_program: spl_token::ID,
+        },
+        instruction::ExecuteHybridTrade {
+            max_slippage_bps,
+            book_orders: makers.len() as u8,
+            reward_records: rewards.len() as u8,
+        },
+    );
+    for &maker in makers {
+        let maker = sim.agents[maker];
+        let (balance_in, balance_out) = match trade.trade_type {
+            0 => (maker.balance_a, maker.balance_b),
+            _ => (maker.balance_b, maker.balance_a),
+        };
+        execute.accounts.extend([
+            AccountMeta::new(maker.trade, false),
+            AccountMeta::new(maker.agent, false),
+            AccountMeta::new(balance_in, false),
+            AccountMeta::new(balance_out, false),
+        ]);
+    }
+    execute.accounts.extend(rewards.iter().map(|&reward| AccountMeta::new(reward, false)));
+    execute.accounts.extend(swap_accounts(&keys, pool));
+    execute
+}
+
+/// Whirlpool's own swap accounts; the pool stands in for the tick arrays
+/// and oracle it doesn't read.
+fn swap_accounts(keys: &AgentKeys, pool: &Pool) -> [AccountMeta; 11] {
+    [
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(keys.owner, true),
+        AccountMeta::new(pool.whirlpool, false),
+        AccountMeta::new(keys.token_a_account, false),
+        AccountMeta::new(pool.vault_a, false),
+        AccountMeta::new(keys.token_b_account, false),
+        AccountMeta::new(pool.vault_b, false),
+        AccountMeta::new(pool.whirlpool, false),
+        AccountMeta::new(pool.whirlpool, false),
+        AccountMeta::new(pool.whirlpool, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ]
+}
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [token_program, authority, whirlpool, owner_a, vault_a, owner_b, vault_b, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if data.len() < SWAP_DATA_LEN || whirlpool.owner != program_id {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let threshold = u64::from_le_bytes(data[16..24].try_into().unwrap());
+    let (amount_is_input, a_to_b) = (data[40] != 0, data[41] != 0);
+    if !amount_is_input {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (fee_bps, bump) = {
+        let pool = whirlpool.try_borrow_data()?;
+        let fee_rate = u16::from_le_bytes([pool[FEE_RATE_OFFSET], pool[FEE_RATE_OFFSET + 1]]);
+        (fee_rate / 100, pool[BUMP_OFFSET])
+    };
+
+    let reserve = |vault: &AccountInfo| -> Result<spl_token::state::Account, ProgramError> {
+        spl_token::state::Account::unpack(&vault.try_borrow_data()?)
+    };
+    let (state_a, state_b) = (reserve(vault_a)?, reserve(vault_b)?);
+    let (owner_in, vault_in, owner_out, vault_out, reserve_in, reserve_out) = if a_to_b {
+        (owner_a, vault_a, owner_b, vault_b, state_a.amount, state_b.amount)
+    } else {
+        (owner_b, vault_b, owner_a, vault_a, state_b.amount, state_a.amount)
+    };
+    let amount_out = quote(reserve_in, reserve_out, amount, fee_bps);
+    if amount_out < threshold || amount_out == 0 {
+        return Err(ProgramError::Custom(6017)); // Whirlpool's AmountOutBelowMinimum
+    }
+
+    invoke(
+        &spl_token::instruction::transfer(token_program.key, owner_in.key, vault_in.key, authority.key, &[], amount)?,
+        &[owner_in.clone(), vault_in.clone(), authority.clone(), token_program.clone()],
+    )?;
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            token_program.key,
+            vault_out.key,
+            owner_out.key,
+            whirlpool.key,
+            &[],
+            amount_out,
+        )?,
+        &[vault_out.clone(), owner_out.clone(), whirlpool.clone(), token_program.clone()],
+        &[&[b"whirlpool", state_a.mint.as_ref(), state_b.mint.as_ref(), &[bump]]],
+    )?;
+
+    let (state_a, state_b) = (reserve(vault_a)?, reserve(vault_b)?);
+    whirlpool.try_borrow_mut_data()?[SQRT_PRICE_OFFSET..SQRT_PRICE_OFFSET + 16]
+        .copy_from_slice(&sqrt_price(state_a.amount, state_b.amount).to_le_bytes());
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! Fuzzes the program with a `Fuzzer` and prints its report as JSON. The
+//! optional argument is a JSON file of `FuzzConfig` fields. Exits non-zero if
+//! anything was found, so it can gate CI. Program logs go to stderr.
+use std::io::Write;
+use std::process::ExitCode;
+
+use neuraltrader_harness::runtime::redirect_program_logs;
+use neuraltrader_harness::{FuzzConfig, Fuzzer};
+
+fn main() -> ExitCode {
+    let config: FuzzConfig = match std::env::args().nth(1) {
+        Some(path) => {
+            let bytes = std::fs::read(&path).unwrap_or_else(|error| panic!("{path}: {error}"));
+            serde_json::from_slice(&bytes).unwrap_or_else(|error| panic!("{path}: {error}"))
+        }
+        None => FuzzConfig::default(),
+    };
+    let mut output = redirect_program_logs();
+    // Panics the program raises are findings, reported with the rest
+    std::panic::set_hook(Box::new(|_| {}));
+    let report = Fuzzer::new(config).run();
+    serde_json::to_writer(&mut output, &report).unwrap();
+    writeln!(output).unwrap();
+    if report.findings.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
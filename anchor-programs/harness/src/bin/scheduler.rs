@@ -0,0 +1,41 @@
+//! Runs a `Scheduler` over a pool of noise traders and prints its metrics as
+//! JSON. The optional argument is a JSON file of `SchedulerConfig` fields
+//! plus `agents`, the pool size, and `noise`, the traders' `NoiseTrader`
+//! settings. Program logs go to stderr.
+use std::io::Write;
+use std::sync::Arc;
+
+use neuraltrader_harness::runtime::redirect_program_logs;
+use neuraltrader_harness::{NoiseTrader, Scheduler, SchedulerConfig, Strategy};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Config {
+    #[serde(flatten)]
+    scheduler: SchedulerConfig,
+    #[serde(default = "default_agents")]
+    agents: usize,
+    #[serde(default)]
+    noise: NoiseTrader,
+}
+
+fn default_agents() -> usize {
+    16
+}
+
+fn main() {
+    let config: Config = match std::env::args().nth(1) {
+        Some(path) => {
+            let bytes = std::fs::read(&path).unwrap_or_else(|error| panic!("{path}: {error}"));
+            serde_json::from_slice(&bytes).unwrap_or_else(|error| panic!("{path}: {error}"))
+        }
+        None => serde_json::from_str("{}").unwrap(),
+    };
+    let strategies = (0..config.agents)
+        .map(|_| Arc::new(config.noise.clone()) as Arc<dyn Strategy>)
+        .collect();
+    let mut output = redirect_program_logs();
+    let run = Scheduler::with_strategies(config.scheduler, strategies).run();
+    serde_json::to_writer(&mut output, &run.metrics).unwrap();
+    writeln!(output).unwrap();
+}
@@ -0,0 +1,78 @@
+//! `TradingEnv` over stdin/stdout, one JSON request and one JSON reply per
+//! line, for clients in other languages:
+//!
+//! ```text
+//! {"op":"reset","seed":7,"config":{"background_agents":4}} -> {"observation":{..}}
+//! {"op":"step","action":{"type":"bid","price":990000,"quantity":1000000}} -> {"observation":{..},"reward":..}
+//! {"op":"observation"} -> {"observation":{..}}
+//! {"op":"save","path":"run.json"} -> {"saved":"run.json"}
+//! {"op":"load","path":"run.json"} -> {"observation":{..}}
+//! ```
+//!
+//! `config` is optional and only read by `reset`; a bad request gets
+//! `{"error":".."}`. The program's own log output goes to stderr so it can't
+//! interleave with replies.
+use std::io::{BufRead, BufWriter, Write};
+
+use neuraltrader_harness::runtime::redirect_program_logs;
+use neuraltrader_harness::{Action, EnvConfig, TradingEnv};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Request {
+    Reset {
+        seed: Option<u64>,
+        config: Option<Box<EnvConfig>>,
+    },
+    Step {
+        action: Action,
+    },
+    Observation,
+    Save {
+        path: String,
+    },
+    Load {
+        path: String,
+    },
+}
+
+fn main() {
+    let mut replies = BufWriter::new(redirect_program_logs());
+    let mut env = TradingEnv::new(EnvConfig::default());
+
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply: Value = match serde_json::from_str::<Request>(&line) {
+            Ok(Request::Reset { seed, config }) => match config.map(|config| TradingEnv::try_new(*config)) {
+                Some(Err(error)) => json!({ "error": error.to_string() }),
+                Some(Ok(configured)) => {
+                    env = configured;
+                    json!({ "observation": env.reset(seed) })
+                }
+                None => json!({ "observation": env.reset(seed) }),
+            },
+            Ok(Request::Step { action }) => serde_json::to_value(env.step(action)).unwrap(),
+            Ok(Request::Observation) => json!({ "observation": env.observation() }),
+            Ok(Request::Save { path }) => match env.save(&path) {
+                Ok(()) => json!({ "saved": path }),
+                Err(error) => json!({ "error": error.to_string() }),
+            },
+            Ok(Request::Load { path }) => match TradingEnv::load(&path) {
+                Ok(loaded) => {
+                    env = loaded;
+                    json!({ "observation": env.observation() })
+                }
+                Err(error) => json!({ "error": error.to_string() }),
+            },
+            Err(error) => json!({ "error": error.to_string() }),
+        };
+        serde_json::to_writer(&mut replies, &reply).unwrap();
+        replies.write_all(b"\n").unwrap();
+        replies.flush().unwrap();
+    }
+}
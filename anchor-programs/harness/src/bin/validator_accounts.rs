@@ -0,0 +1,56 @@
+//! Writes the accounts of a saved checkpoint as `solana-test-validator
+//! --account` files and prints the arguments that load them:
+//!
+//! ```text
+//! solana-test-validator --reset \
+//!     --upgradeable-program <program id> target/deploy/anchor_programs.so <admin> \
+//!     $(validator_accounts run.json accounts/)
+//! ```
+//!
+//! Takes a `TradingEnv::save` file, a `SimulationCheckpoint` or a bare
+//! `Checkpoint`, all as JSON. The program's upgrade authority has to be the
+//! simulation's admin for the saved config to stay valid. For simulation
+//! checkpoints the simulation's keypairs are written to `keys/` in the output
+//! directory too, so its admin and agents can keep signing.
+use std::path::Path;
+use std::process::ExitCode;
+
+use neuraltrader_harness::{Checkpoint, EnvCheckpoint, SimulationCheckpoint};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    let [_, checkpoint, dir] = &args[..] else {
+        eprintln!("usage: validator_accounts <checkpoint.json> <output dir>");
+        return ExitCode::FAILURE;
+    };
+    let result = std::fs::read(checkpoint).map_err(|error| error.to_string()).and_then(|bytes| {
+        let simulation = serde_json::from_slice::<EnvCheckpoint>(&bytes)
+            .map(|env| env.simulation)
+            .or_else(|_| serde_json::from_slice::<SimulationCheckpoint>(&bytes));
+        let state = match simulation {
+            Ok(simulation) => {
+                eprintln!("admin {}", simulation.admin);
+                let keys = Path::new(dir).join("keys");
+                std::fs::create_dir_all(&keys).map_err(|error| error.to_string())?;
+                for keypair in simulation.key_source.keypairs() {
+                    keypair
+                        .write_json(keys.join(format!("{}.json", keypair.pubkey())))
+                        .map_err(|error| error.to_string())?;
+                }
+                simulation.state
+            }
+            Err(_) => serde_json::from_slice::<Checkpoint>(&bytes).map_err(|error| error.to_string())?,
+        };
+        state.write_validator_accounts(dir).map_err(|error| error.to_string())
+    });
+    match result {
+        Ok(args) => {
+            println!("{}", args.join(" "));
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{checkpoint}: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
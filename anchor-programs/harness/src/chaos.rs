@@ -0,0 +1,198 @@
+//! Network conditions between agents and the cluster. Transactions submitted
+//! through a `ChaosNetwork` land after a latency drawn from a distribution,
+//! possibly out of order, and some never land at all; a submitter only learns
+//! that one was dropped once its blockhash would have expired, as on a real
+//! cluster.
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{Failure, Receipt, Runtime};
+use crate::seed::{self, STREAM_NETWORK};
+
+/// Slots from submission to landing.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Latency {
+    Fixed { slots: u64 },
+    /// Uniform over `min..=max`
+    Uniform { min: u64, max: u64 },
+    /// Exponential with mean `mean`, rounded to whole slots
+    Exponential { mean: f64 },
+}
+
+impl Latency {
+    fn sample(&self, rng: &mut impl Rng) -> u64 {
+        match *self {
+            Latency::Fixed { slots } => slots,
+            Latency::Uniform { min, max } => rng.gen_range(min..=max.max(min)),
+            Latency::Exponential { mean } => {
+                let uniform: f64 = rng.gen();
+                (-mean * (1.0 - uniform).ln()).round() as u64
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChaosConfig {
+    /// Defaults to the seed of whatever owns the network
+    pub seed: Option<u64>,
+    pub latency: Latency,
+    /// Chance in percent that a transaction never lands
+    pub drop_percent: u8,
+    /// Land transactions due in the same slot in random order rather than
+    /// in the order they were submitted
+    pub reorder: bool,
+    /// Slots after which a transaction that hasn't landed has expired
+    pub max_age_slots: u64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            latency: Latency::Fixed { slots: 0 },
+            drop_percent: 0,
+            reorder: false,
+            max_age_slots: 150,
+        }
+    }
+}
+
+pub type TxId = u64;
+
+#[derive(Clone, Debug)]
+pub enum Outcome {
+    Landed(Receipt),
+    Failed(Failure),
+    /// Dropped by the network
+    Dropped,
+    /// Its latency outlived its blockhash
+    Expired,
+}
+
+#[derive(Clone, Debug)]
+pub struct Delivery {
+    pub id: TxId,
+    /// Slot the outcome became known in
+    pub slot: u64,
+    pub outcome: Outcome,
+}
+
+// Decided at submission, reported at `due_slot`
+#[derive(Clone, Copy, Serialize, Deserialize)]
+enum Fate {
+    Land,
+    Drop,
+    Expire,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Pending {
+    id: TxId,
+    due_slot: u64,
+    instructions: Vec<Instruction>,
+    signers: Vec<Pubkey>,
+    fate: Fate,
+}
+
+/// Transactions in flight between their submitters and a `Runtime`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ChaosNetwork {
+    config: ChaosConfig,
+    rng: ChaCha8Rng,
+    pending: Vec<Pending>,
+    next_id: TxId,
+}
+
+impl ChaosNetwork {
+    pub fn new(config: ChaosConfig) -> Self {
+        Self {
+            rng: seed::rng(config.seed.unwrap_or_default(), STREAM_NETWORK),
+            config,
+            pending: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    /// Queues a transaction submitted at the runtime's current slot.
+    pub fn submit(&mut self, runtime: &Runtime, instructions: Vec<Instruction>, signers: Vec<Pubkey>) -> TxId {
+        let id = self.next_id;
+        self.next_id += 1;
+        let slot = runtime.clock().slot;
+        let max_age = self.config.max_age_slots;
+        let (due_slot, fate) = if self.rng.gen_range(0..100) < self.config.drop_percent {
+            (slot + max_age, Fate::Drop)
+        } else {
+            match self.config.latency.sample(&mut self.rng) {
+                latency if latency > max_age => (slot + max_age, Fate::Expire),
+                latency => (slot + latency, Fate::Land),
+            }
+        };
+        self.pending.push(Pending {
+            id,
+            due_slot,
+            instructions,
+            signers,
+            fate,
+        });
+        id
+    }
+
+    /// Lands or reports every transaction due by the runtime's current slot.
+    pub fn deliver(&mut self, runtime: &mut Runtime) -> Vec<Delivery> {
+        let slot = runtime.clock().slot;
+        let (mut due, pending): (Vec<_>, Vec<_>) = self.pending.drain(..).partition(|tx| tx.due_slot <= slot);
+        self.pending = pending;
+        if self.config.reorder {
+            due.shuffle(&mut self.rng);
+        }
+        // Stable, so reordering only ever shuffles within a slot
+        due.sort_by_key(|tx| tx.due_slot);
+        due.into_iter()
+            .map(|tx| {
+                let outcome = match tx.fate {
+                    Fate::Land => match runtime.process(&tx.instructions, &tx.signers) {
+                        Ok(receipt) => Outcome::Landed(receipt),
+                        Err(failure) => Outcome::Failed(failure),
+                    },
+                    Fate::Drop => Outcome::Dropped,
+                    Fate::Expire => Outcome::Expired,
+                };
+                Delivery {
+                    id: tx.id,
+                    slot,
+                    outcome,
+                }
+            })
+            .collect()
+    }
+
+    /// Advances the runtime one slot at a time, delivering as it goes.
+    pub fn advance(&mut self, runtime: &mut Runtime, slots: u64) -> Vec<Delivery> {
+        let mut deliveries = self.deliver(runtime);
+        for _ in 0..slots {
+            runtime.advance_slots(1);
+            deliveries.extend(self.deliver(runtime));
+        }
+        deliveries
+    }
+
+    /// Transactions not yet delivered.
+    pub fn in_flight(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_in_flight(&self, id: TxId) -> bool {
+        self.pending.iter().any(|tx| tx.id == id)
+    }
+}
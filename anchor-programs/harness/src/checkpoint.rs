@@ -0,0 +1,139 @@
+//! Saving simulation state to disk and loading it back. A `Checkpoint` holds
+//! every account but programs and sysvars, each in the JSON shape `solana
+//! account --output json` prints, so besides restoring into a `Runtime` it
+//! can seed a fresh `solana-test-validator` through `--account`.
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::{bpf_loader_upgradeable, sysvar};
+use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{Account, Runtime};
+
+/// One account as `solana account --output json` prints it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedAccount {
+    #[serde(with = "base58")]
+    pub pubkey: Pubkey,
+    pub account: UiAccount,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiAccount {
+    pub lamports: u64,
+    /// Base64 data and the encoding's name, `"base64"`
+    pub data: (String, String),
+    #[serde(with = "base58")]
+    pub owner: Pubkey,
+    pub executable: bool,
+    pub rent_epoch: u64,
+    pub space: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub slot: u64,
+    pub unix_timestamp: i64,
+    pub accounts: Vec<SavedAccount>,
+}
+
+impl Checkpoint {
+    /// Every account in `runtime` except executables, sysvars and program
+    /// data, which a fresh cluster brings with it. Sorted by address.
+    pub fn capture(runtime: &Runtime) -> Self {
+        let mut accounts: Vec<SavedAccount> = runtime
+            .accounts()
+            .filter(|(_, account)| {
+                !account.executable && account.owner != sysvar::ID && account.owner != bpf_loader_upgradeable::ID
+            })
+            .map(|(pubkey, account)| SavedAccount {
+                pubkey: *pubkey,
+                account: UiAccount {
+                    lamports: account.lamports,
+                    data: (BASE64_STANDARD.encode(&account.data), "base64".into()),
+                    owner: account.owner,
+                    executable: false,
+                    rent_epoch: u64::MAX,
+                    space: account.data.len() as u64,
+                },
+            })
+            .collect();
+        accounts.sort_by_key(|saved| saved.pubkey);
+        let clock = runtime.clock();
+        Self {
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+            accounts,
+        }
+    }
+
+    /// Writes the saved accounts and clock into `runtime`, typically a fresh
+    /// one.
+    pub fn restore(&self, runtime: &mut Runtime) -> io::Result<()> {
+        for saved in &self.accounts {
+            let data = BASE64_STANDARD
+                .decode(&saved.account.data.0)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            runtime.set_account(
+                saved.pubkey,
+                Account {
+                    lamports: saved.account.lamports,
+                    data,
+                    owner: saved.account.owner,
+                    executable: saved.account.executable,
+                },
+            );
+        }
+        runtime.warp_to_slot(self.slot);
+        runtime.set_unix_timestamp(self.unix_timestamp);
+        Ok(())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, serde_json::to_vec(self)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Writes one `<address>.json` per account into `dir` and returns the
+    /// `solana-test-validator` arguments that load them and warp to the
+    /// checkpoint's slot. The program itself still has to be passed, e.g.
+    /// with `--upgradeable-program`.
+    pub fn write_validator_accounts(&self, dir: impl AsRef<Path>) -> io::Result<Vec<String>> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let mut args = Vec::with_capacity(self.accounts.len() * 3 + 2);
+        for saved in &self.accounts {
+            let path = dir.join(format!("{}.json", saved.pubkey));
+            fs::write(&path, serde_json::to_vec_pretty(saved)?)?;
+            args.push("--account".into());
+            args.push(saved.pubkey.to_string());
+            args.push(path.display().to_string());
+        }
+        args.push("--warp-slot".into());
+        args.push(self.slot.to_string());
+        Ok(args)
+    }
+}
+
+/// Pubkeys as base58 strings rather than byte arrays.
+pub(crate) mod base58 {
+    use std::str::FromStr;
+
+    use anchor_lang::solana_program::pubkey::Pubkey;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(pubkey)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+        Pubkey::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
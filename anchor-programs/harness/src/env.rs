@@ -0,0 +1,477 @@
+//! A `Simulation` as a reinforcement learning environment, in the shape Gym
+//! and its successors expect: `reset` starts an episode and returns the first
+//! observation, `step` applies one action and returns the next observation,
+//! the reward and whether the episode is over.
+//!
+//! The learner is agent 0 of a market whose other agents are noise traders
+//! quoting around a hidden reference price that follows a random walk. An
+//! action replaces, cancels or keeps the learner's resting order; after the
+//! background agents requote, the clock is advanced and the book cranked.
+//! With `EnvConfig::chaos` set, agents' transactions go through a
+//! `ChaosNetwork` instead of landing at once. With `EnvConfig::replay` set,
+//! the reference price and the market's oracle follow recorded data instead,
+//! one record per step, and the episode ends with the data. `save` and `load`
+//! checkpoint an episode mid-way.
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use anchor_programs::PRICE_SCALE;
+
+use crate::chaos::{ChaosConfig, ChaosNetwork, Outcome, TxId};
+use crate::replay::{load_csv, Replay, ReplayConfig, ReplayError};
+use crate::seed::{self, STREAM_AGENTS, STREAM_SHOCKS};
+use crate::sim::{Fill, Side, Simulation, SimulationCheckpoint, SIM_DECIMALS};
+
+/// The learner's index in `TradingEnv::simulation().agents`.
+pub const LEARNER: usize = 0;
+
+/// Parameters of an episode. Prices are token A per token B and amounts are
+/// base units, as on chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvConfig {
+    /// Seeds everything random in the episode: keys, background agents,
+    /// price shocks and, unless it has its own seed, the chaos network
+    pub seed: u64,
+    pub background_agents: usize,
+    /// Reference price at the start, `PRICE_SCALE` fixed point
+    pub initial_price: u64,
+    /// Deposited for every agent, learner included
+    pub initial_token_a: u64,
+    pub initial_token_b: u64,
+    /// Steps before the episode is truncated
+    pub max_steps: u64,
+    pub slots_per_step: u64,
+    /// Largest move of the reference price in one step
+    pub volatility_bps: u64,
+    /// Chance in percent of a shock on a step, a jump of the reference price
+    /// by `shock_bps` either way
+    pub shock_percent: u8,
+    pub shock_bps: u64,
+    /// Furthest a background quote rests from the reference price, either way
+    pub spread_bps: u64,
+    /// Mean size of a background quote, token B
+    pub order_size: u64,
+    /// Chance in percent that a background agent requotes on a step
+    pub requote_percent: u8,
+    /// Levels of each side of the book in an observation
+    pub book_depth: usize,
+    /// Past prices in an observation
+    pub history_len: usize,
+    /// Network conditions for agents' transactions, perfect if unset; the
+    /// cranker is never delayed
+    pub chaos: Option<ChaosConfig>,
+    /// Recorded data to replay instead of the random walk
+    pub replay: Option<ReplaySource>,
+}
+
+/// A CSV file of prices or trades, read as described in `replay`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplaySource {
+    pub path: PathBuf,
+    #[serde(flatten, default)]
+    pub config: ReplayConfig,
+}
+
+impl Default for EnvConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            background_agents: 8,
+            initial_price: PRICE_SCALE,
+            initial_token_a: 1_000_000_000,
+            initial_token_b: 1_000_000_000,
+            max_steps: 1_000,
+            slots_per_step: 1,
+            volatility_bps: 20,
+            shock_percent: 0,
+            shock_bps: 500,
+            spread_bps: 50,
+            order_size: 10_000_000,
+            requote_percent: 50,
+            book_depth: 5,
+            history_len: 16,
+            chaos: None,
+            replay: None,
+        }
+    }
+}
+
+/// What the learner does with its resting order on a step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Action {
+    /// Leave the order, if any, as it is
+    Hold,
+    Cancel,
+    /// Replace the order with a bid for `quantity` token B at `price`
+    Bid { price: u64, quantity: u64 },
+    /// Replace the order with an ask for `quantity` token B at `price`
+    Ask { price: u64, quantity: u64 },
+}
+
+impl Action {
+    /// None to leave the order be, otherwise what to replace it with: nothing
+    /// or a new order as side, price and quantity.
+    pub fn replacement(self) -> Option<Option<(Side, u64, u64)>> {
+        match self {
+            Action::Hold => None,
+            Action::Cancel => Some(None),
+            Action::Bid { price, quantity } => Some(Some((Side::Bid, price, quantity))),
+            Action::Ask { price, quantity } => Some(Some((Side::Ask, price, quantity))),
+        }
+    }
+}
+
+/// Resting size at one price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Level {
+    pub price: u64,
+    pub quantity: u64,
+}
+
+/// The learner's resting order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub side: Side,
+    pub price: u64,
+    /// Token B left to fill
+    pub remaining: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Observation {
+    pub step: u64,
+    pub slot: u64,
+    /// Last traded price, or the initial price before the first fill
+    pub last_price: u64,
+    /// Last price at the end of each of the previous steps, oldest first
+    pub price_history: Vec<u64>,
+    /// Best first
+    pub bids: Vec<Level>,
+    /// Best first
+    pub asks: Vec<Level>,
+    pub balance_a: u64,
+    pub balance_b: u64,
+    pub order: Option<OpenOrder>,
+    /// The learner's transactions submitted but not yet landed or expired
+    pub in_flight: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StepResult {
+    pub observation: Observation,
+    /// Change in the learner's equity over the step, whole token A at the
+    /// last price
+    pub reward: f64,
+    /// The step limit was reached or the replayed data ran out
+    pub done: bool,
+    /// The program rejected a transaction of the learner's that landed
+    /// during the step
+    pub rejected: bool,
+    /// A transaction of the learner's was dropped or expired during the step
+    pub dropped: bool,
+    /// Every fill the crank produced during the step
+    pub fills: Vec<Fill>,
+}
+
+/// Everything a `TradingEnv` needs to carry on exactly where it was.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnvCheckpoint {
+    pub config: EnvConfig,
+    pub simulation: SimulationCheckpoint,
+    network: Option<ChaosNetwork>,
+    replay: Option<Replay>,
+    learner_transactions: Vec<TxId>,
+    rng: ChaCha8Rng,
+    shock_rng: ChaCha8Rng,
+    reference_price: u64,
+    step: u64,
+    history: Vec<u64>,
+    equity: u64,
+}
+
+pub struct TradingEnv {
+    config: EnvConfig,
+    sim: Simulation,
+    network: Option<ChaosNetwork>,
+    replay: Option<Replay>,
+    learner_transactions: Vec<TxId>,
+    rng: ChaCha8Rng,
+    shock_rng: ChaCha8Rng,
+    reference_price: u64,
+    step: u64,
+    history: Vec<u64>,
+    equity: u64,
+}
+
+impl TradingEnv {
+    /// Panics if the replayed data can't be loaded; see `try_new`.
+    pub fn new(config: EnvConfig) -> Self {
+        Self::try_new(config).expect("environment setup failed")
+    }
+
+    pub fn try_new(config: EnvConfig) -> Result<Self, ReplayError> {
+        let mut sim = Simulation::seeded(config.seed);
+        for _ in 0..=config.background_agents {
+            sim.add_agent(config.initial_token_a, config.initial_token_b)?;
+        }
+        let replay = match &config.replay {
+            Some(source) => Some(Replay::new(&mut sim, load_csv(&source.path)?, source.config.clone())?),
+            None => None,
+        };
+        let mut env = Self {
+            rng: seed::rng(config.seed, STREAM_AGENTS),
+            shock_rng: seed::rng(config.seed, STREAM_SHOCKS),
+            reference_price: replay
+                .as_ref()
+                .and_then(Replay::next_record)
+                .map_or(config.initial_price, |record| record.price),
+            network: config.chaos.clone().map(|chaos| {
+                ChaosNetwork::new(ChaosConfig {
+                    seed: chaos.seed.or(Some(config.seed)),
+                    ..chaos
+                })
+            }),
+            replay,
+            learner_transactions: Vec::new(),
+            config,
+            sim,
+            step: 0,
+            history: Vec::new(),
+            equity: 0,
+        };
+        env.equity = env.equity();
+        Ok(env)
+    }
+
+    /// Starts a new episode, reseeded with `seed` if given, and returns its
+    /// first observation.
+    pub fn reset(&mut self, seed: Option<u64>) -> Observation {
+        if let Some(seed) = seed {
+            self.config.seed = seed;
+        }
+        *self = Self::new(self.config.clone());
+        self.observation()
+    }
+
+    pub fn step(&mut self, action: Action) -> StepResult {
+        let mut rejected = self.apply(action).is_err();
+        self.requote_background();
+        let slots = match &self.replay {
+            Some(replay) => replay
+                .next_slot()
+                .map_or(0, |slot| slot.saturating_sub(self.sim.runtime.clock().slot)),
+            None => self.config.slots_per_step,
+        };
+        let mut dropped = false;
+        match &mut self.network {
+            Some(network) => {
+                for delivery in network.advance(&mut self.sim.runtime, slots) {
+                    let Some(index) = self.learner_transactions.iter().position(|id| *id == delivery.id) else {
+                        continue;
+                    };
+                    self.learner_transactions.remove(index);
+                    match delivery.outcome {
+                        Outcome::Landed(_) => {}
+                        Outcome::Failed(_) => rejected = true,
+                        Outcome::Dropped | Outcome::Expired => dropped = true,
+                    }
+                }
+            }
+            None => self.sim.runtime.advance_slots(slots),
+        }
+        if let Some(replay) = &mut self.replay {
+            if let Some(record) = replay.step(&mut self.sim) {
+                self.reference_price = record.expect("replay failed").price;
+            }
+        }
+        let fills = self.sim.crank();
+
+        self.step += 1;
+        self.history.push(self.last_price());
+        if self.history.len() > self.config.history_len {
+            self.history.remove(0);
+        }
+        let equity = self.equity();
+        let scale = 10f64.powi(SIM_DECIMALS as i32);
+        let reward = (equity as f64 - self.equity as f64) / scale;
+        self.equity = equity;
+        StepResult {
+            observation: self.observation(),
+            reward,
+            done: self.step >= self.config.max_steps
+                || self.replay.as_ref().is_some_and(|replay| replay.remaining() == 0),
+            rejected,
+            dropped,
+            fills,
+        }
+    }
+
+    pub fn observation(&self) -> Observation {
+        let (balance_a, balance_b) = self.sim.balances(LEARNER);
+        let order = self
+            .sim
+            .order(LEARNER)
+            .filter(|trade| trade.size() > 0)
+            .map(|trade| OpenOrder {
+                side: if trade.trade_type == 0 { Side::Bid } else { Side::Ask },
+                price: Simulation::order_price(&trade),
+                remaining: trade.size(),
+            });
+        Observation {
+            step: self.step,
+            slot: self.sim.runtime.clock().slot,
+            last_price: self.last_price(),
+            price_history: self.history.clone(),
+            bids: self.levels(Side::Bid),
+            asks: self.levels(Side::Ask),
+            balance_a,
+            balance_b,
+            order,
+            in_flight: self.learner_transactions.len(),
+        }
+    }
+
+    pub fn checkpoint(&self) -> EnvCheckpoint {
+        EnvCheckpoint {
+            config: self.config.clone(),
+            simulation: self.sim.checkpoint(),
+            network: self.network.clone(),
+            replay: self.replay.clone(),
+            learner_transactions: self.learner_transactions.clone(),
+            rng: self.rng.clone(),
+            shock_rng: self.shock_rng.clone(),
+            reference_price: self.reference_price,
+            step: self.step,
+            history: self.history.clone(),
+            equity: self.equity,
+        }
+    }
+
+    pub fn restore(checkpoint: EnvCheckpoint) -> io::Result<Self> {
+        Ok(Self {
+            sim: Simulation::restore(&checkpoint.simulation)?,
+            config: checkpoint.config,
+            network: checkpoint.network,
+            replay: checkpoint.replay,
+            learner_transactions: checkpoint.learner_transactions,
+            rng: checkpoint.rng,
+            shock_rng: checkpoint.shock_rng,
+            reference_price: checkpoint.reference_price,
+            step: checkpoint.step,
+            history: checkpoint.history,
+            equity: checkpoint.equity,
+        })
+    }
+
+    /// Writes `checkpoint()` to `path` as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, serde_json::to_vec(&self.checkpoint())?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::restore(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    pub fn config(&self) -> &EnvConfig {
+        &self.config
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.sim
+    }
+
+    fn apply(&mut self, action: Action) -> Result<(), ()> {
+        match action.replacement() {
+            Some(order) => self.replace_order(LEARNER, order),
+            None => Ok(()),
+        }
+    }
+
+    /// Cancels the agent's order, if it has one, and places `order` in the
+    /// same transaction. Under chaos the transaction is only submitted.
+    fn replace_order(&mut self, agent: usize, order: Option<(Side, u64, u64)>) -> Result<(), ()> {
+        let instructions = self.sim.replace_order_instructions(agent, order);
+        if instructions.is_empty() {
+            return Ok(());
+        }
+        let signers = vec![self.sim.agents[agent].owner];
+        match &mut self.network {
+            Some(network) => {
+                let id = network.submit(&self.sim.runtime, instructions, signers);
+                if agent == LEARNER {
+                    self.learner_transactions.push(id);
+                }
+                Ok(())
+            }
+            None => self.sim.send(&instructions, &signers).map(drop).map_err(drop),
+        }
+    }
+
+    /// Moves the reference price, shocks included, unless it's replayed, and has each
+    /// background agent, with `requote_percent` chance, replace its order with
+    /// a random one around it.
+    fn requote_background(&mut self) {
+        if self.replay.is_none() {
+            let volatility = self.config.volatility_bps as i64;
+            let mut bps = self.rng.gen_range(-volatility..=volatility);
+            if self.shock_rng.gen_range(0..100) < self.config.shock_percent {
+                let shock = self.config.shock_bps as i64;
+                bps += if self.shock_rng.gen_bool(0.5) { shock } else { -shock };
+            }
+            self.reference_price = apply_bps(self.reference_price, bps).max(1);
+        }
+
+        let spread = self.config.spread_bps as i64;
+        for agent in LEARNER + 1..=self.config.background_agents {
+            if self.rng.gen_range(0..100) >= self.config.requote_percent {
+                continue;
+            }
+            let side = if self.rng.gen_bool(0.5) { Side::Bid } else { Side::Ask };
+            let price = apply_bps(self.reference_price, self.rng.gen_range(-spread..=spread)).max(1);
+            let size = self.config.order_size.max(2);
+            let quantity = self.rng.gen_range(size / 2..=size + size / 2);
+            // A background agent that runs out of one token just stops
+            // quoting that side
+            let _ = self.replace_order(agent, Some((side, price, quantity)));
+        }
+    }
+
+    fn last_price(&self) -> u64 {
+        match self.sim.market().last_price {
+            0 => self.config.initial_price,
+            price => price,
+        }
+    }
+
+    fn equity(&self) -> u64 {
+        self.sim.equity(LEARNER, self.last_price())
+    }
+
+    fn levels(&self, side: Side) -> Vec<Level> {
+        let mut levels: Vec<Level> = Vec::new();
+        for (_, trade) in self.sim.book(side) {
+            let price = Simulation::order_price(&trade);
+            if let Some(level) = levels.last_mut().filter(|level| level.price == price) {
+                level.quantity += trade.size();
+            } else if levels.len() == self.config.book_depth {
+                break;
+            } else {
+                levels.push(Level {
+                    price,
+                    quantity: trade.size(),
+                });
+            }
+        }
+        levels
+    }
+}
+
+fn apply_bps(price: u64, bps: i64) -> u64 {
+    (price as i128 * (10_000 + bps) as i128 / 10_000) as u64
+}
@@ -0,0 +1,517 @@
+//! Mutation fuzzing of the program's instruction surface. A `Fuzzer` sends
+//! single-instruction transactions signed only by keys an attacker holds:
+//! mutations of instructions that succeeded before, and fresh instructions
+//! for every entrypoint with random data over a random permutation of the
+//! cluster's accounts. The program must never panic, and no transaction may
+//! break conservation:
+//!
+//! - lamports are neither created nor destroyed
+//! - every mint's supply is what its token accounts hold
+//! - a vault holds at least what its balances say it owes
+//!
+//! The program is built with `strict-checks`, so a transaction its own
+//! invariant checks reject is a finding too.
+use std::collections::{BTreeMap, HashMap};
+use std::panic::{self, AssertUnwindSafe};
+
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{AccountDeserialize, Discriminator, InstructionData};
+use anchor_programs::{accounts, instruction, invariants, Balance};
+use base64::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::Runtime;
+use crate::seed::{self, STREAM_FUZZ};
+use crate::sim::{program_instruction, Side, Simulation};
+
+macro_rules! entrypoints {
+    ($($name:ident),* $(,)?) => {
+        &[$((stringify!($name), <instruction::$name as Discriminator>::DISCRIMINATOR)),*]
+    };
+}
+
+/// Every instruction of the program, by name and discriminator.
+pub const ENTRYPOINTS: &[(&str, &[u8])] = entrypoints![
+    InitializeMarket, RegisterAgent, CreateSubaccount, StartSession, SetSessionLimits,
+    DepositTokens, WithdrawTokens, WithdrawAllTokens, TransferCollateral, InternalTransfer,
+    PlaceTrade, PlaceSignedTrade, SubmitAction, SubmitActionsBatch, SetRelayer,
+    ReimburseRelayer, ExecuteTrade, ModifyTrade, CancelTrade, MatchTrades, IndicateAuction,
+    UncrossAuction, SetStpMode, SetMarketFees, SetFeeTiers, SetSettlementMode,
+    SetMatchingPolicy, SetTieBreakRandomness, SetActionEncoding, SetMaxOrderSize, SetTimeBase,
+    SetTradingHours, SetAuctionSlots, SetCircuitBreaker, SetSandwichGuard, SetJupiterRouting,
+    SetMirrorPool, SetOracleConfig, CreatePriceCache, PostPriceUpdate, OpenSettlement,
+    SettleEpoch, CreateOtcOffer, AcceptOtcOffer, CancelOtcOffer, CreateRfq, SubmitQuote,
+    AcceptQuote, CancelRfq, WriteOption, BuyOption, ExerciseOption, ExpireOption,
+    CreateBinaryMarket, MintOutcomeTokens, RedeemOutcomeTokens, ResolveMarket, CreateIndex,
+    MintIndex, RedeemIndex, FlashSwap, FlashLoan, RepayFlashLoan, ExecuteTradeViaJupiter,
+    ExecuteMirrorTrade, ExecuteHybridTrade, OpenbookPlaceOrder, OpenbookCancelOrder,
+    OpenbookManageAccount, SetPerpParams, SetOpenInterestLimits, SetFundingParams,
+    DepositCollateral, WithdrawCollateral, ModifyPosition, LiquidatePosition, DeclareBankruptcy,
+    FundInsurance, UpdateAdlRank, AutoDeleverage, SetVolatilityFees, SetCongestionTiers,
+    SetEpisodeCapital, ResetAgentForEpisode, SetInventoryPenalty, OpenRewardRecord,
+    SettleReward, CreateCurriculum, AdvanceCurriculum, SetAdversary, PlaceAdversaryOrder,
+    SnapshotMarket, Quote, OpenObservationFrame, UpdateObservationFrame, OpenLeaderboard,
+    CreateEmissionsSchedule, OpenEmissionsAccount, ClaimEmissions, SetEmissionsBoost, LockBond,
+    RelockBond, WithdrawBond, DistributeFeesToStakers, ClaimStakingFees, BuybackAndBurn,
+    PublishScoreRoot, ClaimScoredReward, MintAgentNft, UpdateAgentNft, TransferAgent,
+    TransferAgentOwnership, AcceptAgentOwnership, CreateGuild, ElectGuildManager,
+    DepositToGuild, WithdrawShare, SubscribeToAgent, UnsubscribeFromAgent, SetPerformanceFee,
+    FundMirrorLiquidity, MirrorFill, CreateStrategyVault, DepositToStrategy, RequestRedeem,
+    ProcessRedemptions, ReconcileVault, SweepDonations, CollectExcessLamports, CloseFilledTrade,
+    CloseBalance, CloseAgent, InitializeConfig, UpdateConfig, SetEmergencyMode,
+];
+
+/// Values that tend to sit on arithmetic and bounds edges.
+const INTERESTING: [u64; 9] = [
+    0,
+    1,
+    2,
+    u8::MAX as u64,
+    u16::MAX as u64,
+    u32::MAX as u64,
+    i64::MAX as u64,
+    i64::MIN as u64,
+    u64::MAX,
+];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FuzzConfig {
+    pub seed: u64,
+    pub iterations: u64,
+    /// Agents set up before fuzzing, each with an order resting
+    pub agents: usize,
+    /// Longest instruction data generated, discriminator excluded
+    pub max_data_len: usize,
+    pub max_accounts: usize,
+    /// Chance in percent of mutating an instruction that succeeded before
+    /// rather than generating one
+    pub mutate_percent: u8,
+    /// The clock advances a slot every this many iterations
+    pub slot_every: u64,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            iterations: 10_000,
+            agents: 4,
+            max_data_len: 256,
+            max_accounts: 24,
+            mutate_percent: 50,
+            slot_every: 64,
+        }
+    }
+}
+
+/// An instruction as sent, for reproducing a finding.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Case {
+    pub entrypoint: String,
+    /// Base64
+    pub data: String,
+    pub accounts: Vec<CaseAccount>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaseAccount {
+    #[serde(with = "crate::checkpoint::base58")]
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Finding {
+    Panic { iteration: u64, message: String, case: Case },
+    Invariant { iteration: u64, message: String, case: Case },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntrypointStats {
+    pub sent: u64,
+    pub succeeded: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzReport {
+    pub iterations: u64,
+    pub succeeded: u64,
+    pub entrypoints: BTreeMap<String, EntrypointStats>,
+    pub findings: Vec<Finding>,
+}
+
+/// What the invariants are checked on, measured before and after each
+/// transaction.
+struct Measure {
+    lamports: u128,
+    /// Mint: supply and the sum of its token accounts
+    mints: HashMap<Pubkey, (u64, u128)>,
+    /// Vault: owed through balances and held in token accounts it owns
+    vaults: HashMap<Pubkey, (u128, u128)>,
+}
+
+impl Measure {
+    fn of(runtime: &Runtime) -> Self {
+        let mut measure = Self {
+            lamports: 0,
+            mints: HashMap::new(),
+            vaults: HashMap::new(),
+        };
+        let mut held: HashMap<Pubkey, u128> = HashMap::new();
+        for (key, account) in runtime.accounts() {
+            measure.lamports += account.lamports as u128;
+            if account.owner == spl_token::ID && account.data.len() == spl_token::state::Mint::LEN {
+                if let Ok(mint) = spl_token::state::Mint::unpack(&account.data) {
+                    measure.mints.entry(*key).or_default().0 = mint.supply;
+                }
+            } else if account.owner == spl_token::ID && account.data.len() == spl_token::state::Account::LEN {
+                if let Ok(token) = spl_token::state::Account::unpack(&account.data) {
+                    measure.mints.entry(token.mint).or_default().1 += token.amount as u128;
+                    *held.entry(token.owner).or_default() += token.amount as u128;
+                }
+            } else if account.owner == anchor_programs::ID {
+                if let Ok(balance) = Balance::try_deserialize(&mut &account.data[..]) {
+                    measure.vaults.entry(balance.vault).or_default().0 += (balance.amount + balance.reserved) as u128;
+                }
+            }
+        }
+        for (vault, (_, holdings)) in measure.vaults.iter_mut() {
+            *holdings = held.get(vault).copied().unwrap_or_default();
+        }
+        measure
+    }
+
+    /// Invariants that held in `before` and no longer hold here.
+    fn broken_since(&self, before: &Measure) -> Vec<String> {
+        let mut broken = Vec::new();
+        if self.lamports != before.lamports {
+            broken.push(format!("lamports went from {} to {}", before.lamports, self.lamports));
+        }
+        for (mint, &(supply, held)) in &self.mints {
+            let was_sound = before.mints.get(mint).is_none_or(|&(supply, held)| supply as u128 == held);
+            if supply as u128 != held && was_sound {
+                broken.push(format!("mint {mint} has supply {supply} but its accounts hold {held}"));
+            }
+        }
+        for (vault, &(owed, held)) in &self.vaults {
+            let was_sound = before.vaults.get(vault).is_none_or(|&(owed, held)| owed <= held);
+            if owed > held && was_sound {
+                broken.push(format!("vault {vault} owes {owed} through balances but holds {held}"));
+            }
+        }
+        broken.sort();
+        broken
+    }
+}
+
+pub struct Fuzzer {
+    config: FuzzConfig,
+    sim: Simulation,
+    rng: ChaCha8Rng,
+    /// Keys the attacker signs with
+    attackers: Vec<Pubkey>,
+    /// Instructions that succeeded, mutated later
+    corpus: Vec<Instruction>,
+}
+
+impl Fuzzer {
+    /// A market with `config.agents` agents, each with an order resting on
+    /// alternating sides, and the agents' owners as attackers, plus a key
+    /// nothing else knows about. The corpus starts with valid orders,
+    /// cancels, deposits, withdrawals, full withdrawals and matches.
+    pub fn new(config: FuzzConfig) -> Self {
+        let mut sim = Simulation::seeded(config.seed);
+        let outsider = sim.new_key();
+        sim.runtime.airdrop(outsider, 100_000_000_000);
+        let mut corpus = Vec::new();
+        for agent in 0..config.agents {
+            sim.add_agent(1_000_000_000, 1_000_000_000).expect("agent setup failed");
+            let side = if agent % 2 == 0 { Side::Bid } else { Side::Ask };
+            let place = sim.place_order_instruction(agent, side, anchor_programs::PRICE_SCALE, 1_000_000);
+            let keys = sim.agents[agent];
+            sim.send(std::slice::from_ref(&place), &[keys.owner]).expect("order setup failed");
+            corpus.push(place);
+            corpus.push(sim.cancel_order_instruction(agent));
+            if agent % 2 == 1 {
+                corpus.push(sim.match_orders_instruction(agent - 1, agent, outsider));
+            }
+
+            let market = sim.keys;
+            // The agents' wallets were emptied into their deposits
+            sim.mint_to(keys.token_a_account, market.token_a_mint, keys.owner, 1_000_000_000);
+            sim.mint_to(keys.token_b_account, market.token_b_mint, keys.owner, 1_000_000_000);
+            for (mint, token_account, vault, vault_token_account, balance) in [
+                (market.token_a_mint, keys.token_a_account, market.vault_a, market.vault_a_token_account, keys.balance_a),
+                (market.token_b_mint, keys.token_b_account, market.vault_b, market.vault_b_token_account, keys.balance_b),
+            ] {
+                corpus.push(program_instruction(
+                    accounts::DepositTokens {
+                        agent: keys.agent,
+                        market: market.market,
+                        user: keys.owner,
+                        token_mint: mint,
+                        user_token_account: token_account,
+                        vault,
+                        vault_token_account,
+                        balance,
+                        receipt: None,
+                        token_program: spl_token::ID,
+                        system_program: system_program::ID,
+                    },
+                    instruction::DepositTokens {
+                        amount: 1_000_000,
+                        nonce: None,
+                    },
+                ));
+                let withdraw = program_instruction(
+                    accounts::WithdrawTokens {
+                        agent: keys.agent,
+                        market: market.market,
+                        user: keys.owner,
+                        token_mint: mint,
+                        user_token_account: token_account,
+                        vault,
+                        vault_token_account,
+                        balance,
+                        token_program: spl_token::ID,
+                        associated_token_program: spl_associated_token_account::ID,
+                        system_program: system_program::ID,
+                    },
+                    instruction::WithdrawTokens { amount: 1_000_000 },
+                );
+                corpus.push(Instruction {
+                    data: instruction::WithdrawAllTokens {}.data(),
+                    ..withdraw.clone()
+                });
+                corpus.push(withdraw);
+            }
+        }
+        let mut attackers: Vec<Pubkey> = sim.agents.iter().map(|agent| agent.owner).collect();
+        attackers.push(outsider);
+        Self {
+            rng: seed::rng(config.seed, STREAM_FUZZ),
+            config,
+            sim,
+            attackers,
+            corpus,
+        }
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.sim
+    }
+
+    pub fn run(&mut self) -> FuzzReport {
+        let mut report = FuzzReport::default();
+        for iteration in 0..self.config.iterations {
+            if iteration > 0 && iteration % self.config.slot_every.max(1) == 0 {
+                self.sim.runtime.advance_slots(1);
+            }
+            let mutate = !self.corpus.is_empty() && self.rng.gen_range(0..100) < self.config.mutate_percent;
+            let mut ix = if mutate {
+                let mut ix = self.corpus.choose(&mut self.rng).unwrap().clone();
+                for _ in 0..self.rng.gen_range(1..=4) {
+                    self.mutate(&mut ix);
+                }
+                ix
+            } else {
+                self.generate()
+            };
+            // Only the attacker's keys can sign; the program sees anyone else
+            // as a plain account
+            for meta in &mut ix.accounts {
+                meta.is_signer &= self.attackers.contains(&meta.pubkey);
+            }
+            let mut signers: Vec<Pubkey> = ix.accounts.iter().filter(|meta| meta.is_signer).map(|meta| meta.pubkey).collect();
+            signers.dedup();
+
+            let case = case(&ix);
+            let stats = report.entrypoints.entry(case.entrypoint.clone()).or_default();
+            stats.sent += 1;
+            let before = Measure::of(&self.sim.runtime);
+            let sim = &mut self.sim;
+            match panic::catch_unwind(AssertUnwindSafe(|| sim.send(std::slice::from_ref(&ix), &signers))) {
+                Err(payload) => {
+                    let message = payload
+                        .downcast_ref::<String>()
+                        .cloned()
+                        .or_else(|| payload.downcast_ref::<&str>().map(|message| message.to_string()))
+                        .unwrap_or_default();
+                    report.findings.push(Finding::Panic { iteration, message, case });
+                }
+                Ok(Ok(_)) => {
+                    stats.succeeded += 1;
+                    report.succeeded += 1;
+                    for message in Measure::of(&self.sim.runtime).broken_since(&before) {
+                        report.findings.push(Finding::Invariant {
+                            iteration,
+                            message,
+                            case: case.clone(),
+                        });
+                    }
+                    if self.corpus.len() < 1_024 {
+                        self.corpus.push(ix);
+                    }
+                }
+                Ok(Err(failure)) => {
+                    if failure.code().is_some_and(invariants::is_violation) {
+                        report.findings.push(Finding::Invariant {
+                            iteration,
+                            message: "the program's invariant checks failed".into(),
+                            case,
+                        });
+                    }
+                }
+            }
+            report.iterations += 1;
+        }
+        report
+    }
+
+    /// Every address worth passing: the cluster's accounts, the programs
+    /// and sysvars, and a key with no account. Sorted, so runs repeat.
+    fn addresses(&self) -> Vec<Pubkey> {
+        let mut addresses: Vec<Pubkey> = self.sim.runtime.accounts().map(|(key, _)| *key).collect();
+        addresses.extend([
+            system_program::ID,
+            sysvar::clock::ID,
+            sysvar::rent::ID,
+            sysvar::instructions::ID,
+            Pubkey::default(),
+        ]);
+        addresses.sort();
+        addresses.dedup();
+        addresses
+    }
+
+    fn random_meta(&mut self, addresses: &[Pubkey]) -> AccountMeta {
+        let pubkey = if self.rng.gen_range(0..4) == 0 {
+            *self.attackers.choose(&mut self.rng).unwrap()
+        } else {
+            *addresses.choose(&mut self.rng).unwrap()
+        };
+        AccountMeta {
+            pubkey,
+            is_signer: self.attackers.contains(&pubkey) && self.rng.gen_bool(0.5),
+            is_writable: self.rng.gen_bool(0.5),
+        }
+    }
+
+    fn random_bytes(&mut self, len: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(len + 8);
+        while data.len() < len {
+            if self.rng.gen_bool(0.5) {
+                let value = *INTERESTING.choose(&mut self.rng).unwrap();
+                data.extend_from_slice(&value.to_le_bytes());
+            } else {
+                data.push(self.rng.gen());
+            }
+        }
+        data.truncate(len);
+        data
+    }
+
+    /// A random entrypoint with random data over random accounts.
+    fn generate(&mut self) -> Instruction {
+        let (_, discriminator) = *ENTRYPOINTS.choose(&mut self.rng).unwrap();
+        let addresses = self.addresses();
+        let accounts = (0..self.rng.gen_range(0..=self.config.max_accounts))
+            .map(|_| self.random_meta(&addresses))
+            .collect();
+        let len = self.rng.gen_range(0..=self.config.max_data_len);
+        let mut data = discriminator.to_vec();
+        data.extend(self.random_bytes(len));
+        Instruction {
+            program_id: anchor_programs::ID,
+            accounts,
+            data,
+        }
+    }
+
+    fn mutate(&mut self, ix: &mut Instruction) {
+        let rng = &mut self.rng;
+        let (data, accounts) = (&mut ix.data, &mut ix.accounts);
+        match rng.gen_range(0..11) {
+            0 if !data.is_empty() => {
+                let at = rng.gen_range(0..data.len());
+                data[at] ^= 1 << rng.gen_range(0..8);
+            }
+            1 if !data.is_empty() => {
+                let at = rng.gen_range(0..data.len());
+                data[at] = rng.gen();
+            }
+            2 if data.len() >= 8 => {
+                let at = rng.gen_range(0..=data.len() - 8);
+                let value = *INTERESTING.choose(rng).unwrap();
+                data[at..at + 8].copy_from_slice(&value.to_le_bytes());
+            }
+            3 => {
+                let len = rng.gen_range(0..=data.len());
+                data.truncate(len);
+            }
+            4 => {
+                let len = rng.gen_range(1..=16);
+                let bytes = self.random_bytes(len);
+                ix.data.extend(bytes);
+            }
+            5 if data.len() >= 8 => {
+                let (_, discriminator) = *ENTRYPOINTS.choose(rng).unwrap();
+                data[..8].copy_from_slice(discriminator);
+            }
+            6 if accounts.len() >= 2 => {
+                let (i, j) = (rng.gen_range(0..accounts.len()), rng.gen_range(0..accounts.len()));
+                accounts.swap(i, j);
+            }
+            7 if !accounts.is_empty() => {
+                let at = rng.gen_range(0..accounts.len());
+                let addresses = self.addresses();
+                ix.accounts[at] = self.random_meta(&addresses);
+            }
+            8 if !accounts.is_empty() => {
+                let at = rng.gen_range(0..accounts.len());
+                accounts[at].is_writable ^= true;
+                accounts[at].is_signer ^= true;
+            }
+            9 if !accounts.is_empty() => {
+                let at = rng.gen_range(0..accounts.len());
+                accounts.remove(at);
+            }
+            _ if accounts.len() < self.config.max_accounts => {
+                let at = rng.gen_range(0..=accounts.len());
+                let addresses = self.addresses();
+                let meta = self.random_meta(&addresses);
+                ix.accounts.insert(at, meta);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn case(ix: &Instruction) -> Case {
+    let entrypoint = ENTRYPOINTS
+        .iter()
+        .find(|(_, discriminator)| ix.data.starts_with(discriminator))
+        .map_or("unknown", |(name, _)| name);
+    Case {
+        entrypoint: entrypoint.into(),
+        data: BASE64_STANDARD.encode(&ix.data),
+        accounts: ix
+            .accounts
+            .iter()
+            .map(|meta| CaseAccount {
+                pubkey: meta.pubkey,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+    }
+}
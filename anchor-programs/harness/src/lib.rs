@@ -0,0 +1,33 @@
+//! Simulation harness for the agent market program. `runtime` executes the
+//! program and the native programs it relies on in process, `sim` sets up a
+//! market with agents on top of it, `chaos` puts an unreliable network
+//! between agents and the runtime, `replay` drives a market from recorded
+//! data, `checkpoint` saves and restores cluster state, `env` wraps a
+//! simulation as an environment for reinforcement learning and `scheduler`
+//! runs many markets in parallel. `amm` stands in for the external pool a
+//! mirror market swaps against and `fuzz` throws hostile instructions at the
+//! program. All randomness comes from `seed`, so runs
+//! are reproducible from their seed.
+#![allow(deprecated)]
+
+pub mod amm;
+pub mod chaos;
+pub mod checkpoint;
+pub mod env;
+pub mod fuzz;
+pub mod replay;
+pub mod runtime;
+pub mod scheduler;
+pub mod seed;
+pub mod sim;
+
+pub use amm::Pool;
+pub use chaos::{ChaosConfig, ChaosNetwork, Delivery, Latency, Outcome, TxId};
+pub use checkpoint::Checkpoint;
+pub use env::{Action, EnvCheckpoint, EnvConfig, Observation, StepResult, TradingEnv};
+pub use fuzz::{Finding, FuzzConfig, FuzzReport, Fuzzer};
+pub use replay::{Replay, ReplayConfig, ReplayError, ReplayFeed};
+pub use runtime::{Account, Failure, Processor, Receipt, Runtime};
+pub use scheduler::{Metrics, NoiseTrader, Run, Scheduler, SchedulerConfig, Strategy};
+pub use seed::{KeySource, Keypair};
+pub use sim::{AgentKeys, Fill, MarketKeys, Side, Simulation, SimulationCheckpoint};
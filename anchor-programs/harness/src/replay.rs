@@ -0,0 +1,413 @@
+//! Replays recorded prices or trades into a simulated market. Each record
+//! moves the clock to its time, updates the market's oracle the way the
+//! oracle network would and, for trades, crosses the same quantity on chain
+//! between two replay agents so the market's last price and TWAP follow too.
+//!
+//! Records come from CSV with a header row. The columns used are
+//! `timestamp` (or `time`, `publish_time`), `price`, and optionally `expo`
+//! (a Pyth exponent applied to `price`), `conf` (or `confidence`) and
+//! `quantity` (or `size`, `amount`, `qty`) in whole token B. Timestamps are
+//! unix seconds, unix milliseconds or `YYYY-MM-DD HH:MM:SS[.fff]` UTC. Other
+//! columns are ignored.
+//!
+//! Parquet is out of scope: reading it would pull arrow into the harness for
+//! one input format. Recordings kept as Parquet replay once exported to CSV
+//! with the same columns, e.g. by DuckDB's `COPY ... TO 'trades.csv'`.
+use std::fmt;
+use std::path::Path;
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_program;
+use anchor_programs::oracle::PYTH_RECEIVER_PROGRAM_ID;
+use anchor_programs::{accounts, instruction, PRICE_SCALE};
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{Account, Failure, SLOT_MS};
+use crate::sim::{program_instruction, Side, Simulation, SIM_DECIMALS};
+
+/// Feed id of the replayed pull-oracle feed.
+pub const REPLAY_FEED_ID: [u8; 32] = *b"neuraltrader-harness-replay-feed";
+
+// Pyth v2 price account, as read by the program's oracle module
+const PYTH_PRICE_ACCOUNT_LEN: usize = 3312;
+const PYTH_EXPO: i32 = -8;
+// Pyth receiver PriceUpdateV2, fully verified
+const PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const PRICE_UPDATE_LEN: usize = 134;
+
+/// How recorded prices reach the program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayFeed {
+    /// A Pyth v2 price account, rewritten in place
+    PythV2,
+    /// Receiver-program price updates posted to the market's `PriceCache`
+    PythPull,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplayConfig {
+    pub feed: ReplayFeed,
+    /// Cross each record's quantity on chain at its price
+    pub replay_trades: bool,
+    /// Oracle limits set on the market
+    pub max_staleness_slots: u64,
+    pub max_confidence_bps: u16,
+    /// Deposited for each replay agent in both tokens
+    pub trader_balance: u64,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            feed: ReplayFeed::PythV2,
+            replay_trades: true,
+            max_staleness_slots: 150,
+            max_confidence_bps: 10_000,
+            trader_balance: 1_000_000_000_000_000,
+        }
+    }
+}
+
+/// One recorded price or trade.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub timestamp_ms: i64,
+    /// Token A per token B, `PRICE_SCALE` fixed point
+    pub price: u64,
+    /// Same scale as `price`
+    pub conf: u64,
+    /// Token B base units traded, 0 for a price alone
+    pub quantity: u64,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+    Failure(Failure),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "{error}"),
+            ReplayError::Parse { line, message } => write!(f, "line {line}: {message}"),
+            ReplayError::Failure(failure) => write!(
+                f,
+                "instruction {} failed: {}",
+                failure.instruction, failure.error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<Failure> for ReplayError {
+    fn from(failure: Failure) -> Self {
+        ReplayError::Failure(failure)
+    }
+}
+
+pub fn load_csv(path: impl AsRef<Path>) -> Result<Vec<Record>, ReplayError> {
+    parse_csv(&std::fs::read_to_string(path).map_err(ReplayError::Io)?)
+}
+
+/// Parses records from CSV text, sorted by time.
+pub fn parse_csv(text: &str) -> Result<Vec<Record>, ReplayError> {
+    let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    let Some((_, header)) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let columns: Vec<String> = header.split(',').map(|name| name.trim().to_ascii_lowercase()).collect();
+    let column = |names: &[&str]| columns.iter().position(|name| names.contains(&name.as_str()));
+    let parse_error = |line: usize, message: String| ReplayError::Parse { line: line + 1, message };
+    let timestamp = column(&["timestamp", "time", "publish_time"]).ok_or(parse_error(0, "no timestamp column".into()))?;
+    let price = column(&["price"]).ok_or(parse_error(0, "no price column".into()))?;
+    let (expo, conf) = (column(&["expo"]), column(&["conf", "confidence"]));
+    let quantity = column(&["quantity", "size", "amount", "qty"]);
+
+    let mut records = Vec::new();
+    for (line, text) in lines {
+        let fields: Vec<&str> = text.split(',').map(str::trim).collect();
+        let field = |index: usize| {
+            fields
+                .get(index)
+                .copied()
+                .ok_or_else(|| parse_error(line, format!("missing column {}", columns[index])))
+        };
+        let number = |index: usize| {
+            field(index)?
+                .parse::<f64>()
+                .map_err(|_| parse_error(line, format!("bad {}", columns[index])))
+        };
+        let scale = match expo {
+            Some(index) => 10f64.powi(number(index)? as i32),
+            None => 1.0,
+        };
+        let to_price = |value: f64| (value * scale * PRICE_SCALE as f64).round() as u64;
+        let timestamp_ms = parse_timestamp(field(timestamp)?)
+            .ok_or_else(|| parse_error(line, format!("bad timestamp {}", field(timestamp).unwrap())))?;
+        records.push(Record {
+            timestamp_ms,
+            price: to_price(number(price)?),
+            conf: conf.map(number).transpose()?.map_or(0, to_price),
+            quantity: quantity
+                .map(number)
+                .transpose()?
+                .map_or(0, |quantity| (quantity.abs() * 10f64.powi(SIM_DECIMALS as i32)).round() as u64),
+        });
+    }
+    records.sort_by_key(|record| record.timestamp_ms);
+    Ok(records)
+}
+
+/// Unix milliseconds from unix seconds, unix milliseconds or a UTC date and
+/// time.
+fn parse_timestamp(text: &str) -> Option<i64> {
+    if let Ok(value) = text.parse::<f64>() {
+        // Anything past the year 5000 in seconds is taken as milliseconds
+        return Some(if value.abs() >= 1e11 { value as i64 } else { (value * 1000.0) as i64 });
+    }
+    let text = text.trim_end_matches(" UTC").trim_end_matches('Z');
+    let (date, time) = text.split_once(['T', ' '])?;
+    let mut date = date.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+    let mut time = time.splitn(3, ':');
+    let (hour, minute) = (time.next()?.parse::<i64>().ok()?, time.next()?.parse::<i64>().ok()?);
+    let seconds = time.next().unwrap_or("0").parse::<f64>().ok()?;
+
+    // Days since the epoch of a proleptic Gregorian date
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    Some((days * 86_400 + hour * 3_600 + minute * 60) * 1000 + (seconds * 1000.0).round() as i64)
+}
+
+/// Records being replayed into one simulation's market.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+    config: ReplayConfig,
+    records: Vec<Record>,
+    next: usize,
+    /// Account set as the market's primary oracle
+    feed: Pubkey,
+    price_update: Pubkey,
+    start_ms: i64,
+    start_slot: u64,
+    // Buyer and seller of replayed trades
+    traders: Option<(usize, usize)>,
+    last_publish_time: i64,
+}
+
+impl Replay {
+    /// Points the market's oracle at a feed the replay controls and, if
+    /// trades are replayed, adds the two agents that trade them.
+    pub fn new(sim: &mut Simulation, records: Vec<Record>, config: ReplayConfig) -> Result<Self, ReplayError> {
+        let admin = sim.admin;
+        let market = sim.keys.market;
+        let mut instructions = Vec::new();
+        let feed = match config.feed {
+            ReplayFeed::PythV2 => sim.new_key(),
+            ReplayFeed::PythPull => {
+                let price_cache =
+                    Pubkey::find_program_address(&[b"price_cache", market.as_ref()], &anchor_programs::ID).0;
+                instructions.push(program_instruction(
+                    accounts::CreatePriceCache {
+                        market,
+                        price_cache,
+                        authority: admin,
+                        system_program: system_program::ID,
+                    },
+                    instruction::CreatePriceCache {
+                        feed_id: REPLAY_FEED_ID,
+                    },
+                ));
+                price_cache
+            }
+        };
+        instructions.push(program_instruction(
+            accounts::SetOracleConfig { market, authority: admin },
+            instruction::SetOracleConfig {
+                primary_oracle: feed,
+                fallback_oracle: Pubkey::default(),
+                max_staleness_slots: config.max_staleness_slots,
+                max_confidence_bps: config.max_confidence_bps,
+            },
+        ));
+        sim.send(&instructions, &[admin])?;
+
+        let traders = if config.replay_trades {
+            let balance = config.trader_balance;
+            Some((sim.add_agent(balance, balance)?, sim.add_agent(balance, balance)?))
+        } else {
+            None
+        };
+        let price_update = sim.new_key();
+        let clock = sim.runtime.clock();
+        Ok(Self {
+            start_ms: records.first().map_or(0, |record| record.timestamp_ms),
+            start_slot: clock.slot,
+            config,
+            records,
+            next: 0,
+            feed,
+            price_update,
+            traders,
+            last_publish_time: i64::MIN,
+        })
+    }
+
+    pub fn config(&self) -> &ReplayConfig {
+        &self.config
+    }
+
+    /// The market's oracle, a Pyth price account or the market's `PriceCache`.
+    pub fn feed(&self) -> Pubkey {
+        self.feed
+    }
+
+    /// Replay agents' indices as buyer and seller, if trades are replayed.
+    pub fn traders(&self) -> Option<(usize, usize)> {
+        self.traders
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.records.len() - self.next
+    }
+
+    pub fn next_record(&self) -> Option<&Record> {
+        self.records.get(self.next)
+    }
+
+    /// Slot the next record plays at, `SLOT_MS` per slot from the first
+    /// record's time.
+    pub fn next_slot(&self) -> Option<u64> {
+        let record = self.records.get(self.next)?;
+        Some(self.start_slot + ((record.timestamp_ms - self.start_ms) / SLOT_MS) as u64)
+    }
+
+    /// Plays the next record, warping the clock forward to it first.
+    pub fn step(&mut self, sim: &mut Simulation) -> Option<Result<Record, ReplayError>> {
+        let slot = self.next_slot()?;
+        let record = self.records[self.next];
+        self.next += 1;
+        let runtime = &mut sim.runtime;
+        if slot > runtime.clock().slot {
+            runtime.warp_to_slot(slot);
+        }
+        runtime.set_unix_timestamp(record.timestamp_ms.div_euclid(1000).max(runtime.clock().unix_timestamp));
+        Some(self.play(sim, record).map(|()| record))
+    }
+
+    /// Plays every remaining record, returning how many were played.
+    pub fn run(&mut self, sim: &mut Simulation) -> Result<usize, ReplayError> {
+        let mut played = 0;
+        while let Some(result) = self.step(sim) {
+            result?;
+            played += 1;
+        }
+        Ok(played)
+    }
+
+    fn play(&mut self, sim: &mut Simulation, record: Record) -> Result<(), ReplayError> {
+        let slot = sim.runtime.clock().slot;
+        match self.config.feed {
+            ReplayFeed::PythV2 => {
+                let data = pyth_price_account(record.price, record.conf, slot);
+                let lamports = sim.runtime.rent().minimum_balance(data.len());
+                // The feed's owner isn't checked, only its layout
+                sim.runtime.set_account(
+                    self.feed,
+                    Account {
+                        lamports,
+                        data,
+                        owner: system_program::ID,
+                        executable: false,
+                    },
+                );
+            }
+            // The cache only takes strictly newer publish times
+            ReplayFeed::PythPull if record.timestamp_ms.div_euclid(1000) > self.last_publish_time => {
+                let publish_time = record.timestamp_ms.div_euclid(1000);
+                let data = price_update_account(record.price, record.conf, publish_time, slot);
+                let lamports = sim.runtime.rent().minimum_balance(data.len());
+                sim.runtime.set_account(
+                    self.price_update,
+                    Account {
+                        lamports,
+                        data,
+                        owner: PYTH_RECEIVER_PROGRAM_ID,
+                        executable: false,
+                    },
+                );
+                let post = program_instruction(
+                    accounts::PostPriceUpdate {
+                        market: sim.keys.market,
+                        price_cache: self.feed,
+                        price_update: self.price_update,
+                    },
+                    instruction::PostPriceUpdate {},
+                );
+                let admin = sim.admin;
+                sim.send(&[post], &[admin])?;
+                self.last_publish_time = publish_time;
+            }
+            ReplayFeed::PythPull => {}
+        }
+
+        if let (Some((buyer, seller)), true) = (self.traders, record.quantity > 0 && record.price > 0) {
+            sim.place_order(seller, Side::Ask, record.price, record.quantity)?;
+            sim.place_order(buyer, Side::Bid, record.price, record.quantity)?;
+            let matched = sim.match_orders(buyer, seller);
+            sim.cancel_order(buyer)?;
+            sim.cancel_order(seller)?;
+            matched?;
+        }
+        Ok(())
+    }
+}
+
+/// `value` in units of `10^PYTH_EXPO`.
+fn to_pyth(value: u64) -> i64 {
+    (value as i128 * 10i128.pow(PYTH_EXPO.unsigned_abs()) / PRICE_SCALE as i128) as i64
+}
+
+/// A trading Pyth v2 price account quoting `price` at `slot`.
+fn pyth_price_account(price: u64, conf: u64, slot: u64) -> Vec<u8> {
+    let mut data = vec![0; PYTH_PRICE_ACCOUNT_LEN];
+    data[0..4].copy_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+    data[4..8].copy_from_slice(&2u32.to_le_bytes());
+    data[8..12].copy_from_slice(&3u32.to_le_bytes());
+    data[20..24].copy_from_slice(&PYTH_EXPO.to_le_bytes());
+    data[208..216].copy_from_slice(&to_pyth(price).to_le_bytes());
+    data[216..224].copy_from_slice(&(to_pyth(conf) as u64).to_le_bytes());
+    data[224..228].copy_from_slice(&1u32.to_le_bytes());
+    data[232..240].copy_from_slice(&slot.to_le_bytes());
+    data
+}
+
+/// A fully verified receiver-program `PriceUpdateV2` for the replay feed.
+fn price_update_account(price: u64, conf: u64, publish_time: i64, slot: u64) -> Vec<u8> {
+    let mut data = Vec::with_capacity(PRICE_UPDATE_LEN);
+    data.extend_from_slice(&PRICE_UPDATE_DISCRIMINATOR);
+    data.extend_from_slice(&[0; 32]); // write authority
+    data.push(1); // VerificationLevel::Full
+    data.extend_from_slice(&REPLAY_FEED_ID);
+    data.extend_from_slice(&to_pyth(price).to_le_bytes());
+    data.extend_from_slice(&(to_pyth(conf) as u64).to_le_bytes());
+    data.extend_from_slice(&PYTH_EXPO.to_le_bytes());
+    data.extend_from_slice(&publish_time.to_le_bytes());
+    data.extend_from_slice(&publish_time.to_le_bytes()); // previous publish time
+    data.extend_from_slice(&to_pyth(price).to_le_bytes()); // EMA price
+    data.extend_from_slice(&(to_pyth(conf) as u64).to_le_bytes()); // EMA conf
+    data.extend_from_slice(&slot.to_le_bytes());
+    data.resize(PRICE_UPDATE_LEN, 0);
+    data
+}
+
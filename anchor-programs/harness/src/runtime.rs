@@ -0,0 +1,930 @@
+//! In-process execution of the program. Transactions run natively against an
+//! account store, with the syscalls programs make (logging, CPI, sysvars and
+//! return data) served through `SyscallStubs`, so a whole market can be
+//! simulated without a validator. Accounts are handed to each program in the
+//! loader's own input buffer layout, which keeps `AccountInfo::realloc` and
+//! `assign` working, and the runtime's ownership, lamport and rent rules are
+//! checked after every instruction. The system program is emulated here; SPL
+//! Token and the associated token account program run their own processors.
+//! Compute units and signatures are not checked: signers are named, not signed
+//! for.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Once;
+
+use anchor_lang::solana_program::account_info::{AccountInfo, MAX_PERMITTED_DATA_INCREASE};
+use anchor_lang::solana_program::clock::{Clock, DEFAULT_SLOTS_PER_EPOCH};
+use anchor_lang::solana_program::entrypoint::{
+    self, ProgramResult, BPF_ALIGN_OF_U128, NON_DUP_MARKER,
+};
+use anchor_lang::solana_program::epoch_schedule::EpochSchedule;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, BorrowedAccountMeta, BorrowedInstruction,
+};
+use anchor_lang::solana_program::{bpf_loader, bpf_loader_upgradeable, pubkey, system_program, sysvar};
+use anchor_lang::AnchorDeserialize;
+use base64::prelude::{Engine, BASE64_STANDARD};
+
+/// Native entrypoint of a program, such as an Anchor program's `entry`.
+pub type Processor = for<'a> fn(&Pubkey, &'a [AccountInfo<'a>], &[u8]) -> ProgramResult;
+
+/// Milliseconds of wall clock each slot stands for.
+pub const SLOT_MS: i64 = 400;
+
+const NATIVE_LOADER_ID: Pubkey = pubkey!("NativeLoader1111111111111111111111111111111");
+const GENESIS_TIMESTAMP: i64 = 1_700_000_000;
+const SYSCALL_SUCCESS: u64 = 0;
+const SYSCALL_UNSUPPORTED_SYSVAR: u64 = 17 << 32;
+const SYSCALL_OFFSET_LENGTH_EXCEEDS_SYSVAR: u64 = 15 << 32;
+
+// System program instruction tags and errors, as bincode encodes them
+const SYSTEM_CREATE_ACCOUNT: u32 = 0;
+const SYSTEM_ASSIGN: u32 = 1;
+const SYSTEM_TRANSFER: u32 = 2;
+const SYSTEM_ALLOCATE: u32 = 8;
+const SYSTEM_ERROR_ACCOUNT_IN_USE: u32 = 0;
+const SYSTEM_ERROR_INSUFFICIENT_FUNDS: u32 = 1;
+
+/// Stored state of one account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Account {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+/// Logs of a transaction that ran to completion.
+#[derive(Clone, Debug, Default)]
+pub struct Receipt {
+    pub logs: Vec<String>,
+    /// Return data the last program invoked left set, and that program
+    pub return_data: Option<(Pubkey, Vec<u8>)>,
+}
+
+/// A transaction that failed. None of its changes were kept.
+#[derive(Clone, Debug)]
+pub struct Failure {
+    /// Index of the instruction that failed
+    pub instruction: usize,
+    pub error: ProgramError,
+    pub logs: Vec<String>,
+}
+
+impl Receipt {
+    /// Every event of type `T` the transaction emitted, in order.
+    pub fn events<T: anchor_lang::Event>(&self) -> Vec<T> {
+        decode_events(&self.logs)
+    }
+
+    /// The return data decoded as `T`, as an Anchor instruction returning
+    /// `Result<T>` sets it.
+    pub fn return_value<T: AnchorDeserialize>(&self) -> Option<T> {
+        let (_, data) = self.return_data.as_ref()?;
+        T::try_from_slice(data).ok()
+    }
+}
+
+impl Failure {
+    /// The Anchor error code, if the program failed with one.
+    pub fn code(&self) -> Option<u32> {
+        match self.error {
+            ProgramError::Custom(code) => Some(code),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn decode_events<T: anchor_lang::Event>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|line| line.strip_prefix("Program data: "))
+        .filter_map(|data| BASE64_STANDARD.decode(data).ok())
+        .filter(|data| data.starts_with(T::DISCRIMINATOR))
+        .filter_map(|data| T::try_from_slice(&data[T::DISCRIMINATOR.len()..]).ok())
+        .collect()
+}
+
+/// Points stdout at stderr and returns a handle on the original stdout.
+/// Off chain `msg!` prints straight to stdout, so tools whose output is
+/// machine-read call this first and write to the handle instead.
+pub fn redirect_program_logs() -> std::fs::File {
+    use std::os::fd::FromRawFd;
+
+    // SAFETY: only duplicates the process's standard descriptors
+    unsafe {
+        let fd = libc::dup(libc::STDOUT_FILENO);
+        assert!(fd >= 0, "failed to duplicate stdout");
+        libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO);
+        std::fs::File::from_raw_fd(fd)
+    }
+}
+
+/// Account store, programs and clock of one simulated cluster.
+#[derive(Clone)]
+pub struct Runtime {
+    accounts: HashMap<Pubkey, Account>,
+    programs: HashMap<Pubkey, Processor>,
+    clock: Clock,
+    // Wall time in milliseconds, so slots shorter than a second add up
+    unix_ms: i64,
+    rent: Rent,
+}
+
+impl Runtime {
+    /// A cluster with the program deployed, upgradeable by
+    /// `upgrade_authority`, next to the system, token and associated token
+    /// account programs.
+    pub fn new(upgrade_authority: Pubkey) -> Self {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            program_stubs::set_syscall_stubs(Box::new(Stubs));
+        });
+
+        let mut runtime = Self {
+            accounts: HashMap::new(),
+            programs: HashMap::new(),
+            clock: Clock {
+                slot: 1,
+                unix_timestamp: GENESIS_TIMESTAMP,
+                epoch_start_timestamp: GENESIS_TIMESTAMP,
+                ..Clock::default()
+            },
+            unix_ms: GENESIS_TIMESTAMP * 1000,
+            rent: Rent::default(),
+        };
+        runtime.add_builtin(system_program::ID, system_processor);
+        runtime.add_program(spl_token::ID, spl_token::processor::Processor::process);
+        runtime.add_program(
+            spl_associated_token_account::ID,
+            spl_associated_token_account::processor::process_instruction,
+        );
+        runtime.add_upgradeable_program(anchor_programs::ID, anchor_programs::entry, upgrade_authority);
+        runtime.sync_sysvars();
+        runtime
+    }
+
+    fn add_builtin(&mut self, program_id: Pubkey, processor: Processor) {
+        self.programs.insert(program_id, processor);
+        self.accounts.insert(
+            program_id,
+            Account {
+                lamports: 1,
+                data: Vec::new(),
+                owner: NATIVE_LOADER_ID,
+                executable: true,
+            },
+        );
+    }
+
+    /// Deploys a native `processor` at `program_id`, e.g. a stand-in for an
+    /// external venue.
+    pub fn add_program(&mut self, program_id: Pubkey, processor: Processor) {
+        self.programs.insert(program_id, processor);
+        let lamports = self.rent.minimum_balance(0);
+        self.accounts.insert(
+            program_id,
+            Account {
+                lamports,
+                data: Vec::new(),
+                owner: bpf_loader::ID,
+                executable: true,
+            },
+        );
+    }
+
+    fn add_upgradeable_program(
+        &mut self,
+        program_id: Pubkey,
+        processor: Processor,
+        upgrade_authority: Pubkey,
+    ) {
+        self.programs.insert(program_id, processor);
+        let (programdata, _) =
+            Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID);
+
+        // UpgradeableLoaderState::Program and ::ProgramData, bincode encoded
+        let mut program = 2u32.to_le_bytes().to_vec();
+        program.extend_from_slice(programdata.as_ref());
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&self.clock.slot.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(upgrade_authority.as_ref());
+
+        for (key, data, executable) in [(program_id, program, true), (programdata, data, false)] {
+            let lamports = self.rent.minimum_balance(data.len());
+            self.accounts.insert(
+                key,
+                Account {
+                    lamports,
+                    data,
+                    owner: bpf_loader_upgradeable::ID,
+                    executable,
+                },
+            );
+        }
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Option<&Account> {
+        self.accounts.get(key)
+    }
+
+    /// Every stored account, for snapshots and metrics.
+    pub fn accounts(&self) -> impl Iterator<Item = (&Pubkey, &Account)> {
+        self.accounts.iter()
+    }
+
+    /// Overwrites an account outright, bypassing every program.
+    pub fn set_account(&mut self, key: Pubkey, account: Account) {
+        self.accounts.insert(key, account);
+    }
+
+    /// Decodes an Anchor account, or None if it is missing or isn't a `T`.
+    pub fn get<T: anchor_lang::AccountDeserialize>(&self, key: &Pubkey) -> Option<T> {
+        let account = self.accounts.get(key)?;
+        T::try_deserialize(&mut &account.data[..]).ok()
+    }
+
+    /// Decodes an Anchor zero-copy account, or None if it is missing or
+    /// isn't a `T`.
+    pub fn load<T: anchor_lang::ZeroCopy + anchor_lang::Owner>(&self, key: &Pubkey) -> Option<T> {
+        let account = self.accounts.get(key)?;
+        let data = account.data.strip_prefix(T::DISCRIMINATOR)?;
+        let data = data.get(..std::mem::size_of::<T>())?;
+        (account.owner == T::owner()).then(|| anchor_lang::__private::bytemuck::pod_read_unaligned(data))
+    }
+
+    /// Credits `lamports` to a system account, creating it if needed.
+    pub fn airdrop(&mut self, key: Pubkey, lamports: u64) {
+        self.accounts.entry(key).or_default().lamports += lamports;
+    }
+
+    pub fn rent(&self) -> &Rent {
+        &self.rent
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Moves the clock to `slot`, advancing wall time `SLOT_MS` per slot.
+    pub fn warp_to_slot(&mut self, slot: u64) {
+        let elapsed = slot as i64 - self.clock.slot as i64;
+        self.clock.slot = slot;
+        self.unix_ms += elapsed * SLOT_MS;
+        self.clock.unix_timestamp = self.unix_ms.div_euclid(1000);
+        let epoch = slot / DEFAULT_SLOTS_PER_EPOCH;
+        if epoch != self.clock.epoch {
+            self.clock.epoch = epoch;
+            self.clock.leader_schedule_epoch = epoch + 1;
+            self.clock.epoch_start_timestamp = self.clock.unix_timestamp;
+        }
+        self.sync_sysvars();
+    }
+
+    pub fn advance_slots(&mut self, slots: u64) {
+        self.warp_to_slot(self.clock.slot + slots);
+    }
+
+    /// Sets wall time without moving the slot, e.g. to follow recorded data.
+    pub fn set_unix_timestamp(&mut self, unix_timestamp: i64) {
+        self.unix_ms = unix_timestamp * 1000;
+        self.clock.unix_timestamp = unix_timestamp;
+        self.sync_sysvars();
+    }
+
+    fn sync_sysvars(&mut self) {
+        for (key, data) in [
+            (sysvar::clock::ID, clock_bytes(&self.clock)),
+            (sysvar::rent::ID, rent_bytes(&self.rent)),
+        ] {
+            let lamports = self.rent.minimum_balance(data.len());
+            self.accounts.insert(
+                key,
+                Account {
+                    lamports,
+                    data,
+                    owner: sysvar::ID,
+                    executable: false,
+                },
+            );
+        }
+    }
+
+    /// Runs `instructions` as one atomic transaction signed by `signers`.
+    pub fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[Pubkey],
+    ) -> Result<Receipt, Failure> {
+        let fail = |instruction, error, logs| {
+            Err(Failure {
+                instruction,
+                error,
+                logs,
+            })
+        };
+
+        // Privileges are per transaction: a key signs or is writable in every
+        // instruction if it is in any of them
+        let mut privileges: HashMap<Pubkey, (bool, bool)> = HashMap::new();
+        for (index, ix) in instructions.iter().enumerate() {
+            privileges.entry(ix.program_id).or_default();
+            for meta in &ix.accounts {
+                if meta.is_signer && !signers.contains(&meta.pubkey) {
+                    return fail(index, ProgramError::MissingRequiredSignature, Vec::new());
+                }
+                let entry = privileges.entry(meta.pubkey).or_default();
+                entry.0 |= meta.is_signer;
+                entry.1 |= meta.is_writable;
+            }
+        }
+        let messages: Vec<Vec<AccountMeta>> = instructions
+            .iter()
+            .map(|ix| {
+                ix.accounts
+                    .iter()
+                    .map(|meta| {
+                        let (is_signer, is_writable) = privileges[&meta.pubkey];
+                        AccountMeta {
+                            pubkey: meta.pubkey,
+                            is_signer,
+                            is_writable,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let borrowed: Vec<BorrowedInstruction> = instructions
+            .iter()
+            .zip(&messages)
+            .map(|(ix, metas)| BorrowedInstruction {
+                program_id: &ix.program_id,
+                accounts: metas
+                    .iter()
+                    .map(|meta| BorrowedAccountMeta {
+                        pubkey: &meta.pubkey,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                    .collect(),
+                data: &ix.data,
+            })
+            .collect();
+        let mut instructions_data = instructions_sysvar::construct_instructions_data(&borrowed);
+
+        let undo: HashMap<Pubkey, Option<Account>> = privileges
+            .keys()
+            .map(|key| (*key, self.accounts.get(key).cloned()))
+            .collect();
+        CONTEXT.with(|context| {
+            *context.borrow_mut() = Some(Context {
+                programs: self.programs.clone(),
+                clock: self.clock.clone(),
+                rent: self.rent.clone(),
+                logs: Vec::new(),
+                return_data: None,
+                stack: Vec::new(),
+            })
+        });
+        let take_context = || CONTEXT.with(|context| context.borrow_mut().take());
+        let take_logs = || take_context().map(|context| context.logs).unwrap_or_default();
+
+        for (index, (ix, metas)) in instructions.iter().zip(&messages).enumerate() {
+            instructions_sysvar::store_current_index(&mut instructions_data, index as u16);
+            let mut accounts: HashMap<Pubkey, Account> = metas
+                .iter()
+                .map(|meta| {
+                    let account = if meta.pubkey == instructions_sysvar::ID {
+                        Account {
+                            lamports: 1,
+                            data: instructions_data.clone(),
+                            owner: sysvar::ID,
+                            executable: false,
+                        }
+                    } else {
+                        self.accounts.get(&meta.pubkey).cloned().unwrap_or_default()
+                    };
+                    (meta.pubkey, account)
+                })
+                .collect();
+            let result = if self.accounts.get(&ix.program_id).is_some_and(|program| program.executable) {
+                execute(&ix.program_id, metas, &mut accounts, &ix.data)
+            } else {
+                Err(ProgramError::IncorrectProgramId)
+            };
+            if let Err(error) = result {
+                self.restore(undo);
+                return fail(index, error, take_logs());
+            }
+            accounts.remove(&instructions_sysvar::ID);
+            self.accounts.extend(accounts);
+        }
+
+        // Every account the transaction left funded must be rent exempt
+        for (key, previous) in &undo {
+            let Some(account) = self.accounts.get(key) else {
+                continue;
+            };
+            if account.lamports > 0
+                && previous.as_ref() != Some(account)
+                && !self.rent.is_exempt(account.lamports, account.data.len())
+            {
+                self.restore(undo);
+                return fail(
+                    instructions.len().saturating_sub(1),
+                    ProgramError::AccountNotRentExempt,
+                    take_logs(),
+                );
+            }
+        }
+        self.accounts
+            .retain(|_, account| account.lamports > 0 || account.executable);
+        let (logs, return_data) = take_context()
+            .map(|context| (context.logs, context.return_data))
+            .unwrap_or_default();
+        Ok(Receipt { logs, return_data })
+    }
+
+    fn restore(&mut self, undo: HashMap<Pubkey, Option<Account>>) {
+        for (key, account) in undo {
+            match account {
+                Some(account) => self.accounts.insert(key, account),
+                None => self.accounts.remove(&key),
+            };
+        }
+    }
+}
+
+struct Context {
+    programs: HashMap<Pubkey, Processor>,
+    clock: Clock,
+    rent: Rent,
+    logs: Vec<String>,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+    // Programs currently executing, innermost last
+    stack: Vec<Frame>,
+}
+
+/// One executing instruction: its program, the privileges it was given and
+/// the state of its accounts as of its last check, the start of the
+/// instruction or its latest CPI.
+struct Frame {
+    program_id: Pubkey,
+    privileges: HashMap<Pubkey, (bool, bool)>,
+    baseline: HashMap<Pubkey, Account>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Option<Context>> = const { RefCell::new(None) };
+}
+
+fn with_context<R>(f: impl FnOnce(&mut Context) -> R) -> Option<R> {
+    CONTEXT.with(|context| context.borrow_mut().as_mut().map(f))
+}
+
+fn log(message: String) {
+    with_context(|context| context.logs.push(message));
+}
+
+/// Runs one instruction of `program_id` over `accounts`, which holds every
+/// account `metas` names and receives their new state on success.
+fn execute(
+    program_id: &Pubkey,
+    metas: &[AccountMeta],
+    accounts: &mut HashMap<Pubkey, Account>,
+    data: &[u8],
+) -> ProgramResult {
+    let Some(processor) = with_context(|context| context.programs.get(program_id).copied()).flatten()
+    else {
+        return Err(ProgramError::IncorrectProgramId);
+    };
+    let mut privileges: HashMap<Pubkey, (bool, bool)> = HashMap::new();
+    for meta in metas {
+        let entry = privileges.entry(meta.pubkey).or_default();
+        entry.0 |= meta.is_signer;
+        entry.1 |= meta.is_writable;
+    }
+
+    let mut input = Input::new(program_id, metas, &privileges, accounts, data);
+    let frame = Frame {
+        program_id: *program_id,
+        privileges,
+        baseline: accounts.clone(),
+    };
+    // As the loader does, each invocation starts with no return data
+    let depth = with_context(|context| {
+        context.stack.push(frame);
+        context.return_data = None;
+        context.stack.len()
+    })
+    .unwrap_or_default();
+    log(format!("Program {program_id} invoke [{depth}]"));
+    let result = {
+        // SAFETY: the buffer is laid out exactly as the loader serializes it
+        // and outlives every `AccountInfo` pointing into it
+        let (id, infos, data) = unsafe { entrypoint::deserialize(input.as_mut_ptr()) };
+        processor(id, &infos, data)
+    };
+    let frame = with_context(|context| context.stack.pop()).flatten();
+    let return_data = with_context(|context| context.return_data.clone()).flatten();
+    if let Some((id, data)) = return_data.filter(|(id, data)| id == program_id && !data.is_empty()) {
+        log(format!("Program return: {id} {}", BASE64_STANDARD.encode(data)));
+    }
+
+    let result = result.and_then(|()| match frame {
+        Some(frame) => input.apply(&frame, accounts),
+        None => Err(ProgramError::InvalidArgument),
+    });
+    match &result {
+        Ok(()) => log(format!("Program {program_id} success")),
+        Err(error) => log(format!("Program {program_id} failed: {error}")),
+    }
+    result
+}
+
+/// A program's input buffer, in the loader's aligned serialization format.
+struct Input {
+    buffer: Vec<u64>,
+    // Byte offset of each unique account's owner field
+    owners: Vec<(Pubkey, usize)>,
+}
+
+impl Input {
+    fn new(
+        program_id: &Pubkey,
+        metas: &[AccountMeta],
+        privileges: &HashMap<Pubkey, (bool, bool)>,
+        accounts: &HashMap<Pubkey, Account>,
+        data: &[u8],
+    ) -> Self {
+        let mut bytes = Vec::new();
+        let mut owners = Vec::new();
+        let mut positions: HashMap<Pubkey, usize> = HashMap::new();
+        bytes.extend_from_slice(&(metas.len() as u64).to_le_bytes());
+        for (index, meta) in metas.iter().enumerate() {
+            if let Some(&first) = positions.get(&meta.pubkey) {
+                bytes.push(first as u8);
+                bytes.extend_from_slice(&[0; 7]);
+                continue;
+            }
+            positions.insert(meta.pubkey, index);
+            let account = &accounts[&meta.pubkey];
+            let (is_signer, is_writable) = privileges[&meta.pubkey];
+            bytes.extend_from_slice(&[
+                NON_DUP_MARKER,
+                is_signer as u8,
+                is_writable as u8,
+                account.executable as u8,
+            ]);
+            bytes.extend_from_slice(&[0; 4]); // original data length, set by deserialize
+            bytes.extend_from_slice(meta.pubkey.as_ref());
+            owners.push((meta.pubkey, bytes.len()));
+            bytes.extend_from_slice(account.owner.as_ref());
+            bytes.extend_from_slice(&account.lamports.to_le_bytes());
+            bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&account.data);
+            bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            bytes.resize(bytes.len().next_multiple_of(BPF_ALIGN_OF_U128), 0);
+            bytes.extend_from_slice(&u64::MAX.to_le_bytes()); // rent epoch
+        }
+        bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(data);
+        bytes.extend_from_slice(program_id.as_ref());
+
+        let mut buffer = vec![0u64; bytes.len().div_ceil(8)];
+        // SAFETY: `buffer` spans at least `bytes.len()` bytes
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer.as_mut_ptr() as *mut u8, bytes.len());
+        }
+        Self { buffer, owners }
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.buffer.as_mut_ptr() as *mut u8
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // SAFETY: reinterpreting initialized u64s as bytes
+        unsafe { std::slice::from_raw_parts(self.buffer.as_ptr() as *const u8, self.buffer.len() * 8) }
+    }
+
+    /// Reads the accounts back out, checks what the frame's program changed
+    /// since its baseline and stores them in `accounts`.
+    fn apply(&self, frame: &Frame, accounts: &mut HashMap<Pubkey, Account>) -> ProgramResult {
+        let bytes = self.bytes();
+        let read_u64 = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let mut updates = Vec::with_capacity(self.owners.len());
+        let (mut lamports_before, mut lamports_after) = (0u128, 0u128);
+        for &(key, offset) in &self.owners {
+            let before = &frame.baseline[&key];
+            let data_len = read_u64(offset + 40) as usize;
+            let after = Account {
+                owner: Pubkey::try_from(&bytes[offset..offset + 32]).unwrap(),
+                lamports: read_u64(offset + 32),
+                data: bytes[offset + 48..offset + 48 + data_len].to_vec(),
+                executable: before.executable,
+            };
+            lamports_before += before.lamports as u128;
+            lamports_after += after.lamports as u128;
+            check_change(frame, &key, before, &after)?;
+            updates.push((key, after));
+        }
+        if lamports_before != lamports_after {
+            log(format!("Program {} unbalanced the accounts' lamports", frame.program_id));
+            return Err(ProgramError::InvalidAccountData);
+        }
+        accounts.extend(updates);
+        Ok(())
+    }
+}
+
+/// Holds `frame`'s program to the runtime's rules on which accounts it may
+/// write, debit and reassign.
+fn check_change(frame: &Frame, key: &Pubkey, before: &Account, after: &Account) -> ProgramResult {
+    if after == before {
+        return Ok(());
+    }
+    let program_id = &frame.program_id;
+    let violation = if !frame.privileges.get(key).is_some_and(|privileges| privileges.1) {
+        "modified a read-only account"
+    } else if before.owner != *program_id && after.data != before.data {
+        "modified data of an account it does not own"
+    } else if before.owner != *program_id && after.lamports < before.lamports {
+        "debited an account it does not own"
+    } else if after.owner != before.owner
+        && (before.owner != *program_id || after.data.iter().any(|byte| *byte != 0))
+    {
+        "changed the owner of an account it may not reassign"
+    } else {
+        return Ok(());
+    };
+    log(format!("Program {program_id} {violation}: {key}"));
+    Err(ProgramError::InvalidAccountData)
+}
+
+/// Cross-program invocation: runs `instruction` over the caller's
+/// `infos` and writes the callee's changes back into them.
+fn invoke_signed(
+    instruction: &Instruction,
+    infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let caller = with_context(|context| context.stack.last().map(|frame| frame.program_id))
+        .flatten()
+        .ok_or(ProgramError::InvalidArgument)?;
+    let signers = signers_seeds
+        .iter()
+        .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+    let mut accounts = HashMap::new();
+    for meta in &instruction.accounts {
+        let info = infos
+            .iter()
+            .find(|info| *info.key == meta.pubkey)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if meta.is_signer && !info.is_signer && !signers.contains(&meta.pubkey) {
+            log(format!("{}'s signer privilege escalated", meta.pubkey));
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if meta.is_writable && !info.is_writable {
+            log(format!("{}'s writable privilege escalated", meta.pubkey));
+            return Err(ProgramError::InvalidArgument);
+        }
+        accounts.entry(meta.pubkey).or_insert_with(|| Account {
+            lamports: info.lamports(),
+            data: info.data.borrow().to_vec(),
+            owner: *info.owner,
+            executable: info.executable,
+        });
+    }
+    // The caller's own changes so far are checked before the callee sees them
+    rebase(&accounts, true)??;
+    execute(&instruction.program_id, &instruction.accounts, &mut accounts, &instruction.data)?;
+    rebase(&accounts, false)??;
+
+    for (key, account) in accounts {
+        let info = infos.iter().find(|info| *info.key == key).unwrap();
+        if !info.is_writable {
+            continue;
+        }
+        **info.try_borrow_mut_lamports()? = account.lamports;
+        if info.data_len() != account.data.len() {
+            info.resize(account.data.len())?;
+        }
+        info.try_borrow_mut_data()?.copy_from_slice(&account.data);
+        if *info.owner != account.owner {
+            info.assign(&account.owner);
+        }
+    }
+    Ok(())
+}
+
+/// Moves the caller's baseline for `accounts` to their current state,
+/// checking the caller's changes first if `check` is set.
+fn rebase(accounts: &HashMap<Pubkey, Account>, check: bool) -> Result<ProgramResult, ProgramError> {
+    with_context(|context| {
+        let frame = context.stack.last_mut().ok_or(ProgramError::InvalidArgument)?;
+        for (key, account) in accounts {
+            if let Some(before) = frame.baseline.get(key) {
+                if check {
+                    if let Err(error) = check_change(frame, key, before, account) {
+                        return Ok(Err(error));
+                    }
+                }
+            }
+            frame.baseline.insert(*key, account.clone());
+        }
+        Ok(Ok(()))
+    })
+    .ok_or(ProgramError::InvalidArgument)?
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        log(format!("Program log: {message}"));
+    }
+
+    fn sol_log_compute_units(&self) {}
+
+    fn sol_remaining_compute_units(&self) -> u64 {
+        u64::MAX
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        invoke_signed(instruction, account_infos, signers_seeds)
+    }
+
+    fn sol_get_sysvar(&self, sysvar_id_addr: *const u8, var_addr: *mut u8, offset: u64, length: u64) -> u64 {
+        // SAFETY: the caller passes a pointer to a sysvar id
+        let id = unsafe { *(sysvar_id_addr as *const Pubkey) };
+        let Some(Some(bytes)) = with_context(|context| match id {
+            sysvar::clock::ID => Some(clock_bytes(&context.clock)),
+            sysvar::rent::ID => Some(rent_bytes(&context.rent)),
+            _ => None,
+        }) else {
+            return SYSCALL_UNSUPPORTED_SYSVAR;
+        };
+        let (offset, length) = (offset as usize, length as usize);
+        if offset.saturating_add(length) > bytes.len() {
+            return SYSCALL_OFFSET_LENGTH_EXCEEDS_SYSVAR;
+        }
+        // SAFETY: the caller passes a buffer of `length` bytes
+        unsafe { std::ptr::copy_nonoverlapping(bytes[offset..].as_ptr(), var_addr, length) };
+        SYSCALL_SUCCESS
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        match with_context(|context| context.clock.clone()) {
+            Some(clock) => {
+                // SAFETY: the caller passes a pointer to a `Clock`
+                unsafe { *(var_addr as *mut Clock) = clock };
+                SYSCALL_SUCCESS
+            }
+            None => SYSCALL_UNSUPPORTED_SYSVAR,
+        }
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        match with_context(|context| context.rent.clone()) {
+            Some(rent) => {
+                // SAFETY: the caller passes a pointer to a `Rent`
+                unsafe { *(var_addr as *mut Rent) = rent };
+                SYSCALL_SUCCESS
+            }
+            None => SYSCALL_UNSUPPORTED_SYSVAR,
+        }
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        // SAFETY: the caller passes a pointer to an `EpochSchedule`
+        unsafe { *(var_addr as *mut EpochSchedule) = EpochSchedule::default() };
+        SYSCALL_SUCCESS
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        with_context(|context| context.return_data.clone()).flatten()
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        with_context(|context| {
+            let program_id = context.stack.last().map_or(Pubkey::default(), |frame| frame.program_id);
+            context.return_data = Some((program_id, data.to_vec()));
+        });
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields.iter().map(|field| BASE64_STANDARD.encode(field)).collect();
+        log(format!("Program data: {}", fields.join(" ")));
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        with_context(|context| context.stack.len() as u64).unwrap_or_default()
+    }
+}
+
+fn clock_bytes(clock: &Clock) -> Vec<u8> {
+    [
+        clock.slot.to_le_bytes(),
+        clock.epoch_start_timestamp.to_le_bytes(),
+        clock.epoch.to_le_bytes(),
+        clock.leader_schedule_epoch.to_le_bytes(),
+        clock.unix_timestamp.to_le_bytes(),
+    ]
+    .concat()
+}
+
+fn rent_bytes(rent: &Rent) -> Vec<u8> {
+    let mut bytes = rent.lamports_per_byte_year.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+    bytes.push(rent.burn_percent);
+    bytes
+}
+
+/// The system program's account creation, assignment and transfer
+/// instructions, which is all the programs here invoke.
+fn system_processor(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let read_u64 = |offset: usize| {
+        data.get(offset..offset + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+    let read_pubkey = |offset: usize| {
+        data.get(offset..offset + 32)
+            .map(|bytes| Pubkey::try_from(bytes).unwrap())
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+    let account = |index: usize| accounts.get(index).ok_or(ProgramError::NotEnoughAccountKeys);
+    let tag = data
+        .get(..4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    match tag {
+        SYSTEM_CREATE_ACCOUNT => {
+            let (from, to) = (account(0)?, account(1)?);
+            if to.lamports() > 0 || !to.data_is_empty() || *to.owner != system_program::ID {
+                return Err(ProgramError::Custom(SYSTEM_ERROR_ACCOUNT_IN_USE));
+            }
+            transfer(from, to, read_u64(4)?)?;
+            allocate(to, read_u64(12)?)?;
+            assign(to, &read_pubkey(20)?)
+        }
+        SYSTEM_ASSIGN => assign(account(0)?, &read_pubkey(4)?),
+        SYSTEM_TRANSFER => transfer(account(0)?, account(1)?, read_u64(4)?),
+        SYSTEM_ALLOCATE => allocate(account(0)?, read_u64(4)?),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    if !from.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !from.data_is_empty() || *from.owner != system_program::ID {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if from.lamports() < lamports {
+        return Err(ProgramError::Custom(SYSTEM_ERROR_INSUFFICIENT_FUNDS));
+    }
+    **from.try_borrow_mut_lamports()? -= lamports;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+fn allocate(account: &AccountInfo, space: u64) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !account.data_is_empty() || *account.owner != system_program::ID {
+        return Err(ProgramError::Custom(SYSTEM_ERROR_ACCOUNT_IN_USE));
+    }
+    account.resize(space as usize)
+}
+
+fn assign(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account.owner == owner {
+        return Ok(());
+    }
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *account.owner != system_program::ID {
+        return Err(ProgramError::InvalidArgument);
+    }
+    account.assign(owner);
+    Ok(())
+}
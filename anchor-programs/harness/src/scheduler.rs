@@ -0,0 +1,298 @@
+//! Many markets at once. A `Scheduler` runs each market's simulation on a
+//! worker thread, all driven by one pool of agents: every pool agent has the
+//! same owner in every market and trades all of them with its `Strategy`.
+//!
+//! Markets advance a slot at a time. On each slot every agent without a
+//! transaction already queued decides what to do, the market processes up to
+//! `max_transactions_per_slot` queued transactions as that slot's batch,
+//! oldest first, and the book is cranked. Transactions that don't fit wait
+//! for the next slot.
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use anchor_programs::PRICE_SCALE;
+
+use crate::env::Action;
+use crate::seed::{self, KeySource, STREAM_KEYS, STREAM_MARKETS};
+use crate::sim::{Simulation, SIM_DECIMALS};
+
+/// Decides what one pool agent does in one market on one slot.
+pub trait Strategy: Send + Sync {
+    /// `agent` is the pool agent's index in `sim.agents`.
+    fn act(&self, sim: &Simulation, agent: usize, rng: &mut ChaCha8Rng) -> Action;
+}
+
+/// Quotes a random side around the last price now and then, so that prices
+/// in a market of noise traders wander.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoiseTrader {
+    /// Chance in percent of requoting on a slot
+    pub activity_percent: u8,
+    /// Furthest a quote rests from the last price, either way
+    pub spread_bps: u64,
+    /// Mean quote size, token B
+    pub order_size: u64,
+    /// Used before a market's first fill
+    pub initial_price: u64,
+}
+
+impl Default for NoiseTrader {
+    fn default() -> Self {
+        Self {
+            activity_percent: 30,
+            spread_bps: 50,
+            order_size: 10_000_000,
+            initial_price: PRICE_SCALE,
+        }
+    }
+}
+
+impl Strategy for NoiseTrader {
+    fn act(&self, sim: &Simulation, _agent: usize, rng: &mut ChaCha8Rng) -> Action {
+        if rng.gen_range(0..100) >= self.activity_percent {
+            return Action::Hold;
+        }
+        let last_price = match sim.market().last_price {
+            0 => self.initial_price,
+            price => price,
+        };
+        let spread = self.spread_bps as i64;
+        let bps = rng.gen_range(-spread..=spread);
+        let price = ((last_price as i128 * (10_000 + bps) as i128 / 10_000) as u64).max(1);
+        let size = self.order_size.max(2);
+        let quantity = rng.gen_range(size / 2..=size + size / 2);
+        if rng.gen_bool(0.5) {
+            Action::Bid { price, quantity }
+        } else {
+            Action::Ask { price, quantity }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SchedulerConfig {
+    /// Seeds the pool's keys and each market's keys, agent order and
+    /// strategies' randomness
+    pub seed: u64,
+    pub markets: usize,
+    /// Worker threads, 0 = one per available core
+    pub threads: usize,
+    pub slots: u64,
+    /// Deposited for every pool agent in every market
+    pub initial_token_a: u64,
+    pub initial_token_b: u64,
+    pub max_transactions_per_slot: usize,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            markets: 4,
+            threads: 0,
+            slots: 1_000,
+            initial_token_a: 1_000_000_000,
+            initial_token_b: 1_000_000_000,
+            max_transactions_per_slot: 64,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketMetrics {
+    pub market: usize,
+    pub slots: u64,
+    /// Transactions processed, failed ones included
+    pub transactions: u64,
+    pub failed: u64,
+    /// Largest number of transactions waiting after a slot's batch
+    pub max_backlog: usize,
+    pub fills: u64,
+    /// Token B traded
+    pub volume: u64,
+    pub last_price: u64,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Metrics {
+    pub markets: Vec<MarketMetrics>,
+    /// Each pool agent's equity change summed over markets, whole token A at
+    /// each market's last price
+    pub agent_pnl: Vec<f64>,
+    pub transactions: u64,
+    pub elapsed_ms: u64,
+    pub transactions_per_second: f64,
+}
+
+/// A finished run: metrics and every market's final state.
+pub struct Run {
+    pub metrics: Metrics,
+    pub simulations: Vec<Simulation>,
+}
+
+pub struct Scheduler {
+    config: SchedulerConfig,
+    key_source: KeySource,
+    owners: Vec<Pubkey>,
+    strategies: Vec<Arc<dyn Strategy>>,
+}
+
+impl Scheduler {
+    /// A pool of `agents` default noise traders.
+    pub fn new(config: SchedulerConfig, agents: usize) -> Self {
+        let strategies = (0..agents)
+            .map(|_| Arc::new(NoiseTrader::default()) as Arc<dyn Strategy>)
+            .collect();
+        Self::with_strategies(config, strategies)
+    }
+
+    /// One pool agent per strategy.
+    pub fn with_strategies(config: SchedulerConfig, strategies: Vec<Arc<dyn Strategy>>) -> Self {
+        let mut key_source = KeySource::new(seed::rng(config.seed, STREAM_KEYS));
+        Self {
+            owners: strategies.iter().map(|_| key_source.next_key()).collect(),
+            key_source,
+            config,
+            strategies,
+        }
+    }
+
+    pub fn config(&self) -> &SchedulerConfig {
+        &self.config
+    }
+
+    /// Pool agents' owners, the same in every market.
+    pub fn owners(&self) -> &[Pubkey] {
+        &self.owners
+    }
+
+    /// Keypairs of the pool agents' owners.
+    pub fn key_source(&self) -> &KeySource {
+        &self.key_source
+    }
+
+    pub fn run(&self) -> Run {
+        let started = Instant::now();
+        let threads = match self.config.threads {
+            0 => std::thread::available_parallelism().map_or(1, usize::from),
+            threads => threads,
+        }
+        .clamp(1, self.config.markets.max(1));
+
+        // Markets are dealt round robin so threads get similar loads
+        let mut results: Vec<(MarketMetrics, Vec<f64>, Simulation)> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|thread| {
+                    scope.spawn(move || {
+                        (thread..self.config.markets)
+                            .step_by(threads)
+                            .map(|market| self.run_market(market))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("market worker panicked"))
+                .collect()
+        });
+        results.sort_by_key(|(metrics, _, _)| metrics.market);
+
+        let mut metrics = Metrics {
+            agent_pnl: vec![0.0; self.owners.len()],
+            ..Metrics::default()
+        };
+        let mut simulations = Vec::with_capacity(results.len());
+        for (market, pnl, sim) in results {
+            for (total, pnl) in metrics.agent_pnl.iter_mut().zip(pnl) {
+                *total += pnl;
+            }
+            metrics.transactions += market.transactions;
+            metrics.markets.push(market);
+            simulations.push(sim);
+        }
+        let elapsed = started.elapsed();
+        metrics.elapsed_ms = elapsed.as_millis() as u64;
+        metrics.transactions_per_second = metrics.transactions as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+        Run { metrics, simulations }
+    }
+
+    fn run_market(&self, market: usize) -> (MarketMetrics, Vec<f64>, Simulation) {
+        let started = Instant::now();
+        let config = &self.config;
+        let streams = STREAM_MARKETS + 2 * market as u64;
+        let mut sim = Simulation::with_key_source(KeySource::new(seed::rng(config.seed, streams)));
+        for owner in &self.owners {
+            sim.add_agent_with_owner(*owner, config.initial_token_a, config.initial_token_b)
+                .expect("agent setup failed");
+        }
+        let mark_price = |sim: &Simulation| match sim.market().last_price {
+            0 => PRICE_SCALE,
+            price => price,
+        };
+        let start_equity: Vec<u64> = (0..self.owners.len())
+            .map(|agent| sim.equity(agent, mark_price(&sim)))
+            .collect();
+
+        let mut rng = seed::rng(config.seed, streams + 1);
+        let mut metrics = MarketMetrics {
+            market,
+            ..MarketMetrics::default()
+        };
+        let mut queue: VecDeque<(usize, Vec<Instruction>)> = VecDeque::new();
+        let mut agents: Vec<usize> = (0..self.owners.len()).collect();
+        for _ in 0..config.slots {
+            agents.shuffle(&mut rng);
+            for &agent in &agents {
+                if queue.iter().any(|(queued, _)| *queued == agent) {
+                    continue;
+                }
+                if let Some(order) = self.strategies[agent].act(&sim, agent, &mut rng).replacement() {
+                    let instructions = sim.replace_order_instructions(agent, order);
+                    if !instructions.is_empty() {
+                        queue.push_back((agent, instructions));
+                    }
+                }
+            }
+
+            let batch = queue.len().min(config.max_transactions_per_slot);
+            for (agent, instructions) in queue.drain(..batch) {
+                let owner = sim.agents[agent].owner;
+                metrics.transactions += 1;
+                if sim.send(&instructions, &[owner]).is_err() {
+                    metrics.failed += 1;
+                }
+            }
+            metrics.max_backlog = metrics.max_backlog.max(queue.len());
+
+            for fill in sim.crank() {
+                metrics.fills += 1;
+                metrics.volume += fill.quantity;
+            }
+            sim.runtime.advance_slots(1);
+            metrics.slots += 1;
+        }
+
+        let price = mark_price(&sim);
+        let scale = 10f64.powi(SIM_DECIMALS as i32);
+        let pnl = start_equity
+            .iter()
+            .enumerate()
+            .map(|(agent, start)| (sim.equity(agent, price) as f64 - *start as f64) / scale)
+            .collect();
+        metrics.last_price = sim.market().last_price;
+        metrics.elapsed_ms = started.elapsed().as_millis() as u64;
+        (metrics, pnl, sim)
+    }
+}
@@ -0,0 +1,104 @@
+//! Seeded randomness. Everything random in the harness, keys included, comes
+//! from a ChaCha8 stream of one seed, so a run is reproduced bit for bit by
+//! its seed and configuration. Each consumer has its own stream, so adding
+//! draws to one doesn't shift the others.
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+use curve25519_dalek::edwards::EdwardsPoint;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+/// Admin, mint, agent and feed keys of one simulation.
+pub const STREAM_KEYS: u64 = 0;
+/// Background agents' quotes.
+pub const STREAM_AGENTS: u64 = 1;
+/// Chaos network latencies, drops and reordering.
+pub const STREAM_NETWORK: u64 = 2;
+/// Timing and direction of price shocks.
+pub const STREAM_SHOCKS: u64 = 3;
+/// Instructions the fuzzer generates and mutates.
+pub const STREAM_FUZZ: u64 = 4;
+/// Scheduler market `m` takes streams `STREAM_MARKETS + 2 * m` for its keys
+/// and the one after for its agents.
+pub const STREAM_MARKETS: u64 = 16;
+
+/// The `stream`th generator of `seed`.
+pub fn rng(seed: u64, stream: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(stream);
+    rng
+}
+
+/// An ed25519 keypair, usable with `solana-keygen` and validators.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keypair {
+    secret: [u8; 32],
+    #[serde(with = "crate::checkpoint::base58")]
+    pubkey: Pubkey,
+}
+
+impl Keypair {
+    pub fn from_secret(secret: [u8; 32]) -> Self {
+        let hash = Sha512::digest(secret);
+        let scalar: [u8; 32] = hash[..32].try_into().unwrap();
+        let pubkey = EdwardsPoint::mul_base_clamped(scalar).compress().to_bytes();
+        Self {
+            secret,
+            pubkey: Pubkey::new_from_array(pubkey),
+        }
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    /// Secret then public key, the layout of Solana keypair files.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&self.secret);
+        bytes[32..].copy_from_slice(self.pubkey.as_ref());
+        bytes
+    }
+
+    /// Writes a `solana-keygen` style JSON keypair file.
+    pub fn write_json(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, serde_json::to_vec(&self.to_bytes().to_vec())?)
+    }
+}
+
+/// Hands out keypairs from a seeded stream and remembers them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeySource {
+    rng: ChaCha8Rng,
+    keypairs: Vec<Keypair>,
+}
+
+impl KeySource {
+    pub fn new(rng: ChaCha8Rng) -> Self {
+        Self { rng, keypairs: Vec::new() }
+    }
+
+    /// A fresh keypair's address.
+    pub fn next_key(&mut self) -> Pubkey {
+        let mut secret = [0; 32];
+        self.rng.fill_bytes(&mut secret);
+        let keypair = Keypair::from_secret(secret);
+        let pubkey = keypair.pubkey();
+        self.keypairs.push(keypair);
+        pubkey
+    }
+
+    pub fn keypair(&self, pubkey: &Pubkey) -> Option<&Keypair> {
+        self.keypairs.iter().find(|keypair| keypair.pubkey == *pubkey)
+    }
+
+    /// Every keypair handed out so far, oldest first.
+    pub fn keypairs(&self) -> &[Keypair] {
+        &self.keypairs
+    }
+}
@@ -0,0 +1,1382 @@
+//! A market and its agents on top of a `Runtime`. Setup goes through the
+//! program's own instructions, except that SOL and tokens are minted straight
+//! into accounts, and orders are placed, cancelled and matched the way a
+//! client and a cranker would.
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::{bpf_loader_upgradeable, system_program};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_programs::{
+    accounts, instruction, Agent, AuctionIndication, Balance, Config, Curriculum, CurriculumStage, Market,
+    ObservationFrame, OrderAction, OrderParams, RewardRecord, Trade, ACTION_LEN, MATCHING_PRO_RATA, PRICE_SCALE,
+    TRADE_TAG_LEN, UNCROSS_TALLY,
+};
+use serde::{Deserialize, Serialize};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::checkpoint::Checkpoint;
+use crate::runtime::{Account, Failure, Receipt, Runtime};
+use crate::seed::{self, KeySource, STREAM_KEYS};
+
+/// Features the simulated config enables: order book, margin, batching,
+/// adversary agents and episode resets.
+pub const SIM_FEATURES: u64 = 0b111011;
+/// Decimals of both simulated mints, so a price is also atoms per atom.
+pub const SIM_DECIMALS: u8 = 6;
+
+const AGENT_LAMPORTS: u64 = 100_000_000_000;
+/// Orders each `uncross_auction` crank passes, well inside a transaction.
+pub const UNCROSS_PAGE: usize = 8;
+
+/// Orders each `update_observation_frame` crank passes.
+pub const OBSERVATION_PAGE: usize = 8;
+
+/// 0 = buy token B with token A, 1 = sell token B for token A.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+/// Addresses of the simulated market.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct MarketKeys {
+    pub market: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub vault_a_token_account: Pubkey,
+    pub vault_b_token_account: Pubkey,
+}
+
+/// Addresses of one simulated agent.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct AgentKeys {
+    pub owner: Pubkey,
+    pub agent: Pubkey,
+    pub token_a_account: Pubkey,
+    pub token_b_account: Pubkey,
+    pub balance_a: Pubkey,
+    pub balance_b: Pubkey,
+    /// The agent's trade 0, the one every helper without an order id uses
+    pub trade: Pubkey,
+}
+
+/// A fill produced by `Simulation::crank`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fill {
+    pub bid_agent: usize,
+    pub ask_agent: usize,
+    /// Token B traded
+    pub quantity: u64,
+    /// Market price after the fill, `PRICE_SCALE` fixed point
+    pub price: u64,
+}
+
+#[derive(Clone)]
+pub struct Simulation {
+    pub runtime: Runtime,
+    pub admin: Pubkey,
+    pub keys: MarketKeys,
+    pub agents: Vec<AgentKeys>,
+    /// Source of every key the simulation creates
+    pub key_source: KeySource,
+}
+
+/// A `Simulation`'s accounts and the addresses needed to drive them again.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulationCheckpoint {
+    pub admin: Pubkey,
+    pub keys: MarketKeys,
+    pub agents: Vec<AgentKeys>,
+    pub key_source: KeySource,
+    pub state: Checkpoint,
+}
+
+pub fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &anchor_programs::ID).0
+}
+
+pub fn global_stats_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"global_stats"], &anchor_programs::ID).0
+}
+
+/// The leaderboard of `market`'s `epoch`.
+pub fn leaderboard_address(market: &Pubkey, epoch: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"leaderboard", market.as_ref(), &epoch.to_le_bytes()], &anchor_programs::ID).0
+}
+
+/// The snapshot of `market`'s `epoch`.
+pub fn snapshot_address(market: &Pubkey, epoch: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"snapshot", market.as_ref(), &epoch.to_le_bytes()], &anchor_programs::ID).0
+}
+
+/// `agent`'s trade `order_id` on `market`.
+pub fn trade_address(agent: &Pubkey, market: &Pubkey, order_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"trade", agent.as_ref(), market.as_ref(), &order_id.to_le_bytes()],
+        &anchor_programs::ID,
+    )
+    .0
+}
+
+/// `agent`'s reward record for `market`'s `epoch`.
+pub fn reward_address(market: &Pubkey, agent: &Pubkey, epoch: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"reward", market.as_ref(), agent.as_ref(), &epoch.to_le_bytes()],
+        &anchor_programs::ID,
+    )
+    .0
+}
+
+/// The curriculum of `market`.
+pub fn curriculum_address(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"curriculum", market.as_ref()], &anchor_programs::ID).0
+}
+
+/// The copy feed of `leader`'s fills on `market`.
+pub fn copy_feed_address(market: &Pubkey, leader: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"copy_feed", market.as_ref(), leader.as_ref()], &anchor_programs::ID).0
+}
+
+/// `follower`'s subscription to `leader` on `market`.
+pub fn subscription_address(market: &Pubkey, follower: &Pubkey, leader: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"subscription", market.as_ref(), follower.as_ref(), leader.as_ref()],
+        &anchor_programs::ID,
+    )
+    .0
+}
+
+/// The observation frame of `market`.
+pub fn observation_frame_address(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"observation", market.as_ref()], &anchor_programs::ID).0
+}
+
+/// Listing `index` of the market list.
+pub fn market_listing_address(index: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"market_listing", &index.to_le_bytes()], &anchor_programs::ID).0
+}
+
+/// Listing `index` of the agent list.
+pub fn agent_listing_address(index: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"agent_listing", &index.to_le_bytes()], &anchor_programs::ID).0
+}
+
+/// A compact action: `side`, size bucket `bucket` (0-31), `offset` ticks
+/// from the last fill price and TRADE_FLAG_* `flags`.
+pub fn encode_action(side: Side, bucket: u8, offset: i16, flags: u8) -> [u8; ACTION_LEN] {
+    let [low, high] = offset.to_le_bytes();
+    [side as u8 | flags << 1 | bucket << 3, low, high]
+}
+
+pub fn program_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: anchor_programs::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+impl Simulation {
+    /// A fresh cluster with the config set up and one market over two new
+    /// mints, with no fees, keyed from seed 0.
+    pub fn new() -> Self {
+        Self::seeded(0)
+    }
+
+    /// Like `new`, with every key drawn from `seed`.
+    pub fn seeded(seed: u64) -> Self {
+        Self::with_key_source(KeySource::new(seed::rng(seed, STREAM_KEYS)))
+    }
+
+    /// Like `new`, with the admin, the mints and later keys from `key_source`.
+    pub fn with_key_source(mut key_source: KeySource) -> Self {
+        let (admin, token_a_mint, token_b_mint) = (key_source.next_key(), key_source.next_key(), key_source.next_key());
+        Self::with_keys(admin, token_a_mint, token_b_mint, key_source)
+    }
+
+    /// Like `new`, with the admin and the mints' addresses chosen by the
+    /// caller and later keys from `key_source`.
+    pub fn with_keys(admin: Pubkey, token_a_mint: Pubkey, token_b_mint: Pubkey, key_source: KeySource) -> Self {
+        let mut runtime = Runtime::new(admin);
+        runtime.airdrop(admin, AGENT_LAMPORTS);
+        for mint in [token_a_mint, token_b_mint] {
+            let mut data = vec![0; spl_token::state::Mint::LEN];
+            spl_token::state::Mint {
+                mint_authority: Some(admin).into(),
+                supply: 0,
+                decimals: SIM_DECIMALS,
+                is_initialized: true,
+                freeze_authority: None.into(),
+            }
+            .pack_into_slice(&mut data);
+            let lamports = runtime.rent().minimum_balance(data.len());
+            runtime.set_account(
+                mint,
+                Account {
+                    lamports,
+                    data,
+                    owner: spl_token::ID,
+                    executable: false,
+                },
+            );
+        }
+
+        let vault = |mint: &Pubkey| {
+            Pubkey::find_program_address(
+                &[b"vault", token_a_mint.as_ref(), token_b_mint.as_ref(), mint.as_ref()],
+                &anchor_programs::ID,
+            )
+            .0
+        };
+        let (vault_a, vault_b) = (vault(&token_a_mint), vault(&token_b_mint));
+        let keys = MarketKeys {
+            market: Pubkey::find_program_address(
+                &[b"market", token_a_mint.as_ref(), token_b_mint.as_ref()],
+                &anchor_programs::ID,
+            )
+            .0,
+            token_a_mint,
+            token_b_mint,
+            vault_a,
+            vault_b,
+            vault_a_token_account: get_associated_token_address(&vault_a, &token_a_mint),
+            vault_b_token_account: get_associated_token_address(&vault_b, &token_b_mint),
+        };
+        let mut sim = Self {
+            runtime,
+            admin,
+            keys,
+            agents: Vec::new(),
+            key_source,
+        };
+
+        let program_data = Pubkey::find_program_address(
+            &[anchor_programs::ID.as_ref()],
+            &bpf_loader_upgradeable::ID,
+        )
+        .0;
+        let initialize_config = program_instruction(
+            accounts::InitializeConfig {
+                config: config_address(),
+                global_stats: global_stats_address(),
+                program: anchor_programs::ID,
+                program_data,
+                upgrade_authority: admin,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeConfig {
+                admin,
+                maker_fee_bps: 0,
+                taker_fee_bps: 0,
+                features: SIM_FEATURES,
+            },
+        );
+        let initialize_market = program_instruction(
+            accounts::InitializeMarket {
+                config: config_address(),
+                market: keys.market,
+                market_listing: market_listing_address(0),
+                global_stats: global_stats_address(),
+                vault_a,
+                vault_b,
+                vault_a_token_account: keys.vault_a_token_account,
+                vault_b_token_account: keys.vault_b_token_account,
+                token_a_mint,
+                token_b_mint,
+                signer: admin,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeMarket {},
+        );
+        sim.send(&[initialize_config, initialize_market], &[admin])
+            .expect("market setup failed");
+        sim
+    }
+
+    pub fn checkpoint(&self) -> SimulationCheckpoint {
+        SimulationCheckpoint {
+            admin: self.admin,
+            keys: self.keys,
+            agents: self.agents.clone(),
+            key_source: self.key_source.clone(),
+            state: Checkpoint::capture(&self.runtime),
+        }
+    }
+
+    /// A simulation on a fresh cluster holding the checkpoint's accounts.
+    pub fn restore(checkpoint: &SimulationCheckpoint) -> std::io::Result<Self> {
+        let mut runtime = Runtime::new(checkpoint.admin);
+        checkpoint.state.restore(&mut runtime)?;
+        Ok(Self {
+            runtime,
+            admin: checkpoint.admin,
+            keys: checkpoint.keys,
+            agents: checkpoint.agents.clone(),
+            key_source: checkpoint.key_source.clone(),
+        })
+    }
+
+    /// Runs `instructions` as one transaction.
+    pub fn send(&mut self, instructions: &[Instruction], signers: &[Pubkey]) -> Result<Receipt, Failure> {
+        self.runtime.process(instructions, signers)
+    }
+
+    /// Registers a new agent owned by a new key and deposits `token_a` and
+    /// `token_b` for it, returning its index.
+    pub fn add_agent(&mut self, token_a: u64, token_b: u64) -> Result<usize, Failure> {
+        let owner = self.new_key();
+        self.add_agent_with_owner(owner, token_a, token_b)
+    }
+
+    /// A new address from the simulation's key source.
+    pub fn new_key(&mut self) -> Pubkey {
+        self.key_source.next_key()
+    }
+
+    pub fn add_agent_with_owner(&mut self, owner: Pubkey, token_a: u64, token_b: u64) -> Result<usize, Failure> {
+        let keys = self.keys;
+        let agent = Pubkey::find_program_address(&[b"agent", owner.as_ref()], &anchor_programs::ID).0;
+        let balance = |vault: &Pubkey| {
+            Pubkey::find_program_address(&[b"balance", vault.as_ref(), agent.as_ref()], &anchor_programs::ID).0
+        };
+        let agent_keys = AgentKeys {
+            owner,
+            agent,
+            token_a_account: get_associated_token_address(&owner, &keys.token_a_mint),
+            token_b_account: get_associated_token_address(&owner, &keys.token_b_mint),
+            balance_a: balance(&keys.vault_a),
+            balance_b: balance(&keys.vault_b),
+            trade: trade_address(&agent, &keys.market, 0),
+        };
+        self.runtime.airdrop(owner, AGENT_LAMPORTS);
+        self.mint_to(agent_keys.token_a_account, keys.token_a_mint, owner, token_a);
+        self.mint_to(agent_keys.token_b_account, keys.token_b_mint, owner, token_b);
+
+        let mut instructions = vec![program_instruction(
+            accounts::RegisterAgent {
+                config: config_address(),
+                agent,
+                agent_listing: agent_listing_address(self.config().agent_count),
+                global_stats: global_stats_address(),
+                user: owner,
+                system_program: system_program::ID,
+            },
+            instruction::RegisterAgent {},
+        )];
+        for (amount, mint, token_account, vault, vault_token_account, balance) in [
+            (
+                token_a,
+                keys.token_a_mint,
+                agent_keys.token_a_account,
+                keys.vault_a,
+                keys.vault_a_token_account,
+                agent_keys.balance_a,
+            ),
+            (
+                token_b,
+                keys.token_b_mint,
+                agent_keys.token_b_account,
+                keys.vault_b,
+                keys.vault_b_token_account,
+                agent_keys.balance_b,
+            ),
+        ] {
+            instructions.push(program_instruction(
+                accounts::DepositTokens {
+                    agent,
+                    market: keys.market,
+                    user: owner,
+                    token_mint: mint,
+                    user_token_account: token_account,
+                    vault,
+                    vault_token_account,
+                    balance,
+                    receipt: None,
+                    token_program: spl_token::ID,
+                    system_program: system_program::ID,
+                },
+                instruction::DepositTokens { amount, nonce: None },
+            ));
+        }
+        self.send(&instructions, &[owner])?;
+        self.agents.push(agent_keys);
+        Ok(self.agents.len() - 1)
+    }
+
+    /// Adds `amount` to `owner`'s token account for `mint`, creating it if
+    /// needed, and to the mint's supply.
+    pub fn mint_to(&mut self, token_account: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) {
+        let mut state = self
+            .runtime
+            .account(&token_account)
+            .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+            .unwrap_or(spl_token::state::Account {
+                mint,
+                owner,
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            });
+        state.amount += amount;
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        state.pack_into_slice(&mut data);
+        let lamports = self.runtime.rent().minimum_balance(data.len());
+        self.runtime.set_account(
+            token_account,
+            Account {
+                lamports,
+                data,
+                owner: spl_token::ID,
+                executable: false,
+            },
+        );
+
+        let mint_account = self.runtime.account(&mint).cloned().expect("unknown mint");
+        let mut mint_state = spl_token::state::Mint::unpack(&mint_account.data).unwrap();
+        mint_state.supply += amount;
+        let mut data = mint_account.data.clone();
+        mint_state.pack_into_slice(&mut data);
+        self.runtime.set_account(mint, Account { data, ..mint_account });
+    }
+
+    /// Rests an order for `quantity` token B at `price` token A per token B,
+    /// `PRICE_SCALE` fixed point.
+    pub fn place_order(&mut self, agent: usize, side: Side, price: u64, quantity: u64) -> Result<Receipt, Failure> {
+        let place = self.place_order_instruction(agent, side, price, quantity);
+        let owner = self.agents[agent].owner;
+        self.send(&[place], &[owner])
+    }
+
+    /// Like `place_order`, as the agent's trade `order_id`.
+    pub fn place_order_at(
+        &mut self,
+        agent: usize,
+        order_id: u64,
+        side: Side,
+        price: u64,
+        quantity: u64,
+    ) -> Result<Receipt, Failure> {
+        let place = self.place_order_at_instruction(agent, order_id, side, price, quantity);
+        let owner = self.agents[agent].owner;
+        self.send(&[place], &[owner])
+    }
+
+    /// The `place_trade` instruction behind `place_order`, signed by the
+    /// agent's owner.
+    pub fn place_order_instruction(&self, agent: usize, side: Side, price: u64, quantity: u64) -> Instruction {
+        self.place_order_at_instruction(agent, 0, side, price, quantity)
+    }
+
+    /// The `place_trade` instruction behind `place_order_at`.
+    pub fn place_order_at_instruction(
+        &self,
+        agent: usize,
+        order_id: u64,
+        side: Side,
+        price: u64,
+        quantity: u64,
+    ) -> Instruction {
+        let keys = self.agents[agent];
+        let quote = (quantity as u128 * price as u128 / PRICE_SCALE as u128) as u64;
+        let (trade_type, amount, price) = match side {
+            Side::Bid => (0, quote, quantity),
+            Side::Ask => (1, quantity, quote),
+        };
+        program_instruction(
+            accounts::PlaceTrade {
+                agent: keys.agent,
+                market: self.keys.market,
+                config: config_address(),
+                trade: trade_address(&keys.agent, &self.keys.market, order_id),
+                vault_a: None,
+                balance_a: None,
+                user: keys.owner,
+                system_program: system_program::ID,
+            },
+            instruction::PlaceTrade {
+                order_id,
+                trade_type,
+                amount,
+                price,
+                display_size: 0,
+                flags: 0,
+                tag: [0; TRADE_TAG_LEN],
+            },
+        )
+    }
+
+    /// Places the order a compact action encodes, see `encode_action`.
+    pub fn submit_action(&mut self, agent: usize, action: [u8; ACTION_LEN]) -> Result<Receipt, Failure> {
+        let keys = self.agents[agent];
+        let submit = program_instruction(
+            accounts::PlaceTrade {
+                agent: keys.agent,
+                market: self.keys.market,
+                config: config_address(),
+                trade: keys.trade,
+                vault_a: None,
+                balance_a: None,
+                user: keys.owner,
+                system_program: system_program::ID,
+            },
+            instruction::SubmitAction { order_id: 0, action },
+        );
+        self.send(&[submit], &[keys.owner])
+    }
+
+    /// Submits each `(agent, action)` pair in one transaction signed by
+    /// `coordinator`, the owner or session key of every agent named.
+    pub fn submit_actions_batch(
+        &mut self,
+        coordinator: Pubkey,
+        actions: &[(usize, [u8; ACTION_LEN])],
+    ) -> Result<Receipt, Failure> {
+        let mut submit = program_instruction(
+            accounts::SubmitActionsBatch {
+                market: self.keys.market,
+                config: config_address(),
+                vault_a: self.keys.vault_a,
+                coordinator,
+                system_program: system_program::ID,
+            },
+            instruction::SubmitActionsBatch {
+                actions: actions.iter().map(|&(_, action)| OrderAction { order_id: 0, action }).collect(),
+            },
+        );
+        for &(agent, _) in actions {
+            let keys = self.agents[agent];
+            submit.accounts.extend([
+                AccountMeta::new(keys.agent, false),
+                AccountMeta::new(keys.trade, false),
+                AccountMeta::new(keys.balance_a, false),
+            ]);
+        }
+        self.send(&[submit], &[coordinator])
+    }
+
+    /// Sets how makers at one price share a taker, MATCHING_*, as the admin.
+    pub fn set_matching_policy(&mut self, matching_policy: u8) -> Result<Receipt, Failure> {
+        let set = program_instruction(
+            accounts::SetMatchingPolicy {
+                market: self.keys.market,
+                authority: self.admin,
+            },
+            instruction::SetMatchingPolicy { matching_policy },
+        );
+        let admin = self.admin;
+        self.send(&[set], &[admin])
+    }
+
+    /// Caps the size of orders placed on the market, as the admin.
+    pub fn set_max_order_size(&mut self, max_order_size: u64) -> Result<Receipt, Failure> {
+        let set = program_instruction(
+            accounts::SetMatchingPolicy {
+                market: self.keys.market,
+                authority: self.admin,
+            },
+            instruction::SetMaxOrderSize { max_order_size },
+        );
+        let admin = self.admin;
+        self.send(&[set], &[admin])
+    }
+
+    /// Opens the market for trading from `open` to `close` into each epoch,
+    /// with call auctions of `auction_slots` opening and closing each
+    /// session, as the admin.
+    pub fn set_call_auctions(&mut self, open: u64, close: u64, auction_slots: u64) -> Result<Receipt, Failure> {
+        let accounts = || accounts::SetTradingHours {
+            market: self.keys.market,
+            authority: self.admin,
+        };
+        let hours = program_instruction(accounts(), instruction::SetTradingHours { open, close });
+        let auctions = program_instruction(accounts(), instruction::SetAuctionSlots { auction_slots });
+        let admin = self.admin;
+        self.send(&[hours, auctions], &[admin])
+    }
+
+    /// Halts the market for `halt_slots` after a fill moving its price more
+    /// than `threshold_bps`, reopening through a call auction of
+    /// `reopen_auction_slots`, as the admin.
+    pub fn set_circuit_breaker(
+        &mut self,
+        threshold_bps: u32,
+        halt_slots: u64,
+        reopen_auction_slots: u64,
+    ) -> Result<Receipt, Failure> {
+        let set = program_instruction(
+            accounts::SetTradingHours {
+                market: self.keys.market,
+                authority: self.admin,
+            },
+            instruction::SetCircuitBreaker {
+                threshold_bps,
+                halt_slots,
+                reopen_auction_slots,
+            },
+        );
+        let admin = self.admin;
+        self.send(&[set], &[admin])
+    }
+
+    /// Designates the agent as the market's adversary with scripted orders
+    /// capped at `max_size`, as the admin.
+    pub fn set_adversary(&mut self, agent: usize, max_size: u64) -> Result<Receipt, Failure> {
+        let set = program_instruction(
+            accounts::SetAdversary {
+                market: self.keys.market,
+                config: config_address(),
+                authority: self.admin,
+            },
+            instruction::SetAdversary {
+                adversary: self.agents[agent].agent,
+                max_size,
+            },
+        );
+        let admin = self.admin;
+        self.send(&[set], &[admin])
+    }
+
+    /// Places or replaces the adversary agent's scripted order, signed by its
+    /// owner.
+    pub fn place_adversary_order(
+        &mut self,
+        agent: usize,
+        side: Side,
+        price: u64,
+        quantity: u64,
+    ) -> Result<Receipt, Failure> {
+        let keys = self.agents[agent];
+        let quote = (quantity as u128 * price as u128 / PRICE_SCALE as u128) as u64;
+        let (trade_type, amount_in, amount_out) = match side {
+            Side::Bid => (0, quote, quantity),
+            Side::Ask => (1, quantity, quote),
+        };
+        let place = program_instruction(
+            accounts::PlaceAdversaryOrder {
+                agent: keys.agent,
+                market: self.keys.market,
+                config: config_address(),
+                trade: keys.trade,
+                user: keys.owner,
+                system_program: system_program::ID,
+            },
+            instruction::PlaceAdversaryOrder {
+                order_id: 0,
+                params: OrderParams {
+                    trade_type,
+                    amount_in,
+                    amount_out,
+                    display_size: 0,
+                    flags: 0,
+                    tag: [0; TRADE_TAG_LEN],
+                },
+            },
+        );
+        self.send(&[place], &[keys.owner])
+    }
+
+    /// Closes the agent's order, filled or not.
+    pub fn cancel_order(&mut self, agent: usize) -> Result<Receipt, Failure> {
+        let cancel = self.cancel_order_instruction(agent);
+        let owner = self.agents[agent].owner;
+        self.send(&[cancel], &[owner])
+    }
+
+    /// Like `cancel_order`, for the agent's trade `order_id`.
+    pub fn cancel_order_at(&mut self, agent: usize, order_id: u64) -> Result<Receipt, Failure> {
+        let cancel = self.cancel_order_at_instruction(agent, order_id);
+        let owner = self.agents[agent].owner;
+        self.send(&[cancel], &[owner])
+    }
+
+    /// The `cancel_trade` instruction behind `cancel_order`, signed by the
+    /// agent's owner.
+    pub fn cancel_order_instruction(&self, agent: usize) -> Instruction {
+        self.cancel_order_at_instruction(agent, 0)
+    }
+
+    /// The `cancel_trade` instruction behind `cancel_order_at`.
+    pub fn cancel_order_at_instruction(&self, agent: usize, order_id: u64) -> Instruction {
+        let keys = self.agents[agent];
+        program_instruction(
+            accounts::CancelTrade {
+                agent: keys.agent,
+                market: self.keys.market,
+                trade: trade_address(&keys.agent, &self.keys.market, order_id),
+                user: keys.owner,
+            },
+            instruction::CancelTrade {},
+        )
+    }
+
+    /// Instructions that cancel the agent's order, if it has one, and place
+    /// `order` as side, price and quantity instead, if given.
+    pub fn replace_order_instructions(&self, agent: usize, order: Option<(Side, u64, u64)>) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        if self.order(agent).is_some() {
+            instructions.push(self.cancel_order_instruction(agent));
+        }
+        if let Some((side, price, quantity)) = order {
+            instructions.push(self.place_order_instruction(agent, side, price, quantity));
+        }
+        instructions
+    }
+
+    /// The agent's order, if it has one open.
+    pub fn order(&self, agent: usize) -> Option<Trade> {
+        self.runtime.get(&self.agents[agent].trade)
+    }
+
+    /// The agent's trade `order_id`, if it exists.
+    pub fn order_at(&self, agent: usize, order_id: u64) -> Option<Trade> {
+        self.runtime.get(&trade_address(&self.agents[agent].agent, &self.keys.market, order_id))
+    }
+
+    /// Limit price of an order, token A per token B, `PRICE_SCALE` fixed point.
+    pub fn order_price(trade: &Trade) -> u64 {
+        let (quote, base) = match trade.trade_type {
+            0 => (trade.amount_in, trade.amount_out),
+            _ => (trade.amount_out, trade.amount_in),
+        };
+        (quote as u128 * PRICE_SCALE as u128 / base.max(1) as u128) as u64
+    }
+
+    /// Open orders with size left on `side`, best first then oldest first.
+    pub fn book(&self, side: Side) -> Vec<(usize, Trade)> {
+        let trade_type = match side {
+            Side::Bid => 0,
+            Side::Ask => 1,
+        };
+        let mut orders: Vec<(usize, Trade)> = (0..self.agents.len())
+            .filter_map(|agent| self.order(agent).map(|trade| (agent, trade)))
+            .filter(|(_, trade)| trade.trade_type == trade_type && trade.size() > 0)
+            .collect();
+        orders.sort_by(|(_, a), (_, b)| {
+            let (pa, pb) = (Self::order_price(a), Self::order_price(b));
+            let by_price = match side {
+                Side::Bid => pb.cmp(&pa),
+                Side::Ask => pa.cmp(&pb),
+            };
+            by_price.then(a.sequence.cmp(&b.sequence))
+        });
+        orders
+    }
+
+    /// Matches the best bid against the best ask until the book no longer
+    /// crosses, as a cranker would. Orders that fail to match are skipped.
+    /// On a pro-rata market the taker is matched against each maker at the
+    /// best level in turn, passing the level's makers still to come.
+    pub fn crank(&mut self) -> Vec<Fill> {
+        let mut fills = Vec::new();
+        let mut skipped = Vec::new();
+        loop {
+            let bids = self.book(Side::Bid);
+            let asks = self.book(Side::Ask);
+            let Some(&(bid_agent, ref bid)) = bids.iter().find(|(agent, _)| !skipped.contains(agent)) else {
+                break;
+            };
+            let Some(&(ask_agent, ref ask)) = asks.iter().find(|(agent, _)| !skipped.contains(agent)) else {
+                break;
+            };
+            if Self::order_price(bid) < Self::order_price(ask) {
+                break;
+            }
+            if self.market().matching_policy == MATCHING_PRO_RATA {
+                let (taker, makers) = if bid.sequence < ask.sequence {
+                    (ask_agent, &bids)
+                } else {
+                    (bid_agent, &asks)
+                };
+                let price = Self::order_price(&makers[0].1);
+                let mut level: Vec<usize> = makers
+                    .iter()
+                    .filter(|(_, trade)| Self::order_price(trade) == price)
+                    .map(|(agent, _)| *agent)
+                    .collect();
+                while !level.is_empty() {
+                    // With tie-break randomness the program fixes who goes next
+                    let market = self.market();
+                    let next = self
+                        .runtime
+                        .account(&market.randomness_account)
+                        .and_then(|account| {
+                            anchor_programs::randomness::seed(&self.keys.market, market.crank_sequence, &account.data).ok()
+                        })
+                        .and_then(|seed| {
+                            (0..level.len()).min_by_key(|&index| {
+                                anchor_programs::randomness::rank(&seed, &self.agents[level[index]].trade)
+                            })
+                        })
+                        .unwrap_or(0);
+                    let maker = level.remove(next);
+                    let (bid_agent, ask_agent) = if taker == ask_agent { (maker, taker) } else { (taker, maker) };
+                    let before = self.order(maker).map_or(0, |trade| trade.size());
+                    let mut match_trades = self.match_orders_instruction(bid_agent, ask_agent, self.admin);
+                    match_trades.accounts.extend(
+                        level.iter().map(|&agent| AccountMeta::new_readonly(self.agents[agent].trade, false)),
+                    );
+                    let admin = self.admin;
+                    match self.send(&[match_trades], &[admin]) {
+                        Ok(_) => fills.push(Fill {
+                            bid_agent,
+                            ask_agent,
+                            quantity: before - self.order(maker).map_or(0, |trade| trade.size()),
+                            price: self.market().last_price,
+                        }),
+                        // As below, the younger order is skipped: the taker
+                        Err(_) => {
+                            skipped.push(taker);
+                            break;
+                        }
+                    }
+                    if self.order(taker).is_none_or(|trade| trade.size() == 0) {
+                        break;
+                    }
+                }
+                continue;
+            }
+            let quantity = bid.displayed().min(ask.displayed());
+            match self.match_orders(bid_agent, ask_agent) {
+                Ok(_) => fills.push(Fill {
+                    bid_agent,
+                    ask_agent,
+                    quantity,
+                    price: self.market().last_price,
+                }),
+                // The younger order can't fill, e.g. for lack of balance
+                Err(_) => skipped.push(if bid.sequence > ask.sequence { bid_agent } else { ask_agent }),
+            }
+        }
+        fills
+    }
+
+    /// Sends `match_trades` for the two agents' orders, cranked by the admin.
+    pub fn match_orders(&mut self, bid_agent: usize, ask_agent: usize) -> Result<Receipt, Failure> {
+        let match_trades = self.match_orders_instruction(bid_agent, ask_agent, self.admin);
+        let admin = self.admin;
+        self.send(&[match_trades], &[admin])
+    }
+
+    /// Like `match_orders`, for the bid agent's trade `bid_id` and the ask
+    /// agent's trade `ask_id`.
+    pub fn match_orders_at(
+        &mut self,
+        (bid_agent, bid_id): (usize, u64),
+        (ask_agent, ask_id): (usize, u64),
+    ) -> Result<Receipt, Failure> {
+        let match_trades = self.match_orders_at_instruction((bid_agent, bid_id), (ask_agent, ask_id), self.admin);
+        let admin = self.admin;
+        self.send(&[match_trades], &[admin])
+    }
+
+    /// The `match_trades` instruction behind `match_orders`, signed by
+    /// `cranker` and numbered for the market's current crank sequence.
+    pub fn match_orders_instruction(&self, bid_agent: usize, ask_agent: usize, cranker: Pubkey) -> Instruction {
+        self.match_orders_at_instruction((bid_agent, 0), (ask_agent, 0), cranker)
+    }
+
+    /// The `match_trades` instruction behind `match_orders_at`.
+    pub fn match_orders_at_instruction(
+        &self,
+        (bid_agent, bid_id): (usize, u64),
+        (ask_agent, ask_id): (usize, u64),
+        cranker: Pubkey,
+    ) -> Instruction {
+        let (bid, ask) = (self.agents[bid_agent], self.agents[ask_agent]);
+        let keys = self.keys;
+        // The current epoch's leaderboard, snapshot and reward records are
+        // updated too once someone opens them, as are copy feeds once
+        // someone subscribes
+        let market = self.market();
+        let now = anchor_programs::clock::read(self.runtime.clock(), market.time_base).unwrap_or_default();
+        let leaderboard = leaderboard_address(&keys.market, market.current_epoch(now));
+        let snapshot = snapshot_address(&keys.market, market.current_epoch(now));
+        let bid_reward = reward_address(&keys.market, &bid.agent, market.current_epoch(now));
+        let ask_reward = reward_address(&keys.market, &ask.agent, market.current_epoch(now));
+        let bid_feed = copy_feed_address(&keys.market, &bid.agent);
+        let ask_feed = copy_feed_address(&keys.market, &ask.agent);
+        program_instruction(
+            accounts::MatchTrades {
+                market: keys.market,
+                config: config_address(),
+                bid: trade_address(&bid.agent, &keys.market, bid_id),
+                ask: trade_address(&ask.agent, &keys.market, ask_id),
+                bid_agent: bid.agent,
+                ask_agent: ask.agent,
+                vault_a: keys.vault_a,
+                vault_b: keys.vault_b,
+                bid_balance_a: bid.balance_a,
+                bid_balance_b: bid.balance_b,
+                ask_balance_a: ask.balance_a,
+                ask_balance_b: ask.balance_b,
+                bid_copy_feed: self.runtime.account(&bid_feed).map(|_| bid_feed),
+                ask_copy_feed: self.runtime.account(&ask_feed).map(|_| ask_feed),
+                leaderboard: self.runtime.account(&leaderboard).map(|_| leaderboard),
+                snapshot: self.runtime.account(&snapshot).map(|_| snapshot),
+                randomness: (market.randomness_account != Pubkey::default()).then_some(market.randomness_account),
+                emissions: None,
+                bid_emissions: None,
+                ask_emissions: None,
+                bid_bond: None,
+                ask_bond: None,
+                bid_reward: self.runtime.account(&bid_reward).map(|_| bid_reward),
+                ask_reward: self.runtime.account(&ask_reward).map(|_| ask_reward),
+                global_stats: global_stats_address(),
+                cranker,
+                system_program: system_program::ID,
+            },
+            instruction::MatchTrades {
+                crank_sequence: self.market().crank_sequence,
+            },
+        )
+    }
+
+    /// Every trade account the agent has open.
+    pub fn trades(&self, agent: usize) -> Vec<Pubkey> {
+        let key = self.agents[agent].agent;
+        self.runtime
+            .accounts()
+            .map(|(&address, _)| address)
+            .filter(|address| self.runtime.get::<Trade>(address).is_some_and(|trade| trade.agent == key))
+            .collect()
+    }
+
+    /// Agents with an order resting on the market, in agent order.
+    fn resting(&self) -> Vec<usize> {
+        (0..self.agents.len())
+            .filter(|&agent| self.order(agent).is_some_and(|trade| trade.size() > 0))
+            .collect()
+    }
+
+    /// Uncrosses the call auction due on the market as the admin, cranking
+    /// UNCROSS_PAGE orders at a time until it's done. Returns the last
+    /// crank's receipt.
+    pub fn uncross_auction(&mut self) -> Result<Receipt, Failure> {
+        let admin = self.admin;
+        loop {
+            let uncross = self.uncross_auction_instruction();
+            let receipt = self.send(&[uncross], &[admin])?;
+            if self.market().uncross.end == 0 {
+                return Ok(receipt);
+            }
+        }
+    }
+
+    /// The next `uncross_auction` crank behind `uncross_auction`: up to
+    /// UNCROSS_PAGE of the orders the stage under way still needs, with
+    /// their agents' balances and reward records.
+    pub fn uncross_auction_instruction(&self) -> Instruction {
+        let uncross = self.market().uncross;
+        let page: Vec<usize> = (0..self.agents.len())
+            .filter(|&agent| {
+                self.order(agent).is_some_and(|trade| match (uncross.end, uncross.stage) {
+                    (0, _) => trade.size() > 0,
+                    (end, UNCROSS_TALLY) => trade.size() > 0 && trade.uncross_end != end,
+                    (end, _) => trade.uncross_end == end,
+                })
+            })
+            .take(UNCROSS_PAGE)
+            .collect();
+        let rewards: Vec<Pubkey> = page.iter().filter_map(|&agent| self.current_reward(agent)).collect();
+        let mut crank = program_instruction(
+            accounts::UncrossAuction {
+                market: self.keys.market,
+                config: config_address(),
+                vault_a: self.keys.vault_a,
+                vault_b: self.keys.vault_b,
+                global_stats: global_stats_address(),
+            },
+            instruction::UncrossAuction {
+                reward_records: rewards.len() as u8,
+                crank_sequence: self.market().crank_sequence,
+            },
+        );
+        for agent in page {
+            let keys = self.agents[agent];
+            crank.accounts.extend([
+                AccountMeta::new(keys.trade, false),
+                AccountMeta::new(keys.balance_a, false),
+                AccountMeta::new(keys.balance_b, false),
+            ]);
+        }
+        crank.accounts.extend(rewards.iter().map(|&reward| AccountMeta::new(reward, false)));
+        crank
+    }
+
+    /// Where the running call auction would clear with the book as it is.
+    pub fn indicate_auction(&mut self) -> Result<AuctionIndication, Failure> {
+        let mut indicate = program_instruction(
+            accounts::IndicateAuction { market: self.keys.market },
+            instruction::IndicateAuction {},
+        );
+        for agent in self.resting() {
+            indicate.accounts.push(AccountMeta::new_readonly(self.agents[agent].trade, false));
+        }
+        let admin = self.admin;
+        let receipt = self.send(&[indicate], &[admin])?;
+        Ok(receipt.return_value().expect("indicate_auction returns its indication"))
+    }
+
+    /// Opens the market's observation frame as the admin.
+    pub fn open_observation_frame(&mut self, interval: u64) -> Result<Receipt, Failure> {
+        let open = program_instruction(
+            accounts::OpenObservationFrame {
+                market: self.keys.market,
+                observation_frame: observation_frame_address(&self.keys.market),
+                authority: self.admin,
+                system_program: system_program::ID,
+            },
+            instruction::OpenObservationFrame { interval },
+        );
+        let admin = self.admin;
+        self.send(&[open], &[admin])
+    }
+
+    /// Updates the market's observation frame from every resting order,
+    /// OBSERVATION_PAGE orders a crank in ascending key order. Returns the
+    /// last crank's receipt.
+    pub fn update_observation_frame(&mut self) -> Result<Receipt, Failure> {
+        let mut orders: Vec<Pubkey> = self.resting().into_iter().map(|agent| self.agents[agent].trade).collect();
+        orders.sort_unstable();
+        let admin = self.admin;
+        let mut pages = orders.chunks(OBSERVATION_PAGE).peekable();
+        loop {
+            let page = pages.next().unwrap_or_default();
+            let mut update = program_instruction(
+                accounts::UpdateObservationFrame {
+                    market: self.keys.market,
+                    observation_frame: observation_frame_address(&self.keys.market),
+                },
+                instruction::UpdateObservationFrame {},
+            );
+            update.accounts.extend(page.iter().map(|&order| AccountMeta::new_readonly(order, false)));
+            let receipt = self.send(&[update], &[admin])?;
+            if pages.peek().is_none() {
+                return Ok(receipt);
+            }
+        }
+    }
+
+    pub fn observation_frame(&self) -> ObservationFrame {
+        self.runtime
+            .load(&observation_frame_address(&self.keys.market))
+            .expect("observation frame missing")
+    }
+
+    /// Sets the balances `reset_agent` leaves an agent with, as the admin.
+    pub fn set_episode_capital(&mut self, capital_a: u64, capital_b: u64) -> Result<Receipt, Failure> {
+        let set = program_instruction(
+            accounts::SetEpisodeCapital {
+                market: self.keys.market,
+                config: config_address(),
+                authority: self.admin,
+            },
+            instruction::SetEpisodeCapital { capital_a, capital_b },
+        );
+        let admin = self.admin;
+        self.send(&[set], &[admin])
+    }
+
+    /// Resets the agent for a new episode as the admin, who is also the
+    /// simulated mints' authority: closes all its trades, zeroes its position
+    /// and sets its balances to the episode capital.
+    pub fn reset_agent(&mut self, agent: usize) -> Result<Receipt, Failure> {
+        let reset = self.reset_agent_instruction(agent);
+        let admin = self.admin;
+        self.send(&[reset], &[admin])
+    }
+
+    /// The `reset_agent_for_episode` instruction behind `reset_agent`, with
+    /// the agent's trades after its first passed last.
+    pub fn reset_agent_instruction(&self, agent: usize) -> Instruction {
+        let keys = self.agents[agent];
+        let position = Pubkey::find_program_address(
+            &[b"position", self.keys.market.as_ref(), keys.agent.as_ref()],
+            &anchor_programs::ID,
+        )
+        .0;
+        let mut reset = program_instruction(
+            accounts::ResetAgentForEpisode {
+                market: self.keys.market,
+                config: config_address(),
+                agent: keys.agent,
+                owner: keys.owner,
+                trade: self.runtime.account(&keys.trade).map(|_| keys.trade),
+                position: self.runtime.account(&position).map(|_| position),
+                adl_queue: Pubkey::find_program_address(&[b"adl", self.keys.market.as_ref()], &anchor_programs::ID).0,
+                vault_a: self.keys.vault_a,
+                vault_token_account_a: self.keys.vault_a_token_account,
+                token_a_mint: self.keys.token_a_mint,
+                balance_a: keys.balance_a,
+                vault_b: self.keys.vault_b,
+                vault_token_account_b: self.keys.vault_b_token_account,
+                token_b_mint: self.keys.token_b_mint,
+                balance_b: keys.balance_b,
+                authority: self.admin,
+                token_program: spl_token::ID,
+            },
+            instruction::ResetAgentForEpisode {},
+        );
+        let trades = self.trades(agent).into_iter().filter(|&trade| trade != keys.trade);
+        reset.accounts.extend(trades.map(|trade| AccountMeta::new(trade, false)));
+        reset
+    }
+
+    /// Sets the inventory penalty settled rewards are charged, as the admin.
+    pub fn set_inventory_penalty(&mut self, inventory_penalty_bps: u16) -> Result<Receipt, Failure> {
+        let set = program_instruction(
+            accounts::SetInventoryPenalty {
+                market: self.keys.market,
+                authority: self.admin,
+            },
+            instruction::SetInventoryPenalty { inventory_penalty_bps },
+        );
+        let admin = self.admin;
+        self.send(&[set], &[admin])
+    }
+
+    /// Opens the agent's reward record for the current epoch, paid by the
+    /// admin; `match_orders`, `uncross_auction`, `mirror_fill` and hybrid
+    /// trades pass it from then on.
+    pub fn open_reward_record(&mut self, agent: usize) -> Result<Receipt, Failure> {
+        let epoch = self.epoch();
+        let agent = self.agents[agent].agent;
+        let open = program_instruction(
+            accounts::OpenRewardRecord {
+                market: self.keys.market,
+                agent,
+                reward: reward_address(&self.keys.market, &agent, epoch),
+                payer: self.admin,
+                system_program: system_program::ID,
+            },
+            instruction::OpenRewardRecord { epoch },
+        );
+        let admin = self.admin;
+        self.send(&[open], &[admin])
+    }
+
+    /// Settles the agent's reward record for `epoch`, cranked by the admin.
+    pub fn settle_reward(&mut self, agent: usize, epoch: u64) -> Result<Receipt, Failure> {
+        let settle = program_instruction(
+            accounts::SettleReward {
+                market: self.keys.market,
+                reward: reward_address(&self.keys.market, &self.agents[agent].agent, epoch),
+            },
+            instruction::SettleReward {},
+        );
+        let admin = self.admin;
+        self.send(&[settle], &[admin])
+    }
+
+    /// The agent's reward record for the current epoch, once someone opens it.
+    pub fn current_reward(&self, agent: usize) -> Option<Pubkey> {
+        let market = self.market();
+        let now = anchor_programs::clock::read(self.runtime.clock(), market.time_base).unwrap_or_default();
+        let reward = reward_address(&self.keys.market, &self.agents[agent].agent, market.current_epoch(now));
+        self.runtime.account(&reward).map(|_| reward)
+    }
+
+    pub fn reward_record(&self, agent: usize, epoch: u64) -> RewardRecord {
+        self.runtime
+            .get(&reward_address(&self.keys.market, &self.agents[agent].agent, epoch))
+            .expect("reward record missing")
+    }
+
+    /// The market's current epoch.
+    pub fn epoch(&self) -> u64 {
+        let market = self.market();
+        let now = anchor_programs::clock::read(self.runtime.clock(), market.time_base).unwrap_or_default();
+        market.current_epoch(now)
+    }
+
+    /// Creates the market's curriculum from `stages`, as the admin.
+    pub fn create_curriculum(&mut self, stages: Vec<CurriculumStage>) -> Result<Receipt, Failure> {
+        let create = program_instruction(
+            accounts::CreateCurriculum {
+                market: self.keys.market,
+                curriculum: curriculum_address(&self.keys.market),
+                authority: self.admin,
+                system_program: system_program::ID,
+            },
+            instruction::CreateCurriculum { stages },
+        );
+        let admin = self.admin;
+        self.send(&[create], &[admin])
+    }
+
+    /// Applies the curriculum's due stages, passing every resting order in
+    /// case one withdraws liquidity; cranked by the admin.
+    pub fn advance_curriculum(&mut self) -> Result<Receipt, Failure> {
+        let mut advance = program_instruction(
+            accounts::AdvanceCurriculum {
+                market: self.keys.market,
+                curriculum: curriculum_address(&self.keys.market),
+            },
+            instruction::AdvanceCurriculum {},
+        );
+        for agent in self.resting() {
+            advance.accounts.push(AccountMeta::new(self.agents[agent].trade, false));
+        }
+        let admin = self.admin;
+        self.send(&[advance], &[admin])
+    }
+
+    /// Sets the performance fee the leader charges new followers.
+    pub fn set_performance_fee(&mut self, leader: usize, performance_fee_bps: u16) -> Result<Receipt, Failure> {
+        let keys = self.agents[leader];
+        let set = program_instruction(
+            accounts::SetPerformanceFee { agent: keys.agent, user: keys.owner },
+            instruction::SetPerformanceFee { performance_fee_bps },
+        );
+        self.send(&[set], &[keys.owner])
+    }
+
+    /// Subscribes the follower to `fraction_bps` of the leader's fills.
+    pub fn subscribe(&mut self, follower: usize, leader: usize, fraction_bps: u16) -> Result<Receipt, Failure> {
+        let (follower, leader) = (self.agents[follower], self.agents[leader]);
+        let market = self.keys.market;
+        let subscribe = program_instruction(
+            accounts::SubscribeToAgent {
+                follower: follower.agent,
+                leader: leader.agent,
+                market,
+                copy_feed: copy_feed_address(&market, &leader.agent),
+                subscription: subscription_address(&market, &follower.agent, &leader.agent),
+                user: follower.owner,
+                system_program: system_program::ID,
+            },
+            instruction::SubscribeToAgent { fraction_bps },
+        );
+        self.send(&[subscribe], &[follower.owner])
+    }
+
+    /// Donates `amount` of the agent's token A, or token B if `token_b`, to
+    /// the vault's own liquidity for mirrored fills.
+    pub fn fund_mirror_liquidity(&mut self, agent: usize, token_b: bool, amount: u64) -> Result<Receipt, Failure> {
+        let keys = self.agents[agent];
+        let (vault, balance) =
+            if token_b { (self.keys.vault_b, keys.balance_b) } else { (self.keys.vault_a, keys.balance_a) };
+        let fund = program_instruction(
+            accounts::FundMirrorLiquidity {
+                agent: keys.agent,
+                market: self.keys.market,
+                vault,
+                balance,
+                user: keys.owner,
+            },
+            instruction::FundMirrorLiquidity { amount },
+        );
+        self.send(&[fund], &[keys.owner])
+    }
+
+    /// Mirrors the follower's next fill copied from the leader, cranked by
+    /// the admin.
+    pub fn mirror_fill(&mut self, follower: usize, leader: usize) -> Result<Receipt, Failure> {
+        let follower_reward = self.current_reward(follower);
+        let (follower, leader) = (self.agents[follower], self.agents[leader]);
+        let market = self.keys.market;
+        let mirror = program_instruction(
+            accounts::MirrorFill {
+                market,
+                subscription: subscription_address(&market, &follower.agent, &leader.agent),
+                copy_feed: copy_feed_address(&market, &leader.agent),
+                oracle: None,
+                fallback_oracle: None,
+                vault_a: self.keys.vault_a,
+                vault_b: self.keys.vault_b,
+                follower_balance_a: follower.balance_a,
+                follower_balance_b: follower.balance_b,
+                leader_balance_a: leader.balance_a,
+                follower_reward,
+                global_stats: global_stats_address(),
+                cranker: self.admin,
+                system_program: system_program::ID,
+            },
+            instruction::MirrorFill {
+                crank_sequence: self.market().crank_sequence,
+            },
+        );
+        let admin = self.admin;
+        self.send(&[mirror], &[admin])
+    }
+
+    pub fn curriculum(&self) -> Curriculum {
+        self.runtime.get(&curriculum_address(&self.keys.market)).expect("curriculum missing")
+    }
+
+    pub fn config(&self) -> Config {
+        self.runtime.get(&config_address()).expect("config missing")
+    }
+
+    pub fn market(&self) -> Market {
+        self.runtime.get(&self.keys.market).expect("market missing")
+    }
+
+    pub fn agent(&self, agent: usize) -> Agent {
+        self.runtime.get(&self.agents[agent].agent).expect("agent missing")
+    }
+
+    /// The agent's token A and token B balances held by the program.
+    pub fn balances(&self, agent: usize) -> (u64, u64) {
+        let keys = &self.agents[agent];
+        let amount = |key: &Pubkey| self.runtime.get::<Balance>(key).map_or(0, |balance| balance.amount);
+        (amount(&keys.balance_a), amount(&keys.balance_b))
+    }
+
+    /// Withdraws `amount` of the agent's token B balance, or token A, to
+    /// its owner's wallet.
+    pub fn withdraw(&mut self, agent: usize, token_b: bool, amount: u64) -> Result<Receipt, Failure> {
+        let keys = self.agents[agent];
+        let (token_mint, user_token_account, vault, vault_token_account, balance) = match token_b {
+            false => (
+                self.keys.token_a_mint,
+                keys.token_a_account,
+                self.keys.vault_a,
+                self.keys.vault_a_token_account,
+                keys.balance_a,
+            ),
+            true => (
+                self.keys.token_b_mint,
+                keys.token_b_account,
+                self.keys.vault_b,
+                self.keys.vault_b_token_account,
+                keys.balance_b,
+            ),
+        };
+        let withdraw = program_instruction(
+            accounts::WithdrawTokens {
+                agent: keys.agent,
+                market: self.keys.market,
+                user: keys.owner,
+                token_mint,
+                user_token_account,
+                vault,
+                vault_token_account,
+                balance,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+            },
+            instruction::WithdrawTokens { amount },
+        );
+        self.send(&[withdraw], &[keys.owner])
+    }
+
+    /// Token A value of the agent's balances at `price`.
+    pub fn equity(&self, agent: usize, price: u64) -> u64 {
+        let (a, b) = self.balances(agent);
+        a + (b as u128 * price as u128 / PRICE_SCALE as u128) as u64
+    }
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
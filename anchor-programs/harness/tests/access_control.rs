@@ -0,0 +1,822 @@
+//! Access-control matrix. Every gated instruction is sent as its rightful
+//! signer would send it, then again with one thing wrong: someone else
+//! signing, the right key not signing, a PDA belonging to another agent, or
+//! an account from another market. Each wrong variant must fail with the
+//! error the instruction's constraints name, and the right one must succeed
+//! from the same state, so a failure can't come from anything but the
+//! tampered account.
+#![allow(deprecated)]
+use anchor_lang::error::ErrorCode as AnchorError;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_program;
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::{accounts, instruction, FeeTier, MATCHING_PRICE_TIME, SESSION_SCOPE_CANCEL};
+use neuraltrader_harness::runtime::{Account, Failure, Receipt};
+use neuraltrader_harness::sim::{
+    config_address, global_stats_address, market_listing_address, program_instruction, MarketKeys,
+    SIM_DECIMALS,
+};
+use neuraltrader_harness::{Side, Simulation};
+use spl_associated_token_account::get_associated_token_address;
+
+const LAMPORTS: u64 = 10_000_000_000;
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+
+#[derive(Clone, Copy, Debug)]
+enum Expect {
+    Program(ErrorCode),
+    Anchor(AnchorError),
+}
+
+/// A single instruction and who signs it.
+#[derive(Clone)]
+struct Call {
+    instruction: Instruction,
+    signers: Vec<Pubkey>,
+}
+
+impl Call {
+    fn new(instruction: Instruction, signers: &[Pubkey]) -> Self {
+        Self {
+            instruction,
+            signers: signers.to_vec(),
+        }
+    }
+
+    fn send(&self, sim: &mut Simulation) -> Result<Receipt, Failure> {
+        sim.send(std::slice::from_ref(&self.instruction), &self.signers)
+    }
+
+    /// The same call with every use of `from` replaced by `to`, and `to`
+    /// signing wherever `from` did.
+    fn replace(&self, from: Pubkey, to: Pubkey) -> Self {
+        let mut call = self.clone();
+        for meta in &mut call.instruction.accounts {
+            if meta.pubkey == from {
+                meta.pubkey = to;
+            }
+        }
+        for signer in &mut call.signers {
+            if *signer == from {
+                *signer = to;
+            }
+        }
+        call
+    }
+
+    /// The same call sent by `intruder` in place of `signer`: swapped in as
+    /// the signing account, while the accounts it acts on stay the victim's.
+    fn signed_by(&self, signer: Pubkey, intruder: Pubkey) -> Self {
+        self.replace(signer, intruder)
+    }
+
+    /// The same call with `key` passed but not signing.
+    fn unsigned(&self, key: Pubkey) -> Self {
+        let mut call = self.clone();
+        for meta in &mut call.instruction.accounts {
+            if meta.pubkey == key {
+                meta.is_signer = false;
+            }
+        }
+        call.signers.retain(|signer| *signer != key);
+        call
+    }
+}
+
+/// The simulated market, a second market with its own authority, two agents
+/// on the first, a session key Alice delegated cancelling to and a key with
+/// nothing to do with any of them.
+struct World {
+    sim: Simulation,
+    foreign: MarketKeys,
+    foreign_authority: Pubkey,
+    alice: usize,
+    bob: usize,
+    delegate: Pubkey,
+    intruder: Pubkey,
+}
+
+impl World {
+    fn new() -> Self {
+        let mut sim = Simulation::new();
+        let alice = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+        let bob = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+        // The vault fills at the market price out of its own liquidity, so
+        // the market has traded at Alice's price and has token B to sell
+        let maker = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+        let taker = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+        sim.place_order(maker, Side::Ask, PRICE, 1_000_000).unwrap();
+        sim.place_order(taker, Side::Bid, 2 * PRICE, 1_000_000).unwrap();
+        sim.match_orders(taker, maker).unwrap();
+        sim.fund_mirror_liquidity(maker, true, 1_000_000).unwrap();
+        sim.place_order(alice, Side::Bid, PRICE, 1_000_000).unwrap();
+        sim.place_order(bob, Side::Ask, 2 * PRICE, 1_000_000).unwrap();
+        let intruder = sim.new_key();
+        sim.runtime.airdrop(intruder, LAMPORTS);
+        let foreign_authority = sim.new_key();
+        sim.runtime.airdrop(foreign_authority, LAMPORTS);
+        let foreign = add_market(&mut sim, foreign_authority);
+        let perps = program_instruction(
+            accounts::SetPerpParams { market: sim.keys.market, authority: sim.admin },
+            instruction::SetPerpParams { initial_margin_bps: 1_000, maintenance_margin_bps: 500, liquidation_fee_bps: 100 },
+        );
+        let admin = sim.admin;
+        sim.send(&[perps], &[admin]).unwrap();
+        let delegate = sim.new_key();
+        let keys = sim.agents[alice];
+        let session = program_instruction(
+            accounts::StartSession { agent: keys.agent, user: keys.owner },
+            instruction::StartSession { session_key: delegate, expiry_slot: u64::MAX, scope: SESSION_SCOPE_CANCEL },
+        );
+        sim.send(&[session], &[keys.owner]).unwrap();
+
+        // Executing against a vault pays from the wallet, not the balance,
+        // so everyone who tries has token A of both markets to pay with
+        for owner in [sim.agents[alice].owner, sim.agents[bob].owner, intruder] {
+            for mint in [sim.keys.token_a_mint, foreign.token_a_mint] {
+                sim.mint_to(get_associated_token_address(&owner, &mint), mint, owner, 1_000_000_000);
+            }
+        }
+        Self {
+            sim,
+            foreign,
+            foreign_authority,
+            alice,
+            bob,
+            delegate,
+            intruder,
+        }
+    }
+
+    /// `call` made by `impostor` instead of Alice, paying from and receiving
+    /// into the impostor's own wallet.
+    fn impersonate(&self, call: &Call, impostor: Pubkey) -> Call {
+        let alice = self.sim.agents[self.alice];
+        let wallet = |mint: &Pubkey| get_associated_token_address(&impostor, mint);
+        call.signed_by(alice.owner, impostor)
+            .replace(alice.token_a_account, wallet(&self.sim.keys.token_a_mint))
+            .replace(alice.token_b_account, wallet(&self.sim.keys.token_b_mint))
+    }
+
+    /// Runs `right` and `wrong` from the current state, each on its own copy.
+    fn check(&self, name: &str, right: &Call, wrong: &Call, expect: Expect) {
+        let mut sim = self.sim.clone();
+        if let Err(failure) = right.send(&mut sim) {
+            panic!("{name}: rightful call failed: {:?}\n{:#?}", failure.error, failure.logs);
+        }
+
+        let mut sim = self.sim.clone();
+        let failure = match wrong.send(&mut sim) {
+            Ok(_) => panic!("{name}: expected {expect:?}, but it succeeded"),
+            Err(failure) => failure,
+        };
+        let code = failure.code();
+        let matches = match expect {
+            Expect::Program(error) => code == Some(u32::from(error)),
+            Expect::Anchor(error) => code == Some(u32::from(error)),
+        };
+        assert!(matches, "{name}: expected {expect:?}, got {:?}\n{:#?}", failure.error, failure.logs);
+    }
+}
+
+/// Lists a second market over two new mints under `authority`, handing the
+/// config's admin role over for its creation and back.
+fn add_market(sim: &mut Simulation, authority: Pubkey) -> MarketKeys {
+    let (token_a_mint, token_b_mint) = (sim.new_key(), sim.new_key());
+    for mint in [token_a_mint, token_b_mint] {
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: Some(sim.admin).into(),
+            supply: 0,
+            decimals: SIM_DECIMALS,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        }
+        .pack_into_slice(&mut data);
+        let lamports = sim.runtime.rent().minimum_balance(data.len());
+        sim.runtime.set_account(
+            mint,
+            Account {
+                lamports,
+                data,
+                owner: spl_token::ID,
+                executable: false,
+            },
+        );
+    }
+    let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &anchor_programs::ID).0;
+    let vault = |mint: &Pubkey| find(&[b"vault", token_a_mint.as_ref(), token_b_mint.as_ref(), mint.as_ref()]);
+    let (vault_a, vault_b) = (vault(&token_a_mint), vault(&token_b_mint));
+    let keys = MarketKeys {
+        market: find(&[b"market", token_a_mint.as_ref(), token_b_mint.as_ref()]),
+        token_a_mint,
+        token_b_mint,
+        vault_a,
+        vault_b,
+        vault_a_token_account: get_associated_token_address(&vault_a, &token_a_mint),
+        vault_b_token_account: get_associated_token_address(&vault_b, &token_b_mint),
+    };
+
+    let config = sim.config();
+    let set_admin = |admin: Pubkey| {
+        program_instruction(
+            accounts::UpdateConfig {
+                config: config_address(),
+                admin: if admin == authority { sim.admin } else { authority },
+            },
+            instruction::UpdateConfig {
+                admin,
+                maker_fee_bps: config.maker_fee_bps,
+                taker_fee_bps: config.taker_fee_bps,
+                features: config.features,
+            },
+        )
+    };
+    let initialize_market = program_instruction(
+        accounts::InitializeMarket {
+            config: config_address(),
+            market: keys.market,
+            market_listing: market_listing_address(config.market_count),
+            global_stats: global_stats_address(),
+            vault_a,
+            vault_b,
+            vault_a_token_account: keys.vault_a_token_account,
+            vault_b_token_account: keys.vault_b_token_account,
+            token_a_mint,
+            token_b_mint,
+            signer: authority,
+            associated_token_program: spl_associated_token_account::ID,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeMarket {},
+    );
+    let admin = sim.admin;
+    let instructions = [set_admin(authority), initialize_market, set_admin(admin)];
+    sim.send(&instructions, &[admin, authority])
+        .expect("foreign market setup failed");
+    keys
+}
+
+/// Calls only the market's authority may make, each with the accounts
+/// `market` and `authority`.
+fn authority_calls(sim: &Simulation, market: Pubkey, authority: Pubkey) -> Vec<(&'static str, Call)> {
+    let setter = |instruction| Call::new(instruction, &[authority]);
+    let vault_a = sim.keys.vault_a;
+    vec![
+        (
+            "set_market_fees",
+            setter(program_instruction(
+                accounts::SetMarketFees { market, authority },
+                instruction::SetMarketFees { maker_fee_bps: 1, taker_fee_bps: 2 },
+            )),
+        ),
+        (
+            "set_fee_tiers",
+            setter(program_instruction(
+                accounts::SetMarketFees { market, authority },
+                instruction::SetFeeTiers {
+                    tiers: vec![FeeTier { min_volume: 1_000, maker_fee_bps: 0, taker_fee_bps: 0 }],
+                },
+            )),
+        ),
+        (
+            "set_volatility_fees",
+            setter(program_instruction(
+                accounts::SetMarketFees { market, authority },
+                instruction::SetVolatilityFees { coefficient: 1, min_taker_fee_bps: 0, max_taker_fee_bps: 10 },
+            )),
+        ),
+        (
+            "set_settlement_mode",
+            setter(program_instruction(
+                accounts::SetSettlementMode { market, authority },
+                instruction::SetSettlementMode { net_settlement: true },
+            )),
+        ),
+        (
+            "set_matching_policy",
+            setter(program_instruction(
+                accounts::SetMatchingPolicy { market, authority },
+                instruction::SetMatchingPolicy { matching_policy: MATCHING_PRICE_TIME },
+            )),
+        ),
+        (
+            "set_tie_break_randomness",
+            setter(program_instruction(
+                accounts::SetMatchingPolicy { market, authority },
+                instruction::SetTieBreakRandomness { randomness_account: Pubkey::new_unique() },
+            )),
+        ),
+        (
+            "set_action_encoding",
+            setter(program_instruction(
+                accounts::SetMatchingPolicy { market, authority },
+                instruction::SetActionEncoding { action_lot: 1_000, action_tick: 1_000 },
+            )),
+        ),
+        (
+            "set_max_order_size",
+            setter(program_instruction(
+                accounts::SetMatchingPolicy { market, authority },
+                instruction::SetMaxOrderSize { max_order_size: 1_000 },
+            )),
+        ),
+        (
+            "set_episode_capital",
+            setter(program_instruction(
+                accounts::SetEpisodeCapital { market, config: config_address(), authority },
+                instruction::SetEpisodeCapital { capital_a: 1_000, capital_b: 1_000 },
+            )),
+        ),
+        (
+            "set_inventory_penalty",
+            setter(program_instruction(
+                accounts::SetInventoryPenalty { market, authority },
+                instruction::SetInventoryPenalty { inventory_penalty_bps: 100 },
+            )),
+        ),
+        (
+            "set_adversary",
+            setter(program_instruction(
+                accounts::SetAdversary { market, config: config_address(), authority },
+                instruction::SetAdversary { adversary: Pubkey::new_unique(), max_size: 1_000 },
+            )),
+        ),
+        (
+            "set_trading_hours",
+            setter(program_instruction(
+                accounts::SetTradingHours { market, authority },
+                instruction::SetTradingHours { open: 100, close: 200 },
+            )),
+        ),
+        (
+            "set_auction_slots",
+            setter(program_instruction(
+                accounts::SetTradingHours { market, authority },
+                instruction::SetAuctionSlots { auction_slots: 0 },
+            )),
+        ),
+        (
+            "set_circuit_breaker",
+            setter(program_instruction(
+                accounts::SetTradingHours { market, authority },
+                instruction::SetCircuitBreaker { threshold_bps: 1_000, halt_slots: 100, reopen_auction_slots: 100 },
+            )),
+        ),
+        (
+            "set_sandwich_guard",
+            setter(program_instruction(
+                accounts::SetSandwichGuard { market, authority },
+                instruction::SetSandwichGuard { enabled: true },
+            )),
+        ),
+        (
+            "set_jupiter_routing",
+            setter(program_instruction(
+                accounts::SetJupiterRouting { market, authority },
+                instruction::SetJupiterRouting { enabled: true },
+            )),
+        ),
+        (
+            "set_mirror_pool",
+            setter(program_instruction(
+                accounts::SetMirrorPool { market, authority },
+                instruction::SetMirrorPool { venue: 0, pool: Pubkey::default() },
+            )),
+        ),
+        (
+            "set_oracle_config",
+            setter(program_instruction(
+                accounts::SetOracleConfig { market, authority },
+                instruction::SetOracleConfig {
+                    primary_oracle: Pubkey::new_unique(),
+                    fallback_oracle: Pubkey::default(),
+                    max_staleness_slots: 25,
+                    max_confidence_bps: 100,
+                },
+            )),
+        ),
+        (
+            "set_relayer",
+            setter(program_instruction(
+                accounts::SetRelayer { market, authority },
+                instruction::SetRelayer { relayer: Pubkey::new_unique(), fee_oracle: Pubkey::default() },
+            )),
+        ),
+        (
+            "set_perp_params",
+            setter(program_instruction(
+                accounts::SetPerpParams { market, authority },
+                instruction::SetPerpParams {
+                    initial_margin_bps: 1_000,
+                    maintenance_margin_bps: 500,
+                    liquidation_fee_bps: 100,
+                },
+            )),
+        ),
+        (
+            "set_open_interest_limits",
+            setter(program_instruction(
+                accounts::SetOpenInterestLimits { market, authority },
+                instruction::SetOpenInterestLimits { max_long: 1, max_short: 1, skew_scale: 0 },
+            )),
+        ),
+        (
+            "set_funding_params",
+            setter(program_instruction(
+                accounts::SetFundingParams { market, authority },
+                instruction::SetFundingParams { max_funding_bps: 10, borrow_rate_bps: 100 },
+            )),
+        ),
+        (
+            "collect_excess_lamports",
+            setter(program_instruction(
+                accounts::CollectExcessLamports { market, account: vault_a, authority },
+                instruction::CollectExcessLamports {},
+            )),
+        ),
+    ]
+}
+
+#[test]
+fn market_setters_need_the_market_authority() {
+    let world = World::new();
+    let (market, authority) = (world.sim.keys.market, world.sim.admin);
+    for (name, right) in authority_calls(&world.sim, market, authority) {
+        let unauthorized = Expect::Program(ErrorCode::Unauthorized);
+        world.check(name, &right, &right.signed_by(authority, world.intruder), unauthorized);
+        // Being another market's authority grants nothing here
+        world.check(name, &right, &right.signed_by(authority, world.foreign_authority), unauthorized);
+        world.check(name, &right, &right.unsigned(authority), Expect::Anchor(AnchorError::AccountNotSigner));
+    }
+}
+
+#[test]
+fn sweeping_donations_needs_the_market_authority_and_its_own_vault() {
+    let world = World::new();
+    let (sim, authority) = (&world.sim, world.sim.admin);
+    let right = Call::new(
+        program_instruction(
+            accounts::SweepDonations {
+                market: sim.keys.market,
+                vault: sim.keys.vault_a,
+                vault_token_account: sim.keys.vault_a_token_account,
+                authority,
+            },
+            instruction::SweepDonations { insurance_bps: 0 },
+        ),
+        &[authority],
+    );
+    world.check("sweep_donations", &right, &right.signed_by(authority, world.intruder), Expect::Program(ErrorCode::Unauthorized));
+    let foreign_vault = right
+        .replace(sim.keys.vault_a, world.foreign.vault_a)
+        .replace(sim.keys.vault_a_token_account, world.foreign.vault_a_token_account);
+    world.check("sweep_donations", &right, &foreign_vault, Expect::Anchor(AnchorError::ConstraintSeeds));
+}
+
+#[test]
+fn config_updates_need_the_admin() {
+    let world = World::new();
+    let (sim, admin) = (&world.sim, world.sim.admin);
+    let config = sim.config();
+    let update = Call::new(
+        program_instruction(
+            accounts::UpdateConfig { config: config_address(), admin },
+            instruction::UpdateConfig {
+                admin,
+                maker_fee_bps: config.maker_fee_bps,
+                taker_fee_bps: config.taker_fee_bps,
+                features: config.features,
+            },
+        ),
+        &[admin],
+    );
+    let emergency = Call::new(
+        program_instruction(
+            accounts::UpdateConfig { config: config_address(), admin },
+            instruction::SetEmergencyMode { enabled: true },
+        ),
+        &[admin],
+    );
+    for (name, right) in [("update_config", update), ("set_emergency_mode", emergency)] {
+        world.check(name, &right, &right.signed_by(admin, world.intruder), Expect::Program(ErrorCode::Unauthorized));
+        world.check(name, &right, &right.signed_by(admin, world.foreign_authority), Expect::Program(ErrorCode::Unauthorized));
+        world.check(name, &right, &right.unsigned(admin), Expect::Anchor(AnchorError::AccountNotSigner));
+        // A look-alike account in place of the config PDA
+        world.check(name, &right, &right.replace(config_address(), sim.keys.market), Expect::Anchor(AnchorError::AccountDiscriminatorMismatch));
+    }
+}
+
+/// Calls that act for Alice's agent, signed by Alice.
+fn owner_calls(world: &World) -> Vec<(&'static str, Call)> {
+    let sim = &world.sim;
+    let keys = sim.agents[world.alice];
+    let (agent, user) = (keys.agent, keys.owner);
+    let market = sim.keys.market;
+    let owned = |instruction| Call::new(instruction, &[user]);
+    let settlement = Pubkey::find_program_address(&[b"settlement", market.as_ref(), agent.as_ref()], &anchor_programs::ID).0;
+    let position = Pubkey::find_program_address(&[b"position", market.as_ref(), agent.as_ref()], &anchor_programs::ID).0;
+    vec![
+        (
+            "set_stp_mode",
+            owned(program_instruction(accounts::SetStpMode { agent, user }, instruction::SetStpMode { stp_mode: 1 })),
+        ),
+        (
+            "set_performance_fee",
+            owned(program_instruction(
+                accounts::SetPerformanceFee { agent, user },
+                instruction::SetPerformanceFee { performance_fee_bps: 1 },
+            )),
+        ),
+        (
+            "start_session",
+            owned(program_instruction(
+                accounts::StartSession { agent, user },
+                instruction::StartSession { session_key: world.intruder, expiry_slot: u64::MAX, scope: SESSION_SCOPE_CANCEL },
+            )),
+        ),
+        (
+            "set_session_limits",
+            owned(program_instruction(
+                accounts::SetSessionLimits { agent, user },
+                instruction::SetSessionLimits { session_key: world.delegate, spend_limits: [1; 2], epoch_caps: [1; 2] },
+            )),
+        ),
+        (
+            "transfer_agent_ownership",
+            owned(program_instruction(
+                accounts::TransferAgentOwnership { agent, user },
+                instruction::TransferAgentOwnership { new_owner: world.intruder },
+            )),
+        ),
+        (
+            "modify_trade",
+            owned(program_instruction(
+                accounts::ModifyTrade {
+                    agent,
+                    market,
+                    config: config_address(),
+                    trade: keys.trade,
+                    vault_a: None,
+                    balance_a: None,
+                    user,
+                },
+                instruction::ModifyTrade { amount_in: 500_000, amount_out: 1_000_000 },
+            )),
+        ),
+        (
+            "cancel_trade",
+            owned(sim.cancel_order_instruction(world.alice)),
+        ),
+        (
+            "execute_trade",
+            owned(program_instruction(
+                accounts::ExecuteTrade {
+                    trade: keys.trade,
+                    market,
+                    config: config_address(),
+                    agent,
+                    user,
+                    token_in_mint: sim.keys.token_a_mint,
+                    token_out_mint: sim.keys.token_b_mint,
+                    user_token_account_in: keys.token_a_account,
+                    user_token_account_out: keys.token_b_account,
+                    vault_in: sim.keys.vault_a,
+                    vault_token_account_in: sim.keys.vault_a_token_account,
+                    vault_out: sim.keys.vault_b,
+                    vault_token_account_out: sim.keys.vault_b_token_account,
+                    settlement: None,
+                    reward: None,
+                    oracle: None,
+                    fallback_oracle: None,
+                    instructions: None,
+                    global_stats: global_stats_address(),
+                    token_program: spl_token::ID,
+                    associated_token_program: spl_associated_token_account::ID,
+                    system_program: system_program::ID,
+                },
+                instruction::ExecuteTrade {},
+            )),
+        ),
+        (
+            "withdraw_tokens",
+            owned(program_instruction(
+                accounts::WithdrawTokens {
+                    agent,
+                    market,
+                    user,
+                    token_mint: sim.keys.token_a_mint,
+                    user_token_account: keys.token_a_account,
+                    vault: sim.keys.vault_a,
+                    vault_token_account: sim.keys.vault_a_token_account,
+                    balance: keys.balance_a,
+                    token_program: spl_token::ID,
+                    associated_token_program: spl_associated_token_account::ID,
+                    system_program: system_program::ID,
+                },
+                instruction::WithdrawTokens { amount: 1 },
+            )),
+        ),
+        (
+            "internal_transfer",
+            owned(program_instruction(
+                accounts::InternalTransfer {
+                    from_agent: agent,
+                    to_agent: sim.agents[world.bob].agent,
+                    user,
+                    market,
+                    vault: sim.keys.vault_a,
+                    balance_from: keys.balance_a,
+                    balance_to: sim.agents[world.bob].balance_a,
+                    system_program: system_program::ID,
+                },
+                instruction::InternalTransfer { amount: 1 },
+            )),
+        ),
+        (
+            "fund_insurance",
+            owned(program_instruction(
+                accounts::FundInsurance { agent, market, vault_a: sim.keys.vault_a, balance_a: keys.balance_a, user },
+                instruction::FundInsurance { amount: 1 },
+            )),
+        ),
+        (
+            "fund_mirror_liquidity",
+            owned(program_instruction(
+                accounts::FundMirrorLiquidity { agent, market, vault: sim.keys.vault_a, balance: keys.balance_a, user },
+                instruction::FundMirrorLiquidity { amount: 1 },
+            )),
+        ),
+        (
+            "open_settlement",
+            owned(program_instruction(
+                accounts::OpenSettlement { agent, market, settlement, user, system_program: system_program::ID },
+                instruction::OpenSettlement {},
+            )),
+        ),
+        (
+            "deposit_collateral",
+            owned(program_instruction(
+                accounts::PositionCollateral {
+                    agent,
+                    market,
+                    position,
+                    vault_a: sim.keys.vault_a,
+                    balance_a: keys.balance_a,
+                    oracle: None,
+                    fallback_oracle: None,
+                    user,
+                    system_program: system_program::ID,
+                },
+                instruction::DepositCollateral { amount: 1 },
+            )),
+        ),
+    ]
+}
+
+#[test]
+fn agent_instructions_need_the_agent_owner() {
+    let world = World::new();
+    let alice = world.sim.agents[world.alice].owner;
+    let bob = world.sim.agents[world.bob].owner;
+    for (name, right) in owner_calls(&world) {
+        let not_owner = Expect::Program(ErrorCode::InvalidAgentOwner);
+        world.check(name, &right, &world.impersonate(&right, world.intruder), not_owner);
+        // Another agent's owner is no more entitled than a stranger
+        world.check(name, &right, &world.impersonate(&right, bob), not_owner);
+        world.check(name, &right, &right.unsigned(alice), Expect::Anchor(AnchorError::AccountNotSigner));
+    }
+}
+
+#[test]
+fn sessions_only_grant_their_scope() {
+    let mut world = World::new();
+    let keys = world.sim.agents[world.alice];
+    let session = world.intruder;
+    world
+        .sim
+        .send(
+            &[program_instruction(
+                accounts::StartSession { agent: keys.agent, user: keys.owner },
+                instruction::StartSession { session_key: session, expiry_slot: u64::MAX, scope: SESSION_SCOPE_CANCEL },
+            )],
+            &[keys.owner],
+        )
+        .unwrap();
+
+    // The session may cancel but not modify, and never withdraw
+    let calls = owner_calls(&world);
+    let call = |name: &str| calls.iter().find(|(call, _)| *call == name).unwrap().1.clone();
+    let cancel = call("cancel_trade");
+    let by_session = cancel.signed_by(keys.owner, session);
+    by_session.send(&mut world.sim.clone()).unwrap();
+    for name in ["modify_trade", "withdraw_tokens", "set_stp_mode"] {
+        let right = call(name);
+        world.check(name, &right, &right.signed_by(keys.owner, session), Expect::Program(ErrorCode::InvalidAgentOwner));
+    }
+}
+
+#[test]
+fn agent_pdas_must_be_the_agents_own() {
+    let world = World::new();
+    let sim = &world.sim;
+    let (alice, bob) = (sim.agents[world.alice], sim.agents[world.bob]);
+    let calls = owner_calls(&world);
+    let call = |name: &str| calls.iter().find(|(call, _)| *call == name).unwrap().1.clone();
+
+    // Bob's balance and trade under Alice's agent
+    let seeds = Expect::Anchor(AnchorError::ConstraintSeeds);
+    let withdraw = call("withdraw_tokens");
+    world.check("withdraw_tokens", &withdraw, &withdraw.replace(alice.balance_a, bob.balance_a), seeds);
+    let fund = call("fund_insurance");
+    world.check("fund_insurance", &fund, &fund.replace(alice.balance_a, bob.balance_a), seeds);
+    let cancel = call("cancel_trade");
+    world.check("cancel_trade", &cancel, &cancel.replace(alice.trade, bob.trade), seeds);
+
+    // Alice signing with Bob's agent is still not Bob
+    let stp = call("set_stp_mode");
+    world.check("set_stp_mode", &stp, &stp.replace(alice.agent, bob.agent), Expect::Program(ErrorCode::InvalidAgentOwner));
+}
+
+#[test]
+fn foreign_market_accounts_are_rejected() {
+    let world = World::new();
+    let sim = &world.sim;
+    let (market, foreign) = (sim.keys, world.foreign);
+    let calls = owner_calls(&world);
+    let call = |name: &str| calls.iter().find(|(call, _)| *call == name).unwrap().1.clone();
+
+    // Alice's trade rests on the first market, so it can't be cancelled,
+    // modified or executed through the second
+    let seeds = Expect::Anchor(AnchorError::ConstraintSeeds);
+    for name in ["cancel_trade", "modify_trade"] {
+        let right = call(name);
+        world.check(name, &right, &right.replace(market.market, foreign.market), seeds);
+    }
+    let execute = call("execute_trade");
+    let alice = sim.agents[world.alice];
+    let foreign_wallet = |mint: &Pubkey| get_associated_token_address(&alice.owner, mint);
+    let foreign_execute = execute
+        .replace(alice.token_a_account, foreign_wallet(&foreign.token_a_mint))
+        .replace(alice.token_b_account, foreign_wallet(&foreign.token_b_mint))
+        .replace(market.market, foreign.market)
+        .replace(market.token_a_mint, foreign.token_a_mint)
+        .replace(market.token_b_mint, foreign.token_b_mint)
+        .replace(market.vault_a, foreign.vault_a)
+        .replace(market.vault_b, foreign.vault_b)
+        .replace(market.vault_a_token_account, foreign.vault_a_token_account)
+        .replace(market.vault_b_token_account, foreign.vault_b_token_account);
+    world.check("execute_trade", &execute, &foreign_execute, Expect::Program(ErrorCode::InvalidMarket));
+
+    // The first market's balances paired with the second market's vault
+    let withdraw = call("withdraw_tokens");
+    let foreign_vault = withdraw
+        .replace(market.vault_a, foreign.vault_a)
+        .replace(market.vault_a_token_account, foreign.vault_a_token_account);
+    world.check("withdraw_tokens", &withdraw, &foreign_vault, seeds);
+
+    // Nor can the second market's authority run the first market's vault
+    let authority = sim.admin;
+    let collect = authority_calls(sim, market.market, authority)
+        .into_iter()
+        .find(|(name, _)| *name == "collect_excess_lamports")
+        .unwrap()
+        .1;
+    world.check(
+        "collect_excess_lamports",
+        &collect,
+        &collect.replace(market.market, foreign.market).signed_by(authority, world.intruder),
+        Expect::Program(ErrorCode::Unauthorized),
+    );
+}
+
+#[test]
+fn ownership_transfers_need_the_pending_owner() {
+    let mut world = World::new();
+    let keys = world.sim.agents[world.alice];
+    let new_owner = world.sim.agents[world.bob].owner;
+    world
+        .sim
+        .send(
+            &[program_instruction(
+                accounts::TransferAgentOwnership { agent: keys.agent, user: keys.owner },
+                instruction::TransferAgentOwnership { new_owner },
+            )],
+            &[keys.owner],
+        )
+        .unwrap();
+    let accept = Call::new(
+        program_instruction(
+            accounts::AcceptAgentOwnership { agent: keys.agent, new_owner },
+            instruction::AcceptAgentOwnership {},
+        ),
+        &[new_owner],
+    );
+    let not_pending = Expect::Program(ErrorCode::NotPendingOwner);
+    world.check("accept_agent_ownership", &accept, &accept.signed_by(new_owner, world.intruder), not_pending);
+    // The current owner can't accept on the new owner's behalf either
+    world.check("accept_agent_ownership", &accept, &accept.signed_by(new_owner, keys.owner), not_pending);
+}
@@ -0,0 +1,49 @@
+//! Handing an agent to a new owner, and the identity NFT taking over once
+//! the agent has one.
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::AccountSerialize;
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::{accounts, instruction};
+use neuraltrader_harness::sim::program_instruction;
+use neuraltrader_harness::Simulation;
+
+/// Binds the agent to an identity NFT in place, leaving the rest of it as it
+/// was; `mint_agent_nft` needs the token metadata program, which the harness
+/// doesn't load.
+fn bind_nft(sim: &mut Simulation, agent: usize, nft_mint: Pubkey) {
+    let key = sim.agents[agent].agent;
+    let mut state = sim.agent(agent);
+    state.nft_mint = nft_mint;
+    let mut account = sim.runtime.account(&key).unwrap().clone();
+    account.data.clear();
+    state.try_serialize(&mut account.data).unwrap();
+    sim.runtime.set_account(key, account);
+}
+
+#[test]
+fn an_nft_bound_agent_only_changes_hands_with_its_nft() {
+    let mut sim = Simulation::new();
+    let alice = sim.add_agent(1_000_000, 1_000_000).unwrap();
+    let keys = sim.agents[alice];
+    let new_owner = sim.new_key();
+    let offer = program_instruction(
+        accounts::TransferAgentOwnership { agent: keys.agent, user: keys.owner },
+        instruction::TransferAgentOwnership { new_owner },
+    );
+    let accept = program_instruction(
+        accounts::AcceptAgentOwnership { agent: keys.agent, new_owner },
+        instruction::AcceptAgentOwnership {},
+    );
+
+    // An offer made before the agent had an NFT can't be taken up after
+    sim.send(std::slice::from_ref(&offer), &[keys.owner]).unwrap();
+    let nft_mint = sim.new_key();
+    bind_nft(&mut sim, alice, nft_mint);
+    let failure = sim.send(&[accept], &[new_owner]).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::AgentNftExists.into()));
+    assert_eq!(sim.agent(alice).owner, keys.owner);
+
+    // Nor can a new one be made
+    let failure = sim.send(&[offer], &[keys.owner]).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::AgentNftExists.into()));
+}
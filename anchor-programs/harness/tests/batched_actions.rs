@@ -0,0 +1,35 @@
+//! Compact actions submitted in a batch by a coordinator for many agents.
+use anchor_programs::{accounts, instruction};
+use neuraltrader_harness::sim::{encode_action, program_instruction};
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+
+#[test]
+fn a_batch_creates_trades_whose_address_already_holds_lamports() {
+    let mut sim = Simulation::new();
+    let alice = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    let bob = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    let carol = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    let set = program_instruction(
+        accounts::SetMatchingPolicy { market: sim.keys.market, authority: sim.admin },
+        instruction::SetActionEncoding { action_lot: QUANTITY, action_tick: PRICE / 100 },
+    );
+    let admin = sim.admin;
+    sim.send(&[set], &[admin]).unwrap();
+    sim.place_order(alice, Side::Bid, PRICE, QUANTITY).unwrap();
+    sim.place_order(bob, Side::Ask, PRICE, QUANTITY).unwrap();
+    sim.match_orders(alice, bob).unwrap();
+
+    // A lamport sent to Carol's trade address ahead of time doesn't stop
+    // the batch creating it
+    let trade = sim.agents[carol].trade;
+    sim.runtime.airdrop(trade, 1);
+    let owner = sim.agents[carol].owner;
+    sim.submit_actions_batch(owner, &[(carol, encode_action(Side::Bid, 0, -1, 0))]).unwrap();
+    let order = sim.order(carol).unwrap();
+    assert_eq!(order.size(), QUANTITY);
+    let account = sim.runtime.account(&trade).unwrap();
+    assert_eq!(account.lamports, sim.runtime.rent().minimum_balance(account.data.len()));
+}
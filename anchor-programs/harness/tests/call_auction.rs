@@ -0,0 +1,158 @@
+//! Call auctions, at the open and on reopening after a circuit-breaker
+//! halt, uncrossing over as many cranks as their book needs, with orders
+//! their agents can no longer pay for capped instead of holding the market
+//! in the auction.
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::SESSION_OPENING_AUCTION;
+use neuraltrader_harness::sim::UNCROSS_PAGE;
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+const OPEN: u64 = 100;
+const AUCTION_SLOTS: u64 = 50;
+
+/// A market whose sessions open `OPEN` slots into each epoch with a call
+/// auction, part way through the first one.
+fn auction_market() -> Simulation {
+    let mut sim = Simulation::new();
+    sim.set_call_auctions(OPEN, 8_000, AUCTION_SLOTS).unwrap();
+    sim.runtime.warp_to_slot(OPEN + 10);
+    sim
+}
+
+fn code(error: ErrorCode) -> Option<u32> {
+    Some(u32::from(error))
+}
+
+#[test]
+fn indication_is_where_the_book_would_clear() {
+    let mut sim = auction_market();
+    let bid = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    let high_bid = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    let ask = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    sim.place_order(bid, Side::Bid, PRICE, QUANTITY).unwrap();
+    sim.place_order(high_bid, Side::Bid, 2 * PRICE, QUANTITY).unwrap();
+    sim.place_order(ask, Side::Ask, PRICE, 3 * QUANTITY).unwrap();
+
+    let indication = sim.indicate_auction().unwrap();
+    assert_eq!(indication.phase, SESSION_OPENING_AUCTION);
+    assert_eq!((indication.price, indication.volume), (PRICE, 2 * QUANTITY));
+    assert_eq!(indication.imbalance, -(QUANTITY as i64));
+
+    // Nothing matches continuously until the auction has uncrossed
+    let failure = sim.match_orders(high_bid, ask).unwrap_err();
+    assert_eq!(failure.code(), code(ErrorCode::AuctionInProgress));
+    let failure = sim.uncross_auction().unwrap_err();
+    assert_eq!(failure.code(), code(ErrorCode::AuctionNotDue));
+}
+
+#[test]
+fn uncross_pages_through_the_book_and_caps_what_agents_cant_pay() {
+    let mut sim = auction_market();
+    let bids: Vec<usize> = (0..2 * UNCROSS_PAGE).map(|_| sim.add_agent(10 * QUANTITY, 0).unwrap()).collect();
+    let asks: Vec<usize> = (0..2 * UNCROSS_PAGE).map(|_| sim.add_agent(0, 10 * QUANTITY).unwrap()).collect();
+    for &bid in &bids {
+        sim.place_order(bid, Side::Bid, PRICE, QUANTITY).unwrap();
+    }
+    for &ask in &asks {
+        sim.place_order(ask, Side::Ask, PRICE, QUANTITY).unwrap();
+    }
+    // One bidder takes its token A out and one asker half its token B
+    let (broke, short) = (bids[0], asks[0]);
+    sim.withdraw(broke, false, 10 * QUANTITY).unwrap();
+    sim.withdraw(short, true, 10 * QUANTITY - QUANTITY / 2).unwrap();
+
+    sim.runtime.warp_to_slot(OPEN + AUCTION_SLOTS + 1);
+    let before_b = sim.balances(broke).1;
+    let crank = sim.uncross_auction_instruction();
+    let admin = sim.admin;
+    sim.send(&[crank], &[admin]).unwrap();
+    assert_ne!(sim.market().uncross.end, 0);
+    // The book is frozen until the uncross is done
+    let failure = sim.cancel_order(bids[1]).unwrap_err();
+    assert_eq!(failure.code(), code(ErrorCode::AuctionUncrossing));
+    let failure = sim.place_order_at(bids[1], 1, Side::Bid, PRICE, QUANTITY).unwrap_err();
+    assert_eq!(failure.code(), code(ErrorCode::AuctionUncrossing));
+
+    sim.uncross_auction().unwrap();
+    let market = sim.market();
+    assert_eq!(market.uncross.end, 0);
+    assert_eq!(market.last_uncross, OPEN + AUCTION_SLOTS);
+    assert_eq!(market.last_price, PRICE);
+
+    // Every funded bid filled in full; the asks shared the volume pro rata
+    assert_eq!(sim.balances(broke).1, before_b);
+    assert_eq!(sim.order(broke).unwrap().size(), QUANTITY);
+    for &bid in &bids[1..] {
+        assert_eq!(sim.order(bid).unwrap().size(), 0);
+        assert_eq!(sim.balances(bid).1, QUANTITY);
+    }
+    let sold: u64 = asks.iter().map(|&ask| QUANTITY - sim.order(ask).unwrap().size()).sum();
+    assert_eq!(sold, (bids.len() as u64 - 1) * QUANTITY);
+    assert!(QUANTITY - sim.order(short).unwrap().size() <= QUANTITY / 2);
+    for agent in bids.iter().chain(&asks) {
+        let keys = sim.agents[*agent];
+        for balance in [keys.balance_a, keys.balance_b] {
+            let reserved = sim.runtime.get::<anchor_programs::Balance>(&balance).map_or(0, |balance| balance.reserved);
+            assert_eq!(reserved, 0);
+        }
+    }
+
+    // Continuous trading is open once the auction is done
+    sim.cancel_order(broke).unwrap();
+    let resting_ask = asks[1..].iter().copied().find(|&ask| sim.order(ask).unwrap().size() > 0).unwrap();
+    sim.place_order_at(bids[1], 1, Side::Bid, PRICE, QUANTITY / 4).unwrap();
+    sim.match_orders_at((bids[1], 1), (resting_ask, 0)).unwrap();
+}
+
+#[test]
+fn a_tripped_breaker_reopens_through_a_paged_uncross() {
+    let mut sim = Simulation::new();
+    sim.set_circuit_breaker(1_000, 20, 10).unwrap();
+    let maker = sim.add_agent(100 * QUANTITY, 100 * QUANTITY).unwrap();
+    let taker = sim.add_agent(100 * QUANTITY, 100 * QUANTITY).unwrap();
+    sim.place_order(maker, Side::Bid, PRICE, QUANTITY).unwrap();
+    sim.place_order(taker, Side::Ask, PRICE, QUANTITY).unwrap();
+    sim.match_orders(maker, taker).unwrap();
+
+    // A fill at twice the last price halts the market
+    sim.place_order_at(maker, 1, Side::Bid, 2 * PRICE, QUANTITY).unwrap();
+    sim.place_order_at(taker, 1, Side::Ask, 2 * PRICE, QUANTITY).unwrap();
+    sim.match_orders_at((maker, 1), (taker, 1)).unwrap();
+    let halted_until = sim.market().halted_until;
+    assert_ne!(halted_until, 0);
+    let failure = sim.place_order_at(maker, 2, Side::Bid, PRICE, QUANTITY).unwrap_err();
+    assert_eq!(failure.code(), code(ErrorCode::MarketHalted));
+
+    // Orders gather for the reopening auction, one of them unfunded
+    sim.runtime.warp_to_slot(halted_until);
+    let bids: Vec<usize> = (0..UNCROSS_PAGE).map(|_| sim.add_agent(10 * QUANTITY, 0).unwrap()).collect();
+    let asks: Vec<usize> = (0..UNCROSS_PAGE).map(|_| sim.add_agent(0, 10 * QUANTITY).unwrap()).collect();
+    for &bid in &bids {
+        sim.place_order(bid, Side::Bid, 3 * PRICE / 2, QUANTITY).unwrap();
+    }
+    for &ask in &asks {
+        sim.place_order(ask, Side::Ask, 3 * PRICE / 2, QUANTITY).unwrap();
+    }
+    sim.withdraw(asks[0], true, 10 * QUANTITY).unwrap();
+    let failure = sim.match_orders(bids[0], asks[1]).unwrap_err();
+    assert_eq!(failure.code(), code(ErrorCode::AuctionInProgress));
+    let failure = sim.uncross_auction().unwrap_err();
+    assert_eq!(failure.code(), code(ErrorCode::AuctionNotDue));
+
+    sim.runtime.warp_to_slot(halted_until + 10);
+    sim.uncross_auction().unwrap();
+    let market = sim.market();
+    assert_eq!(market.last_uncross, halted_until + 10);
+    assert_eq!(market.last_price, 3 * PRICE / 2);
+    // The unfunded ask sat the auction out and one bid went without
+    assert_eq!(sim.order(asks[0]).unwrap().size(), QUANTITY);
+    let unfilled: u64 = bids.iter().map(|&bid| sim.order(bid).unwrap().size()).sum();
+    assert_eq!(unfilled, QUANTITY);
+
+    // Continuous trading is back
+    let (bid, ask) = (*bids.iter().find(|&&bid| sim.order(bid).unwrap().size() > 0).unwrap(), asks[1]);
+    sim.place_order_at(ask, 1, Side::Ask, 3 * PRICE / 2, QUANTITY).unwrap();
+    sim.match_orders_at((bid, 0), (ask, 1)).unwrap();
+}
@@ -0,0 +1,46 @@
+//! Followers copying a leader's fills through `mirror_fill`, against the
+//! vault's own liquidity.
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::Subscription;
+use neuraltrader_harness::sim::subscription_address;
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+
+#[test]
+fn mirrors_trade_at_the_current_price_against_vault_liquidity() {
+    let mut sim = Simulation::new();
+    let leader = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    let maker = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    let follower = sim.add_agent(10_000_000, 0).unwrap();
+    let broke = sim.add_agent(0, 0).unwrap();
+    sim.set_performance_fee(leader, 1_000).unwrap();
+    sim.subscribe(follower, leader, 5_000).unwrap();
+    sim.subscribe(broke, leader, 5_000).unwrap();
+    // Raising the fee later leaves the subscription at the old one
+    sim.set_performance_fee(leader, 5_000).unwrap();
+
+    sim.place_order(leader, Side::Bid, 2 * PRICE, QUANTITY).unwrap();
+    sim.place_order(maker, Side::Ask, 2 * PRICE, QUANTITY).unwrap();
+    sim.match_orders(leader, maker).unwrap();
+    let error = sim.mirror_fill(follower, leader).unwrap_err();
+    assert_eq!(error.code(), Some(ErrorCode::VaultDeficit.into()));
+
+    sim.fund_mirror_liquidity(maker, true, QUANTITY).unwrap();
+    sim.open_reward_record(follower).unwrap();
+    sim.mirror_fill(follower, leader).unwrap();
+    // A buy the follower can't pay for fails rather than being skipped
+    let error = sim.mirror_fill(broke, leader).unwrap_err();
+    assert_eq!(error.code(), Some(ErrorCode::InsufficientBalance.into()));
+    let (token_a, token_b) = sim.balances(follower);
+    assert_eq!(token_b, QUANTITY / 2);
+    // Half the leader's 1 token B at the market price of 2 token A
+    assert_eq!(token_a, 10_000_000 - QUANTITY);
+    // The copy counts toward the follower's reward like any fill
+    let reward = sim.reward_record(follower, sim.epoch());
+    assert_eq!((reward.fills, reward.cash_a, reward.inventory_b), (1, -(QUANTITY as i64), QUANTITY as i64 / 2));
+    let address = subscription_address(&sim.keys.market, &sim.agents[follower].agent, &sim.agents[leader].agent);
+    let subscription: Subscription = sim.runtime.get(&address).unwrap();
+    assert_eq!(subscription.performance_fee_bps, 1_000);
+}
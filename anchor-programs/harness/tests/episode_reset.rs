@@ -0,0 +1,36 @@
+//! Agents put back at the start of an episode with none of their orders
+//! left resting.
+use anchor_programs::errors::ErrorCode;
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+
+#[test]
+fn a_reset_closes_every_trade_the_agent_has() {
+    let mut sim = Simulation::new();
+    sim.set_episode_capital(5 * QUANTITY, 5 * QUANTITY).unwrap();
+    let alice = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    sim.place_order(alice, Side::Bid, PRICE, QUANTITY).unwrap();
+    sim.place_order_at(alice, 1, Side::Ask, 2 * PRICE, QUANTITY).unwrap();
+    sim.place_order_at(alice, 2, Side::Ask, 3 * PRICE, QUANTITY).unwrap();
+    assert_eq!(sim.agent(alice).open_trades, 3);
+
+    // Leaving one out would keep it resting into the next episode
+    let mut partial = sim.reset_agent_instruction(alice);
+    partial.accounts.pop();
+    let admin = sim.admin;
+    let failure = sim.send(&[partial], &[admin]).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::IncompleteTrades.into()));
+
+    sim.reset_agent(alice).unwrap();
+    assert!(sim.trades(alice).is_empty());
+    assert_eq!(sim.agent(alice).open_trades, 0);
+    assert_eq!(sim.market().open_orders, 0);
+    assert_eq!(sim.balances(alice), (5 * QUANTITY, 5 * QUANTITY));
+
+    // and the agent starts the next one able to trade again
+    sim.place_order(alice, Side::Bid, PRICE, QUANTITY).unwrap();
+    sim.cancel_order(alice).unwrap();
+    assert_eq!(sim.agent(alice).open_trades, 0);
+}
@@ -0,0 +1,223 @@
+//! Flash swaps against a stand-in callback program that replays whatever
+//! instructions it's handed. Unlike a real cluster, the harness lets a callee
+//! call back into the program that invoked it, so these tests can show the
+//! market's `in_flight` guard turning away a callback that tries to act on the
+//! market before the swap has settled.
+#![allow(deprecated)]
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_program;
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::{accounts, instruction};
+use neuraltrader_harness::sim::{config_address, global_stats_address, program_instruction, AgentKeys};
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+
+/// Callback data: each instruction as its program id, account count, each
+/// account's key, signer and writable flags, then data length and data.
+fn encode(instructions: &[Instruction]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for instruction in instructions {
+        data.extend_from_slice(instruction.program_id.as_ref());
+        data.push(instruction.accounts.len() as u8);
+        for meta in &instruction.accounts {
+            data.extend_from_slice(meta.pubkey.as_ref());
+            data.extend([meta.is_signer as u8, meta.is_writable as u8]);
+        }
+        data.extend_from_slice(&(instruction.data.len() as u16).to_le_bytes());
+        data.extend_from_slice(&instruction.data);
+    }
+    data
+}
+
+fn decode(mut data: &[u8]) -> Option<Vec<Instruction>> {
+    fn take<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        let (head, tail) = data.split_at_checked(len)?;
+        *data = tail;
+        Some(head)
+    }
+    let mut instructions = Vec::new();
+    while !data.is_empty() {
+        let program_id = Pubkey::try_from(take(&mut data, 32)?).ok()?;
+        let count = take(&mut data, 1)?[0];
+        let mut accounts = Vec::new();
+        for _ in 0..count {
+            let pubkey = Pubkey::try_from(take(&mut data, 32)?).ok()?;
+            let flags = take(&mut data, 2)?;
+            accounts.push(AccountMeta { pubkey, is_signer: flags[0] != 0, is_writable: flags[1] != 0 });
+        }
+        let len = u16::from_le_bytes(take(&mut data, 2)?.try_into().ok()?) as usize;
+        let data = take(&mut data, len)?.to_vec();
+        instructions.push(Instruction { program_id, accounts, data });
+    }
+    Some(instructions)
+}
+
+/// The callback program: invokes each encoded instruction in turn.
+fn replay<'a>(_program_id: &Pubkey, accounts: &'a [AccountInfo<'a>], data: &[u8]) -> ProgramResult {
+    for instruction in decode(data).ok_or(ProgramError::InvalidInstructionData)? {
+        invoke(&instruction, accounts)?;
+    }
+    Ok(())
+}
+
+/// The callback's remaining accounts: every account `instructions` use, with
+/// the widest privileges any of them needs.
+fn remaining_accounts(instructions: &[Instruction]) -> Vec<AccountMeta> {
+    let mut metas: Vec<AccountMeta> = Vec::new();
+    for meta in instructions.iter().flat_map(|instruction| &instruction.accounts) {
+        match metas.iter_mut().find(|known| known.pubkey == meta.pubkey) {
+            Some(known) => {
+                known.is_signer |= meta.is_signer;
+                known.is_writable |= meta.is_writable;
+            }
+            None => metas.push(meta.clone()),
+        }
+    }
+    metas
+}
+
+/// A market whose first agent rests a bid for `QUANTITY` token B and holds
+/// enough token A in its wallet to repay a flash swap of it.
+fn setup() -> (Simulation, Pubkey) {
+    let mut sim = Simulation::new();
+    let callback_program = sim.new_key();
+    sim.runtime.add_program(callback_program, replay);
+    let agent = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    sim.place_order(agent, Side::Bid, PRICE, QUANTITY).unwrap();
+    let keys = sim.keys;
+    let wallet = sim.agents[agent].token_a_account;
+    sim.mint_to(wallet, keys.token_a_mint, sim.agents[agent].owner, 10 * QUANTITY);
+    (sim, callback_program)
+}
+
+/// The user's repayment of the bid's token A, fee included.
+fn repayment(sim: &Simulation, keys: &AgentKeys) -> Instruction {
+    let trade = sim.order(0).unwrap();
+    let amount = trade.amount_in + sim.market().taker_fee(trade.amount_in).unwrap();
+    spl_token::instruction::transfer(
+        &spl_token::ID,
+        &keys.token_a_account,
+        &sim.keys.vault_a_token_account,
+        &keys.owner,
+        &[],
+        amount,
+    )
+    .unwrap()
+}
+
+/// `execute_trade` of the agent's own resting bid.
+fn execute(sim: &Simulation, keys: &AgentKeys) -> Instruction {
+    program_instruction(
+        accounts::ExecuteTrade {
+            trade: keys.trade,
+            market: sim.keys.market,
+            config: config_address(),
+            agent: keys.agent,
+            user: keys.owner,
+            token_in_mint: sim.keys.token_a_mint,
+            token_out_mint: sim.keys.token_b_mint,
+            user_token_account_in: keys.token_a_account,
+            user_token_account_out: keys.token_b_account,
+            vault_in: sim.keys.vault_a,
+            vault_token_account_in: sim.keys.vault_a_token_account,
+            vault_out: sim.keys.vault_b,
+            vault_token_account_out: sim.keys.vault_b_token_account,
+            settlement: None,
+            reward: None,
+            oracle: None,
+            fallback_oracle: None,
+            instructions: None,
+            global_stats: global_stats_address(),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteTrade {},
+    )
+}
+
+fn flash_swap(sim: &Simulation, keys: &AgentKeys, callback_program: Pubkey, callback: &[Instruction]) -> Instruction {
+    let mut swap = program_instruction(
+        accounts::FlashSwap {
+            trade: keys.trade,
+            market: sim.keys.market,
+            config: config_address(),
+            agent: keys.agent,
+            user: keys.owner,
+            token_in_mint: sim.keys.token_a_mint,
+            token_out_mint: sim.keys.token_b_mint,
+            user_token_account_out: keys.token_b_account,
+            vault_in: sim.keys.vault_a,
+            vault_token_account_in: sim.keys.vault_a_token_account,
+            vault_out: sim.keys.vault_b,
+            vault_token_account_out: sim.keys.vault_b_token_account,
+            callback_program,
+            global_stats: global_stats_address(),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        },
+        instruction::FlashSwap { callback_data: encode(callback) },
+    );
+    swap.accounts.extend(remaining_accounts(callback));
+    swap
+}
+
+#[test]
+fn repaid_swap_fills_the_order_and_clears_the_guard() {
+    let (mut sim, callback_program) = setup();
+    let keys = sim.agents[0];
+    let reserves_b = sim.runtime.get::<anchor_programs::Vault>(&sim.keys.vault_b).unwrap().reserves;
+
+    let swap = flash_swap(&sim, &keys, callback_program, &[repayment(&sim, &keys)]);
+    if let Err(failure) = sim.send(&[swap], &[keys.owner]) {
+        panic!("{:?}\n{:#?}", failure.error, failure.logs);
+    }
+
+    assert!(sim.order(0).is_none());
+    assert!(!sim.market().in_flight);
+    let vault_b = sim.runtime.get::<anchor_programs::Vault>(&sim.keys.vault_b).unwrap();
+    assert_eq!(vault_b.reserves, reserves_b - QUANTITY);
+    let stats = sim.runtime.get::<anchor_programs::GlobalStats>(&global_stats_address()).unwrap();
+    assert_eq!((stats.markets, stats.agents, stats.trades), (1, 1, 1));
+    assert_eq!(stats.volume, QUANTITY as u128);
+}
+
+#[test]
+fn callback_cannot_fill_the_order_again() {
+    let (mut sim, callback_program) = setup();
+    let keys = sim.agents[0];
+
+    // Without the guard the re-entered execute_trade would find the bid still
+    // open and pay its token B out a second time
+    let callback = [repayment(&sim, &keys), execute(&sim, &keys)];
+    let swap = flash_swap(&sim, &keys, callback_program, &callback);
+    let failure = match sim.send(&[swap], &[keys.owner]) {
+        Ok(_) => panic!("re-entrant fill succeeded"),
+        Err(failure) => failure,
+    };
+    assert_eq!(failure.code(), Some(u32::from(ErrorCode::MarketInFlight)), "{:#?}", failure.logs);
+    assert!(sim.order(0).is_some());
+    assert!(!sim.market().in_flight);
+}
+
+#[test]
+fn callback_cannot_touch_the_market_at_all() {
+    let (mut sim, callback_program) = setup();
+    let keys = sim.agents[0];
+
+    let callback = [repayment(&sim, &keys), sim.cancel_order_instruction(0)];
+    let swap = flash_swap(&sim, &keys, callback_program, &callback);
+    let failure = match sim.send(&[swap], &[keys.owner]) {
+        Ok(_) => panic!("re-entrant cancel succeeded"),
+        Err(failure) => failure,
+    };
+    assert_eq!(failure.code(), Some(u32::from(ErrorCode::MarketInFlight)), "{:#?}", failure.logs);
+}
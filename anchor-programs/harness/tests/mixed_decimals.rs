@@ -0,0 +1,84 @@
+//! Prices are quoted per whole token, so on a market whose tokens have
+//! different decimals every token A amount taken from a price has to be
+//! scaled between them.
+use anchor_programs::{Market, RewardRecord, PRICE_SCALE};
+use neuraltrader_harness::sim::encode_action;
+use neuraltrader_harness::{Side, Simulation};
+
+/// Token A with 6 decimals, token B with 9.
+const ONE_A: u64 = 1_000_000;
+const ONE_B: u64 = 1_000_000_000;
+
+/// The simulation's market as if token A had 6 decimals and token B 9.
+fn mixed_market() -> Market {
+    let mut market = Simulation::new().market();
+    market.decimals_a = 6;
+    market.decimals_b = 9;
+    market
+}
+
+#[test]
+fn compact_actions_quote_token_a_in_its_own_decimals() {
+    let mut market = mixed_market();
+    market.last_price = 2 * PRICE_SCALE;
+    market.action_lot = ONE_B;
+    market.action_tick = PRICE_SCALE / 100;
+
+    // One whole token B at 2.00 costs two whole token A
+    let bid = market.decode_action(encode_action(Side::Bid, 0, 0, 0)).unwrap();
+    assert_eq!((bid.amount_in, bid.amount_out), (2 * ONE_A, ONE_B));
+
+    // Two at 2.01 fetch 4.02
+    let ask = market.decode_action(encode_action(Side::Ask, 1, 1, 0)).unwrap();
+    assert_eq!((ask.amount_in, ask.amount_out), (2 * ONE_B, 4_020_000));
+}
+
+/// A record of an agent that bought `inventory` token B for `cost` token A
+/// over the whole of epoch 0.
+fn bought(inventory: i64, cost: i64) -> RewardRecord {
+    RewardRecord {
+        market: Default::default(),
+        agent: Default::default(),
+        epoch: 0,
+        cash_a: -cost,
+        inventory_b: inventory,
+        fees: 0,
+        fills: 1,
+        inventory_at: 0,
+        inventory_time: 0,
+        settled: false,
+        price: 0,
+        average_inventory: 0,
+        pnl: 0,
+        inventory_penalty: 0,
+        reward: 0,
+        bump: 0,
+    }
+}
+
+#[test]
+fn rewards_mark_inventory_in_token_a_decimals() {
+    let mut market = mixed_market();
+    market.last_price = 3 * PRICE_SCALE;
+    market.epoch_slots = 100;
+
+    // Two whole token B bought at 2.00 and marked at 3.00 made 2 token A
+    let mut reward = bought(2 * ONE_B as i64, 4 * ONE_A as i64);
+    reward.settle(&market).unwrap();
+    assert_eq!(reward.pnl, 2 * ONE_A as i64);
+}
+
+#[test]
+fn inventory_penalty_is_charged_in_token_a_decimals() {
+    let mut market = mixed_market();
+    market.last_price = 2 * PRICE_SCALE;
+    market.epoch_slots = 100;
+    market.inventory_penalty_bps = 100;
+
+    // One whole token B held all epoch is worth 2 token A, 1% of it 0.02
+    let mut reward = bought(ONE_B as i64, 2 * ONE_A as i64);
+    reward.settle(&market).unwrap();
+    assert_eq!(reward.average_inventory, ONE_B);
+    assert_eq!(reward.inventory_penalty, 20_000);
+    assert_eq!(reward.reward, -20_000);
+}
@@ -0,0 +1,39 @@
+//! An agent resting more than one order on a market, each under its own
+//! order id.
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+
+#[test]
+fn orders_rest_and_fill_under_their_own_ids() {
+    let mut sim = Simulation::new();
+    let alice = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    let bob = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    sim.place_order_at(alice, 0, Side::Bid, 2 * PRICE, QUANTITY).unwrap();
+    sim.place_order_at(alice, 1, Side::Bid, PRICE, QUANTITY).unwrap();
+    sim.place_order_at(alice, 7, Side::Ask, 4 * PRICE, QUANTITY).unwrap();
+    sim.place_order(bob, Side::Ask, PRICE, 2 * QUANTITY).unwrap();
+    assert_eq!(sim.market().open_orders, 4);
+    assert_eq!(sim.order_at(alice, 7).unwrap().order_id, 7);
+
+    // A taken id stays taken until its order is closed
+    assert!(sim.place_order_at(alice, 1, Side::Bid, PRICE, QUANTITY).is_err());
+
+    sim.match_orders_at((alice, 0), (bob, 0)).unwrap();
+    sim.match_orders_at((alice, 1), (bob, 0)).unwrap();
+    assert_eq!(sim.order_at(alice, 0).unwrap().size(), 0);
+    assert_eq!(sim.order_at(alice, 1).unwrap().size(), 0);
+    assert_eq!(sim.order_at(alice, 7).unwrap().size(), QUANTITY);
+    // Bob's ask filled in full too
+    assert_eq!(sim.market().open_orders, 1);
+    let (_, token_b) = sim.balances(alice);
+    assert_eq!(token_b, 1_000_000_000 + 2 * QUANTITY);
+
+    for order_id in [0, 1, 7] {
+        sim.cancel_order_at(alice, order_id).unwrap();
+        assert!(sim.order_at(alice, order_id).is_none());
+    }
+    assert_eq!(sim.market().open_orders, 0);
+    sim.place_order_at(alice, 1, Side::Bid, PRICE, QUANTITY).unwrap();
+}
@@ -0,0 +1,86 @@
+//! The observation frame read off the book a page at a time, with its depth
+//! priced per whole token like the market's last price.
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::AccountSerialize;
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::{accounts, instruction, PRICE_SCALE};
+use neuraltrader_harness::sim::{observation_frame_address, program_instruction, OBSERVATION_PAGE};
+use neuraltrader_harness::{Side, Simulation};
+
+/// Token A with 6 decimals, token B with 9.
+const ONE_A: u64 = 1_000_000;
+const ONE_B: u64 = 1_000_000_000;
+
+/// A book price for `price` whole token A per whole token B.
+fn raw(price: u64) -> u64 {
+    price * ONE_A / ONE_B
+}
+
+/// A simulation whose market prices token B as if it had 9 decimals to
+/// token A's 6, with the observation frame open.
+fn mixed_market() -> Simulation {
+    let mut sim = Simulation::new();
+    let key = sim.keys.market;
+    let mut market = sim.market();
+    market.decimals_b = 9;
+    let mut account = sim.runtime.account(&key).unwrap().clone();
+    account.data.clear();
+    market.try_serialize(&mut account.data).unwrap();
+    sim.runtime.set_account(key, account);
+    sim.open_observation_frame(10).unwrap();
+    sim
+}
+
+#[test]
+fn depth_is_priced_like_the_last_fill_across_pages() {
+    let mut sim = mixed_market();
+    let alice = sim.add_agent(100 * ONE_A, 10 * ONE_B).unwrap();
+    let bob = sim.add_agent(100 * ONE_A, 10 * ONE_B).unwrap();
+    sim.place_order(alice, Side::Bid, raw(2 * PRICE_SCALE), ONE_B).unwrap();
+    sim.place_order(bob, Side::Ask, raw(2 * PRICE_SCALE), ONE_B).unwrap();
+    sim.match_orders(alice, bob).unwrap();
+    assert_eq!(sim.market().last_price, 2 * PRICE_SCALE);
+
+    // More orders than fit a page, bids from 1.00 down and asks from 3.00 up
+    let bids: Vec<usize> = (0..OBSERVATION_PAGE).map(|_| sim.add_agent(100 * ONE_A, 0).unwrap()).collect();
+    let asks: Vec<usize> = (0..OBSERVATION_PAGE).map(|_| sim.add_agent(0, 10 * ONE_B).unwrap()).collect();
+    for (level, &bid) in bids.iter().enumerate() {
+        let price = PRICE_SCALE - level as u64 / 2 * PRICE_SCALE / 10;
+        sim.place_order(bid, Side::Bid, raw(price), ONE_B).unwrap();
+    }
+    for (level, &ask) in asks.iter().enumerate() {
+        let price = 3 * PRICE_SCALE + level as u64 * PRICE_SCALE / 10;
+        sim.place_order(ask, Side::Ask, raw(price), ONE_B).unwrap();
+    }
+
+    // A page read before the book changes is thrown away
+    let frame_key = observation_frame_address(&sim.keys.market);
+    let mut first_page: Vec<_> = bids.iter().chain(&asks).map(|&agent| sim.agents[agent].trade).collect();
+    first_page.sort_unstable();
+    let mut update = program_instruction(
+        accounts::UpdateObservationFrame { market: sim.keys.market, observation_frame: frame_key },
+        instruction::UpdateObservationFrame {},
+    );
+    update.accounts.extend(first_page[..OBSERVATION_PAGE].iter().map(|&order| AccountMeta::new_readonly(order, false)));
+    let admin = sim.admin;
+    sim.send(&[update], &[admin]).unwrap();
+    assert_eq!(sim.observation_frame().building, 1);
+    assert_eq!(sim.observation_frame().sequence, 0);
+    sim.cancel_order(asks[0]).unwrap();
+
+    sim.update_observation_frame().unwrap();
+    let frame = sim.observation_frame();
+    assert_eq!((frame.building, frame.sequence), (0, 1));
+    assert_eq!(frame.last_price, 2 * PRICE_SCALE);
+    let tenth = PRICE_SCALE / 10;
+    assert_eq!(frame.bid_prices, [PRICE_SCALE, PRICE_SCALE - tenth, PRICE_SCALE - 2 * tenth, PRICE_SCALE - 3 * tenth, 0]);
+    assert_eq!(frame.bid_sizes, [2 * ONE_B, 2 * ONE_B, 2 * ONE_B, 2 * ONE_B, 0]);
+    let ask = |level: u64| 3 * PRICE_SCALE + level * tenth;
+    assert_eq!(frame.ask_prices, [ask(1), ask(2), ask(3), ask(4), ask(5)]);
+    assert_eq!(frame.ask_sizes, [ONE_B; 5]);
+    assert_eq!((frame.mid, frame.spread), ((PRICE_SCALE + ask(1)) / 2, ask(1) - PRICE_SCALE));
+
+    // And not again until the interval is up
+    let failure = sim.update_observation_frame().unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::ObservationNotDue.into()));
+}
@@ -0,0 +1,155 @@
+//! Covered options written against an agent's balance, bought for their
+//! premium, and cash-settled at the market's price or left to expire.
+#![allow(deprecated)]
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_program;
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::{accounts, instruction, OPTION_CALL, OPTION_PUT};
+use neuraltrader_harness::runtime::{Failure, Receipt};
+use neuraltrader_harness::sim::program_instruction;
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+const PREMIUM: u64 = 10_000;
+
+fn option_address(sim: &Simulation, writer: usize, option_id: u64) -> Pubkey {
+    let seeds: &[&[u8]] = &[
+        b"option",
+        sim.keys.market.as_ref(),
+        sim.agents[writer].agent.as_ref(),
+        &option_id.to_le_bytes(),
+    ];
+    Pubkey::find_program_address(seeds, &anchor_programs::ID).0
+}
+
+/// The vault and writer's balance an option of `kind` locks its collateral in.
+fn collateral(sim: &Simulation, writer: usize, kind: u8) -> (Pubkey, Pubkey) {
+    let keys = sim.agents[writer];
+    if kind == OPTION_CALL {
+        (sim.keys.vault_b, keys.balance_b)
+    } else {
+        (sim.keys.vault_a, keys.balance_a)
+    }
+}
+
+fn write(sim: &mut Simulation, writer: usize, option_id: u64, kind: u8, strike_amount: u64) -> Result<Receipt, Failure> {
+    let (collateral_vault, collateral_balance) = collateral(sim, writer, kind);
+    let keys = sim.agents[writer];
+    let expiry_slot = sim.runtime.clock().slot + 100;
+    let write = program_instruction(
+        accounts::WriteOption {
+            agent: keys.agent,
+            market: sim.keys.market,
+            option: option_address(sim, writer, option_id),
+            collateral_vault,
+            collateral_balance,
+            user: keys.owner,
+            system_program: system_program::ID,
+        },
+        instruction::WriteOption { option_id, kind, size: QUANTITY, strike_amount, premium: PREMIUM, expiry_slot },
+    );
+    sim.send(&[write], &[keys.owner])
+}
+
+fn buy(sim: &mut Simulation, buyer: usize, writer: usize, option_id: u64) -> Result<Receipt, Failure> {
+    let keys = sim.agents[buyer];
+    let buy = program_instruction(
+        accounts::BuyOption {
+            buyer_agent: keys.agent,
+            writer_agent: sim.agents[writer].agent,
+            market: sim.keys.market,
+            option: option_address(sim, writer, option_id),
+            vault_a: sim.keys.vault_a,
+            buyer_balance_a: keys.balance_a,
+            writer_balance_a: sim.agents[writer].balance_a,
+            user: keys.owner,
+            system_program: system_program::ID,
+        },
+        instruction::BuyOption {},
+    );
+    sim.send(&[buy], &[keys.owner])
+}
+
+fn exercise(sim: &mut Simulation, holder: usize, writer: usize, option_id: u64, kind: u8) -> Result<Receipt, Failure> {
+    let (collateral_vault, writer_balance) = collateral(sim, writer, kind);
+    let holder_balance = collateral(sim, holder, kind).1;
+    let keys = sim.agents[holder];
+    let exercise = program_instruction(
+        accounts::ExerciseOption {
+            holder_agent: keys.agent,
+            writer_agent: sim.agents[writer].agent,
+            market: sim.keys.market,
+            option: option_address(sim, writer, option_id),
+            collateral_vault,
+            holder_balance,
+            writer_balance,
+            oracle: None,
+            fallback_oracle: None,
+            writer_owner: sim.agents[writer].owner,
+            user: keys.owner,
+            system_program: system_program::ID,
+        },
+        instruction::ExerciseOption {},
+    );
+    sim.send(&[exercise], &[keys.owner])
+}
+
+fn expire(sim: &mut Simulation, writer: usize, option_id: u64, kind: u8) -> Result<Receipt, Failure> {
+    let (collateral_vault, collateral_balance) = collateral(sim, writer, kind);
+    let keys = sim.agents[writer];
+    let expire = program_instruction(
+        accounts::ExpireOption {
+            agent: keys.agent,
+            market: sim.keys.market,
+            option: option_address(sim, writer, option_id),
+            collateral_vault,
+            collateral_balance,
+            user: keys.owner,
+        },
+        instruction::ExpireOption {},
+    );
+    sim.send(&[expire], &[keys.owner])
+}
+
+#[test]
+fn options_settle_in_cash_at_the_market_price_or_expire() {
+    let mut sim = Simulation::new();
+    let writer = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    let holder = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    // A fill at 1.00 gives the market a price to settle at
+    sim.place_order(writer, Side::Bid, PRICE, QUANTITY).unwrap();
+    sim.place_order(holder, Side::Ask, PRICE, QUANTITY).unwrap();
+    sim.match_orders(writer, holder).unwrap();
+    let (writer_a, writer_b) = sim.balances(writer);
+    let (holder_a, holder_b) = sim.balances(holder);
+
+    // A call struck at 0.80 locks the writer's token B
+    write(&mut sim, writer, 0, OPTION_CALL, 4 * QUANTITY / 5).unwrap();
+    assert_eq!(sim.balances(writer).1, writer_b - QUANTITY);
+    buy(&mut sim, holder, writer, 0).unwrap();
+    let failure = buy(&mut sim, holder, writer, 0).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::OptionAlreadySold.into()));
+    // which pays the holder 0.20 of it in token B, the writer keeping the rest
+    exercise(&mut sim, holder, writer, 0, OPTION_CALL).unwrap();
+    assert_eq!(sim.balances(holder), (holder_a - PREMIUM, holder_b + QUANTITY / 5));
+    assert_eq!(sim.balances(writer), (writer_a + PREMIUM, writer_b - QUANTITY / 5));
+    assert!(sim.runtime.account(&option_address(&sim, writer, 0)).is_none());
+
+    // A put struck at 0.50 is out of the money at 1.00
+    let (writer_a, _) = sim.balances(writer);
+    write(&mut sim, writer, 1, OPTION_PUT, QUANTITY / 2).unwrap();
+    assert_eq!(sim.balances(writer).0, writer_a - QUANTITY / 2);
+    buy(&mut sim, holder, writer, 1).unwrap();
+    let failure = exercise(&mut sim, holder, writer, 1, OPTION_PUT).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::OptionOutOfTheMoney.into()));
+    // and its collateral only comes back once it has expired
+    let failure = expire(&mut sim, writer, 1, OPTION_PUT).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::OptionNotExpired.into()));
+    let expiry = sim.runtime.get::<anchor_programs::OptionContract>(&option_address(&sim, writer, 1)).unwrap().expiry_slot;
+    sim.runtime.warp_to_slot(expiry + 1);
+    let failure = exercise(&mut sim, holder, writer, 1, OPTION_PUT).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::OptionExpired.into()));
+    expire(&mut sim, writer, 1, OPTION_PUT).unwrap();
+    assert_eq!(sim.balances(writer).0, writer_a + PREMIUM);
+}
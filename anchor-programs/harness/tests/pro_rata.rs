@@ -0,0 +1,42 @@
+//! A pro-rata market sharing a taker among the makers at its price, which
+//! the cranker has to pass in full.
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::MATCHING_PRO_RATA;
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+
+#[test]
+fn makers_share_the_taker_by_displayed_size() {
+    let mut sim = Simulation::new();
+    sim.set_matching_policy(MATCHING_PRO_RATA).unwrap();
+    let alice = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    let bob = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    let carol = sim.add_agent(1_000_000_000, 1_000_000_000).unwrap();
+    sim.place_order(alice, Side::Bid, PRICE, 3 * QUANTITY).unwrap();
+    sim.place_order(bob, Side::Bid, PRICE, QUANTITY).unwrap();
+    sim.place_order(carol, Side::Ask, PRICE, 2 * QUANTITY).unwrap();
+
+    // Alice can't take the whole taker by leaving Bob out of her level
+    let admin = sim.admin;
+    let alone = sim.match_orders_instruction(alice, carol, admin);
+    assert_eq!(sim.send(&[alone], &[admin]).unwrap_err().code(), Some(ErrorCode::IncompleteLevel.into()));
+
+    let bob_trade = sim.agents[bob].trade;
+    let mut first = sim.match_orders_instruction(alice, carol, admin);
+    first.accounts.push(AccountMeta::new_readonly(bob_trade, false));
+    sim.send(&[first], &[admin]).unwrap();
+    assert_eq!(sim.order(alice).unwrap().size(), 3 * QUANTITY / 2);
+
+    // Nor fill twice in one round
+    let mut again = sim.match_orders_instruction(alice, carol, admin);
+    again.accounts.push(AccountMeta::new_readonly(bob_trade, false));
+    assert_eq!(sim.send(&[again], &[admin]).unwrap_err().code(), Some(ErrorCode::IncompleteLevel.into()));
+
+    sim.match_orders(bob, carol).unwrap();
+    assert_eq!(sim.order(bob).unwrap().size(), QUANTITY / 2);
+    assert_eq!(sim.order(carol).unwrap().size(), 0);
+    assert_eq!(sim.market().open_orders, 2);
+}
@@ -0,0 +1,87 @@
+//! Session keys trading an agent side by side, each within its own
+//! allowance: token A committed to bids, token B to asks.
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::{accounts, instruction, MAX_DELEGATES, SESSION_SCOPE_PLACE};
+use neuraltrader_harness::runtime::{Failure, Receipt};
+use neuraltrader_harness::sim::program_instruction;
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+const LAMPORTS: u64 = 10_000_000_000;
+
+/// A session key with the lamports to pay for the trades it opens.
+fn hot_key(sim: &mut Simulation) -> Pubkey {
+    let key = sim.new_key();
+    sim.runtime.airdrop(key, LAMPORTS);
+    key
+}
+
+fn start_session(sim: &mut Simulation, agent: usize, session_key: Pubkey) -> Result<Receipt, Failure> {
+    let keys = sim.agents[agent];
+    let start = program_instruction(
+        accounts::StartSession { agent: keys.agent, user: keys.owner },
+        instruction::StartSession { session_key, expiry_slot: u64::MAX, scope: SESSION_SCOPE_PLACE },
+    );
+    sim.send(&[start], &[keys.owner])
+}
+
+/// Places the agent's order `order_id` signed by `signer` in place of its
+/// owner.
+fn place_as(
+    sim: &mut Simulation,
+    signer: Pubkey,
+    agent: usize,
+    order_id: u64,
+    side: Side,
+    price: u64,
+    quantity: u64,
+) -> Result<Receipt, Failure> {
+    let owner = sim.agents[agent].owner;
+    let mut place = sim.place_order_at_instruction(agent, order_id, side, price, quantity);
+    for meta in &mut place.accounts {
+        if meta.pubkey == owner {
+            meta.pubkey = signer;
+        }
+    }
+    sim.send(&[place], &[signer])
+}
+
+#[test]
+fn each_session_spends_its_own_allowance_in_the_token_it_commits() {
+    let mut sim = Simulation::new();
+    let alice = sim.add_agent(100 * QUANTITY, 100 * QUANTITY).unwrap();
+    let (bot, other_bot) = (hot_key(&mut sim), hot_key(&mut sim));
+    start_session(&mut sim, alice, bot).unwrap();
+    start_session(&mut sim, alice, other_bot).unwrap();
+    let keys = sim.agents[alice];
+    let limits = program_instruction(
+        accounts::SetSessionLimits { agent: keys.agent, user: keys.owner },
+        instruction::SetSessionLimits { session_key: bot, spend_limits: [QUANTITY, 2 * QUANTITY], epoch_caps: [0; 2] },
+    );
+    sim.send(&[limits], &[keys.owner]).unwrap();
+
+    // A bid commits token A
+    let failure = place_as(&mut sim, bot, alice, 0, Side::Bid, 2 * PRICE, QUANTITY).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::SessionSpendExceeded.into()));
+    place_as(&mut sim, bot, alice, 0, Side::Bid, PRICE, QUANTITY).unwrap();
+    // An ask commits token B, however little token A it asks for
+    place_as(&mut sim, bot, alice, 1, Side::Ask, PRICE / 10, 2 * QUANTITY).unwrap();
+    let failure = place_as(&mut sim, bot, alice, 2, Side::Ask, PRICE / 10, 1).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::SessionSpendExceeded.into()));
+    let spent = sim.agent(alice).delegates.iter().find(|delegate| delegate.key == bot).unwrap().spent;
+    assert_eq!(spent, [QUANTITY, 2 * QUANTITY]);
+
+    // The other session's allowance is its own
+    place_as(&mut sim, other_bot, alice, 2, Side::Bid, 10 * PRICE, QUANTITY).unwrap();
+
+    // Every slot taken by an unexpired session leaves no room for another
+    for _ in 2..MAX_DELEGATES {
+        let key = sim.new_key();
+        start_session(&mut sim, alice, key).unwrap();
+    }
+    let key = sim.new_key();
+    let failure = start_session(&mut sim, alice, key).unwrap_err();
+    assert_eq!(failure.code(), Some(ErrorCode::DelegatesFull.into()));
+}
@@ -0,0 +1,90 @@
+//! `execute_trade` filling an agent's own trade from the vault: at the market
+//! price rather than the trade's, and only out of the vault's own liquidity.
+#![allow(deprecated)]
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_program;
+use anchor_programs::errors::ErrorCode;
+use anchor_programs::{accounts, instruction};
+use neuraltrader_harness::sim::{config_address, global_stats_address, program_instruction};
+use neuraltrader_harness::{Side, Simulation};
+
+const PRICE: u64 = anchor_programs::PRICE_SCALE;
+const QUANTITY: u64 = 1_000_000;
+
+fn token_balance(sim: &Simulation, token_account: &Pubkey) -> u64 {
+    sim.runtime
+        .account(token_account)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+fn execute(sim: &Simulation, agent: usize) -> Instruction {
+    let keys = sim.agents[agent];
+    program_instruction(
+        accounts::ExecuteTrade {
+            trade: keys.trade,
+            market: sim.keys.market,
+            config: config_address(),
+            agent: keys.agent,
+            user: keys.owner,
+            token_in_mint: sim.keys.token_a_mint,
+            token_out_mint: sim.keys.token_b_mint,
+            user_token_account_in: keys.token_a_account,
+            user_token_account_out: keys.token_b_account,
+            vault_in: sim.keys.vault_a,
+            vault_token_account_in: sim.keys.vault_a_token_account,
+            vault_out: sim.keys.vault_b,
+            vault_token_account_out: sim.keys.vault_b_token_account,
+            settlement: None,
+            reward: None,
+            oracle: None,
+            fallback_oracle: None,
+            instructions: None,
+            global_stats: global_stats_address(),
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteTrade {},
+    )
+}
+
+/// A market that has traded at `PRICE`, and an agent bidding twice that for
+/// `QUANTITY` token B with the token A to pay for it in its wallet.
+fn setup() -> Simulation {
+    let mut sim = Simulation::new();
+    let buyer = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    let bid = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    let ask = sim.add_agent(10 * QUANTITY, 10 * QUANTITY).unwrap();
+    sim.place_order(bid, Side::Bid, PRICE, QUANTITY).unwrap();
+    sim.place_order(ask, Side::Ask, PRICE, QUANTITY).unwrap();
+    sim.match_orders(bid, ask).unwrap();
+    sim.place_order(buyer, Side::Bid, 2 * PRICE, QUANTITY).unwrap();
+    let keys = sim.agents[buyer];
+    sim.mint_to(keys.token_a_account, sim.keys.token_a_mint, keys.owner, 10 * QUANTITY);
+    sim
+}
+
+#[test]
+fn vault_fills_at_the_market_price_out_of_its_own_liquidity() {
+    let mut sim = setup();
+    let keys = sim.agents[0];
+
+    // Other agents' deposits back none of the vault's side
+    let failure = sim.send(&[execute(&sim, 0)], &[keys.owner]).unwrap_err();
+    assert_eq!(failure.code(), Some(u32::from(ErrorCode::VaultDeficit)), "{:#?}", failure.logs);
+
+    sim.fund_mirror_liquidity(1, true, QUANTITY).unwrap();
+    let wallet_a = token_balance(&sim, &keys.token_a_account);
+    if let Err(failure) = sim.send(&[execute(&sim, 0)], &[keys.owner]) {
+        panic!("{:?}\n{:#?}", failure.error, failure.logs);
+    }
+    // The bid's own price was twice the market's
+    assert_eq!(token_balance(&sim, &keys.token_a_account), wallet_a - QUANTITY);
+    assert_eq!(token_balance(&sim, &keys.token_b_account), QUANTITY);
+    let vault_a = sim.runtime.get::<anchor_programs::Vault>(&sim.keys.vault_a).unwrap();
+    let vault_b = sim.runtime.get::<anchor_programs::Vault>(&sim.keys.vault_b).unwrap();
+    assert_eq!((vault_a.mirror_liquidity, vault_b.mirror_liquidity), (QUANTITY, 0));
+}
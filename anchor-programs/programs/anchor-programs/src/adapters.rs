@@ -0,0 +1,276 @@
+//! Instruction builders for external venues. Swaps for the pools a mirror
+//! market can route to are encoded here: callers pass the venue's swap
+//! accounts in the venue's own order and the adapter checks the pool and
+//! signer positions and encodes an exact-input swap with a minimum output.
+//! OpenBook v2 instructions are encoded by the client and only vetted here
+//! before the agent PDA signs them. Metaplex token metadata instructions for
+//! agent identity NFTs are encoded here too.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+
+pub const MIRROR_NONE: u8 = 0;
+pub const MIRROR_WHIRLPOOL: u8 = 1;
+pub const MIRROR_RAYDIUM_CLMM: u8 = 2;
+
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey = pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+pub const OPENBOOK_V2_PROGRAM_ID: Pubkey = pubkey!("opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb");
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// OpenBook v2 instructions an agent PDA will sign, grouped by purpose
+pub const OPENBOOK_PLACE_INSTRUCTIONS: [[u8; 8]; 2] = [
+    [51, 194, 155, 175, 109, 130, 96, 106], // place_order
+    [3, 44, 71, 3, 26, 199, 203, 85],       // place_take_order
+];
+pub const OPENBOOK_CANCEL_INSTRUCTIONS: [[u8; 8]; 3] = [
+    [95, 129, 237, 240, 8, 49, 223, 132],    // cancel_order
+    [115, 178, 201, 8, 175, 183, 123, 119],  // cancel_order_by_client_order_id
+    [196, 83, 243, 171, 17, 100, 160, 143],  // cancel_all_orders
+];
+pub const OPENBOOK_ACCOUNT_INSTRUCTIONS: [[u8; 8]; 3] = [
+    [64, 64, 153, 255, 217, 71, 249, 133],  // create_open_orders_indexer
+    [204, 181, 175, 222, 40, 125, 188, 71], // create_open_orders_account
+    [238, 64, 163, 96, 75, 171, 16, 33],    // settle_funds
+];
+
+// Both venues name their instruction `swap`, so share the Anchor discriminator
+const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+
+// Whirlpool swap: token_program, token_authority, whirlpool, token_owner_account_a,
+// token_vault_a, token_owner_account_b, token_vault_b, tick_array_0..2, oracle
+const WHIRLPOOL_AUTHORITY_INDEX: usize = 1;
+const WHIRLPOOL_POOL_INDEX: usize = 2;
+const WHIRLPOOL_OWNER_ACCOUNT_A_INDEX: usize = 3;
+const WHIRLPOOL_ACCOUNT_COUNT: usize = 11;
+const WHIRLPOOL_MIN_SQRT_PRICE: u128 = 4_295_048_016;
+const WHIRLPOOL_MAX_SQRT_PRICE: u128 = 79_226_673_515_401_279_992_447_579_055;
+
+// Raydium CLMM swap: payer, amm_config, pool_state, input_token_account,
+// output_token_account, input_vault, output_vault, observation_state,
+// token_program, tick_array, then any further tick arrays
+const RAYDIUM_PAYER_INDEX: usize = 0;
+const RAYDIUM_POOL_INDEX: usize = 2;
+const RAYDIUM_ACCOUNT_COUNT: usize = 10;
+
+// Where each venue's pool keeps its Q64.64 sqrt price and first mint; the
+// price is of the second mint in units of the first
+const WHIRLPOOL_SQRT_PRICE_OFFSET: usize = 65;
+const WHIRLPOOL_MINT_A_OFFSET: usize = 101;
+const RAYDIUM_SQRT_PRICE_OFFSET: usize = 253;
+const RAYDIUM_MINT_0_OFFSET: usize = 73;
+
+// Token metadata instructions are Borsh enums tagged by a single byte
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+
+/// Program a mirror venue's swaps are sent to.
+pub fn program_id(venue: u8) -> Result<Pubkey> {
+    match venue {
+        MIRROR_WHIRLPOOL => Ok(WHIRLPOOL_PROGRAM_ID),
+        MIRROR_RAYDIUM_CLMM => Ok(RAYDIUM_CLMM_PROGRAM_ID),
+        _ => err!(ErrorCode::InvalidMirrorVenue),
+    }
+}
+
+/// Builds a swap of exactly `amount_in` of `mint_in` into `pool` on `venue`,
+/// failing inside the venue if less than `min_out` comes back.
+pub fn swap_instruction(
+    venue: u8,
+    pool: &Pubkey,
+    authority: &Pubkey,
+    mint_in: &Pubkey,
+    accounts: &[AccountInfo],
+    amount_in: u64,
+    min_out: u64,
+) -> Result<Instruction> {
+    let (pool_index, authority_index, min_accounts) = match venue {
+        MIRROR_WHIRLPOOL => (
+            WHIRLPOOL_POOL_INDEX,
+            WHIRLPOOL_AUTHORITY_INDEX,
+            WHIRLPOOL_ACCOUNT_COUNT,
+        ),
+        MIRROR_RAYDIUM_CLMM => (
+            RAYDIUM_POOL_INDEX,
+            RAYDIUM_PAYER_INDEX,
+            RAYDIUM_ACCOUNT_COUNT,
+        ),
+        _ => return err!(ErrorCode::InvalidMirrorVenue),
+    };
+    if accounts.len() < min_accounts
+        || accounts[pool_index].key != pool
+        || accounts[authority_index].key != authority
+    {
+        return err!(ErrorCode::InvalidMirrorAccounts);
+    }
+
+    let mut data = SWAP_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&min_out.to_le_bytes());
+    if venue == MIRROR_WHIRLPOOL {
+        // Whirlpool wants the direction spelled out; read it off the owner's
+        // token A account rather than trusting the pool's mint ordering
+        let owner_account_a = TokenAccount::try_deserialize(
+            &mut &accounts[WHIRLPOOL_OWNER_ACCOUNT_A_INDEX].try_borrow_data()?[..],
+        )
+        .map_err(|_| ErrorCode::InvalidMirrorAccounts)?;
+        let a_to_b = owner_account_a.mint == *mint_in;
+        let sqrt_price_limit = if a_to_b {
+            WHIRLPOOL_MIN_SQRT_PRICE
+        } else {
+            WHIRLPOOL_MAX_SQRT_PRICE
+        };
+        data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
+        data.push(1); // amount_specified_is_input
+        data.push(a_to_b as u8);
+    } else {
+        // Raydium infers direction from the vault order; 0 lets it use the
+        // widest price limit for that direction
+        data.extend_from_slice(&0u128.to_le_bytes());
+        data.push(1); // is_base_input
+    }
+
+    Ok(Instruction {
+        program_id: program_id(venue)?,
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    })
+}
+
+/// Marginal price of `pool` on `venue` as token A per token B, in
+/// `PRICE_SCALE` fixed point and rounded down, read from the sqrt price the
+/// pool account keeps. `token_a` is the market's, to orient the pool's mints.
+pub fn pool_price(venue: u8, pool: &AccountInfo, token_a: &Pubkey) -> Result<u64> {
+    let (sqrt_offset, mint_offset) = match venue {
+        MIRROR_WHIRLPOOL => (WHIRLPOOL_SQRT_PRICE_OFFSET, WHIRLPOOL_MINT_A_OFFSET),
+        MIRROR_RAYDIUM_CLMM => (RAYDIUM_SQRT_PRICE_OFFSET, RAYDIUM_MINT_0_OFFSET),
+        _ => return err!(ErrorCode::InvalidMirrorVenue),
+    };
+    let data = pool.try_borrow_data()?;
+    let (Some(sqrt_price), Some(first_mint)) = (
+        data.get(sqrt_offset..sqrt_offset + 16),
+        data.get(mint_offset..mint_offset + 32),
+    ) else {
+        return err!(ErrorCode::InvalidMirrorAccounts);
+    };
+    // Drop to Q32.32 so the square fits; the pool's price is then Q64.64
+    let sqrt_price = u128::from_le_bytes(sqrt_price.try_into().unwrap()) >> 32;
+    let price = sqrt_price.checked_mul(sqrt_price).ok_or(ErrorCode::MathOverflow)?;
+    if price == 0 {
+        return err!(ErrorCode::InvalidMirrorAccounts);
+    }
+    let scaled = if first_mint == token_a.as_ref() {
+        // Token B per token A, so invert
+        ((crate::PRICE_SCALE as u128) << 64) / price
+    } else {
+        ((price >> 32) * crate::PRICE_SCALE as u128) >> 32
+    };
+    u64::try_from(scaled).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Wraps a client-encoded OpenBook v2 instruction for `agent` to sign. Only
+/// instructions in `allowed` pass, and `agent` must appear in `accounts`,
+/// where it is marked as the signer.
+pub fn openbook_instruction(
+    agent: &Pubkey,
+    accounts: &[AccountInfo],
+    data: Vec<u8>,
+    allowed: &[[u8; 8]],
+) -> Result<Instruction> {
+    if !allowed.iter().any(|discriminator| data.starts_with(discriminator))
+        || !accounts.iter().any(|account| account.key == agent)
+    {
+        return err!(ErrorCode::InvalidOpenbookInstruction);
+    }
+    Ok(Instruction {
+        program_id: OPENBOOK_V2_PROGRAM_ID,
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key == agent,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    })
+}
+
+/// Metadata account Metaplex keeps for `mint`.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Creates mutable metadata for `mint` with no creators, royalties,
+/// collection or uses. `authority` must be the mint authority and becomes the
+/// update authority.
+pub fn create_metadata_instruction(
+    metadata: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Instruction {
+    let mut data = vec![CREATE_METADATA_ACCOUNT_V3];
+    encode_metadata(&mut data, name, symbol, uri);
+    data.push(1); // is_mutable
+    data.push(0); // collection_details
+    Instruction {
+        program_id: TOKEN_METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Replaces the name, symbol and uri of metadata `authority` updates.
+pub fn update_metadata_instruction(
+    metadata: &Pubkey,
+    authority: &Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Instruction {
+    let mut data = vec![UPDATE_METADATA_ACCOUNT_V2, 1];
+    encode_metadata(&mut data, name, symbol, uri);
+    data.extend_from_slice(&[0, 0, 0]); // keep update authority, primary sale and mutability
+    Instruction {
+        program_id: TOKEN_METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data,
+    }
+}
+
+// DataV2 with zero royalties and no creators, collection or uses
+fn encode_metadata(data: &mut Vec<u8>, name: &str, symbol: &str, uri: &str) {
+    for field in [name, symbol, uri] {
+        data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        data.extend_from_slice(field.as_bytes());
+    }
+    data.extend_from_slice(&0u16.to_le_bytes());
+    data.extend_from_slice(&[0, 0, 0]);
+}
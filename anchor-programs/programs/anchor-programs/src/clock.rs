@@ -0,0 +1,44 @@
+//! Market clocks. Each market keeps time either in slots or in unix seconds,
+//! per its `time_base`, and everything scheduled on the market is counted in
+//! those units: epochs, which are `epoch_slots` long, option, RFQ and signed
+//! order expiries, and strategy vault fee accrual. Slot time replays the same
+//! in a backtest however fast the wall clock runs; unix time follows the
+//! wall clock, as live sims need. Oracle staleness, TWAPs and session keys
+//! stay in slots on every market.
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::{TIME_BASE_SLOT, TIME_BASE_UNIX};
+
+/// Slot length the two time bases convert at.
+pub const MS_PER_SLOT: u64 = 400;
+
+/// The current time on a `time_base` clock.
+pub fn now(time_base: u8) -> Result<u64> {
+    read(&Clock::get()?, time_base)
+}
+
+pub fn read(clock: &Clock, time_base: u8) -> Result<u64> {
+    match time_base {
+        TIME_BASE_SLOT => Ok(clock.slot),
+        TIME_BASE_UNIX => {
+            u64::try_from(clock.unix_timestamp).map_err(|_| error!(ErrorCode::InvalidTimeBase))
+        }
+        _ => err!(ErrorCode::InvalidTimeBase),
+    }
+}
+
+/// A `duration` on a `time_base` clock, in slots.
+pub fn to_slots(time_base: u8, duration: u64) -> u64 {
+    match time_base {
+        TIME_BASE_UNIX => (duration as u128 * 1_000 / MS_PER_SLOT as u128).min(u64::MAX as u128) as u64,
+        _ => duration,
+    }
+}
+
+/// `slots` as a duration on a `time_base` clock.
+pub fn from_slots(time_base: u8, slots: u64) -> u64 {
+    match time_base {
+        TIME_BASE_UNIX => (slots as u128 * MS_PER_SLOT as u128 / 1_000) as u64,
+        _ => slots,
+    }
+}
@@ -13,6 +13,266 @@ pub enum ErrorCode {
     #[msg("Invalid token accounts")]
     InvalidTokenAccounts,
 
-    #[msg("Invlaid agent owner")]
-    InvalidAgentOwner
-}
\ No newline at end of file
+    #[msg("Invalid agent owner")]
+    InvalidAgentOwner,
+    #[msg("Invalid trade amount")]
+    InvalidTradeAmount,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Insufficient tracked balance")]
+    InsufficientBalance,
+    #[msg("Trades do not cross")]
+    TradesDoNotCross,
+    #[msg("Invalid self-trade prevention mode")]
+    InvalidStpMode,
+    #[msg("Invalid trade flags")]
+    InvalidTradeFlags,
+    #[msg("Post-only order would cross")]
+    PostOnlyWouldCross,
+    #[msg("Reduce-only order would increase exposure")]
+    ReduceOnlyWouldIncrease,
+    #[msg("Invalid fee schedule")]
+    InvalidFeeSchedule,
+    #[msg("Settlement account required for net-settled market")]
+    SettlementRequired,
+    #[msg("Previous epoch has not been settled")]
+    SettlementPending,
+    #[msg("Epoch is still in progress")]
+    EpochNotEnded,
+    #[msg("Offer is reserved for another agent")]
+    OfferCounterpartyMismatch,
+    #[msg("Request for quote has expired")]
+    RfqExpired,
+    #[msg("Request for quote has no room for more quotes")]
+    RfqFull,
+    #[msg("No quote available to accept")]
+    NoQuotes,
+    #[msg("Invalid option kind")]
+    InvalidOptionKind,
+    #[msg("Option has expired")]
+    OptionExpired,
+    #[msg("Option has not expired yet")]
+    OptionNotExpired,
+    #[msg("Option has already been sold")]
+    OptionAlreadySold,
+    #[msg("Binary market is already resolved")]
+    MarketAlreadyResolved,
+    #[msg("Invalid outcome")]
+    InvalidOutcome,
+    #[msg("Invalid index composition")]
+    InvalidIndexComponents,
+    #[msg("Flash swap was not repaid with its fee")]
+    FlashRepaymentShort,
+    #[msg("Invalid callback program")]
+    InvalidCallbackProgram,
+    #[msg("Flash loan already outstanding")]
+    FlashLoanOutstanding,
+    #[msg("Flash loan must be repaid later in the same transaction")]
+    FlashLoanNotRepaid,
+    #[msg("Flash loans cannot be taken through CPI")]
+    FlashLoanCpi,
+    #[msg("Instructions sysvar required by the market's sandwich guard")]
+    InstructionsSysvarRequired,
+    #[msg("Transaction touches the market in another instruction")]
+    SandwichDetected,
+    #[msg("Invalid oracle configuration")]
+    InvalidOracleConfig,
+    #[msg("Oracle account does not match the market's configuration")]
+    OracleAccountMismatch,
+    #[msg("Oracle account data is not a recognised price feed")]
+    InvalidOracleAccount,
+    #[msg("Oracle price is not currently trading")]
+    OraclePriceUnavailable,
+    #[msg("Oracle price is stale")]
+    OracleStale,
+    #[msg("Oracle confidence interval too wide")]
+    OracleConfidenceTooWide,
+    #[msg("Price update is not fully verified")]
+    PriceUpdateUnverified,
+    #[msg("Price update is older than the cached price")]
+    PriceUpdateOutdated,
+    #[msg("External routing is disabled for this market")]
+    ExternalRoutingDisabled,
+    #[msg("Swap output below the slippage limit")]
+    SlippageExceeded,
+    #[msg("Invalid mirror venue")]
+    InvalidMirrorVenue,
+    #[msg("Mirror swap accounts do not match the market's pool")]
+    InvalidMirrorAccounts,
+    #[msg("OpenBook instruction not allowed for this agent")]
+    InvalidOpenbookInstruction,
+    #[msg("Invalid session key, expiry or scope")]
+    InvalidSession,
+    #[msg("Signed order is expired, replayed or for another agent or market")]
+    InvalidSignedOrder,
+    #[msg("Missing or mismatched Ed25519 signature verification")]
+    SignatureVerificationRequired,
+    #[msg("Relayer debt is owed to a different relayer")]
+    RelayerMismatch,
+    #[msg("Perpetual positions are disabled for this market")]
+    PerpsDisabled,
+    #[msg("Invalid margin parameters")]
+    InvalidMarginParams,
+    #[msg("Position would fall below its initial margin")]
+    InsufficientMargin,
+    #[msg("Position is above its maintenance margin")]
+    NotLiquidatable,
+    #[msg("Position is bankrupt and must go through declare_bankruptcy")]
+    PositionBankrupt,
+    #[msg("Position equity is not negative")]
+    NotBankrupt,
+    #[msg("Insurance fund can still cover the deficit")]
+    InsuranceSufficient,
+    #[msg("Counterparty is not the top of the auto-deleveraging queue")]
+    NotTopOfAdlQueue,
+    #[msg("Open interest cap exceeded")]
+    OpenInterestCapExceeded,
+    #[msg("Epoch is not the current epoch")]
+    InvalidEpoch,
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Claims exceed the published rewards")]
+    RewardsExhausted,
+    #[msg("Agent already has an identity NFT")]
+    AgentNftExists,
+    #[msg("Agent identity NFT not held")]
+    AgentNftNotHeld,
+    #[msg("Signer is not the pending agent owner")]
+    NotPendingOwner,
+    #[msg("Invalid share amount")]
+    InvalidShareAmount,
+    #[msg("Invalid copy-trading subscription")]
+    InvalidSubscription,
+    #[msg("No leader fill left to mirror")]
+    NoFillToMirror,
+    #[msg("Invalid strategy vault fee")]
+    InvalidStrategyFee,
+    #[msg("Invalid redemption request")]
+    InvalidRedemption,
+    #[msg("Vault holds fewer tokens than it has accounted for")]
+    VaultDeficit,
+    #[msg("Account is not owned by this program")]
+    NotProgramAccount,
+    #[msg("Trade still has size left to fill")]
+    TradeNotFilled,
+    #[msg("Balance is not empty")]
+    BalanceNotEmpty,
+    #[msg("Feature is disabled in this deployment")]
+    FeatureDisabled,
+    #[msg("An external oracle price is required")]
+    OracleRequired,
+    #[msg("Trading is halted while emergency mode is on")]
+    EmergencyMode,
+    #[msg("Account belongs to a different market")]
+    InvalidMarket,
+    #[msg("Account belongs to a different agent")]
+    InvalidAgent,
+    #[msg("Signed order has expired")]
+    OrderExpired,
+    #[msg("Signed order nonce was already used")]
+    NonceReused,
+    #[msg("Slippage tolerance above 100%")]
+    InvalidSlippage,
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+    #[msg("Internal invariant violated")]
+    InvariantViolated,
+    #[msg("Crank sequence doesn't match the market's")]
+    CrankOutOfSequence,
+    #[msg("Deposit receipt must be passed exactly when a nonce is")]
+    InvalidDepositReceipt,
+    #[msg("Unknown time base")]
+    InvalidTimeBase,
+    #[msg("Time base can't change once the market has traded")]
+    TimeBaseLocked,
+    #[msg("Funding cap can't exceed 100% per epoch")]
+    InvalidFundingParams,
+    #[msg("Market is mid flash swap")]
+    MarketInFlight,
+    #[msg("Emissions need a positive rate over a non-empty window and a maker share of at most 100%")]
+    InvalidEmissionsSchedule,
+    #[msg("Counting a fill for emissions needs both agents' emissions accounts")]
+    EmissionsAccountRequired,
+    #[msg("No emissions to claim")]
+    NothingToClaim,
+    #[msg("Unlock must be in the future, no earlier than the current one and within the longest lock")]
+    InvalidLockDuration,
+    #[msg("Bond is still locked")]
+    BondLocked,
+    #[msg("No bonds to pay fees to")]
+    NoBondsToPay,
+    #[msg("Amount exceeds the market's accrued fees")]
+    InsufficientFees,
+    #[msg("Order exceeds the session's spending limit")]
+    SessionSpendExceeded,
+    #[msg("Order passed more than once")]
+    DuplicateOrder,
+    #[msg("The market is congested; placing needs the agent's token A balance")]
+    CongestionBalanceRequired,
+    #[msg("Invalid matching policy")]
+    InvalidMatchingPolicy,
+    #[msg("Order is not resting at the maker's price")]
+    NotAtMakerLevel,
+    #[msg("Tie-break randomness is missing, wrong or not yet fulfilled")]
+    RandomnessUnavailable,
+    #[msg("Maker is not next in its level's tie-break order")]
+    OutOfTieBreakOrder,
+    #[msg("Trading hours must fall within an epoch")]
+    InvalidTradingHours,
+    #[msg("Market is outside its trading hours")]
+    MarketClosed,
+    #[msg("Auctions need trading hours long enough to hold both")]
+    InvalidAuctionSlots,
+    #[msg("Market is in a call auction")]
+    AuctionInProgress,
+    #[msg("No call auction is running or due to uncross")]
+    AuctionNotDue,
+    #[msg("Every resting order must be passed")]
+    IncompleteBook,
+    #[msg("Market is halted by its circuit breaker")]
+    MarketHalted,
+    #[msg("A circuit breaker needs a reopening auction")]
+    InvalidCircuitBreaker,
+    #[msg("Observation interval must be at least one slot")]
+    InvalidObservationInterval,
+    #[msg("Observation frame was updated too recently")]
+    ObservationNotDue,
+    #[msg("Market doesn't take compact actions")]
+    ActionsDisabled,
+    #[msg("Compact actions need a last fill price to offset from")]
+    ActionPriceUnavailable,
+    #[msg("Action doesn't encode a valid order")]
+    InvalidAction,
+    #[msg("Each action needs its agent, trade and vault A balance")]
+    InvalidActionAccounts,
+    #[msg("Inventory penalty can't exceed 100%")]
+    InvalidInventoryPenalty,
+    #[msg("Reward record is already settled")]
+    RewardAlreadySettled,
+    #[msg("Curriculum stages must be known changes in epoch order")]
+    InvalidCurriculum,
+    #[msg("No curriculum stage is due")]
+    CurriculumNotDue,
+    #[msg("Order is larger than the market allows")]
+    OrderTooLarge,
+    #[msg("Agent isn't the market's adversary")]
+    NotAdversary,
+    #[msg("Market has no free book level for the order's price")]
+    BookLevelsFull,
+    #[msg("Orders passed aren't the rest of the maker's level")]
+    IncompleteLevel,
+    #[msg("Market still has orders resting")]
+    OrdersResting,
+    #[msg("Agent has more than one reward record passed")]
+    DuplicateRewardRecord,
+    #[msg("Option is out of the money at the oracle price")]
+    OptionOutOfTheMoney,
+    #[msg("A call auction is part way through uncrossing")]
+    AuctionUncrossing,
+    #[msg("Not every trade the agent has open was passed")]
+    IncompleteTrades,
+    #[msg("Every delegate slot holds an unexpired session")]
+    DelegatesFull,
+    #[msg("Deposit nonce was already used on another vault")]
+    DepositNonceReused,
+}
@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use crate::{Market, Trade, TRADE_TAG_LEN};
+
+/// An order's state on the book after it was placed, changed, filled or
+/// taken off; zero amounts mean it left the book. `book_sequence` numbers a
+/// market's updates from 1 without gaps, so a client mirroring the book can
+/// tell when it missed one.
+#[event]
+pub struct OrderUpdated {
+    pub market: Pubkey,
+    pub book_sequence: u64,
+    pub trade: Pubkey,
+    pub agent: Pubkey,
+    pub trade_type: u8,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Queue priority, lower rests ahead
+    pub sequence: u64,
+    /// Token B shown on the book, less than the size for an iceberg
+    pub displayed: u64,
+    pub tag: [u8; TRADE_TAG_LEN],
+}
+
+impl OrderUpdated {
+    /// Emits `trade`'s current state as the market's next book update. An
+    /// update leaving the order empty takes it out of `open_orders`. Nothing
+    /// but the uncross itself may change the book while one is under way.
+    pub fn emit(market: &mut Market, trade: &mut Account<Trade>) -> Result<()> {
+        market.ensure_not_uncrossing()?;
+        Self::emit_uncrossed(market, trade)
+    }
+
+    /// `emit` for a fill of `uncross_auction`'s own.
+    pub(crate) fn emit_uncrossed(market: &mut Market, trade: &mut Account<Trade>) -> Result<()> {
+        if trade.size() == 0 {
+            market.open_orders = market.open_orders.saturating_sub(1);
+        }
+        let displayed = trade.displayed();
+        market.rebook(trade, displayed)?;
+        let (amount_in, amount_out) = (trade.amount_in, trade.amount_out);
+        Self::emit_state(market, trade, amount_in, amount_out, trade.displayed())
+    }
+
+    /// Emits `trade` leaving the book, for when its account is being closed.
+    pub fn emit_removed(market: &mut Market, trade: &mut Account<Trade>) -> Result<()> {
+        market.ensure_not_uncrossing()?;
+        if trade.size() > 0 {
+            market.open_orders = market.open_orders.saturating_sub(1);
+        }
+        market.rebook(trade, 0)?;
+        Self::emit_state(market, trade, 0, 0, 0)
+    }
+
+    fn emit_state(
+        market: &mut Market,
+        trade: &Account<Trade>,
+        amount_in: u64,
+        amount_out: u64,
+        displayed: u64,
+    ) -> Result<()> {
+        emit!(OrderUpdated {
+            market: trade.market,
+            book_sequence: market.next_book_sequence()?,
+            trade: trade.key(),
+            agent: trade.agent,
+            trade_type: trade.trade_type,
+            amount_in,
+            amount_out,
+            sequence: trade.sequence,
+            displayed,
+            tag: trade.tag,
+        });
+        Ok(())
+    }
+}
+
+/// A bid and ask matched on the book, at the resting order's price.
+#[event]
+pub struct OrderFilled {
+    pub market: Pubkey,
+    pub bid_agent: Pubkey,
+    pub ask_agent: Pubkey,
+    /// Tags the two orders were placed with
+    pub bid_tag: [u8; TRADE_TAG_LEN],
+    pub ask_tag: [u8; TRADE_TAG_LEN],
+    pub bid_is_maker: bool,
+    /// Token B
+    pub quantity: u64,
+    /// Token A, before fees
+    pub quote_amount: u64,
+    /// Token A, negative for a rebate
+    pub bid_fee: i64,
+    pub ask_fee: i64,
+    /// Seed the maker's level was ordered by, zero when none was drawn
+    pub tie_break_seed: [u8; 32],
+}
+
+/// Where a running call auction would clear, published by `indicate_auction`.
+#[event]
+pub struct AuctionIndicated {
+    pub market: Pubkey,
+    /// SESSION_* auction
+    pub phase: u8,
+    /// Token A per token B, `PRICE_SCALE` fixed point; 0 when nothing crosses
+    pub price: u64,
+    /// Token B that would trade
+    pub volume: u64,
+    /// Bids less asks crossing at `price`, in token B
+    pub imbalance: i64,
+}
+
+/// A fill moved the price past the market's circuit breaker, halting it.
+#[event]
+pub struct CircuitBreakerTripped {
+    pub market: Pubkey,
+    /// The fill's price and how far it moved from the one before
+    pub price: u64,
+    pub move_bps: u64,
+    pub halted_until: u64,
+    /// End of the reopening auction that follows the halt
+    pub reopens_at: u64,
+}
+
+impl CircuitBreakerTripped {
+    /// Halts `market` and emits the trip if a fill moved its price by
+    /// `move_bps`, past its circuit breaker. Returns whether it tripped.
+    pub fn check(market: &mut Account<Market>, move_bps: u64) -> Result<bool> {
+        if !market.trip_circuit_breaker(move_bps)? {
+            return Ok(false);
+        }
+        emit!(CircuitBreakerTripped {
+            market: market.key(),
+            price: market.last_price,
+            move_bps,
+            halted_until: market.halted_until,
+            reopens_at: market.reopens_at(),
+        });
+        Ok(true)
+    }
+}
+
+/// A call auction cleared at a single price.
+#[event]
+pub struct AuctionUncrossed {
+    pub market: Pubkey,
+    /// SESSION_* auction
+    pub phase: u8,
+    pub price: u64,
+    /// Token B traded
+    pub volume: u64,
+    /// Token A taken in fees and rounding
+    pub fees: u64,
+}
+
+/// A trade filled in full against the market's vaults.
+#[event]
+pub struct TradeExecuted {
+    pub market: Pubkey,
+    pub agent: Pubkey,
+    pub tag: [u8; TRADE_TAG_LEN],
+    pub trade_type: u8,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+/// How a hybrid trade split between the book and the mirrored pool.
+#[event]
+pub struct TradeRouted {
+    pub market: Pubkey,
+    pub agent: Pubkey,
+    pub tag: [u8; TRADE_TAG_LEN],
+    pub trade_type: u8,
+    /// Token B filled against resting orders
+    pub book_quantity: u64,
+    /// Paid and received across the book fills, fees included
+    pub book_amount_in: u64,
+    pub book_amount_out: u64,
+    pub pool_amount_in: u64,
+    pub pool_amount_out: u64,
+}
+
+/// A position closed at the oracle price for falling below maintenance
+/// margin.
+#[event]
+pub struct PositionLiquidated {
+    pub market: Pubkey,
+    pub agent: Pubkey,
+    pub liquidator: Pubkey,
+    /// Size closed at the oracle price
+    pub base: i64,
+    pub price: u64,
+    pub liquidator_fee: u64,
+    pub insurance_fee: u64,
+}
+
+/// A position's deficit and how it was absorbed; `insurance_drawn`,
+/// `socialized` and `bad_debt` always sum to `deficit`.
+#[event]
+pub struct BankruptcyDeclared {
+    pub market: Pubkey,
+    pub agent: Pubkey,
+    pub base: i64,
+    pub price: u64,
+    pub deficit: u64,
+    pub insurance_drawn: u64,
+    pub socialized: u64,
+    pub bad_debt: u64,
+}
+
+/// A bankrupt position closed against the top-ranked opposing position.
+#[event]
+pub struct AutoDeleveraged {
+    pub market: Pubkey,
+    pub bankrupt_agent: Pubkey,
+    pub counterparty_agent: Pubkey,
+    /// Size taken off the bankrupt position
+    pub base: i64,
+    /// Token A the bankrupt position paid to close, negative if it received
+    pub quote: i64,
+}
+
+/// Outcome of reconcile_vault: `surplus` is what the token account held
+/// above the vault's reserves and has now been added to them.
+#[event]
+pub struct ReconciliationReport {
+    pub market: Pubkey,
+    pub vault: Pubkey,
+    /// Token account balance
+    pub actual: u64,
+    /// After taking in `surplus`
+    pub reserves: u64,
+    pub surplus: u64,
+    /// Unswept total, including `surplus`
+    pub donations: u64,
+}
+
+/// Accrued token A fees spent on token B through the mirrored pool, and the
+/// token B burned.
+#[event]
+pub struct BuybackBurned {
+    pub market: Pubkey,
+    pub spent: u64,
+    pub burned: u64,
+    /// Token A fees left accrued afterwards
+    pub fees_remaining: u64,
+}
@@ -0,0 +1,323 @@
+//! Strategy vault fee math. Fees are charged by minting the manager new
+//! shares rather than moving tokens: a fee worth `fee` token A out of a NAV
+//! of `nav` is paid with exactly the shares that leave existing holders
+//! owning `nav - fee`. The management fee accrues pro rata on NAV for the
+//! slots since the last crystallization; the performance fee is charged on
+//! the rise in NAV per share above the high-water mark, after the management
+//! fee. Every amount rounds down, in holders' favour, per `math`.
+use crate::errors::ErrorCode;
+use crate::math::mul_div_floor;
+use crate::{BPS_DENOMINATOR, PRICE_SCALE};
+
+pub const SLOTS_PER_YEAR: u64 = 78_840_000; // 400ms slots
+
+/// Shares minted to the manager by one crystallization and the high-water
+/// mark to keep afterwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeAccrual {
+    pub management_shares: u64,
+    pub performance_shares: u64,
+    pub high_water_mark: u64,
+}
+
+impl FeeAccrual {
+    pub fn shares(&self) -> u64 {
+        self.management_shares + self.performance_shares
+    }
+}
+
+/// Token A per share, `PRICE_SCALE` fixed point; `None` with no shares out.
+pub fn nav_per_share(nav: u64, supply: u64) -> std::result::Result<Option<u64>, ErrorCode> {
+    if supply == 0 {
+        return Ok(None);
+    }
+    mul_div_floor(nav, PRICE_SCALE, supply).map(Some)
+}
+
+/// Management fee on `nav` for `elapsed_slots` at `management_fee_bps` a
+/// year, never more than `nav`.
+pub fn management_fee(nav: u64, management_fee_bps: u16, elapsed_slots: u64) -> u64 {
+    // A product past u128 is a fee far beyond `nav`
+    (nav as u128 * management_fee_bps as u128)
+        .checked_mul(elapsed_slots as u128)
+        .map_or(nav as u128, |accrued| {
+            accrued / (BPS_DENOMINATOR as u128 * SLOTS_PER_YEAR as u128)
+        })
+        .min(nav as u128) as u64
+}
+
+/// Performance fee on the part of `nav` above `supply` shares at
+/// `high_water_mark`.
+pub fn performance_fee(
+    nav: u64,
+    supply: u64,
+    high_water_mark: u64,
+    performance_fee_bps: u16,
+) -> u64 {
+    // A hurdle past u64 is one no NAV clears
+    let hurdle = mul_div_floor(high_water_mark, supply, PRICE_SCALE).unwrap_or(u64::MAX);
+    let gain = nav.saturating_sub(hurdle);
+    mul_div_floor(gain, performance_fee_bps as u64, BPS_DENOMINATOR).unwrap_or(0)
+}
+
+/// Shares that dilute `supply` existing shares by `fee` out of `nav`:
+/// `fee * supply / (nav - fee)`. A fee taking the whole NAV mints nothing,
+/// since no number of shares is worth all of it.
+pub fn dilution_shares(fee: u64, nav: u64, supply: u64) -> std::result::Result<u64, ErrorCode> {
+    if fee == 0 || fee >= nav {
+        return Ok(0);
+    }
+    mul_div_floor(fee, supply, nav - fee)
+}
+
+/// Management and performance fees owed at `nav` with `supply` shares out,
+/// `elapsed_slots` after the last crystallization. The high-water mark rises
+/// to the post-fee NAV per share whenever a performance fee is charged and
+/// is left alone otherwise.
+pub fn crystallize(
+    nav: u64,
+    supply: u64,
+    high_water_mark: u64,
+    management_fee_bps: u16,
+    performance_fee_bps: u16,
+    elapsed_slots: u64,
+) -> std::result::Result<FeeAccrual, ErrorCode> {
+    let mut accrual = FeeAccrual {
+        high_water_mark,
+        ..FeeAccrual::default()
+    };
+    if supply == 0 || nav == 0 {
+        return Ok(accrual);
+    }
+    let management = management_fee(nav, management_fee_bps, elapsed_slots);
+    let performance = performance_fee(nav - management, supply, high_water_mark, performance_fee_bps);
+    let total = management + performance;
+    let shares = dilution_shares(total, nav, supply)?;
+    if shares == 0 {
+        return Ok(accrual);
+    }
+    accrual.management_shares = mul_div_floor(shares, management, total)?;
+    accrual.performance_shares = shares - accrual.management_shares;
+    if performance > 0 {
+        let supply = supply.checked_add(shares).ok_or(ErrorCode::MathOverflow)?;
+        let post_fee = nav_per_share(nav, supply)?.unwrap_or(high_water_mark);
+        accrual.high_water_mark = high_water_mark.max(post_fee);
+    }
+    Ok(accrual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: u64 = PRICE_SCALE;
+
+    /// Token A value of `shares` at `nav` with `supply` out.
+    fn value_of(shares: u64, nav: u64, supply: u64) -> u128 {
+        shares as u128 * nav as u128 / supply as u128
+    }
+
+    #[test]
+    fn nav_per_share_without_shares_is_none() {
+        assert_eq!(nav_per_share(1_000, 0).unwrap(), None);
+    }
+
+    #[test]
+    fn nav_per_share_is_scaled() {
+        assert_eq!(nav_per_share(1_000, 1_000).unwrap(), Some(ONE));
+        assert_eq!(nav_per_share(1_500, 1_000).unwrap(), Some(ONE * 3 / 2));
+        assert_eq!(nav_per_share(1, 3).unwrap(), Some(333_333));
+    }
+
+    #[test]
+    fn nav_per_share_overflow_is_reported() {
+        assert!(matches!(nav_per_share(u64::MAX, 1), Err(ErrorCode::MathOverflow)));
+    }
+
+    #[test]
+    fn management_fee_is_prorated_by_slot() {
+        assert_eq!(management_fee(1_000_000, 200, SLOTS_PER_YEAR), 20_000);
+        assert_eq!(management_fee(1_000_000, 200, SLOTS_PER_YEAR / 2), 10_000);
+        assert_eq!(management_fee(1_000_000, 200, SLOTS_PER_YEAR / 4), 5_000);
+    }
+
+    #[test]
+    fn management_fee_is_zero_without_time_or_rate() {
+        assert_eq!(management_fee(1_000_000, 200, 0), 0);
+        assert_eq!(management_fee(1_000_000, 0, SLOTS_PER_YEAR), 0);
+        assert_eq!(management_fee(0, 200, SLOTS_PER_YEAR), 0);
+    }
+
+    #[test]
+    fn management_fee_rounds_down() {
+        // 1_000 at 1 bps a year for one slot is far below one base unit
+        assert_eq!(management_fee(1_000, 1, 1), 0);
+    }
+
+    #[test]
+    fn management_fee_never_exceeds_nav() {
+        assert_eq!(management_fee(1_000, 10_000, SLOTS_PER_YEAR * 5), 1_000);
+        assert_eq!(management_fee(u64::MAX, u16::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn performance_fee_is_zero_at_or_below_the_mark() {
+        assert_eq!(performance_fee(1_000, 1_000, ONE, 2_000), 0);
+        assert_eq!(performance_fee(900, 1_000, ONE, 2_000), 0);
+        assert_eq!(performance_fee(1_500, 1_000, 2 * ONE, 2_000), 0);
+    }
+
+    #[test]
+    fn performance_fee_charges_gain_above_the_mark() {
+        assert_eq!(performance_fee(1_500, 1_000, ONE, 2_000), 100);
+        assert_eq!(performance_fee(1_500, 1_000, ONE, 10_000), 500);
+        assert_eq!(performance_fee(1_500, 1_000, ONE, 0), 0);
+        assert_eq!(performance_fee(2_400, 1_000, 2 * ONE, 5_000), 200);
+    }
+
+    #[test]
+    fn performance_fee_rounds_down() {
+        assert_eq!(performance_fee(1_009, 1_000, ONE, 1_000), 0);
+        assert_eq!(performance_fee(1_010, 1_000, ONE, 1_000), 1);
+    }
+
+    #[test]
+    fn dilution_shares_are_zero_for_no_fee_or_whole_nav() {
+        assert_eq!(dilution_shares(0, 1_000, 1_000).unwrap(), 0);
+        assert_eq!(dilution_shares(1_000, 1_000, 1_000).unwrap(), 0);
+        assert_eq!(dilution_shares(2_000, 1_000, 1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn dilution_shares_are_worth_the_fee() {
+        // 100 shares more on 1_000 are worth 1_100 * 100 / 1_100
+        assert_eq!(dilution_shares(100, 1_100, 1_000).unwrap(), 100);
+        assert_eq!(dilution_shares(500, 1_000, 1_000).unwrap(), 1_000);
+        assert_eq!(dilution_shares(1, 3, 2).unwrap(), 1);
+    }
+
+    #[test]
+    fn dilution_shares_overflow_is_reported() {
+        assert!(matches!(
+            dilution_shares(u64::MAX - 1, u64::MAX, u64::MAX),
+            Err(ErrorCode::MathOverflow)
+        ));
+    }
+
+    #[test]
+    fn crystallize_without_shares_or_nav_charges_nothing() {
+        let none = FeeAccrual {
+            high_water_mark: ONE,
+            ..FeeAccrual::default()
+        };
+        assert_eq!(crystallize(1_000, 0, ONE, 200, 2_000, SLOTS_PER_YEAR).unwrap(), none);
+        assert_eq!(crystallize(0, 1_000, ONE, 200, 2_000, SLOTS_PER_YEAR).unwrap(), none);
+    }
+
+    #[test]
+    fn crystallize_below_the_mark_only_charges_management() {
+        let accrual = crystallize(1_000_000, 1_000_000, ONE, 200, 2_000, SLOTS_PER_YEAR).unwrap();
+        assert_eq!(accrual.performance_shares, 0);
+        assert_eq!(accrual.management_shares, 20_408); // 20_000 * 1e6 / 980_000
+        assert_eq!(accrual.high_water_mark, ONE);
+    }
+
+    #[test]
+    fn crystallize_above_the_mark_raises_it_to_post_fee_nav() {
+        let accrual = crystallize(1_500_000, 1_000_000, ONE, 0, 2_000, 0).unwrap();
+        assert_eq!(accrual.management_shares, 0);
+        // 100_000 fee out of 1_500_000: 1e6 * 1e5 / 1.4e6
+        assert_eq!(accrual.performance_shares, 71_428);
+        assert_eq!(accrual.high_water_mark, 1_400_000);
+    }
+
+    #[test]
+    fn crystallize_charges_performance_after_management() {
+        let accrual = crystallize(1_500_000, 1_000_000, ONE, 200, 2_000, SLOTS_PER_YEAR).unwrap();
+        // 30_000 management, then 20% of 1_470_000 - 1_000_000
+        let (management, performance) = (30_000u128, 94_000u128);
+        let shares = accrual.shares() as u128;
+        assert_eq!(shares, (management + performance) * 1_000_000 / (1_500_000 - 124_000));
+        assert_eq!(
+            accrual.management_shares as u128,
+            shares * management / (management + performance)
+        );
+    }
+
+    #[test]
+    fn crystallize_twice_at_the_same_nav_charges_once() {
+        let first = crystallize(1_500_000, 1_000_000, ONE, 0, 2_000, 0).unwrap();
+        let supply = 1_000_000 + first.shares();
+        let second = crystallize(1_500_000, supply, first.high_water_mark, 0, 2_000, 0).unwrap();
+        assert_eq!(second.shares(), 0);
+        assert_eq!(second.high_water_mark, first.high_water_mark);
+    }
+
+    #[test]
+    fn crystallize_after_a_drawdown_waits_for_the_mark() {
+        let first = crystallize(1_500_000, 1_000_000, ONE, 0, 2_000, 0).unwrap();
+        let supply = 1_000_000 + first.shares();
+        let drawdown = crystallize(1_200_000, supply, first.high_water_mark, 0, 2_000, 0).unwrap();
+        assert_eq!(drawdown.shares(), 0);
+        assert_eq!(drawdown.high_water_mark, first.high_water_mark);
+        let recovered = crystallize(1_500_000, supply, first.high_water_mark, 0, 2_000, 0).unwrap();
+        assert_eq!(recovered.shares(), 0);
+    }
+
+    #[test]
+    fn crystallize_full_fee_leaves_holders_the_hurdle() {
+        let accrual = crystallize(2_000_000, 1_000_000, ONE, 0, 10_000, 0).unwrap();
+        let supply = 1_000_000 + accrual.shares();
+        assert_eq!(value_of(1_000_000, 2_000_000, supply), 1_000_000);
+        assert_eq!(accrual.high_water_mark, ONE);
+    }
+
+    #[test]
+    fn crystallize_overflow_is_reported() {
+        assert!(matches!(
+            crystallize(u64::MAX, u64::MAX, 0, 0, 9_999, 0),
+            Err(ErrorCode::MathOverflow)
+        ));
+    }
+
+    #[test]
+    fn crystallize_never_takes_more_than_the_fees_owed() {
+        let navs = [1, 7, 999, 1_000, 1_001, 123_457, 1_000_000, 3_000_000, 987_654_321];
+        let supplies = [1, 3, 1_000, 999_999, 1_000_000, 50_000_000];
+        let marks = [0, 1, ONE / 2, ONE, ONE * 3 / 2, ONE * 10];
+        let management_rates = [0, 1, 200, 10_000];
+        let performance_rates = [0, 1, 2_000, 10_000];
+        let elapsed = [0, 1, SLOTS_PER_YEAR / 12, SLOTS_PER_YEAR];
+        for nav in navs {
+            for supply in supplies {
+                for mark in marks {
+                    for management_bps in management_rates {
+                        for performance_bps in performance_rates {
+                            for slots in elapsed {
+                                let accrual = crystallize(
+                                    nav,
+                                    supply,
+                                    mark,
+                                    management_bps,
+                                    performance_bps,
+                                    slots,
+                                )
+                                .unwrap();
+                                let management = management_fee(nav, management_bps, slots);
+                                let performance =
+                                    performance_fee(nav - management, supply, mark, performance_bps);
+                                let owed = management as u128 + performance as u128;
+                                let taken = value_of(accrual.shares(), nav, supply + accrual.shares());
+                                assert!(taken <= owed, "nav {nav} supply {supply} mark {mark}");
+                                assert!(accrual.high_water_mark >= mark);
+                                if performance == 0 {
+                                    assert_eq!(accrual.high_water_mark, mark);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::Agent;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct AcceptAgentOwnership<'info> {
+    #[account(
+        mut,
+        constraint = agent.pending_owner == new_owner.key() @ ErrorCode::NotPendingOwner,
+        constraint = agent.nft_mint == Pubkey::default() @ ErrorCode::AgentNftExists
+    )]
+    pub agent: Account<'info, Agent>,
+    pub new_owner: Signer<'info>,
+}
+
+/// Completes a pending transfer, ending the previous owner's session. An
+/// identity NFT minted since the offer voids it.
+pub fn accept_agent_ownership(ctx: Context<AcceptAgentOwnership>) -> Result<()> {
+    let new_owner = ctx.accounts.new_owner.key();
+    ctx.accounts.agent.set_owner(new_owner);
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{Agent, Market, OtcOffer};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct AcceptOtcOffer<'info> {
+    #[account(
+        constraint = taker_agent.owner == taker.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub taker_agent: Account<'info, Agent>,
+    #[account(address = offer.maker @ ErrorCode::Unauthorized)]
+    pub maker_agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"otc", market.key().as_ref(), maker_agent.key().as_ref(), offer.offer_id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = maker_owner
+    )]
+    pub offer: Account<'info, OtcOffer>,
+    #[account(address = offer.give_mint @ ErrorCode::InvalidTokenMint)]
+    pub give_mint: Account<'info, Mint>,
+    #[account(address = offer.want_mint @ ErrorCode::InvalidTokenMint)]
+    pub want_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = give_mint,
+        associated_token::authority = offer
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = want_mint,
+        token::authority = taker
+    )]
+    pub taker_want_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = give_mint,
+        associated_token::authority = taker
+    )]
+    pub taker_give_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = want_mint,
+        token::authority = maker_owner
+    )]
+    pub maker_want_account: Account<'info, TokenAccount>,
+    #[account(mut, address = maker_agent.owner @ ErrorCode::InvalidAgentOwner)]
+    pub maker_owner: SystemAccount<'info>,
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn accept_otc_offer(ctx: Context<AcceptOtcOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    if offer.counterparty != Pubkey::default() && offer.counterparty != ctx.accounts.taker_agent.key() {
+        return err!(ErrorCode::OfferCounterpartyMismatch);
+    }
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.taker_want_account.to_account_info(),
+        to: ctx.accounts.maker_want_account.to_account_info(),
+        authority: ctx.accounts.taker.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, offer.want_amount)?;
+
+    release_escrow(
+        &ctx.accounts.token_program,
+        offer,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.taker_give_account,
+        &ctx.accounts.maker_owner.to_account_info(),
+    )
+}
+
+/// Pays the escrowed tokens to `recipient` and closes the escrow ATA, with
+/// its rent going back to the maker's owner.
+pub(crate) fn release_escrow<'info>(
+    token_program: &Program<'info, Token>,
+    offer: &Account<'info, OtcOffer>,
+    escrow_token_account: &Account<'info, TokenAccount>,
+    recipient: &Account<'info, TokenAccount>,
+    rent_receiver: &AccountInfo<'info>,
+) -> Result<()> {
+    let offer_id = offer.offer_id.to_le_bytes();
+    let seeds = &[
+        b"otc",
+        offer.market.as_ref(),
+        offer.maker.as_ref(),
+        offer_id.as_ref(),
+        &[offer.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: escrow_token_account.to_account_info(),
+        to: recipient.to_account_info(),
+        authority: offer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, offer.give_amount)?;
+
+    let cpi_accounts = CloseAccount {
+        account: escrow_token_account.to_account_info(),
+        destination: rent_receiver.clone(),
+        authority: offer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::close_account(cpi_ctx)
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Balance, Market, Rfq, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct AcceptQuote<'info> {
+    #[account(
+        constraint = taker_agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub taker_agent: Account<'info, Agent>,
+    pub maker_agent: Account<'info, Agent>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"rfq", market.key().as_ref(), taker_agent.key().as_ref(), rfq.rfq_id.to_le_bytes().as_ref()],
+        bump = rfq.bump,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = user
+    )]
+    pub rfq: Account<'info, Rfq>,
+
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), taker_agent.key().as_ref()],
+        bump
+    )]
+    pub taker_balance_a: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_b.key().as_ref(), taker_agent.key().as_ref()],
+        bump
+    )]
+    pub taker_balance_b: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), maker_agent.key().as_ref()],
+        bump
+    )]
+    pub maker_balance_a: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_b.key().as_ref(), maker_agent.key().as_ref()],
+        bump
+    )]
+    pub maker_balance_b: Account<'info, Balance>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Fills the request at the maker's quote. Quotes lock nothing when they're
+/// submitted, so the taker picks any quote whose maker can still pay, the
+/// best one included, and the fill fails if the maker's balance falls short.
+pub fn accept_quote(ctx: Context<AcceptQuote>) -> Result<()> {
+    let rfq = &ctx.accounts.rfq;
+    if ctx.accounts.market.now()? > rfq.expiry_slot {
+        return err!(ErrorCode::RfqExpired);
+    }
+    let quote = rfq
+        .quote_from(&ctx.accounts.maker_agent.key())
+        .ok_or(ErrorCode::NoQuotes)?;
+    let (size, taker_buys) = (rfq.size, rfq.side == 0);
+
+    let accounts = &mut *ctx.accounts;
+    let bumps = &ctx.bumps;
+    for (balance, agent, vault, bump) in [
+        (&mut accounts.taker_balance_a, accounts.taker_agent.key(), accounts.vault_a.key(), bumps.taker_balance_a),
+        (&mut accounts.taker_balance_b, accounts.taker_agent.key(), accounts.vault_b.key(), bumps.taker_balance_b),
+        (&mut accounts.maker_balance_a, accounts.maker_agent.key(), accounts.vault_a.key(), bumps.maker_balance_a),
+        (&mut accounts.maker_balance_b, accounts.maker_agent.key(), accounts.vault_b.key(), bumps.maker_balance_b),
+    ] {
+        balance.agent = agent;
+        balance.vault = vault;
+        balance.bump = bump;
+    }
+
+    if taker_buys {
+        accounts.taker_balance_a.debit(quote.quote_amount)?;
+        accounts.maker_balance_a.credit(quote.quote_amount)?;
+        accounts.maker_balance_b.debit(size)?;
+        accounts.taker_balance_b.credit(size)?;
+    } else {
+        accounts.maker_balance_a.debit(quote.quote_amount)?;
+        accounts.taker_balance_a.credit(quote.quote_amount)?;
+        accounts.taker_balance_b.debit(size)?;
+        accounts.maker_balance_b.credit(size)?;
+    }
+    Ok(())
+}
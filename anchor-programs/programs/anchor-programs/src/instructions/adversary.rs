@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use crate::{Agent, Config, Market, OrderParams, Trade, FEATURE_ADVERSARY, FEATURE_ORDER_BOOK, SESSION_SCOPE_PLACE};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+
+#[derive(Accounts)]
+pub struct SetAdversary<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_ADVERSARY) @ ErrorCode::FeatureDisabled
+    )]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct PlaceAdversaryOrder<'info> {
+    #[account(
+      mut,
+      constraint = agent.is_authorized(&user.key(), SESSION_SCOPE_PLACE, Clock::get()?.slot)
+          @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = market.adversary == agent.key() @ ErrorCode::NotAdversary,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_ORDER_BOOK) @ ErrorCode::FeatureDisabled,
+        constraint = config.enabled(FEATURE_ADVERSARY) @ ErrorCode::FeatureDisabled,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Trade::INIT_SPACE,
+        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref(), &order_id.to_le_bytes()],
+        bump
+    )]
+    pub trade: Account<'info, Trade>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Designates `adversary` as the market's adversary agent, or
+/// `Pubkey::default()` for none, with `max_size` capping its scripted orders
+/// in place of the market's own cap.
+pub fn set_adversary(ctx: Context<SetAdversary>, adversary: Pubkey, max_size: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.adversary = adversary;
+    market.adversary_max_size = max_size;
+    Ok(())
+}
+
+/// Places the adversary's next scripted order as its trade `order_id`,
+/// replacing any resting there, which goes to the back of the queue. It skips
+/// the congestion surcharge and is capped by the adversary's size limit
+/// instead of the market's; a session key placing it is still held to its
+/// spend limits for what it adds. It otherwise rests and fills like any order.
+pub fn place_adversary_order(
+    ctx: Context<PlaceAdversaryOrder>,
+    order_id: u64,
+    params: OrderParams,
+) -> Result<()> {
+    params.validate()?;
+    let market = &mut ctx.accounts.market;
+    market.ensure_open()?;
+    let trade = &mut ctx.accounts.trade;
+    // What a resting order on the same side committed is only replaced
+    let old_committed = if trade.size() > 0 {
+        market.open_orders = market.open_orders.saturating_sub(1);
+        if trade.trade_type == params.trade_type { trade.amount_in } else { 0 }
+    } else {
+        0
+    };
+    market.open_orders = market.open_orders.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    let sequence = market.next_sequence()?;
+    // A trade just created has no market yet
+    if trade.market == Pubkey::default() {
+        ctx.accounts.agent.open_trade()?;
+    }
+    trade.open(ctx.accounts.agent.key(), market.key(), order_id, sequence, &params, ctx.bumps.trade);
+    if market.adversary_max_size != 0 && trade.size() > market.adversary_max_size {
+        return err!(ErrorCode::OrderTooLarge);
+    }
+    let epoch = market.current_epoch(market.now()?);
+    ctx.accounts
+        .agent
+        .spend(&ctx.accounts.user.key(), trade.trade_type, trade.amount_in.saturating_sub(old_committed), epoch)?;
+    OrderUpdated::emit(market, trade)
+}
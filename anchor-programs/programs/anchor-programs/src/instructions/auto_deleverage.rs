@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::events::AutoDeleveraged;
+use crate::oracle::read_price;
+use crate::{AdlQueue, Market, Position, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct AutoDeleverage<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"adl", market.key().as_ref()],
+        bump = adl_queue.bump
+    )]
+    pub adl_queue: Account<'info, AdlQueue>,
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), bankrupt.agent.as_ref()],
+        bump = bankrupt.bump,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub bankrupt: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), counterparty.agent.as_ref()],
+        bump = counterparty.bump,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub counterparty: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    pub cranker: Signer<'info>,
+}
+
+/// Closes a bankrupt position the insurance fund cannot cover against the
+/// top-ranked opposing position, at the bankruptcy price: each unit closed
+/// takes its proportional share of the bankrupt position's quote, so what is
+/// left of the bankrupt position stays exactly at zero equity at that price.
+pub fn auto_deleverage(ctx: Context<AutoDeleverage>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let price = read_price(
+        market,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+        Clock::get()?.slot,
+    )?
+    .price;
+
+    let bankrupt = &mut ctx.accounts.bankrupt;
+    let counterparty = &mut ctx.accounts.counterparty;
+    bankrupt.settle(market)?;
+    counterparty.settle(market)?;
+    market.reprice_funding(price)?;
+
+    let equity = bankrupt.equity(market, price)?;
+    if equity >= 0 || bankrupt.base == 0 {
+        return err!(ErrorCode::NotBankrupt);
+    }
+    if equity.unsigned_abs() <= market.insurance_fund {
+        return err!(ErrorCode::InsuranceSufficient);
+    }
+    if ctx.accounts.adl_queue.top_opposing(bankrupt.base) != counterparty.key()
+        || counterparty.base.signum() != -bankrupt.base.signum()
+    {
+        return err!(ErrorCode::NotTopOfAdlQueue);
+    }
+
+    let size = bankrupt.base.unsigned_abs().min(counterparty.base.unsigned_abs());
+    let quote = i64::try_from(
+        bankrupt.quote as i128 * size as i128 / bankrupt.base.unsigned_abs() as i128,
+    )
+    .map_err(|_| ErrorCode::MathOverflow)?;
+    let base_delta = -bankrupt.base.signum() * size as i64;
+
+    // Whatever the bankrupt side pays to close, the counterparty receives
+    let realized = bankrupt.apply_fill(market, base_delta, quote)?;
+    let counter_realized = counterparty.apply_fill(market, -base_delta, -quote)?;
+    // The vault held the other side of both, and realizes what they didn't
+    let realized = realized.checked_add(counter_realized).ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.vault_a.settle_pnl(realized)?;
+
+    let score = counterparty.adl_score(market, price)?;
+    ctx.accounts
+        .adl_queue
+        .rank(counterparty.key(), counterparty.base, score);
+
+    emit!(AutoDeleveraged {
+        market: market.key(),
+        bankrupt_agent: bankrupt.agent,
+        counterparty_agent: counterparty.agent,
+        base: base_delta,
+        quote,
+    });
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Balance, Market, OptionContract, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct BuyOption<'info> {
+    #[account(
+        constraint = buyer_agent.owner == user.key() @ ErrorCode::InvalidAgentOwner,
+        constraint = buyer_agent.key() != option.writer @ ErrorCode::Unauthorized
+    )]
+    pub buyer_agent: Account<'info, Agent>,
+    #[account(address = option.writer @ ErrorCode::Unauthorized)]
+    pub writer_agent: Account<'info, Agent>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        constraint = option.holder == Pubkey::default() @ ErrorCode::OptionAlreadySold
+    )]
+    pub option: Account<'info, OptionContract>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_a.key().as_ref(), buyer_agent.key().as_ref()],
+        bump = buyer_balance_a.bump
+    )]
+    pub buyer_balance_a: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), writer_agent.key().as_ref()],
+        bump
+    )]
+    pub writer_balance_a: Account<'info, Balance>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn buy_option(ctx: Context<BuyOption>) -> Result<()> {
+    if ctx.accounts.market.now()? > ctx.accounts.option.expiry_slot {
+        return err!(ErrorCode::OptionExpired);
+    }
+    let premium = ctx.accounts.option.premium;
+    ctx.accounts.option.holder = ctx.accounts.buyer_agent.key();
+
+    ctx.accounts.buyer_balance_a.debit(premium)?;
+    let writer_balance_a = &mut ctx.accounts.writer_balance_a;
+    writer_balance_a.bind(
+        ctx.accounts.writer_agent.key(),
+        ctx.accounts.vault_a.key(),
+        ctx.bumps.writer_balance_a,
+    );
+    writer_balance_a.credit(premium)
+}
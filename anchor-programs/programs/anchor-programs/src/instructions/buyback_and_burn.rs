@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Burn, Mint, Token, TokenAccount},
+};
+
+use crate::adapters;
+use crate::{Market, Vault};
+use crate::errors::ErrorCode;
+use crate::events::BuybackBurned;
+
+#[derive(Accounts)]
+pub struct BuybackAndBurn<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_a,
+        associated_token::authority = vault_a
+    )]
+    pub vault_token_account_a: Account<'info, TokenAccount>,
+    #[account(mut, address = market.token_b @ ErrorCode::InvalidTokenMint)]
+    pub token_b_mint: Account<'info, Mint>,
+    /// Receives the bought token B for burning, owned by vault A so it can
+    /// sign both sides of the swap
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = token_b_mint,
+        associated_token::authority = vault_a
+    )]
+    pub buyback_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: must be the program of the market's mirror venue
+    #[account(
+        constraint = adapters::program_id(market.mirror_venue)? == venue_program.key()
+            @ ErrorCode::InvalidMirrorVenue
+    )]
+    pub venue_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: the venue's swap accounts, in the venue's order,
+    // with vault A as the swap authority
+}
+
+/// Spends `amount` of the market's accrued token A fees buying token B, the
+/// protocol token on a market that lists it, through the mirrored pool, and
+/// burns what it bought. Fails inside the venue if less than `min_out` comes
+/// back.
+pub fn buyback_and_burn<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuybackAndBurn<'info>>,
+    amount: u64,
+    min_out: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+    let market = &mut ctx.accounts.market;
+    market.fees_accrued = market
+        .fees_accrued
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFees)?;
+    ctx.accounts.vault_a.send(amount)?;
+
+    let (token_a, token_b) = (market.token_a, market.token_b);
+    let vault_a_key = ctx.accounts.vault_a.key();
+    let mut swap = adapters::swap_instruction(
+        market.mirror_venue,
+        &market.mirror_pool,
+        &vault_a_key,
+        &token_a,
+        ctx.remaining_accounts,
+        amount,
+        min_out,
+    )?;
+    for meta in swap.accounts.iter_mut().filter(|meta| meta.pubkey == vault_a_key) {
+        meta.is_signer = true;
+    }
+    let seeds = &[
+        b"vault",
+        token_a.as_ref(),
+        token_b.as_ref(),
+        token_a.as_ref(),
+        &[ctx.accounts.vault_a.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let in_before = ctx.accounts.vault_token_account_a.amount;
+    let out_before = ctx.accounts.buyback_token_account.amount;
+    invoke_signed(&swap, ctx.remaining_accounts, signer_seeds)?;
+    ctx.accounts.vault_token_account_a.reload()?;
+    ctx.accounts.buyback_token_account.reload()?;
+    let spent = in_before.saturating_sub(ctx.accounts.vault_token_account_a.amount);
+    let bought = ctx.accounts.buyback_token_account.amount.saturating_sub(out_before);
+    if spent > amount || bought < min_out {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+    // Whatever the pool didn't take goes back to the fees it came from
+    let unspent = amount - spent;
+    ctx.accounts.vault_a.receive(unspent)?;
+    ctx.accounts.market.accrue_fee(token_a, unspent)?;
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.token_b_mint.to_account_info(),
+        from: ctx.accounts.buyback_token_account.to_account_info(),
+        authority: ctx.accounts.vault_a.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::burn(cpi_ctx, bought)?;
+
+    emit!(BuybackBurned {
+        market: ctx.accounts.market.key(),
+        spent,
+        burned: bought,
+        fees_remaining: ctx.accounts.market.fees_accrued,
+    });
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use super::accept_otc_offer::release_escrow;
+use crate::{Agent, Market, OtcOffer};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CancelOtcOffer<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"otc", market.key().as_ref(), agent.key().as_ref(), offer.offer_id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = user
+    )]
+    pub offer: Account<'info, OtcOffer>,
+    #[account(address = offer.give_mint @ ErrorCode::InvalidTokenMint)]
+    pub give_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = give_mint,
+        associated_token::authority = offer
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = give_mint,
+        token::authority = user
+    )]
+    pub user_give_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn cancel_otc_offer(ctx: Context<CancelOtcOffer>) -> Result<()> {
+    release_escrow(
+        &ctx.accounts.token_program,
+        &ctx.accounts.offer,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.user_give_account,
+        &ctx.accounts.user.to_account_info(),
+    )
+}
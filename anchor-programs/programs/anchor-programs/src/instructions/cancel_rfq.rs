@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Market, Rfq};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CancelRfq<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"rfq", market.key().as_ref(), agent.key().as_ref(), rfq.rfq_id.to_le_bytes().as_ref()],
+        bump = rfq.bump,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = user
+    )]
+    pub rfq: Account<'info, Rfq>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn cancel_rfq(_ctx: Context<CancelRfq>) -> Result<()> {
+    Ok(())
+}
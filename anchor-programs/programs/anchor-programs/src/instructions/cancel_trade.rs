@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Market, Trade, SESSION_SCOPE_CANCEL};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+
+#[derive(Accounts)]
+pub struct CancelTrade<'info> {
+    #[account(
+        mut,
+        constraint = agent.is_authorized(&user.key(), SESSION_SCOPE_CANCEL, Clock::get()?.slot)
+            @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref(), &trade.order_id.to_le_bytes()],
+        bump = trade.bump,
+        has_one = agent @ ErrorCode::InvalidAgent,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = user
+    )]
+    pub trade: Account<'info, Trade>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn cancel_trade(ctx: Context<CancelTrade>) -> Result<()> {
+    ctx.accounts.agent.close_trade();
+    OrderUpdated::emit_removed(&mut ctx.accounts.market, &mut ctx.accounts.trade)
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{Agent, EmissionsAccount, EmissionsSchedule, Market};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ClaimEmissions<'info> {
+    #[account(address = schedule.market @ ErrorCode::InvalidMarket)]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"emissions", market.key().as_ref(), schedule.reward_mint.as_ref()],
+        bump = schedule.bump
+    )]
+    pub schedule: Account<'info, EmissionsSchedule>,
+    #[account(
+        mut,
+        seeds = [b"emissions_account", schedule.key().as_ref(), agent.key().as_ref()],
+        bump = emissions_account.bump
+    )]
+    pub emissions_account: Account<'info, EmissionsAccount>,
+    #[account(constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner)]
+    pub agent: Account<'info, Agent>,
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint = schedule.reward_mint,
+        associated_token::authority = schedule
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = schedule.reward_mint,
+        token::authority = user
+    )]
+    pub user_reward_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays the agent's owner what the agent earned under the schedule in
+/// finished epochs. The current epoch's volume is paid once it ends.
+pub fn claim_emissions(ctx: Context<ClaimEmissions>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let epoch = market.current_epoch(market.now()?);
+    let schedule = &ctx.accounts.schedule;
+    let emissions_account = &mut ctx.accounts.emissions_account;
+    schedule.settle(emissions_account, epoch, market.epoch_slots.max(1))?;
+    let amount = std::mem::take(&mut emissions_account.unclaimed);
+    if amount == 0 {
+        return err!(ErrorCode::NothingToClaim);
+    }
+
+    let seeds = &[
+        b"emissions",
+        schedule.market.as_ref(),
+        schedule.reward_mint.as_ref(),
+        &[schedule.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.reward_vault.to_account_info(),
+        to: ctx.accounts.user_reward_account.to_account_info(),
+        authority: schedule.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::{Agent, Balance, Market, ScoreClaim, ScoreRoot, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ClaimScoredReward<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub score_root: Account<'info, ScoreRoot>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + ScoreClaim::INIT_SPACE,
+        seeds = [b"score_claim", score_root.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub score_claim: Account<'info, ScoreClaim>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub balance_a: Account<'info, Balance>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Leaf committing to `agent`'s reward. Leaves and inner nodes hash under
+/// different prefixes so a node can never pass as a leaf.
+pub fn score_leaf(agent: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[&[0], agent.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+/// Folds `proof` into `leaf`, hashing each pair in sorted order.
+fn merkle_root(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling {
+            (node, *sibling)
+        } else {
+            (*sibling, node)
+        };
+        hashv(&[&[1], &left, &right]).to_bytes()
+    })
+}
+
+/// Credits the agent's tracked token A with the reward its score leaf
+/// assigns, once per published root.
+pub fn claim_scored_reward(
+    ctx: Context<ClaimScoredReward>,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let agent_key = ctx.accounts.agent.key();
+    let score_root = &mut ctx.accounts.score_root;
+    if merkle_root(score_leaf(&agent_key, amount), &proof) != score_root.root {
+        return err!(ErrorCode::InvalidMerkleProof);
+    }
+    score_root.claimed = score_root
+        .claimed
+        .checked_add(amount)
+        .filter(|claimed| *claimed <= score_root.total_rewards)
+        .ok_or(ErrorCode::RewardsExhausted)?;
+
+    let score_claim = &mut ctx.accounts.score_claim;
+    score_claim.score_root = score_root.key();
+    score_claim.agent = agent_key;
+    score_claim.amount = amount;
+    score_claim.bump = ctx.bumps.score_claim;
+
+    let balance_a = &mut ctx.accounts.balance_a;
+    balance_a.bind(agent_key, ctx.accounts.vault_a.key(), ctx.bumps.balance_a);
+    balance_a.credit(amount)
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::{Agent, Balance, Bond, EmissionsSchedule, Market, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ClaimStakingFees<'info> {
+    #[account(
+        address = schedule.market @ ErrorCode::InvalidMarket,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub schedule: Account<'info, EmissionsSchedule>,
+    #[account(
+        mut,
+        seeds = [b"bond", schedule.key().as_ref(), agent.key().as_ref()],
+        bump = bond.bump
+    )]
+    pub bond: Account<'info, Bond>,
+    #[account(constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub balance_a: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_b.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub balance_b: Account<'info, Balance>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Credits the agent's tracked balances with the market fees its bond has
+/// earned.
+pub fn claim_staking_fees(ctx: Context<ClaimStakingFees>) -> Result<()> {
+    let bond = &mut ctx.accounts.bond;
+    bond.settle_fees(&ctx.accounts.schedule)?;
+    let amount_a = std::mem::take(&mut bond.fees_owed_a);
+    let amount_b = std::mem::take(&mut bond.fees_owed_b);
+    if amount_a == 0 && amount_b == 0 {
+        return err!(ErrorCode::NothingToClaim);
+    }
+
+    let agent_key = ctx.accounts.agent.key();
+    let balance_a = &mut ctx.accounts.balance_a;
+    balance_a.bind(agent_key, ctx.accounts.vault_a.key(), ctx.bumps.balance_a);
+    balance_a.credit(amount_a)?;
+    let balance_b = &mut ctx.accounts.balance_b;
+    balance_b.bind(agent_key, ctx.accounts.vault_b.key(), ctx.bumps.balance_b);
+    balance_b.credit(amount_b)
+}
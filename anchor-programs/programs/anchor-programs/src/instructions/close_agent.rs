@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::Agent;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CloseAgent<'info> {
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner,
+        close = user
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Closes an agent, or a sub-account, returning its rent to the owner.
+/// Accounts keyed by the agent are not checked: they should be withdrawn
+/// and closed first, and registering the same agent again reaches any that
+/// weren't.
+pub fn close_agent(_ctx: Context<CloseAgent>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Balance};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CloseBalance<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [b"balance", balance.vault.as_ref(), agent.key().as_ref()],
+        bump = balance.bump,
+        constraint = balance.amount == 0 && balance.reserved == 0 @ ErrorCode::BalanceNotEmpty,
+        close = user
+    )]
+    pub balance: Account<'info, Balance>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Closes an agent's empty balance; depositing again recreates it.
+pub fn close_balance(_ctx: Context<CloseBalance>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Trade};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CloseFilledTrade<'info> {
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        has_one = agent @ ErrorCode::InvalidAgent,
+        constraint = trade.size() == 0 @ ErrorCode::TradeNotFilled,
+        close = owner
+    )]
+    pub trade: Account<'info, Trade>,
+    /// CHECK: receives the trade's rent
+    #[account(mut, address = agent.owner @ ErrorCode::InvalidAgentOwner)]
+    pub owner: UncheckedAccount<'info>,
+}
+
+/// Closes a fully filled trade, returning its rent to the agent's owner so
+/// the agent can place on the market again. Anyone can close.
+pub fn close_filled_trade(ctx: Context<CloseFilledTrade>) -> Result<()> {
+    ctx.accounts.agent.close_trade();
+    Ok(())
+}
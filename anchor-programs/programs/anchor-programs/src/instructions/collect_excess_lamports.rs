@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::Market;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CollectExcessLamports<'info> {
+    #[account(has_one = authority @ ErrorCode::Unauthorized)]
+    pub market: Account<'info, Market>,
+    /// CHECK: any account this program owns; its data is left untouched and
+    /// only lamports above its rent-exempt minimum are moved.
+    #[account(mut, owner = crate::ID @ ErrorCode::NotProgramAccount)]
+    pub account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Moves lamports sent to a program account beyond its rent-exempt minimum
+/// to the market authority. Program accounts never hold lamports as value,
+/// so the excess is unowned.
+pub fn collect_excess_lamports(ctx: Context<CollectExcessLamports>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    let minimum = Rent::get()?.minimum_balance(account.data_len());
+    let excess = account.lamports().saturating_sub(minimum);
+    if excess > 0 {
+        account.sub_lamports(excess)?;
+        ctx.accounts.authority.add_lamports(excess)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::BinaryMarket;
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CreateBinaryMarket<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + BinaryMarket::INIT_SPACE,
+        seeds = [b"binary_market", collateral_mint.key().as_ref(), market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub binary_market: Account<'info, BinaryMarket>,
+    pub collateral_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"yes", binary_market.key().as_ref()],
+        bump,
+        mint::decimals = collateral_mint.decimals,
+        mint::authority = binary_market
+    )]
+    pub yes_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"no", binary_market.key().as_ref()],
+        bump,
+        mint::decimals = collateral_mint.decimals,
+        mint::authority = binary_market
+    )]
+    pub no_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = binary_market
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_binary_market(
+    ctx: Context<CreateBinaryMarket>,
+    market_id: u64,
+    oracle: Pubkey,
+) -> Result<()> {
+    let binary_market = &mut ctx.accounts.binary_market;
+    binary_market.authority = ctx.accounts.signer.key();
+    binary_market.oracle = oracle;
+    binary_market.market_id = market_id;
+    binary_market.collateral_mint = ctx.accounts.collateral_mint.key();
+    binary_market.yes_mint = ctx.accounts.yes_mint.key();
+    binary_market.no_mint = ctx.accounts.no_mint.key();
+    binary_market.bump = ctx.bumps.binary_market;
+    Ok(())
+}
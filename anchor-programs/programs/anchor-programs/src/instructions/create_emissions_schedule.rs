@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{EmissionsSchedule, Market, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CreateEmissionsSchedule<'info> {
+    #[account(has_one = authority @ ErrorCode::Unauthorized)]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EmissionsSchedule::INIT_SPACE,
+        seeds = [b"emissions", market.key().as_ref(), reward_mint.key().as_ref()],
+        bump
+    )]
+    pub schedule: Account<'info, EmissionsSchedule>,
+    pub reward_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = reward_mint,
+        associated_token::authority = schedule
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = authority
+    )]
+    pub authority_reward_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Starts emitting `rate` reward tokens per unit of market time over
+/// [`start`, `end`), depositing the whole window's rewards up front so every
+/// claim is funded.
+pub fn create_emissions_schedule(
+    ctx: Context<CreateEmissionsSchedule>,
+    rate: u64,
+    start: u64,
+    end: u64,
+    maker_share_bps: u16,
+) -> Result<()> {
+    if rate == 0 || start >= end || maker_share_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidEmissionsSchedule);
+    }
+    let total = (end - start).checked_mul(rate).ok_or(ErrorCode::MathOverflow)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.authority_reward_account.to_account_info(),
+        to: ctx.accounts.reward_vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, total)?;
+
+    let schedule = &mut ctx.accounts.schedule;
+    schedule.market = ctx.accounts.market.key();
+    schedule.authority = ctx.accounts.authority.key();
+    schedule.reward_mint = ctx.accounts.reward_mint.key();
+    schedule.rate = rate;
+    schedule.start = start;
+    schedule.end = end;
+    schedule.maker_share_bps = maker_share_bps;
+    schedule.bump = ctx.bumps.schedule;
+    Ok(())
+}
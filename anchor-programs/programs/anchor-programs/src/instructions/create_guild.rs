@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Guild, Market, SESSION_SCOPES_ALL};
+
+#[derive(Accounts)]
+pub struct CreateGuild<'info> {
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Guild::INIT_SPACE,
+        seeds = [b"guild", market.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub guild: Account<'info, Guild>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Agent::INIT_SPACE,
+        seeds = [b"guild_agent", guild.key().as_ref()],
+        bump
+    )]
+    pub guild_agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a guild and its agent on `market`, with `manager` trading it.
+pub fn create_guild(ctx: Context<CreateGuild>, manager: Pubkey) -> Result<()> {
+    let guild = &mut ctx.accounts.guild;
+    guild.market = ctx.accounts.market.key();
+    guild.authority = ctx.accounts.authority.key();
+    guild.agent = ctx.accounts.guild_agent.key();
+    guild.manager = manager;
+    guild.bump = ctx.bumps.guild;
+
+    let guild_agent = &mut ctx.accounts.guild_agent;
+    guild_agent.owner = guild.key();
+    guild_agent.start_session(manager, u64::MAX, SESSION_SCOPES_ALL, 0)?;
+    guild_agent.bump = ctx.bumps.guild_agent;
+    Ok(())
+}
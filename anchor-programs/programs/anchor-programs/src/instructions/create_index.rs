@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{IndexBasket, IndexComponent, INDEX_DECIMALS, MAX_INDEX_COMPONENTS};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(index_id: u64)]
+pub struct CreateIndex<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + IndexBasket::INIT_SPACE,
+        seeds = [b"index", index_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub index: Account<'info, IndexBasket>,
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"index_mint", index.key().as_ref()],
+        bump,
+        mint::decimals = INDEX_DECIMALS,
+        mint::authority = index
+    )]
+    pub basket_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_index(
+    ctx: Context<CreateIndex>,
+    index_id: u64,
+    components: Vec<IndexComponent>,
+) -> Result<()> {
+    if components.is_empty() || components.len() > MAX_INDEX_COMPONENTS {
+        return err!(ErrorCode::InvalidIndexComponents);
+    }
+    for (i, component) in components.iter().enumerate() {
+        let duplicate = components[..i].iter().any(|other| other.mint == component.mint);
+        if component.units == 0 || duplicate {
+            return err!(ErrorCode::InvalidIndexComponents);
+        }
+    }
+
+    let index = &mut ctx.accounts.index;
+    index.authority = ctx.accounts.signer.key();
+    index.index_id = index_id;
+    index.basket_mint = ctx.accounts.basket_mint.key();
+    index.components[..components.len()].copy_from_slice(&components);
+    index.component_count = components.len() as u8;
+    index.bump = ctx.bumps.index;
+    Ok(())
+}
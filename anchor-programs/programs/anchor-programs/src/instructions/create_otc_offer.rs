@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{Agent, Market, OtcOffer};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(offer_id: u64)]
+pub struct CreateOtcOffer<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + OtcOffer::INIT_SPACE,
+        seeds = [b"otc", market.key().as_ref(), agent.key().as_ref(), offer_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, OtcOffer>,
+    pub give_mint: Account<'info, Mint>,
+    pub want_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = give_mint,
+        token::authority = user
+    )]
+    pub user_give_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = give_mint,
+        associated_token::authority = offer
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_otc_offer(
+    ctx: Context<CreateOtcOffer>,
+    offer_id: u64,
+    give_amount: u64,
+    want_amount: u64,
+    counterparty: Pubkey,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let give_mint = ctx.accounts.give_mint.key();
+    let want_mint = ctx.accounts.want_mint.key();
+    let is_pair = (give_mint == market.token_a && want_mint == market.token_b)
+        || (give_mint == market.token_b && want_mint == market.token_a);
+    if !is_pair {
+        return err!(ErrorCode::InvalidTokenMint);
+    }
+    if give_amount == 0 || want_amount == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+
+    let offer = &mut ctx.accounts.offer;
+    offer.maker = ctx.accounts.agent.key();
+    offer.market = market.key();
+    offer.offer_id = offer_id;
+    offer.give_mint = give_mint;
+    offer.give_amount = give_amount;
+    offer.want_mint = want_mint;
+    offer.want_amount = want_amount;
+    offer.counterparty = counterparty;
+    offer.bump = ctx.bumps.offer;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_give_account.to_account_info(),
+        to: ctx.accounts.escrow_token_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, give_amount)?;
+    Ok(())
+}
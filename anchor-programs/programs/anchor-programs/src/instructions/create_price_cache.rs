@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::{Market, PriceCache};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CreatePriceCache<'info> {
+    #[account(
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceCache::INIT_SPACE,
+        seeds = [b"price_cache", market.key().as_ref()],
+        bump
+    )]
+    pub price_cache: Account<'info, PriceCache>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_price_cache(ctx: Context<CreatePriceCache>, feed_id: [u8; 32]) -> Result<()> {
+    let price_cache = &mut ctx.accounts.price_cache;
+    price_cache.market = ctx.accounts.market.key();
+    price_cache.feed_id = feed_id;
+    price_cache.bump = ctx.bumps.price_cache;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Market, Rfq};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(rfq_id: u64)]
+pub struct CreateRfq<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Rfq::INIT_SPACE,
+        seeds = [b"rfq", market.key().as_ref(), agent.key().as_ref(), rfq_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub rfq: Account<'info, Rfq>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_rfq(
+    ctx: Context<CreateRfq>,
+    rfq_id: u64,
+    side: u8,
+    size: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    if side > 1 {
+        return err!(ErrorCode::InvalidTradeType);
+    }
+    if size == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+    if expiry_slot <= ctx.accounts.market.now()? {
+        return err!(ErrorCode::RfqExpired);
+    }
+    let rfq = &mut ctx.accounts.rfq;
+    rfq.taker = ctx.accounts.agent.key();
+    rfq.market = ctx.accounts.market.key();
+    rfq.rfq_id = rfq_id;
+    rfq.side = side;
+    rfq.size = size;
+    rfq.expiry_slot = expiry_slot;
+    rfq.bump = ctx.bumps.rfq;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{Agent, Market, StrategyVault, BPS_DENOMINATOR, PRICE_SCALE, SESSION_SCOPES_ALL};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CreateStrategyVault<'info> {
+    #[account(
+        constraint = manager_agent.owner == manager.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub manager_agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + StrategyVault::INIT_SPACE,
+        seeds = [b"strategy_vault", market.key().as_ref(), manager_agent.key().as_ref()],
+        bump
+    )]
+    pub strategy_vault: Account<'info, StrategyVault>,
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + Agent::INIT_SPACE,
+        seeds = [b"strategy_agent", strategy_vault.key().as_ref()],
+        bump
+    )]
+    pub strategy_agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = manager,
+        seeds = [b"strategy_shares", strategy_vault.key().as_ref()],
+        bump,
+        mint::decimals = market.decimals_a,
+        mint::authority = strategy_vault
+    )]
+    pub share_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = manager,
+        associated_token::mint = share_mint,
+        associated_token::authority = manager
+    )]
+    pub manager_share_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a strategy vault on `market` managed by `manager_agent`'s owner.
+pub fn create_strategy_vault(
+    ctx: Context<CreateStrategyVault>,
+    management_fee_bps: u16,
+    performance_fee_bps: u16,
+) -> Result<()> {
+    if management_fee_bps as u64 > BPS_DENOMINATOR || performance_fee_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidStrategyFee);
+    }
+    let manager = ctx.accounts.manager.key();
+    let strategy_vault = &mut ctx.accounts.strategy_vault;
+    strategy_vault.market = ctx.accounts.market.key();
+    strategy_vault.manager_agent = ctx.accounts.manager_agent.key();
+    strategy_vault.manager = manager;
+    strategy_vault.agent = ctx.accounts.strategy_agent.key();
+    strategy_vault.share_mint = ctx.accounts.share_mint.key();
+    strategy_vault.management_fee_bps = management_fee_bps;
+    strategy_vault.performance_fee_bps = performance_fee_bps;
+    strategy_vault.high_water_mark = PRICE_SCALE;
+    let now = ctx.accounts.market.now()?;
+    strategy_vault.fee_epoch = ctx.accounts.market.current_epoch(now);
+    strategy_vault.fee_slot = now;
+    strategy_vault.bump = ctx.bumps.strategy_vault;
+
+    let strategy_agent = &mut ctx.accounts.strategy_agent;
+    strategy_agent.owner = strategy_vault.key();
+    strategy_agent.start_session(manager, u64::MAX, SESSION_SCOPES_ALL, 0)?;
+    strategy_agent.bump = ctx.bumps.strategy_agent;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::Agent;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct CreateSubaccount<'info> {
+    #[account(
+        seeds = [b"agent", agent.registrant.as_ref()],
+        bump = agent.bump,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Agent::INIT_SPACE,
+        seeds = [b"subaccount", agent.key().as_ref(), &index.to_le_bytes()],
+        bump
+    )]
+    pub subaccount: Account<'info, Agent>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Sub-accounts are full agents owned by the same wallet, so balances and
+/// trades keyed by agent stay isolated between strategies.
+pub fn create_subaccount(ctx: Context<CreateSubaccount>, index: u16) -> Result<()> {
+    let subaccount = &mut ctx.accounts.subaccount;
+    subaccount.owner = ctx.accounts.user.key();
+    subaccount.parent = ctx.accounts.agent.key();
+    subaccount.subaccount_index = index;
+    subaccount.stp_mode = ctx.accounts.agent.stp_mode;
+    subaccount.bump = ctx.bumps.subaccount;
+    Ok(())
+}
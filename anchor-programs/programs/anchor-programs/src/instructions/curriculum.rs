@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Curriculum, CurriculumStage, Market, Trade, BPS_DENOMINATOR, CURRICULUM_MAKER_FEE, CURRICULUM_TAKER_FEE,
+    CURRICULUM_VOLATILITY_SHOCK, CURRICULUM_WITHDRAW_LIQUIDITY, MAX_CURRICULUM_STAGES,
+};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+use crate::instructions::uncross_auction::check_book;
+use crate::math::mul_div_ceil;
+
+#[derive(Accounts)]
+pub struct CreateCurriculum<'info> {
+    #[account(has_one = authority @ ErrorCode::Unauthorized)]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Curriculum::INIT_SPACE,
+        seeds = [b"curriculum", market.key().as_ref()],
+        bump
+    )]
+    pub curriculum: Account<'info, Curriculum>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceCurriculum<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"curriculum", market.key().as_ref()],
+        bump = curriculum.bump,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub curriculum: Account<'info, Curriculum>,
+    // remaining_accounts: every order resting on the market, when a stage
+    // withdrawing liquidity is due
+}
+
+/// Creates the market's curriculum from `stages` in epoch order. The fee
+/// changes are checked in sequence against the market's fee schedule as it
+/// stands, so a curriculum can't be created that would later fail to apply.
+pub fn create_curriculum(ctx: Context<CreateCurriculum>, stages: Vec<CurriculumStage>) -> Result<()> {
+    if stages.len() > MAX_CURRICULUM_STAGES || stages.windows(2).any(|pair| pair[0].epoch > pair[1].epoch) {
+        return err!(ErrorCode::InvalidCurriculum);
+    }
+    let mut preview = Market::clone(&ctx.accounts.market);
+    for stage in &stages {
+        apply(&mut preview, stage)?;
+        preview.validate_fees()?;
+    }
+    let curriculum = &mut ctx.accounts.curriculum;
+    curriculum.market = ctx.accounts.market.key();
+    curriculum.stages[..stages.len()].copy_from_slice(&stages);
+    curriculum.stage_count = stages.len() as u8;
+    curriculum.bump = ctx.bumps.curriculum;
+    Ok(())
+}
+
+/// Applies every stage whose epoch the market has reached, in order. Anyone
+/// can crank it. Withdrawing liquidity needs every resting order passed, so
+/// none escape it, and shrinks each in place without losing its queue spot.
+pub fn advance_curriculum<'info>(ctx: Context<'_, '_, 'info, 'info, AdvanceCurriculum<'info>>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let curriculum = &mut ctx.accounts.curriculum;
+    let epoch = market.current_epoch(market.now()?);
+    let due = curriculum.stages[curriculum.next_stage as usize..curriculum.stage_count as usize]
+        .iter()
+        .take_while(|stage| stage.epoch <= epoch)
+        .count();
+    if due == 0 {
+        return err!(ErrorCode::CurriculumNotDue);
+    }
+
+    let mut withdrawals = Vec::new();
+    for stage in &curriculum.stages[curriculum.next_stage as usize..][..due] {
+        match apply(market, stage)? {
+            0 => {}
+            bps => withdrawals.push(bps),
+        }
+    }
+    market.validate_fees()?;
+    curriculum.next_stage += due as u8;
+    if withdrawals.is_empty() {
+        return Ok(());
+    }
+
+    let mut orders = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts {
+        orders.push(Account::<Trade>::try_from(info)?);
+    }
+    check_book(market, &orders)?;
+    for order in orders.iter_mut().filter(|order| order.size() > 0) {
+        for &bps in &withdrawals {
+            let size = order.size();
+            order.reduce(mul_div_ceil(size, bps, BPS_DENOMINATOR)?)?;
+        }
+        OrderUpdated::emit(market, order)?;
+        order.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+/// Makes `stage`'s change to `market`, returning the bps of resting
+/// liquidity it withdraws, which the book has to be passed for.
+fn apply(market: &mut Market, stage: &CurriculumStage) -> Result<u64> {
+    let invalid = |_| ErrorCode::InvalidCurriculum;
+    match stage.kind {
+        CURRICULUM_VOLATILITY_SHOCK => market.volatility_bps = u32::try_from(stage.value).map_err(invalid)?,
+        CURRICULUM_TAKER_FEE => market.taker_fee_bps = u16::try_from(stage.value).map_err(invalid)?,
+        CURRICULUM_MAKER_FEE => market.maker_fee_bps = i16::try_from(stage.value).map_err(invalid)?,
+        CURRICULUM_WITHDRAW_LIQUIDITY => {
+            let bps = u64::try_from(stage.value).map_err(invalid)?;
+            if bps > BPS_DENOMINATOR {
+                return err!(ErrorCode::InvalidCurriculum);
+            }
+            return Ok(bps);
+        }
+        _ => return err!(ErrorCode::InvalidCurriculum),
+    }
+    Ok(0)
+}
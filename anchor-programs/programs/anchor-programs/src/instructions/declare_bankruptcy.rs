@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::events::BankruptcyDeclared;
+use crate::oracle::read_price;
+use crate::{Market, Position, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct DeclareBankruptcy<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), position.agent.as_ref()],
+        bump = position.bump,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    pub cranker: Signer<'info>,
+}
+
+/// Closes a position whose equity is negative and clears its deficit: the
+/// insurance fund pays first, then the opposite side's open interest is
+/// haircut pro-rata through its loss index, charged as each of those
+/// positions next settles. Whatever is left over with no opposite side is
+/// recorded as bad debt. The vault's own liquidity takes the other side of
+/// the PnL the close realizes.
+pub fn declare_bankruptcy(ctx: Context<DeclareBankruptcy>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let price = read_price(
+        market,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+        Clock::get()?.slot,
+    )?
+    .price;
+
+    let position = &mut ctx.accounts.position;
+    position.settle(market)?;
+    market.reprice_funding(price)?;
+    let equity = position.equity(market, price)?;
+    if equity >= 0 {
+        return err!(ErrorCode::NotBankrupt);
+    }
+
+    let closed = position.base;
+    let close_cost = market.base_value(-closed, price, true)?;
+    let realized = position.apply_fill(market, -closed, close_cost)?;
+    ctx.accounts.vault_a.settle_pnl(realized)?;
+    position.quote = 0;
+
+    let deficit = equity.unsigned_abs();
+    let insurance_drawn = deficit.min(market.insurance_fund);
+    market.insurance_fund -= insurance_drawn;
+    let bad_debt = market.socialize_loss(closed, deficit - insurance_drawn)?;
+    let socialized = deficit - insurance_drawn - bad_debt;
+    market.bad_debt = market.bad_debt.checked_add(bad_debt).ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(BankruptcyDeclared {
+        market: market.key(),
+        agent: position.agent,
+        base: closed,
+        price,
+        deficit,
+        insurance_drawn,
+        socialized,
+        bad_debt,
+    });
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Token, TokenAccount, Transfer},
+};
+
+use crate::oracle::read_price;
+use crate::{Agent, Balance, Guild, GuildMember, Market, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct DepositToGuild<'info> {
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub guild: Account<'info, Guild>,
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + GuildMember::INIT_SPACE,
+        seeds = [b"guild_member", guild.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub guild_member: Account<'info, GuildMember>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        address = guild.agent @ ErrorCode::InvalidAgentOwner
+    )]
+    pub guild_agent: Account<'info, Agent>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_a,
+        associated_token::authority = vault_a
+    )]
+    pub vault_token_account_a: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), guild_agent.key().as_ref()],
+        bump
+    )]
+    pub guild_balance_a: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_b.key().as_ref(), guild_agent.key().as_ref()],
+        bump
+    )]
+    pub guild_balance_b: Account<'info, Balance>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = market.token_a,
+        token::authority = member
+    )]
+    pub member_token_account_a: Account<'info, TokenAccount>,
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Token A value of an agent's two balances, token B valued at the oracle
+/// price and rounded down. The oracle is only read when token B is held.
+pub(crate) fn net_asset_value<'info>(
+    market: &Market,
+    balance_a: &Balance,
+    balance_b: &Balance,
+    oracle: Option<&AccountInfo<'info>>,
+    fallback_oracle: Option<&AccountInfo<'info>>,
+) -> Result<u64> {
+    if balance_b.amount == 0 {
+        return Ok(balance_a.amount);
+    }
+    let price = read_price(market, oracle, fallback_oracle, Clock::get()?.slot)?.price;
+    let base = i64::try_from(balance_b.amount).map_err(|_| ErrorCode::MathOverflow)?;
+    let value_b = market.base_value(base, price, false)?;
+    balance_a
+        .amount
+        .checked_add(value_b as u64)
+        .ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// Adds `amount` token A to the guild agent's balance for shares priced at
+/// the guild's net asset value, its token B valued at the oracle price.
+pub fn deposit_to_guild(ctx: Context<DepositToGuild>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let guild_agent = ctx.accounts.guild_agent.key();
+    let balance_a = &mut ctx.accounts.guild_balance_a;
+    balance_a.bind(guild_agent, ctx.accounts.vault_a.key(), ctx.bumps.guild_balance_a);
+    let balance_b = &mut ctx.accounts.guild_balance_b;
+    balance_b.bind(guild_agent, ctx.accounts.vault_b.key(), ctx.bumps.guild_balance_b);
+
+    let nav = net_asset_value(
+        market,
+        balance_a,
+        balance_b,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+    )?;
+    let guild = &mut ctx.accounts.guild;
+    let shares = guild.shares_for(amount, nav)?;
+    if shares == 0 {
+        return err!(ErrorCode::InvalidShareAmount);
+    }
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.member_token_account_a.to_account_info(),
+        to: ctx.accounts.vault_token_account_a.to_account_info(),
+        authority: ctx.accounts.member.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+    ctx.accounts.vault_a.receive(amount)?;
+    ctx.accounts.guild_balance_a.credit(amount)?;
+
+    guild.total_shares = guild.total_shares.checked_add(shares).ok_or(ErrorCode::MathOverflow)?;
+    let guild_member = &mut ctx.accounts.guild_member;
+    guild_member.guild = guild.key();
+    guild_member.member = ctx.accounts.member.key();
+    guild_member.shares = guild_member.shares.checked_add(shares).ok_or(ErrorCode::MathOverflow)?;
+    guild_member.bump = ctx.bumps.guild_member;
+    Ok(())
+}
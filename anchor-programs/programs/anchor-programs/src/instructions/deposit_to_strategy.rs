@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount, Transfer},
+};
+
+use crate::instructions::net_asset_value;
+use crate::{clock, fees};
+use crate::{Balance, Market, StrategyVault, Vault};
+use crate::errors::ErrorCode;
+use crate::math::mul_div_floor;
+
+#[derive(Accounts)]
+pub struct DepositToStrategy<'info> {
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        has_one = share_mint @ ErrorCode::InvalidTokenMint
+    )]
+    pub strategy_vault: Account<'info, StrategyVault>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(mut)]
+    pub share_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = share_mint,
+        associated_token::authority = depositor
+    )]
+    pub depositor_share_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = strategy_vault.manager
+    )]
+    pub manager_share_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_a,
+        associated_token::authority = vault_a
+    )]
+    pub vault_token_account_a: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), strategy_vault.agent.as_ref()],
+        bump
+    )]
+    pub strategy_balance_a: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_b.key().as_ref(), strategy_vault.agent.as_ref()],
+        bump
+    )]
+    pub strategy_balance_b: Account<'info, Balance>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = market.token_a,
+        token::authority = depositor
+    )]
+    pub depositor_token_account_a: Account<'info, TokenAccount>,
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposits `amount` token A into the strategy for share tokens priced at
+/// NAV per share, after crystallizing any fees due this epoch and fixing the
+/// epoch's redemption settlement.
+pub fn deposit_to_strategy(ctx: Context<DepositToStrategy>, amount: u64) -> Result<()> {
+    let agent = ctx.accounts.strategy_vault.agent;
+    let balance_a = &mut ctx.accounts.strategy_balance_a;
+    balance_a.bind(agent, ctx.accounts.vault_a.key(), ctx.bumps.strategy_balance_a);
+    let balance_b = &mut ctx.accounts.strategy_balance_b;
+    balance_b.bind(agent, ctx.accounts.vault_b.key(), ctx.bumps.strategy_balance_b);
+    let nav = net_asset_value(
+        &ctx.accounts.market,
+        balance_a,
+        balance_b,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+    )?;
+
+    let supply = ctx.accounts.share_mint.supply;
+    let fee_shares =
+        crystallize_fees(&mut ctx.accounts.strategy_vault, &ctx.accounts.market, nav, supply)?;
+    let supply = supply.checked_add(fee_shares).ok_or(ErrorCode::MathOverflow)?;
+    // Redemptions due this epoch are paid from the holdings before the deposit
+    let epoch = ctx.accounts.market.current_epoch(ctx.accounts.market.now()?);
+    ctx.accounts.strategy_vault.fix_settlement(
+        epoch,
+        supply,
+        ctx.accounts.strategy_balance_a.amount,
+        ctx.accounts.strategy_balance_b.amount,
+    );
+    let shares = if supply == 0 || nav == 0 {
+        amount
+    } else {
+        mul_div_floor(amount, supply, nav)?
+    };
+    if shares == 0 {
+        return err!(ErrorCode::InvalidShareAmount);
+    }
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.depositor_token_account_a.to_account_info(),
+        to: ctx.accounts.vault_token_account_a.to_account_info(),
+        authority: ctx.accounts.depositor.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+    ctx.accounts.vault_a.receive(amount)?;
+    ctx.accounts.strategy_balance_a.credit(amount)?;
+
+    let strategy_vault = &ctx.accounts.strategy_vault;
+    mint_shares(
+        strategy_vault,
+        &ctx.accounts.share_mint,
+        &ctx.accounts.manager_share_account,
+        &ctx.accounts.token_program,
+        fee_shares,
+    )?;
+    mint_shares(
+        strategy_vault,
+        &ctx.accounts.share_mint,
+        &ctx.accounts.depositor_share_account,
+        &ctx.accounts.token_program,
+        shares,
+    )
+}
+
+/// Crystallizes the management and performance fees owed at `nav` if this
+/// is the first chance in a new epoch, returning the shares to mint the
+/// manager. Within an epoch fees keep accruing uncharged.
+pub(crate) fn crystallize_fees(
+    strategy_vault: &mut StrategyVault,
+    market: &Market,
+    nav: u64,
+    supply: u64,
+) -> Result<u64> {
+    let now = market.now()?;
+    let epoch = market.current_epoch(now);
+    if epoch <= strategy_vault.fee_epoch {
+        return Ok(0);
+    }
+    let accrual = fees::crystallize(
+        nav,
+        supply,
+        strategy_vault.high_water_mark,
+        strategy_vault.management_fee_bps,
+        strategy_vault.performance_fee_bps,
+        clock::to_slots(market.time_base, now.saturating_sub(strategy_vault.fee_slot)),
+    )?;
+    strategy_vault.high_water_mark = accrual.high_water_mark;
+    strategy_vault.fee_epoch = epoch;
+    strategy_vault.fee_slot = now;
+    Ok(accrual.shares())
+}
+
+pub(crate) fn mint_shares<'info>(
+    strategy_vault: &Account<'info, StrategyVault>,
+    share_mint: &Account<'info, Mint>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    shares: u64,
+) -> Result<()> {
+    if shares == 0 {
+        return Ok(());
+    }
+    let seeds = &[
+        b"strategy_vault",
+        strategy_vault.market.as_ref(),
+        strategy_vault.manager_agent.as_ref(),
+        &[strategy_vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = MintTo {
+        mint: share_mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: strategy_vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::mint_to(cpi_ctx, shares)
+}
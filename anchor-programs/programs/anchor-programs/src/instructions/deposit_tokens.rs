@@ -3,15 +3,16 @@ use anchor_spl::{
     token::{self, Mint, Token, TokenAccount, Transfer},
 };
 
-use crate::{Agent, Market, Vault, errors::ErrorCode};
+use crate::{Agent, Balance, DepositReceipt, Market, Vault, errors::ErrorCode, invariants::strict_check};
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
+#[instruction(amount: u64, nonce: Option<u64>)]
 pub struct DepositTokens<'info> {
     #[account(
         constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
     )]
     pub agent: Account<'info, Agent>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
     pub market: Account<'info, Market>,
     #[account(mut)]
     pub user: Signer<'info>,
@@ -26,6 +27,7 @@ pub struct DepositTokens<'info> {
     pub user_token_account: Account<'info, TokenAccount>,
 
     #[account(
+        mut,
         seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), token_mint.key().as_ref()],
         bump = vault.bump,
         has_one = market @ ErrorCode::InvalidVault
@@ -39,11 +41,52 @@ pub struct DepositTokens<'info> {
         constraint = vault_token_account.owner == vault.key() @ ErrorCode::InvalidVault,
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub balance: Account<'info, Balance>,
+
+    /// Required with a nonce, whose first use creates it
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DepositReceipt::INIT_SPACE,
+        seeds = [b"deposit_receipt", agent.key().as_ref(), nonce.unwrap_or_default().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub receipt: Option<Account<'info, DepositReceipt>>,
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64) -> Result<()> {
-   
+/// Moves `amount` of the user's tokens into the vault and credits the agent.
+/// With a `nonce`, only the first deposit carrying it is credited; retries
+/// of it succeed without moving anything, and fail if made to another vault.
+pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64, nonce: Option<u64>) -> Result<()> {
+    match (nonce, ctx.accounts.receipt.as_mut()) {
+        (None, None) => {}
+        (Some(nonce), Some(receipt)) => {
+            if receipt.agent != Pubkey::default() {
+                if receipt.vault != ctx.accounts.vault.key() {
+                    return err!(ErrorCode::DepositNonceReused);
+                }
+                msg!("Deposit {} already credited", nonce);
+                return Ok(());
+            }
+            receipt.agent = ctx.accounts.agent.key();
+            receipt.vault = ctx.accounts.vault.key();
+            receipt.nonce = nonce;
+            receipt.amount = amount;
+            receipt.bump = ctx.bumps.receipt.unwrap_or_default();
+        }
+        _ => return err!(ErrorCode::InvalidDepositReceipt),
+    }
+
     let token_mint_key = ctx.accounts.token_mint.key();
     if token_mint_key != ctx.accounts.market.token_a && token_mint_key != ctx.accounts.market.token_b {
         return err!(ErrorCode::InvalidTokenMint);
@@ -51,6 +94,10 @@ pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64) -> Result<()> {
     if ctx.accounts.vault.token != token_mint_key {
         return err!(ErrorCode::InvalidVault);
     }
+    strict_check!(
+        ctx.accounts.vault.reserves <= ctx.accounts.vault_token_account.amount,
+        "a vault's reserves are in its token account"
+    );
 
     let cpi_accounts = Transfer {
         from: ctx.accounts.user_token_account.to_account_info(),
@@ -59,5 +106,13 @@ pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64) -> Result<()> {
     };
     let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
     token::transfer(cpi_ctx, amount)?;
+    ctx.accounts.vault.receive(amount)?;
+
+    let balance = &mut ctx.accounts.balance;
+    balance.agent = ctx.accounts.agent.key();
+    balance.vault = ctx.accounts.vault.key();
+    balance.amount = balance.amount.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    balance.bump = ctx.bumps.balance;
+    strict_check!(balance.amount <= ctx.accounts.vault.reserves, "no balance exceeds its vault's reserves");
     Ok(())
 }
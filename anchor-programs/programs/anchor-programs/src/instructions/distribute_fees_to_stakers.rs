@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{EmissionsSchedule, Market, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+use crate::math::mul_div_floor;
+
+#[derive(Accounts)]
+pub struct DistributeFeesToStakers<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"emissions", market.key().as_ref(), schedule.reward_mint.as_ref()],
+        bump = schedule.bump
+    )]
+    pub schedule: Account<'info, EmissionsSchedule>,
+    pub authority: Signer<'info>,
+}
+
+/// Turns on the fee switch for `share_bps` of the market's accrued fees,
+/// paying them to the schedule's bonds pro rata to their amount. Fees stay
+/// in kind in the market's vaults, owed to bonds until claimed with
+/// `claim_staking_fees`.
+pub fn distribute_fees_to_stakers(ctx: Context<DistributeFeesToStakers>, share_bps: u16) -> Result<()> {
+    if share_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidFeeSchedule);
+    }
+    let market = &mut ctx.accounts.market;
+    let amount_a = mul_div_floor(market.fees_accrued, share_bps as u64, BPS_DENOMINATOR)?;
+    let amount_b = mul_div_floor(market.fees_accrued_b, share_bps as u64, BPS_DENOMINATOR)?;
+    let (paid_a, paid_b) = ctx.accounts.schedule.distribute_fees(amount_a, amount_b)?;
+    market.fees_accrued -= paid_a;
+    market.fees_accrued_b -= paid_b;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Guild, SESSION_SCOPES_ALL};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ElectGuildManager<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub guild: Account<'info, Guild>,
+    #[account(
+        mut,
+        address = guild.agent @ ErrorCode::InvalidAgentOwner
+    )]
+    pub guild_agent: Account<'info, Agent>,
+    pub authority: Signer<'info>,
+}
+
+/// Replaces the guild's manager; the old manager loses trading rights at once.
+pub fn elect_guild_manager(ctx: Context<ElectGuildManager>, manager: Pubkey) -> Result<()> {
+    ctx.accounts.guild.manager = manager;
+    let guild_agent = &mut ctx.accounts.guild_agent;
+    guild_agent.end_sessions();
+    guild_agent.start_session(manager, u64::MAX, SESSION_SCOPES_ALL, 0)
+}
@@ -0,0 +1,398 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::adapters;
+use crate::instructions::execute_trade_via_jupiter::{invoke_swap, min_output};
+use crate::instructions::match_trades::take;
+use crate::instructions::reward_record::load_rewards;
+use crate::math::mul_div_floor;
+use crate::{Agent, Balance, Config, GlobalStats, Market, Trade, Vault, FEATURE_ORDER_BOOK, MATCHING_PRICE_TIME};
+use crate::errors::ErrorCode;
+use crate::events::{CircuitBreakerTripped, OrderFilled, OrderUpdated, TradeRouted};
+
+/// Accounts passed for each resting order: the order, its agent, and the
+/// agent's balances in the vaults the taker pays into and is paid out of.
+const MAKER_ACCOUNTS: usize = 4;
+
+#[derive(Accounts)]
+pub struct ExecuteHybridTrade<'info> {
+    #[account(
+        mut,
+        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref(), &trade.order_id.to_le_bytes()],
+        bump = trade.bump,
+        has_one = agent @ ErrorCode::InvalidAgent,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = user
+    )]
+    pub trade: Account<'info, Trade>,
+
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight,
+        constraint = market.matching_policy == MATCHING_PRICE_TIME @ ErrorCode::InvalidMatchingPolicy
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_ORDER_BOOK) @ ErrorCode::FeatureDisabled,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut, token::authority = user)]
+    pub user_token_account_in: Account<'info, TokenAccount>,
+
+    #[account(mut, token::authority = user)]
+    pub user_token_account_out: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vault",
+            market.token_a.as_ref(),
+            market.token_b.as_ref(),
+            user_token_account_in.mint.as_ref()
+        ],
+        bump = vault_in.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_in: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = user_token_account_in.mint,
+        associated_token::authority = vault_in
+    )]
+    pub vault_token_account_in: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vault",
+            market.token_a.as_ref(),
+            market.token_b.as_ref(),
+            user_token_account_out.mint.as_ref()
+        ],
+        bump = vault_out.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_out: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = user_token_account_out.mint,
+        associated_token::authority = vault_out
+    )]
+    pub vault_token_account_out: Account<'info, TokenAccount>,
+
+    /// CHECK: must be the program of the market's mirror venue
+    #[account(
+        constraint = adapters::program_id(market.mirror_venue)? == venue_program.key()
+            @ ErrorCode::InvalidMirrorVenue
+    )]
+    pub venue_program: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: `book_orders` groups of a resting order, its agent
+    // and the agent's balances in vault_in and vault_out, then
+    // `reward_records` of the current epoch for any of the agents, then the
+    // venue's swap accounts in the venue's order
+}
+
+/// Fills the agent's trade across the market's book and its mirrored pool,
+/// whichever prices the next unit better. Resting orders passed that cross
+/// the trade and sit at or better than the pool's marginal price fill first,
+/// best price first and then by queue priority, at their own price with the
+/// usual fees; the rest of the trade swaps in the pool. The pool's price only
+/// worsens as it fills, so no order it beats at the start is worth taking
+/// after. The agent's own orders are passed over, and a fill that trips the
+/// circuit breaker is the last from the book. Fills count toward the reward
+/// records passed, the pool swap toward the agent's. Returns the output
+/// received from both.
+pub fn execute_hybrid_trade<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteHybridTrade<'info>>,
+    max_slippage_bps: u16,
+    book_orders: u8,
+    reward_records: u8,
+) -> Result<u64> {
+    let trade = &ctx.accounts.trade;
+    let market = &ctx.accounts.market;
+
+    if trade.is_post_only() {
+        return err!(ErrorCode::PostOnlyWouldCross);
+    }
+    market.ensure_continuous()?;
+    let (token_in_key, token_out_key) = if trade.trade_type == 0 {
+        (market.token_a, market.token_b)
+    } else {
+        (market.token_b, market.token_a)
+    };
+    if ctx.accounts.user_token_account_in.mint != token_in_key
+        || ctx.accounts.user_token_account_out.mint != token_out_key
+    {
+        return err!(ErrorCode::InvalidTokenAccounts);
+    }
+
+    let (book_accounts, venue_accounts) = ctx
+        .remaining_accounts
+        .split_at_checked(book_orders as usize * MAKER_ACCOUNTS)
+        .ok_or(ErrorCode::InvalidMirrorAccounts)?;
+    let (reward_accounts, venue_accounts) = venue_accounts
+        .split_at_checked(reward_records as usize)
+        .ok_or(ErrorCode::InvalidMirrorAccounts)?;
+    let mut rewards = load_rewards(&market.key(), reward_accounts)?;
+    let pool = venue_accounts
+        .iter()
+        .find(|info| *info.key == market.mirror_pool)
+        .ok_or(ErrorCode::InvalidMirrorAccounts)?;
+    let pool_price = adapters::pool_price(market.mirror_venue, pool, &market.token_a)?;
+
+    // A buy takes asks (trade_type 1) priced at or under the pool's, a sell
+    // takes bids at or over it
+    let mut makers = Vec::with_capacity(book_orders as usize);
+    for group in book_accounts.chunks(MAKER_ACCOUNTS) {
+        let order = Account::<Trade>::try_from(&group[0])?;
+        if order.market != market.key() {
+            return err!(ErrorCode::InvalidMarket);
+        }
+        let agent = Account::<Agent>::try_from(&group[1])?;
+        if agent.key() != order.agent {
+            return err!(ErrorCode::InvalidAgent);
+        }
+        if order.trade_type == trade.trade_type || order.displayed() == 0 || agent.owner == ctx.accounts.agent.owner {
+            continue;
+        }
+        let beats_pool = if trade.trade_type == 0 {
+            order.price() <= pool_price
+        } else {
+            order.price() >= pool_price
+        };
+        // Token A per token B as ratios: the taker's limit against the maker's
+        let crosses = if trade.trade_type == 0 {
+            trade.quote() as u128 * order.size() as u128 >= order.quote() as u128 * trade.size() as u128
+        } else {
+            order.quote() as u128 * trade.size() as u128 >= trade.quote() as u128 * order.size() as u128
+        };
+        if beats_pool && crosses {
+            let balance_in = Account::<Balance>::try_from(&group[2])?;
+            let balance_out = Account::<Balance>::try_from(&group[3])?;
+            if balance_in.agent != agent.key() || balance_out.agent != agent.key() {
+                return err!(ErrorCode::InvalidAgent);
+            }
+            if balance_in.vault != ctx.accounts.vault_in.key() || balance_out.vault != ctx.accounts.vault_out.key() {
+                return err!(ErrorCode::InvalidVault);
+            }
+            makers.push((order, agent, balance_in, balance_out));
+        }
+    }
+    makers.sort_by(|(a, ..), (b, ..)| {
+        let (a_price, b_price) = (a.quote() as u128 * b.size() as u128, b.quote() as u128 * a.size() as u128);
+        let by_price = if trade.trade_type == 0 { a_price.cmp(&b_price) } else { b_price.cmp(&a_price) };
+        by_price.then(a.sequence.cmp(&b.sequence))
+    });
+    if makers.windows(2).any(|pair| pair[0].0.key() == pair[1].0.key()) {
+        return err!(ErrorCode::DuplicateOrder);
+    }
+
+    let size = trade.size();
+    let (limit_quote, limit_size) = (trade.quote(), trade.size());
+    let mut book_quantity = 0u64;
+    let (mut book_paid, mut book_received) = (0u64, 0u64);
+    let slot = Clock::get()?.slot;
+    let epoch = ctx.accounts.market.current_epoch(ctx.accounts.market.now()?);
+    for (order, maker_agent, balance_in, balance_out) in makers.iter_mut() {
+        let mut quantity = order.displayed().min(size - book_quantity);
+        if order.is_reduce_only() {
+            // The maker is an ask here, selling the taker its token B
+            quantity = quantity.min(balance_out.amount);
+        }
+        if quantity == 0 {
+            continue;
+        }
+        let quote_amount = mul_div_floor(quantity, order.quote(), order.size())?;
+
+        let market = &mut ctx.accounts.market;
+        let move_bps = market.record_fill_price(quote_amount, quantity, slot)?;
+        let halted = CircuitBreakerTripped::check(market, move_bps)?;
+        ctx.accounts.agent.roll_volume(epoch);
+        maker_agent.roll_volume(epoch);
+        let (maker_fee, taker_fee) = market.fill_fees(
+            quote_amount,
+            maker_agent.rolling_volume(),
+            ctx.accounts.agent.rolling_volume(),
+        )?;
+        ctx.accounts.agent.record_volume(quote_amount);
+        maker_agent.record_volume(quote_amount);
+        let fees = u64::try_from(maker_fee as i128 + taker_fee as i128).map_err(|_| ErrorCode::MathOverflow)?;
+        market.fees_accrued = market.fees_accrued.checked_add(fees).ok_or(ErrorCode::MathOverflow)?;
+        ctx.accounts.global_stats.record_fill(quote_amount, fees, 0)?;
+
+        // Makers settle through their balances, the taker through its wallet
+        let (taker_pays, taker_receives) = if trade.trade_type == 0 {
+            let maker_receives = u64::try_from(quote_amount as i128 - maker_fee as i128)
+                .map_err(|_| ErrorCode::MathOverflow)?;
+            balance_in.credit(maker_receives)?;
+            balance_out.debit(quantity)?;
+            (quote_amount.checked_add(taker_fee).ok_or(ErrorCode::MathOverflow)?, quantity)
+        } else {
+            let maker_pays = u64::try_from(quote_amount as i128 + maker_fee as i128)
+                .map_err(|_| ErrorCode::MathOverflow)?;
+            balance_in.credit(quantity)?;
+            balance_out.debit(maker_pays)?;
+            (quantity, quote_amount.checked_sub(taker_fee).ok_or(ErrorCode::MathOverflow)?)
+        };
+        book_paid = book_paid.checked_add(taker_pays).ok_or(ErrorCode::MathOverflow)?;
+        book_received = book_received.checked_add(taker_receives).ok_or(ErrorCode::MathOverflow)?;
+        book_quantity += quantity;
+
+        take(order, &mut ctx.accounts.market, quantity)?;
+        OrderUpdated::emit(&mut ctx.accounts.market, order)?;
+        let (bid_agent, ask_agent, bid_tag, ask_tag) = if trade.trade_type == 0 {
+            (trade.agent, order.agent, trade.tag, order.tag)
+        } else {
+            (order.agent, trade.agent, order.tag, trade.tag)
+        };
+        let (bid_fee, ask_fee) = if trade.trade_type == 0 {
+            (taker_fee as i64, maker_fee)
+        } else {
+            (maker_fee, taker_fee as i64)
+        };
+        let now = ctx.accounts.market.now()?;
+        for reward in rewards.iter_mut() {
+            let (trade_type, fee) = if reward.agent == trade.agent {
+                (trade.trade_type, taker_fee as i64)
+            } else if reward.agent == order.agent {
+                (order.trade_type, maker_fee)
+            } else {
+                continue;
+            };
+            reward.record_fill(epoch, trade_type, quantity, quote_amount, fee, now)?;
+        }
+        emit!(OrderFilled {
+            market: ctx.accounts.market.key(),
+            bid_agent,
+            ask_agent,
+            bid_tag,
+            ask_tag,
+            bid_is_maker: trade.trade_type == 1,
+            quantity,
+            quote_amount,
+            bid_fee,
+            ask_fee,
+            tie_break_seed: [0; 32],
+        });
+        if book_quantity == size || halted {
+            break;
+        }
+    }
+    for (order, maker_agent, balance_in, balance_out) in &makers {
+        order.exit(&crate::ID)?;
+        maker_agent.exit(&crate::ID)?;
+        balance_in.exit(&crate::ID)?;
+        balance_out.exit(&crate::ID)?;
+    }
+
+    // Book and vault state is final before any tokens move
+    ctx.accounts.vault_in.receive(book_paid)?;
+    ctx.accounts.vault_out.send(book_received)?;
+    ctx.accounts.agent.close_trade();
+    OrderUpdated::emit_removed(&mut ctx.accounts.market, &mut ctx.accounts.trade)?;
+
+    let accounts = &mut *ctx.accounts;
+    if book_paid > 0 {
+        let to_vault = Transfer {
+            from: accounts.user_token_account_in.to_account_info(),
+            to: accounts.vault_token_account_in.to_account_info(),
+            authority: accounts.user.to_account_info(),
+        };
+        token::transfer(CpiContext::new(accounts.token_program.to_account_info(), to_vault), book_paid)?;
+
+        let market = &accounts.market;
+        let seeds = &[
+            b"vault",
+            market.token_a.as_ref(),
+            market.token_b.as_ref(),
+            token_out_key.as_ref(),
+            &[accounts.vault_out.bump],
+        ];
+        let from_vault = Transfer {
+            from: accounts.vault_token_account_out.to_account_info(),
+            to: accounts.user_token_account_out.to_account_info(),
+            authority: accounts.vault_out.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(accounts.token_program.to_account_info(), from_vault, &[&seeds[..]]),
+            book_received,
+        )?;
+        accounts.user_token_account_in.reload()?;
+        accounts.user_token_account_out.reload()?;
+    }
+
+    // The rest swaps in the pool, priced against the trade's own limit
+    let rest = size - book_quantity;
+    let (pool_in, pool_received) = if rest == 0 {
+        (0, 0)
+    } else {
+        let rest_quote = mul_div_floor(rest, limit_quote, limit_size)?;
+        let (amount_in, expected_out) = if accounts.trade.trade_type == 0 { (rest_quote, rest) } else { (rest, rest_quote) };
+        let min_out = min_output(expected_out, max_slippage_bps)?;
+        let swap = adapters::swap_instruction(
+            accounts.market.mirror_venue,
+            &accounts.market.mirror_pool,
+            &accounts.user.key(),
+            &token_in_key,
+            venue_accounts,
+            amount_in,
+            min_out,
+        )?;
+        let received = invoke_swap(
+            &swap,
+            venue_accounts,
+            &mut accounts.user_token_account_in,
+            &mut accounts.user_token_account_out,
+            amount_in,
+            min_out,
+        )?;
+        (amount_in, received)
+    };
+
+    if pool_in > 0 {
+        let (quantity, quote_amount) = if accounts.trade.trade_type == 0 {
+            (pool_received, pool_in)
+        } else {
+            (pool_in, pool_received)
+        };
+        let now = accounts.market.now()?;
+        let epoch = accounts.market.current_epoch(now);
+        if let Some(reward) = rewards.iter_mut().find(|reward| reward.agent == accounts.trade.agent) {
+            reward.record_fill(epoch, accounts.trade.trade_type, quantity, quote_amount, 0, now)?;
+        }
+    }
+    for reward in &rewards {
+        reward.exit(&crate::ID)?;
+    }
+
+    emit!(TradeRouted {
+        market: accounts.market.key(),
+        agent: accounts.trade.agent,
+        tag: accounts.trade.tag,
+        trade_type: accounts.trade.trade_type,
+        book_quantity,
+        book_amount_in: book_paid,
+        book_amount_out: book_received,
+        pool_amount_in: pool_in,
+        pool_amount_out: pool_received,
+    });
+    book_received.checked_add(pool_received).ok_or(ErrorCode::MathOverflow.into())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::adapters;
+use crate::instructions::execute_trade_via_jupiter::{invoke_swap, min_output};
+use crate::{Agent, Config, Market, Trade};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+
+#[derive(Accounts)]
+pub struct ExecuteMirrorTrade<'info> {
+    #[account(
+        mut,
+        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref(), &trade.order_id.to_le_bytes()],
+        bump = trade.bump,
+        has_one = agent @ ErrorCode::InvalidAgent,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = user
+    )]
+    pub trade: Account<'info, Trade>,
+
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut, token::authority = user)]
+    pub user_token_account_in: Account<'info, TokenAccount>,
+
+    #[account(mut, token::authority = user)]
+    pub user_token_account_out: Account<'info, TokenAccount>,
+
+    /// CHECK: must be the program of the market's mirror venue
+    #[account(
+        constraint = adapters::program_id(market.mirror_venue)? == venue_program.key()
+            @ ErrorCode::InvalidMirrorVenue
+    )]
+    pub venue_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: the venue's swap accounts, in the venue's order
+}
+
+/// Fills the agent's trade against the market's mirrored external pool, so
+/// real liquidity is traded under the same agent and trade accounts.
+/// Returns the output received.
+pub fn execute_mirror_trade<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteMirrorTrade<'info>>,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    let trade = &ctx.accounts.trade;
+    let market = &ctx.accounts.market;
+
+    if trade.is_post_only() {
+        return err!(ErrorCode::PostOnlyWouldCross);
+    }
+
+    let (token_in_key, token_out_key) = if trade.trade_type == 0 {
+        (market.token_a, market.token_b)
+    } else {
+        (market.token_b, market.token_a)
+    };
+    if ctx.accounts.user_token_account_in.mint != token_in_key
+        || ctx.accounts.user_token_account_out.mint != token_out_key
+    {
+        return err!(ErrorCode::InvalidTokenAccounts);
+    }
+
+    let min_out = min_output(trade.amount_out, max_slippage_bps)?;
+    let swap = adapters::swap_instruction(
+        market.mirror_venue,
+        &market.mirror_pool,
+        &ctx.accounts.user.key(),
+        &token_in_key,
+        ctx.remaining_accounts,
+        trade.amount_in,
+        min_out,
+    )?;
+    ctx.accounts.agent.close_trade();
+    OrderUpdated::emit_removed(&mut ctx.accounts.market, &mut ctx.accounts.trade)?;
+    let accounts = &mut *ctx.accounts;
+    invoke_swap(
+        &swap,
+        ctx.remaining_accounts,
+        &mut accounts.user_token_account_in,
+        &mut accounts.user_token_account_out,
+        accounts.trade.amount_in,
+        min_out,
+    )
+}
@@ -1,26 +1,42 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, Mint, Token, TokenAccount, Transfer},
 };
 
-use crate::{Trade, Agent, Market, Vault};
+use crate::{Trade, Agent, Config, GlobalStats, Market, RewardRecord, Settlement, Vault};
 use crate::errors::ErrorCode;
+use crate::events::{OrderUpdated, TradeExecuted};
+use crate::oracle::read_price;
 
 #[derive(Accounts)]
 pub struct ExecuteTrade<'info> {
     #[account(
         mut,
-        has_one = agent @ ErrorCode::Unauthorized,
+        has_one = agent @ ErrorCode::InvalidAgent,
+        has_one = market @ ErrorCode::InvalidMarket,
         close = user
     )]
     pub trade: Account<'info, Trade>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
     pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
 
     #[account(
-       mut
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
     )]
     pub agent: Account<'info, Agent>,
 
@@ -46,6 +62,7 @@ pub struct ExecuteTrade<'info> {
     pub user_token_account_out: Account<'info, TokenAccount>,
 
     #[account(
+        mut,
         seeds = [
             b"vault",
             market.token_a.as_ref(),
@@ -66,6 +83,7 @@ pub struct ExecuteTrade<'info> {
     pub vault_token_account_in: Account<'info, TokenAccount>,
 
     #[account(
+        mut,
         seeds = [
             b"vault",
             market.token_a.as_ref(),
@@ -85,15 +103,62 @@ pub struct ExecuteTrade<'info> {
     )]
     pub vault_token_account_out: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"settlement", market.key().as_ref(), agent.key().as_ref()],
+        bump = settlement.bump,
+    )]
+    pub settlement: Option<Account<'info, Settlement>>,
+
+    /// The current epoch's, counting the fill toward the agent's reward
+    #[account(
+        mut,
+        seeds = [b"reward", market.key().as_ref(), agent.key().as_ref(), &reward.epoch.to_le_bytes()],
+        bump = reward.bump
+    )]
+    pub reward: Option<Account<'info, RewardRecord>>,
+
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the Instructions sysvar, pinned by address; needed when the
+    /// market's sandwich guard is on
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn execute_trade(ctx: Context<ExecuteTrade>) -> Result<()> {
+/// Fills the agent's trade against the market's vaults and returns the
+/// token out it delivers, paid now or owed at the epoch's net settlement.
+/// Either way the fill is at the market price, within the trade's own price
+/// as a limit, and the vault's own liquidity takes the other side. Owed at
+/// settlement, whatever the agent ends up owing is escrowed as it trades.
+pub fn execute_trade(ctx: Context<ExecuteTrade>) -> Result<u64> {
     let trade = &ctx.accounts.trade;
     let market = &ctx.accounts.market;
 
+    if market.sandwich_guard {
+        let instructions = ctx
+            .accounts
+            .instructions
+            .as_ref()
+            .ok_or(ErrorCode::InstructionsSysvarRequired)?;
+        ensure_sole_market_instruction(&instructions.to_account_info(), &market.key())?;
+    }
+
+    // The vault always takes the other side immediately
+    if trade.is_post_only() {
+        return err!(ErrorCode::PostOnlyWouldCross);
+    }
+
     // Determine token_in and token_out based on trade_type
     let (token_in_key, token_out_key) = if trade.trade_type == 0 {
         // Buy Token B with Token A
@@ -110,6 +175,101 @@ pub fn execute_trade(ctx: Context<ExecuteTrade>) -> Result<()> {
         return err!(ErrorCode::InvalidTokenAccounts);
     }
 
+    // The vault fills at the market price: buys are charged rounding up and
+    // sells paid rounding down, and neither may be worse than the trade's own
+    let price = read_price(
+        market,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+        Clock::get()?.slot,
+    )?
+    .price;
+    let (amount_in, amount_out) = if trade.trade_type == 0 {
+        let quantity = i64::try_from(trade.amount_out).map_err(|_| ErrorCode::MathOverflow)?;
+        let pays = market.base_value(quantity, price, true)? as u64;
+        if pays > trade.amount_in {
+            return err!(ErrorCode::SlippageExceeded);
+        }
+        (pays, trade.amount_out)
+    } else {
+        let quantity = i64::try_from(trade.amount_in).map_err(|_| ErrorCode::MathOverflow)?;
+        let proceeds = market.base_value(quantity, price, false)? as u64;
+        if proceeds < trade.amount_out {
+            return err!(ErrorCode::SlippageExceeded);
+        }
+        (trade.amount_in, proceeds)
+    };
+    // The vault's own liquidity caps what it pays out, now or at settlement
+    ctx.accounts.vault_in.mirror_in(amount_in)?;
+    ctx.accounts.vault_out.mirror_out(amount_out)?;
+
+    emit!(TradeExecuted {
+        market: market.key(),
+        agent: trade.agent,
+        tag: trade.tag,
+        trade_type: trade.trade_type,
+        amount_in,
+        amount_out,
+    });
+    // The vault charges no fee
+    let volume = if trade.trade_type == 0 { amount_in } else { amount_out };
+    ctx.accounts.global_stats.record_fill(volume, 0, 0)?;
+    if let Some(reward) = ctx.accounts.reward.as_mut() {
+        let now = market.now()?;
+        let quantity = if trade.trade_type == 0 { amount_out } else { amount_in };
+        reward.record_fill(market.current_epoch(now), trade.trade_type, quantity, volume, 0, now)?;
+    }
+
+    if market.net_settlement {
+        let epoch = market.current_epoch(market.now()?);
+        let settlement: &mut Settlement = ctx
+            .accounts
+            .settlement
+            .as_mut()
+            .ok_or(ErrorCode::SettlementRequired)?;
+        if settlement.epoch != epoch {
+            if settlement.delta_a != 0 || settlement.delta_b != 0 {
+                return err!(ErrorCode::SettlementPending);
+            }
+            settlement.epoch = epoch;
+        }
+
+        let amount_in = i64::try_from(amount_in).map_err(|_| ErrorCode::MathOverflow)?;
+        let amount_out = i64::try_from(amount_out).map_err(|_| ErrorCode::MathOverflow)?;
+        let (delta_in, delta_out) = if trade.trade_type == 0 {
+            (&mut settlement.delta_a, &mut settlement.delta_b)
+        } else {
+            (&mut settlement.delta_b, &mut settlement.delta_a)
+        };
+        *delta_in = delta_in.checked_sub(amount_in).ok_or(ErrorCode::MathOverflow)?;
+        *delta_out = delta_out.checked_add(amount_out).ok_or(ErrorCode::MathOverflow)?;
+
+        // What the agent owes at settlement is escrowed now, so it can't be
+        // moved out of the wallet before then
+        let escrow = (*delta_in).min(0).unsigned_abs();
+        *delta_in += escrow as i64;
+        ctx.accounts.agent.close_trade();
+        OrderUpdated::emit_removed(&mut ctx.accounts.market, &mut ctx.accounts.trade)?;
+        if escrow > 0 {
+            ctx.accounts.vault_in.receive(escrow)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.user_token_account_in.to_account_info(),
+                to: ctx.accounts.vault_token_account_in.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, escrow)?;
+        }
+        return Ok(amount_out as u64);
+    }
+
+    // Book and vault state is final before any tokens move
+    ctx.accounts.vault_in.receive(amount_in)?;
+    ctx.accounts.vault_out.send(amount_out)?;
+    ctx.accounts.agent.close_trade();
+    OrderUpdated::emit_removed(&mut ctx.accounts.market, &mut ctx.accounts.trade)?;
+
+    let market = &ctx.accounts.market;
     // Transfer tokens from user to vault_in
     let cpi_accounts_to_vault = Transfer {
         from: ctx.accounts.user_token_account_in.to_account_info(),
@@ -120,7 +280,7 @@ pub fn execute_trade(ctx: Context<ExecuteTrade>) -> Result<()> {
         ctx.accounts.token_program.to_account_info(),
         cpi_accounts_to_vault,
     );
-    token::transfer(cpi_ctx_to_vault, trade.amount_in)?;
+    token::transfer(cpi_ctx_to_vault, amount_in)?;
 
     // Transfer tokens from vault_out to user
     let seeds = &[
@@ -142,7 +302,25 @@ pub fn execute_trade(ctx: Context<ExecuteTrade>) -> Result<()> {
         cpi_accounts_from_vault,
         signer_seeds,
     );
-    token::transfer(cpi_ctx_from_vault, trade.amount_out)?;
+    token::transfer(cpi_ctx_from_vault, amount_out)?;
+    Ok(amount_out)
+}
 
+/// Rejects the transaction if any other instruction invokes this program with
+/// `market`, so a trade cannot be wrapped by front- and back-running legs.
+fn ensure_sole_market_instruction(instructions: &AccountInfo, market: &Pubkey) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)? as usize;
+    if load_instruction_at_checked(current_index, instructions)?.program_id != crate::ID {
+        return err!(ErrorCode::SandwichDetected);
+    }
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        let touches_market = ix.program_id == crate::ID
+            && ix.accounts.iter().any(|meta| meta.pubkey == *market);
+        if index != current_index && touches_market {
+            return err!(ErrorCode::SandwichDetected);
+        }
+        index += 1;
+    }
     Ok(())
 }
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::{Agent, Config, Market, Trade, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+#[derive(Accounts)]
+pub struct ExecuteTradeViaJupiter<'info> {
+    #[account(
+        mut,
+        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref(), &trade.order_id.to_le_bytes()],
+        bump = trade.bump,
+        has_one = agent @ ErrorCode::InvalidAgent,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = user
+    )]
+    pub trade: Account<'info, Trade>,
+
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut, token::authority = user)]
+    pub user_token_account_in: Account<'info, TokenAccount>,
+
+    #[account(mut, token::authority = user)]
+    pub user_token_account_out: Account<'info, TokenAccount>,
+
+    /// CHECK: pinned to the Jupiter aggregator program
+    #[account(address = JUPITER_PROGRAM_ID)]
+    pub jupiter_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: the Jupiter route accounts, in route order
+}
+
+/// Fills the agent's trade against external liquidity. The client builds the
+/// Jupiter route and passes its instruction data and accounts; the program
+/// only enforces that the route spends at most the trade's input and returns
+/// at least its output less `max_slippage_bps`. Returns the output received.
+pub fn execute_trade_via_jupiter<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteTradeViaJupiter<'info>>,
+    route_data: Vec<u8>,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    let trade = &ctx.accounts.trade;
+    let market = &ctx.accounts.market;
+
+    if !market.jupiter_routing {
+        return err!(ErrorCode::ExternalRoutingDisabled);
+    }
+    if trade.is_post_only() {
+        return err!(ErrorCode::PostOnlyWouldCross);
+    }
+
+    let (token_in_key, token_out_key) = if trade.trade_type == 0 {
+        (market.token_a, market.token_b)
+    } else {
+        (market.token_b, market.token_a)
+    };
+    if ctx.accounts.user_token_account_in.mint != token_in_key
+        || ctx.accounts.user_token_account_out.mint != token_out_key
+    {
+        return err!(ErrorCode::InvalidTokenAccounts);
+    }
+
+    let min_out = min_output(trade.amount_out, max_slippage_bps)?;
+    let route = Instruction {
+        program_id: JUPITER_PROGRAM_ID,
+        accounts: ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: route_data,
+    };
+    ctx.accounts.agent.close_trade();
+    OrderUpdated::emit_removed(&mut ctx.accounts.market, &mut ctx.accounts.trade)?;
+    let accounts = &mut *ctx.accounts;
+    invoke_swap(
+        &route,
+        ctx.remaining_accounts,
+        &mut accounts.user_token_account_in,
+        &mut accounts.user_token_account_out,
+        accounts.trade.amount_in,
+        min_out,
+    )
+}
+
+/// `amount_out` less `max_slippage_bps`, rounded down.
+pub(crate) fn min_output(amount_out: u64, max_slippage_bps: u16) -> Result<u64> {
+    if max_slippage_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidSlippage);
+    }
+    u64::try_from(
+        amount_out as u128 * (BPS_DENOMINATOR - max_slippage_bps as u64) as u128
+            / BPS_DENOMINATOR as u128,
+    )
+    .map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Invokes an external swap and checks it against the user's own token
+/// accounts: at most `max_in` may leave and at least `min_out` must arrive.
+/// Returns what arrived.
+pub(crate) fn invoke_swap<'info>(
+    swap: &Instruction,
+    accounts: &[AccountInfo<'info>],
+    user_token_account_in: &mut Account<'info, TokenAccount>,
+    user_token_account_out: &mut Account<'info, TokenAccount>,
+    max_in: u64,
+    min_out: u64,
+) -> Result<u64> {
+    let in_before = user_token_account_in.amount;
+    let out_before = user_token_account_out.amount;
+    invoke(swap, accounts)?;
+
+    user_token_account_in.reload()?;
+    user_token_account_out.reload()?;
+    let spent = in_before.saturating_sub(user_token_account_in.amount);
+    let received = user_token_account_out.amount.saturating_sub(out_before);
+    if spent > max_in || received < min_out {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+    Ok(received)
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Balance, Market, OptionContract, Vault, OPTION_CALL};
+use crate::errors::ErrorCode;
+use crate::oracle::read_price;
+
+#[derive(Accounts)]
+pub struct ExerciseOption<'info> {
+    #[account(
+        constraint = holder_agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub holder_agent: Account<'info, Agent>,
+    #[account(address = option.writer @ ErrorCode::Unauthorized)]
+    pub writer_agent: Account<'info, Agent>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        constraint = option.holder == holder_agent.key() @ ErrorCode::Unauthorized,
+        close = writer_owner
+    )]
+    pub option: Account<'info, OptionContract>,
+    #[account(has_one = market @ ErrorCode::InvalidVault)]
+    pub collateral_vault: Account<'info, Vault>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", collateral_vault.key().as_ref(), holder_agent.key().as_ref()],
+        bump
+    )]
+    pub holder_balance: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", collateral_vault.key().as_ref(), writer_agent.key().as_ref()],
+        bump
+    )]
+    pub writer_balance: Account<'info, Balance>,
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    #[account(mut, address = writer_agent.owner @ ErrorCode::InvalidAgentOwner)]
+    pub writer_owner: SystemAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Cash-settles an in-the-money option at the market's oracle price: the
+/// holder takes the intrinsic value out of the locked collateral and the
+/// writer gets the rest back. Rounding favours the writer.
+pub fn exercise_option(ctx: Context<ExerciseOption>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let option = &ctx.accounts.option;
+    if market.now()? > option.expiry_slot {
+        return err!(ErrorCode::OptionExpired);
+    }
+    let (collateral_mint, collateral) = option.collateral(market);
+    if ctx.accounts.collateral_vault.token != collateral_mint {
+        return err!(ErrorCode::InvalidVault);
+    }
+    let price = read_price(
+        market,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+        Clock::get()?.slot,
+    )?
+    .price;
+    let size = i64::try_from(option.size).map_err(|_| ErrorCode::MathOverflow)?;
+
+    let payout = if option.kind == OPTION_CALL {
+        // The holder takes the locked token B left after covering the strike
+        if market.base_value(size, price, false)? as u64 <= option.strike_amount {
+            return err!(ErrorCode::OptionOutOfTheMoney);
+        }
+        option.size - market.base_amount(option.strike_amount, price, true)?
+    } else {
+        // The holder takes the locked strike beyond the token B's value
+        let value = market.base_value(size, price, true)? as u64;
+        if value >= option.strike_amount {
+            return err!(ErrorCode::OptionOutOfTheMoney);
+        }
+        option.strike_amount - value
+    };
+
+    let accounts = &mut *ctx.accounts;
+    let bumps = &ctx.bumps;
+    let (holder, writer) = (accounts.holder_agent.key(), accounts.writer_agent.key());
+    let collateral_vault = accounts.collateral_vault.key();
+    accounts.holder_balance.bind(holder, collateral_vault, bumps.holder_balance);
+    accounts.writer_balance.bind(writer, collateral_vault, bumps.writer_balance);
+    accounts.holder_balance.credit(payout)?;
+    accounts.writer_balance.credit(collateral - payout)
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Balance, Market, OptionContract, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ExpireOption<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"option", market.key().as_ref(), agent.key().as_ref(), option.option_id.to_le_bytes().as_ref()],
+        bump = option.bump,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = user
+    )]
+    pub option: Account<'info, OptionContract>,
+    #[account(has_one = market @ ErrorCode::InvalidVault)]
+    pub collateral_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"balance", collateral_vault.key().as_ref(), agent.key().as_ref()],
+        bump = collateral_balance.bump
+    )]
+    pub collateral_balance: Account<'info, Balance>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn expire_option(ctx: Context<ExpireOption>) -> Result<()> {
+    let option = &ctx.accounts.option;
+    // Unsold options can be reclaimed at any time, sold ones only after expiry
+    if option.holder != Pubkey::default() && ctx.accounts.market.now()? <= option.expiry_slot {
+        return err!(ErrorCode::OptionNotExpired);
+    }
+    let (collateral_mint, collateral) = option.collateral(&ctx.accounts.market);
+    if ctx.accounts.collateral_vault.token != collateral_mint {
+        return err!(ErrorCode::InvalidVault);
+    }
+    ctx.accounts.collateral_balance.credit(collateral)
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{Config, Market, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct FlashLoan<'info> {
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+    pub token_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), token_mint.key().as_ref()],
+        bump = vault.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = token_mint
+    )]
+    pub borrower_token_account: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    /// CHECK: the Instructions sysvar, pinned by address
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn flash_loan(ctx: Context<FlashLoan>, amount: u64) -> Result<()> {
+    if ctx.accounts.vault.flash_loan_amount != 0 {
+        return err!(ErrorCode::FlashLoanOutstanding);
+    }
+
+    // Only a top-level call can see the rest of the transaction, so refuse CPI
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current_index = load_current_index_checked(&instructions)? as usize;
+    let current = load_instruction_at_checked(current_index, &instructions)?;
+    if current.program_id != crate::ID {
+        return err!(ErrorCode::FlashLoanCpi);
+    }
+
+    // A repay_flash_loan for this vault has to follow in the same transaction
+    let vault_key = ctx.accounts.vault.key();
+    let mut index = current_index + 1;
+    let repaid = loop {
+        let Ok(ix) = load_instruction_at_checked(index, &instructions) else {
+            break false;
+        };
+        if ix.program_id == crate::ID
+            && ix.data.starts_with(crate::instruction::RepayFlashLoan::DISCRIMINATOR)
+            && ix.accounts.get(REPAY_VAULT_INDEX).map(|meta| meta.pubkey) == Some(vault_key)
+        {
+            break true;
+        }
+        index += 1;
+    };
+    if !repaid {
+        return err!(ErrorCode::FlashLoanNotRepaid);
+    }
+
+    ctx.accounts.vault.flash_loan_amount = amount;
+
+    let market = &ctx.accounts.market;
+    let seeds = &[
+        b"vault",
+        market.token_a.as_ref(),
+        market.token_b.as_ref(),
+        ctx.accounts.vault.token.as_ref(),
+        &[ctx.accounts.vault.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        to: ctx.accounts.borrower_token_account.to_account_info(),
+        authority: ctx.accounts.vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)
+}
+
+/// Position of `vault` in RepayFlashLoan's account list.
+const REPAY_VAULT_INDEX: usize = 2;
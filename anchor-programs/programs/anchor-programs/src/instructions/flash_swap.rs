@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{Agent, Config, GlobalStats, Market, Trade, Vault};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+
+#[derive(Accounts)]
+pub struct FlashSwap<'info> {
+    #[account(
+        mut,
+        has_one = agent @ ErrorCode::InvalidAgent,
+        has_one = market @ ErrorCode::InvalidMarket,
+        close = user
+    )]
+    pub trade: Account<'info, Trade>,
+
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_in_mint: Account<'info, Mint>,
+    pub token_out_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_out_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account_out: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), token_in_mint.key().as_ref()],
+        bump = vault_in.bump,
+        has_one = market @ ErrorCode::InvalidVault,
+    )]
+    pub vault_in: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_in_mint,
+        associated_token::authority = vault_in
+    )]
+    pub vault_token_account_in: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), token_out_mint.key().as_ref()],
+        bump = vault_out.bump,
+        has_one = market @ ErrorCode::InvalidVault,
+    )]
+    pub vault_out: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_out_mint,
+        associated_token::authority = vault_out
+    )]
+    pub vault_token_account_out: Account<'info, TokenAccount>,
+
+    /// CHECK: arbitrary program chosen by the caller, invoked once the output
+    /// has been sent; it must not be this program.
+    #[account(
+        executable,
+        constraint = callback_program.key() != crate::ID @ ErrorCode::InvalidCallbackProgram
+    )]
+    pub callback_program: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: passed through to the callback program as-is
+}
+
+/// Pays out `trade.amount_out` first, lets the callback program use it, then
+/// requires `trade.amount_in` plus the taker fee to have reached vault_in.
+/// Everything but the repayment is written before the callback runs, and the
+/// market stays `in_flight` until it returns, so nothing the callback does can
+/// act on the market or its vaults half way through the swap.
+pub fn flash_swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FlashSwap<'info>>,
+    callback_data: Vec<u8>,
+) -> Result<()> {
+    let trade = &ctx.accounts.trade;
+    let market = &ctx.accounts.market;
+    if trade.is_post_only() {
+        return err!(ErrorCode::PostOnlyWouldCross);
+    }
+    let (token_in_key, token_out_key) = if trade.trade_type == 0 {
+        (market.token_a, market.token_b)
+    } else {
+        (market.token_b, market.token_a)
+    };
+    if ctx.accounts.token_in_mint.key() != token_in_key
+        || ctx.accounts.token_out_mint.key() != token_out_key
+    {
+        return err!(ErrorCode::InvalidTokenAccounts);
+    }
+    let (trade_type, amount_in, amount_out) = (trade.trade_type, trade.amount_in, trade.amount_out);
+    let fee = market.taker_fee(amount_in)?;
+    let repayment = amount_in.checked_add(fee).ok_or(ErrorCode::MathOverflow)?;
+    let required = ctx
+        .accounts
+        .vault_token_account_in
+        .amount
+        .checked_add(repayment)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Written out before the callback, which sees only committed state
+    ctx.accounts.vault_out.send(amount_out)?;
+    OrderUpdated::emit_removed(&mut ctx.accounts.market, &mut ctx.accounts.trade)?;
+    ctx.accounts.agent.close_trade();
+    ctx.accounts.market.in_flight = true;
+    ctx.accounts.market.exit(&crate::ID)?;
+    ctx.accounts.vault_out.exit(&crate::ID)?;
+    ctx.accounts.agent.exit(&crate::ID)?;
+
+    let market = &ctx.accounts.market;
+    let seeds = &[
+        b"vault",
+        market.token_a.as_ref(),
+        market.token_b.as_ref(),
+        token_out_key.as_ref(),
+        &[ctx.accounts.vault_out.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault_token_account_out.to_account_info(),
+        to: ctx.accounts.user_token_account_out.to_account_info(),
+        authority: ctx.accounts.vault_out.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount_out)?;
+
+    let callback = Instruction {
+        program_id: ctx.accounts.callback_program.key(),
+        accounts: ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: callback_data,
+    };
+    invoke(&callback, ctx.remaining_accounts)?;
+    // The callback may have opened trades for the agent on other markets
+    ctx.accounts.agent.reload()?;
+
+    ctx.accounts.vault_token_account_in.reload()?;
+    if ctx.accounts.vault_token_account_in.amount < required {
+        return err!(ErrorCode::FlashRepaymentShort);
+    }
+    // Anything sent beyond the repayment is left for reconcile_vault to find
+    ctx.accounts.vault_in.receive(repayment)?;
+    ctx.accounts.market.accrue_fee(token_in_key, fee)?;
+    let (volume, fees_a, fees_b) = if trade_type == 0 {
+        (amount_in, fee, 0)
+    } else {
+        (amount_out, 0, fee)
+    };
+    // Unlike the market's, the stats aren't guarded, so pick up whatever the
+    // callback did to them before adding to them
+    ctx.accounts.global_stats.reload()?;
+    ctx.accounts.global_stats.record_fill(volume, fees_a, fees_b)?;
+    ctx.accounts.market.in_flight = false;
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Balance, Market, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct FundInsurance<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_a.key().as_ref(), agent.key().as_ref()],
+        bump = balance_a.bump
+    )]
+    pub balance_a: Account<'info, Balance>,
+    pub user: Signer<'info>,
+}
+
+/// Donates tracked token A to the market's insurance fund.
+pub fn fund_insurance(ctx: Context<FundInsurance>, amount: u64) -> Result<()> {
+    ctx.accounts.balance_a.debit(amount)?;
+    let market = &mut ctx.accounts.market;
+    market.insurance_fund = market
+        .insurance_fund
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
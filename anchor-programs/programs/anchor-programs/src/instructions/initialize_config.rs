@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::program::AgentMarketSim;
+use crate::{Config, GlobalStats};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = 8 + GlobalStats::INIT_SPACE,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ ErrorCode::NotUpgradeAuthority
+    )]
+    pub program: Program<'info, AgentMarketSim>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key())
+            @ ErrorCode::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+/// Creates the deployment's config and its stats. Only the program's upgrade authority can,
+/// so whoever deployed the program picks the admin.
+pub fn initialize_config(
+    ctx: Context<InitializeConfig>,
+    admin: Pubkey,
+    maker_fee_bps: i16,
+    taker_fee_bps: u16,
+    features: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = admin;
+    config.maker_fee_bps = maker_fee_bps;
+    config.taker_fee_bps = taker_fee_bps;
+    config.features = features;
+    config.bump = ctx.bumps.config;
+    ctx.accounts.global_stats.bump = ctx.bumps.global_stats;
+    config.validate_fees()
+}
+
+/// Replaces the config's settings, including handing it to a new admin.
+/// Markets already created keep their own authority and fees.
+pub fn update_config(
+    ctx: Context<UpdateConfig>,
+    admin: Pubkey,
+    maker_fee_bps: i16,
+    taker_fee_bps: u16,
+    features: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = admin;
+    config.maker_fee_bps = maker_fee_bps;
+    config.taker_fee_bps = taker_fee_bps;
+    config.features = features;
+    config.validate_fees()
+}
+
+/// Halts trading across every market, or lifts the halt. While it holds,
+/// balances can still be withdrawn and settlements and redemptions are paid
+/// without waiting for their epoch to end.
+pub fn set_emergency_mode(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+    ctx.accounts.config.emergency_mode = enabled;
+    Ok(())
+}
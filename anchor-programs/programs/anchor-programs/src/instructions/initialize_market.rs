@@ -4,10 +4,19 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{Market, Vault};
+use crate::{Config, GlobalStats, Market, MarketListing, Vault, DEFAULT_EPOCH_SLOTS};
+use crate::errors::ErrorCode;
 
 #[derive(Accounts)]
 pub struct InitializeMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.admin == signer.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
     #[account(
         init,
         payer = signer,
@@ -17,6 +26,18 @@ pub struct InitializeMarket<'info> {
     )]
     pub market: Account<'info, Market>,
 
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + MarketListing::INIT_SPACE,
+        seeds = [b"market_listing", config.market_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market_listing: Account<'info, MarketListing>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
     #[account(
         init,
         payer = signer,
@@ -60,12 +81,21 @@ pub struct InitializeMarket<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Creates a market under the config admin, starting from the config's
+/// default fees, and lists it after the markets before it.
 pub fn initialize_market(
     ctx: Context<InitializeMarket>,
 ) -> Result<()> {
+    let config = &ctx.accounts.config;
     let market = &mut ctx.accounts.market;
     market.token_a = ctx.accounts.token_a_mint.key();
     market.token_b = ctx.accounts.token_b_mint.key();
+    market.authority = config.admin;
+    market.maker_fee_bps = config.maker_fee_bps;
+    market.taker_fee_bps = config.taker_fee_bps;
+    market.epoch_slots = DEFAULT_EPOCH_SLOTS;
+    market.decimals_a = ctx.accounts.token_a_mint.decimals;
+    market.decimals_b = ctx.accounts.token_b_mint.decimals;
     market.bump = ctx.bumps.market;
 
     let market_key = market.key();
@@ -79,5 +109,13 @@ pub fn initialize_market(
     vault_b.token = ctx.accounts.token_b_mint.key();
     vault_b.bump = ctx.bumps.vault_b;
 
+    let config = &mut ctx.accounts.config;
+    let listing = &mut ctx.accounts.market_listing;
+    listing.market = market_key;
+    listing.index = config.market_count;
+    listing.bump = ctx.bumps.market_listing;
+    config.market_count += 1;
+    ctx.accounts.global_stats.markets += 1;
+
     Ok(())
 }
\ No newline at end of file
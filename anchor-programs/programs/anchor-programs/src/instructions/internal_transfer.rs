@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Balance, Market, Vault, errors::ErrorCode};
+
+#[derive(Accounts)]
+pub struct InternalTransfer<'info> {
+    #[account(constraint = from_agent.owner == user.key() @ ErrorCode::InvalidAgentOwner)]
+    pub from_agent: Account<'info, Agent>,
+    #[account(constraint = to_agent.key() != from_agent.key() @ ErrorCode::InvalidAgent)]
+    pub to_agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), vault.token.as_ref()],
+        bump = vault.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault.key().as_ref(), from_agent.key().as_ref()],
+        bump = balance_from.bump,
+    )]
+    pub balance_from: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault.key().as_ref(), to_agent.key().as_ref()],
+        bump
+    )]
+    pub balance_to: Account<'info, Balance>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves `amount` of the vault's tracked balance from one agent to another.
+/// No tokens move, since both balances are held in the same vault.
+pub fn internal_transfer(ctx: Context<InternalTransfer>, amount: u64) -> Result<()> {
+    ctx.accounts.balance_from.debit(amount)?;
+    let balance_to = &mut ctx.accounts.balance_to;
+    balance_to.bind(ctx.accounts.to_agent.key(), ctx.accounts.vault.key(), ctx.bumps.balance_to);
+    balance_to.credit(amount)
+}
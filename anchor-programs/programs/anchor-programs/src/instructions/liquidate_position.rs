@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::events::PositionLiquidated;
+use crate::oracle::read_price;
+use crate::{
+    Agent, Balance, Market, Position, Vault, BPS_DENOMINATOR, LIQUIDATION_INSURANCE_SHARE_BPS,
+};
+use crate::errors::ErrorCode;
+use crate::math::mul_div_ceil;
+
+#[derive(Accounts)]
+pub struct LiquidatePosition<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), position.agent.as_ref()],
+        bump = position.bump,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        constraint = liquidator_agent.owner == liquidator.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub liquidator_agent: Account<'info, Agent>,
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), liquidator_agent.key().as_ref()],
+        bump
+    )]
+    pub liquidator_balance_a: Account<'info, Balance>,
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Closes a solvent position that fell below maintenance margin at the oracle
+/// price. The liquidation fee comes out of the remaining equity and is split
+/// between the liquidator and the insurance fund; the rest stays with the
+/// position as collateral. The vault's own liquidity takes the other side of
+/// the PnL the close realizes.
+pub fn liquidate_position(ctx: Context<LiquidatePosition>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let price = read_price(
+        market,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+        Clock::get()?.slot,
+    )?
+    .price;
+
+    let position = &mut ctx.accounts.position;
+    position.settle(market)?;
+    market.reprice_funding(price)?;
+    let equity = position.equity(market, price)?;
+    let maintenance =
+        market.margin_requirement(position.base, price, market.maintenance_margin_bps)?;
+    if equity >= maintenance as i64 {
+        return err!(ErrorCode::NotLiquidatable);
+    }
+    if equity < 0 {
+        return err!(ErrorCode::PositionBankrupt);
+    }
+
+    let closed = position.base;
+    let close_cost = market.base_value(-closed, price, true)?;
+    let realized = position.apply_fill(market, -closed, close_cost)?;
+    ctx.accounts.vault_a.settle_pnl(realized)?;
+
+    let fee = market
+        .margin_requirement(closed, price, market.liquidation_fee_bps)?
+        .min(equity as u64);
+    let insurance_fee = mul_div_ceil(fee, LIQUIDATION_INSURANCE_SHARE_BPS, BPS_DENOMINATOR)?;
+    let liquidator_fee = fee - insurance_fee;
+    position.quote -= fee as i64;
+    market.insurance_fund = market
+        .insurance_fund
+        .checked_add(insurance_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let liquidator_balance_a = &mut ctx.accounts.liquidator_balance_a;
+    liquidator_balance_a.bind(
+        ctx.accounts.liquidator_agent.key(),
+        ctx.accounts.vault_a.key(),
+        ctx.bumps.liquidator_balance_a,
+    );
+    liquidator_balance_a.credit(liquidator_fee)?;
+
+    emit!(PositionLiquidated {
+        market: market.key(),
+        agent: position.agent,
+        liquidator: ctx.accounts.liquidator_agent.key(),
+        base: closed,
+        price,
+        liquidator_fee,
+        insurance_fee,
+    });
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{Agent, Bond, EmissionsSchedule, Market};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct LockBond<'info> {
+    #[account(address = schedule.market @ ErrorCode::InvalidMarket)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"emissions", market.key().as_ref(), schedule.reward_mint.as_ref()],
+        bump = schedule.bump
+    )]
+    pub schedule: Account<'info, EmissionsSchedule>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Bond::INIT_SPACE,
+        seeds = [b"bond", schedule.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub bond: Account<'info, Bond>,
+    #[account(constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner)]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(address = schedule.reward_mint @ ErrorCode::InvalidTokenMint)]
+    pub reward_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = bond
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = user
+    )]
+    pub user_reward_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Adds `amount` reward tokens to the agent's bond and locks the whole bond
+/// until `unlock`, which can't come before its current unlock.
+pub fn lock_bond(ctx: Context<LockBond>, amount: u64, unlock: u64) -> Result<()> {
+    if amount == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+    let market = &ctx.accounts.market;
+    let bond = &mut ctx.accounts.bond;
+    bond.schedule = ctx.accounts.schedule.key();
+    bond.agent = ctx.accounts.agent.key();
+    bond.bump = ctx.bumps.bond;
+    bond.settle_fees(&ctx.accounts.schedule)?;
+    bond.relock(unlock, market.now()?, market.epoch_slots.max(1))?;
+    bond.amount = bond.amount.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    let schedule = &mut ctx.accounts.schedule;
+    schedule.bonded = schedule.bonded.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_reward_account.to_account_info(),
+        to: ctx.accounts.bond_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)
+}
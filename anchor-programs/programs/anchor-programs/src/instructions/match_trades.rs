@@ -0,0 +1,516 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Agent, Balance, Bond, Config, CopyFeed, CopyFill, EmissionsAccount, EmissionsSchedule, GlobalStats, Leaderboard, Market, RewardRecord, Snapshot, Trade, Vault,
+    FEATURE_ORDER_BOOK, MATCHING_PRO_RATA,
+    STP_CANCEL_NEWEST, STP_CANCEL_OLDEST, STP_DECREMENT_BOTH,
+};
+use crate::errors::ErrorCode;
+use crate::events::{CircuitBreakerTripped, OrderFilled, OrderUpdated};
+use crate::invariants::strict_check;
+use crate::randomness;
+
+#[derive(Accounts)]
+pub struct MatchTrades<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_ORDER_BOOK) @ ErrorCode::FeatureDisabled,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        constraint = bid.trade_type == 0 @ ErrorCode::InvalidTradeType
+    )]
+    pub bid: Account<'info, Trade>,
+
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        constraint = ask.trade_type == 1 @ ErrorCode::InvalidTradeType
+    )]
+    pub ask: Account<'info, Trade>,
+
+    #[account(mut, constraint = bid_agent.key() == bid.agent @ ErrorCode::InvalidAgent)]
+    pub bid_agent: Account<'info, Agent>,
+
+    #[account(mut, constraint = ask_agent.key() == ask.agent @ ErrorCode::InvalidAgent)]
+    pub ask_agent: Account<'info, Agent>,
+
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", vault_a.key().as_ref(), bid_agent.key().as_ref()],
+        bump = bid_balance_a.bump
+    )]
+    pub bid_balance_a: Account<'info, Balance>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_b.key().as_ref(), bid_agent.key().as_ref()],
+        bump
+    )]
+    pub bid_balance_b: Account<'info, Balance>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), ask_agent.key().as_ref()],
+        bump
+    )]
+    pub ask_balance_a: Account<'info, Balance>,
+
+    #[account(
+        mut,
+        seeds = [b"balance", vault_b.key().as_ref(), ask_agent.key().as_ref()],
+        bump = ask_balance_b.bump
+    )]
+    pub ask_balance_b: Account<'info, Balance>,
+
+    #[account(
+        mut,
+        seeds = [b"copy_feed", market.key().as_ref(), bid_agent.key().as_ref()],
+        bump = bid_copy_feed.bump
+    )]
+    pub bid_copy_feed: Option<Account<'info, CopyFeed>>,
+
+    #[account(
+        mut,
+        seeds = [b"copy_feed", market.key().as_ref(), ask_agent.key().as_ref()],
+        bump = ask_copy_feed.bump
+    )]
+    pub ask_copy_feed: Option<Account<'info, CopyFeed>>,
+
+    /// The current epoch's, if it has been opened
+    #[account(
+        mut,
+        seeds = [b"leaderboard", market.key().as_ref(), &leaderboard.epoch.to_le_bytes()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+    /// The current epoch's, if it has been taken, for its fill histograms
+    #[account(
+        mut,
+        seeds = [b"snapshot", market.key().as_ref(), &snapshot.epoch.to_le_bytes()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Option<Account<'info, Snapshot>>,
+    /// CHECK: the market's tie-break randomness, pinned by address; needed
+    /// on a pro-rata market that names one
+    #[account(address = market.randomness_account @ ErrorCode::RandomnessUnavailable)]
+    pub randomness: Option<UncheckedAccount<'info>>,
+
+    /// Counts the fill toward the schedule's emissions, with both agents'
+    /// accounts under it
+    #[account(
+        mut,
+        seeds = [b"emissions", market.key().as_ref(), emissions.reward_mint.as_ref()],
+        bump = emissions.bump
+    )]
+    pub emissions: Option<Account<'info, EmissionsSchedule>>,
+    #[account(
+        mut,
+        seeds = [b"emissions_account", bid_emissions.schedule.as_ref(), bid_agent.key().as_ref()],
+        bump = bid_emissions.bump
+    )]
+    pub bid_emissions: Option<Account<'info, EmissionsAccount>>,
+    #[account(
+        mut,
+        seeds = [b"emissions_account", ask_emissions.schedule.as_ref(), ask_agent.key().as_ref()],
+        bump = ask_emissions.bump
+    )]
+    pub ask_emissions: Option<Account<'info, EmissionsAccount>>,
+    /// Boost the agents' counted volume
+    #[account(
+        seeds = [b"bond", bid_bond.schedule.as_ref(), bid_agent.key().as_ref()],
+        bump = bid_bond.bump
+    )]
+    pub bid_bond: Option<Account<'info, Bond>>,
+    #[account(
+        seeds = [b"bond", ask_bond.schedule.as_ref(), ask_agent.key().as_ref()],
+        bump = ask_bond.bump
+    )]
+    pub ask_bond: Option<Account<'info, Bond>>,
+    /// The current epoch's, counting the fill toward the agents' rewards
+    #[account(
+        mut,
+        seeds = [b"reward", market.key().as_ref(), bid_agent.key().as_ref(), &bid_reward.epoch.to_le_bytes()],
+        bump = bid_reward.bump
+    )]
+    pub bid_reward: Option<Account<'info, RewardRecord>>,
+    #[account(
+        mut,
+        seeds = [b"reward", market.key().as_ref(), ask_agent.key().as_ref(), &ask_reward.epoch.to_le_bytes()],
+        bump = ask_reward.bump
+    )]
+    pub ask_reward: Option<Account<'info, RewardRecord>>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: on a pro-rata market, the other orders resting at
+    // the maker's price that have yet to fill against the taker
+}
+
+/// Fills a crossing bid and ask. `crank_sequence` must be the market's next
+/// one, so when several keepers race to crank the same state only the first
+/// lands and the rest fail instead of filling twice.
+///
+/// On a pro-rata market the maker fills only its share of the taker, by
+/// displayed size, among the orders at its price. Cranking a level's makers
+/// in turn, each passing those still to come, splits the taker in proportion,
+/// with the rounding left to the last; the market counts each level's orders
+/// and displayed size, so the orders passed must be all of them. A market
+/// with tie-break randomness fixes that turn order: the maker must come
+/// first, by `randomness::rank` under this crank's seed, among itself and
+/// the orders passed.
+pub fn match_trades<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MatchTrades<'info>>,
+    crank_sequence: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.take_crank(crank_sequence)?;
+    market.ensure_continuous()?;
+
+    let bid = &ctx.accounts.bid;
+    let ask = &ctx.accounts.ask;
+
+    // Bid pays amount_in A for amount_out B, ask gives amount_in B for amount_out A
+    let crosses = bid.amount_in as u128 * ask.amount_in as u128
+        >= ask.amount_out as u128 * bid.amount_out as u128;
+    if !crosses {
+        return err!(ErrorCode::TradesDoNotCross);
+    }
+    let mut quantity = bid.displayed().min(ask.displayed());
+    if quantity == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+
+    let bid_is_maker = bid.sequence < ask.sequence;
+
+    // A post-only order that arrives crossing the book is rejected, not filled
+    let taker_post_only = if bid_is_maker {
+        ask.is_post_only()
+    } else {
+        bid.is_post_only()
+    };
+    if taker_post_only {
+        let taker = if bid_is_maker {
+            &mut ctx.accounts.ask
+        } else {
+            &mut ctx.accounts.bid
+        };
+        taker.cancel();
+        return OrderUpdated::emit(&mut ctx.accounts.market, taker);
+    }
+
+    // Reduce-only asks can sell down to a flat inventory but never past it
+    if ask.is_reduce_only() {
+        quantity = quantity.min(ctx.accounts.ask_balance_b.amount);
+        if quantity == 0 {
+            ctx.accounts.ask.cancel();
+            return OrderUpdated::emit(&mut ctx.accounts.market, &mut ctx.accounts.ask);
+        }
+    }
+
+    let mut tie_break_seed = [0; 32];
+    let pro_rata = ctx.accounts.market.matching_policy == MATCHING_PRO_RATA;
+    let mut maker_displayed = 0;
+    if pro_rata {
+        let market = &ctx.accounts.market;
+        let seed = if market.randomness_account == Pubkey::default() {
+            None
+        } else {
+            let randomness = ctx.accounts.randomness.as_ref().ok_or(ErrorCode::RandomnessUnavailable)?;
+            tie_break_seed = randomness::seed(&market.key(), crank_sequence, &randomness.try_borrow_data()?)?;
+            Some(tie_break_seed)
+        };
+        let (maker, taker) = if bid_is_maker {
+            (&ctx.accounts.bid, &ctx.accounts.ask)
+        } else {
+            (&ctx.accounts.ask, &ctx.accounts.bid)
+        };
+        let share = pro_rata_share(&mut ctx.accounts.market, maker, taker, ctx.remaining_accounts, seed)?;
+        quantity = quantity.min(share);
+        maker_displayed = maker.displayed();
+    }
+
+    // Self-trade prevention follows the taker's (newest order's) policy
+    if ctx.accounts.bid_agent.owner == ctx.accounts.ask_agent.owner {
+        let stp_mode = if bid_is_maker {
+            ctx.accounts.ask_agent.stp_mode
+        } else {
+            ctx.accounts.bid_agent.stp_mode
+        };
+        let market = &mut ctx.accounts.market;
+        let (bid, ask) = (&mut ctx.accounts.bid, &mut ctx.accounts.ask);
+        let (maker, taker) = if bid_is_maker { (bid, ask) } else { (ask, bid) };
+        match stp_mode {
+            STP_CANCEL_NEWEST => {
+                taker.cancel();
+                OrderUpdated::emit(market, taker)?;
+            }
+            STP_CANCEL_OLDEST => {
+                maker.cancel();
+                OrderUpdated::emit(market, maker)?;
+            }
+            STP_DECREMENT_BOTH => {
+                take(maker, market, quantity)?;
+                take(taker, market, quantity)?;
+                OrderUpdated::emit(market, maker)?;
+                OrderUpdated::emit(market, taker)?;
+            }
+            _ => return err!(ErrorCode::InvalidStpMode),
+        }
+        return Ok(());
+    }
+
+    // Fills execute at the resting (maker) order's price
+    let (price_num, price_den) = if bid_is_maker {
+        (bid.amount_in, bid.amount_out)
+    } else {
+        (ask.amount_out, ask.amount_in)
+    };
+    let quote_amount = u64::try_from(quantity as u128 * price_num as u128 / price_den as u128)
+        .map_err(|_| ErrorCode::MathOverflow)?;
+
+    let slot = Clock::get()?.slot;
+    let move_bps = ctx.accounts.market.record_fill_price(quote_amount, quantity, slot)?;
+    CircuitBreakerTripped::check(&mut ctx.accounts.market, move_bps)?;
+
+    // Fee tiers are picked from volume traded before this fill
+    let epoch = ctx.accounts.market.current_epoch(ctx.accounts.market.now()?);
+    ctx.accounts.bid_agent.roll_volume(epoch);
+    ctx.accounts.ask_agent.roll_volume(epoch);
+    let bid_volume = ctx.accounts.bid_agent.rolling_volume();
+    let ask_volume = ctx.accounts.ask_agent.rolling_volume();
+    ctx.accounts.bid_agent.record_volume(quote_amount);
+    ctx.accounts.ask_agent.record_volume(quote_amount);
+    if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+        if leaderboard.epoch != epoch {
+            return err!(ErrorCode::InvalidEpoch);
+        }
+        for agent in [&ctx.accounts.bid_agent, &ctx.accounts.ask_agent] {
+            leaderboard.rank(agent.key(), agent.epoch_volume());
+        }
+    }
+    if let Some(snapshot) = ctx.accounts.snapshot.as_mut() {
+        if snapshot.epoch != epoch {
+            return err!(ErrorCode::InvalidEpoch);
+        }
+        snapshot.record_fill(quantity, move_bps);
+    }
+    if let Some(schedule) = ctx.accounts.emissions.as_mut() {
+        let (Some(bid_emissions), Some(ask_emissions)) =
+            (ctx.accounts.bid_emissions.as_mut(), ctx.accounts.ask_emissions.as_mut())
+        else {
+            return err!(ErrorCode::EmissionsAccountRequired);
+        };
+        if bid_emissions.schedule != schedule.key() || ask_emissions.schedule != schedule.key() {
+            return err!(ErrorCode::InvalidEmissionsSchedule);
+        }
+        let bid_bond = ctx.accounts.bid_bond.as_deref();
+        let ask_bond = ctx.accounts.ask_bond.as_deref();
+        if [bid_bond, ask_bond].into_iter().flatten().any(|bond| bond.schedule != schedule.key()) {
+            return err!(ErrorCode::InvalidEmissionsSchedule);
+        }
+        let now = ctx.accounts.market.now()?;
+        let epoch_slots = ctx.accounts.market.epoch_slots.max(1);
+        let bid_volume = schedule.boosted(quote_amount, bid_bond, now, epoch_slots)?;
+        let ask_volume = schedule.boosted(quote_amount, ask_bond, now, epoch_slots)?;
+        if bid_is_maker {
+            schedule.record(bid_emissions, epoch, epoch_slots, bid_volume, 0)?;
+            schedule.record(ask_emissions, epoch, epoch_slots, 0, ask_volume)?;
+        } else {
+            schedule.record(ask_emissions, epoch, epoch_slots, ask_volume, 0)?;
+            schedule.record(bid_emissions, epoch, epoch_slots, 0, bid_volume)?;
+        }
+    }
+
+    // Fees are charged in token A; the resting side pays maker, the crossing side taker
+    let (maker_volume, taker_volume) = if bid_is_maker {
+        (bid_volume, ask_volume)
+    } else {
+        (ask_volume, bid_volume)
+    };
+    let (maker_fee, taker_fee) = ctx
+        .accounts
+        .market
+        .fill_fees(quote_amount, maker_volume, taker_volume)?;
+    let (bid_fee, ask_fee) = if bid_is_maker {
+        (maker_fee, taker_fee as i64)
+    } else {
+        (taker_fee as i64, maker_fee)
+    };
+    let bid_pays = u64::try_from(quote_amount as i128 + bid_fee as i128)
+        .map_err(|_| ErrorCode::MathOverflow)?;
+    let ask_receives = u64::try_from(quote_amount as i128 - ask_fee as i128)
+        .map_err(|_| ErrorCode::MathOverflow)?;
+
+    // What the bid pays is what the ask receives plus the fees, and rebates
+    // never exceed the taker fee they're paid from
+    strict_check!(
+        bid_pays as i128 == ask_receives as i128 + bid_fee as i128 + ask_fee as i128,
+        "a fill conserves token A"
+    );
+    strict_check!(bid_fee as i128 + ask_fee as i128 >= 0, "a fill's fees are non-negative");
+
+    let fees = u64::try_from(bid_fee as i128 + ask_fee as i128).map_err(|_| ErrorCode::MathOverflow)?;
+    let market = &mut ctx.accounts.market;
+    market.fees_accrued = market.fees_accrued.checked_add(fees).ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.global_stats.record_fill(quote_amount, fees, 0)?;
+
+    take(&mut ctx.accounts.bid, &mut ctx.accounts.market, quantity)?;
+    take(&mut ctx.accounts.ask, &mut ctx.accounts.market, quantity)?;
+    OrderUpdated::emit(&mut ctx.accounts.market, &mut ctx.accounts.bid)?;
+    OrderUpdated::emit(&mut ctx.accounts.market, &mut ctx.accounts.ask)?;
+    if pro_rata {
+        let (bid, ask) = (&mut ctx.accounts.bid, &mut ctx.accounts.ask);
+        let (maker, taker) = if bid_is_maker { (bid, ask) } else { (ask, bid) };
+        ctx.accounts.market.end_pro_rata_turn(maker, maker_displayed, taker);
+    }
+
+    ctx.accounts.bid_balance_a.debit(bid_pays)?;
+    ctx.accounts.ask_balance_b.debit(quantity)?;
+
+    let ask_balance_a = &mut ctx.accounts.ask_balance_a;
+    ask_balance_a.agent = ctx.accounts.ask_agent.key();
+    ask_balance_a.vault = ctx.accounts.vault_a.key();
+    ask_balance_a.bump = ctx.bumps.ask_balance_a;
+    ask_balance_a.credit(ask_receives)?;
+
+    let bid_balance_b = &mut ctx.accounts.bid_balance_b;
+    bid_balance_b.agent = ctx.accounts.bid_agent.key();
+    bid_balance_b.vault = ctx.accounts.vault_b.key();
+    bid_balance_b.bump = ctx.bumps.bid_balance_b;
+    bid_balance_b.credit(quantity)?;
+
+    // Leaders with followers get the fill logged for copying
+    for (copy_feed, trade_type) in [
+        (ctx.accounts.bid_copy_feed.as_mut(), 0),
+        (ctx.accounts.ask_copy_feed.as_mut(), 1),
+    ] {
+        if let Some(copy_feed) = copy_feed {
+            copy_feed.record(CopyFill {
+                trade_type,
+                quantity,
+                quote_amount,
+            })?;
+        }
+    }
+
+    let now = ctx.accounts.market.now()?;
+    for (reward, trade_type, fee) in [
+        (ctx.accounts.bid_reward.as_mut(), 0, bid_fee),
+        (ctx.accounts.ask_reward.as_mut(), 1, ask_fee),
+    ] {
+        if let Some(reward) = reward {
+            reward.record_fill(epoch, trade_type, quantity, quote_amount, fee, now)?;
+        }
+    }
+
+    emit!(OrderFilled {
+        market: ctx.accounts.market.key(),
+        bid_agent: ctx.accounts.bid_agent.key(),
+        ask_agent: ctx.accounts.ask_agent.key(),
+        bid_tag: ctx.accounts.bid.tag,
+        ask_tag: ctx.accounts.ask.tag,
+        bid_is_maker,
+        quantity,
+        quote_amount,
+        bid_fee,
+        ask_fee,
+        tie_break_seed,
+    });
+    Ok(())
+}
+
+/// `maker`'s share of the taker in the market's pro-rata round at its
+/// level: of the taker and level as the round began, by displayed size, at
+/// least one unit so every crank fills, and what the taker has left for the
+/// level's last maker. `rest` must be the level's other makers yet to fill in
+/// the round, as the market counts them; with a tie-break `seed` the maker
+/// must also rank first among them.
+fn pro_rata_share<'info>(
+    market: &mut Market,
+    maker: &Account<'info, Trade>,
+    taker: &Account<'info, Trade>,
+    rest: &'info [AccountInfo<'info>],
+    seed: Option<[u8; 32]>,
+) -> Result<u64> {
+    let round = market.pro_rata_round_for(maker, taker)?;
+    if maker.pro_rata_round == round.round {
+        return err!(ErrorCode::IncompleteLevel);
+    }
+    if let Some(seed) = seed {
+        let maker_rank = randomness::rank(&seed, &maker.key());
+        if rest.iter().any(|info| randomness::rank(&seed, info.key) < maker_rank) {
+            return err!(ErrorCode::OutOfTieBreakOrder);
+        }
+    }
+    let mut orders = 1;
+    let mut displayed = maker.displayed() as u128;
+    let mut seen = vec![maker.key()];
+    for info in rest {
+        if seen.contains(&info.key()) {
+            return err!(ErrorCode::DuplicateOrder);
+        }
+        seen.push(info.key());
+        let order = Account::<Trade>::try_from(info)?;
+        if order.market != maker.market {
+            return err!(ErrorCode::InvalidMarket);
+        }
+        if order.booked_level != maker.booked_level {
+            return err!(ErrorCode::NotAtMakerLevel);
+        }
+        if order.pro_rata_round == round.round {
+            return err!(ErrorCode::IncompleteLevel);
+        }
+        orders += 1;
+        displayed += order.displayed() as u128;
+    }
+    if orders != round.orders_left || displayed != round.displayed_left as u128 {
+        return err!(ErrorCode::IncompleteLevel);
+    }
+    if round.orders_left == 1 {
+        return Ok(taker.displayed());
+    }
+    let share = round.taker_size as u128 * maker.displayed() as u128 / round.level_size.max(1) as u128;
+    Ok((share as u64).max(1))
+}
+
+pub(crate) fn take(trade: &mut Trade, market: &mut Market, quantity: u64) -> Result<()> {
+    trade.reduce(quantity)?;
+    if trade.consume_display(quantity) {
+        let previous = trade.sequence;
+        trade.sequence = market.next_sequence()?;
+        strict_check!(trade.sequence > previous, "a refreshed order queues behind its old place");
+    }
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, spl_token::instruction::AuthorityType, Mint, MintTo, SetAuthority, Token, TokenAccount},
+};
+
+use crate::adapters::{create_metadata_instruction, metadata_address, TOKEN_METADATA_PROGRAM_ID};
+use crate::Agent;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct MintAgentNft<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", agent.registrant.as_ref()],
+        bump = agent.bump,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner,
+        constraint = agent.nft_mint == Pubkey::default() @ ErrorCode::AgentNftExists
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"agent_nft", agent.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = agent,
+        mint::freeze_authority = agent
+    )]
+    pub nft_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = nft_mint,
+        associated_token::authority = user
+    )]
+    pub user_nft_account: Account<'info, TokenAccount>,
+    /// CHECK: created by the token metadata program at its PDA for `nft_mint`
+    #[account(
+        mut,
+        address = metadata_address(&nft_mint.key())
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: pinned to the Metaplex token metadata program
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Mints the agent's one-of-one identity NFT to its owner. Whoever holds the
+/// NFT can take ownership of the agent through `transfer_agent`, and the
+/// agent PDA stays the metadata's update authority.
+pub fn mint_agent_nft(
+    ctx: Context<MintAgentNft>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let agent = &ctx.accounts.agent;
+    let seeds = &[b"agent", agent.registrant.as_ref(), &[agent.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.nft_mint.to_account_info(),
+        to: ctx.accounts.user_nft_account.to_account_info(),
+        authority: agent.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::mint_to(cpi_ctx, 1)?;
+
+    let ix = create_metadata_instruction(
+        &ctx.accounts.metadata.key(),
+        &ctx.accounts.nft_mint.key(),
+        &agent.key(),
+        &ctx.accounts.user.key(),
+        &name,
+        &symbol,
+        &uri,
+    );
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.metadata.to_account_info(),
+            ctx.accounts.nft_mint.to_account_info(),
+            agent.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_metadata_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    // Fix the supply at one
+    let cpi_accounts = SetAuthority {
+        current_authority: agent.to_account_info(),
+        account_or_mint: ctx.accounts.nft_mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+
+    // From here on the agent changes hands with the NFT, so an open offer lapses
+    let agent = &mut ctx.accounts.agent;
+    agent.nft_mint = ctx.accounts.nft_mint.key();
+    agent.pending_owner = Pubkey::default();
+    Ok(())
+}
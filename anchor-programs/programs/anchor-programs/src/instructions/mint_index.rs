@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount, Transfer},
+};
+
+use crate::{IndexBasket, IndexComponent};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct MintIndex<'info> {
+    #[account(has_one = basket_mint @ ErrorCode::InvalidTokenMint)]
+    pub index: Account<'info, IndexBasket>,
+    #[account(mut)]
+    pub basket_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = basket_mint,
+        associated_token::authority = user
+    )]
+    pub user_basket_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: per component, in index order,
+    // [user component account, index component account]
+}
+
+pub fn mint_index<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MintIndex<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let index = &ctx.accounts.index;
+    let pairs = component_accounts(index, ctx.remaining_accounts)?;
+    for (component, user_account, index_account) in pairs {
+        if user_account.owner != ctx.accounts.user.key() {
+            return err!(ErrorCode::InvalidTokenAccounts);
+        }
+        let required = IndexBasket::component_amount(&component, amount, true)?;
+        let cpi_accounts = Transfer {
+            from: user_account.to_account_info(),
+            to: index_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, required)?;
+    }
+
+    let index_id = index.index_id.to_le_bytes();
+    let seeds = &[b"index", index_id.as_ref(), &[index.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.basket_mint.to_account_info(),
+        to: ctx.accounts.user_basket_account.to_account_info(),
+        authority: index.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::mint_to(cpi_ctx, amount)
+}
+
+pub(crate) type ComponentAccounts<'info> = (
+    IndexComponent,
+    Account<'info, TokenAccount>,
+    Account<'info, TokenAccount>,
+);
+
+/// Pairs each component with its (user, index) token accounts from
+/// `remaining_accounts`, checking mints and that the index side is owned by
+/// the index.
+pub(crate) fn component_accounts<'info>(
+    index: &Account<'info, IndexBasket>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<ComponentAccounts<'info>>> {
+    let components = index.components();
+    if remaining_accounts.len() != components.len() * 2 {
+        return err!(ErrorCode::InvalidIndexComponents);
+    }
+    components
+        .iter()
+        .zip(remaining_accounts.chunks(2))
+        .map(|(component, accounts)| {
+            let user_account = Account::<TokenAccount>::try_from(&accounts[0])?;
+            let index_account = Account::<TokenAccount>::try_from(&accounts[1])?;
+            if user_account.mint != component.mint
+                || index_account.mint != component.mint
+                || index_account.owner != index.key()
+            {
+                return err!(ErrorCode::InvalidTokenAccounts);
+            }
+            Ok((*component, user_account, index_account))
+        })
+        .collect()
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount, Transfer},
+};
+
+use crate::{BinaryMarket, OUTCOME_UNRESOLVED};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct MintOutcomeTokens<'info> {
+    #[account(
+        has_one = collateral_mint @ ErrorCode::InvalidTokenMint,
+        has_one = yes_mint @ ErrorCode::InvalidTokenMint,
+        has_one = no_mint @ ErrorCode::InvalidTokenMint,
+        constraint = binary_market.outcome == OUTCOME_UNRESOLVED @ ErrorCode::MarketAlreadyResolved
+    )]
+    pub binary_market: Account<'info, BinaryMarket>,
+    pub collateral_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub yes_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub no_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = binary_market
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = collateral_mint,
+        token::authority = user
+    )]
+    pub user_collateral_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = yes_mint,
+        associated_token::authority = user
+    )]
+    pub user_yes_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = no_mint,
+        associated_token::authority = user
+    )]
+    pub user_no_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Locks `amount` of collateral and mints one YES and one NO token per unit.
+pub fn mint_outcome_tokens(ctx: Context<MintOutcomeTokens>, amount: u64) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_collateral_account.to_account_info(),
+        to: ctx.accounts.collateral_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    let binary_market = &ctx.accounts.binary_market;
+    let market_id = binary_market.market_id.to_le_bytes();
+    let seeds = &[
+        b"binary_market",
+        binary_market.collateral_mint.as_ref(),
+        market_id.as_ref(),
+        &[binary_market.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    for (mint, to) in [
+        (&ctx.accounts.yes_mint, &ctx.accounts.user_yes_account),
+        (&ctx.accounts.no_mint, &ctx.accounts.user_no_account),
+    ] {
+        let cpi_accounts = MintTo {
+            mint: mint.to_account_info(),
+            to: to.to_account_info(),
+            authority: binary_market.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::mint_to(cpi_ctx, amount)?;
+    }
+    Ok(())
+}
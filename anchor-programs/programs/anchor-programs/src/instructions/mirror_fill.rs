@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use crate::oracle::read_price;
+use crate::{Agent, Balance, CopyFeed, GlobalStats, Market, RewardRecord, Subscription, Vault, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+use crate::math::mul_div_floor;
+
+#[derive(Accounts)]
+pub struct FundMirrorLiquidity<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), vault.token.as_ref()],
+        bump = vault.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault.key().as_ref(), agent.key().as_ref()],
+        bump = balance.bump
+    )]
+    pub balance: Account<'info, Balance>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MirrorFill<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        seeds = [b"copy_feed", market.key().as_ref(), subscription.leader.as_ref()],
+        bump = copy_feed.bump
+    )]
+    pub copy_feed: Account<'info, CopyFeed>,
+
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), subscription.follower.as_ref()],
+        bump
+    )]
+    pub follower_balance_a: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_b.key().as_ref(), subscription.follower.as_ref()],
+        bump
+    )]
+    pub follower_balance_b: Account<'info, Balance>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_a.key().as_ref(), subscription.leader.as_ref()],
+        bump
+    )]
+    pub leader_balance_a: Account<'info, Balance>,
+    /// The current epoch's, counting the fill toward the follower's reward
+    #[account(
+        mut,
+        seeds = [
+            b"reward",
+            market.key().as_ref(),
+            subscription.follower.as_ref(),
+            &follower_reward.epoch.to_le_bytes()
+        ],
+        bump = follower_reward.bump
+    )]
+    pub follower_reward: Option<Account<'info, RewardRecord>>,
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Donates tracked tokens to the vault's own liquidity, which takes the other
+/// side of mirrored fills and of PnL realized by perp positions.
+pub fn fund_mirror_liquidity(ctx: Context<FundMirrorLiquidity>, amount: u64) -> Result<()> {
+    ctx.accounts.balance.debit(amount)?;
+    ctx.accounts.vault.mirror_in(amount)
+}
+
+/// Copies the subscription's next leader fill into the follower's balances,
+/// with the vault's own liquidity taking the other side at the market's
+/// current price plus the base taker fee. A buy the follower cannot afford,
+/// or the vault cannot fill, fails and stays next to mirror; sells are
+/// capped at the token B bought by copying, and fills that dropped out of
+/// the feed before being mirrored are skipped. `crank_sequence` must be the
+/// market's next, as for `match_trades`.
+pub fn mirror_fill(ctx: Context<MirrorFill>, crank_sequence: u64) -> Result<()> {
+    ctx.accounts.market.take_crank(crank_sequence)?;
+    let subscription = &mut ctx.accounts.subscription;
+    let copy_feed = &ctx.accounts.copy_feed;
+    subscription.next_fill = subscription.next_fill.max(copy_feed.oldest());
+    let fill = copy_feed
+        .fill(subscription.next_fill)
+        .ok_or(ErrorCode::NoFillToMirror)?;
+    subscription.next_fill += 1;
+
+    let (follower, leader) = (subscription.follower, subscription.leader);
+    let follower_a = &mut ctx.accounts.follower_balance_a;
+    follower_a.bind(follower, ctx.accounts.vault_a.key(), ctx.bumps.follower_balance_a);
+    let follower_b = &mut ctx.accounts.follower_balance_b;
+    follower_b.bind(follower, ctx.accounts.vault_b.key(), ctx.bumps.follower_balance_b);
+    let leader_a = &mut ctx.accounts.leader_balance_a;
+    leader_a.bind(leader, ctx.accounts.vault_a.key(), ctx.bumps.leader_balance_a);
+
+    let market = &mut ctx.accounts.market;
+    let token_a = market.token_a;
+    let mut quantity = mul_div_floor(fill.quantity, subscription.fraction_bps as u64, BPS_DENOMINATOR)?;
+    if fill.trade_type == 1 {
+        quantity = quantity.min(subscription.base_held).min(follower_b.amount);
+    }
+    if quantity == 0 {
+        return Ok(());
+    }
+    // The leader's fill may be long stale, so the copy trades at the current
+    // price: buys are charged rounding up, sells paid rounding down
+    let price = read_price(
+        market,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+        Clock::get()?.slot,
+    )?
+    .price;
+    let base = i64::try_from(quantity).map_err(|_| ErrorCode::MathOverflow)?;
+    let value = market.base_value(base, price, fill.trade_type == 0)?;
+    let quote = u64::try_from(value).map_err(|_| ErrorCode::MathOverflow)?;
+    let fee = market.taker_fee(quote)?;
+
+    let (vault_a, vault_b) = (&mut ctx.accounts.vault_a, &mut ctx.accounts.vault_b);
+    if fill.trade_type == 0 {
+        let pays = quote.checked_add(fee).ok_or(ErrorCode::MathOverflow)?;
+        follower_a.debit(pays)?;
+        vault_a.mirror_in(quote)?;
+        vault_b.mirror_out(quantity)?;
+        follower_b.credit(quantity)?;
+        subscription.base_held = subscription
+            .base_held
+            .checked_add(quantity)
+            .ok_or(ErrorCode::MathOverflow)?;
+        subscription.cost_basis = subscription
+            .cost_basis
+            .checked_add(quote)
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        let cost = mul_div_floor(subscription.cost_basis, quantity, subscription.base_held)?;
+        subscription.base_held -= quantity;
+        subscription.cost_basis -= cost;
+        let proceeds = quote.saturating_sub(fee);
+        let performance_fee = mul_div_floor(
+            proceeds.saturating_sub(cost),
+            subscription.performance_fee_bps as u64,
+            BPS_DENOMINATOR,
+        )?;
+        follower_b.debit(quantity)?;
+        vault_b.mirror_in(quantity)?;
+        vault_a.mirror_out(quote)?;
+        follower_a.credit(proceeds - performance_fee)?;
+        leader_a.credit(performance_fee)?;
+    }
+    ctx.accounts.global_stats.record_fill(quote, fee, 0)?;
+    if let Some(reward) = ctx.accounts.follower_reward.as_mut() {
+        let now = market.now()?;
+        let fee = i64::try_from(fee).map_err(|_| ErrorCode::MathOverflow)?;
+        reward.record_fill(market.current_epoch(now), fill.trade_type, quantity, quote, fee, now)?;
+    }
+    market.accrue_fee(token_a, fee)
+}
@@ -4,10 +4,220 @@ pub mod deposit_tokens;
 pub mod withdraw_tokens;
 pub mod place_trade;
 pub mod execute_trade;
+pub mod modify_trade;
+pub mod cancel_trade;
+pub mod match_trades;
+pub mod set_stp_mode;
+pub mod set_market_fees;
+pub mod set_settlement_mode;
+pub mod set_time_base;
+pub mod open_settlement;
+pub mod settle_epoch;
+pub mod create_otc_offer;
+pub mod accept_otc_offer;
+pub mod cancel_otc_offer;
+pub mod create_rfq;
+pub mod submit_quote;
+pub mod accept_quote;
+pub mod cancel_rfq;
+pub mod write_option;
+pub mod buy_option;
+pub mod exercise_option;
+pub mod expire_option;
+pub mod create_binary_market;
+pub mod mint_outcome_tokens;
+pub mod redeem_outcome_tokens;
+pub mod resolve_market;
+pub mod create_index;
+pub mod mint_index;
+pub mod redeem_index;
+pub mod flash_swap;
+pub mod flash_loan;
+pub mod repay_flash_loan;
+pub mod set_sandwich_guard;
 
 pub use initialize_market::*;
 pub use register_agent::*;
 pub use deposit_tokens::*;
 pub use withdraw_tokens::*;
 pub use place_trade::*;
-pub use execute_trade::*;
\ No newline at end of file
+pub use execute_trade::*;
+pub use modify_trade::*;
+pub use cancel_trade::*;
+pub use match_trades::*;
+pub use set_stp_mode::*;
+pub use set_market_fees::*;
+pub use set_settlement_mode::*;
+pub use set_time_base::*;
+pub use open_settlement::*;
+pub use settle_epoch::*;
+pub use create_otc_offer::*;
+pub use accept_otc_offer::*;
+pub use cancel_otc_offer::*;
+pub use create_rfq::*;
+pub use submit_quote::*;
+pub use accept_quote::*;
+pub use cancel_rfq::*;
+pub use write_option::*;
+pub use buy_option::*;
+pub use exercise_option::*;
+pub use expire_option::*;
+pub use create_binary_market::*;
+pub use mint_outcome_tokens::*;
+pub use redeem_outcome_tokens::*;
+pub use resolve_market::*;
+pub use create_index::*;
+pub use mint_index::*;
+pub use redeem_index::*;
+pub use flash_swap::*;
+pub use flash_loan::*;
+pub use repay_flash_loan::*;
+pub use set_sandwich_guard::*;
+pub mod set_oracle_config;
+pub use set_oracle_config::*;
+pub mod create_price_cache;
+pub use create_price_cache::*;
+pub mod post_price_update;
+pub use post_price_update::*;
+pub mod set_jupiter_routing;
+pub use set_jupiter_routing::*;
+pub mod execute_trade_via_jupiter;
+pub use execute_trade_via_jupiter::*;
+pub mod set_mirror_pool;
+pub use set_mirror_pool::*;
+pub mod execute_mirror_trade;
+pub use execute_mirror_trade::*;
+pub mod openbook_order;
+pub use openbook_order::*;
+pub mod create_subaccount;
+pub use create_subaccount::*;
+pub mod start_session;
+pub use start_session::*;
+pub mod place_signed_trade;
+pub use place_signed_trade::*;
+pub mod set_relayer;
+pub use set_relayer::*;
+pub mod reimburse_relayer;
+pub use reimburse_relayer::*;
+pub mod set_perp_params;
+pub use set_perp_params::*;
+pub mod position_collateral;
+pub use position_collateral::*;
+pub mod modify_position;
+pub use modify_position::*;
+pub mod liquidate_position;
+pub use liquidate_position::*;
+pub mod declare_bankruptcy;
+pub use declare_bankruptcy::*;
+pub mod fund_insurance;
+pub use fund_insurance::*;
+pub mod update_adl_rank;
+pub use update_adl_rank::*;
+pub mod auto_deleverage;
+pub use auto_deleverage::*;
+pub mod set_open_interest_limits;
+pub use set_open_interest_limits::*;
+pub mod set_funding_params;
+pub use set_funding_params::*;
+pub mod snapshot_market;
+pub use snapshot_market::*;
+pub mod open_leaderboard;
+pub use open_leaderboard::*;
+pub mod create_emissions_schedule;
+pub use create_emissions_schedule::*;
+pub mod open_emissions_account;
+pub use open_emissions_account::*;
+pub mod claim_emissions;
+pub use claim_emissions::*;
+pub mod set_emissions_boost;
+pub use set_emissions_boost::*;
+pub mod lock_bond;
+pub use lock_bond::*;
+pub mod relock_bond;
+pub use relock_bond::*;
+pub mod withdraw_bond;
+pub use withdraw_bond::*;
+pub mod distribute_fees_to_stakers;
+pub use distribute_fees_to_stakers::*;
+pub mod claim_staking_fees;
+pub use claim_staking_fees::*;
+pub mod buyback_and_burn;
+pub use buyback_and_burn::*;
+pub mod transfer_collateral;
+pub use transfer_collateral::*;
+pub mod internal_transfer;
+pub use internal_transfer::*;
+pub mod set_session_limits;
+pub use set_session_limits::*;
+pub mod quote;
+pub use quote::*;
+pub mod set_matching_policy;
+pub use set_matching_policy::*;
+pub mod execute_hybrid_trade;
+pub use execute_hybrid_trade::*;
+pub mod set_trading_hours;
+pub use set_trading_hours::*;
+pub mod uncross_auction;
+pub use uncross_auction::*;
+pub mod observation_frame;
+pub use observation_frame::*;
+pub mod submit_action;
+pub use submit_action::*;
+pub mod reset_agent_for_episode;
+pub use reset_agent_for_episode::*;
+pub mod reward_record;
+pub use reward_record::*;
+pub mod curriculum;
+pub use curriculum::*;
+pub mod adversary;
+pub use adversary::*;
+pub mod publish_score_root;
+pub use publish_score_root::*;
+pub mod claim_scored_reward;
+pub use claim_scored_reward::*;
+pub mod mint_agent_nft;
+pub use mint_agent_nft::*;
+pub mod update_agent_nft;
+pub use update_agent_nft::*;
+pub mod transfer_agent;
+pub use transfer_agent::*;
+pub mod transfer_agent_ownership;
+pub use transfer_agent_ownership::*;
+pub mod accept_agent_ownership;
+pub use accept_agent_ownership::*;
+pub mod create_guild;
+pub use create_guild::*;
+pub mod elect_guild_manager;
+pub use elect_guild_manager::*;
+pub mod deposit_to_guild;
+pub use deposit_to_guild::*;
+pub mod withdraw_share;
+pub use withdraw_share::*;
+pub mod subscribe_to_agent;
+pub use subscribe_to_agent::*;
+pub mod unsubscribe_from_agent;
+pub use unsubscribe_from_agent::*;
+pub mod mirror_fill;
+pub use mirror_fill::*;
+pub mod create_strategy_vault;
+pub use create_strategy_vault::*;
+pub mod deposit_to_strategy;
+pub use deposit_to_strategy::*;
+pub mod request_redeem;
+pub use request_redeem::*;
+pub mod process_redemptions;
+pub use process_redemptions::*;
+pub mod reconcile_vault;
+pub use reconcile_vault::*;
+pub mod sweep_donations;
+pub use sweep_donations::*;
+pub mod collect_excess_lamports;
+pub use collect_excess_lamports::*;
+pub mod close_filled_trade;
+pub use close_filled_trade::*;
+pub mod close_balance;
+pub use close_balance::*;
+pub mod close_agent;
+pub use close_agent::*;
+pub mod initialize_config;
+pub use initialize_config::*;
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use crate::oracle::{read_price, ORACLE_SOURCE_TWAP};
+use crate::{
+    Agent, Config, Market, Position, RewardRecord, Vault, FEATURE_MARGIN, FEATURE_ORACLE_REQUIRED, SESSION_SCOPE_PLACE,
+    TRADE_FLAG_REDUCE_ONLY,
+};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ModifyPosition<'info> {
+    #[account(
+        mut,
+        constraint = agent.is_authorized(&user.key(), SESSION_SCOPE_PLACE, Clock::get()?.slot)
+            @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_MARGIN) @ ErrorCode::FeatureDisabled,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), agent.key().as_ref()],
+        bump = position.bump,
+        has_one = agent @ ErrorCode::InvalidAgent,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    /// The current epoch's, counting the fill toward the agent's reward
+    #[account(
+        mut,
+        seeds = [b"reward", market.key().as_ref(), agent.key().as_ref(), &reward.epoch.to_le_bytes()],
+        bump = reward.bump
+    )]
+    pub reward: Option<Account<'info, RewardRecord>>,
+    pub user: Signer<'info>,
+}
+
+/// Buys (`base_delta` > 0) or sells token B exposure from the vault at the
+/// oracle price plus the skew premium, paying the base taker fee, with the
+/// vault's own liquidity taking the other side of realized PnL. Anything
+/// that grows or flips the position must leave it above initial margin and
+/// within the market's open interest caps, and counts the token A value of
+/// the exposure it adds against a session key's spend limits. With
+/// `TRADE_FLAG_REDUCE_ONLY`, the only flag taken here, anything but a move
+/// toward flat is rejected.
+pub fn modify_position(ctx: Context<ModifyPosition>, base_delta: i64, flags: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if market.initial_margin_bps == 0 {
+        return err!(ErrorCode::PerpsDisabled);
+    }
+    if base_delta == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+    if flags & !TRADE_FLAG_REDUCE_ONLY != 0 {
+        return err!(ErrorCode::InvalidTradeFlags);
+    }
+    let price = read_price(
+        market,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+        Clock::get()?.slot,
+    )?;
+    if price.source == ORACLE_SOURCE_TWAP && ctx.accounts.config.enabled(FEATURE_ORACLE_REQUIRED) {
+        return err!(ErrorCode::OracleRequired);
+    }
+    let price = price.price;
+
+    let position = &mut ctx.accounts.position;
+    position.settle(market)?;
+    market.reprice_funding(price)?;
+
+    // Reduce-only must move toward flat without growing or flipping the position
+    if flags & TRADE_FLAG_REDUCE_ONLY != 0
+        && (position.base.signum() != -base_delta.signum()
+            || base_delta.unsigned_abs() > position.base.unsigned_abs())
+    {
+        return err!(ErrorCode::ReduceOnlyWouldIncrease);
+    }
+
+    // Fills pay the skew premium; costs round up on buys and proceeds down on sells
+    let fill_price = market.skewed_price(price, base_delta)?;
+    let cost = market.base_value(base_delta, fill_price, true)?;
+    let fee = market.taker_fee(cost.unsigned_abs())?;
+    let fee_i64 = i64::try_from(fee).map_err(|_| ErrorCode::MathOverflow)?;
+    position.quote = position.quote.checked_sub(fee_i64).ok_or(ErrorCode::MathOverflow)?;
+    let token_a = market.token_a;
+    market.accrue_fee(token_a, fee)?;
+    if let Some(reward) = ctx.accounts.reward.as_mut() {
+        let now = market.now()?;
+        let trade_type = if base_delta > 0 { 0 } else { 1 };
+        let (quantity, quote_amount) = (base_delta.unsigned_abs(), cost.unsigned_abs());
+        reward.record_fill(market.current_epoch(now), trade_type, quantity, quote_amount, fee_i64, now)?;
+    }
+
+    let old_base = position.base;
+    let (old_long, old_short) = (market.open_interest_long, market.open_interest_short);
+    let realized = position.apply_fill(market, base_delta, cost)?;
+    ctx.accounts.vault_a.settle_pnl(realized)?;
+    market.check_open_interest(old_long, old_short)?;
+    let new_base = position.base;
+
+    let flipped = old_base != 0 && new_base != 0 && old_base.signum() != new_base.signum();
+    if new_base.unsigned_abs() > old_base.unsigned_abs() || flipped {
+        let required = market.margin_requirement(new_base, price, market.initial_margin_bps)?;
+        if position.equity(market, price)? < required as i64 {
+            return err!(ErrorCode::InsufficientMargin);
+        }
+        // A session key is charged for the exposure it adds, as for an order
+        let added = match flipped {
+            true => new_base.unsigned_abs(),
+            false => new_base.unsigned_abs() - old_base.unsigned_abs(),
+        };
+        let added = i64::try_from(added).map_err(|_| ErrorCode::MathOverflow)?;
+        let notional = market.base_value(added, fill_price, true)?.unsigned_abs();
+        let epoch = market.current_epoch(market.now()?);
+        ctx.accounts.agent.spend(&ctx.accounts.user.key(), 0, notional, epoch)?;
+    }
+    Ok(())
+}
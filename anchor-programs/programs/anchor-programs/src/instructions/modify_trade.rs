@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Balance, Config, Market, Trade, Vault, SESSION_SCOPE_PLACE};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+use crate::instructions::place_trade::open_order;
+
+#[derive(Accounts)]
+pub struct ModifyTrade<'info> {
+    #[account(
+        mut,
+        constraint = agent.is_authorized(&user.key(), SESSION_SCOPE_PLACE, Clock::get()?.slot)
+            @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref(), &trade.order_id.to_le_bytes()],
+        bump = trade.bump,
+        has_one = agent @ ErrorCode::InvalidAgent,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub trade: Account<'info, Trade>,
+    /// Vault A and the agent's balance in it, which pay the market's
+    /// congestion surcharge when a filled order is put back on the book
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump
+    )]
+    pub vault_a: Option<Account<'info, Vault>>,
+    #[account(
+        mut,
+        seeds = [b"balance", balance_a.vault.as_ref(), agent.key().as_ref()],
+        bump = balance_a.bump
+    )]
+    pub balance_a: Option<Account<'info, Balance>>,
+    pub user: Signer<'info>,
+}
+
+pub fn modify_trade(
+    ctx: Context<ModifyTrade>,
+    amount_in: u64,
+    amount_out: u64,
+) -> Result<()> {
+    if amount_in == 0 || amount_out == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+    // A filled order given a new size is back on the book like a placement
+    if ctx.accounts.trade.size() == 0 {
+        let accounts = &mut *ctx.accounts;
+        open_order(&mut accounts.market, accounts.vault_a.as_ref(), accounts.balance_a.as_mut())?;
+    } else {
+        ctx.accounts.market.ensure_open()?;
+    }
+    let market = &mut ctx.accounts.market;
+    let trade = &mut ctx.accounts.trade;
+    let old_size = trade.size();
+    let old_committed = if old_size > 0 { trade.amount_in } else { 0 };
+    trade.amount_in = amount_in;
+    trade.amount_out = amount_out;
+    market.check_order_size(trade.size())?;
+    // Sessions are charged only for what the change adds to the order
+    let epoch = market.current_epoch(market.now()?);
+    ctx.accounts
+        .agent
+        .spend(&ctx.accounts.user.key(), trade.trade_type, trade.amount_in.saturating_sub(old_committed), epoch)?;
+
+    // Price changes and size reductions keep their place in the queue,
+    // growing the order sends it to the back like a fresh placement.
+    if trade.size() > old_size {
+        trade.sequence = market.next_sequence()?;
+    }
+    OrderUpdated::emit(market, trade)
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+
+use crate::{Market, ObservationFrame, Trade, OBSERVATION_LEVELS};
+use crate::errors::ErrorCode;
+use crate::instructions::uncross_auction::ask_price;
+
+#[derive(Accounts)]
+pub struct OpenObservationFrame<'info> {
+    #[account(has_one = authority @ ErrorCode::Unauthorized)]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ObservationFrame>(),
+        seeds = [b"observation", market.key().as_ref()],
+        bump
+    )]
+    pub observation_frame: AccountLoader<'info, ObservationFrame>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateObservationFrame<'info> {
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"observation", market.key().as_ref()],
+        bump = observation_frame.load()?.bump
+    )]
+    pub observation_frame: AccountLoader<'info, ObservationFrame>,
+    // remaining_accounts: the next page of the market's orders, in ascending
+    // key order
+}
+
+/// Creates the market's observation frame, updatable every `interval` slots.
+pub fn open_observation_frame(ctx: Context<OpenObservationFrame>, interval: u64) -> Result<()> {
+    if interval == 0 {
+        return err!(ErrorCode::InvalidObservationInterval);
+    }
+    let mut frame = ctx.accounts.observation_frame.load_init()?;
+    frame.market = ctx.accounts.market.key();
+    frame.interval = interval;
+    frame.bump = ctx.bumps.observation_frame;
+    Ok(())
+}
+
+/// Reads the next page of the market's book into its observation frame,
+/// and rewrites the frame from its state and book once the last order has
+/// been read. Anyone can crank it once `interval` slots have passed since
+/// the last update. Every resting order has to be read, so the depth can't
+/// be skewed by leaving some out, and any change to the book in between
+/// starts the update over, as does a page not carrying on from the last.
+pub fn update_observation_frame<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateObservationFrame<'info>>,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let slot = Clock::get()?.slot;
+    let mut frame = ctx.accounts.observation_frame.load_mut()?;
+    let carries_on = frame.building != 0
+        && frame.build_book_sequence == market.book_sequence
+        && ctx
+            .remaining_accounts
+            .first()
+            .is_none_or(|info| *info.key > frame.build_last_order);
+    if !carries_on {
+        if frame.sequence > 0 && slot < frame.slot.saturating_add(frame.interval) {
+            return err!(ErrorCode::ObservationNotDue);
+        }
+        frame.building = 1;
+        frame.build_book_sequence = market.book_sequence;
+        frame.build_orders_left = market.open_orders;
+        frame.build_last_order = Pubkey::default();
+        frame.build_bid_prices = [0; OBSERVATION_LEVELS];
+        frame.build_bid_sizes = [0; OBSERVATION_LEVELS];
+        frame.build_ask_prices = [0; OBSERVATION_LEVELS];
+        frame.build_ask_sizes = [0; OBSERVATION_LEVELS];
+    }
+
+    for info in ctx.remaining_accounts {
+        if *info.key <= frame.build_last_order {
+            return err!(ErrorCode::DuplicateOrder);
+        }
+        let order = Account::<Trade>::try_from(info)?;
+        if order.market != market.key() {
+            return err!(ErrorCode::InvalidMarket);
+        }
+        frame.build_last_order = order.key();
+        if order.size() == 0 {
+            continue;
+        }
+        frame.build_orders_left = frame.build_orders_left.checked_sub(1).ok_or(ErrorCode::IncompleteBook)?;
+        if order.displayed() == 0 {
+            continue;
+        }
+        let frame = &mut *frame;
+        match order.trade_type {
+            0 => add_level(
+                (&mut frame.build_bid_prices, &mut frame.build_bid_sizes),
+                market.display_price(order.price())?,
+                order.displayed(),
+                |price, level| price > level,
+            ),
+            _ => add_level(
+                (&mut frame.build_ask_prices, &mut frame.build_ask_sizes),
+                market.display_price(ask_price(&order))?,
+                order.displayed(),
+                |price, level| price < level,
+            ),
+        }
+    }
+    if frame.build_orders_left > 0 {
+        return Ok(());
+    }
+
+    frame.building = 0;
+    (frame.mid, frame.spread) = match (frame.build_bid_sizes[0], frame.build_ask_sizes[0]) {
+        (0, _) | (_, 0) => (market.last_price, 0),
+        _ => {
+            let (bid, ask) = (frame.build_bid_prices[0], frame.build_ask_prices[0]);
+            (((bid as u128 + ask as u128) / 2) as u64, ask.saturating_sub(bid))
+        }
+    };
+    frame.bid_prices = frame.build_bid_prices;
+    frame.bid_sizes = frame.build_bid_sizes;
+    frame.ask_prices = frame.build_ask_prices;
+    frame.ask_sizes = frame.build_ask_sizes;
+    frame.last_price = market.last_price;
+    frame.open_interest_long = market.open_interest_long;
+    frame.open_interest_short = market.open_interest_short;
+    frame.funding_per_epoch = market.funding_per_epoch;
+    frame.volatility_bps = market.volatility_bps;
+    frame.slot = slot;
+    frame.sequence = frame.sequence.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+/// Adds `size` at `price` to one side's levels, kept best first by
+/// `better`, dropping whatever falls past OBSERVATION_LEVELS.
+fn add_level(
+    (prices, sizes): (&mut [u64; OBSERVATION_LEVELS], &mut [u64; OBSERVATION_LEVELS]),
+    price: u64,
+    size: u64,
+    better: fn(u64, u64) -> bool,
+) {
+    for level in 0..OBSERVATION_LEVELS {
+        if sizes[level] > 0 && prices[level] == price {
+            sizes[level] = sizes[level].saturating_add(size);
+            return;
+        }
+        if sizes[level] == 0 || better(price, prices[level]) {
+            prices.copy_within(level..OBSERVATION_LEVELS - 1, level + 1);
+            sizes.copy_within(level..OBSERVATION_LEVELS - 1, level + 1);
+            (prices[level], sizes[level]) = (price, size);
+            return;
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{Agent, EmissionsAccount, EmissionsSchedule};
+
+#[derive(Accounts)]
+pub struct OpenEmissionsAccount<'info> {
+    pub schedule: Account<'info, EmissionsSchedule>,
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + EmissionsAccount::INIT_SPACE,
+        seeds = [b"emissions_account", schedule.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub emissions_account: Account<'info, EmissionsAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the account an agent's volume under `schedule` is counted in.
+/// Anyone can open it.
+pub fn open_emissions_account(ctx: Context<OpenEmissionsAccount>) -> Result<()> {
+    let emissions_account = &mut ctx.accounts.emissions_account;
+    emissions_account.schedule = ctx.accounts.schedule.key();
+    emissions_account.agent = ctx.accounts.agent.key();
+    emissions_account.bump = ctx.bumps.emissions_account;
+    Ok(())
+}
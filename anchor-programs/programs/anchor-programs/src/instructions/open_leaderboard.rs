@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{Leaderboard, Market};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct OpenLeaderboard<'info> {
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [b"leaderboard", market.key().as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the market's leaderboard for the current `epoch`, for crankers to
+/// pass to `match_trades`. Anyone can open it.
+pub fn open_leaderboard(ctx: Context<OpenLeaderboard>, epoch: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    if market.current_epoch(market.now()?) != epoch {
+        return err!(ErrorCode::InvalidEpoch);
+    }
+    let leaderboard = &mut ctx.accounts.leaderboard;
+    leaderboard.market = market.key();
+    leaderboard.epoch = epoch;
+    leaderboard.bump = ctx.bumps.leaderboard;
+    Ok(())
+}
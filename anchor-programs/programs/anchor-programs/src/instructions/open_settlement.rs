@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Market, Settlement};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct OpenSettlement<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Settlement::INIT_SPACE,
+        seeds = [b"settlement", market.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub settlement: Account<'info, Settlement>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_settlement(ctx: Context<OpenSettlement>) -> Result<()> {
+    let settlement = &mut ctx.accounts.settlement;
+    settlement.agent = ctx.accounts.agent.key();
+    settlement.market = ctx.accounts.market.key();
+    settlement.epoch = ctx.accounts.market.current_epoch(ctx.accounts.market.now()?);
+    settlement.bump = ctx.bumps.settlement;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::adapters::{
+    openbook_instruction, OPENBOOK_ACCOUNT_INSTRUCTIONS, OPENBOOK_CANCEL_INSTRUCTIONS,
+    OPENBOOK_PLACE_INSTRUCTIONS, OPENBOOK_V2_PROGRAM_ID,
+};
+use crate::Agent;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct OpenbookOrder<'info> {
+    #[account(
+        seeds = [b"agent", agent.registrant.as_ref()],
+        bump = agent.bump,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: pinned to the OpenBook v2 program
+    #[account(address = OPENBOOK_V2_PROGRAM_ID)]
+    pub openbook_program: UncheckedAccount<'info>,
+    // remaining_accounts: the OpenBook instruction's accounts, with the agent
+    // PDA wherever OpenBook expects the open-orders owner
+}
+
+/// Places an order on OpenBook v2 from the agent's open-orders account.
+pub fn openbook_place_order<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenbookOrder<'info>>,
+    data: Vec<u8>,
+) -> Result<()> {
+    invoke_as_agent(ctx, data, &OPENBOOK_PLACE_INSTRUCTIONS)
+}
+
+/// Cancels one or all of the agent's resting OpenBook v2 orders.
+pub fn openbook_cancel_order<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenbookOrder<'info>>,
+    data: Vec<u8>,
+) -> Result<()> {
+    invoke_as_agent(ctx, data, &OPENBOOK_CANCEL_INSTRUCTIONS)
+}
+
+/// Creates the agent's open-orders indexer and accounts, or settles their
+/// funds, with the agent PDA as owner.
+pub fn openbook_manage_account<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenbookOrder<'info>>,
+    data: Vec<u8>,
+) -> Result<()> {
+    invoke_as_agent(ctx, data, &OPENBOOK_ACCOUNT_INSTRUCTIONS)
+}
+
+fn invoke_as_agent<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenbookOrder<'info>>,
+    data: Vec<u8>,
+    allowed: &[[u8; 8]],
+) -> Result<()> {
+    let agent = &ctx.accounts.agent;
+    let ix = openbook_instruction(&agent.key(), ctx.remaining_accounts, data, allowed)?;
+    let seeds = &[b"agent", agent.registrant.as_ref(), &[agent.bump]];
+    invoke_signed(&ix, ctx.remaining_accounts, &[&seeds[..]])?;
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::{
+    Agent, Balance, Config, Market, RelayerDebt, SignedOrder, Trade, Vault, FEATURE_ORDER_BOOK,
+    LAMPORTS_PER_SIGNATURE,
+};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+use crate::instructions::place_trade::open_order;
+
+// Ed25519 program instruction: count, padding, then one offsets record
+const ED25519_HEADER_LEN: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_SIGNATURE_LEN: usize = 64;
+const ED25519_PUBKEY_LEN: usize = 32;
+
+#[derive(Accounts)]
+#[instruction(order: SignedOrder)]
+pub struct PlaceSignedTrade<'info> {
+    #[account(
+        mut,
+        constraint = agent.key() == order.agent @ ErrorCode::InvalidSignedOrder
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = market.key() == order.market @ ErrorCode::InvalidSignedOrder,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_ORDER_BOOK) @ ErrorCode::FeatureDisabled,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + Trade::INIT_SPACE,
+        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref(), &order.order_id.to_le_bytes()],
+        bump
+    )]
+    pub trade: Account<'info, Trade>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + RelayerDebt::INIT_SPACE,
+        seeds = [b"relayer_debt", market.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub relayer_debt: Account<'info, RelayerDebt>,
+    /// Vault A and the agent's balance in it, which pay the market's
+    /// congestion surcharge when one applies
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump
+    )]
+    pub vault_a: Option<Account<'info, Vault>>,
+    #[account(
+        mut,
+        seeds = [b"balance", balance_a.vault.as_ref(), agent.key().as_ref()],
+        bump = balance_a.bump
+    )]
+    pub balance_a: Option<Account<'info, Balance>>,
+    /// Pays for the transaction and the trade account on the owner's behalf
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    /// CHECK: the Instructions sysvar, pinned by address
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Posts an order the agent owner signed off-chain. The instruction right
+/// before this one must be an Ed25519 program check of the owner's signature
+/// over the Borsh-encoded `order`, so any relayer can submit it and pay.
+pub fn place_signed_trade(ctx: Context<PlaceSignedTrade>, order: SignedOrder) -> Result<()> {
+    let agent = &ctx.accounts.agent;
+    if ctx.accounts.market.now()? >= order.expiry_slot {
+        return err!(ErrorCode::OrderExpired);
+    }
+    if order.nonce <= agent.signed_order_nonce {
+        return err!(ErrorCode::NonceReused);
+    }
+    order.params.validate()?;
+    verify_owner_signature(
+        &ctx.accounts.instructions.to_account_info(),
+        &agent.owner,
+        &order.try_to_vec()?,
+    )?;
+
+    ctx.accounts.agent.signed_order_nonce = order.nonce;
+    ctx.accounts.agent.open_trade()?;
+    let accounts = &mut *ctx.accounts;
+    open_order(&mut accounts.market, accounts.vault_a.as_ref(), accounts.balance_a.as_mut())?;
+    let market = &mut ctx.accounts.market;
+    let sequence = market.next_sequence()?;
+    ctx.accounts.trade.open(
+        order.agent,
+        order.market,
+        order.order_id,
+        sequence,
+        &order.params,
+        ctx.bumps.trade,
+    );
+    market.check_order_size(ctx.accounts.trade.size())?;
+    OrderUpdated::emit(&mut ctx.accounts.market, &mut ctx.accounts.trade)?;
+
+    // Only the market's designated relayer is reimbursed: for the trade
+    // account's rent and its own transaction signature
+    let relayer = ctx.accounts.relayer.key();
+    if relayer == ctx.accounts.market.relayer {
+        let cost = Rent::get()?
+            .minimum_balance(8 + Trade::INIT_SPACE)
+            .checked_add(LAMPORTS_PER_SIGNATURE)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let debt = &mut ctx.accounts.relayer_debt;
+        if debt.lamports == 0 {
+            debt.market = order.market;
+            debt.agent = order.agent;
+            debt.relayer = relayer;
+            debt.bump = ctx.bumps.relayer_debt;
+        } else if debt.relayer != relayer {
+            return err!(ErrorCode::RelayerMismatch);
+        }
+        debt.lamports = debt.lamports.checked_add(cost).ok_or(ErrorCode::MathOverflow)?;
+    }
+    Ok(())
+}
+
+/// Checks that the preceding instruction verified exactly one signature by
+/// `signer` over `message`, with all data inline in that instruction.
+fn verify_owner_signature(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)? as usize;
+    let ix = current_index
+        .checked_sub(1)
+        .map(|index| load_instruction_at_checked(index, instructions))
+        .ok_or(ErrorCode::SignatureVerificationRequired)??;
+    let data = &ix.data;
+    if ix.program_id != ed25519_program::ID
+        || data.len() < ED25519_HEADER_LEN + ED25519_OFFSETS_LEN
+        || data[0] != 1
+    {
+        return err!(ErrorCode::SignatureVerificationRequired);
+    }
+
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let offsets = ED25519_HEADER_LEN;
+    let signature_offset = read_u16(offsets) as usize;
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_len = read_u16(offsets + 10) as usize;
+    // u16::MAX points each field at the Ed25519 instruction itself
+    let inline = [offsets + 2, offsets + 6, offsets + 12]
+        .iter()
+        .all(|&index_offset| read_u16(index_offset) == u16::MAX);
+
+    let in_bounds = signature_offset + ED25519_SIGNATURE_LEN <= data.len()
+        && pubkey_offset + ED25519_PUBKEY_LEN <= data.len()
+        && message_offset + message_len <= data.len();
+    if !inline
+        || !in_bounds
+        || data[pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN] != signer.to_bytes()
+        || data[message_offset..message_offset + message_len] != *message
+    {
+        return err!(ErrorCode::SignatureVerificationRequired);
+    }
+    Ok(())
+}
@@ -1,42 +1,126 @@
 use anchor_lang::prelude::*;
-use crate::{Agent, Market, Trade};
+use crate::{
+    Agent, Balance, Config, Market, OrderParams, Trade, Vault, FEATURE_ORDER_BOOK,
+    SESSION_SCOPE_PLACE, TRADE_TAG_LEN,
+};
 use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
 
 #[derive(Accounts)]
+#[instruction(order_id: u64)]
 pub struct PlaceTrade<'info> {
     #[account(
-      mut)]
+      mut,
+      constraint = agent.is_authorized(&user.key(), SESSION_SCOPE_PLACE, Clock::get()?.slot)
+          @ ErrorCode::InvalidAgentOwner
+    )]
     pub agent: Account<'info, Agent>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
     pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_ORDER_BOOK) @ ErrorCode::FeatureDisabled,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
     #[account(
         init,
         payer = user,
         space = 8 + Trade::INIT_SPACE,
-        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref()],
+        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref(), &order_id.to_le_bytes()],
         bump
     )]
     pub trade: Account<'info, Trade>,
+    /// Vault A and the agent's balance in it, which pay the market's
+    /// congestion surcharge when one applies
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump
+    )]
+    pub vault_a: Option<Account<'info, Vault>>,
+    #[account(
+        mut,
+        seeds = [b"balance", balance_a.vault.as_ref(), agent.key().as_ref()],
+        bump = balance_a.bump
+    )]
+    pub balance_a: Option<Account<'info, Balance>>,
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn place_trade(
     ctx: Context<PlaceTrade>,
+    order_id: u64,
     trade_type: u8,
     amount_in: u64,
     amount_out: u64,
+    display_size: u64,
+    flags: u8,
+    tag: [u8; TRADE_TAG_LEN],
+) -> Result<()> {
+    let params = OrderParams {
+        trade_type,
+        amount_in,
+        amount_out,
+        display_size,
+        flags,
+        tag,
+    };
+    params.validate()?;
+    place(ctx, order_id, &params)
+}
+
+/// Opens the agent's trade `order_id` from validated `params`.
+pub(crate) fn place(ctx: Context<PlaceTrade>, order_id: u64, params: &OrderParams) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    open_order(&mut accounts.market, accounts.vault_a.as_ref(), accounts.balance_a.as_mut())?;
+    let market = &mut ctx.accounts.market;
+    let sequence = market.next_sequence()?;
+    ctx.accounts.trade.open(
+        ctx.accounts.agent.key(),
+        market.key(),
+        order_id,
+        sequence,
+        params,
+        ctx.bumps.trade,
+    );
+    market.check_order_size(ctx.accounts.trade.size())?;
+    let epoch = market.current_epoch(market.now()?);
+    ctx.accounts.agent.open_trade()?;
+    let trade = &ctx.accounts.trade;
+    ctx.accounts
+        .agent
+        .spend(&ctx.accounts.user.key(), trade.trade_type, trade.amount_in, epoch)?;
+    OrderUpdated::emit(&mut ctx.accounts.market, &mut ctx.accounts.trade)
+}
+
+/// Counts an order onto the book, which must be within trading hours,
+/// charging the agent's vault A balance the market's congestion surcharge as
+/// of the orders already resting.
+pub(crate) fn open_order(
+    market: &mut Market,
+    vault_a: Option<&Account<Vault>>,
+    balance_a: Option<&mut Account<Balance>>,
 ) -> Result<()> {
-    if trade_type > 1 {
-        return err!(ErrorCode::InvalidTradeType);
+    market.ensure_open()?;
+    let surcharge = market.congestion_surcharge();
+    market.open_orders = market.open_orders.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    if surcharge == 0 {
+        return Ok(());
     }
-    let trade = &mut ctx.accounts.trade;
-    trade.agent = ctx.accounts.agent.key();
-    trade.market = ctx.accounts.market.key();
-    trade.trade_type = trade_type;
-    trade.amount_in = amount_in;
-    trade.amount_out = amount_out;
-    trade.bump = ctx.bumps.trade;
-    Ok(())
-}
\ No newline at end of file
+    let (Some(vault_a), Some(balance_a)) = (vault_a, balance_a) else {
+        return err!(ErrorCode::CongestionBalanceRequired);
+    };
+    if balance_a.vault != vault_a.key() {
+        return err!(ErrorCode::InvalidVault);
+    }
+    balance_a.debit(surcharge)?;
+    let token_a = market.token_a;
+    market.accrue_fee(token_a, surcharge)
+}
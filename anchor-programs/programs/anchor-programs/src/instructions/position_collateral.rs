@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use crate::oracle::read_price;
+use crate::{Agent, Balance, Market, Position, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct PositionCollateral<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", market.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_a.key().as_ref(), agent.key().as_ref()],
+        bump = balance_a.bump
+    )]
+    pub balance_a: Account<'info, Balance>,
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves tracked token A from the agent's balance into position collateral.
+pub fn deposit_collateral(ctx: Context<PositionCollateral>, amount: u64) -> Result<()> {
+    if ctx.accounts.market.initial_margin_bps == 0 {
+        return err!(ErrorCode::PerpsDisabled);
+    }
+    let amount_i64 = i64::try_from(amount).map_err(|_| ErrorCode::MathOverflow)?;
+    ctx.accounts.balance_a.debit(amount)?;
+
+    let position = &mut ctx.accounts.position;
+    position.bind(ctx.accounts.agent.key(), ctx.accounts.market.key(), ctx.bumps.position);
+    position.settle(&mut ctx.accounts.market)?;
+    position.quote = position.quote.checked_add(amount_i64).ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+/// Returns collateral to the agent's balance. Unrealized profit cannot be
+/// withdrawn, and an open position must stay above its initial margin.
+pub fn withdraw_collateral(ctx: Context<PositionCollateral>, amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+    position.settle(market)?;
+
+    let amount_i64 = i64::try_from(amount).map_err(|_| ErrorCode::MathOverflow)?;
+    if amount_i64 > position.quote.min(position.collateral()?) {
+        return err!(ErrorCode::InsufficientBalance);
+    }
+    position.quote -= amount_i64;
+
+    if position.base != 0 {
+        let price = read_price(
+            market,
+            ctx.accounts.oracle.as_deref(),
+            ctx.accounts.fallback_oracle.as_deref(),
+            Clock::get()?.slot,
+        )?
+        .price;
+        market.reprice_funding(price)?;
+        let required = market.margin_requirement(position.base, price, market.initial_margin_bps)?;
+        if position.equity(market, price)? < required as i64 {
+            return err!(ErrorCode::InsufficientMargin);
+        }
+    }
+    ctx.accounts.balance_a.credit(amount)
+}
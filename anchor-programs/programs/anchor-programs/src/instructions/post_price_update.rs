@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::oracle::{read_price_update, PYTH_RECEIVER_PROGRAM_ID};
+use crate::{Market, PriceCache};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct PostPriceUpdate<'info> {
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"price_cache", market.key().as_ref()],
+        bump = price_cache.bump,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub price_cache: Account<'info, PriceCache>,
+    /// CHECK: owner is checked here; layout, verification level and feed id
+    /// are checked by `read_price_update`
+    #[account(owner = PYTH_RECEIVER_PROGRAM_ID @ ErrorCode::InvalidOracleAccount)]
+    pub price_update: UncheckedAccount<'info>,
+}
+
+pub fn post_price_update(ctx: Context<PostPriceUpdate>) -> Result<()> {
+    let update = read_price_update(&ctx.accounts.price_update.try_borrow_data()?)?;
+    let price_cache = &mut ctx.accounts.price_cache;
+    if update.feed_id != price_cache.feed_id {
+        return err!(ErrorCode::OracleAccountMismatch);
+    }
+    if update.publish_time <= price_cache.publish_time {
+        return err!(ErrorCode::PriceUpdateOutdated);
+    }
+    price_cache.price = update.price;
+    price_cache.conf = update.conf;
+    price_cache.publish_time = update.publish_time;
+    price_cache.posted_slot = update.posted_slot;
+    Ok(())
+}
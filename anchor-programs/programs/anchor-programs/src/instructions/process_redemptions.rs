@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Burn, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::instructions::{crystallize_fees, mint_shares, net_asset_value};
+use crate::{Balance, Config, Market, RedemptionRequest, StrategyVault, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ProcessRedemptions<'info> {
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        has_one = share_mint @ ErrorCode::InvalidTokenMint
+    )]
+    pub strategy_vault: Account<'info, StrategyVault>,
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub share_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = strategy_vault
+    )]
+    pub escrow_share_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = strategy_vault.manager
+    )]
+    pub manager_share_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_a,
+        associated_token::authority = vault_a
+    )]
+    pub vault_token_account_a: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_a.key().as_ref(), strategy_vault.agent.as_ref()],
+        bump = strategy_balance_a.bump
+    )]
+    pub strategy_balance_a: Account<'info, Balance>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_b,
+        associated_token::authority = vault_b
+    )]
+    pub vault_token_account_b: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_b.key().as_ref(), strategy_vault.agent.as_ref()],
+        bump = strategy_balance_b.bump
+    )]
+    pub strategy_balance_b: Account<'info, Balance>,
+
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    // remaining_accounts: per redemption, the RedemptionRequest, the owner's
+    // token A and token B accounts and the owner's wallet, which gets the
+    // request's rent
+}
+
+/// Pays out redemptions queued in earlier epochs, each its share of the
+/// strategy's token A and token B in kind. The first deposit or redemption
+/// call in an epoch crystallizes fees and fixes the holdings and supply the
+/// epoch's redemptions are paid from, before anything else in the epoch can
+/// move them; with no price in the payout, no one redeeming can pick the
+/// price they get. `crank_sequence` must be the market's next, as for
+/// `match_trades`.
+pub fn process_redemptions<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProcessRedemptions<'info>>,
+    crank_sequence: u64,
+) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    accounts.market.take_crank(crank_sequence)?;
+    let epoch = accounts.market.current_epoch(accounts.market.now()?);
+    if accounts.strategy_vault.settlement_epoch != epoch {
+        let nav = net_asset_value(
+            &accounts.market,
+            &accounts.strategy_balance_a,
+            &accounts.strategy_balance_b,
+            accounts.oracle.as_deref(),
+            accounts.fallback_oracle.as_deref(),
+        )?;
+        let supply = accounts.share_mint.supply;
+        let fee_shares =
+            crystallize_fees(&mut accounts.strategy_vault, &accounts.market, nav, supply)?;
+        mint_shares(
+            &accounts.strategy_vault,
+            &accounts.share_mint,
+            &accounts.manager_share_account,
+            &accounts.token_program,
+            fee_shares,
+        )?;
+        let supply = supply.checked_add(fee_shares).ok_or(ErrorCode::MathOverflow)?;
+        accounts.strategy_vault.fix_settlement(
+            epoch,
+            supply,
+            accounts.strategy_balance_a.amount,
+            accounts.strategy_balance_b.amount,
+        );
+    }
+
+    let strategy_vault = &accounts.strategy_vault;
+    let market = &accounts.market;
+    let vault_seeds = &[
+        b"strategy_vault",
+        strategy_vault.market.as_ref(),
+        strategy_vault.manager_agent.as_ref(),
+        &[strategy_vault.bump],
+    ];
+    let token_a_seeds = &[
+        b"vault",
+        market.token_a.as_ref(),
+        market.token_b.as_ref(),
+        market.token_a.as_ref(),
+        &[accounts.vault_a.bump],
+    ];
+    let token_b_seeds = &[
+        b"vault",
+        market.token_a.as_ref(),
+        market.token_b.as_ref(),
+        market.token_b.as_ref(),
+        &[accounts.vault_b.bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+    let token_a_signer = &[&token_a_seeds[..]];
+    let token_b_signer = &[&token_b_seeds[..]];
+    for redemption in ctx.remaining_accounts.chunks(4) {
+        let [request_info, owner_token_account_a, owner_token_account_b, owner] = redemption else {
+            return err!(ErrorCode::InvalidRedemption);
+        };
+        let request = Account::<RedemptionRequest>::try_from(request_info)?;
+        let owner_account_a = Account::<TokenAccount>::try_from(owner_token_account_a)?;
+        let owner_account_b = Account::<TokenAccount>::try_from(owner_token_account_b)?;
+        if request.strategy_vault != strategy_vault.key()
+            || (request.epoch >= epoch && !accounts.config.emergency_mode)
+            || owner.key() != request.owner
+            || owner_account_a.owner != request.owner
+            || owner_account_a.mint != market.token_a
+            || owner_account_b.owner != request.owner
+            || owner_account_b.mint != market.token_b
+        {
+            return err!(ErrorCode::InvalidRedemption);
+        }
+        let (amount_a, amount_b) = strategy_vault.redemption(request.shares)?;
+        accounts.strategy_balance_a.debit(amount_a)?;
+        accounts.strategy_balance_b.debit(amount_b)?;
+
+        let cpi_accounts = Burn {
+            mint: accounts.share_mint.to_account_info(),
+            from: accounts.escrow_share_account.to_account_info(),
+            authority: strategy_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            vault_signer,
+        );
+        token::burn(cpi_ctx, request.shares)?;
+
+        let cpi_accounts = Transfer {
+            from: accounts.vault_token_account_a.to_account_info(),
+            to: owner_token_account_a.clone(),
+            authority: accounts.vault_a.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            token_a_signer,
+        );
+        token::transfer(cpi_ctx, amount_a)?;
+        accounts.vault_a.send(amount_a)?;
+
+        let cpi_accounts = Transfer {
+            from: accounts.vault_token_account_b.to_account_info(),
+            to: owner_token_account_b.clone(),
+            authority: accounts.vault_b.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            token_b_signer,
+        );
+        token::transfer(cpi_ctx, amount_b)?;
+        accounts.vault_b.send(amount_b)?;
+
+        request.close(owner.clone())?;
+    }
+    Ok(())
+}
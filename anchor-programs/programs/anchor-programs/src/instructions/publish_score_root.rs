@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Token, TokenAccount, Transfer},
+};
+
+use crate::{Market, ScoreRoot, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct PublishScoreRoot<'info> {
+    #[account(
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ScoreRoot::INIT_SPACE,
+        seeds = [b"score_root", market.key().as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub score_root: Account<'info, ScoreRoot>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = market.token_a,
+        token::authority = authority
+    )]
+    pub authority_token_account_a: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_a,
+        associated_token::authority = vault_a
+    )]
+    pub vault_token_account_a: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Publishes the root of an epoch's off-chain score tree and deposits the
+/// token A rewards its leaves pay out. Each epoch's root is published once.
+pub fn publish_score_root(
+    ctx: Context<PublishScoreRoot>,
+    epoch: u64,
+    root: [u8; 32],
+    total_rewards: u64,
+) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.authority_token_account_a.to_account_info(),
+        to: ctx.accounts.vault_token_account_a.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, total_rewards)?;
+    ctx.accounts.vault_a.receive(total_rewards)?;
+
+    let score_root = &mut ctx.accounts.score_root;
+    score_root.market = ctx.accounts.market.key();
+    score_root.epoch = epoch;
+    score_root.root = root;
+    score_root.total_rewards = total_rewards;
+    score_root.bump = ctx.bumps.score_root;
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::{BookQuote, Market, Trade, PRICE_SCALE};
+use crate::errors::ErrorCode;
+use crate::math::mul_div_floor;
+
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    // remaining_accounts: the market's resting Trade accounts to walk, in
+    // any order
+}
+
+/// Walks the orders passed on the side a taker of `size` token B would hit,
+/// best price first and then by queue priority, filling their displayed
+/// size at each order's own price as `match_trades` would. Side 0 takes
+/// asks, 1 takes bids. The result is set as return data for a calling
+/// program to read; only the orders passed are counted, so a quote is as
+/// deep as the book the caller supplies.
+pub fn quote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Quote<'info>>,
+    size: u64,
+    side: u8,
+) -> Result<BookQuote> {
+    if side > 1 {
+        return err!(ErrorCode::InvalidTradeType);
+    }
+    if size == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+    let market = &ctx.accounts.market;
+
+    // A taker buying hits asks (trade_type 1), one selling hits bids
+    let mut orders = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts {
+        let trade = Account::<Trade>::try_from(info)?;
+        if trade.market != market.key() {
+            return err!(ErrorCode::InvalidMarket);
+        }
+        if trade.trade_type == 1 - side && trade.displayed() > 0 {
+            // Token A per token B as a ratio
+            let (num, den) = (trade.quote(), trade.size());
+            orders.push((info.key(), num, den, trade.sequence, trade.displayed()));
+        }
+    }
+    orders.sort_by(|a, b| {
+        let (a_price, b_price) = (a.1 as u128 * b.2 as u128, b.1 as u128 * a.2 as u128);
+        let by_price = if side == 0 { a_price.cmp(&b_price) } else { b_price.cmp(&a_price) };
+        by_price.then(a.3.cmp(&b.3))
+    });
+    if orders.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        return err!(ErrorCode::DuplicateOrder);
+    }
+
+    let mut result = BookQuote::default();
+    for (_, num, den, _, displayed) in orders {
+        let quantity = displayed.min(size - result.size);
+        if quantity == 0 {
+            break;
+        }
+        let quote_amount = mul_div_floor(quantity, num, den)?;
+        result.size += quantity;
+        result.quote_amount = result
+            .quote_amount
+            .checked_add(quote_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        result.fee = result
+            .fee
+            .checked_add(market.taker_fee(quote_amount)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    if result.size > 0 {
+        result.average_price = mul_div_floor(result.quote_amount, PRICE_SCALE, result.size)?;
+    }
+    Ok(result)
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{Market, Vault};
+use crate::errors::ErrorCode;
+use crate::events::ReconciliationReport;
+
+#[derive(Accounts)]
+pub struct ReconcileVault<'info> {
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), vault.token.as_ref()],
+        bump = vault.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        associated_token::mint = vault.token,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+}
+
+/// Compares the vault's token account with its reserves. Tokens sent to the
+/// account without going through the program are taken into the reserves as
+/// donations for sweep_donations to allocate; holding less than the reserves
+/// is never accepted. Anyone can reconcile.
+pub fn reconcile_vault(ctx: Context<ReconcileVault>) -> Result<()> {
+    reconcile(&mut ctx.accounts.vault, &ctx.accounts.vault_token_account)
+}
+
+pub(crate) fn reconcile(vault: &mut Account<Vault>, vault_token_account: &TokenAccount) -> Result<()> {
+    let actual = vault_token_account.amount;
+    // A flash loan is out only within its own transaction
+    let expected = vault
+        .reserves
+        .checked_sub(vault.flash_loan_amount)
+        .ok_or(ErrorCode::VaultDeficit)?;
+    if actual < expected {
+        return err!(ErrorCode::VaultDeficit);
+    }
+
+    let surplus = actual - expected;
+    vault.receive(surplus)?;
+    vault.donations = vault.donations.checked_add(surplus).ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(ReconciliationReport {
+        market: vault.market,
+        vault: vault.key(),
+        actual,
+        reserves: vault.reserves,
+        surplus,
+        donations: vault.donations,
+    });
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+
+use super::mint_index::component_accounts;
+use crate::IndexBasket;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RedeemIndex<'info> {
+    #[account(has_one = basket_mint @ ErrorCode::InvalidTokenMint)]
+    pub index: Account<'info, IndexBasket>,
+    #[account(mut)]
+    pub basket_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = basket_mint,
+        token::authority = user
+    )]
+    pub user_basket_account: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: per component, in index order,
+    // [user component account, index component account]
+}
+
+pub fn redeem_index<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RedeemIndex<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.basket_mint.to_account_info(),
+        from: ctx.accounts.user_basket_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::burn(cpi_ctx, amount)?;
+
+    let index = &ctx.accounts.index;
+    let index_id = index.index_id.to_le_bytes();
+    let seeds = &[b"index", index_id.as_ref(), &[index.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let pairs = component_accounts(index, ctx.remaining_accounts)?;
+    for (component, user_account, index_account) in pairs {
+        let released = IndexBasket::component_amount(&component, amount, false)?;
+        let cpi_accounts = Transfer {
+            from: index_account.to_account_info(),
+            to: user_account.to_account_info(),
+            authority: index.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, released)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+
+use crate::{BinaryMarket, OUTCOME_UNRESOLVED, OUTCOME_YES};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RedeemOutcomeTokens<'info> {
+    #[account(
+        has_one = collateral_mint @ ErrorCode::InvalidTokenMint,
+        has_one = yes_mint @ ErrorCode::InvalidTokenMint,
+        has_one = no_mint @ ErrorCode::InvalidTokenMint
+    )]
+    pub binary_market: Account<'info, BinaryMarket>,
+    pub collateral_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub yes_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub no_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = collateral_mint,
+        associated_token::authority = binary_market
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = collateral_mint,
+        token::authority = user
+    )]
+    pub user_collateral_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = yes_mint,
+        token::authority = user
+    )]
+    pub user_yes_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = no_mint,
+        token::authority = user
+    )]
+    pub user_no_account: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Before resolution a YES+NO pair redeems for one unit of collateral; after
+/// resolution each winning token does and losing tokens are worthless.
+pub fn redeem_outcome_tokens(ctx: Context<RedeemOutcomeTokens>, amount: u64) -> Result<()> {
+    let outcome = ctx.accounts.binary_market.outcome;
+    let burns = match outcome {
+        OUTCOME_UNRESOLVED => vec![
+            (&ctx.accounts.yes_mint, &ctx.accounts.user_yes_account),
+            (&ctx.accounts.no_mint, &ctx.accounts.user_no_account),
+        ],
+        OUTCOME_YES => vec![(&ctx.accounts.yes_mint, &ctx.accounts.user_yes_account)],
+        _ => vec![(&ctx.accounts.no_mint, &ctx.accounts.user_no_account)],
+    };
+    for (mint, from) in burns {
+        let cpi_accounts = Burn {
+            mint: mint.to_account_info(),
+            from: from.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::burn(cpi_ctx, amount)?;
+    }
+
+    let binary_market = &ctx.accounts.binary_market;
+    let market_id = binary_market.market_id.to_le_bytes();
+    let seeds = &[
+        b"binary_market",
+        binary_market.collateral_mint.as_ref(),
+        market_id.as_ref(),
+        &[binary_market.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.collateral_vault.to_account_info(),
+        to: ctx.accounts.user_collateral_account.to_account_info(),
+        authority: binary_market.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)
+}
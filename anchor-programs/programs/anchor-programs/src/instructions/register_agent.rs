@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::Agent;
+use crate::{Agent, AgentListing, Config, GlobalStats};
 
 #[derive(Accounts)]
 pub struct RegisterAgent<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
     #[account(
         init,
         payer = user,
@@ -11,15 +13,34 @@ pub struct RegisterAgent<'info> {
         bump
     )]
     pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + AgentListing::INIT_SPACE,
+        seeds = [b"agent_listing", config.agent_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agent_listing: Account<'info, AgentListing>,
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-
+/// Registers the signer's agent and lists it after the agents before it.
 pub fn register_agent(ctx: Context<RegisterAgent>) -> Result<()> {
     let agent = &mut ctx.accounts.agent;
     agent.owner = ctx.accounts.user.key();
+    agent.registrant = ctx.accounts.user.key();
     agent.bump = ctx.bumps.agent;
+
+    let config = &mut ctx.accounts.config;
+    let listing = &mut ctx.accounts.agent_listing;
+    listing.agent = agent.key();
+    listing.index = config.agent_count;
+    listing.bump = ctx.bumps.agent_listing;
+    config.agent_count += 1;
+    ctx.accounts.global_stats.agents += 1;
     Ok(())
 }
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::oracle::read_feed_price;
+use crate::{Agent, Market, RelayerDebt, PRICE_SCALE};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ReimburseRelayer<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"relayer_debt", market.key().as_ref(), agent.key().as_ref()],
+        bump = relayer_debt.bump
+    )]
+    pub relayer_debt: Account<'info, RelayerDebt>,
+    /// CHECK: the market's SOL price feed, decoded by the oracle module
+    #[account(
+        address = market.relayer_fee_oracle @ ErrorCode::OracleAccountMismatch
+    )]
+    pub relayer_fee_oracle: UncheckedAccount<'info>,
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = market.token_a,
+        token::authority = user
+    )]
+    pub user_token_account_a: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = market.token_a,
+        token::authority = relayer_debt.relayer
+    )]
+    pub relayer_token_account_a: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Repays up to `lamports` of relayer debt in token A at the feed's SOL
+/// price, rounded up in the relayer's favour.
+pub fn reimburse_relayer(ctx: Context<ReimburseRelayer>, lamports: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let lamports = lamports.min(ctx.accounts.relayer_debt.lamports);
+    let price = read_feed_price(
+        market,
+        &ctx.accounts.relayer_fee_oracle.to_account_info(),
+        Clock::get()?.slot,
+    )?;
+
+    let denominator = LAMPORTS_PER_SOL as u128 * PRICE_SCALE as u128;
+    let amount = (lamports as u128 * price.price as u128 * 10u128.pow(market.decimals_a as u32))
+        .div_ceil(denominator);
+    let amount = u64::try_from(amount).map_err(|_| ErrorCode::MathOverflow)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account_a.to_account_info(),
+        to: ctx.accounts.relayer_token_account_a.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    let relayer_debt = &mut ctx.accounts.relayer_debt;
+    relayer_debt.lamports = relayer_debt
+        .lamports
+        .checked_sub(lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{Agent, Bond, EmissionsSchedule, Market};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RelockBond<'info> {
+    #[account(address = schedule.market @ ErrorCode::InvalidMarket)]
+    pub market: Account<'info, Market>,
+    pub schedule: Account<'info, EmissionsSchedule>,
+    #[account(
+        mut,
+        seeds = [b"bond", schedule.key().as_ref(), agent.key().as_ref()],
+        bump = bond.bump,
+        constraint = bond.amount > 0 @ ErrorCode::InvalidTradeAmount
+    )]
+    pub bond: Account<'info, Bond>,
+    #[account(constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner)]
+    pub agent: Account<'info, Agent>,
+    pub user: Signer<'info>,
+}
+
+/// Extends the agent's bond to `unlock`, restoring the boost its lock has
+/// decayed by. An expired bond can be locked again without withdrawing it.
+pub fn relock_bond(ctx: Context<RelockBond>, unlock: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    ctx.accounts.bond.relock(unlock, market.now()?, market.epoch_slots.max(1))
+}
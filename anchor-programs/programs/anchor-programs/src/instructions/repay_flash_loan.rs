@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{Market, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RepayFlashLoan<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub token_mint: Account<'info, Mint>,
+    // Must stay at index 2, flash_loan looks for it there
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), token_mint.key().as_ref()],
+        bump = vault.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = user
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn repay_flash_loan(ctx: Context<RepayFlashLoan>) -> Result<()> {
+    let amount = ctx.accounts.vault.flash_loan_amount;
+    let fee = ctx.accounts.market.taker_fee(amount)?;
+    let repayment = amount.checked_add(fee).ok_or(ErrorCode::MathOverflow)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.payer_token_account.to_account_info(),
+        to: ctx.accounts.vault_token_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, repayment)?;
+
+    ctx.accounts.vault.flash_loan_amount = 0;
+    ctx.accounts.vault.receive(fee)?;
+    let token = ctx.accounts.vault.token;
+    ctx.accounts.market.accrue_fee(token, fee)
+}
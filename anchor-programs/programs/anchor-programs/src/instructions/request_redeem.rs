@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{Market, RedemptionRequest, StrategyVault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RequestRedeem<'info> {
+    #[account(
+        has_one = market @ ErrorCode::InvalidMarket,
+        has_one = share_mint @ ErrorCode::InvalidTokenMint
+    )]
+    pub strategy_vault: Account<'info, StrategyVault>,
+    pub market: Account<'info, Market>,
+    pub share_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = owner
+    )]
+    pub owner_share_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = share_mint,
+        associated_token::authority = strategy_vault
+    )]
+    pub escrow_share_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + RedemptionRequest::INIT_SPACE,
+        seeds = [b"redemption", strategy_vault.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub redemption_request: Account<'info, RedemptionRequest>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves `shares` into escrow to be redeemed after the current epoch ends.
+/// An owner has at most one request queued per strategy vault.
+pub fn request_redeem(ctx: Context<RequestRedeem>, shares: u64) -> Result<()> {
+    if shares == 0 {
+        return err!(ErrorCode::InvalidShareAmount);
+    }
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.owner_share_account.to_account_info(),
+        to: ctx.accounts.escrow_share_account.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, shares)?;
+
+    let redemption_request = &mut ctx.accounts.redemption_request;
+    redemption_request.strategy_vault = ctx.accounts.strategy_vault.key();
+    redemption_request.owner = ctx.accounts.owner.key();
+    redemption_request.shares = shares;
+    redemption_request.epoch = ctx.accounts.market.current_epoch(ctx.accounts.market.now()?);
+    redemption_request.bump = ctx.bumps.redemption_request;
+    Ok(())
+}
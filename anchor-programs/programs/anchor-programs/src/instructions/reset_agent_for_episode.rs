@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount};
+
+use crate::{AdlQueue, Agent, Balance, Config, Market, Position, Trade, Vault, FEATURE_EPISODE_RESET};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+
+#[derive(Accounts)]
+pub struct SetEpisodeCapital<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_EPISODE_RESET) @ ErrorCode::FeatureDisabled
+    )]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResetAgentForEpisode<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_EPISODE_RESET) @ ErrorCode::FeatureDisabled
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    /// CHECK: the agent's owner, refunded the rent of its trade
+    #[account(mut, address = agent.owner @ ErrorCode::InvalidAgentOwner)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"trade", agent.key().as_ref(), market.key().as_ref(), &trade.order_id.to_le_bytes()],
+        bump = trade.bump,
+        close = owner
+    )]
+    pub trade: Option<Account<'info, Trade>>,
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), agent.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+    /// CHECK: the market's ADL queue, which the zeroed position is dropped
+    /// from once `update_adl_rank` has created it
+    #[account(mut, seeds = [b"adl", market.key().as_ref()], bump)]
+    pub adl_queue: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_a,
+        associated_token::authority = vault_a
+    )]
+    pub vault_token_account_a: Account<'info, TokenAccount>,
+    #[account(mut, address = market.token_a @ ErrorCode::InvalidTokenMint)]
+    pub token_a_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_a.key().as_ref(), agent.key().as_ref()],
+        bump = balance_a.bump
+    )]
+    pub balance_a: Account<'info, Balance>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_b,
+        associated_token::authority = vault_b
+    )]
+    pub vault_token_account_b: Account<'info, TokenAccount>,
+    #[account(mut, address = market.token_b @ ErrorCode::InvalidTokenMint)]
+    pub token_b_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_b.key().as_ref(), agent.key().as_ref()],
+        bump = balance_b.bump
+    )]
+    pub balance_b: Account<'info, Balance>,
+
+    /// Mint authority of both tokens whenever capital has to be minted
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: the agent's other trades, closed like `trade`
+}
+
+/// Sets the token A and token B `reset_agent_for_episode` leaves an agent
+/// holding.
+pub fn set_episode_capital(ctx: Context<SetEpisodeCapital>, capital_a: u64, capital_b: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.episode_capital_a = capital_a;
+    market.episode_capital_b = capital_b;
+    Ok(())
+}
+
+/// Puts an agent back at the start of an episode on a simulated market, where
+/// the config enables FEATURE_EPISODE_RESET: every trade it has open is
+/// cancelled and closed, all of which have to be passed and on this market
+/// so no order rests into the next episode; its position zeroed and dropped from the ADL queue and its
+/// balances set to the market's episode capital, minting into the vaults
+/// what that adds and burning what it takes away. Collateral counts toward
+/// the token A held; open exposure is dropped without realizing its PnL, so
+/// an episode resets every agent holding a position.
+pub fn reset_agent_for_episode<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResetAgentForEpisode<'info>>,
+) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    let market = &mut accounts.market;
+    if let Some(trade) = accounts.trade.as_mut() {
+        OrderUpdated::emit_removed(market, trade)?;
+    }
+    let mut closed = accounts.trade.iter().map(|trade| trade.key()).collect::<Vec<_>>();
+    for info in ctx.remaining_accounts {
+        let mut trade = Account::<Trade>::try_from(info)?;
+        if trade.agent != accounts.agent.key() || trade.market != market.key() {
+            return err!(ErrorCode::InvalidAgent);
+        }
+        if closed.contains(&trade.key()) {
+            return err!(ErrorCode::DuplicateOrder);
+        }
+        closed.push(trade.key());
+        OrderUpdated::emit_removed(market, &mut trade)?;
+        trade.close(accounts.owner.to_account_info())?;
+    }
+    if closed.len() as u64 != accounts.agent.open_trades {
+        return err!(ErrorCode::IncompleteTrades);
+    }
+    accounts.agent.open_trades = 0;
+    let mut collateral = 0i64;
+    if let Some(position) = accounts.position.as_mut() {
+        position.settle(market)?;
+        market.update_open_interest(position.base, 0)?;
+        collateral = position.quote;
+        position.base = 0;
+        position.quote = 0;
+        position.cost_basis = 0;
+        if accounts.adl_queue.owner == &crate::ID {
+            let mut data = accounts.adl_queue.try_borrow_mut_data()?;
+            let mut queue = AdlQueue::try_deserialize(&mut &data[..])?;
+            queue.remove(&position.key());
+            queue.try_serialize(&mut &mut data[..])?;
+        }
+    }
+
+    let held_a = accounts.balance_a.amount as i128 + collateral as i128;
+    let (capital_a, capital_b) = (market.episode_capital_a, market.episode_capital_b);
+    let pair = (market.token_a, market.token_b);
+    let (program, authority) = (&accounts.token_program, &accounts.authority);
+    let vault_a = (&mut accounts.vault_a, &accounts.vault_token_account_a, &accounts.token_a_mint);
+    settle_capital(pair, vault_a, program, authority, held_a, capital_a)?;
+    let held_b = accounts.balance_b.amount as i128;
+    let vault_b = (&mut accounts.vault_b, &accounts.vault_token_account_b, &accounts.token_b_mint);
+    settle_capital(pair, vault_b, program, authority, held_b, capital_b)?;
+    accounts.balance_a.amount = capital_a;
+    accounts.balance_b.amount = capital_b;
+    Ok(())
+}
+
+/// Mints into or burns from a vault the difference between what an agent
+/// `held` in it and the `capital` it is reset to.
+fn settle_capital<'info>(
+    (token_a, token_b): (Pubkey, Pubkey),
+    (vault, vault_token_account, mint): (&mut Account<'info, Vault>, &Account<'info, TokenAccount>, &Account<'info, Mint>),
+    token_program: &Program<'info, Token>,
+    authority: &Signer<'info>,
+    held: i128,
+    capital: u64,
+) -> Result<()> {
+    let change = capital as i128 - held;
+    let amount = u64::try_from(change.unsigned_abs()).map_err(|_| ErrorCode::MathOverflow)?;
+    if change > 0 {
+        let cpi_accounts = MintTo {
+            mint: mint.to_account_info(),
+            to: vault_token_account.to_account_info(),
+            authority: authority.to_account_info(),
+        };
+        token::mint_to(CpiContext::new(token_program.to_account_info(), cpi_accounts), amount)?;
+        vault.receive(amount)
+    } else if change < 0 {
+        let seeds = &[b"vault", token_a.as_ref(), token_b.as_ref(), vault.token.as_ref(), &[vault.bump]];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Burn {
+            mint: mint.to_account_info(),
+            from: vault_token_account.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::burn(cpi_ctx, amount)?;
+        vault.send(amount)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::{BinaryMarket, OUTCOME_NO, OUTCOME_UNRESOLVED, OUTCOME_YES};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(
+        mut,
+        has_one = oracle @ ErrorCode::Unauthorized
+    )]
+    pub binary_market: Account<'info, BinaryMarket>,
+    pub oracle: Signer<'info>,
+}
+
+pub fn resolve_market(ctx: Context<ResolveMarket>, outcome: u8) -> Result<()> {
+    if outcome != OUTCOME_YES && outcome != OUTCOME_NO {
+        return err!(ErrorCode::InvalidOutcome);
+    }
+    let binary_market = &mut ctx.accounts.binary_market;
+    if binary_market.outcome != OUTCOME_UNRESOLVED {
+        return err!(ErrorCode::MarketAlreadyResolved);
+    }
+    binary_market.outcome = outcome;
+    Ok(())
+}
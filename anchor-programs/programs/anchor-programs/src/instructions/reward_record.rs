@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::{Agent, Market, RewardRecord, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetInventoryPenalty<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct OpenRewardRecord<'info> {
+    pub market: Account<'info, Market>,
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RewardRecord::INIT_SPACE,
+        seeds = [b"reward", market.key().as_ref(), agent.key().as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub reward: Account<'info, RewardRecord>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleReward<'info> {
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket,
+        seeds = [b"reward", market.key().as_ref(), reward.agent.as_ref(), &reward.epoch.to_le_bytes()],
+        bump = reward.bump
+    )]
+    pub reward: Account<'info, RewardRecord>,
+}
+
+/// Sets the share of its inventory's value an agent's epoch reward is
+/// charged for holding it when settled.
+pub fn set_inventory_penalty(ctx: Context<SetInventoryPenalty>, inventory_penalty_bps: u16) -> Result<()> {
+    if inventory_penalty_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidInventoryPenalty);
+    }
+    ctx.accounts.market.inventory_penalty_bps = inventory_penalty_bps;
+    Ok(())
+}
+
+/// Creates an agent's reward record for the current `epoch`, for crankers to
+/// pass to `match_trades`. Anyone can open it; fills before it exists aren't
+/// counted.
+pub fn open_reward_record(ctx: Context<OpenRewardRecord>, epoch: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    if market.current_epoch(market.now()?) != epoch {
+        return err!(ErrorCode::InvalidEpoch);
+    }
+    let reward = &mut ctx.accounts.reward;
+    reward.market = market.key();
+    reward.agent = ctx.accounts.agent.key();
+    reward.epoch = epoch;
+    reward.inventory_at = market.now()?;
+    reward.bump = ctx.bumps.reward;
+    Ok(())
+}
+
+/// Splits an ended epoch's reward into pnl, fees and the inventory penalty,
+/// marking what the agent still holds at the market's last fill price and
+/// charging the market's current penalty on the inventory it held on
+/// average over the epoch, so a market maker staying flat pays least.
+/// Anyone can settle it, once.
+pub fn settle_reward(ctx: Context<SettleReward>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let reward = &mut ctx.accounts.reward;
+    if reward.epoch >= market.current_epoch(market.now()?) {
+        return err!(ErrorCode::EpochNotEnded);
+    }
+    if reward.settled {
+        return err!(ErrorCode::RewardAlreadySettled);
+    }
+    reward.settle(market)
+}
+
+/// Reward records passed alongside a fill's orders, for the fills of any of
+/// their agents to count toward. Each names its agent, so they can come in
+/// any order, at most one per agent.
+pub(crate) fn load_rewards<'info>(
+    market: &Pubkey,
+    infos: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, RewardRecord>>> {
+    let mut rewards: Vec<Account<RewardRecord>> = Vec::with_capacity(infos.len());
+    for info in infos {
+        let reward = Account::<RewardRecord>::try_from(info)?;
+        if reward.market != *market {
+            return err!(ErrorCode::InvalidMarket);
+        }
+        if rewards.iter().any(|other| other.agent == reward.agent) {
+            return err!(ErrorCode::DuplicateRewardRecord);
+        }
+        rewards.push(reward);
+    }
+    Ok(rewards)
+}
+
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{EmissionsSchedule, MAX_EMISSIONS_BOOST_BPS};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetEmissionsBoost<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::Unauthorized)]
+    pub schedule: Account<'info, EmissionsSchedule>,
+    pub authority: Signer<'info>,
+}
+
+/// Sets the bond that earns a boost and the boost of the longest lock.
+/// Fills already counted keep the boost they were counted with.
+pub fn set_emissions_boost(ctx: Context<SetEmissionsBoost>, min_bond: u64, max_boost_bps: u16) -> Result<()> {
+    if max_boost_bps > MAX_EMISSIONS_BOOST_BPS {
+        return err!(ErrorCode::InvalidEmissionsSchedule);
+    }
+    let schedule = &mut ctx.accounts.schedule;
+    schedule.min_bond = min_bond;
+    schedule.max_boost_bps = max_boost_bps;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::{Market, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetFundingParams<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+/// Sets the funding cap per epoch and the yearly rate charged on negative
+/// position collateral. What accrued under the old parameters is folded into
+/// the indices first, and a zero cap stops funding at once.
+pub fn set_funding_params(
+    ctx: Context<SetFundingParams>,
+    max_funding_bps: u16,
+    borrow_rate_bps: u16,
+) -> Result<()> {
+    if max_funding_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidFundingParams);
+    }
+    let market = &mut ctx.accounts.market;
+    market.accrue()?;
+    market.max_funding_bps = max_funding_bps;
+    market.borrow_rate_bps = borrow_rate_bps;
+    if max_funding_bps == 0 {
+        market.funding_per_epoch = 0;
+    }
+    Ok(())
+}
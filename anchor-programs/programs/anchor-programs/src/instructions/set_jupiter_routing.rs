@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::Market;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetJupiterRouting<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+pub fn set_jupiter_routing(ctx: Context<SetJupiterRouting>, enabled: bool) -> Result<()> {
+    ctx.accounts.market.jupiter_routing = enabled;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::{CongestionTier, FeeTier, Market, MAX_CONGESTION_TIERS, MAX_FEE_TIERS};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetMarketFees<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+pub fn set_market_fees(
+    ctx: Context<SetMarketFees>,
+    maker_fee_bps: i16,
+    taker_fee_bps: u16,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.maker_fee_bps = maker_fee_bps;
+    market.taker_fee_bps = taker_fee_bps;
+    market.validate_fees()
+}
+
+pub fn set_fee_tiers(ctx: Context<SetMarketFees>, tiers: Vec<FeeTier>) -> Result<()> {
+    if tiers.len() > MAX_FEE_TIERS {
+        return err!(ErrorCode::InvalidFeeSchedule);
+    }
+    let market = &mut ctx.accounts.market;
+    market.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+    market.fee_tiers[..tiers.len()].copy_from_slice(&tiers);
+    market.fee_tier_count = tiers.len() as u8;
+    market.validate_fees()
+}
+
+/// Surcharges placements by how many orders are already resting, each tier
+/// charging its `surcharge` once `min_open_orders` is reached.
+pub fn set_congestion_tiers(ctx: Context<SetMarketFees>, tiers: Vec<CongestionTier>) -> Result<()> {
+    if tiers.len() > MAX_CONGESTION_TIERS {
+        return err!(ErrorCode::InvalidFeeSchedule);
+    }
+    let market = &mut ctx.accounts.market;
+    market.congestion_tiers = [CongestionTier::default(); MAX_CONGESTION_TIERS];
+    market.congestion_tiers[..tiers.len()].copy_from_slice(&tiers);
+    market.congestion_tier_count = tiers.len() as u8;
+    Ok(())
+}
+
+/// Scales taker fees with realized volatility: each 100% of `volatility_bps`
+/// adds `coefficient` bps, clamped to `[min_taker_fee_bps, max_taker_fee_bps]`.
+pub fn set_volatility_fees(
+    ctx: Context<SetMarketFees>,
+    coefficient: u16,
+    min_taker_fee_bps: u16,
+    max_taker_fee_bps: u16,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.volatility_fee_coefficient = coefficient;
+    market.min_taker_fee_bps = min_taker_fee_bps;
+    market.max_taker_fee_bps = max_taker_fee_bps;
+    market.validate_fees()
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::{Market, MATCHING_PRO_RATA};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetMatchingPolicy<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+/// Sets how makers at one price share a taker. Only pro-rata markets count
+/// their orders by level, so the policy only changes while none are resting.
+pub fn set_matching_policy(ctx: Context<SetMatchingPolicy>, matching_policy: u8) -> Result<()> {
+    if matching_policy > MATCHING_PRO_RATA {
+        return err!(ErrorCode::InvalidMatchingPolicy);
+    }
+    let market = &mut ctx.accounts.market;
+    if matching_policy != market.matching_policy && market.open_orders > 0 {
+        return err!(ErrorCode::OrdersResting);
+    }
+    market.matching_policy = matching_policy;
+    Ok(())
+}
+
+/// Names the VRF randomness account pro-rata levels are ordered by, or
+/// `Pubkey::default()` to leave the order to the cranker.
+pub fn set_tie_break_randomness(ctx: Context<SetMatchingPolicy>, randomness_account: Pubkey) -> Result<()> {
+    ctx.accounts.market.randomness_account = randomness_account;
+    Ok(())
+}
+
+/// Sets how compact actions decode: the token B size of size bucket 0 and
+/// the price tick offsets count in. A lot of 0 turns `submit_action` off.
+pub fn set_action_encoding(ctx: Context<SetMatchingPolicy>, action_lot: u64, action_tick: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.action_lot = action_lot;
+    market.action_tick = action_tick;
+    Ok(())
+}
+
+/// Caps the token B size of orders placed on the market, 0 = uncapped.
+/// Orders already resting keep their size.
+pub fn set_max_order_size(ctx: Context<SetMatchingPolicy>, max_order_size: u64) -> Result<()> {
+    ctx.accounts.market.max_order_size = max_order_size;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::adapters::{MIRROR_NONE, MIRROR_RAYDIUM_CLMM};
+use crate::Market;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetMirrorPool<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+pub fn set_mirror_pool(ctx: Context<SetMirrorPool>, venue: u8, pool: Pubkey) -> Result<()> {
+    if venue > MIRROR_RAYDIUM_CLMM || (venue != MIRROR_NONE && pool == Pubkey::default()) {
+        return err!(ErrorCode::InvalidMirrorVenue);
+    }
+    let market = &mut ctx.accounts.market;
+    market.mirror_venue = venue;
+    market.mirror_pool = pool;
+    Ok(())
+}
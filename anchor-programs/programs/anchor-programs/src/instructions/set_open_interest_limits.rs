@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::Market;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetOpenInterestLimits<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+pub fn set_open_interest_limits(
+    ctx: Context<SetOpenInterestLimits>,
+    max_long: u64,
+    max_short: u64,
+    skew_scale: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.max_open_interest_long = max_long;
+    market.max_open_interest_short = max_short;
+    market.skew_scale = skew_scale;
+    Ok(())
+}
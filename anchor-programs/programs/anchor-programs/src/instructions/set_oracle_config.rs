@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::{Market, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetOracleConfig<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+pub fn set_oracle_config(
+    ctx: Context<SetOracleConfig>,
+    primary_oracle: Pubkey,
+    fallback_oracle: Pubkey,
+    max_staleness_slots: u64,
+    max_confidence_bps: u16,
+) -> Result<()> {
+    if max_staleness_slots == 0 || max_confidence_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidOracleConfig);
+    }
+    let market = &mut ctx.accounts.market;
+    market.primary_oracle = primary_oracle;
+    market.fallback_oracle = fallback_oracle;
+    market.oracle_max_staleness_slots = max_staleness_slots;
+    market.oracle_max_confidence_bps = max_confidence_bps;
+    Ok(())
+}
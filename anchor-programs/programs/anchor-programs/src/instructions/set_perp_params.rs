@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::{Market, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetPerpParams<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+/// Enables perpetual positions on the market. The liquidation fee is paid
+/// out of remaining equity, so it cannot exceed the maintenance margin.
+pub fn set_perp_params(
+    ctx: Context<SetPerpParams>,
+    initial_margin_bps: u16,
+    maintenance_margin_bps: u16,
+    liquidation_fee_bps: u16,
+) -> Result<()> {
+    if maintenance_margin_bps == 0
+        || maintenance_margin_bps > initial_margin_bps
+        || initial_margin_bps as u64 > BPS_DENOMINATOR
+        || liquidation_fee_bps > maintenance_margin_bps
+    {
+        return err!(ErrorCode::InvalidMarginParams);
+    }
+    let market = &mut ctx.accounts.market;
+    market.initial_margin_bps = initial_margin_bps;
+    market.maintenance_margin_bps = maintenance_margin_bps;
+    market.liquidation_fee_bps = liquidation_fee_bps;
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::Market;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetRelayer<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+pub fn set_relayer(ctx: Context<SetRelayer>, relayer: Pubkey, fee_oracle: Pubkey) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.relayer = relayer;
+    market.relayer_fee_oracle = fee_oracle;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::Market;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetSandwichGuard<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+pub fn set_sandwich_guard(ctx: Context<SetSandwichGuard>, enabled: bool) -> Result<()> {
+    ctx.accounts.market.sandwich_guard = enabled;
+    Ok(())
+}
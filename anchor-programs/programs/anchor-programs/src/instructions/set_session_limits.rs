@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::Agent;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetSessionLimits<'info> {
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub user: Signer<'info>,
+}
+
+/// Bounds what `session_key` may commit to orders, token A to bids and
+/// token B to asks: in total over its session and per epoch, 0 leaving
+/// either unbounded. What the session has already spent still counts.
+pub fn set_session_limits(
+    ctx: Context<SetSessionLimits>,
+    session_key: Pubkey,
+    spend_limits: [u64; 2],
+    epoch_caps: [u64; 2],
+) -> Result<()> {
+    let delegate = ctx
+        .accounts
+        .agent
+        .delegate_mut(&session_key)
+        .ok_or(ErrorCode::InvalidSession)?;
+    delegate.spend_limit = spend_limits;
+    delegate.epoch_cap = epoch_caps;
+    Ok(())
+}
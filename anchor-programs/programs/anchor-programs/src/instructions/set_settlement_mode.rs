@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::Market;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetSettlementMode<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+pub fn set_settlement_mode(ctx: Context<SetSettlementMode>, net_settlement: bool) -> Result<()> {
+    ctx.accounts.market.net_settlement = net_settlement;
+    Ok(())
+}
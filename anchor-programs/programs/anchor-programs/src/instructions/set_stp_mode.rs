@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, STP_DECREMENT_BOTH};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetStpMode<'info> {
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub user: Signer<'info>,
+}
+
+pub fn set_stp_mode(ctx: Context<SetStpMode>, stp_mode: u8) -> Result<()> {
+    if stp_mode > STP_DECREMENT_BOTH {
+        return err!(ErrorCode::InvalidStpMode);
+    }
+    ctx.accounts.agent.stp_mode = stp_mode;
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::clock;
+use crate::errors::ErrorCode;
+use crate::{Market, TIME_BASE_SLOT, TIME_BASE_UNIX};
+
+#[derive(Accounts)]
+pub struct SetTimeBase<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+/// Switches the market between slot and unix time, keeping its epochs the
+/// same length. Only before the market's first order, since times already
+/// recorded on it would be read in the wrong units.
+pub fn set_time_base(ctx: Context<SetTimeBase>, time_base: u8) -> Result<()> {
+    if time_base != TIME_BASE_SLOT && time_base != TIME_BASE_UNIX {
+        return err!(ErrorCode::InvalidTimeBase);
+    }
+    let market = &mut ctx.accounts.market;
+    if market.sequence != 0 {
+        return err!(ErrorCode::TimeBaseLocked);
+    }
+    // Funding and interest accrue up to the switch in the old units
+    market.accrue()?;
+    let epoch_slots = clock::to_slots(market.time_base, market.epoch_slots);
+    market.epoch_slots = clock::from_slots(time_base, epoch_slots).max(1);
+    // Trading hours keep their place in the epoch
+    let convert = |offset| clock::from_slots(time_base, clock::to_slots(market.time_base, offset));
+    let (open, close) = (convert(market.trading_open), convert(market.trading_close));
+    let auction_slots = convert(market.auction_slots);
+    market.trading_open = open.min(market.epoch_slots - 1);
+    market.trading_close = close.min(market.epoch_slots - 1);
+    market.auction_slots = auction_slots.min(market.session_length() / 2);
+    market.time_base = time_base;
+    market.accrued_at = market.now()?;
+    Ok(())
+}
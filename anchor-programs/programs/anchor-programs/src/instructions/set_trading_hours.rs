@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::Market;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetTradingHours<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+/// Sets the offsets into each epoch, in market time, between which orders
+/// can be placed and matched. Equal offsets keep the market always open;
+/// cancels and withdrawals are never closed. The session must still hold the
+/// market's auctions.
+pub fn set_trading_hours(ctx: Context<SetTradingHours>, open: u64, close: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    if open >= market.epoch_slots || close >= market.epoch_slots {
+        return err!(ErrorCode::InvalidTradingHours);
+    }
+    market.trading_open = open;
+    market.trading_close = close;
+    market.validate_auctions()
+}
+
+/// Sets the fill-to-fill price move, in bps, that halts the market, or 0 for
+/// none, and how long a halt and the reopening auction after it last. A
+/// halted market only trades again through that auction.
+pub fn set_circuit_breaker(
+    ctx: Context<SetTradingHours>,
+    threshold_bps: u32,
+    halt_slots: u64,
+    reopen_auction_slots: u64,
+) -> Result<()> {
+    if threshold_bps != 0 && reopen_auction_slots == 0 {
+        return err!(ErrorCode::InvalidCircuitBreaker);
+    }
+    let market = &mut ctx.accounts.market;
+    market.circuit_breaker_bps = threshold_bps;
+    market.halt_slots = halt_slots;
+    market.reopen_auction_slots = reopen_auction_slots;
+    Ok(())
+}
+
+/// Sets how long the call auctions opening and closing each session run,
+/// or 0 for continuous trading throughout. Both have to fit in the session.
+pub fn set_auction_slots(ctx: Context<SetTradingHours>, auction_slots: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.auction_slots = auction_slots;
+    market.validate_auctions()
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Token, TokenAccount, Transfer},
+};
+
+use crate::{Agent, Config, Market, Settlement, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SettleEpoch<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"settlement", market.key().as_ref(), agent.key().as_ref()],
+        bump = settlement.bump,
+    )]
+    pub settlement: Account<'info, Settlement>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = market.token_a,
+        token::authority = user
+    )]
+    pub user_token_account_a: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = market.token_b,
+        token::authority = user
+    )]
+    pub user_token_account_b: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_a,
+        associated_token::authority = vault_a
+    )]
+    pub vault_token_account_a: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_b,
+        associated_token::authority = vault_b
+    )]
+    pub vault_token_account_b: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn settle_epoch(ctx: Context<SettleEpoch>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let epoch = market.current_epoch(market.now()?);
+    // Emergency mode lets agents settle out without waiting for the epoch
+    if ctx.accounts.settlement.epoch >= epoch && !ctx.accounts.config.emergency_mode {
+        return err!(ErrorCode::EpochNotEnded);
+    }
+
+    let (delta_a, delta_b) = (ctx.accounts.settlement.delta_a, ctx.accounts.settlement.delta_b);
+    let settlement = &mut ctx.accounts.settlement;
+    settlement.delta_a = 0;
+    settlement.delta_b = 0;
+    settlement.epoch = epoch;
+
+    settle_token(
+        &ctx.accounts.token_program,
+        &ctx.accounts.user,
+        &ctx.accounts.user_token_account_a,
+        &mut ctx.accounts.vault_a,
+        &ctx.accounts.vault_token_account_a,
+        market,
+        delta_a,
+    )?;
+    settle_token(
+        &ctx.accounts.token_program,
+        &ctx.accounts.user,
+        &ctx.accounts.user_token_account_b,
+        &mut ctx.accounts.vault_b,
+        &ctx.accounts.vault_token_account_b,
+        market,
+        delta_b,
+    )
+}
+
+/// One transfer per token: the agent pays in what it owes or the vault pays
+/// out what it is owed.
+fn settle_token<'info>(
+    token_program: &Program<'info, Token>,
+    user: &Signer<'info>,
+    user_token_account: &Account<'info, TokenAccount>,
+    vault: &mut Account<'info, Vault>,
+    vault_token_account: &Account<'info, TokenAccount>,
+    market: &Market,
+    delta: i64,
+) -> Result<()> {
+    if delta < 0 {
+        let cpi_accounts = Transfer {
+            from: user_token_account.to_account_info(),
+            to: vault_token_account.to_account_info(),
+            authority: user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, delta.unsigned_abs())?;
+        vault.receive(delta.unsigned_abs())?;
+    } else if delta > 0 {
+        let seeds = &[
+            b"vault",
+            market.token_a.as_ref(),
+            market.token_b.as_ref(),
+            vault.token.as_ref(),
+            &[vault.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: vault_token_account.to_account_info(),
+            to: user_token_account.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, delta as u64)?;
+        vault.send(delta as u64)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{Market, Snapshot, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct SnapshotMarket<'info> {
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Snapshot::INIT_SPACE,
+        seeds = [b"snapshot", market.key().as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        associated_token::mint = market.token_a,
+        associated_token::authority = vault_a
+    )]
+    pub vault_token_account_a: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+    #[account(
+        associated_token::mint = market.token_b,
+        associated_token::authority = vault_b
+    )]
+    pub vault_token_account_b: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Records the market's aggregates for the current `epoch`. Anyone can take
+/// the snapshot, and only the first call in an epoch succeeds.
+pub fn snapshot_market(ctx: Context<SnapshotMarket>, epoch: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let slot = Clock::get()?.slot;
+    if market.current_epoch(market.now()?) != epoch {
+        return err!(ErrorCode::InvalidEpoch);
+    }
+
+    let snapshot = &mut ctx.accounts.snapshot;
+    snapshot.market = market.key();
+    snapshot.epoch = epoch;
+    snapshot.slot = slot;
+    snapshot.sequence = market.sequence;
+    snapshot.reserve_a = ctx.accounts.vault_token_account_a.amount;
+    snapshot.reserve_b = ctx.accounts.vault_token_account_b.amount;
+    snapshot.last_price = market.last_price;
+    snapshot.twap_price = market.twap_price;
+    snapshot.open_interest_long = market.open_interest_long;
+    snapshot.open_interest_short = market.open_interest_short;
+    snapshot.fees_accrued = market.fees_accrued;
+    snapshot.fees_accrued_b = market.fees_accrued_b;
+    snapshot.bump = ctx.bumps.snapshot;
+    Ok(())
+}
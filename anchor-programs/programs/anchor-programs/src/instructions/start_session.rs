@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, SESSION_SCOPES_ALL};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct StartSession<'info> {
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub user: Signer<'info>,
+}
+
+/// Delegates trading to `session_key` until `expiry_slot`, alongside the
+/// agent's other delegates. Replaces the key's earlier session and its
+/// allowance; an expiry at or before the current slot ends it.
+pub fn start_session(
+    ctx: Context<StartSession>,
+    session_key: Pubkey,
+    expiry_slot: u64,
+    scope: u8,
+) -> Result<()> {
+    if scope == 0
+        || scope & !SESSION_SCOPES_ALL != 0
+        || session_key == ctx.accounts.agent.owner
+    {
+        return err!(ErrorCode::InvalidSession);
+    }
+    let slot = Clock::get()?.slot;
+    ctx.accounts.agent.start_session(session_key, expiry_slot, scope, slot)
+}
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+
+use crate::{
+    Agent, Balance, Config, Market, OrderAction, Trade, Vault, ACTION_LEN, FEATURE_BATCHING, FEATURE_ORDER_BOOK,
+    SESSION_SCOPE_PLACE,
+};
+use crate::errors::ErrorCode;
+use crate::events::OrderUpdated;
+use crate::instructions::place_trade::{open_order, place, PlaceTrade};
+
+/// Accounts passed for each action in `submit_actions_batch`: the agent, its
+/// trade of the action's id on the market and its balance in vault A.
+const ACTION_ACCOUNTS: usize = 3;
+
+#[derive(Accounts)]
+pub struct SubmitActionsBatch<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_ORDER_BOOK) @ ErrorCode::FeatureDisabled,
+        constraint = config.enabled(FEATURE_BATCHING) @ ErrorCode::FeatureDisabled,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+    /// Holds the balances that pay the market's congestion surcharge
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    /// Owner or session key of every agent acted for, paying for any trade
+    /// accounts the batch creates
+    #[account(mut)]
+    pub coordinator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: for each action, the agent, its trade of the
+    // action's id on the market and its balance in vault A
+}
+
+/// Places the order a compact action encodes as the agent's trade
+/// `order_id`, see `ACTION_LEN` and `Market::decode_action`, so an agent's
+/// whole decision fits in three bytes. It rests untagged and fully
+/// displayed, like any other order.
+pub fn submit_action(ctx: Context<PlaceTrade>, order_id: u64, action: [u8; ACTION_LEN]) -> Result<()> {
+    let params = ctx.accounts.market.decode_action(action)?;
+    place(ctx, order_id, &params)
+}
+
+/// Submits one compact action for each of many agents, all authorizing the
+/// coordinator as their owner or through a session key scoped to placing.
+/// An agent with no trade of the action's id on the market gets one
+/// created; an order already resting there is replaced and goes to the back
+/// of the queue. Session spend is charged for what each action adds to the
+/// agent's order.
+pub fn submit_actions_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SubmitActionsBatch<'info>>,
+    actions: Vec<OrderAction>,
+) -> Result<()> {
+    if ctx.remaining_accounts.len() != actions.len() * ACTION_ACCOUNTS {
+        return err!(ErrorCode::InvalidActionAccounts);
+    }
+    let coordinator = ctx.accounts.coordinator.key();
+    let slot = Clock::get()?.slot;
+    let market = &mut ctx.accounts.market;
+    let epoch = market.current_epoch(market.now()?);
+
+    for (action, group) in actions.iter().zip(ctx.remaining_accounts.chunks(ACTION_ACCOUNTS)) {
+        let [agent_info, trade_info, balance_info] = group else {
+            return err!(ErrorCode::InvalidActionAccounts);
+        };
+        let mut agent = Account::<Agent>::try_from(agent_info)?;
+        if !agent.is_authorized(&coordinator, SESSION_SCOPE_PLACE, slot) {
+            return err!(ErrorCode::InvalidAgentOwner);
+        }
+        let mut balance_a = Account::<Balance>::try_from(balance_info)?;
+        if balance_a.agent != agent.key() {
+            return err!(ErrorCode::InvalidAgent);
+        }
+        if balance_a.vault != ctx.accounts.vault_a.key() {
+            return err!(ErrorCode::InvalidVault);
+        }
+        let params = market.decode_action(action.action)?;
+
+        let (mut trade, old_committed) = if trade_info.owner == &crate::ID {
+            let trade = Account::<Trade>::try_from(trade_info)?;
+            let order_id = action.order_id;
+            if trade.agent != agent.key() || trade.market != market.key() || trade.order_id != order_id {
+                return err!(ErrorCode::InvalidAgent);
+            }
+            // A resting order is replaced, so it stops counting as one
+            let old_committed = if trade.size() > 0 {
+                market.open_orders = market.open_orders.saturating_sub(1);
+                if trade.trade_type == params.trade_type { trade.amount_in } else { 0 }
+            } else {
+                0
+            };
+            (trade, old_committed)
+        } else {
+            let (coordinator, system_program) = (&ctx.accounts.coordinator, &ctx.accounts.system_program);
+            agent.open_trade()?;
+            (create_trade(coordinator, system_program, market, &agent, action.order_id, trade_info)?, 0)
+        };
+
+        open_order(market, Some(&ctx.accounts.vault_a), Some(&mut balance_a))?;
+        let sequence = market.next_sequence()?;
+        let bump = trade.bump;
+        trade.open(agent.key(), market.key(), action.order_id, sequence, &params, bump);
+        market.check_order_size(trade.size())?;
+        let committed = trade.amount_in.saturating_sub(old_committed);
+        agent.spend(&coordinator, trade.trade_type, committed, epoch)?;
+        OrderUpdated::emit(market, &mut trade)?;
+
+        trade.exit(&crate::ID)?;
+        agent.exit(&crate::ID)?;
+        balance_a.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+/// Creates the agent's trade `order_id` on the market at its PDA, paid for
+/// by the coordinator. Lamports already sent to the address count toward its
+/// rent, as with Anchor's `init`, so a donation can't block the trade.
+fn create_trade<'info>(
+    coordinator: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    market: &Account<'info, Market>,
+    agent: &Account<'info, Agent>,
+    order_id: u64,
+    trade_info: &'info AccountInfo<'info>,
+) -> Result<Account<'info, Trade>> {
+    let (agent, market, order_id) = (agent.key(), market.key(), order_id.to_le_bytes());
+    let seeds: &[&[u8]] = &[b"trade", agent.as_ref(), market.as_ref(), &order_id];
+    let (address, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    if trade_info.key() != address {
+        return err!(ErrorCode::InvalidActionAccounts);
+    }
+    let space = 8 + Trade::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer_seeds: &[&[u8]] = &[b"trade", agent.as_ref(), market.as_ref(), &order_id, &[bump]];
+    let system_program = system_program.to_account_info();
+    if trade_info.lamports() == 0 {
+        create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                CreateAccount {
+                    from: coordinator.to_account_info(),
+                    to: trade_info.clone(),
+                },
+                &[signer_seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        let shortfall = rent.saturating_sub(trade_info.lamports());
+        if shortfall > 0 {
+            let from = Transfer {
+                from: coordinator.to_account_info(),
+                to: trade_info.clone(),
+            };
+            transfer(CpiContext::new(system_program.clone(), from), shortfall)?;
+        }
+        let allocate_trade = Allocate {
+            account_to_allocate: trade_info.clone(),
+        };
+        allocate(
+            CpiContext::new_with_signer(system_program.clone(), allocate_trade, &[signer_seeds]),
+            space as u64,
+        )?;
+        let assign_trade = Assign {
+            account_to_assign: trade_info.clone(),
+        };
+        assign(CpiContext::new_with_signer(system_program, assign_trade, &[signer_seeds]), &crate::ID)?;
+    }
+    let mut trade = Account::<Trade>::try_from_unchecked(trade_info)?;
+    trade.bump = bump;
+    Ok(trade)
+}
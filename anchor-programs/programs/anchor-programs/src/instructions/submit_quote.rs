@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Market, Rfq, RfqQuote, MAX_RFQ_QUOTES};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SubmitQuote<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner,
+        constraint = agent.key() != rfq.taker @ ErrorCode::Unauthorized
+    )]
+    pub agent: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(mut, has_one = market @ ErrorCode::InvalidMarket)]
+    pub rfq: Account<'info, Rfq>,
+    pub user: Signer<'info>,
+}
+
+pub fn submit_quote(ctx: Context<SubmitQuote>, quote_amount: u64) -> Result<()> {
+    if quote_amount == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+    let rfq = &mut ctx.accounts.rfq;
+    if ctx.accounts.market.now()? > rfq.expiry_slot {
+        return err!(ErrorCode::RfqExpired);
+    }
+
+    // A maker that quotes again replaces its earlier quote
+    let maker = ctx.accounts.agent.key();
+    let count = rfq.quote_count as usize;
+    let index = match rfq.quotes[..count].iter().position(|quote| quote.maker == maker) {
+        Some(index) => index,
+        None if count < MAX_RFQ_QUOTES => {
+            rfq.quote_count += 1;
+            count
+        }
+        None => return err!(ErrorCode::RfqFull),
+    };
+    rfq.quotes[index] = RfqQuote { maker, quote_amount };
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, CopyFeed, Market, Subscription, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SubscribeToAgent<'info> {
+    #[account(
+        constraint = follower.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub follower: Account<'info, Agent>,
+    #[account(
+        constraint = leader.key() != follower.key() @ ErrorCode::InvalidSubscription
+    )]
+    pub leader: Account<'info, Agent>,
+    pub market: Account<'info, Market>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + CopyFeed::INIT_SPACE,
+        seeds = [b"copy_feed", market.key().as_ref(), leader.key().as_ref()],
+        bump
+    )]
+    pub copy_feed: Account<'info, CopyFeed>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", market.key().as_ref(), follower.key().as_ref(), leader.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPerformanceFee<'info> {
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub user: Signer<'info>,
+}
+
+/// Starts copying `fraction_bps` of the leader's future fills on `market`,
+/// mirrored by anyone through `mirror_fill`, at the performance fee the
+/// leader charges now.
+pub fn subscribe_to_agent(ctx: Context<SubscribeToAgent>, fraction_bps: u16) -> Result<()> {
+    if fraction_bps == 0 || fraction_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidSubscription);
+    }
+    let copy_feed = &mut ctx.accounts.copy_feed;
+    copy_feed.market = ctx.accounts.market.key();
+    copy_feed.leader = ctx.accounts.leader.key();
+    copy_feed.bump = ctx.bumps.copy_feed;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.follower = ctx.accounts.follower.key();
+    subscription.leader = ctx.accounts.leader.key();
+    subscription.market = ctx.accounts.market.key();
+    subscription.fraction_bps = fraction_bps;
+    subscription.performance_fee_bps = ctx.accounts.leader.performance_fee_bps;
+    subscription.next_fill = copy_feed.fill_count;
+    subscription.bump = ctx.bumps.subscription;
+    Ok(())
+}
+
+/// Sets the performance fee the agent charges followers who subscribe from
+/// now on; existing subscriptions keep the fee they subscribed at.
+pub fn set_performance_fee(ctx: Context<SetPerformanceFee>, performance_fee_bps: u16) -> Result<()> {
+    if performance_fee_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidSubscription);
+    }
+    ctx.accounts.agent.performance_fee_bps = performance_fee_bps;
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{Market, Vault, BPS_DENOMINATOR};
+use crate::errors::ErrorCode;
+use crate::instructions::reconcile;
+use crate::math::mul_div_floor;
+
+#[derive(Accounts)]
+pub struct SweepDonations<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), vault.token.as_ref()],
+        bump = vault.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(
+        associated_token::mint = vault.token,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
+
+/// Reconciles the vault, then hands its donations to the market: `insurance_bps`
+/// of them to the insurance fund, which only token A can back, and the rest
+/// to accrued fees. Swept tokens stay in the vault but belong to the market
+/// from then on, so nothing sits there unowned.
+pub fn sweep_donations(ctx: Context<SweepDonations>, insurance_bps: u16) -> Result<()> {
+    if insurance_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidFeeSchedule);
+    }
+    let vault = &mut ctx.accounts.vault;
+    reconcile(vault, &ctx.accounts.vault_token_account)?;
+
+    let market = &mut ctx.accounts.market;
+    let donations = vault.donations;
+    let insurance = mul_div_floor(donations, insurance_bps as u64, BPS_DENOMINATOR)?;
+    if insurance > 0 && vault.token != market.token_a {
+        return err!(ErrorCode::InvalidVault);
+    }
+    vault.donations = 0;
+    market.insurance_fund = market
+        .insurance_fund
+        .checked_add(insurance)
+        .ok_or(ErrorCode::MathOverflow)?;
+    market.accrue_fee(vault.token, donations - insurance)
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::Agent;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct TransferAgent<'info> {
+    #[account(
+        mut,
+        constraint = agent.nft_mint != Pubkey::default() @ ErrorCode::AgentNftNotHeld
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        token::mint = agent.nft_mint,
+        token::authority = holder,
+        constraint = holder_nft_account.amount == 1 @ ErrorCode::AgentNftNotHeld
+    )]
+    pub holder_nft_account: Account<'info, TokenAccount>,
+    pub holder: Signer<'info>,
+}
+
+/// Makes the holder of the agent's identity NFT its owner.
+pub fn transfer_agent(ctx: Context<TransferAgent>) -> Result<()> {
+    let holder = ctx.accounts.holder.key();
+    let agent = &mut ctx.accounts.agent;
+    if agent.owner != holder {
+        agent.set_owner(holder);
+    }
+    Ok(())
+}
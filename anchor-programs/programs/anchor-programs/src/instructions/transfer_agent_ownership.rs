@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::Agent;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct TransferAgentOwnership<'info> {
+    #[account(
+        mut,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner,
+        constraint = agent.nft_mint == Pubkey::default() @ ErrorCode::AgentNftExists
+    )]
+    pub agent: Account<'info, Agent>,
+    pub user: Signer<'info>,
+}
+
+/// Offers the agent to `new_owner`, who takes it over with
+/// `accept_agent_ownership`. Until then the current owner keeps full control.
+/// An agent with an identity NFT goes to whoever holds the NFT instead, by
+/// `transfer_agent`.
+pub fn transfer_agent_ownership(
+    ctx: Context<TransferAgentOwnership>,
+    new_owner: Pubkey,
+) -> Result<()> {
+    ctx.accounts.agent.pending_owner = new_owner;
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+use crate::{Agent, Balance, Market, Vault, errors::ErrorCode, invariants::strict_check};
+
+#[derive(Accounts)]
+pub struct TransferCollateral<'info> {
+    #[account(constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner)]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(constraint = !market_from.in_flight @ ErrorCode::MarketInFlight)]
+    pub market_from: Account<'info, Market>,
+    #[account(constraint = !market_to.in_flight @ ErrorCode::MarketInFlight)]
+    pub market_to: Account<'info, Market>,
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market_from.token_a.as_ref(), market_from.token_b.as_ref(), token_mint.key().as_ref()],
+        bump = vault_from.bump,
+        constraint = vault_from.market == market_from.key() @ ErrorCode::InvalidVault
+    )]
+    pub vault_from: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_from,
+    )]
+    pub vault_token_account_from: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_from.key().as_ref(), agent.key().as_ref()],
+        bump = balance_from.bump,
+    )]
+    pub balance_from: Account<'info, Balance>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market_to.token_a.as_ref(), market_to.token_b.as_ref(), token_mint.key().as_ref()],
+        bump = vault_to.bump,
+        constraint = vault_to.market == market_to.key() @ ErrorCode::InvalidVault
+    )]
+    pub vault_to: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_to,
+    )]
+    pub vault_token_account_to: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Balance::INIT_SPACE,
+        seeds = [b"balance", vault_to.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub balance_to: Account<'info, Balance>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves `amount` of the agent's tracked balance of a mint from one market's
+/// vault to another's, without the tokens passing through the owner's wallet.
+pub fn transfer_collateral(ctx: Context<TransferCollateral>, amount: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    let mint = accounts.token_mint.key();
+    if accounts.vault_from.token != mint || accounts.vault_to.token != mint {
+        return err!(ErrorCode::InvalidVault);
+    }
+    if accounts.vault_from.key() == accounts.vault_to.key() {
+        return err!(ErrorCode::InvalidMarket);
+    }
+
+    let balance_from = &mut accounts.balance_from;
+    balance_from.amount = balance_from
+        .amount
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientBalance)?;
+    accounts.vault_from.send(amount)?;
+    let balance_to = &mut accounts.balance_to;
+    balance_to.bind(accounts.agent.key(), accounts.vault_to.key(), ctx.bumps.balance_to);
+    balance_to.credit(amount)?;
+    accounts.vault_to.receive(amount)?;
+
+    let seeds = &[
+        b"vault",
+        accounts.market_from.token_a.as_ref(),
+        accounts.market_from.token_b.as_ref(),
+        mint.as_ref(),
+        &[accounts.vault_from.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: accounts.vault_token_account_from.to_account_info(),
+        to: accounts.vault_token_account_to.to_account_info(),
+        authority: accounts.vault_from.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)?;
+    strict_check!(
+        accounts.balance_to.amount <= accounts.vault_to.reserves,
+        "no balance exceeds its vault's reserves"
+    );
+    Ok(())
+}
@@ -0,0 +1,399 @@
+use anchor_lang::prelude::*;
+use crate::{
+    AuctionIndication, Balance, Config, GlobalStats, Market, Trade, Uncross, Vault, BPS_DENOMINATOR,
+    FEATURE_ORDER_BOOK, PRICE_SCALE, SESSION_CLOSING_AUCTION, SESSION_OPENING_AUCTION,
+    SESSION_REOPENING_AUCTION, UNCROSS_FILL, UNCROSS_TALLY,
+};
+use crate::errors::ErrorCode;
+use crate::events::{AuctionIndicated, AuctionUncrossed, OrderUpdated};
+use crate::instructions::match_trades::take;
+use crate::instructions::reward_record::load_rewards;
+use crate::invariants::strict_check;
+use crate::math::{mul_div_ceil, mul_div_floor};
+
+/// Accounts passed for each resting order in `uncross_auction`: the order
+/// and its agent's balances in vault A and vault B.
+const ORDER_ACCOUNTS: usize = 3;
+
+#[derive(Accounts)]
+pub struct IndicateAuction<'info> {
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    // remaining_accounts: every order resting on the market
+}
+
+#[derive(Accounts)]
+pub struct UncrossAuction<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.enabled(FEATURE_ORDER_BOOK) @ ErrorCode::FeatureDisabled,
+        constraint = !config.emergency_mode @ ErrorCode::EmergencyMode
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+
+    #[account(
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+    // remaining_accounts: for some of the orders resting on the market, the
+    // order and its agent's balances in vault A and vault B, then
+    // `reward_records` of the current epoch for any of the agents
+}
+
+/// Publishes where the running call auction would uncross with the book as
+/// it stands, as an `AuctionIndicated` event and as return data. Every
+/// resting order has to be passed, so the indication can't be skewed by
+/// leaving some out; it counts each at its displayed size, whatever its
+/// agent can pay.
+pub fn indicate_auction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, IndicateAuction<'info>>,
+) -> Result<AuctionIndication> {
+    let market = &ctx.accounts.market;
+    let now = market.now()?;
+    let phase = match market.session(now).0 {
+        phase @ (SESSION_OPENING_AUCTION | SESSION_CLOSING_AUCTION | SESSION_REOPENING_AUCTION) => phase,
+        _ => match market.pending_auction(now) {
+            Some((phase, _)) => phase,
+            None => return err!(ErrorCode::AuctionNotDue),
+        },
+    };
+
+    let mut orders = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts {
+        orders.push(Account::<Trade>::try_from(info)?);
+    }
+    check_book(market, &orders)?;
+    let mut tally = Uncross::default();
+    for order in &orders {
+        tally.tally(order.trade_type, limit(order), order.displayed())?;
+    }
+    let (price, volume, imbalance) = tally.clearing();
+    emit!(AuctionIndicated {
+        market: market.key(),
+        phase,
+        price,
+        volume,
+        imbalance,
+    });
+    Ok(AuctionIndication {
+        phase,
+        price,
+        volume,
+        imbalance,
+    })
+}
+
+/// Clears a call auction that has ended at a single price: the one trading
+/// the most token B, then leaving the smallest imbalance, then the middle of
+/// any still tied. It takes as many cranks as the book needs, each passing
+/// some of the resting orders, and freezes the book until it's done.
+///
+/// First every resting order is passed once to be tallied, at its displayed
+/// size capped by what its agent can pay for at its limit, which is set
+/// aside; orders at a price beyond MAX_UNCROSS_LEVELS sit the auction out.
+/// Once all are in, the clearing price is fixed and every order is passed
+/// again to fill: crossing orders better than the last level needed fill in
+/// full and those at it share the rest pro rata, bids paying and asks
+/// receiving the clearing price less the base taker fee each. Nobody is a
+/// maker and fills count no volume toward fee tiers, only toward the reward
+/// records passed. Continuous matching can't start until the opening
+/// auction, or the one reopening a halted market, has uncrossed.
+/// `crank_sequence` must be the market's next, as for `match_trades`.
+pub fn uncross_auction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UncrossAuction<'info>>,
+    reward_records: u8,
+    crank_sequence: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    market.take_crank(crank_sequence)?;
+    let now = market.now()?;
+    if market.uncross.end == 0 {
+        let Some((phase, end)) = market.pending_auction(now) else {
+            return err!(ErrorCode::AuctionNotDue);
+        };
+        market.uncross = Uncross {
+            end,
+            phase,
+            stage: UNCROSS_TALLY,
+            orders_left: market.open_orders,
+            taker_fee_bps: market.volatility_taker_bps(market.taker_fee_bps),
+            ..Uncross::default()
+        };
+    }
+    let book_len = ctx.remaining_accounts.len().saturating_sub(reward_records as usize);
+    let (book_accounts, reward_accounts) = ctx.remaining_accounts.split_at(book_len);
+    if reward_accounts.len() != reward_records as usize || !book_len.is_multiple_of(ORDER_ACCOUNTS) {
+        return err!(ErrorCode::IncompleteBook);
+    }
+    let mut rewards = load_rewards(&market.key(), reward_accounts)?;
+    let (vault_a, vault_b) = (ctx.accounts.vault_a.key(), ctx.accounts.vault_b.key());
+    let (mut orders, mut balances, owners) = load_page(market, vault_a, vault_b, book_accounts)?;
+
+    if market.uncross.stage == UNCROSS_TALLY {
+        tally(market, &mut orders, &mut balances, &owners)?;
+        if market.uncross.orders_left == 0 {
+            market.uncross.close_tally();
+        }
+    } else {
+        let epoch = market.current_epoch(now);
+        for (order, &(a, b)) in orders.iter_mut().zip(&owners) {
+            let (balance_a, balance_b) = pair(&mut balances, a, b);
+            let filled = fill(market, order, balance_a, balance_b)?;
+            if let (Some(reward), Some((quantity, quote_amount, fee))) =
+                (rewards.iter_mut().find(|reward| reward.agent == order.agent), filled)
+            {
+                let fee = i64::try_from(fee).map_err(|_| ErrorCode::MathOverflow)?;
+                reward.record_fill(epoch, order.trade_type, quantity, quote_amount, fee, now)?;
+            }
+        }
+    }
+    if market.uncross.stage == UNCROSS_FILL && market.uncross.orders_left == 0 {
+        finish(market, &mut ctx.accounts.global_stats)?;
+    }
+
+    for order in &orders {
+        order.exit(&crate::ID)?;
+    }
+    for balance in &balances {
+        balance.exit(&crate::ID)?;
+    }
+    for reward in &rewards {
+        reward.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+/// A page of resting orders, each with the indices of its agent's vault A
+/// and vault B balances among the page's distinct balances, so an agent
+/// with several orders passed has each balance loaded once.
+#[allow(clippy::type_complexity)]
+fn load_page<'info>(
+    market: &Account<'info, Market>,
+    vault_a: Pubkey,
+    vault_b: Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<(Vec<Account<'info, Trade>>, Vec<Account<'info, Balance>>, Vec<(usize, usize)>)> {
+    let mut orders: Vec<Account<Trade>> = Vec::with_capacity(accounts.len() / ORDER_ACCOUNTS);
+    let mut balances: Vec<Account<Balance>> = Vec::new();
+    let mut owners = Vec::with_capacity(orders.capacity());
+    for group in accounts.chunks(ORDER_ACCOUNTS) {
+        let order = Account::<Trade>::try_from(&group[0])?;
+        if order.market != market.key() {
+            return err!(ErrorCode::InvalidMarket);
+        }
+        if orders.iter().any(|other| other.key() == order.key()) {
+            return err!(ErrorCode::DuplicateOrder);
+        }
+        let mut indices = [0; 2];
+        for (index, (info, vault)) in indices.iter_mut().zip([(&group[1], vault_a), (&group[2], vault_b)]) {
+            *index = match balances.iter().position(|balance| balance.key() == info.key()) {
+                Some(index) => index,
+                None => {
+                    balances.push(Account::<Balance>::try_from(info)?);
+                    balances.len() - 1
+                }
+            };
+            let balance = &balances[*index];
+            if balance.agent != order.agent {
+                return err!(ErrorCode::InvalidAgent);
+            }
+            if balance.vault != vault {
+                return err!(ErrorCode::InvalidVault);
+            }
+        }
+        orders.push(order);
+        owners.push((indices[0], indices[1]));
+    }
+    Ok((orders, balances, owners))
+}
+
+/// The two distinct balances at `a` and `b`.
+fn pair<'a, 'info>(
+    balances: &'a mut [Account<'info, Balance>],
+    a: usize,
+    b: usize,
+) -> (&'a mut Account<'info, Balance>, &'a mut Account<'info, Balance>) {
+    if a < b {
+        let (low, high) = balances.split_at_mut(b);
+        (&mut low[a], &mut high[0])
+    } else {
+        let (low, high) = balances.split_at_mut(a);
+        (&mut high[0], &mut low[b])
+    }
+}
+
+/// Tallies a page of orders, setting aside what each fill could cost its
+/// agent. Bids are capped to what their vault A balance pays for at their
+/// limit with the taker fee, asks to their vault B balance, so an agent
+/// that moved its tokens out can't hold the uncross up.
+fn tally(
+    market: &mut Account<Market>,
+    orders: &mut [Account<Trade>],
+    balances: &mut [Account<Balance>],
+    owners: &[(usize, usize)],
+) -> Result<()> {
+    let uncross = &mut market.uncross;
+    for (order, &(a, b)) in orders.iter_mut().zip(owners) {
+        if order.size() == 0 {
+            continue;
+        }
+        if order.uncross_end == uncross.end {
+            return err!(ErrorCode::DuplicateOrder);
+        }
+        uncross.orders_left = uncross.orders_left.checked_sub(1).ok_or(ErrorCode::IncompleteBook)?;
+        uncross.orders += 1;
+        let price = limit(order);
+        let (size, reserved) = if order.trade_type == 0 {
+            let balance_a = &mut balances[a];
+            let spendable = mul_div_floor(
+                balance_a.amount,
+                BPS_DENOMINATOR,
+                BPS_DENOMINATOR + uncross.taker_fee_bps as u64,
+            )?;
+            let size = order.displayed().min(mul_div_floor(spendable, PRICE_SCALE, price.max(1))?);
+            let cost = mul_div_ceil(size, price, PRICE_SCALE)?;
+            (size, cost.checked_add(uncross.fee(cost)?).ok_or(ErrorCode::MathOverflow)?)
+        } else {
+            let size = order.displayed().min(balances[b].amount);
+            (size, size)
+        };
+        let (size, reserved) = match size > 0 && uncross.tally(order.trade_type, price, size)? {
+            true => (size, reserved),
+            false => (0, 0),
+        };
+        balances[if order.trade_type == 0 { a } else { b }].reserve(reserved)?;
+        order.uncross_end = uncross.end;
+        order.uncross_size = size;
+        order.uncross_reserved = reserved;
+    }
+    Ok(())
+}
+
+/// Fills a tallied order at the clearing price and settles what its agent
+/// set aside, returning the token B filled, the token A it traded for and
+/// the fee if any filled.
+fn fill(
+    market: &mut Account<Market>,
+    order: &mut Account<Trade>,
+    balance_a: &mut Balance,
+    balance_b: &mut Balance,
+) -> Result<Option<(u64, u64, u64)>> {
+    let uncross = &mut market.uncross;
+    if order.uncross_end != uncross.end {
+        return err!(ErrorCode::DuplicateOrder);
+    }
+    uncross.orders_left -= 1;
+    let (size, reserved) = (order.uncross_size, order.uncross_reserved);
+    (order.uncross_end, order.uncross_size, order.uncross_reserved) = (0, 0, 0);
+    let quantity = uncross.fill(order.trade_type, limit(order), size)?;
+    let price = uncross.price;
+
+    // What the book takes in rounds up, what it pays out down
+    let (quote_amount, fee) = if order.trade_type == 0 {
+        let quote_amount = mul_div_ceil(quantity, price, PRICE_SCALE)?;
+        let fee = uncross.fee(quote_amount)?;
+        balance_a.release(reserved, quote_amount.checked_add(fee).ok_or(ErrorCode::MathOverflow)?)?;
+        balance_b.credit(quantity)?;
+        uncross.bought = uncross.bought.checked_add(quantity).ok_or(ErrorCode::MathOverflow)?;
+        uncross.paid = uncross.paid.checked_add(quote_amount).ok_or(ErrorCode::MathOverflow)?;
+        (quote_amount, fee)
+    } else {
+        let quote_amount = mul_div_floor(quantity, price, PRICE_SCALE)?;
+        let fee = uncross.fee(quote_amount)?;
+        balance_b.release(reserved, quantity)?;
+        balance_a.credit(quote_amount.checked_sub(fee).ok_or(ErrorCode::MathOverflow)?)?;
+        uncross.sold = uncross.sold.checked_add(quantity).ok_or(ErrorCode::MathOverflow)?;
+        uncross.received = uncross.received.checked_add(quote_amount).ok_or(ErrorCode::MathOverflow)?;
+        (quote_amount, fee)
+    };
+    if quantity == 0 {
+        return Ok(None);
+    }
+    uncross.fees = uncross.fees.checked_add(fee).ok_or(ErrorCode::MathOverflow)?;
+    take(order, market, quantity)?;
+    OrderUpdated::emit_uncrossed(market, order)?;
+    Ok(Some((quantity, quote_amount, fee)))
+}
+
+/// Books the uncross's fees and price once every order has had its fill
+/// turn, and unfreezes the book.
+fn finish(market: &mut Account<Market>, global_stats: &mut GlobalStats) -> Result<()> {
+    let uncross = market.uncross;
+    strict_check!(
+        uncross.bought == uncross.volume && uncross.sold == uncross.volume,
+        "an uncross fills its volume on both sides"
+    );
+    // Rounding between what bids paid and asks received stays with the fees
+    let fees = uncross
+        .fees
+        .checked_add(uncross.paid.checked_sub(uncross.received).ok_or(ErrorCode::MathOverflow)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+    market.fees_accrued = market.fees_accrued.checked_add(fees).ok_or(ErrorCode::MathOverflow)?;
+    market.last_uncross = uncross.end;
+    market.uncross = Uncross::default();
+    if uncross.volume > 0 {
+        market.record_fill_price(uncross.received, uncross.volume, Clock::get()?.slot)?;
+        global_stats.record_fill(uncross.received, fees, 0)?;
+    }
+    emit!(AuctionUncrossed {
+        market: market.key(),
+        phase: uncross.phase,
+        price: uncross.price,
+        volume: uncross.volume,
+        fees,
+    });
+    Ok(())
+}
+
+/// Rejects a set of orders that isn't exactly the market's resting book.
+pub(crate) fn check_book(market: &Account<Market>, orders: &[Account<Trade>]) -> Result<()> {
+    let mut keys = Vec::with_capacity(orders.len());
+    for order in orders {
+        if order.market != market.key() {
+            return err!(ErrorCode::InvalidMarket);
+        }
+        if keys.contains(&order.key()) {
+            return err!(ErrorCode::DuplicateOrder);
+        }
+        keys.push(order.key());
+    }
+    let resting = orders.iter().filter(|order| order.size() > 0).count() as u64;
+    if resting != market.open_orders {
+        return err!(ErrorCode::IncompleteBook);
+    }
+    Ok(())
+}
+
+/// An ask's limit rounded up, the lowest price it sells at: `Trade::price`
+/// rounds down, which would sell an ask below its limit.
+pub(crate) fn ask_price(order: &Trade) -> u64 {
+    mul_div_ceil(order.quote(), PRICE_SCALE, order.size().max(1)).unwrap_or(u64::MAX)
+}
+
+/// The price an order takes part in an auction at: its limit, rounded in
+/// its own favour.
+fn limit(order: &Trade) -> u64 {
+    if order.trade_type == 0 {
+        order.price()
+    } else {
+        ask_price(order)
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Subscription};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct UnsubscribeFromAgent<'info> {
+    #[account(
+        constraint = follower.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub follower: Account<'info, Agent>,
+    #[account(
+        mut,
+        has_one = follower @ ErrorCode::Unauthorized,
+        close = user
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Stops copying. Copied token B stays in the follower's balance.
+pub fn unsubscribe_from_agent(_ctx: Context<UnsubscribeFromAgent>) -> Result<()> {
+    Ok(())
+}
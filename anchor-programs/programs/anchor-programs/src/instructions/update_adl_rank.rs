@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::oracle::read_price;
+use crate::{AdlQueue, Market, Position};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct UpdateAdlRank<'info> {
+    #[account(
+        mut,
+        constraint = !market.in_flight @ ErrorCode::MarketInFlight
+    )]
+    pub market: Account<'info, Market>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + AdlQueue::INIT_SPACE,
+        seeds = [b"adl", market.key().as_ref()],
+        bump
+    )]
+    pub adl_queue: Account<'info, AdlQueue>,
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), position.agent.as_ref()],
+        bump = position.bump,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub position: Account<'info, Position>,
+    /// CHECK: market's primary oracle, decoded by the oracle module
+    pub oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: market's fallback oracle, decoded by the oracle module
+    pub fallback_oracle: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Re-scores one position at the oracle price and moves it within the
+/// market's auto-deleveraging queue. Permissionless, so keepers can hold
+/// the ranking current.
+pub fn update_adl_rank(ctx: Context<UpdateAdlRank>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let price = read_price(
+        market,
+        ctx.accounts.oracle.as_deref(),
+        ctx.accounts.fallback_oracle.as_deref(),
+        Clock::get()?.slot,
+    )?
+    .price;
+
+    let position = &mut ctx.accounts.position;
+    position.settle(market)?;
+    market.reprice_funding(price)?;
+    let score = position.adl_score(market, price)?;
+
+    let adl_queue = &mut ctx.accounts.adl_queue;
+    adl_queue.market = market.key();
+    adl_queue.bump = ctx.bumps.adl_queue;
+    adl_queue.rank(position.key(), position.base, score);
+    Ok(())
+}
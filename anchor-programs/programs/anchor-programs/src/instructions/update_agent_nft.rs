@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::adapters::{metadata_address, update_metadata_instruction, TOKEN_METADATA_PROGRAM_ID};
+use crate::Agent;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct UpdateAgentNft<'info> {
+    #[account(
+        seeds = [b"agent", agent.registrant.as_ref()],
+        bump = agent.bump,
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner,
+        constraint = agent.nft_mint != Pubkey::default() @ ErrorCode::AgentNftNotHeld
+    )]
+    pub agent: Account<'info, Agent>,
+    pub user: Signer<'info>,
+    /// CHECK: the token metadata program's PDA for the agent's NFT
+    #[account(
+        mut,
+        address = metadata_address(&agent.nft_mint)
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: pinned to the Metaplex token metadata program
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+/// Points the agent's identity NFT at fresh metadata, e.g. a uri rendering
+/// its latest PnL and rank.
+pub fn update_agent_nft(
+    ctx: Context<UpdateAgentNft>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let agent = &ctx.accounts.agent;
+    let ix = update_metadata_instruction(
+        &ctx.accounts.metadata.key(),
+        &agent.key(),
+        &name,
+        &symbol,
+        &uri,
+    );
+    let seeds = &[b"agent", agent.registrant.as_ref(), &[agent.bump]];
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.metadata.to_account_info(),
+            agent.to_account_info(),
+            ctx.accounts.token_metadata_program.to_account_info(),
+        ],
+        &[&seeds[..]],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{Agent, Bond, EmissionsSchedule, Market};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct WithdrawBond<'info> {
+    #[account(address = schedule.market @ ErrorCode::InvalidMarket)]
+    pub market: Account<'info, Market>,
+    #[account(mut)]
+    pub schedule: Account<'info, EmissionsSchedule>,
+    #[account(
+        mut,
+        seeds = [b"bond", schedule.key().as_ref(), agent.key().as_ref()],
+        bump = bond.bump
+    )]
+    pub bond: Account<'info, Bond>,
+    #[account(constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner)]
+    pub agent: Account<'info, Agent>,
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint = schedule.reward_mint,
+        associated_token::authority = bond
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = schedule.reward_mint,
+        token::authority = user
+    )]
+    pub user_reward_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns an unlocked bond to the agent's owner.
+pub fn withdraw_bond(ctx: Context<WithdrawBond>) -> Result<()> {
+    let bond = &mut ctx.accounts.bond;
+    if ctx.accounts.market.now()? < bond.unlock {
+        return err!(ErrorCode::BondLocked);
+    }
+    bond.settle_fees(&ctx.accounts.schedule)?;
+    let amount = std::mem::take(&mut bond.amount);
+    let schedule = &mut ctx.accounts.schedule;
+    schedule.bonded -= amount;
+
+    let seeds = &[
+        b"bond",
+        bond.schedule.as_ref(),
+        bond.agent.as_ref(),
+        &[bond.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.bond_vault.to_account_info(),
+        to: ctx.accounts.user_reward_account.to_account_info(),
+        authority: bond.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)
+}
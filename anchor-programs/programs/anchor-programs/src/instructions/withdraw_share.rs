@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Token, TokenAccount, Transfer},
+};
+
+use crate::{Balance, Guild, GuildMember, Market, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct WithdrawShare<'info> {
+    #[account(
+        mut,
+        has_one = market @ ErrorCode::InvalidMarket
+    )]
+    pub guild: Account<'info, Guild>,
+    #[account(
+        mut,
+        seeds = [b"guild_member", guild.key().as_ref(), member.key().as_ref()],
+        bump = guild_member.bump
+    )]
+    pub guild_member: Account<'info, GuildMember>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_a.as_ref()],
+        bump = vault_a.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_a: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_a,
+        associated_token::authority = vault_a
+    )]
+    pub vault_token_account_a: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_a.key().as_ref(), guild.agent.as_ref()],
+        bump = guild_balance_a.bump
+    )]
+    pub guild_balance_a: Account<'info, Balance>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), market.token_b.as_ref()],
+        bump = vault_b.bump,
+        has_one = market @ ErrorCode::InvalidVault
+    )]
+    pub vault_b: Account<'info, Vault>,
+    #[account(
+        mut,
+        associated_token::mint = market.token_b,
+        associated_token::authority = vault_b
+    )]
+    pub vault_token_account_b: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault_b.key().as_ref(), guild.agent.as_ref()],
+        bump = guild_balance_b.bump
+    )]
+    pub guild_balance_b: Account<'info, Balance>,
+
+    pub member: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = market.token_a,
+        token::authority = member
+    )]
+    pub member_token_account_a: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = market.token_b,
+        token::authority = member
+    )]
+    pub member_token_account_b: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Redeems `shares` for the same fraction of each of the guild agent's
+/// token balances, paid out in kind.
+pub fn withdraw_share(ctx: Context<WithdrawShare>, shares: u64) -> Result<()> {
+    if shares == 0 || shares > ctx.accounts.guild_member.shares {
+        return err!(ErrorCode::InvalidShareAmount);
+    }
+    let guild = &mut ctx.accounts.guild;
+    let amount_a = guild.share_of(ctx.accounts.guild_balance_a.amount, shares);
+    let amount_b = guild.share_of(ctx.accounts.guild_balance_b.amount, shares);
+    guild.total_shares -= shares;
+    ctx.accounts.guild_member.shares -= shares;
+    ctx.accounts.guild_balance_a.debit(amount_a)?;
+    ctx.accounts.guild_balance_b.debit(amount_b)?;
+
+    let market = &ctx.accounts.market;
+    for (amount, mint, vault, from, to) in [
+        (
+            amount_a,
+            market.token_a,
+            &mut ctx.accounts.vault_a,
+            &ctx.accounts.vault_token_account_a,
+            &ctx.accounts.member_token_account_a,
+        ),
+        (
+            amount_b,
+            market.token_b,
+            &mut ctx.accounts.vault_b,
+            &ctx.accounts.vault_token_account_b,
+            &ctx.accounts.member_token_account_b,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let seeds = &[
+            b"vault",
+            market.token_a.as_ref(),
+            market.token_b.as_ref(),
+            mint.as_ref(),
+            &[vault.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+        vault.send(amount)?;
+    }
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token::{self, spl_token::native_mint, CloseAccount, Mint, Token, TokenAccount, Transfer},
 };
-use crate::{Agent, Market, Vault, errors::ErrorCode};
+use crate::{Agent, Balance, Market, Vault, errors::ErrorCode, invariants::strict_check};
 
 #[derive(Accounts)]
 pub struct WithdrawTokens<'info> {
@@ -12,6 +12,7 @@ pub struct WithdrawTokens<'info> {
         constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
     )]
     pub agent: Account<'info, Agent>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
     pub market: Account<'info, Market>,
     #[account(mut)]
     pub user: Signer<'info>,
@@ -23,6 +24,7 @@ pub struct WithdrawTokens<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
     #[account(
+        mut,
         seeds = [b"vault", market.token_a.as_ref(), market.token_b.as_ref(), token_mint.key().as_ref()],
         bump = vault.bump,
         has_one = market @ ErrorCode::InvalidVault
@@ -34,39 +36,77 @@ pub struct WithdrawTokens<'info> {
         associated_token::authority = vault,
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"balance", vault.key().as_ref(), agent.key().as_ref()],
+        bump = balance.bump,
+    )]
+    pub balance: Account<'info, Balance>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn withdraw_tokens(ctx: Context<WithdrawTokens>, amount: u64) -> Result<()> {
-    let token_mint_key = ctx.accounts.token_mint.key();
-    if token_mint_key != ctx.accounts.market.token_a && token_mint_key != ctx.accounts.market.token_b {
+    withdraw(ctx.accounts, amount)
+}
+
+/// Withdraws the agent's whole balance of the mint. Withdrawing wrapped SOL
+/// also closes the user's token account, unwrapping it into their wallet.
+pub fn withdraw_all_tokens(ctx: Context<WithdrawTokens>) -> Result<()> {
+    let accounts = ctx.accounts;
+    withdraw(accounts, accounts.balance.amount)?;
+    if accounts.token_mint.key() != native_mint::ID {
+        return Ok(());
+    }
+    let cpi_accounts = CloseAccount {
+        account: accounts.user_token_account.to_account_info(),
+        destination: accounts.user.to_account_info(),
+        authority: accounts.user.to_account_info(),
+    };
+    token::close_account(CpiContext::new(accounts.token_program.to_account_info(), cpi_accounts))
+}
+
+fn withdraw(accounts: &mut WithdrawTokens, amount: u64) -> Result<()> {
+    let token_mint_key = accounts.token_mint.key();
+    if token_mint_key != accounts.market.token_a && token_mint_key != accounts.market.token_b {
         return err!(ErrorCode::InvalidTokenMint);
     }
-    if ctx.accounts.vault.token != token_mint_key {
+    if accounts.vault.token != token_mint_key {
         return err!(ErrorCode::InvalidVault);
     }
+    strict_check!(
+        accounts.vault.reserves <= accounts.vault_token_account.amount,
+        "a vault's reserves are in its token account"
+    );
+
+    let balance = &mut accounts.balance;
+    balance.amount = balance.amount.checked_sub(amount).ok_or(ErrorCode::InsufficientBalance)?;
 
     let seeds = &[
         b"vault",
-        ctx.accounts.market.token_a.as_ref(),
-        ctx.accounts.market.token_b.as_ref(),
+        accounts.market.token_a.as_ref(),
+        accounts.market.token_b.as_ref(),
         token_mint_key.as_ref(),
-        &[ctx.accounts.vault.bump],
+        &[accounts.vault.bump],
     ];
     let signer_seeds = &[&seeds[..]];
 
     let cpi_accounts = Transfer {
-        from: ctx.accounts.vault_token_account.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.vault.to_account_info(),
+        from: accounts.vault_token_account.to_account_info(),
+        to: accounts.user_token_account.to_account_info(),
+        authority: accounts.vault.to_account_info(),
     };
     let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        accounts.token_program.to_account_info(),
         cpi_accounts,
         signer_seeds,
     );
     token::transfer(cpi_ctx, amount)?;
+    accounts.vault.send(amount)?;
+    strict_check!(
+        accounts.balance.amount <= accounts.vault.reserves,
+        "no balance exceeds its vault's reserves"
+    );
     Ok(())
 }
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::{Agent, Balance, Market, OptionContract, Vault, OPTION_PUT};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(option_id: u64)]
+pub struct WriteOption<'info> {
+    #[account(
+        constraint = agent.owner == user.key() @ ErrorCode::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(constraint = !market.in_flight @ ErrorCode::MarketInFlight)]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + OptionContract::INIT_SPACE,
+        seeds = [b"option", market.key().as_ref(), agent.key().as_ref(), option_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub option: Account<'info, OptionContract>,
+    #[account(has_one = market @ ErrorCode::InvalidVault)]
+    pub collateral_vault: Account<'info, Vault>,
+    #[account(
+        mut,
+        seeds = [b"balance", collateral_vault.key().as_ref(), agent.key().as_ref()],
+        bump = collateral_balance.bump
+    )]
+    pub collateral_balance: Account<'info, Balance>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn write_option(
+    ctx: Context<WriteOption>,
+    option_id: u64,
+    kind: u8,
+    size: u64,
+    strike_amount: u64,
+    premium: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    if kind > OPTION_PUT {
+        return err!(ErrorCode::InvalidOptionKind);
+    }
+    if size == 0 || strike_amount == 0 {
+        return err!(ErrorCode::InvalidTradeAmount);
+    }
+    if expiry_slot <= ctx.accounts.market.now()? {
+        return err!(ErrorCode::OptionExpired);
+    }
+
+    let option = &mut ctx.accounts.option;
+    option.writer = ctx.accounts.agent.key();
+    option.market = ctx.accounts.market.key();
+    option.option_id = option_id;
+    option.kind = kind;
+    option.size = size;
+    option.strike_amount = strike_amount;
+    option.premium = premium;
+    option.expiry_slot = expiry_slot;
+    option.bump = ctx.bumps.option;
+
+    let (collateral_mint, collateral) = option.collateral(&ctx.accounts.market);
+    if ctx.accounts.collateral_vault.token != collateral_mint {
+        return err!(ErrorCode::InvalidVault);
+    }
+    ctx.accounts.collateral_balance.debit(collateral)
+}
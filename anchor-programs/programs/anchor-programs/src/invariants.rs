@@ -0,0 +1,25 @@
+//! Invariant checks compiled in with the `strict-checks` feature. Tests,
+//! the simulation harness and devnet builds turn it on; mainnet builds leave
+//! it off, and each check then compiles to nothing, costing no compute.
+//! A failed check logs which invariant broke and fails the instruction with
+//! `InvariantViolated`, so nothing it touched is kept.
+
+/// Whether this build checks invariants.
+pub const ENABLED: bool = cfg!(feature = "strict-checks");
+
+/// Whether `code`, as a transaction's custom error, is a failed check.
+pub fn is_violation(code: u32) -> bool {
+    code == u32::from(crate::errors::ErrorCode::InvariantViolated)
+}
+
+/// Fails with `InvariantViolated` unless `condition` holds. Like
+/// `debug_assert!`, the condition is still type checked when checks are off.
+macro_rules! strict_check {
+    ($condition:expr, $invariant:literal) => {
+        if $crate::invariants::ENABLED && !($condition) {
+            anchor_lang::prelude::msg!(concat!("Invariant violated: ", $invariant));
+            return Err($crate::errors::ErrorCode::InvariantViolated.into());
+        }
+    };
+}
+pub(crate) use strict_check;
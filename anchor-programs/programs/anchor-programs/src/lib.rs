@@ -4,7 +4,15 @@
 mod instructions;
 use instructions::*;
 mod state;
-mod errors;
+pub mod errors;
+pub mod events;
+pub mod oracle;
+pub mod randomness;
+pub mod fees;
+pub mod adapters;
+pub mod invariants;
+pub mod clock;
+pub mod math;
 pub use state::*;
 use anchor_lang::prelude::*;
 
@@ -27,11 +35,34 @@ pub mod agent_market_sim {
         instructions::register_agent(ctx)
     }
 
+    pub fn create_subaccount(ctx: Context<CreateSubaccount>, index: u16) -> Result<()> {
+        instructions::create_subaccount(ctx, index)
+    }
+
+    pub fn start_session(
+        ctx: Context<StartSession>,
+        session_key: Pubkey,
+        expiry_slot: u64,
+        scope: u8, // 1 = place/modify, 2 = cancel
+    ) -> Result<()> {
+        instructions::start_session(ctx, session_key, expiry_slot, scope)
+    }
+
+    pub fn set_session_limits(
+        ctx: Context<SetSessionLimits>,
+        session_key: Pubkey,
+        spend_limits: [u64; 2], // [token A on bids, token B on asks], 0 = unbounded
+        epoch_caps: [u64; 2],   // the same per epoch
+    ) -> Result<()> {
+        instructions::set_session_limits(ctx, session_key, spend_limits, epoch_caps)
+    }
+
     pub fn deposit_tokens(
         ctx: Context<DepositTokens>,
         amount: u64,
+        nonce: Option<u64>,
     ) -> Result<()> {
-        instructions::deposit_tokens(ctx, amount)
+        instructions::deposit_tokens(ctx, amount, nonce)
     }
 
     pub fn withdraw_tokens(
@@ -41,18 +72,744 @@ pub mod agent_market_sim {
         instructions::withdraw_tokens(ctx, amount)
     }
 
+    pub fn withdraw_all_tokens(ctx: Context<WithdrawTokens>) -> Result<()> {
+        instructions::withdraw_all_tokens(ctx)
+    }
+
+    pub fn transfer_collateral(ctx: Context<TransferCollateral>, amount: u64) -> Result<()> {
+        instructions::transfer_collateral(ctx, amount)
+    }
+
+    pub fn internal_transfer(ctx: Context<InternalTransfer>, amount: u64) -> Result<()> {
+        instructions::internal_transfer(ctx, amount)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn place_trade(
         ctx: Context<PlaceTrade>,
+        order_id: u64, // the agent's id for the order, see Trade::order_id
         trade_type: u8, // 0 = buy, 1 = sell, 2 = swap
         amount: u64,
         price: u64,
+        display_size: u64, // iceberg tranche in token B, 0 = show everything
+        flags: u8, // 1 = post-only, 2 = reduce-only
+        tag: [u8; TRADE_TAG_LEN], // echoed in fill events
+    ) -> Result<()> {
+        instructions::place_trade(ctx, order_id, trade_type, amount, price, display_size, flags, tag)
+    }
+
+    pub fn submit_action(ctx: Context<PlaceTrade>, order_id: u64, action: [u8; ACTION_LEN]) -> Result<()> {
+        instructions::submit_action(ctx, order_id, action)
+    }
+
+    pub fn submit_actions_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitActionsBatch<'info>>,
+        actions: Vec<OrderAction>,
     ) -> Result<()> {
-        instructions::place_trade(ctx, trade_type, amount, price)
+        instructions::submit_actions_batch(ctx, actions)
     }
 
-    pub fn execute_trade(ctx: Context<ExecuteTrade>) -> Result<()> {
+    pub fn place_signed_trade(ctx: Context<PlaceSignedTrade>, order: SignedOrder) -> Result<()> {
+        instructions::place_signed_trade(ctx, order)
+    }
+
+    pub fn set_relayer(ctx: Context<SetRelayer>, relayer: Pubkey, fee_oracle: Pubkey) -> Result<()> {
+        instructions::set_relayer(ctx, relayer, fee_oracle)
+    }
+
+    pub fn reimburse_relayer(ctx: Context<ReimburseRelayer>, lamports: u64) -> Result<()> {
+        instructions::reimburse_relayer(ctx, lamports)
+    }
+
+    pub fn execute_trade(ctx: Context<ExecuteTrade>) -> Result<u64> {
         instructions::execute_trade(ctx)
     }
+
+    pub fn modify_trade(
+        ctx: Context<ModifyTrade>,
+        amount_in: u64,
+        amount_out: u64,
+    ) -> Result<()> {
+        instructions::modify_trade(ctx, amount_in, amount_out)
+    }
+
+    pub fn cancel_trade(ctx: Context<CancelTrade>) -> Result<()> {
+        instructions::cancel_trade(ctx)
+    }
+
+    pub fn match_trades<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MatchTrades<'info>>,
+        crank_sequence: u64,
+    ) -> Result<()> {
+        instructions::match_trades(ctx, crank_sequence)
+    }
+
+    pub fn set_stp_mode(
+        ctx: Context<SetStpMode>,
+        stp_mode: u8, // 0 = cancel newest, 1 = cancel oldest, 2 = decrement both
+    ) -> Result<()> {
+        instructions::set_stp_mode(ctx, stp_mode)
+    }
+
+    pub fn set_market_fees(
+        ctx: Context<SetMarketFees>,
+        maker_fee_bps: i16, // negative = rebate
+        taker_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_market_fees(ctx, maker_fee_bps, taker_fee_bps)
+    }
+
+    pub fn set_fee_tiers(ctx: Context<SetMarketFees>, tiers: Vec<FeeTier>) -> Result<()> {
+        instructions::set_fee_tiers(ctx, tiers)
+    }
+
+    pub fn set_settlement_mode(
+        ctx: Context<SetSettlementMode>,
+        net_settlement: bool,
+    ) -> Result<()> {
+        instructions::set_settlement_mode(ctx, net_settlement)
+    }
+
+    pub fn set_matching_policy(
+        ctx: Context<SetMatchingPolicy>,
+        matching_policy: u8, // 0 = price-time, 1 = pro-rata
+    ) -> Result<()> {
+        instructions::set_matching_policy(ctx, matching_policy)
+    }
+
+    pub fn set_tie_break_randomness(ctx: Context<SetMatchingPolicy>, randomness_account: Pubkey) -> Result<()> {
+        instructions::set_tie_break_randomness(ctx, randomness_account)
+    }
+
+    pub fn set_time_base(ctx: Context<SetTimeBase>, time_base: u8) -> Result<()> {
+        instructions::set_time_base(ctx, time_base)
+    }
+
+    pub fn set_action_encoding(ctx: Context<SetMatchingPolicy>, action_lot: u64, action_tick: u64) -> Result<()> {
+        instructions::set_action_encoding(ctx, action_lot, action_tick)
+    }
+
+    pub fn set_max_order_size(ctx: Context<SetMatchingPolicy>, max_order_size: u64) -> Result<()> {
+        instructions::set_max_order_size(ctx, max_order_size)
+    }
+
+    pub fn set_trading_hours(ctx: Context<SetTradingHours>, open: u64, close: u64) -> Result<()> {
+        instructions::set_trading_hours(ctx, open, close)
+    }
+
+    pub fn set_auction_slots(ctx: Context<SetTradingHours>, auction_slots: u64) -> Result<()> {
+        instructions::set_auction_slots(ctx, auction_slots)
+    }
+
+    pub fn set_circuit_breaker(
+        ctx: Context<SetTradingHours>,
+        threshold_bps: u32,
+        halt_slots: u64,
+        reopen_auction_slots: u64,
+    ) -> Result<()> {
+        instructions::set_circuit_breaker(ctx, threshold_bps, halt_slots, reopen_auction_slots)
+    }
+
+    pub fn set_sandwich_guard(ctx: Context<SetSandwichGuard>, enabled: bool) -> Result<()> {
+        instructions::set_sandwich_guard(ctx, enabled)
+    }
+
+    pub fn set_jupiter_routing(ctx: Context<SetJupiterRouting>, enabled: bool) -> Result<()> {
+        instructions::set_jupiter_routing(ctx, enabled)
+    }
+
+    pub fn set_mirror_pool(
+        ctx: Context<SetMirrorPool>,
+        venue: u8, // 0 = none, 1 = Orca Whirlpool, 2 = Raydium CLMM
+        pool: Pubkey,
+    ) -> Result<()> {
+        instructions::set_mirror_pool(ctx, venue, pool)
+    }
+
+    pub fn set_oracle_config(
+        ctx: Context<SetOracleConfig>,
+        primary_oracle: Pubkey,
+        fallback_oracle: Pubkey,
+        max_staleness_slots: u64,
+        max_confidence_bps: u16,
+    ) -> Result<()> {
+        instructions::set_oracle_config(
+            ctx,
+            primary_oracle,
+            fallback_oracle,
+            max_staleness_slots,
+            max_confidence_bps,
+        )
+    }
+
+    pub fn create_price_cache(ctx: Context<CreatePriceCache>, feed_id: [u8; 32]) -> Result<()> {
+        instructions::create_price_cache(ctx, feed_id)
+    }
+
+    pub fn post_price_update(ctx: Context<PostPriceUpdate>) -> Result<()> {
+        instructions::post_price_update(ctx)
+    }
+
+    pub fn open_settlement(ctx: Context<OpenSettlement>) -> Result<()> {
+        instructions::open_settlement(ctx)
+    }
+
+    pub fn settle_epoch(ctx: Context<SettleEpoch>) -> Result<()> {
+        instructions::settle_epoch(ctx)
+    }
+
+    pub fn create_otc_offer(
+        ctx: Context<CreateOtcOffer>,
+        offer_id: u64,
+        give_amount: u64,
+        want_amount: u64,
+        counterparty: Pubkey, // agent allowed to accept, default = anyone
+    ) -> Result<()> {
+        instructions::create_otc_offer(ctx, offer_id, give_amount, want_amount, counterparty)
+    }
+
+    pub fn accept_otc_offer(ctx: Context<AcceptOtcOffer>) -> Result<()> {
+        instructions::accept_otc_offer(ctx)
+    }
+
+    pub fn cancel_otc_offer(ctx: Context<CancelOtcOffer>) -> Result<()> {
+        instructions::cancel_otc_offer(ctx)
+    }
+
+    pub fn create_rfq(
+        ctx: Context<CreateRfq>,
+        rfq_id: u64,
+        side: u8, // 0 = buy token B, 1 = sell token B
+        size: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::create_rfq(ctx, rfq_id, side, size, expiry_slot)
+    }
+
+    pub fn submit_quote(ctx: Context<SubmitQuote>, quote_amount: u64) -> Result<()> {
+        instructions::submit_quote(ctx, quote_amount)
+    }
+
+    pub fn accept_quote(ctx: Context<AcceptQuote>) -> Result<()> {
+        instructions::accept_quote(ctx)
+    }
+
+    pub fn cancel_rfq(ctx: Context<CancelRfq>) -> Result<()> {
+        instructions::cancel_rfq(ctx)
+    }
+
+    pub fn write_option(
+        ctx: Context<WriteOption>,
+        option_id: u64,
+        kind: u8, // 0 = call, 1 = put
+        size: u64,
+        strike_amount: u64,
+        premium: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::write_option(ctx, option_id, kind, size, strike_amount, premium, expiry_slot)
+    }
+
+    pub fn buy_option(ctx: Context<BuyOption>) -> Result<()> {
+        instructions::buy_option(ctx)
+    }
+
+    pub fn exercise_option(ctx: Context<ExerciseOption>) -> Result<()> {
+        instructions::exercise_option(ctx)
+    }
+
+    pub fn expire_option(ctx: Context<ExpireOption>) -> Result<()> {
+        instructions::expire_option(ctx)
+    }
+
+    pub fn create_binary_market(
+        ctx: Context<CreateBinaryMarket>,
+        market_id: u64,
+        oracle: Pubkey,
+    ) -> Result<()> {
+        instructions::create_binary_market(ctx, market_id, oracle)
+    }
+
+    pub fn mint_outcome_tokens(ctx: Context<MintOutcomeTokens>, amount: u64) -> Result<()> {
+        instructions::mint_outcome_tokens(ctx, amount)
+    }
+
+    pub fn redeem_outcome_tokens(ctx: Context<RedeemOutcomeTokens>, amount: u64) -> Result<()> {
+        instructions::redeem_outcome_tokens(ctx, amount)
+    }
+
+    pub fn resolve_market(
+        ctx: Context<ResolveMarket>,
+        outcome: u8, // 1 = yes, 2 = no
+    ) -> Result<()> {
+        instructions::resolve_market(ctx, outcome)
+    }
+
+    pub fn create_index(
+        ctx: Context<CreateIndex>,
+        index_id: u64,
+        components: Vec<IndexComponent>,
+    ) -> Result<()> {
+        instructions::create_index(ctx, index_id, components)
+    }
+
+    pub fn mint_index<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintIndex<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::mint_index(ctx, amount)
+    }
+
+    pub fn redeem_index<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RedeemIndex<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::redeem_index(ctx, amount)
+    }
+
+    pub fn flash_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FlashSwap<'info>>,
+        callback_data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::flash_swap(ctx, callback_data)
+    }
+
+    pub fn flash_loan(ctx: Context<FlashLoan>, amount: u64) -> Result<()> {
+        instructions::flash_loan(ctx, amount)
+    }
+
+    pub fn repay_flash_loan(ctx: Context<RepayFlashLoan>) -> Result<()> {
+        instructions::repay_flash_loan(ctx)
+    }
+
+    pub fn execute_trade_via_jupiter<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteTradeViaJupiter<'info>>,
+        route_data: Vec<u8>,
+        max_slippage_bps: u16,
+    ) -> Result<u64> {
+        instructions::execute_trade_via_jupiter(ctx, route_data, max_slippage_bps)
+    }
+
+    pub fn execute_mirror_trade<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteMirrorTrade<'info>>,
+        max_slippage_bps: u16,
+    ) -> Result<u64> {
+        instructions::execute_mirror_trade(ctx, max_slippage_bps)
+    }
+
+    pub fn indicate_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, IndicateAuction<'info>>,
+    ) -> Result<AuctionIndication> {
+        instructions::indicate_auction(ctx)
+    }
+
+    pub fn uncross_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UncrossAuction<'info>>,
+        reward_records: u8,
+        crank_sequence: u64,
+    ) -> Result<()> {
+        instructions::uncross_auction(ctx, reward_records, crank_sequence)
+    }
+
+    pub fn execute_hybrid_trade<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteHybridTrade<'info>>,
+        max_slippage_bps: u16,
+        book_orders: u8,
+        reward_records: u8,
+    ) -> Result<u64> {
+        instructions::execute_hybrid_trade(ctx, max_slippage_bps, book_orders, reward_records)
+    }
+
+    pub fn openbook_place_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenbookOrder<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::openbook_place_order(ctx, data)
+    }
+
+    pub fn openbook_cancel_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenbookOrder<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::openbook_cancel_order(ctx, data)
+    }
+
+    pub fn openbook_manage_account<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenbookOrder<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::openbook_manage_account(ctx, data)
+    }
+
+    pub fn set_perp_params(
+        ctx: Context<SetPerpParams>,
+        initial_margin_bps: u16,
+        maintenance_margin_bps: u16,
+        liquidation_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_perp_params(
+            ctx,
+            initial_margin_bps,
+            maintenance_margin_bps,
+            liquidation_fee_bps,
+        )
+    }
+
+    pub fn set_open_interest_limits(
+        ctx: Context<SetOpenInterestLimits>,
+        max_long: u64, // token B, 0 = uncapped
+        max_short: u64,
+        skew_scale: u64,
+    ) -> Result<()> {
+        instructions::set_open_interest_limits(ctx, max_long, max_short, skew_scale)
+    }
+
+    pub fn set_funding_params(
+        ctx: Context<SetFundingParams>,
+        max_funding_bps: u16, // per epoch, 0 = no funding
+        borrow_rate_bps: u16, // yearly
+    ) -> Result<()> {
+        instructions::set_funding_params(ctx, max_funding_bps, borrow_rate_bps)
+    }
+
+    pub fn deposit_collateral(ctx: Context<PositionCollateral>, amount: u64) -> Result<()> {
+        instructions::deposit_collateral(ctx, amount)
+    }
+
+    pub fn withdraw_collateral(ctx: Context<PositionCollateral>, amount: u64) -> Result<()> {
+        instructions::withdraw_collateral(ctx, amount)
+    }
+
+    pub fn modify_position(
+        ctx: Context<ModifyPosition>,
+        base_delta: i64, // token B, positive buys
+        flags: u8,       // 2 = reduce-only
+    ) -> Result<()> {
+        instructions::modify_position(ctx, base_delta, flags)
+    }
+
+    pub fn liquidate_position(ctx: Context<LiquidatePosition>) -> Result<()> {
+        instructions::liquidate_position(ctx)
+    }
+
+    pub fn declare_bankruptcy(ctx: Context<DeclareBankruptcy>) -> Result<()> {
+        instructions::declare_bankruptcy(ctx)
+    }
+
+    pub fn fund_insurance(ctx: Context<FundInsurance>, amount: u64) -> Result<()> {
+        instructions::fund_insurance(ctx, amount)
+    }
+
+    pub fn update_adl_rank(ctx: Context<UpdateAdlRank>) -> Result<()> {
+        instructions::update_adl_rank(ctx)
+    }
+
+    pub fn auto_deleverage(ctx: Context<AutoDeleverage>) -> Result<()> {
+        instructions::auto_deleverage(ctx)
+    }
+
+    pub fn set_volatility_fees(
+        ctx: Context<SetMarketFees>,
+        coefficient: u16, // 0 = flat taker fees
+        min_taker_fee_bps: u16,
+        max_taker_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_volatility_fees(ctx, coefficient, min_taker_fee_bps, max_taker_fee_bps)
+    }
+
+    pub fn set_congestion_tiers(ctx: Context<SetMarketFees>, tiers: Vec<CongestionTier>) -> Result<()> {
+        instructions::set_congestion_tiers(ctx, tiers)
+    }
+
+    pub fn set_episode_capital(ctx: Context<SetEpisodeCapital>, capital_a: u64, capital_b: u64) -> Result<()> {
+        instructions::set_episode_capital(ctx, capital_a, capital_b)
+    }
+
+    pub fn reset_agent_for_episode<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResetAgentForEpisode<'info>>,
+    ) -> Result<()> {
+        instructions::reset_agent_for_episode(ctx)
+    }
+
+    pub fn set_inventory_penalty(ctx: Context<SetInventoryPenalty>, inventory_penalty_bps: u16) -> Result<()> {
+        instructions::set_inventory_penalty(ctx, inventory_penalty_bps)
+    }
+
+    pub fn open_reward_record(ctx: Context<OpenRewardRecord>, epoch: u64) -> Result<()> {
+        instructions::open_reward_record(ctx, epoch)
+    }
+
+    pub fn settle_reward(ctx: Context<SettleReward>) -> Result<()> {
+        instructions::settle_reward(ctx)
+    }
+
+    pub fn create_curriculum(ctx: Context<CreateCurriculum>, stages: Vec<CurriculumStage>) -> Result<()> {
+        instructions::create_curriculum(ctx, stages)
+    }
+
+    pub fn advance_curriculum<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AdvanceCurriculum<'info>>,
+    ) -> Result<()> {
+        instructions::advance_curriculum(ctx)
+    }
+
+    pub fn set_adversary(ctx: Context<SetAdversary>, adversary: Pubkey, max_size: u64) -> Result<()> {
+        instructions::set_adversary(ctx, adversary, max_size)
+    }
+
+    pub fn place_adversary_order(
+        ctx: Context<PlaceAdversaryOrder>,
+        order_id: u64,
+        params: OrderParams,
+    ) -> Result<()> {
+        instructions::place_adversary_order(ctx, order_id, params)
+    }
+
+    pub fn snapshot_market(ctx: Context<SnapshotMarket>, epoch: u64) -> Result<()> {
+        instructions::snapshot_market(ctx, epoch)
+    }
+
+    pub fn open_observation_frame(ctx: Context<OpenObservationFrame>, interval: u64) -> Result<()> {
+        instructions::open_observation_frame(ctx, interval)
+    }
+
+    pub fn update_observation_frame<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateObservationFrame<'info>>,
+    ) -> Result<()> {
+        instructions::update_observation_frame(ctx)
+    }
+
+    pub fn quote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Quote<'info>>,
+        size: u64, // token B
+        side: u8,  // 0 = taker buys, 1 = taker sells
+    ) -> Result<BookQuote> {
+        instructions::quote(ctx, size, side)
+    }
+
+    pub fn open_leaderboard(ctx: Context<OpenLeaderboard>, epoch: u64) -> Result<()> {
+        instructions::open_leaderboard(ctx, epoch)
+    }
+
+    pub fn create_emissions_schedule(
+        ctx: Context<CreateEmissionsSchedule>,
+        rate: u64, // reward tokens per unit of market time
+        start: u64,
+        end: u64,
+        maker_share_bps: u16,
+    ) -> Result<()> {
+        instructions::create_emissions_schedule(ctx, rate, start, end, maker_share_bps)
+    }
+
+    pub fn open_emissions_account(ctx: Context<OpenEmissionsAccount>) -> Result<()> {
+        instructions::open_emissions_account(ctx)
+    }
+
+    pub fn claim_emissions(ctx: Context<ClaimEmissions>) -> Result<()> {
+        instructions::claim_emissions(ctx)
+    }
+
+    pub fn set_emissions_boost(
+        ctx: Context<SetEmissionsBoost>,
+        min_bond: u64,
+        max_boost_bps: u16,
+    ) -> Result<()> {
+        instructions::set_emissions_boost(ctx, min_bond, max_boost_bps)
+    }
+
+    pub fn lock_bond(ctx: Context<LockBond>, amount: u64, unlock: u64) -> Result<()> {
+        instructions::lock_bond(ctx, amount, unlock)
+    }
+
+    pub fn relock_bond(ctx: Context<RelockBond>, unlock: u64) -> Result<()> {
+        instructions::relock_bond(ctx, unlock)
+    }
+
+    pub fn withdraw_bond(ctx: Context<WithdrawBond>) -> Result<()> {
+        instructions::withdraw_bond(ctx)
+    }
+
+    pub fn distribute_fees_to_stakers(ctx: Context<DistributeFeesToStakers>, share_bps: u16) -> Result<()> {
+        instructions::distribute_fees_to_stakers(ctx, share_bps)
+    }
+
+    pub fn claim_staking_fees(ctx: Context<ClaimStakingFees>) -> Result<()> {
+        instructions::claim_staking_fees(ctx)
+    }
+
+    pub fn buyback_and_burn<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuybackAndBurn<'info>>,
+        amount: u64, // token A fees
+        min_out: u64,
+    ) -> Result<()> {
+        instructions::buyback_and_burn(ctx, amount, min_out)
+    }
+
+    pub fn publish_score_root(
+        ctx: Context<PublishScoreRoot>,
+        epoch: u64,
+        root: [u8; 32],
+        total_rewards: u64, // token A
+    ) -> Result<()> {
+        instructions::publish_score_root(ctx, epoch, root, total_rewards)
+    }
+
+    pub fn claim_scored_reward(
+        ctx: Context<ClaimScoredReward>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_scored_reward(ctx, amount, proof)
+    }
+
+    pub fn mint_agent_nft(
+        ctx: Context<MintAgentNft>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::mint_agent_nft(ctx, name, symbol, uri)
+    }
+
+    pub fn update_agent_nft(
+        ctx: Context<UpdateAgentNft>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::update_agent_nft(ctx, name, symbol, uri)
+    }
+
+    pub fn transfer_agent(ctx: Context<TransferAgent>) -> Result<()> {
+        instructions::transfer_agent(ctx)
+    }
+
+    pub fn transfer_agent_ownership(
+        ctx: Context<TransferAgentOwnership>,
+        new_owner: Pubkey, // default cancels a pending transfer
+    ) -> Result<()> {
+        instructions::transfer_agent_ownership(ctx, new_owner)
+    }
+
+    pub fn accept_agent_ownership(ctx: Context<AcceptAgentOwnership>) -> Result<()> {
+        instructions::accept_agent_ownership(ctx)
+    }
+
+    pub fn create_guild(ctx: Context<CreateGuild>, manager: Pubkey) -> Result<()> {
+        instructions::create_guild(ctx, manager)
+    }
+
+    pub fn elect_guild_manager(ctx: Context<ElectGuildManager>, manager: Pubkey) -> Result<()> {
+        instructions::elect_guild_manager(ctx, manager)
+    }
+
+    pub fn deposit_to_guild(ctx: Context<DepositToGuild>, amount: u64) -> Result<()> {
+        instructions::deposit_to_guild(ctx, amount)
+    }
+
+    pub fn withdraw_share(ctx: Context<WithdrawShare>, shares: u64) -> Result<()> {
+        instructions::withdraw_share(ctx, shares)
+    }
+
+    pub fn set_performance_fee(
+        ctx: Context<SetPerformanceFee>,
+        performance_fee_bps: u16, // of followers' realized profit
+    ) -> Result<()> {
+        instructions::set_performance_fee(ctx, performance_fee_bps)
+    }
+
+    pub fn subscribe_to_agent(
+        ctx: Context<SubscribeToAgent>,
+        fraction_bps: u16, // of each leader fill
+    ) -> Result<()> {
+        instructions::subscribe_to_agent(ctx, fraction_bps)
+    }
+
+    pub fn unsubscribe_from_agent(ctx: Context<UnsubscribeFromAgent>) -> Result<()> {
+        instructions::unsubscribe_from_agent(ctx)
+    }
+
+    pub fn fund_mirror_liquidity(ctx: Context<FundMirrorLiquidity>, amount: u64) -> Result<()> {
+        instructions::fund_mirror_liquidity(ctx, amount)
+    }
+
+    pub fn mirror_fill(ctx: Context<MirrorFill>, crank_sequence: u64) -> Result<()> {
+        instructions::mirror_fill(ctx, crank_sequence)
+    }
+
+    pub fn create_strategy_vault(
+        ctx: Context<CreateStrategyVault>,
+        management_fee_bps: u16, // a year, on NAV
+        performance_fee_bps: u16, // of gains above the high-water mark
+    ) -> Result<()> {
+        instructions::create_strategy_vault(ctx, management_fee_bps, performance_fee_bps)
+    }
+
+    pub fn deposit_to_strategy(ctx: Context<DepositToStrategy>, amount: u64) -> Result<()> {
+        instructions::deposit_to_strategy(ctx, amount)
+    }
+
+    pub fn request_redeem(ctx: Context<RequestRedeem>, shares: u64) -> Result<()> {
+        instructions::request_redeem(ctx, shares)
+    }
+
+    pub fn process_redemptions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessRedemptions<'info>>,
+        crank_sequence: u64,
+    ) -> Result<()> {
+        instructions::process_redemptions(ctx, crank_sequence)
+    }
+
+    pub fn reconcile_vault(ctx: Context<ReconcileVault>) -> Result<()> {
+        instructions::reconcile_vault(ctx)
+    }
+
+    pub fn sweep_donations(
+        ctx: Context<SweepDonations>,
+        insurance_bps: u16, // of the donations, the rest accrues as fees
+    ) -> Result<()> {
+        instructions::sweep_donations(ctx, insurance_bps)
+    }
+
+    pub fn collect_excess_lamports(ctx: Context<CollectExcessLamports>) -> Result<()> {
+        instructions::collect_excess_lamports(ctx)
+    }
+
+    pub fn close_filled_trade(ctx: Context<CloseFilledTrade>) -> Result<()> {
+        instructions::close_filled_trade(ctx)
+    }
+
+    pub fn close_balance(ctx: Context<CloseBalance>) -> Result<()> {
+        instructions::close_balance(ctx)
+    }
+
+    pub fn close_agent(ctx: Context<CloseAgent>) -> Result<()> {
+        instructions::close_agent(ctx)
+    }
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        admin: Pubkey,
+        maker_fee_bps: i16, // defaults for new markets
+        taker_fee_bps: u16,
+        features: u64,
+    ) -> Result<()> {
+        instructions::initialize_config(ctx, admin, maker_fee_bps, taker_fee_bps, features)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        admin: Pubkey,
+        maker_fee_bps: i16,
+        taker_fee_bps: u16,
+        features: u64,
+    ) -> Result<()> {
+        instructions::update_config(ctx, admin, maker_fee_bps, taker_fee_bps, features)
+    }
+
+    pub fn set_emergency_mode(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        instructions::set_emergency_mode(ctx, enabled)
+    }
 }
 
 
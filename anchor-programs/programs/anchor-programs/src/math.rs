@@ -0,0 +1,139 @@
+//! `a * b / d` with a u128 intermediate, so no pair of u64 operands can
+//! overflow before the division, and an explicit rounding direction. The
+//! policy everywhere the program prices fees, interest or shares: what the
+//! protocol or a vault takes in rounds up, what it pays out or issues rounds
+//! down. Splitting an operation into many small ones then never leaks dust
+//! out of the protocol, since each piece loses its remainder to it.
+use crate::errors::ErrorCode;
+
+/// `a * b / d` rounded down, for amounts paid out and shares issued.
+pub fn mul_div_floor(a: u64, b: u64, d: u64) -> std::result::Result<u64, ErrorCode> {
+    if d == 0 {
+        return Err(ErrorCode::MathOverflow);
+    }
+    to_u64(a as u128 * b as u128 / d as u128)
+}
+
+/// `a * b / d` rounded up, for fees and amounts charged.
+pub fn mul_div_ceil(a: u64, b: u64, d: u64) -> std::result::Result<u64, ErrorCode> {
+    if d == 0 {
+        return Err(ErrorCode::MathOverflow);
+    }
+    to_u64((a as u128 * b as u128).div_ceil(d as u128))
+}
+
+fn to_u64(value: u128) -> std::result::Result<u64, ErrorCode> {
+    u64::try_from(value).map_err(|_| ErrorCode::MathOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUES: [u64; 16] = [
+        0,
+        1,
+        2,
+        3,
+        7,
+        9_999,
+        10_000,
+        10_001,
+        1_000_000,
+        123_456_789,
+        u32::MAX as u64,
+        u32::MAX as u64 + 1,
+        u64::MAX / 3,
+        u64::MAX / 2,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+
+    #[test]
+    fn division_by_zero_is_reported() {
+        assert!(matches!(mul_div_floor(1, 1, 0), Err(ErrorCode::MathOverflow)));
+        assert!(matches!(mul_div_ceil(0, 0, 0), Err(ErrorCode::MathOverflow)));
+    }
+
+    #[test]
+    fn products_past_u64_are_not_overflow() {
+        assert_eq!(mul_div_floor(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(mul_div_ceil(u64::MAX, 10_000, 10_000).unwrap(), u64::MAX);
+        assert!(matches!(mul_div_floor(u64::MAX, 2, 1), Err(ErrorCode::MathOverflow)));
+        assert!(matches!(mul_div_ceil(u64::MAX, u64::MAX, u64::MAX - 1), Err(ErrorCode::MathOverflow)));
+    }
+
+    #[test]
+    fn rounding_directions() {
+        assert_eq!(mul_div_floor(1, 1, 3).unwrap(), 0);
+        assert_eq!(mul_div_ceil(1, 1, 3).unwrap(), 1);
+        assert_eq!(mul_div_floor(6, 1, 3).unwrap(), 2);
+        assert_eq!(mul_div_ceil(6, 1, 3).unwrap(), 2);
+        // A 5 bps fee on 1 base unit is still charged
+        assert_eq!(mul_div_ceil(1, 5, 10_000).unwrap(), 1);
+    }
+
+    #[test]
+    fn floor_and_ceil_bracket_the_exact_quotient() {
+        for a in VALUES {
+            for b in VALUES {
+                for d in VALUES.into_iter().filter(|&d| d != 0) {
+                    let product = a as u128 * b as u128;
+                    let exact = product / d as u128;
+                    let remainder = product % d as u128;
+                    match (mul_div_floor(a, b, d), mul_div_ceil(a, b, d)) {
+                        (Ok(floor), Ok(ceil)) => {
+                            // floor * d <= a * b <= ceil * d, at most one apart
+                            assert!(floor as u128 * d as u128 <= product, "{a} {b} {d}");
+                            assert!(ceil as u128 * d as u128 >= product, "{a} {b} {d}");
+                            assert_eq!(ceil - floor, (remainder != 0) as u64, "{a} {b} {d}");
+                            assert_eq!(floor as u128, exact);
+                        }
+                        (Ok(floor), Err(_)) => {
+                            assert_eq!(floor, u64::MAX, "{a} {b} {d}");
+                            assert_ne!(remainder, 0);
+                        }
+                        (Err(_), Err(_)) => assert!(exact > u64::MAX as u128, "{a} {b} {d}"),
+                        (Err(_), Ok(_)) => panic!("ceil fit where floor didn't: {a} {b} {d}"),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn results_are_symmetric_and_monotonic() {
+        for a in VALUES {
+            for b in VALUES {
+                for d in VALUES.into_iter().filter(|&d| d != 0) {
+                    assert_eq!(mul_div_floor(a, b, d).ok(), mul_div_floor(b, a, d).ok());
+                    assert_eq!(mul_div_ceil(a, b, d).ok(), mul_div_ceil(b, a, d).ok());
+                    if let (Ok(lower), Ok(higher)) = (mul_div_floor(a, b, d), mul_div_floor(a.saturating_add(1), b, d)) {
+                        assert!(lower <= higher, "{a} {b} {d}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn splitting_never_pays_out_more_or_charges_less() {
+        // Paying out a * b / d in pieces never exceeds paying it at once, and
+        // charging in pieces never takes less
+        let rates = [1, 3, 5, 30, 9_999];
+        let pieces = [1, 2, 3, 7, 10, 97];
+        for amount in [1u64, 2, 99, 1_000, 123_457, 1_000_000] {
+            for rate in rates {
+                for count in pieces {
+                    let piece = amount / count;
+                    let rest = amount - piece * (count - 1);
+                    let split = |f: fn(u64, u64, u64) -> std::result::Result<u64, ErrorCode>| {
+                        f(piece, rate, 10_000).unwrap() * (count - 1) + f(rest, rate, 10_000).unwrap()
+                    };
+                    assert!(split(mul_div_floor) <= mul_div_floor(amount, rate, 10_000).unwrap());
+                    assert!(split(mul_div_ceil) >= mul_div_ceil(amount, rate, 10_000).unwrap());
+                }
+            }
+        }
+    }
+}
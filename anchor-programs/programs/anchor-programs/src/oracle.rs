@@ -0,0 +1,261 @@
+//! Market price reads. A market names up to two feeds, a primary and a
+//! fallback, each either a Pyth v2 price account or a Switchboard v2
+//! aggregator. Feeds are tried in order and the first that is trading, fresh
+//! and tight enough wins; when neither qualifies the market's own last-trade
+//! TWAP is used. A market's `PriceCache` can stand in for either feed to use
+//! Pyth pull-oracle prices. All prices are whole token A per whole token B scaled by
+//! `PRICE_SCALE`. Feeds are trusted by address, which only the market
+//! authority can set, so the account layout is checked but not its owner.
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::{Market, PriceCache, BPS_DENOMINATOR};
+
+pub const ORACLE_SOURCE_PYTH: u8 = 0;
+pub const ORACLE_SOURCE_SWITCHBOARD: u8 = 1;
+pub const ORACLE_SOURCE_TWAP: u8 = 2;
+pub const ORACLE_SOURCE_PYTH_PULL: u8 = 3;
+
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+const PRICE_DECIMALS: i32 = 6; // log10 of PRICE_SCALE
+
+// Pyth v2 price account
+const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_EXPO_OFFSET: usize = 20;
+const PYTH_AGG_PRICE_OFFSET: usize = 208;
+const PYTH_AGG_CONF_OFFSET: usize = 216;
+const PYTH_AGG_STATUS_OFFSET: usize = 224;
+const PYTH_AGG_PUB_SLOT_OFFSET: usize = 232;
+
+// Switchboard v2 AggregatorAccountData, packed, after the Anchor discriminator
+const SWITCHBOARD_DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+const SWITCHBOARD_ROUND_OPEN_SLOT_OFFSET: usize = 350;
+const SWITCHBOARD_RESULT_OFFSET: usize = 366;
+const SWITCHBOARD_STD_DEVIATION_OFFSET: usize = 386;
+
+// Pyth receiver PriceUpdateV2, after the Anchor discriminator and write authority
+const PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const PRICE_UPDATE_VERIFICATION_OFFSET: usize = 40;
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+const PRICE_UPDATE_MESSAGE_OFFSET: usize = 41; // only valid for fully verified updates
+const PRICE_UPDATE_LEN: usize = PRICE_UPDATE_MESSAGE_OFFSET + 92;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: u64,
+    pub conf: u64, // one-sided confidence interval, same scale as `price`
+    pub slot: u64, // slot the price was published at
+    pub source: u8, // ORACLE_SOURCE_*
+}
+
+/// Price carried by a fully verified Pyth receiver `PriceUpdateV2` account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceUpdate {
+    pub feed_id: [u8; 32],
+    pub price: u64,
+    pub conf: u64,
+    pub publish_time: i64,
+    pub posted_slot: u64,
+}
+
+/// Best available price for `market` at `slot`. `primary` and `fallback` are
+/// the accounts passed for the market's configured feeds, if any. When every
+/// source fails, the error from the first configured one is returned.
+pub fn read_price<'info>(
+    market: &Market,
+    primary: Option<&AccountInfo<'info>>,
+    fallback: Option<&AccountInfo<'info>>,
+    slot: u64,
+) -> std::result::Result<OraclePrice, ErrorCode> {
+    let mut first_error = None;
+    for (configured, account) in [
+        (market.primary_oracle, primary),
+        (market.fallback_oracle, fallback),
+    ] {
+        if configured == Pubkey::default() {
+            continue;
+        }
+        let price = match account {
+            Some(account) if account.key() == configured => read_feed(account),
+            _ => Err(ErrorCode::OracleAccountMismatch),
+        };
+        match price.and_then(|price| check_price(market, price, slot)) {
+            Ok(price) => return Ok(price),
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+    twap_price(market)
+        .and_then(|price| check_price(market, price, slot))
+        .map_err(|error| first_error.unwrap_or(error))
+}
+
+/// Price from one specific feed, held to the market's staleness and
+/// confidence limits, for prices other than the market pair's own.
+pub fn read_feed_price(
+    market: &Market,
+    account: &AccountInfo,
+    slot: u64,
+) -> std::result::Result<OraclePrice, ErrorCode> {
+    read_feed(account).and_then(|price| check_price(market, price, slot))
+}
+
+fn read_feed(account: &AccountInfo) -> std::result::Result<OraclePrice, ErrorCode> {
+    let data = account
+        .try_borrow_data()
+        .map_err(|_| ErrorCode::InvalidOracleAccount)?;
+    if data.starts_with(&SWITCHBOARD_DISCRIMINATOR) {
+        read_switchboard(&data)
+    } else if data.starts_with(PriceCache::DISCRIMINATOR) && account.owner == &crate::ID {
+        let cache = PriceCache::try_deserialize(&mut &data[..])
+            .map_err(|_| ErrorCode::InvalidOracleAccount)?;
+        if cache.posted_slot == 0 {
+            return Err(ErrorCode::OraclePriceUnavailable);
+        }
+        Ok(OraclePrice {
+            price: cache.price,
+            conf: cache.conf,
+            slot: cache.posted_slot,
+            source: ORACLE_SOURCE_PYTH_PULL,
+        })
+    } else {
+        read_pyth(&data)
+    }
+}
+
+fn read_pyth(data: &[u8]) -> std::result::Result<OraclePrice, ErrorCode> {
+    if data.len() < PYTH_AGG_PUB_SLOT_OFFSET + 8
+        || read_u32(data, 0) != PYTH_MAGIC
+        || read_u32(data, 4) != PYTH_VERSION
+        || read_u32(data, 8) != PYTH_ACCOUNT_TYPE_PRICE
+    {
+        return Err(ErrorCode::InvalidOracleAccount);
+    }
+    if read_u32(data, PYTH_AGG_STATUS_OFFSET) != PYTH_STATUS_TRADING {
+        return Err(ErrorCode::OraclePriceUnavailable);
+    }
+    let expo = read_u32(data, PYTH_EXPO_OFFSET) as i32;
+    let price = read_u64(data, PYTH_AGG_PRICE_OFFSET) as i64;
+    if price <= 0 {
+        return Err(ErrorCode::OraclePriceUnavailable);
+    }
+    Ok(OraclePrice {
+        price: rescale(price as u128, expo)?,
+        conf: rescale(read_u64(data, PYTH_AGG_CONF_OFFSET) as u128, expo)?,
+        slot: read_u64(data, PYTH_AGG_PUB_SLOT_OFFSET),
+        source: ORACLE_SOURCE_PYTH,
+    })
+}
+
+fn read_switchboard(data: &[u8]) -> std::result::Result<OraclePrice, ErrorCode> {
+    if data.len() < SWITCHBOARD_STD_DEVIATION_OFFSET + 20 {
+        return Err(ErrorCode::InvalidOracleAccount);
+    }
+    let (mantissa, scale) = read_decimal(data, SWITCHBOARD_RESULT_OFFSET);
+    if mantissa <= 0 {
+        return Err(ErrorCode::OraclePriceUnavailable);
+    }
+    let (deviation, deviation_scale) = read_decimal(data, SWITCHBOARD_STD_DEVIATION_OFFSET);
+    Ok(OraclePrice {
+        price: rescale(mantissa as u128, -(scale as i32))?,
+        conf: rescale(deviation.unsigned_abs(), -(deviation_scale as i32))?,
+        slot: read_u64(data, SWITCHBOARD_ROUND_OPEN_SLOT_OFFSET),
+        source: ORACLE_SOURCE_SWITCHBOARD,
+    })
+}
+
+/// Decodes a receiver-program price update. Partially verified updates are
+/// rejected since fewer guardian signatures than quorum back them.
+pub fn read_price_update(data: &[u8]) -> std::result::Result<PriceUpdate, ErrorCode> {
+    if !data.starts_with(&PRICE_UPDATE_DISCRIMINATOR)
+        || data.len() <= PRICE_UPDATE_VERIFICATION_OFFSET
+    {
+        return Err(ErrorCode::InvalidOracleAccount);
+    }
+    if data[PRICE_UPDATE_VERIFICATION_OFFSET] != VERIFICATION_LEVEL_FULL {
+        return Err(ErrorCode::PriceUpdateUnverified);
+    }
+    if data.len() < PRICE_UPDATE_LEN {
+        return Err(ErrorCode::InvalidOracleAccount);
+    }
+    let message = &data[PRICE_UPDATE_MESSAGE_OFFSET..];
+    let price = read_u64(message, 32) as i64;
+    if price <= 0 {
+        return Err(ErrorCode::OraclePriceUnavailable);
+    }
+    let expo = read_u32(message, 48) as i32;
+    Ok(PriceUpdate {
+        feed_id: message[..32].try_into().unwrap(),
+        price: rescale(price as u128, expo)?,
+        conf: rescale(read_u64(message, 40) as u128, expo)?,
+        publish_time: read_u64(message, 52) as i64,
+        posted_slot: read_u64(message, 84),
+    })
+}
+
+/// The market's own last-trade TWAP, with the gap between the last fill and
+/// the TWAP standing in for a confidence interval.
+fn twap_price(market: &Market) -> std::result::Result<OraclePrice, ErrorCode> {
+    if market.twap_slot == 0 || market.twap_price == 0 {
+        return Err(ErrorCode::OraclePriceUnavailable);
+    }
+    Ok(OraclePrice {
+        price: market.twap_price,
+        conf: market.last_price.abs_diff(market.twap_price),
+        slot: market.twap_slot,
+        source: ORACLE_SOURCE_TWAP,
+    })
+}
+
+fn check_price(
+    market: &Market,
+    price: OraclePrice,
+    slot: u64,
+) -> std::result::Result<OraclePrice, ErrorCode> {
+    // Nothing reading a price can divide by 0
+    if price.price == 0 {
+        return Err(ErrorCode::OraclePriceUnavailable);
+    }
+    if slot.saturating_sub(price.slot) > market.oracle_max_staleness_slots {
+        return Err(ErrorCode::OracleStale);
+    }
+    if price.conf as u128 * BPS_DENOMINATOR as u128
+        > price.price as u128 * market.oracle_max_confidence_bps as u128
+    {
+        return Err(ErrorCode::OracleConfidenceTooWide);
+    }
+    Ok(price)
+}
+
+/// Converts `value * 10^expo` to `PRICE_SCALE` fixed point, truncating.
+fn rescale(value: u128, expo: i32) -> std::result::Result<u64, ErrorCode> {
+    let shift = expo + PRICE_DECIMALS;
+    let scaled = if shift >= 0 {
+        10u128
+            .checked_pow(shift as u32)
+            .and_then(|factor| value.checked_mul(factor))
+    } else {
+        Some(10u128.checked_pow(shift.unsigned_abs()).map_or(0, |factor| value / factor))
+    };
+    scaled
+        .and_then(|scaled| u64::try_from(scaled).ok())
+        .ok_or(ErrorCode::MathOverflow)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_decimal(data: &[u8], offset: usize) -> (i128, u32) {
+    let mantissa = i128::from_le_bytes(data[offset..offset + 16].try_into().unwrap());
+    (mantissa, read_u32(data, offset + 16))
+}
@@ -0,0 +1,34 @@
+//! Tie-break randomness for pro-rata matching. A market can name an ORAO VRF
+//! randomness account; while its request is fulfilled, the makers at a price
+//! level are shared out in an order drawn from the result instead of one the
+//! cranker picks, so who takes a level's rounding can't be arranged. Each
+//! crank draws afresh, so one fulfilled request doesn't fix every level's
+//! order for as long as it stands. Like
+//! oracle feeds, the account is trusted by address, which only the market
+//! authority can set, and only its layout is checked.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::errors::ErrorCode;
+
+// ORAO VRF RandomnessAccountData: discriminator, seed, then the randomness
+const ORAO_RANDOMNESS_OFFSET: usize = 40;
+const ORAO_RANDOMNESS_LEN: usize = 64;
+
+/// The seed a fulfilled randomness account gives `market`'s crank number
+/// `crank_sequence`.
+pub fn seed(market: &Pubkey, crank_sequence: u64, data: &[u8]) -> Result<[u8; 32]> {
+    let randomness = data
+        .get(ORAO_RANDOMNESS_OFFSET..ORAO_RANDOMNESS_OFFSET + ORAO_RANDOMNESS_LEN)
+        .ok_or(ErrorCode::RandomnessUnavailable)?;
+    // Unfulfilled requests read as zero
+    if randomness.iter().all(|&byte| byte == 0) {
+        return err!(ErrorCode::RandomnessUnavailable);
+    }
+    Ok(hashv(&[randomness, market.as_ref(), &crank_sequence.to_le_bytes()]).to_bytes())
+}
+
+/// Where `trade` falls in the order `seed` draws; lower goes first.
+pub fn rank(seed: &[u8; 32], trade: &Pubkey) -> [u8; 32] {
+    hashv(&[seed, trade.as_ref()]).to_bytes()
+}
@@ -1,35 +1,2861 @@
+//! Program accounts. Each keeps the key it is most often looked up by at
+//! fixed offsets just after the discriminator: the market or parent account
+//! at byte 8, then the agent or owner at byte 40. Fields after the first
+//! variable-size one have no fixed offset.
 use anchor_lang::prelude::*;
 
+/// A token A / token B pair with its vaults, fee schedule, perp parameters
+/// and price state. Token A is the quote token throughout.
 #[account]
 #[derive(InitSpace)]
 pub struct Market {
     pub token_a: Pubkey,
     pub token_b: Pubkey,
+    pub authority: Pubkey,
+    /// Next order sequence number, used for queue priority
+    pub sequence: u64,
+    /// Number of the next crank: `match_trades`, `uncross_auction`,
+    /// `mirror_fill` or `process_redemptions`, which the cranker must pass
+    pub crank_sequence: u64,
+    /// Order book updates emitted so far, the last `OrderUpdated::book_sequence`
+    pub book_sequence: u64,
+    /// Negative = rebate paid out of taker fees
+    pub maker_fee_bps: i16,
+    pub taker_fee_bps: u16,
+    /// Token A fees held in vault A, not owned by any agent
+    pub fees_accrued: u64,
+    /// Token B fees held in vault B
+    pub fees_accrued_b: u64,
+    /// Epoch length, in slots or seconds per `time_base`
+    pub epoch_slots: u64,
+    /// TIME_BASE_*, what the market's epochs and expiries are counted in
+    pub time_base: u8,
+    pub fee_tier_count: u8,
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+    /// Vault trades accrue to Settlement instead of transferring
+    pub net_settlement: bool,
+    /// MATCHING_*, how a taker is shared among makers at one price
+    pub matching_policy: u8,
+    /// VRF account ordering pro-rata makers at a level, default = cranker's order
+    pub randomness_account: Pubkey,
+    /// A pro-rata market's resting orders by price level, so a crank can be
+    /// held to passing a maker's whole level
+    pub levels: [BookLevel; MAX_BOOK_LEVELS],
+    /// Taker being shared out among the makers of one pro-rata level
+    pub pro_rata_round: ProRataRound,
+    /// Token B size of a compact action's size bucket 0, each bucket doubling
+    /// it; 0 = compact actions disabled
+    pub action_lot: u64,
+    /// Price step of a compact action's offset from the last fill, PRICE_SCALE
+    pub action_tick: u64,
+    /// Token A and token B `reset_agent_for_episode` leaves an agent holding
+    pub episode_capital_a: u64,
+    pub episode_capital_b: u64,
+    /// Charge against an agent's epoch reward for the inventory it held
+    /// over the epoch, bps of its time-weighted average value
+    pub inventory_penalty_bps: u16,
+    /// Token B cap on an order's size, 0 = uncapped
+    pub max_order_size: u64,
+    /// Agent whose scripted flow `place_adversary_order` places, default =
+    /// none, and the cap on its orders' size there, 0 = uncapped
+    pub adversary: Pubkey,
+    pub adversary_max_size: u64,
+    /// Offsets into each epoch, in market time, that trading opens at and
+    /// closes at; equal = always open, open after close = open across the
+    /// epoch boundary
+    pub trading_open: u64,
+    pub trading_close: u64,
+    /// Length of the call auctions that open and close each session, in
+    /// market time; 0 = continuous trading throughout
+    pub auction_slots: u64,
+    /// End of the last call auction uncrossed, in market time
+    pub last_uncross: u64,
+    /// Fill-to-fill price move that halts the market, 0 = no circuit breaker
+    pub circuit_breaker_bps: u32,
+    /// How long a halt lasts and the reopening auction after it runs, in
+    /// market time
+    pub halt_slots: u64,
+    pub reopen_auction_slots: u64,
+    /// When the last halt ends, 0 = never halted
+    pub halted_until: u64,
+    /// execute_trade must be the only instruction touching the market
+    pub sandwich_guard: bool,
+    /// Trades may be filled by external liquidity through Jupiter
+    pub jupiter_routing: bool,
+    /// adapters::MIRROR_*, external pool execute_mirror_trade routes to
+    pub mirror_venue: u8,
+    pub mirror_pool: Pubkey,
+    /// Relayer whose signed-order postings agents owe lamports for
+    pub relayer: Pubkey,
+    /// Feed quoting SOL in token A, for repaying the relayer
+    pub relayer_fee_oracle: Pubkey,
+    /// 0 = perps disabled
+    pub initial_margin_bps: u16,
+    pub maintenance_margin_bps: u16,
+    pub liquidation_fee_bps: u16,
+    /// Token A held in vault A to absorb bankrupt positions
+    pub insurance_fund: u64,
+    /// Bankrupt deficit neither insurance nor the winning side covered
+    pub bad_debt: u64,
+    /// Token B base units
+    pub open_interest_long: u64,
+    pub open_interest_short: u64,
+    /// 0 = uncapped
+    pub max_open_interest_long: u64,
+    pub max_open_interest_short: u64,
+    /// Skew in token B at which fills pay a 100% premium, 0 = no impact
+    pub skew_scale: u64,
+    /// Socialized loss per long base unit, LOSS_INDEX_SCALE
+    pub long_loss_index: u128,
+    pub short_loss_index: u128,
+    /// Funding owed per long base unit, ACCRUAL_INDEX_SCALE, negative = received
+    pub long_funding_index: i128,
+    pub short_funding_index: i128,
+    /// Token A per long base unit per epoch, ACCRUAL_INDEX_SCALE, negative =
+    /// shorts pay; set from the premium at the last priced interaction
+    pub funding_per_epoch: i128,
+    /// Interest owed per token A borrowed, ACCRUAL_INDEX_SCALE
+    pub borrow_index: u128,
+    /// Market time the funding and borrow indices were last brought up to
+    pub accrued_at: u64,
+    /// Cap on the funding premium per epoch, 0 = no funding
+    pub max_funding_bps: u16,
+    /// Yearly interest on negative position collateral
+    pub borrow_rate_bps: u16,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+    /// Pyth or Switchboard feed quoting token B in token A, default = none
+    pub primary_oracle: Pubkey,
+    pub fallback_oracle: Pubkey,
+    pub oracle_max_staleness_slots: u64,
+    pub oracle_max_confidence_bps: u16,
+    /// Last fill, whole token A per whole token B scaled by PRICE_SCALE
+    pub last_price: u64,
+    /// Last-trade TWAP over TWAP_WINDOW_SLOTS, same scale
+    pub twap_price: u64,
+    /// Slot of the last fill folded into the TWAP
+    pub twap_slot: u64,
+    /// EWMA of fill-to-fill price moves over VOLATILITY_WINDOW_FILLS
+    pub volatility_bps: u32,
+    /// Extra taker bps per 100% volatility, BPS_DENOMINATOR scale, 0 = flat fees
+    pub volatility_fee_coefficient: u16,
+    /// Bounds on the volatility-scaled taker rate
+    pub min_taker_fee_bps: u16,
+    pub max_taker_fee_bps: u16,
+    /// Orders resting with something left to fill
+    pub open_orders: u64,
+    /// Placement surcharges by open-order count, none = no surcharge
+    pub congestion_tier_count: u8,
+    pub congestion_tiers: [CongestionTier; MAX_CONGESTION_TIERS],
+    /// A flash swap's callback is running; every instruction that writes the
+    /// market or its vaults refuses it until the swap settles
+    pub in_flight: bool,
+    /// Call auction part way through `uncross_auction`; the book is frozen
+    /// until it finishes
+    pub uncross: Uncross,
     pub bump: u8,
 }
 
+/// Discounted fee rates for agents whose rolling volume reaches `min_volume`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct FeeTier {
+    /// Token A traded over the last VOLUME_EPOCHS epochs
+    pub min_volume: u64,
+    pub maker_fee_bps: i16,
+    pub taker_fee_bps: u16,
+}
+
+/// The orders resting at one price on one side of a pro-rata market.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct BookLevel {
+    pub trade_type: u8,
+    /// PRICE_SCALE
+    pub price: u64,
+    /// 0 = free slot
+    pub orders: u32,
+    /// Token B displayed across the orders
+    pub displayed: u64,
+}
+
+/// A taker part way through a pro-rata level. Each maker's share is taken
+/// of the taker and level as they stood when the round began, whatever
+/// turn it fills in; anything else touching the level or taker in between
+/// starts a new round.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct ProRataRound {
+    /// Rounds so far; a maker filled in this one carries its number
+    pub round: u64,
+    pub taker: Pubkey,
+    /// Index into `Market::levels`
+    pub level: u8,
+    /// Displayed sizes of the taker and the level when the round began
+    pub taker_size: u64,
+    pub level_size: u64,
+    /// Level makers yet to fill in the round and their displayed size
+    pub orders_left: u32,
+    pub displayed_left: u64,
+    /// Level and taker as the round's last fill left them
+    pub level_orders: u32,
+    pub level_displayed: u64,
+    pub taker_displayed: u64,
+}
+
+/// A call auction being uncrossed over as many `uncross_auction` cranks as
+/// its book needs: first every resting order is tallied into `levels`, with
+/// what its agent can pay for set aside, then each is passed again to fill
+/// at the clearing price.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct Uncross {
+    /// End of the auction, in market time, 0 = none under way
+    pub end: u64,
+    /// SESSION_* auction
+    pub phase: u8,
+    /// UNCROSS_TALLY, then UNCROSS_FILL
+    pub stage: u8,
+    /// Orders still to pass in the stage
+    pub orders_left: u64,
+    /// Orders tallied, each owed a fill turn
+    pub orders: u64,
+    /// Base taker rate as the uncross began, charged on every fill
+    pub taker_fee_bps: u16,
+    pub level_count: u8,
+    pub levels: [UncrossLevel; MAX_UNCROSS_LEVELS],
+    /// Clearing price and volume, once every order is tallied
+    pub price: u64,
+    pub volume: u64,
+    pub bid_edge: UncrossEdge,
+    pub ask_edge: UncrossEdge,
+    /// Token B bought and sold so far, and token A paid, received and
+    /// taken in fees
+    pub bought: u64,
+    pub sold: u64,
+    pub paid: u64,
+    pub received: u64,
+    pub fees: u64,
+}
+
+/// Token B tallied at one limit price on one side of an uncross.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct UncrossLevel {
+    pub trade_type: u8,
+    /// PRICE_SCALE
+    pub price: u64,
+    pub size: u64,
+}
+
+/// The level one side of an uncross stops filling in full at: better
+/// prices fill in full, this one shares `fill` of its `size` pro rata and
+/// worse ones sit out. `seen` is the size of its orders filled so far, so
+/// the shares add up to `fill` whatever order they're passed in.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct UncrossEdge {
+    pub price: u64,
+    pub size: u64,
+    pub fill: u64,
+    pub seen: u64,
+}
+
+/// Token A charged for each order placed while `min_open_orders` or more
+/// are resting on the market, paid into its fee pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct CongestionTier {
+    pub min_open_orders: u64,
+    pub surcharge: u64,
+}
+
+/// A change a curriculum makes to its market once the market reaches `epoch`,
+/// CURRICULUM_* `kind` with its `value`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct CurriculumStage {
+    pub epoch: u64,
+    pub kind: u8,
+    pub value: i64,
+}
+
+/// A hot key an agent's owner delegated trading to, with its own allowance.
+/// Limits and spend are indexed by trade type: token A committed to bids,
+/// token B to asks.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct Delegate {
+    /// Default = free slot
+    pub key: Pubkey,
+    /// Valid strictly before this slot
+    pub expiry_slot: u64,
+    /// SESSION_SCOPE_* bitmask
+    pub scope: u8,
+    /// What the key may commit over its session, 0 = unbounded
+    pub spend_limit: [u64; 2],
+    pub spent: [u64; 2],
+    /// What the key may commit per epoch, 0 = unbounded
+    pub epoch_cap: [u64; 2],
+    /// Epoch `epoch_spent` counts
+    pub epoch: u64,
+    pub epoch_spent: [u64; 2],
+}
+
+/// A trading identity. Primary agents derive from their registrant's wallet;
+/// sub-accounts from their parent.
 #[account]
 #[derive(InitSpace)]
 pub struct Agent {
     pub owner: Pubkey,
+    /// Wallet a primary agent's PDA derives from, kept when ownership moves
+    pub registrant: Pubkey,
+    /// 0 = cancel newest, 1 = cancel oldest, 2 = decrement both
+    pub stp_mode: u8,
+    /// Most recent epoch written to `volume`
+    pub volume_epoch: u64,
+    /// Token A traded, ring buffer indexed by epoch
+    pub volume: [u64; VOLUME_EPOCHS],
+    /// Primary agent of a sub-account, default for a primary agent
+    pub parent: Pubkey,
+    pub subaccount_index: u16,
+    /// Hot keys allowed to trade but never withdraw
+    pub delegates: [Delegate; MAX_DELEGATES],
+    /// Highest nonce of an off-chain signed order posted
+    pub signed_order_nonce: u64,
+    /// Identity NFT whose holder owns the agent, default = none
+    pub nft_mint: Pubkey,
+    /// Wallet offered ownership, default = none
+    pub pending_owner: Pubkey,
+    /// Share of followers' realized copy profit the agent takes as a leader,
+    /// fixed for each follower when it subscribes
+    pub performance_fee_bps: u16,
+    /// Trade accounts the agent has open, on any market; an episode reset
+    /// has to be passed every one
+    pub open_trades: u64,
     pub bump: u8,
 }
 
+/// An agent's resting order on a market, one per order id.
 #[account]
 #[derive(InitSpace)]
 pub struct Trade {
-    pub agent: Pubkey,
     pub market: Pubkey,
-    pub trade_type: u8, // 0 = buy, 1 = sell
+    pub agent: Pubkey,
+    /// 0 = buy, 1 = sell
+    pub trade_type: u8,
     pub amount_in: u64,
     pub amount_out: u64,
+    /// Queue priority, lower rests ahead
+    pub sequence: u64,
+    /// Iceberg tranche size, 0 = fully displayed
+    pub display_size: u64,
+    /// Unfilled part of the current tranche
+    pub visible_size: u64,
+    /// TRADE_FLAG_* bitmask
+    pub flags: u8,
+    /// Placer's label for the order, echoed in its fills
+    pub tag: [u8; TRADE_TAG_LEN],
+    /// Agent's id for the order, part of its address, so an agent rests one
+    /// order per id on each market and as many ids as it likes
+    pub order_id: u64,
+    /// Slot in `Market::levels` the order is counted in plus one, 0 = none,
+    /// and the displayed size it's counted with
+    pub booked_level: u8,
+    pub booked_displayed: u64,
+    /// `ProRataRound::round` the order last filled in as a maker
+    pub pro_rata_round: u64,
+    /// `Uncross::end` of the auction the order is tallied in, 0 once it has
+    /// had its fill turn, with the size it takes part with and the token its
+    /// agent set aside for it
+    pub uncross_end: u64,
+    pub uncross_size: u64,
+    pub uncross_reserved: u64,
     pub bump: u8,
 }
 
+/// Perpetual exposure of an agent to a market's token B, margined in token A
+/// held in vault A and marked at the oracle price.
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub market: Pubkey,
+    pub agent: Pubkey,
+    /// Token B base units, positive = long
+    pub base: i64,
+    /// Token A base units: collateral less the cost of `base`
+    pub quote: i64,
+    /// Token A paid for the open `base`, negative when short
+    pub cost_basis: i64,
+    /// Side's loss index when socialized losses were last applied
+    pub loss_index: u128,
+    /// Side's funding index when funding was last applied
+    pub funding_index: i128,
+    /// Market's borrow index when interest was last charged
+    pub borrow_index: u128,
+    pub bump: u8,
+}
+
+/// Most profitable positions on each side of a market by `adl_score`, best
+/// first, kept fresh by `update_adl_rank` and consumed by `auto_deleverage`.
+#[account]
+#[derive(InitSpace)]
+pub struct AdlQueue {
+    pub market: Pubkey,
+    pub longs: [AdlEntry; ADL_QUEUE_LEN],
+    pub shorts: [AdlEntry; ADL_QUEUE_LEN],
+    pub bump: u8,
+}
+
+/// A position queued for auto-deleveraging and its rank score.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct AdlEntry {
+    /// Default = empty slot
+    pub position: Pubkey,
+    pub score: u64,
+}
+
+/// A market epoch's top agents by volume, highest first, kept current by
+/// `match_trades` so headline rankings need no indexer.
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub market: Pubkey,
+    pub epoch: u64,
+    pub entries: [LeaderboardEntry; LEADERBOARD_LEN],
+    pub bump: u8,
+}
+
+/// An agent on a leaderboard and the token A it traded in the epoch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct LeaderboardEntry {
+    /// Default = empty slot
+    pub agent: Pubkey,
+    pub volume: u64,
+}
+
+/// Lamports a market's designated relayer has spent posting signed orders
+/// for an agent, repaid in token A through `reimburse_relayer`.
+#[account]
+#[derive(InitSpace)]
+pub struct RelayerDebt {
+    pub market: Pubkey,
+    pub agent: Pubkey,
+    pub relayer: Pubkey,
+    pub lamports: u64,
+    pub bump: u8,
+}
+
+/// Everything needed to open a trade, shared by on-chain and signed orders.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct OrderParams {
+    pub trade_type: u8,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub display_size: u64,
+    pub flags: u8,
+    pub tag: [u8; TRADE_TAG_LEN],
+}
+
+/// Order signed off-chain by an agent owner; the Borsh encoding is the
+/// signed message.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SignedOrder {
+    pub agent: Pubkey,
+    pub market: Pubkey,
+    /// Id of the trade the order opens
+    pub order_id: u64,
+    pub params: OrderParams,
+    /// Must exceed the agent's signed_order_nonce
+    pub nonce: u64,
+    /// In the market's time units
+    pub expiry_slot: u64,
+}
+
+/// A compact action for the agent's trade `order_id`, see `ACTION_LEN`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct OrderAction {
+    pub order_id: u64,
+    pub action: [u8; ACTION_LEN],
+}
+
+/// What taking `size` token B from the book would fill, returned by `quote`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct BookQuote {
+    /// Token B filled, less than asked for when the book runs out
+    pub size: u64,
+    /// Token A paid (taker buying) or received (taker selling), before fees
+    pub quote_amount: u64,
+    /// Base taker fee on `quote_amount`, in token A
+    pub fee: u64,
+    /// `quote_amount` per `size`, `PRICE_SCALE` fixed point, rounded down
+    pub average_price: u64,
+}
+
+/// Where a call auction would uncross with the orders resting now, returned
+/// by `indicate_auction`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct AuctionIndication {
+    /// SESSION_* auction the market is calling
+    pub phase: u8,
+    /// Token A per token B, `PRICE_SCALE` fixed point; 0 when nothing crosses
+    pub price: u64,
+    /// Token B that would trade
+    pub volume: u64,
+    /// Bids less asks crossing at `price`, in token B
+    pub imbalance: i64,
+}
+
+/// Owner of a market's token account for one of its mints.
 #[account]
 #[derive(InitSpace)]
 pub struct Vault {
     pub market: Pubkey,
     pub token: Pubkey,
+    /// Outstanding within the current transaction
+    pub flash_loan_amount: u64,
+    /// Tokens moved in through the program less those moved out
+    pub reserves: u64,
+    /// Part of `reserves` reconcile_vault found sent in directly, not yet swept
+    pub donations: u64,
+    /// Part of `reserves` owned by the vault itself, the side it takes in
+    /// mirrored fills and against perp positions
+    pub mirror_liquidity: u64,
+    pub bump: u8,
+}
+
+/// Tokens an agent holds in a vault, tracked by the program.
+#[account]
+#[derive(InitSpace)]
+pub struct Balance {
+    pub vault: Pubkey,
+    pub agent: Pubkey,
+    /// Tokens held in the vault on the agent's behalf
+    pub amount: u64,
+    /// Tokens set aside for orders in an auction being uncrossed, not part
+    /// of `amount` until their fills are settled
+    pub reserved: u64,
+    pub bump: u8,
+}
+
+/// Marks deposit `nonce` of an agent as credited, so a retried
+/// `deposit_tokens` carrying the same nonce credits nothing.
+#[account]
+#[derive(InitSpace)]
+pub struct DepositReceipt {
+    pub agent: Pubkey,
+    pub vault: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+/// Token movements owed between an agent and the vaults for one epoch of a
+/// net-settled market; positive deltas are owed to the agent.
+#[account]
+#[derive(InitSpace)]
+pub struct Settlement {
+    pub market: Pubkey,
+    pub agent: Pubkey,
+    pub epoch: u64,
+    pub delta_a: i64,
+    pub delta_b: i64,
+    pub bump: u8,
+}
+
+/// Off-book block trade: the maker's `give_amount` sits in an escrow ATA owned
+/// by this account until a taker pays `want_amount` or the maker cancels.
+#[account]
+#[derive(InitSpace)]
+pub struct OtcOffer {
+    pub market: Pubkey,
+    /// Maker agent
+    pub maker: Pubkey,
+    pub offer_id: u64,
+    pub give_mint: Pubkey,
+    pub give_amount: u64,
+    pub want_mint: Pubkey,
+    pub want_amount: u64,
+    /// Agent allowed to accept, default = anyone
+    pub counterparty: Pubkey,
+    pub bump: u8,
+}
+
+/// Request for quote on `size` of token B; market makers answer with the
+/// amount of token A they would pay (taker selling) or charge (taker buying).
+#[account]
+#[derive(InitSpace)]
+pub struct Rfq {
+    pub market: Pubkey,
+    /// Requesting agent
+    pub taker: Pubkey,
+    pub rfq_id: u64,
+    /// 0 = taker buys token B, 1 = taker sells token B
+    pub side: u8,
+    pub size: u64,
+    /// In the market's time units
+    pub expiry_slot: u64,
+    pub quote_count: u8,
+    pub quotes: [RfqQuote; MAX_RFQ_QUOTES],
+    pub bump: u8,
+}
+
+/// One maker's response to a request for quote.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RfqQuote {
+    /// Quoting agent
+    pub maker: Pubkey,
+    /// Token A for the full size
+    pub quote_amount: u64,
+}
+
+/// Covered option on `size` of token B. Calls lock the writer's token B,
+/// puts lock `strike_amount` of token A; exercise cash-settles, paying the
+/// holder the intrinsic value at the oracle price out of that collateral.
+#[account]
+#[derive(InitSpace)]
+pub struct OptionContract {
+    pub market: Pubkey,
+    /// Writer agent
+    pub writer: Pubkey,
+    /// Buyer agent, default = not sold yet
+    pub holder: Pubkey,
+    pub option_id: u64,
+    /// 0 = call, 1 = put
+    pub kind: u8,
+    pub size: u64,
+    /// Token A for the full size
+    pub strike_amount: u64,
+    /// Token A
+    pub premium: u64,
+    /// In the market's time units
+    pub expiry_slot: u64,
+    pub bump: u8,
+}
+
+pub const OPTION_CALL: u8 = 0;
+pub const OPTION_PUT: u8 = 1;
+
+/// Event market paying one unit of collateral per winning YES or NO token.
+#[account]
+#[derive(InitSpace)]
+pub struct BinaryMarket {
+    pub authority: Pubkey,
+    /// Signer allowed to resolve the outcome
+    pub oracle: Pubkey,
+    pub market_id: u64,
+    pub collateral_mint: Pubkey,
+    pub yes_mint: Pubkey,
+    pub no_mint: Pubkey,
+    /// 0 = unresolved, 1 = yes, 2 = no
+    pub outcome: u8,
+    pub bump: u8,
+}
+
+pub const OUTCOME_UNRESOLVED: u8 = 0;
+pub const OUTCOME_YES: u8 = 1;
+pub const OUTCOME_NO: u8 = 2;
+
+/// Basket token backed by fixed amounts of several component tokens held in
+/// ATAs owned by this account.
+#[account]
+#[derive(InitSpace)]
+pub struct IndexBasket {
+    pub authority: Pubkey,
+    pub index_id: u64,
+    pub basket_mint: Pubkey,
+    pub component_count: u8,
+    pub components: [IndexComponent; MAX_INDEX_COMPONENTS],
+    pub bump: u8,
+}
+
+/// A mint in an index basket and the amount backing each whole basket token.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct IndexComponent {
+    pub mint: Pubkey,
+    /// Component base units backing one whole basket token
+    pub units: u64,
+}
+
+/// Latest verified Pyth pull-oracle price for a market, refreshed by anyone
+/// through `post_price_update` and readable as a feed by `oracle::read_price`.
+#[account]
+#[derive(InitSpace)]
+pub struct PriceCache {
+    pub market: Pubkey,
+    /// Pyth price feed id accepted for this market
+    pub feed_id: [u8; 32],
+    /// PRICE_SCALE fixed point
+    pub price: u64,
+    pub conf: u64,
+    pub publish_time: i64,
+    /// Slot the update was posted to the receiver program
+    pub posted_slot: u64,
+    pub bump: u8,
+}
+
+/// A market's key aggregates as of the first `snapshot_market` call in an
+/// epoch. Only the histograms change afterwards, counting the epoch's fills
+/// cranked with the snapshot passed.
+#[account]
+#[derive(InitSpace)]
+pub struct Snapshot {
+    pub market: Pubkey,
+    pub epoch: u64,
+    /// Slot the snapshot was taken at
+    pub slot: u64,
+    pub sequence: u64,
+    /// Vault token account balances
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub last_price: u64,
+    pub twap_price: u64,
+    pub open_interest_long: u64,
+    pub open_interest_short: u64,
+    pub fees_accrued: u64,
+    pub fees_accrued_b: u64,
+    /// Fills by token B size: bucket `i` counts sizes in `[10^i, 10^(i+1))`
+    pub size_histogram: [u64; SIZE_BUCKETS],
+    /// Fills by move from the previous fill's price: bucket 0 counts no move
+    /// and the market's first fill, bucket `i` moves in `[2^(i-1), 2^i)` bps,
+    /// the last everything beyond
+    pub impact_histogram: [u64; IMPACT_BUCKETS],
+    pub bump: u8,
+}
+
+/// An agent's reward for one market epoch, built up from the `match_trades`
+/// fills cranked with the record passed, then split into its components by
+/// `settle_reward` once the epoch is over.
+#[account]
+#[derive(InitSpace)]
+pub struct RewardRecord {
+    pub market: Pubkey,
+    pub agent: Pubkey,
+    pub epoch: u64,
+    /// Token A the fills received less paid, before fees
+    pub cash_a: i64,
+    /// Token B they bought less sold
+    pub inventory_b: i64,
+    /// Token A fees paid, net of maker rebates
+    pub fees: i64,
+    pub fills: u64,
+    /// Market time `inventory_b` last changed, and its absolute value summed
+    /// over market time up to then
+    pub inventory_at: u64,
+    pub inventory_time: u128,
+    pub settled: bool,
+    /// Last fill price the inventory was marked at, PRICE_SCALE
+    pub price: u64,
+    /// Mean absolute `inventory_b` over the epoch's market time
+    pub average_inventory: u64,
+    /// Cash plus the inventory's value at `price`
+    pub pnl: i64,
+    /// `inventory_penalty_bps` of `average_inventory`'s value at `price`
+    pub inventory_penalty: i64,
+    /// `pnl` less fees and the inventory penalty
+    pub reward: i64,
+    pub bump: u8,
+}
+
+/// A market's schedule of parameter changes for staged training, applied by
+/// `advance_curriculum` as the market's epochs start. Stages are in epoch
+/// order; `next_stage` is the first not yet applied.
+#[account]
+#[derive(InitSpace)]
+pub struct Curriculum {
+    pub market: Pubkey,
+    pub stage_count: u8,
+    pub stages: [CurriculumStage; MAX_CURRICULUM_STAGES],
+    pub next_stage: u8,
+    pub bump: u8,
+}
+
+/// A market's fixed-size feature vector for agents to observe, rewritten by
+/// `update_observation_frame` at most once every `interval` slots. Prices are
+/// PRICE_SCALE per whole token like `Market::last_price`, sizes token B;
+/// zero-copy so readers can cast it in place.
+#[account(zero_copy)]
+pub struct ObservationFrame {
+    pub market: Pubkey,
+    /// `Market::funding_per_epoch` as of the update
+    pub funding_per_epoch: i128,
+    /// Minimum slots between updates
+    pub interval: u64,
+    /// Slot of the last update
+    pub slot: u64,
+    /// Updates made, 0 = never updated
+    pub sequence: u64,
+    /// Midpoint of the best bid and ask, the last fill price while a side is empty
+    pub mid: u64,
+    /// Best ask less best bid, 0 while a side is empty or the book is crossed
+    pub spread: u64,
+    pub last_price: u64,
+    pub open_interest_long: u64,
+    pub open_interest_short: u64,
+    /// Best OBSERVATION_LEVELS price levels per side, best first; a level
+    /// with no size is empty
+    pub bid_prices: [u64; OBSERVATION_LEVELS],
+    pub bid_sizes: [u64; OBSERVATION_LEVELS],
+    pub ask_prices: [u64; OBSERVATION_LEVELS],
+    pub ask_sizes: [u64; OBSERVATION_LEVELS],
+    /// `Market::book_sequence` the update being built reads; any change to
+    /// the book starts it over
+    pub build_book_sequence: u64,
+    /// Resting orders the update being built has still to read
+    pub build_orders_left: u64,
+    /// Last order the update being built read, pages passing orders in
+    /// ascending key order
+    pub build_last_order: Pubkey,
+    pub build_bid_prices: [u64; OBSERVATION_LEVELS],
+    pub build_bid_sizes: [u64; OBSERVATION_LEVELS],
+    pub build_ask_prices: [u64; OBSERVATION_LEVELS],
+    pub build_ask_sizes: [u64; OBSERVATION_LEVELS],
+    pub volatility_bps: u32,
+    pub bump: u8,
+    /// An update is part way through being built
+    pub building: u8,
+    pub padding: [u8; 10],
+}
+
+/// Merkle root over off-chain agent scores for one market epoch, with the
+/// token A rewards the authority deposited to back it. Leaves are
+/// `score_leaf(agent, amount)`.
+#[account]
+#[derive(InitSpace)]
+pub struct ScoreRoot {
+    pub market: Pubkey,
+    pub epoch: u64,
+    pub root: [u8; 32],
+    /// Token A held in vault A for claims
+    pub total_rewards: u64,
+    pub claimed: u64,
+    pub bump: u8,
+}
+
+/// Marks an agent's reward under a `ScoreRoot` as claimed.
+#[account]
+#[derive(InitSpace)]
+pub struct ScoreClaim {
+    pub score_root: Pubkey,
+    pub agent: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Capital pooled by several wallets into one guild agent on a market. The
+/// elected manager trades the agent as its session key; members own it
+/// through shares and exit pro rata in kind with `withdraw_share`.
+#[account]
+#[derive(InitSpace)]
+pub struct Guild {
+    pub market: Pubkey,
+    /// Elects the manager
+    pub authority: Pubkey,
+    /// Guild agent PDA, owned by the guild so no wallet can withdraw
+    pub agent: Pubkey,
+    pub manager: Pubkey,
+    pub total_shares: u64,
+    pub bump: u8,
+}
+
+/// A member's shares in a guild.
+#[account]
+#[derive(InitSpace)]
+pub struct GuildMember {
+    pub guild: Pubkey,
+    pub member: Pubkey,
+    pub shares: u64,
+    pub bump: u8,
+}
+
+/// Most recent order book fills of a leader agent on a market, appended by
+/// `match_trades` whenever the cranker passes the feed, for followers to copy.
+#[account]
+#[derive(InitSpace)]
+pub struct CopyFeed {
+    pub market: Pubkey,
+    pub leader: Pubkey,
+    /// Fills ever recorded; fill n lives at n % COPY_FEED_LEN
+    pub fill_count: u64,
+    pub fills: [CopyFill; COPY_FEED_LEN],
+    pub bump: u8,
+}
+
+/// A leader fill as recorded for followers to mirror.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct CopyFill {
+    /// 0 = bought token B, 1 = sold
+    pub trade_type: u8,
+    /// Token B
+    pub quantity: u64,
+    /// Token A, before fees
+    pub quote_amount: u64,
+}
+
+/// A follower copying `fraction_bps` of a leader's fills, paying the leader
+/// `performance_fee_bps` of the profit realized on copied positions.
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub market: Pubkey,
+    pub follower: Pubkey,
+    pub leader: Pubkey,
+    pub fraction_bps: u16,
+    pub performance_fee_bps: u16,
+    /// Next CopyFeed fill to mirror
+    pub next_fill: u64,
+    /// Token B bought by copying and not yet sold
+    pub base_held: u64,
+    /// Token A paid for `base_held`
+    pub cost_basis: u64,
+    pub bump: u8,
+}
+
+/// Third-party capital managed by one agent. Depositors hold SPL share
+/// tokens of `share_mint`; the manager trades the strategy agent as its
+/// session key and earns management and performance fees, crystallized by
+/// the first deposit or redemption processing of each epoch. Redemptions
+/// queue for an epoch and are paid in token A at the NAV per share fixed
+/// when the next epoch's redemptions are first processed.
+#[account]
+#[derive(InitSpace)]
+pub struct StrategyVault {
+    pub market: Pubkey,
+    pub manager_agent: Pubkey,
+    /// Wallet trading the strategy agent and holding fee shares
+    pub manager: Pubkey,
+    /// Strategy agent PDA, owned by the strategy vault
+    pub agent: Pubkey,
+    /// Mint authority is the strategy vault
+    pub share_mint: Pubkey,
+    pub management_fee_bps: u16,
+    pub performance_fee_bps: u16,
+    /// Highest NAV per share fees were charged at, PRICE_SCALE
+    pub high_water_mark: u64,
+    /// Epoch fees were last crystallized in
+    pub fee_epoch: u64,
+    /// Market time fees were last crystallized at
+    pub fee_slot: u64,
+    /// Epoch the settlement below was fixed in
+    pub settlement_epoch: u64,
+    /// Share supply and strategy holdings when the epoch began; redemptions
+    /// are paid their share of both tokens in kind
+    pub settlement_supply: u64,
+    pub settlement_a: u64,
+    pub settlement_b: u64,
+    pub bump: u8,
+}
+
+/// Shares queued for redemption, held in the strategy vault's escrow.
+#[account]
+#[derive(InitSpace)]
+pub struct RedemptionRequest {
+    pub strategy_vault: Pubkey,
+    pub owner: Pubkey,
+    pub shares: u64,
+    /// Redeemable once this epoch has ended
+    pub epoch: u64,
+    pub bump: u8,
+}
+
+/// Deployment-wide settings, one per program.
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    /// Creates markets and becomes their authority
+    pub admin: Pubkey,
+    /// Fees new markets start with
+    pub maker_fee_bps: i16,
+    pub taker_fee_bps: u16,
+    /// FEATURE_* bitmask
+    pub features: u64,
+    /// Trading halted, withdrawals skip epoch waits
+    pub emergency_mode: bool,
+    /// Markets created so far, and the index of the next `MarketListing`
+    pub market_count: u64,
+    /// Agents registered so far, and the index of the next `AgentListing`
+    pub agent_count: u64,
+    pub bump: u8,
+}
+
+/// Entry `index` in the list of markets, created with the market. Clients
+/// enumerate markets by deriving listings `0..config.market_count`, in pages
+/// of any size.
+#[account]
+#[derive(InitSpace)]
+pub struct MarketListing {
+    pub market: Pubkey,
+    pub index: u64,
+    pub bump: u8,
+}
+
+/// Entry `index` in the list of agents, created at registration and
+/// enumerated like `MarketListing`s, over `0..config.agent_count`.
+#[account]
+#[derive(InitSpace)]
+pub struct AgentListing {
+    pub agent: Pubkey,
+    pub index: u64,
+    pub bump: u8,
+}
+
+/// Deployment-wide running totals, one per program, created with the config
+/// and kept current by the instructions that move them, so a dashboard gets
+/// its headline numbers from one account. Amounts add up base units of each
+/// market's own mints, so they only mean one thing across markets that share
+/// them.
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalStats {
+    pub markets: u64,
+    pub agents: u64,
+    /// Fills the program settled: order book matches, vault fills and
+    /// copied fills
+    pub trades: u64,
+    /// Token A that changed hands in those fills
+    pub volume: u128,
+    /// Fees charged on them, net of maker rebates
+    pub fees_a: u128,
+    pub fees_b: u128,
+    pub bump: u8,
+}
+
+/// A reward token paid out over a window of market time at `rate` per slot
+/// (or second, per the market's time base), for studying incentive-driven
+/// trading. Each epoch's emission is split between the book's liquidity
+/// providers, its makers, and its takers by `maker_share_bps`, then pro rata
+/// to the volume each side's agents traded in the epoch, counting the
+/// `match_trades` fills the cranker passed the schedule to.
+#[account]
+#[derive(InitSpace)]
+pub struct EmissionsSchedule {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub reward_mint: Pubkey,
+    pub rate: u64,
+    /// Market time emissions run over, start inclusive
+    pub start: u64,
+    pub end: u64,
+    /// Of each epoch's emission, rest to takers
+    pub maker_share_bps: u16,
+    /// Volume counted per epoch, ring buffer indexed by epoch
+    pub epochs: [EmissionsEpoch; EMISSIONS_EPOCHS],
+    /// Smallest bond that earns a boost
+    pub min_bond: u64,
+    /// Boost for a bond locked the longest, BPS_DENOMINATOR = 1x; below 1x
+    /// turns boosts off
+    pub max_boost_bps: u16,
+    /// Reward tokens locked in bonds
+    pub bonded: u64,
+    /// Market fees paid to bonds per bonded token, ACCRUAL_INDEX_SCALE
+    pub fee_index_a: u128,
+    pub fee_index_b: u128,
+    pub bump: u8,
+}
+
+/// Token A volume an emissions schedule counted in one epoch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct EmissionsEpoch {
+    pub epoch: u64,
+    pub maker_volume: u64,
+    pub taker_volume: u64,
+}
+
+/// Reward tokens an agent's owner locked under an emissions schedule until
+/// `unlock`, vote-escrow style: the agent's volume counts for more the
+/// longer the lock has left to run, so the boost decays as unlock nears and
+/// a re-lock restores it. Bonds also share the market fees distributed to
+/// the schedule, pro rata to their amount.
+#[account]
+#[derive(InitSpace)]
+pub struct Bond {
+    pub schedule: Pubkey,
+    pub agent: Pubkey,
+    pub amount: u64,
+    /// Market time the tokens can be withdrawn from
+    pub unlock: u64,
+    /// The schedule's fee indices when `fees_owed_*` were last settled
+    pub fee_index_a: u128,
+    pub fee_index_b: u128,
+    /// Market fees earned and not yet claimed
+    pub fees_owed_a: u64,
+    pub fees_owed_b: u64,
+    pub bump: u8,
+}
+
+/// An agent's volume under an emissions schedule in the last epoch it was
+/// counted in, and what earlier epochs earned it. An epoch's share is lost
+/// if the schedule's ring buffer moves past it unsettled, so agents claim
+/// within EMISSIONS_EPOCHS epochs.
+#[account]
+#[derive(InitSpace)]
+pub struct EmissionsAccount {
+    pub schedule: Pubkey,
+    pub agent: Pubkey,
+    pub epoch: u64,
+    pub maker_volume: u64,
+    pub taker_volume: u64,
+    pub unclaimed: u64,
+    pub bump: u8,
+}
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const DEFAULT_EPOCH_SLOTS: u64 = 9_000;
+pub const MAX_FEE_TIERS: usize = 4;
+pub const MAX_CONGESTION_TIERS: usize = 4;
+/// Distinct prices a pro-rata market can have orders resting at, both sides.
+pub const MAX_BOOK_LEVELS: usize = 32;
+/// Distinct limit prices an uncross tallies, both sides; orders at any
+/// further price sit the auction out.
+pub const MAX_UNCROSS_LEVELS: usize = 32;
+pub const MAX_CURRICULUM_STAGES: usize = 16;
+pub const VOLUME_EPOCHS: usize = 30;
+pub const MAX_RFQ_QUOTES: usize = 8;
+pub const MAX_INDEX_COMPONENTS: usize = 8;
+pub const INDEX_DECIMALS: u8 = 6;
+pub const PRICE_SCALE: u64 = 1_000_000;
+pub const TWAP_WINDOW_SLOTS: u64 = 150;
+pub const VOLATILITY_WINDOW_FILLS: u64 = 32;
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+pub const ADL_QUEUE_LEN: usize = 8;
+pub const LEADERBOARD_LEN: usize = 32;
+pub const COPY_FEED_LEN: usize = 16;
+pub const EMISSIONS_EPOCHS: usize = 30;
+pub const SIZE_BUCKETS: usize = 20;
+pub const IMPACT_BUCKETS: usize = 16;
+pub const OBSERVATION_LEVELS: usize = 5;
+pub const MAX_BOND_LOCK_EPOCHS: u64 = 52;
+pub const MAX_EMISSIONS_BOOST_BPS: u16 = 25_000;
+pub const LOSS_INDEX_SCALE: u128 = 1_000_000_000_000;
+pub const ACCRUAL_INDEX_SCALE: u128 = 1_000_000_000_000;
+pub const LIQUIDATION_INSURANCE_SHARE_BPS: u64 = 5_000; // of the liquidation fee, rest to the liquidator
+
+/// Market time is the slot.
+pub const TIME_BASE_SLOT: u8 = 0;
+/// Market time is the cluster's unix timestamp, in seconds.
+pub const TIME_BASE_UNIX: u8 = 1;
+
+pub const STP_CANCEL_NEWEST: u8 = 0;
+pub const STP_CANCEL_OLDEST: u8 = 1;
+pub const STP_DECREMENT_BOTH: u8 = 2;
+
+/// Orders match as they cross.
+pub const SESSION_CONTINUOUS: u8 = 0;
+/// Orders accumulate for a single-price uncross when continuous trading opens.
+pub const SESSION_OPENING_AUCTION: u8 = 1;
+/// Orders accumulate for a single-price uncross at the close.
+pub const SESSION_CLOSING_AUCTION: u8 = 2;
+/// Outside trading hours.
+pub const SESSION_CLOSED: u8 = 3;
+/// Halted by the circuit breaker; orders can only be cancelled.
+pub const SESSION_HALTED: u8 = 4;
+/// Orders accumulate for the single-price uncross that ends a halt.
+pub const SESSION_REOPENING_AUCTION: u8 = 5;
+
+/// `Uncross::stage`: resting orders are being tallied into the auction's
+/// levels, or passed again to fill once its clearing price is known.
+pub const UNCROSS_TALLY: u8 = 0;
+pub const UNCROSS_FILL: u8 = 1;
+
+/// Makers at a price fill in queue order.
+pub const MATCHING_PRICE_TIME: u8 = 0;
+/// Makers at a price fill in proportion to their displayed size.
+pub const MATCHING_PRO_RATA: u8 = 1;
+
+/// Order may only rest on the book, never take liquidity.
+pub const TRADE_FLAG_POST_ONLY: u8 = 1 << 0;
+/// Order may only shrink the agent's token B inventory.
+pub const TRADE_FLAG_REDUCE_ONLY: u8 = 1 << 1;
+pub const TRADE_FLAGS_ALL: u8 = TRADE_FLAG_POST_ONLY | TRADE_FLAG_REDUCE_ONLY;
+
+/// Length of a trade's tag, free for the placer to label it with, e.g. a
+/// strategy or episode id.
+pub const TRADE_TAG_LEN: usize = 16;
+
+/// Length of a compact action: a header byte holding the side in bit 0, the
+/// TRADE_FLAG_* bits in bits 1-2 and the size bucket in bits 3-7, then the
+/// signed price offset in ticks, little-endian.
+pub const ACTION_LEN: usize = 3;
+
+/// Sets the market's realized volatility to `value` bps, as if its fills
+/// had moved that much; later fills decay it as usual.
+pub const CURRICULUM_VOLATILITY_SHOCK: u8 = 0;
+/// Sets the market's base taker fee to `value` bps.
+pub const CURRICULUM_TAKER_FEE: u8 = 1;
+/// Sets the market's base maker fee to `value` bps, negative = rebate.
+pub const CURRICULUM_MAKER_FEE: u8 = 2;
+/// Takes `value` bps of its remaining size off every resting order.
+pub const CURRICULUM_WITHDRAW_LIQUIDITY: u8 = 3;
+
+/// Session key may place and modify trades.
+pub const SESSION_SCOPE_PLACE: u8 = 1 << 0;
+/// Session key may cancel trades.
+pub const SESSION_SCOPE_CANCEL: u8 = 1 << 1;
+pub const SESSION_SCOPES_ALL: u8 = SESSION_SCOPE_PLACE | SESSION_SCOPE_CANCEL;
+/// Session keys an agent can have delegated to at once.
+pub const MAX_DELEGATES: usize = 4;
+
+/// Trades can be placed on and matched by the order book.
+pub const FEATURE_ORDER_BOOK: u64 = 1 << 0;
+/// Perp positions can be opened and resized.
+pub const FEATURE_MARGIN: u64 = 1 << 1;
+/// Margin prices must come from an external feed, never the market's own TWAP.
+pub const FEATURE_ORACLE_REQUIRED: u64 = 1 << 2;
+/// Instructions acting for many agents at once are allowed.
+pub const FEATURE_BATCHING: u64 = 1 << 3;
+/// Markets may designate an adversary agent placing scripted flow.
+pub const FEATURE_ADVERSARY: u64 = 1 << 4;
+/// Market authorities may reset agents between episodes, minting and burning
+/// vault tokens to restore their starting capital.
+pub const FEATURE_EPISODE_RESET: u64 = 1 << 5;
+
+impl Market {
+    /// Hands out the next order sequence number; lower numbers have queue priority.
+    pub fn next_sequence(&mut self) -> Result<u64> {
+        let sequence = self.sequence;
+        self.sequence = sequence
+            .checked_add(1)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        crate::invariants::strict_check!(self.sequence > sequence, "order sequences only increase");
+        Ok(sequence)
+    }
+
+    /// Recounts `trade` in the pro-rata levels with `displayed` shown at its
+    /// current price, 0 = off the book. A market matching by price and time
+    /// keeps no levels.
+    pub fn rebook(&mut self, trade: &mut Trade, displayed: u64) -> Result<()> {
+        if trade.booked_level > 0 {
+            let level = &mut self.levels[trade.booked_level as usize - 1];
+            level.orders = level.orders.saturating_sub(1);
+            level.displayed = level.displayed.saturating_sub(trade.booked_displayed);
+            trade.booked_level = 0;
+            trade.booked_displayed = 0;
+        }
+        if displayed == 0 || self.matching_policy != MATCHING_PRO_RATA {
+            return Ok(());
+        }
+        let (trade_type, price) = (trade.trade_type, trade.price());
+        let index = match self
+            .levels
+            .iter()
+            .position(|level| level.orders > 0 && level.trade_type == trade_type && level.price == price)
+        {
+            Some(index) => index,
+            None => {
+                let index = self
+                    .levels
+                    .iter()
+                    .position(|level| level.orders == 0)
+                    .ok_or(crate::errors::ErrorCode::BookLevelsFull)?;
+                self.levels[index] = BookLevel {
+                    trade_type,
+                    price,
+                    orders: 0,
+                    displayed: 0,
+                };
+                index
+            }
+        };
+        let level = &mut self.levels[index];
+        level.orders = level.orders.checked_add(1).ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        level.displayed = level
+            .displayed
+            .checked_add(displayed)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        trade.booked_level = index as u8 + 1;
+        trade.booked_displayed = displayed;
+        Ok(())
+    }
+
+    /// Joins the pro-rata round for `taker` at `maker`'s level, starting a
+    /// new one unless the last fill left the level and taker as they are.
+    pub fn pro_rata_round_for(&mut self, maker: &Trade, taker: &Account<Trade>) -> Result<ProRataRound> {
+        if maker.booked_level == 0 {
+            return err!(crate::errors::ErrorCode::NotAtMakerLevel);
+        }
+        let index = maker.booked_level - 1;
+        let level = self.levels[index as usize];
+        let round = &mut self.pro_rata_round;
+        let current = round.orders_left > 0
+            && round.taker == taker.key()
+            && round.level == index
+            && round.level_orders == level.orders
+            && round.level_displayed == level.displayed
+            && round.taker_displayed == taker.displayed();
+        if !current {
+            *round = ProRataRound {
+                round: round.round.checked_add(1).ok_or(crate::errors::ErrorCode::MathOverflow)?,
+                taker: taker.key(),
+                level: index,
+                taker_size: taker.displayed(),
+                level_size: level.displayed,
+                orders_left: level.orders,
+                displayed_left: level.displayed,
+                level_orders: level.orders,
+                level_displayed: level.displayed,
+                taker_displayed: taker.displayed(),
+            };
+        }
+        Ok(*round)
+    }
+
+    /// Closes `maker`'s turn in the pro-rata round once its fill, which it
+    /// joined showing `displayed`, is booked.
+    pub fn end_pro_rata_turn(&mut self, maker: &mut Trade, displayed: u64, taker: &Trade) {
+        let round = &mut self.pro_rata_round;
+        maker.pro_rata_round = round.round;
+        round.orders_left = round.orders_left.saturating_sub(1);
+        round.displayed_left = round.displayed_left.saturating_sub(displayed);
+        let level = self.levels[round.level as usize];
+        round.level_orders = level.orders;
+        round.level_displayed = level.displayed;
+        round.taker_displayed = taker.displayed();
+    }
+
+    /// Numbers the next order book update, from 1.
+    pub fn next_book_sequence(&mut self) -> Result<u64> {
+        self.book_sequence = self
+            .book_sequence
+            .checked_add(1)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(self.book_sequence)
+    }
+
+    /// The current time in the market's units, see `clock`.
+    pub fn now(&self) -> Result<u64> {
+        crate::clock::now(self.time_base)
+    }
+
+    /// Epoch that market time `now` falls in.
+    pub fn current_epoch(&self, now: u64) -> u64 {
+        now / self.epoch_slots.max(1)
+    }
+
+    /// Length of the market's trading session in each epoch, 0 when it
+    /// never closes.
+    pub fn session_length(&self) -> u64 {
+        let epoch = self.epoch_slots.max(1);
+        (self.trading_close + epoch - self.trading_open) % epoch
+    }
+
+    /// SESSION_* phase market time `now` falls in, and when the session it
+    /// belongs to opened; the most recent one while closed. A halt and its
+    /// reopening auction override the schedule until the auction uncrosses,
+    /// and start when the halt ends.
+    pub fn session(&self, now: u64) -> (u8, u64) {
+        if self.halted_until != 0 {
+            if now < self.halted_until {
+                return (SESSION_HALTED, self.halted_until);
+            }
+            if self.last_uncross < self.reopens_at() {
+                return (SESSION_REOPENING_AUCTION, self.halted_until);
+            }
+        }
+        let length = self.session_length();
+        if length == 0 {
+            return (SESSION_CONTINUOUS, 0);
+        }
+        let epoch = self.epoch_slots.max(1);
+        let into = (now % epoch + epoch - self.trading_open) % epoch;
+        let phase = if into >= length {
+            SESSION_CLOSED
+        } else if into < self.auction_slots {
+            SESSION_OPENING_AUCTION
+        } else if into >= length - self.auction_slots {
+            SESSION_CLOSING_AUCTION
+        } else {
+            SESSION_CONTINUOUS
+        };
+        (phase, now.saturating_sub(into))
+    }
+
+    /// When the reopening auction after the last halt ends.
+    pub fn reopens_at(&self) -> u64 {
+        self.halted_until.saturating_add(self.reopen_auction_slots)
+    }
+
+    /// Whether market time `now` falls within the market's trading hours
+    /// and outside any halt.
+    pub fn is_open(&self, now: u64) -> bool {
+        !matches!(self.session(now).0, SESSION_CLOSED | SESSION_HALTED)
+    }
+
+    /// Rejects placing orders outside trading hours or during a halt.
+    pub fn ensure_open(&self) -> Result<()> {
+        match self.session(self.now()?).0 {
+            SESSION_CLOSED => err!(crate::errors::ErrorCode::MarketClosed),
+            SESSION_HALTED => err!(crate::errors::ErrorCode::MarketHalted),
+            _ => Ok(()),
+        }
+    }
+
+    /// Rejects an order larger than the market's cap.
+    pub fn check_order_size(&self, size: u64) -> Result<()> {
+        if self.max_order_size != 0 && size > self.max_order_size {
+            return err!(crate::errors::ErrorCode::OrderTooLarge);
+        }
+        Ok(())
+    }
+
+    /// The order a compact action encodes: `action_lot << bucket` of token B
+    /// at the last fill price moved by the offset in `action_tick`s. Bids
+    /// round their token A down and asks up, so neither trades worse than the
+    /// encoded price.
+    pub fn decode_action(&self, action: [u8; ACTION_LEN]) -> Result<OrderParams> {
+        if self.action_lot == 0 {
+            return err!(crate::errors::ErrorCode::ActionsDisabled);
+        }
+        if self.last_price == 0 {
+            return err!(crate::errors::ErrorCode::ActionPriceUnavailable);
+        }
+        let header = action[0];
+        let trade_type = header & 1;
+        let offset = i16::from_le_bytes([action[1], action[2]]) as i128;
+        let size = (self.action_lot as u128) << (header >> 3);
+        let price = self.last_price as i128 + offset * self.action_tick as i128;
+        if size > i64::MAX as u128 || price <= 0 || price > u64::MAX as i128 {
+            return err!(crate::errors::ErrorCode::InvalidAction);
+        }
+        let (size, price) = (size as u64, price as u64);
+        let quote = self.base_value(size as i64, price, trade_type != 0)? as u64;
+        if quote == 0 {
+            return err!(crate::errors::ErrorCode::InvalidAction);
+        }
+        let (amount_in, amount_out) = if trade_type == 0 { (quote, size) } else { (size, quote) };
+        let params = OrderParams {
+            trade_type,
+            amount_in,
+            amount_out,
+            display_size: 0,
+            flags: (header >> 1) & TRADE_FLAGS_ALL,
+            tag: [0; TRADE_TAG_LEN],
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Halts the market if a fill moved its price by `move_bps`, more than
+    /// its circuit breaker allows. Returns whether it did.
+    pub fn trip_circuit_breaker(&mut self, move_bps: u64) -> Result<bool> {
+        if self.circuit_breaker_bps == 0 || move_bps <= self.circuit_breaker_bps as u64 {
+            return Ok(false);
+        }
+        // Never 0, which would read as never halted
+        self.halted_until = self.now()?.saturating_add(self.halt_slots).max(1);
+        Ok(true)
+    }
+
+    /// Takes crank number `crank_sequence`, which must be the next one, so
+    /// when several keepers race to crank the same state only the first
+    /// lands and the rest fail instead of acting twice.
+    pub fn take_crank(&mut self, crank_sequence: u64) -> Result<()> {
+        if crank_sequence != self.crank_sequence {
+            return err!(crate::errors::ErrorCode::CrankOutOfSequence);
+        }
+        self.crank_sequence += 1;
+        Ok(())
+    }
+
+    /// Rejects matching orders outside continuous trading: while closed,
+    /// during a call auction, or before the opening one has uncrossed.
+    pub fn ensure_continuous(&self) -> Result<()> {
+        let (phase, start) = self.session(self.now()?);
+        match phase {
+            SESSION_CLOSED => err!(crate::errors::ErrorCode::MarketClosed),
+            SESSION_HALTED => err!(crate::errors::ErrorCode::MarketHalted),
+            SESSION_CONTINUOUS if self.auction_slots == 0 || self.last_uncross >= start + self.auction_slots => Ok(()),
+            _ => err!(crate::errors::ErrorCode::AuctionInProgress),
+        }
+    }
+
+    /// Rejects changing the book while an auction is part way through
+    /// uncrossing, so the orders it tallied stay as they were.
+    pub fn ensure_not_uncrossing(&self) -> Result<()> {
+        if self.uncross.end != 0 {
+            return err!(crate::errors::ErrorCode::AuctionUncrossing);
+        }
+        Ok(())
+    }
+
+    /// The call auction due to uncross at market time `now`, if any, as its
+    /// SESSION_* phase and the time it ended.
+    pub fn pending_auction(&self, now: u64) -> Option<(u8, u64)> {
+        let (phase, start) = self.session(now);
+        let (auction, end) = match phase {
+            SESSION_REOPENING_AUCTION if now >= self.reopens_at() => (phase, self.reopens_at()),
+            SESSION_CONTINUOUS if self.auction_slots > 0 => (SESSION_OPENING_AUCTION, start + self.auction_slots),
+            SESSION_CLOSED if self.auction_slots > 0 => (SESSION_CLOSING_AUCTION, start + self.session_length()),
+            _ => return None,
+        };
+        (self.last_uncross < end).then_some((auction, end))
+    }
+
+    /// Rejects auctions that wouldn't leave both of their own lengths inside
+    /// the session.
+    pub fn validate_auctions(&self) -> Result<()> {
+        if self.auction_slots != 0 && self.auction_slots.saturating_mul(2) > self.session_length() {
+            return err!(crate::errors::ErrorCode::InvalidAuctionSlots);
+        }
+        Ok(())
+    }
+
+    /// Fee rates for an agent with the given rolling volume: the highest tier
+    /// it qualifies for, or the base schedule when it reaches none.
+    pub fn fee_rates(&self, volume: u64) -> (i16, u16) {
+        self.fee_tiers[..self.fee_tier_count as usize]
+            .iter()
+            .filter(|tier| volume >= tier.min_volume)
+            .max_by_key(|tier| tier.min_volume)
+            .map_or((self.maker_fee_bps, self.taker_fee_bps), |tier| {
+                (tier.maker_fee_bps, tier.taker_fee_bps)
+            })
+    }
+
+    /// Surcharge on the next order placed: the highest tier the book's
+    /// open-order count reaches, or nothing while it reaches none.
+    pub fn congestion_surcharge(&self) -> u64 {
+        self.congestion_tiers[..self.congestion_tier_count as usize]
+            .iter()
+            .filter(|tier| self.open_orders >= tier.min_open_orders)
+            .max_by_key(|tier| tier.min_open_orders)
+            .map_or(0, |tier| tier.surcharge)
+    }
+
+    /// Taker rate `taker_fee_bps` scaled up by recent volatility and clamped
+    /// to the market's bounds; unchanged while volatility fees are off.
+    pub fn volatility_taker_bps(&self, taker_fee_bps: u16) -> u16 {
+        if self.volatility_fee_coefficient == 0 {
+            return taker_fee_bps;
+        }
+        // Rounds up with the rest of the taker fee
+        let extra = crate::math::mul_div_ceil(
+            self.volatility_bps as u64,
+            self.volatility_fee_coefficient as u64,
+            BPS_DENOMINATOR,
+        )
+        .unwrap_or(u64::MAX);
+        (taker_fee_bps as u64).saturating_add(extra)
+            .clamp(self.min_taker_fee_bps as u64, self.max_taker_fee_bps as u64) as u16
+    }
+
+    /// Base-rate taker fee on `amount`, for flows that have no maker side.
+    pub fn taker_fee(&self, amount: u64) -> Result<u64> {
+        mul_bps_ceil(amount, self.volatility_taker_bps(self.taker_fee_bps) as u64)
+    }
+
+    /// Adds `fee` to the accumulator for `mint`.
+    pub fn accrue_fee(&mut self, mint: Pubkey, fee: u64) -> Result<()> {
+        let accrued = if mint == self.token_a {
+            &mut self.fees_accrued
+        } else {
+            &mut self.fees_accrued_b
+        };
+        *accrued = accrued
+            .checked_add(fee)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Rejects schedules where a rebate at one tier could exceed the taker fee
+    /// charged at another, since rebates are paid out of taker fees. With
+    /// volatility fees on, each taker rate counts at its calmest-market value.
+    pub fn validate_fees(&self) -> Result<()> {
+        if self.volatility_fee_coefficient != 0
+            && (self.min_taker_fee_bps > self.max_taker_fee_bps
+                || self.max_taker_fee_bps as u64 > BPS_DENOMINATOR)
+        {
+            return err!(crate::errors::ErrorCode::InvalidFeeSchedule);
+        }
+        let calm_taker_bps = |taker_fee_bps: u16| {
+            if self.volatility_fee_coefficient == 0 {
+                taker_fee_bps
+            } else {
+                taker_fee_bps.clamp(self.min_taker_fee_bps, self.max_taker_fee_bps)
+            }
+        };
+        let rates = std::iter::once((self.maker_fee_bps, self.taker_fee_bps)).chain(
+            self.fee_tiers[..self.fee_tier_count as usize]
+                .iter()
+                .map(|tier| (tier.maker_fee_bps, tier.taker_fee_bps)),
+        );
+        let mut max_rebate = 0i64;
+        let mut min_taker = BPS_DENOMINATOR as i64;
+        for (maker_fee_bps, taker_fee_bps) in rates {
+            if taker_fee_bps as u64 > BPS_DENOMINATOR || maker_fee_bps as i64 > BPS_DENOMINATOR as i64 {
+                return err!(crate::errors::ErrorCode::InvalidFeeSchedule);
+            }
+            max_rebate = max_rebate.max(-(maker_fee_bps as i64));
+            min_taker = min_taker.min(calm_taker_bps(taker_fee_bps) as i64);
+        }
+        if max_rebate > min_taker {
+            return err!(crate::errors::ErrorCode::InvalidFeeSchedule);
+        }
+        Ok(())
+    }
+
+    /// Maker and taker fees on a fill worth `quote_amount` of token A, each side
+    /// priced at its own volume tier. Taker fees round up and rebates round
+    /// down, so the protocol never pays out more than it collects.
+    pub fn fill_fees(
+        &self,
+        quote_amount: u64,
+        maker_volume: u64,
+        taker_volume: u64,
+    ) -> Result<(i64, u64)> {
+        let (maker_fee_bps, _) = self.fee_rates(maker_volume);
+        let (_, taker_fee_bps) = self.fee_rates(taker_volume);
+        let taker_fee = mul_bps_ceil(quote_amount, self.volatility_taker_bps(taker_fee_bps) as u64)?;
+        let maker_bps = maker_fee_bps.unsigned_abs() as u64;
+        let maker_fee = if maker_fee_bps >= 0 {
+            mul_bps_ceil(quote_amount, maker_bps)? as i64
+        } else {
+            // Rebates are paid out, so round down
+            -(crate::math::mul_div_floor(quote_amount, maker_bps, BPS_DENOMINATOR)? as i64)
+        };
+        Ok((maker_fee, taker_fee))
+    }
+
+    /// Token A value of `base` token B at `price`, rounded towards negative
+    /// infinity unless `round_up`.
+    pub fn base_value(&self, base: i64, price: u64, round_up: bool) -> Result<i64> {
+        let numerator = base as i128 * price as i128 * 10i128.pow(self.decimals_a as u32);
+        let denominator = PRICE_SCALE as i128 * 10i128.pow(self.decimals_b as u32);
+        let value = if round_up {
+            -(-numerator).div_euclid(denominator)
+        } else {
+            numerator.div_euclid(denominator)
+        };
+        i64::try_from(value).map_err(|_| crate::errors::ErrorCode::MathOverflow.into())
+    }
+
+    /// Token B worth `value` token A at `price`, rounded down unless
+    /// `round_up`.
+    pub fn base_amount(&self, value: u64, price: u64, round_up: bool) -> Result<u64> {
+        let numerator = value as u128 * PRICE_SCALE as u128 * 10u128.pow(self.decimals_b as u32);
+        let denominator = price as u128 * 10u128.pow(self.decimals_a as u32);
+        let amount = if round_up {
+            numerator.div_ceil(denominator)
+        } else {
+            numerator / denominator
+        };
+        u64::try_from(amount).map_err(|_| crate::errors::ErrorCode::MathOverflow.into())
+    }
+
+    /// Margin needed at `margin_bps` for `base` marked at `price`, rounded up.
+    pub fn margin_requirement(&self, base: i64, price: u64, margin_bps: u16) -> Result<u64> {
+        let notional = self.base_value(base.saturating_abs(), price, true)?;
+        mul_bps_ceil(notional as u64, margin_bps as u64)
+    }
+
+    pub fn loss_index(&self, base: i64) -> u128 {
+        if base < 0 {
+            self.short_loss_index
+        } else {
+            self.long_loss_index
+        }
+    }
+
+    pub fn funding_index(&self, base: i64) -> i128 {
+        if base < 0 {
+            self.short_funding_index
+        } else {
+            self.long_funding_index
+        }
+    }
+
+    /// Brings the funding and borrow indices up to the current market time.
+    /// Funding runs at `funding_per_epoch` and only while both sides are
+    /// open; payers' share rounds up and receivers' down, so no more is
+    /// credited than is charged. Positions catch up when next touched, so
+    /// the cost doesn't grow with their number.
+    pub fn accrue(&mut self) -> Result<()> {
+        let now = self.now()?;
+        let elapsed = now.saturating_sub(self.accrued_at);
+        self.accrued_at = now;
+        if elapsed == 0 {
+            return Ok(());
+        }
+
+        let (long, short) = (self.open_interest_long as i128, self.open_interest_short as i128);
+        if self.funding_per_epoch != 0 && long > 0 && short > 0 {
+            let (payers, receivers, payer_index, receiver_index) = if self.funding_per_epoch > 0 {
+                (long, short, &mut self.long_funding_index, &mut self.short_funding_index)
+            } else {
+                (short, long, &mut self.short_funding_index, &mut self.long_funding_index)
+            };
+            let funding = self
+                .funding_per_epoch
+                .unsigned_abs()
+                .checked_mul(elapsed as u128)
+                .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+            let epoch = self.epoch_slots.max(1) as u128;
+            let paid = funding.div_ceil(epoch);
+            let received = funding
+                .checked_mul(payers as u128)
+                .ok_or(crate::errors::ErrorCode::MathOverflow)?
+                / (epoch * receivers as u128);
+            let to_index = |amount: u128| {
+                i128::try_from(amount).map_err(|_| crate::errors::ErrorCode::MathOverflow)
+            };
+            *payer_index = payer_index
+                .checked_add(to_index(paid)?)
+                .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+            *receiver_index = receiver_index
+                .checked_sub(to_index(received)?)
+                .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        }
+
+        if self.borrow_rate_bps != 0 {
+            let slots = crate::clock::to_slots(self.time_base, elapsed) as u128;
+            let interest = (ACCRUAL_INDEX_SCALE * self.borrow_rate_bps as u128 * slots)
+                .div_ceil(BPS_DENOMINATOR as u128 * crate::fees::SLOTS_PER_YEAR as u128);
+            self.borrow_index = self
+                .borrow_index
+                .checked_add(interest)
+                .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the funding rate from the premium of the last-trade TWAP over
+    /// `price`, capped at `max_funding_bps` per epoch. Longs pay while the
+    /// market trades above the mark, shorts while it trades below. Accrue
+    /// first so the old rate covers the time before.
+    pub fn reprice_funding(&mut self, price: u64) -> Result<()> {
+        if self.max_funding_bps == 0 || self.twap_price == 0 || price == 0 {
+            self.funding_per_epoch = 0;
+            return Ok(());
+        }
+        let cap = self.max_funding_bps as i128;
+        let premium_bps = ((self.twap_price as i128 - price as i128) * BPS_DENOMINATOR as i128
+            / price as i128)
+            .clamp(-cap, cap);
+        // Token A per base unit at `price`, ACCRUAL_INDEX_SCALE
+        let unit_value = (price as u128)
+            .checked_mul(10u128.pow(self.decimals_a as u32) * ACCRUAL_INDEX_SCALE)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?
+            / (PRICE_SCALE as u128 * 10u128.pow(self.decimals_b as u32));
+        let unit_value =
+            i128::try_from(unit_value).map_err(|_| crate::errors::ErrorCode::MathOverflow)?;
+        self.funding_per_epoch = unit_value
+            .checked_mul(premium_bps)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?
+            / BPS_DENOMINATOR as i128;
+        Ok(())
+    }
+
+    /// Moves a position's contribution to open interest from `old_base` to `new_base`.
+    pub fn update_open_interest(&mut self, old_base: i64, new_base: i64) -> Result<()> {
+        let side = |base: i64| (base.max(0) as u64, base.min(0).unsigned_abs());
+        let (old_long, old_short) = side(old_base);
+        let (new_long, new_short) = side(new_base);
+        self.open_interest_long = self
+            .open_interest_long
+            .checked_sub(old_long)
+            .and_then(|long| long.checked_add(new_long))
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        self.open_interest_short = self
+            .open_interest_short
+            .checked_sub(old_short)
+            .and_then(|short| short.checked_add(new_short))
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Long minus short open interest.
+    pub fn skew(&self) -> i128 {
+        self.open_interest_long as i128 - self.open_interest_short as i128
+    }
+
+    /// Oracle `price` adjusted for a fill moving skew by `base_delta`: trades
+    /// that add to the crowded side pay a premium proportional to the average
+    /// skew across the fill, trades that reduce it get a discount.
+    pub fn skewed_price(&self, price: u64, base_delta: i64) -> Result<u64> {
+        if self.skew_scale == 0 {
+            return Ok(price);
+        }
+        let skew = self.skew();
+        let average_skew = skew + base_delta as i128 / 2;
+        let scale = self.skew_scale as i128;
+        let adjusted = (price as i128 * (scale + average_skew)).div_euclid(scale).max(0);
+        u64::try_from(adjusted).map_err(|_| crate::errors::ErrorCode::MathOverflow.into())
+    }
+
+    /// Rejects open interest above a side's cap after that side grew.
+    pub fn check_open_interest(&self, old_long: u64, old_short: u64) -> Result<()> {
+        let over = |open_interest: u64, old: u64, cap: u64| {
+            cap != 0 && open_interest > cap && open_interest > old
+        };
+        if over(self.open_interest_long, old_long, self.max_open_interest_long)
+            || over(self.open_interest_short, old_short, self.max_open_interest_short)
+        {
+            return err!(crate::errors::ErrorCode::OpenInterestCapExceeded);
+        }
+        Ok(())
+    }
+
+    /// Spreads `loss` over the open interest of the side that gained while a
+    /// `bankrupt_base` position lost. Returns what could not be spread.
+    pub fn socialize_loss(&mut self, bankrupt_base: i64, loss: u64) -> Result<u64> {
+        let (open_interest, index) = if bankrupt_base > 0 {
+            (self.open_interest_short, &mut self.short_loss_index)
+        } else if bankrupt_base < 0 {
+            (self.open_interest_long, &mut self.long_loss_index)
+        } else {
+            return Ok(loss);
+        };
+        if open_interest == 0 {
+            return Ok(loss);
+        }
+        let per_unit = (loss as u128 * LOSS_INDEX_SCALE).div_ceil(open_interest as u128);
+        *index = index
+            .checked_add(per_unit)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(0)
+    }
+
+    /// Folds a fill of `quantity` token B for `quote_amount` token A into the
+    /// A book price, PRICE_SCALE token A base units per token B base unit,
+    /// as the price per whole token `last_price` is kept in.
+    pub fn display_price(&self, price: u64) -> Result<u64> {
+        let price = price as u128 * 10u128.pow(self.decimals_b as u32) / 10u128.pow(self.decimals_a as u32);
+        Ok(u64::try_from(price).map_err(|_| crate::errors::ErrorCode::MathOverflow)?)
+    }
+
+    /// Last-trade TWAP and the volatility average. The previous price is
+    /// weighted by how long it stood, capped at one TWAP window. Returns the
+    /// move from the previous price in bps, 0 for the market's first fill.
+    pub fn record_fill_price(&mut self, quote_amount: u64, quantity: u64, slot: u64) -> Result<u64> {
+        let price = quote_amount as u128 * PRICE_SCALE as u128 * 10u128.pow(self.decimals_b as u32)
+            / (quantity as u128 * 10u128.pow(self.decimals_a as u32));
+        let price = u64::try_from(price).map_err(|_| crate::errors::ErrorCode::MathOverflow)?;
+        let mut move_bps = 0;
+        if self.last_price != 0 {
+            move_bps = (price.abs_diff(self.last_price) as u128 * BPS_DENOMINATOR as u128
+                / self.last_price as u128)
+                .min(u32::MAX as u128) as u64;
+            self.volatility_bps = ((self.volatility_bps as u64 * (VOLATILITY_WINDOW_FILLS - 1)
+                + move_bps)
+                / VOLATILITY_WINDOW_FILLS) as u32;
+        }
+        if self.twap_slot == 0 {
+            self.twap_price = price;
+        } else {
+            let weight = slot.saturating_sub(self.twap_slot).min(TWAP_WINDOW_SLOTS) as u128;
+            self.twap_price = ((self.twap_price as u128 * (TWAP_WINDOW_SLOTS as u128 - weight)
+                + self.last_price as u128 * weight)
+                / TWAP_WINDOW_SLOTS as u128) as u64;
+        }
+        self.last_price = price;
+        self.twap_slot = slot;
+        Ok(move_bps)
+    }
+}
+
+impl Snapshot {
+    /// Counts a fill of `quantity` token B that moved the price `move_bps`.
+    pub fn record_fill(&mut self, quantity: u64, move_bps: u64) {
+        let size_bucket = quantity.checked_ilog10().unwrap_or(0) as usize;
+        let impact_bucket = (u64::BITS - move_bps.leading_zeros()) as usize;
+        let sizes = &mut self.size_histogram[size_bucket.min(SIZE_BUCKETS - 1)];
+        *sizes = sizes.saturating_add(1);
+        let impacts = &mut self.impact_histogram[impact_bucket.min(IMPACT_BUCKETS - 1)];
+        *impacts = impacts.saturating_add(1);
+    }
+}
+
+impl CopyFeed {
+    pub fn record(&mut self, fill: CopyFill) -> Result<()> {
+        self.fills[(self.fill_count % COPY_FEED_LEN as u64) as usize] = fill;
+        self.fill_count = self
+            .fill_count
+            .checked_add(1)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Fill number `index`, or `None` once it has been overwritten or before
+    /// it is recorded.
+    pub fn fill(&self, index: u64) -> Option<CopyFill> {
+        if index >= self.fill_count || self.fill_count - index > COPY_FEED_LEN as u64 {
+            return None;
+        }
+        Some(self.fills[(index % COPY_FEED_LEN as u64) as usize])
+    }
+
+    /// Oldest fill still held.
+    pub fn oldest(&self) -> u64 {
+        self.fill_count.saturating_sub(COPY_FEED_LEN as u64)
+    }
+}
+
+impl Guild {
+    /// Shares a deposit worth `value` token A buys when the guild holds `nav`,
+    /// rounded down in the guild's favour.
+    pub fn shares_for(&self, value: u64, nav: u64) -> Result<u64> {
+        if self.total_shares == 0 || nav == 0 {
+            return Ok(value);
+        }
+        Ok(crate::math::mul_div_floor(value, self.total_shares, nav)?)
+    }
+
+    /// Part of `amount` that `shares` are entitled to, rounded down.
+    pub fn share_of(&self, amount: u64, shares: u64) -> u64 {
+        if self.total_shares == 0 {
+            return 0;
+        }
+        crate::math::mul_div_floor(amount, shares.min(self.total_shares), self.total_shares)
+            .unwrap_or(0)
+    }
+}
+
+impl StrategyVault {
+    /// Fixes `epoch`'s settlement at these holdings and share supply unless
+    /// an earlier call in the epoch already has.
+    pub fn fix_settlement(&mut self, epoch: u64, supply: u64, held_a: u64, held_b: u64) {
+        if self.settlement_epoch == epoch {
+            return;
+        }
+        self.settlement_epoch = epoch;
+        self.settlement_supply = supply;
+        self.settlement_a = held_a;
+        self.settlement_b = held_b;
+    }
+
+    /// Token A and token B `shares` redeem for at the settlement, rounded down.
+    pub fn redemption(&self, shares: u64) -> Result<(u64, u64)> {
+        if self.settlement_supply == 0 {
+            return Ok((0, 0));
+        }
+        let shares = shares.min(self.settlement_supply);
+        Ok((
+            crate::math::mul_div_floor(self.settlement_a, shares, self.settlement_supply)?,
+            crate::math::mul_div_floor(self.settlement_b, shares, self.settlement_supply)?,
+        ))
+    }
+}
+
+impl Config {
+    pub fn enabled(&self, feature: u64) -> bool {
+        self.features & feature == feature
+    }
+
+    /// Same rule a market holds its base fees to: a rebate never exceeds the
+    /// taker fee it is paid out of.
+    pub fn validate_fees(&self) -> Result<()> {
+        if self.taker_fee_bps as u64 > BPS_DENOMINATOR
+            || self.maker_fee_bps as i64 > BPS_DENOMINATOR as i64
+            || -(self.maker_fee_bps as i64) > self.taker_fee_bps as i64
+        {
+            return err!(crate::errors::ErrorCode::InvalidFeeSchedule);
+        }
+        Ok(())
+    }
+}
+
+impl GlobalStats {
+    /// Counts one fill of `volume` token A that charged `fees_a` and `fees_b`.
+    pub fn record_fill(&mut self, volume: u64, fees_a: u64, fees_b: u64) -> Result<()> {
+        self.trades = self
+            .trades
+            .checked_add(1)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        // u128 totals of u64 amounts can't overflow in any realistic lifetime
+        self.volume = self.volume.saturating_add(volume as u128);
+        self.fees_a = self.fees_a.saturating_add(fees_a as u128);
+        self.fees_b = self.fees_b.saturating_add(fees_b as u128);
+        Ok(())
+    }
+}
+
+impl Vault {
+    pub fn receive(&mut self, amount: u64) -> Result<()> {
+        self.reserves = self
+            .reserves
+            .checked_add(amount)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn send(&mut self, amount: u64) -> Result<()> {
+        self.reserves = self
+            .reserves
+            .checked_sub(amount)
+            .ok_or(crate::errors::ErrorCode::VaultDeficit)?;
+        Ok(())
+    }
+
+    /// Takes `amount` a mirrored fill pays the vault into its own liquidity.
+    pub fn mirror_in(&mut self, amount: u64) -> Result<()> {
+        self.mirror_liquidity = self
+            .mirror_liquidity
+            .checked_add(amount)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Pays `amount` of a mirrored fill out of the vault's own liquidity.
+    pub fn mirror_out(&mut self, amount: u64) -> Result<()> {
+        self.mirror_liquidity = self
+            .mirror_liquidity
+            .checked_sub(amount)
+            .ok_or(crate::errors::ErrorCode::VaultDeficit)?;
+        Ok(())
+    }
+
+    /// Takes the other side of PnL a perp position realized: its profit is
+    /// paid out of the vault's own liquidity and its loss paid into it.
+    pub fn settle_pnl(&mut self, pnl: i64) -> Result<()> {
+        if pnl > 0 {
+            self.mirror_out(pnl as u64)
+        } else {
+            self.mirror_in(pnl.unsigned_abs())
+        }
+    }
+}
+
+impl Balance {
+    /// Fills in the identity of a balance that may have just been created.
+    pub fn bind(&mut self, agent: Pubkey, vault: Pubkey, bump: u8) {
+        self.agent = agent;
+        self.vault = vault;
+        self.bump = bump;
+    }
+
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        self.amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn debit(&mut self, amount: u64) -> Result<()> {
+        self.amount = self
+            .amount
+            .checked_sub(amount)
+            .ok_or(crate::errors::ErrorCode::InsufficientBalance)?;
+        Ok(())
+    }
+
+    /// Sets `amount` aside for an order taking part in an uncross.
+    pub fn reserve(&mut self, amount: u64) -> Result<()> {
+        self.debit(amount)?;
+        self.reserved = self
+            .reserved
+            .checked_add(amount)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Settles `reserved` set aside by `reserve`, of which the order's fill
+    /// spent `spent`; the rest is the agent's again.
+    pub fn release(&mut self, reserved: u64, spent: u64) -> Result<()> {
+        self.reserved = self
+            .reserved
+            .checked_sub(reserved)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        self.credit(reserved.checked_sub(spent).ok_or(crate::errors::ErrorCode::MathOverflow)?)
+    }
+}
+
+impl OptionContract {
+    /// Amount locked by the writer and the mint it is denominated in.
+    pub fn collateral(&self, market: &Market) -> (Pubkey, u64) {
+        if self.kind == OPTION_CALL {
+            (market.token_b, self.size)
+        } else {
+            (market.token_a, self.strike_amount)
+        }
+    }
+}
+
+impl IndexBasket {
+    pub fn components(&self) -> &[IndexComponent] {
+        &self.components[..self.component_count as usize]
+    }
+
+    /// Component amount backing `basket_amount`; deposits round up and
+    /// redemptions round down so the basket is never under-collateralized.
+    pub fn component_amount(component: &IndexComponent, basket_amount: u64, round_up: bool) -> Result<u64> {
+        let scale = 10u128.pow(INDEX_DECIMALS as u32);
+        let product = basket_amount as u128 * component.units as u128;
+        let amount = if round_up {
+            product.div_ceil(scale)
+        } else {
+            product / scale
+        };
+        u64::try_from(amount).map_err(|_| crate::errors::ErrorCode::MathOverflow.into())
+    }
+}
+
+impl Rfq {
+    /// `maker`'s current quote, if it has quoted.
+    pub fn quote_from(&self, maker: &Pubkey) -> Option<RfqQuote> {
+        self.quotes[..self.quote_count as usize]
+            .iter()
+            .copied()
+            .find(|quote| quote.maker == *maker)
+    }
+}
+
+impl Agent {
+    /// Hands the agent to `new_owner`, ending every session the old owner
+    /// started.
+    pub fn set_owner(&mut self, new_owner: Pubkey) {
+        self.owner = new_owner;
+        self.pending_owner = Pubkey::default();
+        self.end_sessions();
+    }
+
+    /// Revokes every delegate at once.
+    pub fn end_sessions(&mut self) {
+        self.delegates = [Delegate::default(); MAX_DELEGATES];
+    }
+
+    /// Delegates to `session_key`, replacing its earlier session and
+    /// allowance if it had one, else taking a free or expired slot as of
+    /// `slot`.
+    pub fn start_session(&mut self, session_key: Pubkey, expiry_slot: u64, scope: u8, slot: u64) -> Result<()> {
+        let index = match self.delegates.iter().position(|delegate| delegate.key == session_key) {
+            Some(index) => index,
+            None => self
+                .delegates
+                .iter()
+                .position(|delegate| delegate.key == Pubkey::default() || slot >= delegate.expiry_slot)
+                .ok_or(crate::errors::ErrorCode::DelegatesFull)?,
+        };
+        self.delegates[index] = Delegate { key: session_key, expiry_slot, scope, ..Delegate::default() };
+        Ok(())
+    }
+
+    /// The session `key` was delegated, if any.
+    pub fn delegate_mut(&mut self, key: &Pubkey) -> Option<&mut Delegate> {
+        match *key == Pubkey::default() {
+            true => None,
+            false => self.delegates.iter_mut().find(|delegate| delegate.key == *key),
+        }
+    }
+
+    /// Counts a trade account opened for the agent.
+    pub fn open_trade(&mut self) -> Result<()> {
+        self.open_trades = self
+            .open_trades
+            .checked_add(1)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Counts one of the agent's trade accounts closed.
+    pub fn close_trade(&mut self) {
+        self.open_trades = self.open_trades.saturating_sub(1);
+    }
+
+    /// Counts `amount` that `signer` committed to an order of `trade_type`
+    /// in `epoch` against its session's allowance: token A for a bid, token
+    /// B for an ask. The owner is never limited.
+    pub fn spend(&mut self, signer: &Pubkey, trade_type: u8, amount: u64, epoch: u64) -> Result<()> {
+        if *signer == self.owner {
+            return Ok(());
+        }
+        let side = usize::from(trade_type != 0);
+        let delegate = self
+            .delegate_mut(signer)
+            .ok_or(crate::errors::ErrorCode::InvalidSession)?;
+        if delegate.epoch != epoch {
+            delegate.epoch = epoch;
+            delegate.epoch_spent = [0; 2];
+        }
+        let spent = delegate.spent[side].saturating_add(amount);
+        let epoch_spent = delegate.epoch_spent[side].saturating_add(amount);
+        if (delegate.spend_limit[side] != 0 && spent > delegate.spend_limit[side])
+            || (delegate.epoch_cap[side] != 0 && epoch_spent > delegate.epoch_cap[side])
+        {
+            return err!(crate::errors::ErrorCode::SessionSpendExceeded);
+        }
+        delegate.spent[side] = spent;
+        delegate.epoch_spent[side] = epoch_spent;
+        Ok(())
+    }
+
+    /// Whether `signer` may act for the agent within `scope`: the owner
+    /// always can, a session key only until expiry and within its scope.
+    pub fn is_authorized(&self, signer: &Pubkey, scope: u8, slot: u64) -> bool {
+        *signer == self.owner
+            || self.delegates.iter().any(|delegate| {
+                *signer == delegate.key
+                    && delegate.key != Pubkey::default()
+                    && slot < delegate.expiry_slot
+                    && delegate.scope & scope == scope
+            })
+    }
+    /// Moves the volume window forward to `epoch`, clearing epochs that
+    /// dropped out of it.
+    pub fn roll_volume(&mut self, epoch: u64) {
+        if epoch <= self.volume_epoch {
+            return;
+        }
+        let elapsed = (epoch - self.volume_epoch).min(VOLUME_EPOCHS as u64);
+        for offset in 1..=elapsed {
+            self.volume[((self.volume_epoch + offset) % VOLUME_EPOCHS as u64) as usize] = 0;
+        }
+        self.volume_epoch = epoch;
+    }
+
+    /// Volume over the last VOLUME_EPOCHS epochs; call `roll_volume` first.
+    pub fn rolling_volume(&self) -> u64 {
+        self.volume.iter().fold(0u64, |total, v| total.saturating_add(*v))
+    }
+
+    pub fn record_volume(&mut self, amount: u64) {
+        let index = (self.volume_epoch % VOLUME_EPOCHS as u64) as usize;
+        self.volume[index] = self.volume[index].saturating_add(amount);
+    }
+
+    /// Volume in `volume_epoch` alone; call `roll_volume` first.
+    pub fn epoch_volume(&self) -> u64 {
+        self.volume[(self.volume_epoch % VOLUME_EPOCHS as u64) as usize]
+    }
+}
+
+fn mul_bps_ceil(amount: u64, bps: u64) -> Result<u64> {
+    Ok(crate::math::mul_div_ceil(amount, bps, BPS_DENOMINATOR)?)
+}
+
+impl Position {
+    /// Fills in the identity of a position that may have just been created.
+    pub fn bind(&mut self, agent: Pubkey, market: Pubkey, bump: u8) {
+        self.agent = agent;
+        self.market = market;
+        self.bump = bump;
+    }
+
+    /// Accrues the market and charges what this position's indices moved by
+    /// since it was last touched: its side's socialized losses and funding,
+    /// pro-rata to its size, then interest on negative collateral, which goes
+    /// to the market's fees. Charges round up and funding received rounds
+    /// down.
+    pub fn settle(&mut self, market: &mut Market) -> Result<()> {
+        market.accrue()?;
+        let size = self.base.unsigned_abs() as u128;
+
+        let index = market.loss_index(self.base);
+        let losses = (index.saturating_sub(self.loss_index) * size).div_ceil(LOSS_INDEX_SCALE);
+        self.loss_index = index;
+
+        let index = market.funding_index(self.base);
+        let owed = (index - self.funding_index)
+            .checked_mul(size as i128)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        let funding = if owed > 0 {
+            owed.unsigned_abs().div_ceil(ACCRUAL_INDEX_SCALE) as i128
+        } else {
+            -((owed.unsigned_abs() / ACCRUAL_INDEX_SCALE) as i128)
+        };
+        self.funding_index = index;
+
+        let charge = i64::try_from(losses as i128 + funding)
+            .map_err(|_| crate::errors::ErrorCode::MathOverflow)?;
+        self.quote = self
+            .quote
+            .checked_sub(charge)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+
+        if self.quote < 0 {
+            let interest = (self.quote.unsigned_abs() as u128
+                * market.borrow_index.saturating_sub(self.borrow_index))
+            .div_ceil(ACCRUAL_INDEX_SCALE);
+            let interest =
+                i64::try_from(interest).map_err(|_| crate::errors::ErrorCode::MathOverflow)?;
+            self.quote = self
+                .quote
+                .checked_sub(interest)
+                .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+            market.accrue_fee(market.token_a, interest as u64)?;
+        }
+        self.borrow_index = market.borrow_index;
+        Ok(())
+    }
+
+    /// Collateral plus unrealized PnL at `price`, rounded down.
+    pub fn equity(&self, market: &Market, price: u64) -> Result<i64> {
+        self.quote
+            .checked_add(market.base_value(self.base, price, false)?)
+            .ok_or(crate::errors::ErrorCode::MathOverflow.into())
+    }
+
+    /// Collateral plus realized PnL, leaving out what `base` is worth.
+    pub fn collateral(&self) -> Result<i64> {
+        self.quote
+            .checked_add(self.cost_basis)
+            .ok_or(crate::errors::ErrorCode::MathOverflow.into())
+    }
+
+    /// Unrealized PnL at `price`, rounded down.
+    pub fn unrealized_pnl(&self, market: &Market, price: u64) -> Result<i64> {
+        market
+            .base_value(self.base, price, false)?
+            .checked_sub(self.cost_basis)
+            .ok_or(crate::errors::ErrorCode::MathOverflow.into())
+    }
+
+    /// Trades `base_delta` for `cost` token A (negative when the position
+    /// receives token A). The closed part of the position releases its share
+    /// of the cost basis and any part opened adds its share of `cost`.
+    /// Returns the PnL the closed part realized, which the position's
+    /// counterparty owes or is owed. The position must be settled first.
+    pub fn apply_fill(&mut self, market: &mut Market, base_delta: i64, cost: i64) -> Result<i64> {
+        let old = self.base as i128;
+        let new = old + base_delta as i128;
+        let delta = base_delta as i128;
+        let closed = if old.signum() == delta.signum() {
+            0
+        } else {
+            delta.abs().min(old.abs())
+        };
+        let basis = self.cost_basis as i128;
+        let mut new_basis = if closed > 0 { basis - basis * closed / old.abs() } else { basis };
+        let opened = delta.abs() - closed;
+        if opened > 0 {
+            new_basis += cost as i128 * opened / delta.abs();
+        }
+        // What the cost basis takes up of `cost`; the rest was realized
+        let realized = i64::try_from(new_basis - basis - cost as i128)
+            .map_err(|_| crate::errors::ErrorCode::MathOverflow)?;
+        self.cost_basis =
+            i64::try_from(new_basis).map_err(|_| crate::errors::ErrorCode::MathOverflow)?;
+        self.quote = self
+            .quote
+            .checked_sub(cost)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        let new = i64::try_from(new).map_err(|_| crate::errors::ErrorCode::MathOverflow)?;
+        self.set_base(market, new)?;
+        Ok(realized)
+    }
+
+    /// Sets the position's size, keeping the market's open interest and the
+    /// position's index snapshots in step. The position must be settled first.
+    pub fn set_base(&mut self, market: &mut Market, base: i64) -> Result<()> {
+        market.update_open_interest(self.base, base)?;
+        self.base = base;
+        self.loss_index = market.loss_index(base);
+        self.funding_index = market.funding_index(base);
+        Ok(())
+    }
+
+    /// Auto-deleveraging rank: unrealized PnL as a share of equity times
+    /// leverage, in bps. Zero for positions not in profit.
+    pub fn adl_score(&self, market: &Market, price: u64) -> Result<u64> {
+        let pnl = self.unrealized_pnl(market, price)?;
+        let equity = self.equity(market, price)?;
+        if pnl <= 0 || equity <= 0 {
+            return Ok(0);
+        }
+        let notional = market.base_value(self.base.saturating_abs(), price, false)? as u128;
+        let score = pnl as u128 * notional * BPS_DENOMINATOR as u128
+            / (equity as u128 * equity as u128);
+        Ok(u64::try_from(score).unwrap_or(u64::MAX))
+    }
+}
+
+impl AdlQueue {
+    /// Re-ranks `position` with `score` on the side given by `base`, dropping
+    /// it from the queue when it is flat or not in profit.
+    pub fn rank(&mut self, position: Pubkey, base: i64, score: u64) {
+        self.remove(&position);
+        if base == 0 || score == 0 {
+            return;
+        }
+        let side = if base > 0 { &mut self.longs } else { &mut self.shorts };
+        let Some(slot) = side
+            .iter()
+            .position(|entry| entry.position == Pubkey::default() || entry.score < score)
+        else {
+            return;
+        };
+        side[slot..].rotate_right(1);
+        side[slot] = AdlEntry { position, score };
+    }
+
+    pub fn remove(&mut self, position: &Pubkey) {
+        for side in [&mut self.longs, &mut self.shorts] {
+            if let Some(slot) = side.iter().position(|entry| entry.position == *position) {
+                side[slot..].rotate_left(1);
+                side[ADL_QUEUE_LEN - 1] = AdlEntry::default();
+            }
+        }
+    }
+
+    /// Highest-ranked position on the side opposite a `base` position.
+    pub fn top_opposing(&self, base: i64) -> Pubkey {
+        if base > 0 {
+            self.shorts[0].position
+        } else {
+            self.longs[0].position
+        }
+    }
+}
+
+impl RewardRecord {
+    /// Counts a fill of `quantity` token B for `quote_amount` token A at
+    /// market time `now`, in `epoch`, with the `fee` the agent paid on it.
+    /// Only the current epoch's record counts fills.
+    pub fn record_fill(
+        &mut self,
+        epoch: u64,
+        trade_type: u8,
+        quantity: u64,
+        quote_amount: u64,
+        fee: i64,
+        now: u64,
+    ) -> Result<()> {
+        if self.epoch != epoch {
+            return err!(crate::errors::ErrorCode::InvalidEpoch);
+        }
+        self.accrue_inventory(now)?;
+        let overflow = |_| crate::errors::ErrorCode::MathOverflow;
+        let quantity = i64::try_from(quantity).map_err(overflow)?;
+        let quote_amount = i64::try_from(quote_amount).map_err(overflow)?;
+        let (cash, inventory) = if trade_type == 0 { (-quote_amount, quantity) } else { (quote_amount, -quantity) };
+        let add = |total: i64, amount: i64| total.checked_add(amount).ok_or(crate::errors::ErrorCode::MathOverflow);
+        self.cash_a = add(self.cash_a, cash)?;
+        self.inventory_b = add(self.inventory_b, inventory)?;
+        self.fees = add(self.fees, fee)?;
+        self.fills += 1;
+        Ok(())
+    }
+
+    /// Marks the inventory at the market's last fill price and fixes the
+    /// reward's components, averaging the inventory held over the epoch and
+    /// charging the market's inventory penalty. The inventory's value rounds
+    /// down and the penalty up, so neither favours the agent.
+    pub fn settle(&mut self, market: &Market) -> Result<()> {
+        let (price, penalty_bps) = (market.last_price, market.inventory_penalty_bps);
+        let epoch_slots = market.epoch_slots.max(1);
+        self.accrue_inventory(self.epoch.saturating_add(1).saturating_mul(epoch_slots))?;
+        let average_inventory = self.inventory_time.div_ceil(epoch_slots as u128);
+        self.average_inventory =
+            u64::try_from(average_inventory).map_err(|_| crate::errors::ErrorCode::MathOverflow)?;
+        let value = market.base_value(self.inventory_b, price, false)? as i128;
+        let average = i64::try_from(self.average_inventory).map_err(|_| crate::errors::ErrorCode::MathOverflow)?;
+        let held = market.base_value(average, price, true)?;
+        let penalty = (held as u128 * penalty_bps as u128).div_ceil(BPS_DENOMINATOR as u128);
+        let pnl = self.cash_a as i128 + value;
+        let reward = pnl - self.fees as i128 - penalty as i128;
+        let narrow = |value: i128| i64::try_from(value).map_err(|_| crate::errors::ErrorCode::MathOverflow);
+        self.price = price;
+        self.pnl = narrow(pnl)?;
+        self.inventory_penalty = narrow(penalty as i128)?;
+        self.reward = narrow(reward)?;
+        self.settled = true;
+        Ok(())
+    }
+
+    /// Adds the inventory held from `inventory_at` until market time `now`.
+    fn accrue_inventory(&mut self, now: u64) -> Result<()> {
+        let held = self.inventory_b.unsigned_abs() as u128 * now.saturating_sub(self.inventory_at) as u128;
+        self.inventory_time = self
+            .inventory_time
+            .checked_add(held)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        self.inventory_at = self.inventory_at.max(now);
+        Ok(())
+    }
+}
+
+impl Leaderboard {
+    /// Moves `agent` to its place for `volume`. An agent's volume only grows
+    /// within an epoch, so one that falls off never belonged, and one that
+    /// missed an update is placed right by its next.
+    pub fn rank(&mut self, agent: Pubkey, volume: u64) {
+        if let Some(slot) = self.entries.iter().position(|entry| entry.agent == agent) {
+            self.entries[slot..].rotate_left(1);
+            self.entries[LEADERBOARD_LEN - 1] = LeaderboardEntry::default();
+        }
+        if volume == 0 {
+            return;
+        }
+        let Some(slot) = self
+            .entries
+            .iter()
+            .position(|entry| entry.agent == Pubkey::default() || entry.volume < volume)
+        else {
+            return;
+        };
+        self.entries[slot..].rotate_right(1);
+        self.entries[slot] = LeaderboardEntry { agent, volume };
+    }
+}
+
+impl EmissionsSchedule {
+    /// Reward emitted over the part of `epoch` inside the window.
+    pub fn epoch_emission(&self, epoch: u64, epoch_slots: u64) -> Result<u64> {
+        let from = epoch.saturating_mul(epoch_slots).max(self.start);
+        let to = epoch.saturating_add(1).saturating_mul(epoch_slots).min(self.end);
+        to.saturating_sub(from)
+            .checked_mul(self.rate)
+            .ok_or(error!(crate::errors::ErrorCode::MathOverflow))
+    }
+
+    /// Longest a bond can be locked for, in market time.
+    pub fn max_lock(epoch_slots: u64) -> u64 {
+        MAX_BOND_LOCK_EPOCHS * epoch_slots
+    }
+
+    /// Pays up to `amount_a` and `amount_b` market fees to the bonds, pro
+    /// rata to their amount, returning what was paid. Rounding remainders are
+    /// never paid, so they stay with the market's fees.
+    pub fn distribute_fees(&mut self, amount_a: u64, amount_b: u64) -> Result<(u64, u64)> {
+        if self.bonded == 0 {
+            return err!(crate::errors::ErrorCode::NoBondsToPay);
+        }
+        let bonded = self.bonded as u128;
+        let pay = |index: &mut u128, amount: u64| {
+            let delta = amount as u128 * ACCRUAL_INDEX_SCALE / bonded;
+            *index += delta;
+            (delta * bonded / ACCRUAL_INDEX_SCALE) as u64
+        };
+        Ok((pay(&mut self.fee_index_a, amount_a), pay(&mut self.fee_index_b, amount_b)))
+    }
+
+    /// Volume counted for an agent's fill of `volume` at market time `now`:
+    /// boosted by the agent's bond if it holds at least `min_bond`, from 1x
+    /// with no lock time left up to `max_boost_bps` with the longest lock.
+    pub fn boosted(&self, volume: u64, bond: Option<&Bond>, now: u64, epoch_slots: u64) -> Result<u64> {
+        let Some(bond) = bond.filter(|bond| bond.amount >= self.min_bond.max(1)) else {
+            return Ok(volume);
+        };
+        let max_lock = Self::max_lock(epoch_slots);
+        let remaining = bond.unlock.saturating_sub(now).min(max_lock);
+        let extra = (self.max_boost_bps as u64).saturating_sub(BPS_DENOMINATOR);
+        let boost = BPS_DENOMINATOR + crate::math::mul_div_floor(extra, remaining, max_lock)?;
+        Ok(crate::math::mul_div_floor(volume, boost, BPS_DENOMINATOR)?)
+    }
+
+    /// Moves `account`'s share of an epoch before `epoch` into its unclaimed
+    /// rewards. Shares round down, leaving the dust in the reward vault.
+    pub fn settle(&self, account: &mut EmissionsAccount, epoch: u64, epoch_slots: u64) -> Result<()> {
+        if account.epoch >= epoch {
+            return Ok(());
+        }
+        let totals = self.epochs[(account.epoch % EMISSIONS_EPOCHS as u64) as usize];
+        if totals.epoch == account.epoch {
+            let emission = self.epoch_emission(account.epoch, epoch_slots)?;
+            let makers = crate::math::mul_div_floor(emission, self.maker_share_bps as u64, BPS_DENOMINATOR)?;
+            let mut reward = 0;
+            for (emission, volume, total) in [
+                (makers, account.maker_volume, totals.maker_volume),
+                (emission - makers, account.taker_volume, totals.taker_volume),
+            ] {
+                if volume > 0 {
+                    reward += crate::math::mul_div_floor(emission, volume, total)?;
+                }
+            }
+            account.unclaimed = account
+                .unclaimed
+                .checked_add(reward)
+                .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        }
+        account.epoch = epoch;
+        account.maker_volume = 0;
+        account.taker_volume = 0;
+        Ok(())
+    }
+
+    /// Counts a fill's maker and taker volume in `epoch` for `account`.
+    pub fn record(
+        &mut self,
+        account: &mut EmissionsAccount,
+        epoch: u64,
+        epoch_slots: u64,
+        maker_volume: u64,
+        taker_volume: u64,
+    ) -> Result<()> {
+        self.settle(account, epoch, epoch_slots)?;
+        let totals = &mut self.epochs[(epoch % EMISSIONS_EPOCHS as u64) as usize];
+        if totals.epoch != epoch {
+            *totals = EmissionsEpoch { epoch, ..Default::default() };
+        }
+        totals.maker_volume = totals.maker_volume.saturating_add(maker_volume);
+        totals.taker_volume = totals.taker_volume.saturating_add(taker_volume);
+        account.maker_volume = account.maker_volume.saturating_add(maker_volume);
+        account.taker_volume = account.taker_volume.saturating_add(taker_volume);
+        Ok(())
+    }
+}
+
+impl Bond {
+    /// Adds the fees `schedule` paid per bonded token since the last settle.
+    /// Call before the amount changes; shares round down.
+    pub fn settle_fees(&mut self, schedule: &EmissionsSchedule) -> Result<()> {
+        let amount = self.amount as u128;
+        for (index, checkpoint, owed) in [
+            (schedule.fee_index_a, &mut self.fee_index_a, &mut self.fees_owed_a),
+            (schedule.fee_index_b, &mut self.fee_index_b, &mut self.fees_owed_b),
+        ] {
+            let earned = amount
+                .checked_mul(index - *checkpoint)
+                .and_then(|earned| u64::try_from(earned / ACCRUAL_INDEX_SCALE).ok())
+                .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+            *owed = owed.checked_add(earned).ok_or(crate::errors::ErrorCode::MathOverflow)?;
+            *checkpoint = index;
+        }
+        Ok(())
+    }
+
+    /// Locks the bond until `unlock`, which must be after `now`, no earlier
+    /// than its current unlock and within the longest lock.
+    pub fn relock(&mut self, unlock: u64, now: u64, epoch_slots: u64) -> Result<()> {
+        if unlock <= now || unlock < self.unlock || unlock - now > EmissionsSchedule::max_lock(epoch_slots) {
+            return err!(crate::errors::ErrorCode::InvalidLockDuration);
+        }
+        self.unlock = unlock;
+        Ok(())
+    }
+}
+
+impl OrderParams {
+    pub fn validate(&self) -> Result<()> {
+        if self.trade_type > 1 {
+            return err!(crate::errors::ErrorCode::InvalidTradeType);
+        }
+        if self.flags & !TRADE_FLAGS_ALL != 0 {
+            return err!(crate::errors::ErrorCode::InvalidTradeFlags);
+        }
+        // Buying token B can only add to the agent's inventory
+        if self.flags & TRADE_FLAG_REDUCE_ONLY != 0 && self.trade_type == 0 {
+            return err!(crate::errors::ErrorCode::ReduceOnlyWouldIncrease);
+        }
+        Ok(())
+    }
+}
+
+impl Uncross {
+    /// Adds `size` token B at `price` on `trade_type`'s side, returning
+    /// false if that would take a level beyond MAX_UNCROSS_LEVELS.
+    pub fn tally(&mut self, trade_type: u8, price: u64, size: u64) -> Result<bool> {
+        let levels = &mut self.levels[..self.level_count as usize];
+        let level = match levels
+            .iter_mut()
+            .find(|level| level.trade_type == trade_type && level.price == price)
+        {
+            Some(level) => level,
+            None if (self.level_count as usize) < MAX_UNCROSS_LEVELS => {
+                self.level_count += 1;
+                let level = &mut self.levels[self.level_count as usize - 1];
+                *level = UncrossLevel {
+                    trade_type,
+                    price,
+                    size: 0,
+                };
+                level
+            }
+            None => return Ok(false),
+        };
+        level.size = level
+            .size
+            .checked_add(size)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        Ok(true)
+    }
+
+    /// Clearing price, volume and imbalance of the levels tallied: the price
+    /// trading the most token B, then leaving the smallest imbalance, then
+    /// the middle of any still tied.
+    pub fn clearing(&self) -> (u64, u64, i64) {
+        let levels = &self.levels[..self.level_count as usize];
+        let mut candidates: Vec<u64> = levels.iter().map(|level| level.price).collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        // Tied best prices, each with its imbalance
+        let mut best = Vec::new();
+        let (mut best_volume, mut best_gap) = (0u64, u64::MAX);
+        for price in candidates {
+            let (mut demand, mut supply) = (0u64, 0u64);
+            for level in levels {
+                if level.trade_type == 0 && level.price >= price {
+                    demand = demand.saturating_add(level.size);
+                } else if level.trade_type == 1 && level.price <= price {
+                    supply = supply.saturating_add(level.size);
+                }
+            }
+            let (volume, gap) = (demand.min(supply), demand.abs_diff(supply));
+            if volume == 0 || (volume, std::cmp::Reverse(gap)) < (best_volume, std::cmp::Reverse(best_gap)) {
+                continue;
+            }
+            if (volume, gap) != (best_volume, best_gap) {
+                best.clear();
+                (best_volume, best_gap) = (volume, gap);
+            }
+            let imbalance = (demand as i128 - supply as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+            best.push((price, imbalance));
+        }
+        match best.get(best.len() / 2) {
+            Some(&(price, imbalance)) => (price, best_volume, imbalance),
+            None => (0, 0, 0),
+        }
+    }
+
+    /// Ends the tally: fixes the clearing price and volume, and where each
+    /// side stops filling in full.
+    pub fn close_tally(&mut self) {
+        let (price, volume, _) = self.clearing();
+        (self.price, self.volume) = (price, volume);
+        self.bid_edge = self.edge(0);
+        self.ask_edge = self.edge(1);
+        self.stage = UNCROSS_FILL;
+        self.orders_left = self.orders;
+    }
+
+    fn edge(&self, trade_type: u8) -> UncrossEdge {
+        let mut levels: Vec<&UncrossLevel> = self.levels[..self.level_count as usize]
+            .iter()
+            .filter(|level| level.trade_type == trade_type)
+            .filter(|level| if trade_type == 0 { level.price >= self.price } else { level.price <= self.price })
+            .collect();
+        // Best price first
+        levels.sort_unstable_by_key(|level| if trade_type == 0 { u64::MAX - level.price } else { level.price });
+        let mut before = 0u64;
+        for level in levels {
+            if self.volume > 0 && before.saturating_add(level.size) >= self.volume {
+                return UncrossEdge {
+                    price: level.price,
+                    size: level.size,
+                    fill: self.volume - before,
+                    seen: 0,
+                };
+            }
+            before = before.saturating_add(level.size);
+        }
+        UncrossEdge::default()
+    }
+
+    /// Token B an order tallied with `size` at `price` on `trade_type`'s
+    /// side fills, once every order is tallied.
+    pub fn fill(&mut self, trade_type: u8, price: u64, size: u64) -> Result<u64> {
+        if self.volume == 0 || size == 0 {
+            return Ok(0);
+        }
+        let edge = if trade_type == 0 { &mut self.bid_edge } else { &mut self.ask_edge };
+        let better = if trade_type == 0 { price > edge.price } else { price < edge.price };
+        if better {
+            return Ok(size);
+        }
+        if price != edge.price {
+            return Ok(0);
+        }
+        let before = crate::math::mul_div_floor(edge.seen, edge.fill, edge.size)?;
+        edge.seen = edge
+            .seen
+            .checked_add(size)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        let after = crate::math::mul_div_floor(edge.seen, edge.fill, edge.size)?;
+        Ok(after - before)
+    }
+
+    /// Taker fee on `amount` at the rate the uncross began with.
+    pub fn fee(&self, amount: u64) -> Result<u64> {
+        mul_bps_ceil(amount, self.taker_fee_bps as u64)
+    }
+}
+
+impl Trade {
+    /// Fills in a freshly created trade from validated `params`.
+    pub fn open(
+        &mut self,
+        agent: Pubkey,
+        market: Pubkey,
+        order_id: u64,
+        sequence: u64,
+        params: &OrderParams,
+        bump: u8,
+    ) {
+        self.agent = agent;
+        self.market = market;
+        self.order_id = order_id;
+        self.trade_type = params.trade_type;
+        self.amount_in = params.amount_in;
+        self.amount_out = params.amount_out;
+        self.sequence = sequence;
+        self.display_size = params.display_size;
+        self.visible_size = params.display_size.min(self.size());
+        self.flags = params.flags;
+        self.tag = params.tag;
+        self.bump = bump;
+    }
+
+    /// Order size in units of token B, the quantity queue priority is tied to.
+    pub fn size(&self) -> u64 {
+        if self.trade_type == 0 {
+            self.amount_out
+        } else {
+            self.amount_in
+        }
+    }
+
+    /// Token A side of the order: paid by a bid, received by an ask.
+    pub fn quote(&self) -> u64 {
+        if self.trade_type == 0 {
+            self.amount_in
+        } else {
+            self.amount_out
+        }
+    }
+
+    /// Token A per token B, `PRICE_SCALE` fixed point, rounded down: the
+    /// price level the order rests at.
+    pub fn price(&self) -> u64 {
+        (self.quote() as u128 * PRICE_SCALE as u128 / self.size().max(1) as u128).min(u64::MAX as u128) as u64
+    }
+
+    pub fn is_post_only(&self) -> bool {
+        self.flags & TRADE_FLAG_POST_ONLY != 0
+    }
+
+    pub fn is_reduce_only(&self) -> bool {
+        self.flags & TRADE_FLAG_REDUCE_ONLY != 0
+    }
+
+    /// Quantity of token B currently shown on the book.
+    pub fn displayed(&self) -> u64 {
+        if self.display_size == 0 {
+            self.size()
+        } else {
+            self.visible_size.min(self.size())
+        }
+    }
+
+    /// Books `quantity` against the visible tranche of an iceberg order and
+    /// returns true when the tranche ran out and was refilled from the hidden
+    /// reserve, in which case the order must go to the back of the queue.
+    pub fn consume_display(&mut self, quantity: u64) -> bool {
+        if self.display_size == 0 {
+            return false;
+        }
+        self.visible_size = self.visible_size.saturating_sub(quantity);
+        if self.visible_size == 0 && self.size() > 0 {
+            self.visible_size = self.display_size.min(self.size());
+            return true;
+        }
+        false
+    }
+
+    /// Pulls the whole order off the book while leaving the account open.
+    pub fn cancel(&mut self) {
+        self.amount_in = 0;
+        self.amount_out = 0;
+    }
+
+    /// Takes `quantity` of token B off the order, scaling the token A leg so
+    /// the limit price is unchanged.
+    pub fn reduce(&mut self, quantity: u64) -> Result<()> {
+        let size = self.size();
+        let remaining = size
+            .checked_sub(quantity)
+            .ok_or(crate::errors::ErrorCode::MathOverflow)?;
+        let (base, quote) = if self.trade_type == 0 {
+            (&mut self.amount_out, &mut self.amount_in)
+        } else {
+            (&mut self.amount_in, &mut self.amount_out)
+        };
+        *quote = if size == 0 {
+            0
+        } else {
+            (*quote as u128 * remaining as u128 / size as u128) as u64
+        };
+        *base = remaining;
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { AgentMarketSim } from "../target/types/agent_market_sim";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import {
   getAssociatedTokenAddress,
   createMint,
@@ -25,6 +25,7 @@ describe("market", () => {
   
   let tokenAMint: PublicKey;
   let tokenBMint: PublicKey;
+  let configPda: PublicKey;
   let marketPda: PublicKey;
   let marketBump: number;
   let vaultAPda: PublicKey;
@@ -35,6 +36,7 @@ describe("market", () => {
   let agentBump: number;
   let tradePda: PublicKey;
   let tradeBump: number;
+  let balanceAPda: PublicKey;
 
   let userTokenAAccount: PublicKey;
   let userTokenBAccount: PublicKey;
@@ -55,6 +57,10 @@ describe("market", () => {
     tokenBMint = await createMint(connection, wallet.payer, wallet.publicKey, null, 9);
 
    
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
     [marketPda, marketBump] = PublicKey.findProgramAddressSync(
       [Buffer.from("market"), tokenAMint.toBuffer(), tokenBMint.toBuffer()],
       program.programId
@@ -72,6 +78,11 @@ describe("market", () => {
       program.programId
     );
 
+    [balanceAPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("balance"), vaultAPda.toBuffer(), agentPda.toBuffer()],
+      program.programId
+    );
+
     // Create user token accounts and mint tokens
     userTokenAAccount = await createAssociatedTokenAccount(connection, wallet.payer, tokenAMint, user.publicKey);
     userTokenBAccount = await createAssociatedTokenAccount(connection, wallet.payer, tokenBMint, user.publicKey);
@@ -83,11 +94,38 @@ describe("market", () => {
     vaultBTokenAccount = await getAssociatedTokenAddress(tokenBMint, vaultBPda, true);
   });
 
+  it("Initializes the config", async () => {
+    const [programDataPda] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    await program.methods
+      // Order book, margin and batching on; TWAP prices allowed
+      .initializeConfig(user.publicKey, 0, 0, new BN(0b1011))
+      .accounts({
+        config: configPda,
+        program: program.programId,
+        programData: programDataPda,
+        upgradeAuthority: user.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const configAccount = await program.account.config.fetch(configPda);
+    expect(configAccount.admin.toBase58()).to.equal(user.publicKey.toBase58());
+  });
+
   it("Initializes a market", async () => {
+    const [marketListingPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("market_listing"), new BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
     await program.methods
       .initializeMarket()
       .accounts({
+        config: configPda,
         market: marketPda,
+        marketListing: marketListingPda,
         vaultA: vaultAPda,
         vaultB: vaultBPda,
         vaultATokenAccount: vaultATokenAccount,
@@ -104,14 +142,109 @@ describe("market", () => {
     const marketAccount = await program.account.market.fetch(marketPda);
     expect(marketAccount.tokenA.toBase58()).to.equal(tokenAMint.toBase58());
     expect(marketAccount.tokenB.toBase58()).to.equal(tokenBMint.toBase58());
+    expect(marketAccount.authority.toBase58()).to.equal(user.publicKey.toBase58());
     expect(marketAccount.bump).to.equal(marketBump);
+
+    const listing = await program.account.marketListing.fetch(marketListingPda);
+    expect(listing.market.toBase58()).to.equal(marketPda.toBase58());
+    const configAccount = await program.account.config.fetch(configPda);
+    expect(configAccount.marketCount.toNumber()).to.equal(1);
+  });
+
+  it("Sets maker/taker fees with a maker rebate", async () => {
+    await program.methods
+      .setMarketFees(-2, 5)
+      .accounts({
+        market: marketPda,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    const marketAccount = await program.account.market.fetch(marketPda);
+    expect(marketAccount.makerFeeBps).to.equal(-2);
+    expect(marketAccount.takerFeeBps).to.equal(5);
+  });
+
+  it("Sets volume-based fee tiers", async () => {
+    await program.methods
+      .setFeeTiers([
+        { minVolume: new BN(1_000_000), makerFeeBps: -3, takerFeeBps: 4 },
+        { minVolume: new BN(10_000_000), makerFeeBps: -4, takerFeeBps: 4 },
+      ])
+      .accounts({
+        market: marketPda,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    const marketAccount = await program.account.market.fetch(marketPda);
+    expect(marketAccount.feeTierCount).to.equal(2);
+    expect(marketAccount.feeTiers[1].minVolume.toString()).to.equal("10000000");
+  });
+
+  it("Configures the market's oracle feeds", async () => {
+    const primary = Keypair.generate().publicKey;
+    await program.methods
+      .setOracleConfig(primary, PublicKey.default, new BN(25), 100)
+      .accounts({
+        market: marketPda,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    const marketAccount = await program.account.market.fetch(marketPda);
+    expect(marketAccount.primaryOracle.toBase58()).to.equal(primary.toBase58());
+    expect(marketAccount.oracleMaxStalenessSlots.toNumber()).to.equal(25);
+    expect(marketAccount.oracleMaxConfidenceBps).to.equal(100);
+  });
+
+  it("Switches the market's clock to unix time and back", async () => {
+    const setTimeBase = (timeBase: number) =>
+      program.methods
+        .setTimeBase(timeBase)
+        .accounts({ market: marketPda, authority: user.publicKey })
+        .rpc();
+
+    const before = await program.account.market.fetch(marketPda);
+    await setTimeBase(1);
+    let marketAccount = await program.account.market.fetch(marketPda);
+    expect(marketAccount.timeBase).to.equal(1);
+    // 400ms slots, so the epoch keeps its length in seconds
+    expect(marketAccount.epochSlots.toNumber()).to.equal(
+      (before.epochSlots.toNumber() * 2) / 5
+    );
+
+    await setTimeBase(0);
+    marketAccount = await program.account.market.fetch(marketPda);
+    expect(marketAccount.timeBase).to.equal(0);
+    expect(marketAccount.epochSlots.toString()).to.equal(
+      before.epochSlots.toString()
+    );
+  });
+
+  it("Sets the funding cap and borrow rate", async () => {
+    await program.methods
+      .setFundingParams(50, 1_000)
+      .accounts({ market: marketPda, authority: user.publicKey })
+      .rpc();
+
+    const marketAccount = await program.account.market.fetch(marketPda);
+    expect(marketAccount.maxFundingBps).to.equal(50);
+    expect(marketAccount.borrowRateBps).to.equal(1_000);
+    expect(marketAccount.accruedAt.toNumber()).to.be.greaterThan(0);
   });
 
   it("Registers an agent", async () => {
+    const [agentListingPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("agent_listing"), new BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
     await program.methods
       .registerAgent()
       .accounts({
+        config: configPda,
         agent: agentPda,
+        agentListing: agentListingPda,
         user: user.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -120,6 +253,42 @@ describe("market", () => {
     const agentAccount = await program.account.agent.fetch(agentPda);
     expect(agentAccount.owner.toBase58()).to.equal(user.publicKey.toBase58());
     expect(agentAccount.bump).to.equal(agentBump);
+
+    const listing = await program.account.agentListing.fetch(agentListingPda);
+    expect(listing.agent.toBase58()).to.equal(agentPda.toBase58());
+
+    const [globalStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_stats")],
+      program.programId
+    );
+    const stats = await program.account.globalStats.fetch(globalStatsPda);
+    expect(stats.markets.toNumber()).to.equal(1);
+    expect(stats.agents.toNumber()).to.equal(1);
+  });
+
+  it("Creates an isolated sub-account", async () => {
+    const index = 1;
+    const indexBytes = Buffer.alloc(2);
+    indexBytes.writeUInt16LE(index);
+    const [subaccountPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("subaccount"), agentPda.toBuffer(), indexBytes],
+      program.programId
+    );
+
+    await program.methods
+      .createSubaccount(index)
+      .accounts({
+        agent: agentPda,
+        subaccount: subaccountPda,
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const subaccount = await program.account.agent.fetch(subaccountPda);
+    expect(subaccount.owner.toBase58()).to.equal(user.publicKey.toBase58());
+    expect(subaccount.parent.toBase58()).to.equal(agentPda.toBase58());
+    expect(subaccount.subaccountIndex).to.equal(index);
   });
 
   it("Deposits tokens (Token A)", async () => {
@@ -127,7 +296,7 @@ describe("market", () => {
 
     
     await program.methods
-      .depositTokens(depositAmount)
+      .depositTokens(depositAmount, null)
       .accounts({
         agent: agentPda,
         market: marketPda,
@@ -136,6 +305,8 @@ describe("market", () => {
         userTokenAccount: userTokenAAccount,
         vault: vaultAPda,
         vaultTokenAccount: vaultATokenAccount,
+        balance: balanceAPda,
+        receipt: null,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -145,6 +316,9 @@ describe("market", () => {
     const vaultTokenAccountInfo = await getAccount(connection, vaultATokenAccount);
     expect(vaultTokenAccountInfo.amount.toString()).to.equal(depositAmount.toString());
 
+    const balanceAccount = await program.account.balance.fetch(balanceAPda);
+    expect(balanceAccount.amount.toString()).to.equal(depositAmount.toString());
+
     const finalUserBalance = (await getAccount(connection, userTokenAAccount)).amount;
     expect(
       new BN(finalUserBalance.toString()).eq(
@@ -168,6 +342,7 @@ describe("market", () => {
         userTokenAccount: userTokenAAccount,
         vault: vaultAPda,
         vaultTokenAccount: vaultATokenAccount,
+        balance: balanceAPda,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -187,16 +362,207 @@ describe("market", () => {
         new BN(initialUserBalance.toString()).add(withdrawAmount)
       )
     ).to.be.true;
+
+    const balanceAccount = await program.account.balance.fetch(balanceAPda);
+    expect(balanceAccount.amount.toString()).to.equal(depositAmount.sub(withdrawAmount).toString());
+  });
+
+  it("Rejects withdrawing more than the tracked balance", async () => {
+    try {
+      await program.methods
+        .withdrawTokens(depositAmount)
+        .accounts({
+          agent: agentPda,
+          market: marketPda,
+          user: user.publicKey,
+          tokenMint: tokenAMint,
+          userTokenAccount: userTokenAAccount,
+          vault: vaultAPda,
+          vaultTokenAccount: vaultATokenAccount,
+          balance: balanceAPda,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("withdrawal should have been rejected");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("InsufficientBalance");
+    }
+  });
+
+  it("Credits a deposit retried with the same nonce once", async () => {
+    const nonce = new BN(1);
+    const [receiptPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit_receipt"), agentPda.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const before = await program.account.balance.fetch(balanceAPda);
+
+    for (let attempt = 0; attempt < 2; attempt++) {
+      await program.methods
+        .depositTokens(tradeAmount, nonce)
+        .accounts({
+          agent: agentPda,
+          market: marketPda,
+          user: user.publicKey,
+          tokenMint: tokenAMint,
+          userTokenAccount: userTokenAAccount,
+          vault: vaultAPda,
+          vaultTokenAccount: vaultATokenAccount,
+          balance: balanceAPda,
+          receipt: receiptPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    const after = await program.account.balance.fetch(balanceAPda);
+    expect(after.amount.sub(before.amount).toString()).to.equal(tradeAmount.toString());
+    const receipt = await program.account.depositReceipt.fetch(receiptPda);
+    expect(receipt.amount.toString()).to.equal(tradeAmount.toString());
+
+    // The nonce is spent, so a retry to the other vault is refused outright
+    const [balanceBPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("balance"), vaultBPda.toBuffer(), agentPda.toBuffer()],
+      program.programId
+    );
+    try {
+      await program.methods
+        .depositTokens(tradeAmount, nonce)
+        .accounts({
+          agent: agentPda,
+          market: marketPda,
+          user: user.publicKey,
+          tokenMint: tokenBMint,
+          userTokenAccount: userTokenBAccount,
+          vault: vaultBPda,
+          vaultTokenAccount: vaultBTokenAccount,
+          balance: balanceBPda,
+          receipt: receiptPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("deposit to another vault should have been rejected");
+    } catch (e) {
+      expect(e.error.errorCode.code).to.equal("DepositNonceReused");
+    }
+  });
+
+  it("Creates and cancels an escrowed OTC offer", async () => {
+    const offerId = new BN(1);
+    const [offerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("otc"), marketPda.toBuffer(), agentPda.toBuffer(), offerId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const escrowTokenAccount = await getAssociatedTokenAddress(tokenAMint, offerPda, true);
+    const initialUserBalance = (await getAccount(connection, userTokenAAccount)).amount;
+
+    await program.methods
+      .createOtcOffer(offerId, new BN(300), new BN(30), PublicKey.default)
+      .accounts({
+        agent: agentPda,
+        market: marketPda,
+        offer: offerPda,
+        giveMint: tokenAMint,
+        wantMint: tokenBMint,
+        userGiveAccount: userTokenAAccount,
+        escrowTokenAccount: escrowTokenAccount,
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const escrowInfo = await getAccount(connection, escrowTokenAccount);
+    expect(escrowInfo.amount.toString()).to.equal("300");
+
+    await program.methods
+      .cancelOtcOffer()
+      .accounts({
+        agent: agentPda,
+        market: marketPda,
+        offer: offerPda,
+        giveMint: tokenAMint,
+        escrowTokenAccount: escrowTokenAccount,
+        userGiveAccount: userTokenAAccount,
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(offerPda)).to.be.null;
+    const finalUserBalance = (await getAccount(connection, userTokenAAccount)).amount;
+    expect(finalUserBalance.toString()).to.equal(initialUserBalance.toString());
+  });
+
+  it("Sets the self-trade prevention mode", async () => {
+    await program.methods
+      .setStpMode(2)
+      .accounts({
+        agent: agentPda,
+        user: user.publicKey,
+      })
+      .rpc();
+
+    const agentAccount = await program.account.agent.fetch(agentPda);
+    expect(agentAccount.stpMode).to.equal(2);
+  });
+
+  it("Starts a trading session for a hot key", async () => {
+    const sessionKey = Keypair.generate().publicKey;
+    const expirySlot = (await connection.getSlot()) + 1_000;
+    await program.methods
+      .startSession(sessionKey, new BN(expirySlot), 3)
+      .accounts({
+        agent: agentPda,
+        user: user.publicKey,
+      })
+      .rpc();
+
+    const agentAccount = await program.account.agent.fetch(agentPda);
+    const delegate = agentAccount.delegates[0];
+    expect(delegate.key.toBase58()).to.equal(sessionKey.toBase58());
+    expect(delegate.expirySlot.toNumber()).to.equal(expirySlot);
+    expect(delegate.scope).to.equal(3);
+  });
+
+  it("Offers and withdraws an agent ownership transfer", async () => {
+    const newOwner = Keypair.generate().publicKey;
+    await program.methods
+      .transferAgentOwnership(newOwner)
+      .accounts({ agent: agentPda, user: user.publicKey })
+      .rpc();
+    let agentAccount = await program.account.agent.fetch(agentPda);
+    expect(agentAccount.pendingOwner.toBase58()).to.equal(newOwner.toBase58());
+
+    await program.methods
+      .transferAgentOwnership(PublicKey.default)
+      .accounts({ agent: agentPda, user: user.publicKey })
+      .rpc();
+    agentAccount = await program.account.agent.fetch(agentPda);
+    expect(agentAccount.pendingOwner.toBase58()).to.equal(PublicKey.default.toBase58());
+    expect(agentAccount.owner.toBase58()).to.equal(user.publicKey.toBase58());
   });
 
   it("Places a trade (Buy Token B with Token A)", async () => {
     [tradePda, tradeBump] = PublicKey.findProgramAddressSync(
-      [Buffer.from("trade"), agentPda.toBuffer(), marketPda.toBuffer()],
+      [
+        Buffer.from("trade"),
+        agentPda.toBuffer(),
+        marketPda.toBuffer(),
+        new BN(0).toArrayLike(Buffer, "le", 8),
+      ],
       program.programId
     );
+    const tag = Array.from(Buffer.from("ppo-ckpt-42".padEnd(16, "\0")));
 
     await program.methods
-      .placeTrade(0, amountIn, amountOut)
+      .placeTrade(new BN(0), 0, amountIn, amountOut, new BN(0), 0, tag)
       .accounts({
         agent: agentPda,
         market: marketPda,
@@ -212,9 +578,32 @@ describe("market", () => {
     expect(tradeAccount.tradeType).to.equal(0);
     expect(tradeAccount.amountIn.toString()).to.equal(amountIn.toString());
     expect(tradeAccount.amountOut.toString()).to.equal(amountOut.toString());
+    expect(tradeAccount.displaySize.toNumber()).to.equal(0);
+    expect(tradeAccount.tag).to.deep.equal(tag);
     expect(tradeAccount.bump).to.equal(tradeBump);
   });
 
+  it("Modifies the trade price in place", async () => {
+    const before = await program.account.trade.fetch(tradePda);
+    const improvedAmountIn = amountIn.subn(100);
+
+    await program.methods
+      .modifyTrade(improvedAmountIn, amountOut)
+      .accounts({
+        agent: agentPda,
+        market: marketPda,
+        trade: tradePda,
+        user: user.publicKey,
+      })
+      .rpc();
+
+    const tradeAccount = await program.account.trade.fetch(tradePda);
+    expect(tradeAccount.amountIn.toString()).to.equal(improvedAmountIn.toString());
+    expect(tradeAccount.amountOut.toString()).to.equal(amountOut.toString());
+    // Same size, so the order keeps its queue position
+    expect(tradeAccount.sequence.toString()).to.equal(before.sequence.toString());
+  });
+
   it("Executes the trade", async () => {
     await mintTo(
       connection,
@@ -240,6 +629,8 @@ describe("market", () => {
         vaultTokenAccountIn: vaultATokenAccount,
         vaultOut: vaultBPda,
         vaultTokenAccountOut: vaultBTokenAccount,
+        settlement: null,
+        instructions: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -250,4 +641,81 @@ describe("market", () => {
     const tradeAccountInfo = await provider.connection.getAccountInfo(tradePda);
     expect(tradeAccountInfo).to.be.null;
   });
+
+  it("Mints, resolves and redeems a binary outcome market", async () => {
+    const binaryMarketId = new BN(1);
+    const [binaryMarketPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("binary_market"), tokenAMint.toBuffer(), binaryMarketId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [yesMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("yes"), binaryMarketPda.toBuffer()],
+      program.programId
+    );
+    const [noMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("no"), binaryMarketPda.toBuffer()],
+      program.programId
+    );
+    const collateralVault = await getAssociatedTokenAddress(tokenAMint, binaryMarketPda, true);
+    const userYesAccount = await getAssociatedTokenAddress(yesMint, user.publicKey);
+    const userNoAccount = await getAssociatedTokenAddress(noMint, user.publicKey);
+
+    await program.methods
+      .createBinaryMarket(binaryMarketId, user.publicKey)
+      .accounts({
+        binaryMarket: binaryMarketPda,
+        collateralMint: tokenAMint,
+        yesMint,
+        noMint,
+        collateralVault,
+        signer: user.publicKey,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .mintOutcomeTokens(new BN(100))
+      .accounts({
+        binaryMarket: binaryMarketPda,
+        collateralMint: tokenAMint,
+        yesMint,
+        noMint,
+        collateralVault,
+        userCollateralAccount: userTokenAAccount,
+        userYesAccount,
+        userNoAccount,
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    expect((await getAccount(connection, userYesAccount)).amount.toString()).to.equal("100");
+    expect((await getAccount(connection, userNoAccount)).amount.toString()).to.equal("100");
+
+    await program.methods
+      .resolveMarket(1)
+      .accounts({ binaryMarket: binaryMarketPda, oracle: user.publicKey })
+      .rpc();
+
+    await program.methods
+      .redeemOutcomeTokens(new BN(100))
+      .accounts({
+        binaryMarket: binaryMarketPda,
+        collateralMint: tokenAMint,
+        yesMint,
+        noMint,
+        collateralVault,
+        userCollateralAccount: userTokenAAccount,
+        userYesAccount,
+        userNoAccount,
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    expect((await getAccount(connection, userYesAccount)).amount.toString()).to.equal("0");
+    expect((await getAccount(connection, collateralVault)).amount.toString()).to.equal("0");
+  });
 });